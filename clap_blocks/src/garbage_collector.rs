@@ -82,4 +82,13 @@ pub struct GarbageCollectorConfig {
         env = "INFLUXDB_IOX_GC_RETENTION_SLEEP_INTERVAL_MINUTES"
     )]
     pub retention_sleep_interval_minutes: u64,
+
+    /// Number of minutes to sleep between refreshes of the storage usage metrics.
+    /// Defaults to 5 minutes.
+    #[clap(
+        long,
+        default_value_t = 5,
+        env = "INFLUXDB_IOX_GC_STORAGE_USAGE_SLEEP_INTERVAL_MINUTES"
+    )]
+    pub storage_usage_sleep_interval_minutes: u64,
 }
@@ -4,6 +4,36 @@ use std::path::PathBuf;
 
 use crate::gossip::GossipConfig;
 
+/// When the ingester fsyncs writes to its write-ahead log.
+#[derive(Debug, Default, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum WalFsyncPolicy {
+    /// fsync each batch of writes before acknowledging them.
+    #[default]
+    PerBatch,
+
+    /// fsync writes at most once per interval, acknowledging them before they
+    /// are fsync-ed.
+    Interval,
+
+    /// Never fsync writes.
+    Off,
+}
+
+/// The compression codec of new write-ahead log files.
+#[derive(Debug, Default, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum WalCompression {
+    /// Snappy compression.
+    #[default]
+    Snappy,
+
+    /// Zstandard compression.
+    Zstd,
+
+    /// No compression.
+    #[value(name = "none")]
+    Uncompressed,
+}
+
 /// CLI config for the ingester using the RPC write path
 #[derive(Debug, Clone, clap::Parser)]
 #[allow(missing_copy_implementations)]
@@ -35,6 +65,150 @@ pub struct IngesterConfig {
     )]
     pub wal_rotation_period_seconds: u64,
 
+    /// Fail WAL replay on startup if a WAL file is damaged.
+    ///
+    /// By default damaged entries are skipped during replay, reporting the
+    /// data lost, and the damaged files are moved to a "quarantine"
+    /// subdirectory of the WAL directory for inspection. The oldest files in
+    /// it are removed once it holds more than 1GiB.
+    ///
+    /// A partially written entry at the end of a file is never considered
+    /// damaged, as its write was never acknowledged.
+    #[clap(
+        long = "wal-replay-strict",
+        env = "INFLUXDB_IOX_WAL_REPLAY_STRICT",
+        action
+    )]
+    pub wal_replay_strict: bool,
+
+    /// When writes to the WAL are fsync-ed to disk.
+    ///
+    /// With "per-batch", each batch of writes is fsync-ed before the writes
+    /// are acknowledged. With "interval", writes are fsync-ed at most once
+    /// every `--wal-fsync-interval-millis` and acknowledged before they are,
+    /// so the writes of the last interval may be lost should the host crash
+    /// or lose power. "off" never fsyncs, and is intended for benchmarks only.
+    ///
+    /// Should an fsync fail, all further writes are rejected until the
+    /// ingester is restarted.
+    #[clap(
+        value_enum,
+        long = "wal-fsync-policy",
+        env = "INFLUXDB_IOX_WAL_FSYNC_POLICY",
+        default_value = "per-batch",
+        ignore_case = true,
+        action
+    )]
+    pub wal_fsync_policy: WalFsyncPolicy,
+
+    /// The number of milliseconds between fsyncs of the WAL with the
+    /// "interval" `--wal-fsync-policy`.
+    #[clap(
+        long = "wal-fsync-interval-millis",
+        env = "INFLUXDB_IOX_WAL_FSYNC_INTERVAL_MILLIS",
+        default_value = "100",
+        action
+    )]
+    pub wal_fsync_interval_millis: u64,
+
+    /// The compression codec of new WAL files.
+    ///
+    /// The codec is recorded in each file, so files written with any codec
+    /// can be replayed after it is changed.
+    #[clap(
+        value_enum,
+        long = "wal-compression",
+        env = "INFLUXDB_IOX_WAL_COMPRESSION",
+        default_value = "snappy",
+        ignore_case = true,
+        action
+    )]
+    pub wal_compression: WalCompression,
+
+    /// Upload WAL files to object storage under this path prefix before they
+    /// are deleted locally, allowing their writes to be restored with
+    /// `influxdb_iox debug wal restore` should the WAL directory be lost.
+    ///
+    /// Each ingester instance must use its own prefix. When unset, WAL files
+    /// are not archived.
+    #[clap(
+        long = "wal-archive-prefix",
+        env = "INFLUXDB_IOX_WAL_ARCHIVE_PREFIX",
+        action
+    )]
+    pub wal_archive_prefix: Option<String>,
+
+    /// The number of hours archived WAL files are retained in object storage
+    /// for. When unset, archived WAL files are retained indefinitely.
+    #[clap(
+        long = "wal-archive-retention-hours",
+        env = "INFLUXDB_IOX_WAL_ARCHIVE_RETENTION_HOURS",
+        action
+    )]
+    pub wal_archive_retention_hours: Option<u64>,
+
+    /// The gRPC address of a follower ingester to synchronously replicate WAL
+    /// entries to, e.g. "http://ingester-1:8083".
+    ///
+    /// Writes are acknowledged only once they are durable in the WAL of both
+    /// this ingester and the follower. The follower ingests the unpersisted
+    /// writes of this ingester only if it stops hearing from it for longer
+    /// than its `--wal-replica-takeover-timeout-seconds`.
+    ///
+    /// The follower must list the `--wal-replication-leader-id` of this
+    /// ingester in its `--wal-replica-leader-ids`.
+    ///
+    /// Requires `--wal-replication-leader-id`.
+    #[clap(
+        long = "wal-replication-follower",
+        env = "INFLUXDB_IOX_WAL_REPLICATION_FOLLOWER",
+        requires = "wal_replication_leader_id",
+        action
+    )]
+    pub wal_replication_follower: Option<String>,
+
+    /// The identifier this ingester replicates its WAL to the follower under.
+    ///
+    /// Must be unique among the ingesters replicating to the same follower,
+    /// and remain the same across restarts of this ingester. May contain only
+    /// ASCII letters, digits, "-" and "_".
+    #[clap(
+        long = "wal-replication-leader-id",
+        env = "INFLUXDB_IOX_WAL_REPLICATION_LEADER_ID",
+        requires = "wal_replication_follower",
+        action
+    )]
+    pub wal_replication_leader_id: Option<String>,
+
+    /// The identifiers of the leader ingesters this ingester accepts WAL
+    /// replication from, as a comma-separated list.
+    ///
+    /// Replication requests from any other leader are rejected, as are all
+    /// replication requests if this list is empty.
+    #[clap(
+        long = "wal-replica-leader-ids",
+        env = "INFLUXDB_IOX_WAL_REPLICA_LEADER_IDS",
+        required = false,
+        num_args=1..,
+        value_delimiter = ','
+    )]
+    pub wal_replica_leader_ids: Vec<String>,
+
+    /// The number of seconds without contact from a leader ingester
+    /// replicating its WAL to this ingester after which this ingester fences
+    /// the leader and ingests the unpersisted writes of the leader itself.
+    ///
+    /// This must be longer than a leader takes to restart, otherwise the
+    /// writes it replays from its own WAL are also ingested here, and
+    /// persisted twice.
+    #[clap(
+        long = "wal-replica-takeover-timeout-seconds",
+        env = "INFLUXDB_IOX_WAL_REPLICA_TAKEOVER_TIMEOUT_SECONDS",
+        default_value = "300",
+        action
+    )]
+    pub wal_replica_takeover_timeout_seconds: u64,
+
     /// Sets how many queries the ingester will handle simultaneously before
     /// rejecting further incoming requests.
     #[clap(
@@ -75,4 +249,57 @@ pub struct IngesterConfig {
         action
     )]
     pub persist_hot_partition_cost: usize,
+
+    /// When set, rows with a timestamp older than this many seconds before the
+    /// newest row of a partition being persisted are persisted to a separate
+    /// parquet file.
+    ///
+    /// This keeps late-arriving writes from widening the time range of the
+    /// files persisted for the live edge of a partition.
+    #[clap(
+        long = "persist-cold-split-threshold-seconds",
+        env = "INFLUXDB_IOX_PERSIST_COLD_SPLIT_THRESHOLD_SECONDS",
+        action
+    )]
+    pub persist_cold_split_threshold_seconds: Option<u64>,
+
+    /// When set, the partitions each table with buffered data will write to
+    /// this many seconds in the future are created in the catalog ahead of
+    /// the first write to them.
+    ///
+    /// This spreads the catalog load caused by many tables rolling over to a
+    /// new time-based partition at once. Only tables partitioned by time
+    /// alone are pre-created.
+    #[clap(
+        long = "partition-precreate-lookahead-seconds",
+        env = "INFLUXDB_IOX_PARTITION_PRECREATE_LOOKAHEAD_SECONDS",
+        action
+    )]
+    pub partition_precreate_lookahead_seconds: Option<u64>,
+
+    /// The limit, in bytes, on the memory used by data buffered across all
+    /// partitions, including data being persisted.
+    ///
+    /// Once reached, writes are rejected with a retryable error until the
+    /// memory used drops below the high watermark. When unset, the memory used
+    /// is unbounded.
+    #[clap(
+        long = "buffer-memory-limit-bytes",
+        env = "INFLUXDB_IOX_BUFFER_MEMORY_LIMIT_BYTES",
+        action
+    )]
+    pub buffer_memory_limit_bytes: Option<usize>,
+
+    /// The percentage of the buffer memory limit above which the partitions
+    /// with the most buffered data are persisted to release memory.
+    ///
+    /// Only used when a buffer memory limit is set.
+    #[clap(
+        long = "buffer-memory-high-watermark-percent",
+        env = "INFLUXDB_IOX_BUFFER_MEMORY_HIGH_WATERMARK_PERCENT",
+        default_value = "80",
+        value_parser = clap::value_parser!(u8).range(1..=100),
+        action
+    )]
+    pub buffer_memory_high_watermark_percent: u8,
 }
@@ -3,9 +3,12 @@
 use crate::{
     ingester_address::IngesterAddress,
     memory_size::MemorySize,
-    single_tenant::{CONFIG_AUTHZ_ENV_NAME, CONFIG_AUTHZ_FLAG},
+    single_tenant::{
+        CONFIG_AUTHZ_ENV_NAME, CONFIG_AUTHZ_FLAG, CONFIG_CST_ENV_NAME, CONFIG_CST_FLAG,
+    },
 };
-use std::{collections::HashMap, num::NonZeroUsize};
+use humantime::parse_duration;
+use std::{collections::HashMap, num::NonZeroUsize, time::Duration};
 
 /// CLI config for querier configuration
 #[derive(Debug, Clone, PartialEq, Eq, clap::Parser)]
@@ -14,6 +17,17 @@ pub struct QuerierConfig {
     #[clap(long = CONFIG_AUTHZ_FLAG, env = CONFIG_AUTHZ_ENV_NAME)]
     pub authz_address: Option<String>,
 
+    /// Differential handling based upon deployment to CST vs MT.
+    ///
+    /// Must match the setting of the routers, so that V2 query requests
+    /// resolve the same namespace as the V2 writes to the same org and bucket.
+    #[clap(
+        long = CONFIG_CST_FLAG,
+        env = CONFIG_CST_ENV_NAME,
+        default_value = "false",
+    )]
+    pub single_tenant_deployment: bool,
+
     /// The number of threads to use for queries.
     ///
     /// If not specified, defaults to the number of cores on the system
@@ -120,6 +134,55 @@ pub struct QuerierConfig {
         action
     )]
     pub datafusion_config: HashMap<String, String>,
+
+    /// HTTP address of the router rolled up data is written to, for example:
+    ///
+    /// "http://127.0.0.1:8080"
+    ///
+    /// If specified, this querier periodically evaluates the rollup rules of all namespaces over
+    /// newly persisted data. Only one querier of a cluster should be configured to do so.
+    #[clap(
+        long = "rollup-router-address",
+        env = "INFLUXDB_IOX_ROLLUP_ROUTER_ADDRESS",
+        action
+    )]
+    pub rollup_router_address: Option<String>,
+
+    /// HTTP address of the router the output of InfluxQL `SELECT ... INTO` statements is
+    /// written to, for example:
+    ///
+    /// "http://127.0.0.1:8080"
+    ///
+    /// If not specified, `SELECT ... INTO` statements are rejected.
+    #[clap(
+        long = "select-into-router-address",
+        env = "INFLUXDB_IOX_SELECT_INTO_ROUTER_ADDRESS",
+        action
+    )]
+    pub select_into_router_address: Option<String>,
+
+    /// gRPC address of the router the namespaces of InfluxQL `CREATE DATABASE` statements are
+    /// created through, for example:
+    ///
+    /// "http://127.0.0.1:8081"
+    ///
+    /// If not specified, `CREATE DATABASE` statements are rejected.
+    #[clap(
+        long = "create-database-router-address",
+        env = "INFLUXDB_IOX_CREATE_DATABASE_ROUTER_ADDRESS",
+        action
+    )]
+    pub create_database_router_address: Option<String>,
+
+    /// How often rollup rules are checked for newly persisted data.
+    /// Parsed with <https://docs.rs/humantime/latest/humantime/fn.parse_duration.html>
+    #[clap(
+        long = "rollup-interval",
+        env = "INFLUXDB_IOX_ROLLUP_INTERVAL",
+        default_value = "1m",
+        value_parser = parse_duration
+    )]
+    pub rollup_interval: Duration,
 }
 
 fn parse_datafusion_config(
@@ -166,6 +229,27 @@ mod tests {
         assert_eq!(actual.num_query_threads, None);
         assert!(actual.ingester_addresses.is_empty());
         assert!(actual.datafusion_config.is_empty());
+        assert_eq!(actual.rollup_router_address, None);
+        assert_eq!(actual.create_database_router_address, None);
+        assert_eq!(actual.rollup_interval, Duration::from_secs(60));
+    }
+
+    #[test]
+    fn test_rollup() {
+        let actual = QuerierConfig::try_parse_from([
+            "my_binary",
+            "--rollup-router-address",
+            "http://127.0.0.1:8080",
+            "--rollup-interval",
+            "30s",
+        ])
+        .unwrap();
+
+        assert_eq!(
+            actual.rollup_router_address.as_deref(),
+            Some("http://127.0.0.1:8080")
+        );
+        assert_eq!(actual.rollup_interval, Duration::from_secs(30));
     }
 
     #[test]
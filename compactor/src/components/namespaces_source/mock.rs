@@ -130,6 +130,7 @@ mod tests {
                     "table1".to_string(),
                     TableSchema {
                         id: TableId::new(1),
+                        retention_period_ns: None,
                         partition_template: Default::default(),
                         columns: ColumnsByName::new([
                             Column {
@@ -151,6 +152,7 @@ mod tests {
                     "table2".to_string(),
                     TableSchema {
                         id: TableId::new(2),
+                        retention_period_ns: None,
                         partition_template: Default::default(),
                         columns: ColumnsByName::new([
                             Column {
@@ -184,6 +186,9 @@ mod tests {
                         name: "ns".to_string(),
                         max_tables: 10,
                         max_columns_per_table: 10,
+                        max_write_bytes_per_second: None,
+                        max_write_lines_per_second: None,
+                        max_concurrent_queries: None,
                         retention_period_ns: None,
                         deleted_at: None,
                         partition_template: Default::default(),
@@ -193,6 +198,9 @@ mod tests {
                         tables,
                         max_columns_per_table: 10,
                         max_tables: 42,
+                        max_write_bytes_per_second: None,
+                        max_write_lines_per_second: None,
+                        max_concurrent_queries: None,
                         retention_period_ns: None,
                         partition_template: Default::default(),
                     },
@@ -23,6 +23,7 @@ impl PartitionInfoBuilder {
             namespace_id,
             name: String::from("table"),
             partition_template: Default::default(),
+            retention_period_ns: None,
         });
         let table_schema = Arc::new(TableSchema::new_empty_from(&table));
 
@@ -57,6 +58,7 @@ impl PartitionInfoBuilder {
 
         let table_schema = Arc::new(TableSchema {
             id: self.inner.table.id,
+            retention_period_ns: None,
             partition_template: Default::default(),
             columns: ColumnsByName::new(columns),
         });
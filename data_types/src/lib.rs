@@ -28,6 +28,8 @@ pub mod partition;
 pub use partition::*;
 pub mod partition_template;
 use partition_template::*;
+pub mod rollup;
+pub use rollup::*;
 
 use observability_deps::tracing::warn;
 use schema::TIME_COLUMN_NAME;
@@ -285,6 +287,15 @@ pub struct Namespace {
     pub max_tables: i32,
     /// The maximum number of columns per table in this namespace
     pub max_columns_per_table: i32,
+    /// The maximum number of line protocol bytes per second that may be written to this
+    /// namespace. None represents no limit.
+    pub max_write_bytes_per_second: Option<i64>,
+    /// The maximum number of lines per second that may be written to this namespace. None
+    /// represents no limit.
+    pub max_write_lines_per_second: Option<i64>,
+    /// The maximum number of queries that may run concurrently against this namespace on each
+    /// querier. None represents no limit.
+    pub max_concurrent_queries: Option<i32>,
     /// When this file was marked for deletion.
     pub deleted_at: Option<Timestamp>,
     /// The partition template to use for new tables in this namespace either created implicitly or
@@ -301,6 +312,13 @@ pub struct NamespaceServiceProtectionLimitsOverride {
     pub max_tables: Option<i32>,
     /// The maximum number of columns per table in this namespace
     pub max_columns_per_table: Option<i32>,
+    /// The maximum number of line protocol bytes per second that may be written to this
+    /// namespace
+    pub max_write_bytes_per_second: Option<i64>,
+    /// The maximum number of lines per second that may be written to this namespace
+    pub max_write_lines_per_second: Option<i64>,
+    /// The maximum number of queries that may run concurrently against this namespace
+    pub max_concurrent_queries: Option<i32>,
 }
 
 impl From<namespace_proto::ServiceProtectionLimits> for NamespaceServiceProtectionLimitsOverride {
@@ -308,10 +326,17 @@ impl From<namespace_proto::ServiceProtectionLimits> for NamespaceServiceProtecti
         let namespace_proto::ServiceProtectionLimits {
             max_tables,
             max_columns_per_table,
+            max_write_bytes_per_second,
+            max_write_lines_per_second,
+            max_concurrent_queries,
         } = value;
         Self {
             max_tables,
             max_columns_per_table,
+            // 0 means "unlimited", like the absence of a value
+            max_write_bytes_per_second: max_write_bytes_per_second.filter(|&v| v != 0),
+            max_write_lines_per_second: max_write_lines_per_second.filter(|&v| v != 0),
+            max_concurrent_queries: max_concurrent_queries.filter(|&v| v != 0),
         }
     }
 }
@@ -328,6 +353,14 @@ pub struct NamespaceSchema {
     pub max_columns_per_table: usize,
     /// The maximum number of tables permitted in this namespace.
     pub max_tables: usize,
+    /// The maximum number of line protocol bytes per second that may be written to this
+    /// namespace, if limited.
+    pub max_write_bytes_per_second: Option<u64>,
+    /// The maximum number of lines per second that may be written to this namespace, if limited.
+    pub max_write_lines_per_second: Option<u64>,
+    /// The maximum number of queries that may run concurrently against this namespace, if
+    /// limited.
+    pub max_concurrent_queries: Option<usize>,
     /// The retention period in ns.
     /// None represents infinite duration (i.e. never drop data).
     pub retention_period_ns: Option<i64>,
@@ -345,6 +378,9 @@ impl NamespaceSchema {
             retention_period_ns,
             max_tables,
             max_columns_per_table,
+            max_write_bytes_per_second,
+            max_write_lines_per_second,
+            max_concurrent_queries,
             ref partition_template,
             ..
         } = namespace;
@@ -354,6 +390,9 @@ impl NamespaceSchema {
             tables: BTreeMap::new(),
             max_columns_per_table: max_columns_per_table as usize,
             max_tables: max_tables as usize,
+            max_write_bytes_per_second: max_write_bytes_per_second.map(|v| v as u64),
+            max_write_lines_per_second: max_write_lines_per_second.map(|v| v as u64),
+            max_concurrent_queries: max_concurrent_queries.map(|v| v as usize),
             retention_period_ns,
             partition_template: partition_template.clone(),
         }
@@ -361,6 +400,17 @@ impl NamespaceSchema {
 }
 
 impl NamespaceSchema {
+    /// Return the retention period in ns that applies to the table named `table_name`.
+    ///
+    /// A table-level retention period overrides the namespace retention period; tables that do
+    /// not exist in this schema or have no override use the namespace retention period.
+    pub fn table_retention_period_ns(&self, table_name: &str) -> Option<i64> {
+        self.tables
+            .get(table_name)
+            .and_then(|t| t.retention_period_ns)
+            .or(self.retention_period_ns)
+    }
+
     /// Estimated Size in bytes including `self`.
     pub fn size(&self) -> usize {
         std::mem::size_of_val(self)
@@ -383,6 +433,9 @@ pub struct Table {
     pub name: String,
     /// The partition template to use for writes in this table.
     pub partition_template: TablePartitionTemplateOverride,
+    /// The retention period in ns, overriding the retention period of the namespace. None means
+    /// the namespace's retention period applies.
+    pub retention_period_ns: Option<i64>,
 }
 
 /// Column definitions for a table
@@ -394,6 +447,9 @@ pub struct TableSchema {
     /// The partition template to use for writes in this table.
     pub partition_template: TablePartitionTemplateOverride,
 
+    /// The retention period in ns that overrides the namespace retention period, if any.
+    pub retention_period_ns: Option<i64>,
+
     /// the table's columns by their name
     pub columns: ColumnsByName,
 }
@@ -404,6 +460,7 @@ impl TableSchema {
         Self {
             id: table.id,
             partition_template: table.partition_template.clone(),
+            retention_period_ns: table.retention_period_ns,
             columns: ColumnsByName::new([]),
         }
     }
@@ -677,6 +734,65 @@ impl From<ParquetFile> for ParquetFileParams {
     }
 }
 
+use generated_types::influxdata::iox::catalog::v1 as catalog_proto;
+
+/// The object storage used by the parquet files of a table at a compaction level, not counting the
+/// files marked for deletion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::FromRow)]
+pub struct StorageUsage {
+    /// the namespace
+    pub namespace_id: NamespaceId,
+    /// the table
+    pub table_id: TableId,
+    /// the compaction level of the files
+    pub compaction_level: CompactionLevel,
+    /// the number of files
+    pub file_count: i64,
+    /// the total size of the files in bytes
+    pub file_size_bytes: i64,
+    /// the total number of rows in the files
+    pub row_count: i64,
+}
+
+impl StorageUsage {
+    /// Sum the usage of `parquet_files`, skipping those marked for deletion.
+    ///
+    /// The result is sorted by namespace, table and compaction level.
+    pub fn from_parquet_files<'a>(
+        parquet_files: impl IntoIterator<Item = &'a ParquetFile>,
+    ) -> Vec<Self> {
+        let mut usage = BTreeMap::new();
+        for f in parquet_files.into_iter().filter(|f| f.to_delete.is_none()) {
+            let u = usage
+                .entry((f.namespace_id, f.table_id, f.compaction_level))
+                .or_insert(Self {
+                    namespace_id: f.namespace_id,
+                    table_id: f.table_id,
+                    compaction_level: f.compaction_level,
+                    file_count: 0,
+                    file_size_bytes: 0,
+                    row_count: 0,
+                });
+            u.file_count += 1;
+            u.file_size_bytes += f.file_size_bytes;
+            u.row_count += f.row_count;
+        }
+        usage.into_values().collect()
+    }
+
+    /// Convert to the protobuf representation, which also carries the name of the table.
+    pub fn to_proto(&self, table_name: impl Into<String>) -> catalog_proto::StorageUsage {
+        catalog_proto::StorageUsage {
+            table_id: self.table_id.get(),
+            table_name: table_name.into(),
+            compaction_level: self.compaction_level as i32,
+            file_count: self.file_count,
+            file_size_bytes: self.file_size_bytes,
+            row_count: self.row_count,
+        }
+    }
+}
+
 /// ID of a chunk.
 ///
 /// This ID is unique within a single partition.
@@ -2555,11 +2671,13 @@ mod tests {
     fn test_table_schema_size() {
         let schema1 = TableSchema {
             id: TableId::new(1),
+            retention_period_ns: None,
             partition_template: Default::default(),
             columns: ColumnsByName::new([]),
         };
         let schema2 = TableSchema {
             id: TableId::new(2),
+            retention_period_ns: None,
             partition_template: Default::default(),
             columns: ColumnsByName::new(
                 [Column {
@@ -2581,6 +2699,9 @@ mod tests {
             tables: BTreeMap::from([]),
             max_columns_per_table: 4,
             max_tables: 42,
+            max_write_bytes_per_second: None,
+            max_write_lines_per_second: None,
+            max_concurrent_queries: None,
             retention_period_ns: None,
             partition_template: Default::default(),
         };
@@ -2590,12 +2711,16 @@ mod tests {
                 String::from("foo"),
                 TableSchema {
                     id: TableId::new(1),
+                    retention_period_ns: None,
                     columns: ColumnsByName::new([]),
                     partition_template: Default::default(),
                 },
             )]),
             max_columns_per_table: 4,
             max_tables: 42,
+            max_write_bytes_per_second: None,
+            max_write_lines_per_second: None,
+            max_concurrent_queries: None,
             retention_period_ns: None,
             partition_template: Default::default(),
         };
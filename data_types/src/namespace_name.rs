@@ -131,6 +131,45 @@ impl<'a> NamespaceName<'a> {
 
         Ok(Self::new(format!("{}_{}", org, bucket))?)
     }
+
+    /// Map an InfluxDB 2.X org & bucket into an IOx NamespaceName the way the
+    /// router maps V2 write requests in the given deployment mode.
+    ///
+    /// In a single tenant deployment the org is discarded and the bucket is
+    /// used unchanged, otherwise the mapping of
+    /// [`NamespaceName::from_org_and_bucket()`] applies.
+    pub fn from_v2_org_and_bucket<O: AsRef<str>, B: AsRef<str>>(
+        org: O,
+        bucket: B,
+        single_tenant: bool,
+    ) -> Result<Self, OrgBucketMappingError> {
+        if !single_tenant {
+            return Self::from_org_and_bucket(org, bucket);
+        }
+
+        let bucket = bucket.as_ref();
+        if bucket.is_empty() {
+            return Err(OrgBucketMappingError::NoOrgBucketSpecified);
+        }
+
+        Ok(Self::new(bucket.to_string())?)
+    }
+
+    /// The InfluxDB 2.X org & bucket a V2 write request must name to be
+    /// mapped back to this namespace by a router in the given deployment mode,
+    /// the inverse of [`NamespaceName::from_v2_org_and_bucket()`].
+    ///
+    /// Returns [`None`] if no org & bucket map to this namespace, which is the
+    /// case for names without an `_` in a multi-tenant deployment.
+    pub fn to_v2_org_and_bucket(&self, single_tenant: bool) -> Option<(&str, &str)> {
+        if single_tenant {
+            return Some(("", self.as_str()));
+        }
+
+        self.as_str()
+            .split_once('_')
+            .filter(|(org, bucket)| !org.is_empty() && !bucket.is_empty())
+    }
 }
 
 impl<'a> std::convert::From<NamespaceName<'a>> for String {
@@ -239,6 +278,41 @@ mod tests {
         assert!(matches!(err, OrgBucketMappingError::NoOrgBucketSpecified));
     }
 
+    #[test]
+    fn test_v2_org_and_bucket() {
+        let got = NamespaceName::from_v2_org_and_bucket("org", "bucket", false).unwrap();
+        assert_eq!(got.as_str(), "org_bucket");
+
+        let got = NamespaceName::from_v2_org_and_bucket("org", "bucket", true).unwrap();
+        assert_eq!(got.as_str(), "bucket");
+        let got = NamespaceName::from_v2_org_and_bucket("", "bucket", true).unwrap();
+        assert_eq!(got.as_str(), "bucket");
+
+        let err = NamespaceName::from_v2_org_and_bucket("org", "", true)
+            .expect_err("should fail with empty bucket");
+        assert!(matches!(err, OrgBucketMappingError::NoOrgBucketSpecified));
+    }
+
+    #[test]
+    fn test_to_v2_org_and_bucket() {
+        for (name, single_tenant) in [
+            ("org_bucket", false),
+            ("my_org_bucket", false),
+            ("bucket", true),
+            ("my_bucket", true),
+        ] {
+            let ns = NamespaceName::new(name).unwrap();
+            let (org, bucket) = ns.to_v2_org_and_bucket(single_tenant).unwrap();
+            let got = NamespaceName::from_v2_org_and_bucket(org, bucket, single_tenant).unwrap();
+            assert_eq!(got, ns);
+        }
+
+        for name in ["bucket", "_bucket", "org_"] {
+            let ns = NamespaceName::new(name).unwrap();
+            assert_eq!(ns.to_v2_org_and_bucket(false), None);
+        }
+    }
+
     #[test]
     fn test_deref() {
         let db = NamespaceName::new("my_example_name").unwrap();
@@ -0,0 +1,245 @@
+//! Rollup rules, declaring how raw data in a table is continuously downsampled
+//! into another table of the same namespace.
+//!
+//! A [`RollupRule`] is the IOx equivalent of an InfluxDB 1.x continuous query:
+//! the rows of `source_table` are aggregated by the rule's aggregate
+//! expressions, grouped by every tag of the source table and by `time` in
+//! windows of `interval_ns`, and the result is written to `target_table`.
+//!
+//! Rules are evaluated incrementally. The [`RollupRule::covered_until`]
+//! watermark records the (exclusive) end of the time range that has already
+//! been rolled up, and only ever moves forward in multiples of the rule's
+//! interval.
+//!
+//! Data persisted late, into windows the watermark already covers, is rolled
+//! up again while it is readable. The [`RollupRule::files_checked_until`]
+//! watermark records the creation time up to which the persisted files of the
+//! source table have been checked for new data.
+//!
+//! A rule may also set a [`RollupRule::raw_retention_period_ns`], tiering the
+//! retention of its source table: raw data older than that period is deleted,
+//! but only once it is covered by the watermark.
+
+use generated_types::influxdata::iox::rollup::v1 as proto;
+use thiserror::Error;
+
+use crate::{NamespaceId, Timestamp};
+
+/// Unique ID for a [`RollupRule`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, sqlx::Type)]
+#[sqlx(transparent)]
+pub struct RollupRuleId(i64);
+
+#[allow(missing_docs)]
+impl RollupRuleId {
+    pub const fn new(v: i64) -> Self {
+        Self(v)
+    }
+    pub fn get(&self) -> i64 {
+        self.0
+    }
+}
+
+impl std::fmt::Display for RollupRuleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// The query language the aggregate expressions of a [`RollupRule`] are
+/// written in.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, sqlx::Type)]
+#[repr(i16)]
+pub enum RollupQueryLanguage {
+    /// DataFusion SQL aggregate expressions, such as `avg(usage) AS usage`.
+    Sql = 1,
+    /// InfluxQL aggregate expressions, such as `mean(usage) AS usage`.
+    InfluxQl = 2,
+}
+
+impl std::fmt::Display for RollupQueryLanguage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Sql => write!(f, "sql"),
+            Self::InfluxQl => write!(f, "influxql"),
+        }
+    }
+}
+
+/// Errors converting a protobuf rollup query language.
+#[derive(Debug, Error)]
+#[allow(missing_copy_implementations)]
+pub enum RollupQueryLanguageError {
+    /// The language was not specified.
+    #[error("rollup query language must be specified")]
+    Unspecified,
+
+    /// The language is not known.
+    #[error("unknown rollup query language: {0}")]
+    Unknown(i32),
+}
+
+impl TryFrom<i32> for RollupQueryLanguage {
+    type Error = RollupQueryLanguageError;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match proto::QueryLanguage::from_i32(value) {
+            Some(proto::QueryLanguage::Sql) => Ok(Self::Sql),
+            Some(proto::QueryLanguage::Influxql) => Ok(Self::InfluxQl),
+            Some(proto::QueryLanguage::Unspecified) => Err(RollupQueryLanguageError::Unspecified),
+            None => Err(RollupQueryLanguageError::Unknown(value)),
+        }
+    }
+}
+
+impl From<RollupQueryLanguage> for proto::QueryLanguage {
+    fn from(value: RollupQueryLanguage) -> Self {
+        match value {
+            RollupQueryLanguage::Sql => Self::Sql,
+            RollupQueryLanguage::InfluxQl => Self::Influxql,
+        }
+    }
+}
+
+/// Data object for a rollup rule
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct RollupRule {
+    /// The id of the rule
+    pub id: RollupRuleId,
+    /// The namespace the rule, its source and its target table are in
+    pub namespace_id: NamespaceId,
+    /// The name of the rule, which is unique within the associated namespace
+    pub name: String,
+    /// The name of the table raw data is read from
+    pub source_table: String,
+    /// The name of the table the rolled up data is written to
+    pub target_table: String,
+    /// The language `aggregates` is written in
+    pub language: RollupQueryLanguage,
+    /// Comma separated list of aggregate expressions
+    pub aggregates: String,
+    /// The `GROUP BY time` interval in nanoseconds
+    pub interval_ns: i64,
+    /// The (exclusive) end of the time range already rolled up into the target table. None
+    /// means no data has been rolled up yet.
+    pub covered_until: Option<Timestamp>,
+    /// How long raw data of the source table is kept once it has been rolled up. None means
+    /// the retention period of the source table applies as usual.
+    pub raw_retention_period_ns: Option<i64>,
+    /// The error of the last failed run of the rule. None means the last run succeeded, or the
+    /// rule has not run yet.
+    pub last_error: Option<String>,
+    /// The creation time up to which the persisted files of the source table have been checked
+    /// for data to roll up. None means the files have not been checked since data was first
+    /// rolled up.
+    pub files_checked_until: Option<Timestamp>,
+}
+
+/// Data for a rollup rule to be created in the catalog.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RollupRuleParams {
+    /// The namespace the rule is created in
+    pub namespace_id: NamespaceId,
+    /// The name of the rule, which must be unique within the namespace
+    pub name: String,
+    /// The name of the table raw data is read from
+    pub source_table: String,
+    /// The name of the table the rolled up data is written to
+    pub target_table: String,
+    /// The language `aggregates` is written in
+    pub language: RollupQueryLanguage,
+    /// Comma separated list of aggregate expressions
+    pub aggregates: String,
+    /// The `GROUP BY time` interval in nanoseconds
+    pub interval_ns: i64,
+    /// How long raw data of the source table is kept once it has been rolled up
+    pub raw_retention_period_ns: Option<i64>,
+}
+
+impl RollupRule {
+    /// Create a new, not yet rolled up [`RollupRule`] with the given `id` from `params`.
+    pub fn from_params(params: RollupRuleParams, id: RollupRuleId) -> Self {
+        let RollupRuleParams {
+            namespace_id,
+            name,
+            source_table,
+            target_table,
+            language,
+            aggregates,
+            interval_ns,
+            raw_retention_period_ns,
+        } = params;
+
+        Self {
+            id,
+            namespace_id,
+            name,
+            source_table,
+            target_table,
+            language,
+            aggregates,
+            interval_ns,
+            covered_until: None,
+            raw_retention_period_ns,
+            last_error: None,
+            files_checked_until: None,
+        }
+    }
+}
+
+impl From<RollupRule> for proto::RollupRule {
+    fn from(rule: RollupRule) -> Self {
+        let RollupRule {
+            id,
+            namespace_id,
+            name,
+            source_table,
+            target_table,
+            language,
+            aggregates,
+            interval_ns,
+            covered_until,
+            raw_retention_period_ns,
+            last_error,
+            files_checked_until: _,
+        } = rule;
+
+        Self {
+            id: id.get(),
+            namespace_id: namespace_id.get(),
+            name,
+            source_table,
+            target_table,
+            language: proto::QueryLanguage::from(language).into(),
+            aggregates,
+            interval_ns,
+            covered_until_ns: covered_until.map(|t| t.get()),
+            raw_retention_period_ns,
+            last_error,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use super::*;
+
+    #[test]
+    fn test_query_language_proto_round_trip() {
+        for language in [RollupQueryLanguage::Sql, RollupQueryLanguage::InfluxQl] {
+            let proto = proto::QueryLanguage::from(language) as i32;
+            assert_eq!(RollupQueryLanguage::try_from(proto).unwrap(), language);
+        }
+
+        assert_matches!(
+            RollupQueryLanguage::try_from(proto::QueryLanguage::Unspecified as i32),
+            Err(RollupQueryLanguageError::Unspecified)
+        );
+        assert_matches!(
+            RollupQueryLanguage::try_from(42),
+            Err(RollupQueryLanguageError::Unknown(42))
+        );
+    }
+}
@@ -2,6 +2,10 @@
 
 use std::fmt::Display;
 
+use arrow::{
+    ipc::{reader::StreamReader, writer::StreamWriter},
+    record_batch::RecordBatch,
+};
 use arrow_flight::sql::{
     ActionClosePreparedStatementRequest, ActionCreatePreparedStatementRequest, Any,
     CommandGetCatalogs, CommandGetCrossReference, CommandGetDbSchemas, CommandGetExportedKeys,
@@ -9,8 +13,10 @@ use arrow_flight::sql::{
     CommandGetTables, CommandGetXdbcTypeInfo, CommandPreparedStatementQuery, CommandStatementQuery,
 };
 use bytes::Bytes;
+use generated_types::influxdata::iox::querier::v1 as proto;
 use prost::Message;
 use snafu::ResultExt;
+use uuid::Uuid;
 
 use crate::error::*;
 
@@ -21,11 +27,20 @@ use crate::error::*;
 pub struct PreparedStatementHandle {
     /// The raw SQL query text
     query: String,
+    /// Unique identifier of the prepared statement
+    id: Bytes,
+    /// The values bound to the parameters of the query, if any
+    params: Option<RecordBatch>,
 }
 
 impl PreparedStatementHandle {
+    /// Create a handle for a new prepared statement running `query`
     pub fn new(query: String) -> Self {
-        Self { query }
+        Self {
+            query,
+            id: Bytes::copy_from_slice(Uuid::new_v4().as_bytes()),
+            params: None,
+        }
     }
 
     /// return the query
@@ -33,16 +48,64 @@ impl PreparedStatementHandle {
         self.query.as_ref()
     }
 
+    /// return the unique identifier of the prepared statement
+    pub fn id(&self) -> &Bytes {
+        &self.id
+    }
+
+    /// return the values bound to the parameters of the query, if any
+    pub fn params(&self) -> Option<&RecordBatch> {
+        self.params.as_ref()
+    }
+
+    /// Bind `params`, a single row with a column per parameter, to the
+    /// parameters of the query
+    pub fn with_params(self, params: RecordBatch) -> Self {
+        Self {
+            params: Some(params),
+            ..self
+        }
+    }
+
     fn try_decode(handle: Bytes) -> Result<Self> {
-        // Note: in IOx  handles are the entire decoded query
-        // It will likely need to get more sophisticated as part of
-        // https://github.com/influxdata/influxdb_iox/issues/6699
-        let query = String::from_utf8(handle.to_vec()).context(InvalidHandleSnafu)?;
-        Ok(Self { query })
+        let proto::PreparedStatementHandle {
+            query,
+            id,
+            parameters,
+        } = Message::decode(handle).context(InvalidHandleSnafu)?;
+
+        let params = if parameters.is_empty() {
+            None
+        } else {
+            let mut reader = StreamReader::try_new(parameters.as_slice(), None)?;
+            reader.next().transpose()?
+        };
+
+        Ok(Self {
+            query,
+            id: id.into(),
+            params,
+        })
     }
 
-    fn encode(self) -> Bytes {
-        Bytes::from(self.query.into_bytes())
+    /// Encode the handle as returned to clients
+    pub fn try_encode(self) -> Result<Bytes> {
+        let parameters = match &self.params {
+            Some(params) => {
+                let mut writer = StreamWriter::try_new(vec![], &params.schema())?;
+                writer.write(params)?;
+                writer.into_inner()?
+            }
+            None => vec![],
+        };
+
+        Ok(proto::PreparedStatementHandle {
+            query: self.query,
+            id: self.id.to_vec(),
+            parameters,
+        }
+        .encode_to_vec()
+        .into())
     }
 }
 
@@ -52,13 +115,6 @@ impl Display for PreparedStatementHandle {
     }
 }
 
-/// Encode a PreparedStatementHandle as Bytes
-impl From<PreparedStatementHandle> for Bytes {
-    fn from(value: PreparedStatementHandle) -> Self {
-        Self::from(value.query.into_bytes())
-    }
-}
-
 /// Decoded / validated FlightSQL command messages
 ///
 /// Handles encoding/decoding prost::Any messages back
@@ -306,7 +362,7 @@ impl FlightSQLCommand {
         let msg = match self {
             Self::CommandStatementQuery(cmd) => Any::pack(&cmd),
             Self::CommandPreparedStatementQuery(handle) => {
-                let prepared_statement_handle = handle.encode();
+                let prepared_statement_handle = handle.try_encode()?;
                 let cmd = CommandPreparedStatementQuery {
                     prepared_statement_handle,
                 };
@@ -324,7 +380,7 @@ impl FlightSQLCommand {
             Self::CommandGetXdbcTypeInfo(cmd) => Any::pack(&cmd),
             Self::ActionCreatePreparedStatementRequest(cmd) => Any::pack(&cmd),
             Self::ActionClosePreparedStatementRequest(handle) => {
-                let prepared_statement_handle = handle.encode();
+                let prepared_statement_handle = handle.try_encode()?;
                 Any::pack(&ActionClosePreparedStatementRequest {
                     prepared_statement_handle,
                 })
@@ -1,6 +1,4 @@
 //! FlightSQL errors
-use std::string::FromUtf8Error;
-
 use arrow::error::ArrowError;
 use arrow_flight::error::FlightError;
 use datafusion::error::DataFusionError;
@@ -16,8 +14,24 @@ pub enum Error {
     #[snafu(context(false))]
     Decode { source: DecodeError },
 
-    #[snafu(display("Invalid PreparedStatement handle (invalid UTF-8:) {}", source))]
-    InvalidHandle { source: FromUtf8Error },
+    #[snafu(display("Invalid PreparedStatement handle: {}", source))]
+    InvalidHandle { source: DecodeError },
+
+    #[snafu(display(
+        "Invalid PreparedStatement parameters: expected a single row, got {}",
+        num_rows
+    ))]
+    InvalidParameters { num_rows: usize },
+
+    #[snafu(display(
+        "Invalid PreparedStatement parameters: expected {} values, got {}",
+        expected,
+        actual
+    ))]
+    InvalidParameterCount { expected: usize, actual: usize },
+
+    #[snafu(display("Unsupported PreparedStatement parameter: {}", id))]
+    UnsupportedParameter { id: String },
 
     #[snafu(display("{}", source))]
     #[snafu(context(false))]
@@ -20,9 +20,11 @@ use workspace_hack as _;
 mod cmd;
 mod error;
 mod planner;
+mod prepared;
 mod sql_info;
 mod xdbc_type_info;
 
 pub use cmd::{FlightSQLCommand, PreparedStatementHandle};
 pub use error::{Error, Result};
 pub use planner::FlightSQLPlanner;
+pub use prepared::{PreparedStatementCache, DEFAULT_MAX_PREPARED_STATEMENTS};
@@ -1,8 +1,9 @@
 //! FlightSQL handling
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use arrow::{
-    array::{ArrayRef, StringArray},
+    array::{ArrayRef, Int32Builder, StringArray, StringBuilder},
+    compute::cast,
     datatypes::{DataType, Field, Schema, SchemaRef},
     error::ArrowError,
     ipc::writer::IpcWriteOptions,
@@ -22,15 +23,17 @@ use bytes::Bytes;
 use datafusion::{
     logical_expr::{LogicalPlan, TableType},
     physical_plan::ExecutionPlan,
+    scalar::ScalarValue,
     sql::TableReference,
 };
 use iox_query::{exec::IOxSessionContext, QueryNamespace};
 use observability_deps::tracing::debug;
 use once_cell::sync::Lazy;
 use prost::Message;
+use snafu::{ensure, OptionExt};
 
 use crate::{error::*, sql_info::iox_sql_info_data, xdbc_type_info::xdbc_type_info_data};
-use crate::{FlightSQLCommand, PreparedStatementHandle};
+use crate::{FlightSQLCommand, PreparedStatementCache, PreparedStatementHandle};
 
 /// Logic for creating plans for various Flight messages against a query database
 #[derive(Debug, Default)]
@@ -55,21 +58,22 @@ impl FlightSQLPlanner {
                 get_schema_for_query(&query, ctx).await
             }
             FlightSQLCommand::CommandPreparedStatementQuery(handle) => {
-                get_schema_for_query(handle.query(), ctx).await
+                let plan = get_prepared_plan(&handle, ctx).await?;
+                Ok(get_schema_for_plan(plan))
             }
             FlightSQLCommand::CommandGetSqlInfo(CommandGetSqlInfo { .. }) => {
                 Ok(iox_sql_info_data().schema())
             }
             FlightSQLCommand::CommandGetCatalogs(req) => Ok(req.into_builder().schema()),
             FlightSQLCommand::CommandGetCrossReference(CommandGetCrossReference { .. }) => {
-                Ok(Arc::clone(&GET_CROSS_REFERENCE_SCHEMA))
+                Ok(Arc::clone(&FOREIGN_KEYS_SCHEMA))
             }
             FlightSQLCommand::CommandGetDbSchemas(req) => Ok(req.into_builder().schema()),
             FlightSQLCommand::CommandGetExportedKeys(CommandGetExportedKeys { .. }) => {
-                Ok(Arc::clone(&GET_EXPORTED_KEYS_SCHEMA))
+                Ok(Arc::clone(&FOREIGN_KEYS_SCHEMA))
             }
             FlightSQLCommand::CommandGetImportedKeys(CommandGetImportedKeys { .. }) => {
-                Ok(Arc::clone(&GET_IMPORTED_KEYS_SCHEMA))
+                Ok(Arc::clone(&FOREIGN_KEYS_SCHEMA))
             }
             FlightSQLCommand::CommandGetPrimaryKeys(CommandGetPrimaryKeys { .. }) => {
                 Ok(Arc::clone(&GET_PRIMARY_KEYS_SCHEMA))
@@ -108,7 +112,8 @@ impl FlightSQLPlanner {
             FlightSQLCommand::CommandPreparedStatementQuery(handle) => {
                 let query = handle.query();
                 debug!(%query, "Planning FlightSQL prepared query");
-                Ok(ctx.sql_to_physical_plan(query).await?)
+                let plan = get_prepared_plan(&handle, ctx).await?;
+                Ok(ctx.create_physical_plan(&plan).await?)
             }
             FlightSQLCommand::CommandGetSqlInfo(cmd) => {
                 debug!(?cmd, "Planning GetSqlInfo query");
@@ -238,6 +243,7 @@ impl FlightSQLPlanner {
         namespace_name: impl Into<String> + Send,
         _database: Arc<dyn QueryNamespace>,
         cmd: FlightSQLCommand,
+        statements: &PreparedStatementCache,
         ctx: &IOxSessionContext,
     ) -> Result<Bytes> {
         let namespace_name = namespace_name.into();
@@ -249,18 +255,28 @@ impl FlightSQLPlanner {
             ) => {
                 debug!(%query, "Creating prepared statement");
 
-                // todo run the planner here and actually figure out parameter schemas
-                // see https://github.com/apache/arrow-datafusion/pull/4701
-                let parameter_schema = vec![];
+                let plan = ctx.sql_to_logical_plan(&query).await?;
+
+                // statements without parameters have an empty parameter schema
+                let parameter_schema = Arc::new(get_parameter_schema(&plan)?);
+                let encoded_parameter_schema = if parameter_schema.fields().is_empty() {
+                    Bytes::new()
+                } else {
+                    encode_schema(&parameter_schema)?
+                };
 
-                let dataset_schema = get_schema_for_query(&query, ctx).await?;
+                let dataset_schema = get_schema_for_plan(plan);
                 let dataset_schema = encode_schema(dataset_schema.as_ref())?;
+
+                // only the parameter schema is kept, to validate the values
+                // bound to the statement
                 let handle = PreparedStatementHandle::new(query);
+                statements.insert_parameter_schema(&namespace_name, &handle, parameter_schema);
 
                 let result = ActionCreatePreparedStatementResult {
-                    prepared_statement_handle: Bytes::from(handle),
+                    prepared_statement_handle: handle.try_encode()?,
                     dataset_schema,
-                    parameter_schema: Bytes::from(parameter_schema),
+                    parameter_schema: encoded_parameter_schema,
                 };
 
                 let msg = Any::pack(&result)?;
@@ -270,7 +286,7 @@ impl FlightSQLPlanner {
                 let query = handle.query();
                 debug!(%query, "Closing prepared statement");
 
-                // Nothing really to do
+                statements.remove(&namespace_name, &handle);
                 Ok(Bytes::new())
             }
             _ => ProtocolSnafu {
@@ -287,6 +303,79 @@ async fn get_schema_for_query(query: &str, ctx: &IOxSessionContext) -> Result<Sc
     Ok(get_schema_for_plan(ctx.sql_to_logical_plan(query).await?))
 }
 
+/// Return the logical plan of the prepared statement `handle`, planned from
+/// its query with the parameter values it carries, if any.
+///
+/// Plans are not cached, so that every execution sees the current tables of
+/// the namespace.
+async fn get_prepared_plan(
+    handle: &PreparedStatementHandle,
+    ctx: &IOxSessionContext,
+) -> Result<LogicalPlan> {
+    let plan = ctx.sql_to_logical_plan(handle.query()).await?;
+    match handle.params() {
+        Some(params) => Ok(plan.with_param_values(param_values(&plan, params)?)?),
+        None => Ok(plan),
+    }
+}
+
+/// Return the schema of the parameters of the logical plan, with a field
+/// named after each placeholder (`$1`, `$2`, ...) in order.
+///
+/// Parameters whose type can not be inferred from the query are typed
+/// [`DataType::Null`].
+fn get_parameter_schema(logical_plan: &LogicalPlan) -> Result<Schema> {
+    let mut types = HashMap::new();
+    for (id, data_type) in logical_plan.get_parameter_types()? {
+        let index = id
+            .strip_prefix('$')
+            .and_then(|index| index.parse::<usize>().ok())
+            .filter(|index| *index > 0)
+            .context(UnsupportedParameterSnafu { id: &id })?;
+        types.insert(index, data_type);
+    }
+
+    // placeholders are bound by position, so any gaps in the numbering
+    // still need a field
+    let num_params = types.keys().max().copied().unwrap_or_default();
+    let fields = (1..=num_params)
+        .map(|index| {
+            let data_type = types.remove(&index).flatten().unwrap_or(DataType::Null);
+            Field::new(format!("${index}"), data_type, true)
+        })
+        .collect::<Vec<_>>();
+
+    Ok(Schema::new(fields))
+}
+
+/// Return the values of the placeholders of the logical plan from `params`,
+/// a single row with a column per parameter, cast to the parameter types
+/// inferred from the query.
+fn param_values(logical_plan: &LogicalPlan, params: &RecordBatch) -> Result<Vec<ScalarValue>> {
+    ensure!(
+        params.num_rows() == 1,
+        InvalidParametersSnafu {
+            num_rows: params.num_rows()
+        }
+    );
+
+    let types = logical_plan.get_parameter_types()?;
+    params
+        .columns()
+        .iter()
+        .enumerate()
+        .map(|(index, column)| {
+            let column = match types.get(&format!("${}", index + 1)) {
+                Some(Some(data_type)) if column.data_type() != data_type => {
+                    cast(column, data_type)?
+                }
+                _ => Arc::clone(column),
+            };
+            Ok(ScalarValue::try_from_array(&column, 0)?)
+        })
+        .collect()
+}
+
 /// Return the schema for the specified logical plan
 fn get_schema_for_plan(logical_plan: LogicalPlan) -> SchemaRef {
     // gather real schema, but only
@@ -334,7 +423,7 @@ async fn plan_get_cross_reference(
     _fk_db_schema: Option<String>,
     _fk_table: String,
 ) -> Result<LogicalPlan> {
-    let batch = RecordBatch::new_empty(Arc::clone(&GET_CROSS_REFERENCE_SCHEMA));
+    let batch = RecordBatch::new_empty(Arc::clone(&FOREIGN_KEYS_SCHEMA));
     Ok(ctx.batch_to_logical_plan(batch)?)
 }
 
@@ -369,7 +458,7 @@ async fn plan_get_exported_keys(
     _db_schema: Option<String>,
     _table: String,
 ) -> Result<LogicalPlan> {
-    let batch = RecordBatch::new_empty(Arc::clone(&GET_EXPORTED_KEYS_SCHEMA));
+    let batch = RecordBatch::new_empty(Arc::clone(&FOREIGN_KEYS_SCHEMA));
     Ok(ctx.batch_to_logical_plan(batch)?)
 }
 
@@ -379,17 +468,74 @@ async fn plan_get_imported_keys(
     _db_schema: Option<String>,
     _table: String,
 ) -> Result<LogicalPlan> {
-    let batch = RecordBatch::new_empty(Arc::clone(&GET_IMPORTED_KEYS_SCHEMA));
+    let batch = RecordBatch::new_empty(Arc::clone(&FOREIGN_KEYS_SCHEMA));
     Ok(ctx.batch_to_logical_plan(batch)?)
 }
 
+/// Return the primary key of the IOx table `table`: its tags, in
+/// lexicographical order, followed by `time`.
+///
+/// Tables that are not IOx tables (such as system tables) have no primary
+/// key.
 async fn plan_get_primary_keys(
     ctx: &IOxSessionContext,
-    _catalog: Option<String>,
-    _db_schema: Option<String>,
-    _table: String,
+    catalog: Option<String>,
+    db_schema: Option<String>,
+    table: String,
 ) -> Result<LogicalPlan> {
-    let batch = RecordBatch::new_empty(Arc::clone(&GET_PRIMARY_KEYS_SCHEMA));
+    let mut catalog_names = StringBuilder::new();
+    let mut db_schema_names = StringBuilder::new();
+    let mut table_names = StringBuilder::new();
+    let mut column_names = StringBuilder::new();
+    let mut key_names = StringBuilder::new();
+    let mut key_sequences = Int32Builder::new();
+
+    let catalog_list = ctx.inner().state().catalog_list();
+    for catalog_name in catalog_list.catalog_names() {
+        if catalog.as_ref().map_or(false, |c| c != &catalog_name) {
+            continue;
+        }
+        let Some(catalog_provider) = catalog_list.catalog(&catalog_name) else {
+            continue
+        };
+
+        for schema_name in catalog_provider.schema_names() {
+            if db_schema.as_ref().map_or(false, |s| s != &schema_name) {
+                continue;
+            }
+            let Some(schema_provider) = catalog_provider.schema(&schema_name) else {
+                continue
+            };
+            let Some(table_provider) = schema_provider.table(&table).await else {
+                continue
+            };
+            let Ok(schema) = schema::Schema::try_from(table_provider.schema()) else {
+                continue
+            };
+
+            for (index, column_name) in schema.primary_key().into_iter().enumerate() {
+                catalog_names.append_value(&catalog_name);
+                db_schema_names.append_value(&schema_name);
+                table_names.append_value(&table);
+                column_names.append_value(column_name);
+                key_names.append_null();
+                // key sequences start at 1
+                key_sequences.append_value(index as i32 + 1);
+            }
+        }
+    }
+
+    let batch = RecordBatch::try_new(
+        Arc::clone(&GET_PRIMARY_KEYS_SCHEMA),
+        vec![
+            Arc::new(catalog_names.finish()),
+            Arc::new(db_schema_names.finish()),
+            Arc::new(table_names.finish()),
+            Arc::new(column_names.finish()),
+            Arc::new(key_names.finish()),
+            Arc::new(key_sequences.finish()),
+        ],
+    )?;
     Ok(ctx.batch_to_logical_plan(batch)?)
 }
 
@@ -490,6 +636,10 @@ static TABLE_TYPES_RECORD_BATCH: Lazy<RecordBatch> = Lazy::new(|| {
     RecordBatch::try_new(Arc::clone(&GET_TABLE_TYPE_SCHEMA), vec![table_type]).unwrap()
 });
 
+/// The schema of the foreign keys returned by GetCrossReference,
+/// GetExportedKeys and GetImportedKeys. IOx has no foreign keys, so these
+/// are always empty.
+///
 /// The returned data should be ordered by pk_catalog_name, pk_db_schema_name,
 /// pk_table_name, pk_key_name, then key_sequence.
 /// update_rule and delete_rule returns a byte that is equivalent to actions:
@@ -498,67 +648,34 @@ static TABLE_TYPES_RECORD_BATCH: Lazy<RecordBatch> = Lazy::new(|| {
 ///    - 2 = SET NULL
 ///    - 3 = NO ACTION
 ///    - 4 = SET DEFAULT
-static GET_CROSS_REFERENCE_SCHEMA: Lazy<SchemaRef> = Lazy::new(|| {
-    Arc::new(Schema::new(vec![
-        Field::new("pk_catalog_name", DataType::Utf8, false),
-        Field::new("pk_db_schema_name", DataType::Utf8, false),
-        Field::new("pk_table_name", DataType::Utf8, false),
-        Field::new("pk_column_name", DataType::Utf8, false),
-        Field::new("fk_catalog_name", DataType::Utf8, false),
-        Field::new("fk_db_schema_name", DataType::Utf8, false),
-        Field::new("fk_table_name", DataType::Utf8, false),
-        Field::new("fk_column_name", DataType::Utf8, false),
-        Field::new("key_sequence", DataType::Int32, false),
-        Field::new("fk_key_name", DataType::Utf8, false),
-        Field::new("pk_key_name", DataType::Utf8, false),
-        Field::new("update_rule", DataType::UInt8, false),
-        Field::new("delete_rule", DataType::UInt8, false),
-    ]))
-});
-
-static GET_EXPORTED_KEYS_SCHEMA: Lazy<SchemaRef> = Lazy::new(|| {
-    Arc::new(Schema::new(vec![
-        Field::new("pk_catalog_name", DataType::Utf8, false),
-        Field::new("pk_db_schema_name", DataType::Utf8, false),
-        Field::new("pk_table_name", DataType::Utf8, false),
-        Field::new("pk_column_name", DataType::Utf8, false),
-        Field::new("fk_catalog_name", DataType::Utf8, false),
-        Field::new("fk_db_schema_name", DataType::Utf8, false),
-        Field::new("fk_table_name", DataType::Utf8, false),
-        Field::new("fk_column_name", DataType::Utf8, false),
-        Field::new("key_sequence", DataType::Int32, false),
-        Field::new("fk_key_name", DataType::Utf8, false),
-        Field::new("pk_key_name", DataType::Utf8, false),
-        Field::new("update_rule", DataType::UInt8, false),
-        Field::new("delete_rule", DataType::UInt8, false),
-    ]))
-});
-
-static GET_IMPORTED_KEYS_SCHEMA: Lazy<SchemaRef> = Lazy::new(|| {
+// From https://github.com/apache/arrow/blob/9588da967c756b2923e213ccc067378ba6c90a86/format/FlightSql.proto#L1338-L1375
+static FOREIGN_KEYS_SCHEMA: Lazy<SchemaRef> = Lazy::new(|| {
     Arc::new(Schema::new(vec![
-        Field::new("pk_catalog_name", DataType::Utf8, false),
-        Field::new("pk_db_schema_name", DataType::Utf8, false),
+        Field::new("pk_catalog_name", DataType::Utf8, true),
+        Field::new("pk_db_schema_name", DataType::Utf8, true),
         Field::new("pk_table_name", DataType::Utf8, false),
         Field::new("pk_column_name", DataType::Utf8, false),
-        Field::new("fk_catalog_name", DataType::Utf8, false),
-        Field::new("fk_db_schema_name", DataType::Utf8, false),
+        Field::new("fk_catalog_name", DataType::Utf8, true),
+        Field::new("fk_db_schema_name", DataType::Utf8, true),
         Field::new("fk_table_name", DataType::Utf8, false),
         Field::new("fk_column_name", DataType::Utf8, false),
         Field::new("key_sequence", DataType::Int32, false),
-        Field::new("fk_key_name", DataType::Utf8, false),
-        Field::new("pk_key_name", DataType::Utf8, false),
+        Field::new("fk_key_name", DataType::Utf8, true),
+        Field::new("pk_key_name", DataType::Utf8, true),
         Field::new("update_rule", DataType::UInt8, false),
         Field::new("delete_rule", DataType::UInt8, false),
     ]))
 });
 
+/// The schema for GetPrimaryKeys
+// From https://github.com/apache/arrow/blob/9588da967c756b2923e213ccc067378ba6c90a86/format/FlightSql.proto#L1251-L1264
 static GET_PRIMARY_KEYS_SCHEMA: Lazy<SchemaRef> = Lazy::new(|| {
     Arc::new(Schema::new(vec![
-        Field::new("catalog_name", DataType::Utf8, false),
-        Field::new("db_schema_name", DataType::Utf8, false),
+        Field::new("catalog_name", DataType::Utf8, true),
+        Field::new("db_schema_name", DataType::Utf8, true),
         Field::new("table_name", DataType::Utf8, false),
         Field::new("column_name", DataType::Utf8, false),
-        Field::new("key_name", DataType::Utf8, false),
+        Field::new("key_name", DataType::Utf8, true),
         Field::new("key_sequence", DataType::Int32, false),
     ]))
 });
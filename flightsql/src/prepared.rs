@@ -0,0 +1,360 @@
+//! Querier local state of FlightSQL prepared statements
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+};
+
+use arrow::{
+    compute::cast,
+    datatypes::{DataType, Field, Schema, SchemaRef},
+    record_batch::RecordBatch,
+};
+use bytes::Bytes;
+use parking_lot::Mutex;
+use snafu::ensure;
+
+use crate::{error::*, PreparedStatementHandle};
+
+/// The default number of prepared statements a [`PreparedStatementCache`]
+/// keeps state for.
+pub const DEFAULT_MAX_PREPARED_STATEMENTS: usize = 1_000;
+
+/// Prepared statements are keyed by the namespace they were prepared in, so
+/// a handle never sees the state of a statement of another namespace.
+type Key = (String, Bytes);
+
+/// State kept for a prepared statement
+#[derive(Debug)]
+struct Entry {
+    /// The query the statement was prepared for
+    query: String,
+    /// The schema of the parameters of the query
+    parameter_schema: Option<SchemaRef>,
+    /// The values bound to the parameters via `DoPut`
+    params: Option<RecordBatch>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    entries: HashMap<Key, Entry>,
+    /// Keys of `entries`, oldest first
+    order: VecDeque<Key>,
+}
+
+/// Caches the parameter schemas of prepared statements and the parameter
+/// values bound to them, keyed by the id of their handle.
+///
+/// Statements are always planned from their query and bound values, so no
+/// plan is kept here. The parameter schema validates the values bound with
+/// `DoPut`, which returns a handle carrying the values. Clients that execute
+/// the original handle instead get the values kept here, which
+/// `GetFlightInfo` then carries to `DoGet` in the ticket.
+///
+/// Only the most recently prepared statements are kept. The values bound to a
+/// handle whose state was evicted (or that was prepared by another querier)
+/// are validated when the statement is planned.
+#[derive(Debug)]
+pub struct PreparedStatementCache {
+    max_statements: usize,
+    inner: Mutex<Inner>,
+}
+
+impl Default for PreparedStatementCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_PREPARED_STATEMENTS)
+    }
+}
+
+impl PreparedStatementCache {
+    /// Create a cache that keeps state for at most `max_statements`
+    /// prepared statements.
+    pub fn new(max_statements: usize) -> Self {
+        Self {
+            max_statements,
+            inner: Default::default(),
+        }
+    }
+
+    /// Return the cached parameter schema of `handle` in `namespace_name`, if
+    /// any.
+    pub fn parameter_schema(
+        &self,
+        namespace_name: &str,
+        handle: &PreparedStatementHandle,
+    ) -> Option<SchemaRef> {
+        let inner = self.inner.lock();
+        inner
+            .entries
+            .get(&key(namespace_name, handle))
+            .filter(|entry| entry.query == handle.query())
+            .and_then(|entry| entry.parameter_schema.clone())
+    }
+
+    /// Cache the `parameter_schema` of `handle` in `namespace_name`.
+    pub fn insert_parameter_schema(
+        &self,
+        namespace_name: &str,
+        handle: &PreparedStatementHandle,
+        parameter_schema: SchemaRef,
+    ) {
+        self.upsert(namespace_name, handle, |entry| {
+            entry.parameter_schema = Some(parameter_schema)
+        });
+    }
+
+    /// Bind `params`, a single row with a column per parameter, to `handle`
+    /// in `namespace_name`, and return `handle` carrying the values.
+    ///
+    /// If the parameter schema of `handle` is cached, the values are
+    /// validated against it and cast to the parameter types.
+    pub fn bind(
+        &self,
+        namespace_name: &str,
+        handle: PreparedStatementHandle,
+        params: RecordBatch,
+    ) -> Result<PreparedStatementHandle> {
+        ensure!(
+            params.num_rows() == 1,
+            InvalidParametersSnafu {
+                num_rows: params.num_rows()
+            }
+        );
+
+        let params = match self.parameter_schema(namespace_name, &handle) {
+            Some(schema) => cast_params(&schema, params)?,
+            None => params,
+        };
+
+        let bound = params.clone();
+        self.upsert(namespace_name, &handle, |entry| entry.params = Some(bound));
+        Ok(handle.with_params(params))
+    }
+
+    /// Return `handle` with the parameter values bound to it in
+    /// `namespace_name`, if it does not carry any values itself.
+    pub fn with_bound_params(
+        &self,
+        namespace_name: &str,
+        handle: PreparedStatementHandle,
+    ) -> PreparedStatementHandle {
+        if handle.params().is_some() {
+            return handle;
+        }
+
+        let params = {
+            let inner = self.inner.lock();
+            inner
+                .entries
+                .get(&key(namespace_name, &handle))
+                .filter(|entry| entry.query == handle.query())
+                .and_then(|entry| entry.params.clone())
+        };
+
+        match params {
+            Some(params) => handle.with_params(params),
+            None => handle,
+        }
+    }
+
+    /// Drop all state of `handle` in `namespace_name`.
+    pub fn remove(&self, namespace_name: &str, handle: &PreparedStatementHandle) {
+        let key = key(namespace_name, handle);
+        let mut inner = self.inner.lock();
+        if inner.entries.remove(&key).is_some() {
+            inner.order.retain(|k| k != &key);
+        }
+    }
+
+    /// Update the state of `handle` in `namespace_name` with `f`, creating
+    /// it (and evicting the oldest statement, if full) if needed.
+    fn upsert(
+        &self,
+        namespace_name: &str,
+        handle: &PreparedStatementHandle,
+        f: impl FnOnce(&mut Entry),
+    ) {
+        let key = key(namespace_name, handle);
+        let mut inner = self.inner.lock();
+
+        match inner.entries.get_mut(&key) {
+            // A handle for another query with the same id must not see (or
+            // change) the state of the original statement
+            Some(entry) if entry.query != handle.query() => return,
+            Some(entry) => {
+                f(entry);
+                return;
+            }
+            None => {}
+        }
+
+        while inner.entries.len() >= self.max_statements {
+            let Some(oldest) = inner.order.pop_front() else {
+                break;
+            };
+            inner.entries.remove(&oldest);
+        }
+
+        let mut entry = Entry {
+            query: handle.query().to_string(),
+            parameter_schema: None,
+            params: None,
+        };
+        f(&mut entry);
+        inner.order.push_back(key.clone());
+        inner.entries.insert(key, entry);
+    }
+}
+
+fn key(namespace_name: &str, handle: &PreparedStatementHandle) -> Key {
+    (namespace_name.to_string(), handle.id().clone())
+}
+
+/// Cast the columns of `params` to the types of `schema`, keeping the
+/// columns of parameters whose type is unknown.
+fn cast_params(schema: &SchemaRef, params: RecordBatch) -> Result<RecordBatch> {
+    ensure!(
+        params.num_columns() == schema.fields().len(),
+        InvalidParameterCountSnafu {
+            expected: schema.fields().len(),
+            actual: params.num_columns(),
+        }
+    );
+
+    let columns = params
+        .columns()
+        .iter()
+        .zip(schema.fields())
+        .map(|(column, field)| match field.data_type() {
+            DataType::Null => Ok(Arc::clone(column)),
+            data_type if data_type == column.data_type() => Ok(Arc::clone(column)),
+            data_type => cast(column, data_type),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let fields = schema
+        .fields()
+        .iter()
+        .zip(&columns)
+        .map(|(field, column)| Field::new(field.name(), column.data_type().clone(), true))
+        .collect::<Vec<_>>();
+
+    Ok(RecordBatch::try_new(
+        Arc::new(Schema::new(fields)),
+        columns,
+    )?)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::array::{ArrayRef, Int64Array};
+
+    use super::*;
+
+    fn params(v: i64) -> RecordBatch {
+        RecordBatch::try_from_iter([("$1", Arc::new(Int64Array::from(vec![v])) as ArrayRef)])
+            .unwrap()
+    }
+
+    #[test]
+    fn test_bind() {
+        let cache = PreparedStatementCache::new(2);
+        let handle = PreparedStatementHandle::new("SELECT $1".into());
+
+        // nothing bound yet
+        let got = cache.with_bound_params("ns", handle.clone());
+        assert_eq!(got.params(), None);
+
+        // the returned handle carries the values
+        let bound = cache.bind("ns", handle.clone(), params(1)).unwrap();
+        assert_eq!(bound.params(), Some(&params(1)));
+        let got = cache.with_bound_params("ns", handle.clone());
+        assert_eq!(got.params(), Some(&params(1)));
+
+        // rebinding replaces the values
+        cache.bind("ns", handle.clone(), params(2)).unwrap();
+        let got = cache.with_bound_params("ns", handle.clone());
+        assert_eq!(got.params(), Some(&params(2)));
+
+        // values carried by the handle take precedence
+        let got = cache.with_bound_params("ns", handle.clone().with_params(params(3)));
+        assert_eq!(got.params(), Some(&params(3)));
+
+        // bindings are scoped to the namespace
+        let got = cache.with_bound_params("other", handle.clone());
+        assert_eq!(got.params(), None);
+
+        // bindings are scoped to the statement
+        let other = PreparedStatementHandle::new("SELECT $1".into());
+        let got = cache.with_bound_params("ns", other);
+        assert_eq!(got.params(), None);
+
+        cache.remove("ns", &handle);
+        let got = cache.with_bound_params("ns", handle.clone());
+        assert_eq!(got.params(), None);
+
+        // only a single row of values can be bound
+        let batch = RecordBatch::try_from_iter([(
+            "$1",
+            Arc::new(Int64Array::from(vec![1, 2])) as ArrayRef,
+        )])
+        .unwrap();
+        let err = cache.bind("ns", handle.clone(), batch).unwrap_err();
+        assert!(matches!(err, Error::InvalidParameters { num_rows: 2 }));
+    }
+
+    #[test]
+    fn test_eviction() {
+        let cache = PreparedStatementCache::new(2);
+        let handles = (0..3)
+            .map(|_| PreparedStatementHandle::new("SELECT $1".into()))
+            .collect::<Vec<_>>();
+
+        for handle in &handles {
+            cache.bind("ns", handle.clone(), params(1)).unwrap();
+        }
+
+        // the oldest statement was evicted
+        let bound = handles
+            .iter()
+            .map(|h| cache.with_bound_params("ns", h.clone()).params().is_some())
+            .collect::<Vec<_>>();
+        assert_eq!(bound, [false, true, true]);
+    }
+
+    #[test]
+    fn test_bind_parameter_schema() {
+        let cache = PreparedStatementCache::new(2);
+        let handle = PreparedStatementHandle::new("SELECT $1, $2".into());
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("$1", DataType::Float64, true),
+            Field::new("$2", DataType::Null, true),
+        ]));
+        cache.insert_parameter_schema("ns", &handle, Arc::clone(&schema));
+        assert_eq!(cache.parameter_schema("ns", &handle), Some(schema));
+        assert_eq!(cache.parameter_schema("other", &handle), None);
+
+        // values are cast to the parameter types, if known
+        let batch = RecordBatch::try_from_iter([
+            ("a", Arc::new(Int64Array::from(vec![1])) as ArrayRef),
+            ("b", Arc::new(Int64Array::from(vec![2])) as ArrayRef),
+        ])
+        .unwrap();
+        let bound = cache.bind("ns", handle.clone(), batch).unwrap();
+        let values = bound.params().unwrap();
+        assert_eq!(values.schema().field(0).name(), "$1");
+        assert_eq!(values.column(0).data_type(), &DataType::Float64);
+        assert_eq!(values.column(1).data_type(), &DataType::Int64);
+
+        // a value is required for every parameter
+        let err = cache.bind("ns", handle, params(1)).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::InvalidParameterCount {
+                expected: 2,
+                actual: 1
+            }
+        ));
+    }
+}
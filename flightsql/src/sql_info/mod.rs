@@ -97,23 +97,26 @@ static INSTANCE: Lazy<SqlInfoData> = Lazy::new(|| {
     // Skip SqlSupportsConvert (which is the map of the conversions that are supported);
     // .with_sql_info(SqlInfo::SqlSupportsConvert, TBD);
     // https://github.com/influxdata/influxdb_iox/issues/7253
-    builder.append(SqlInfo::SqlSupportsTableCorrelationNames, false);
+    // tables can be aliased (`FROM t AS x`)
+    builder.append(SqlInfo::SqlSupportsTableCorrelationNames, true);
     builder.append(SqlInfo::SqlSupportsDifferentTableCorrelationNames, false);
     builder.append(SqlInfo::SqlSupportsExpressionsInOrderBy, true);
     builder.append(SqlInfo::SqlSupportsOrderByUnrelated, true);
     builder.append(SqlInfo::SqlSupportedGroupBy, 3i32);
-    builder.append(SqlInfo::SqlSupportsLikeEscapeClause, true);
+    // DataFusion can not execute `LIKE .. ESCAPE ..`
+    builder.append(SqlInfo::SqlSupportsLikeEscapeClause, false);
     builder.append(SqlInfo::SqlSupportsNonNullableColumns, true);
     builder.append(
         SqlInfo::SqlSupportedGrammar,
         SupportedSqlGrammar::SqlCoreGrammar as i32,
     );
-    // report IOx supports all ansi 92
-    builder.append(SqlInfo::SqlAnsi92SupportedLevel, 0b111_i32);
+    // report IOx supports entry level ansi 92 only
+    builder.append(SqlInfo::SqlAnsi92SupportedLevel, 0b001_i32);
     builder.append(SqlInfo::SqlSupportsIntegrityEnhancementFacility, false);
     builder.append(SqlInfo::SqlOuterJoinsSupportLevel, 2i32);
     builder.append(SqlInfo::SqlSchemaTerm, "schema");
     builder.append(SqlInfo::SqlProcedureTerm, "procedure");
+    builder.append(SqlInfo::SqlCatalogTerm, "catalog");
     builder.append(SqlInfo::SqlCatalogAtStart, false);
     builder.append(SqlInfo::SqlSchemasSupportedActions, 0i32);
     builder.append(SqlInfo::SqlCatalogsSupportedActions, 0i32);
@@ -131,6 +131,70 @@ static XDBC_TYPE_INFO_DATA: Lazy<XdbcTypeInfoData> = Lazy::new(|| {
         num_prec_radix: Some(2), // https://github.com/apache/arrow-datafusion/blob/3801d45fe5ea3d9b207488527b758a0264665263/datafusion/core/src/catalog/information_schema.rs#L568
         interval_precision: None,
     });
+    builder.append(XdbcTypeInfo {
+        type_name: "BIGINT".to_string(),
+        data_type: XdbcDataType::XdbcBigint,
+        column_size: Some(64), // https://github.com/apache/arrow-datafusion/blob/3801d45fe5ea3d9b207488527b758a0264665263/datafusion/core/src/catalog/information_schema.rs#L563
+        literal_prefix: None,
+        literal_suffix: None,
+        create_params: None,
+        nullable: Nullable::NullabilityNullable,
+        case_sensitive: false,
+        searchable: Searchable::Full,
+        unsigned_attribute: Some(false),
+        fixed_prec_scale: false,
+        auto_increment: Some(false),
+        local_type_name: Some("BIGINT".to_string()),
+        minimum_scale: None,
+        maximum_scale: None,
+        sql_data_type: XdbcDataType::XdbcBigint,
+        datetime_subcode: None,
+        num_prec_radix: Some(2), // https://github.com/apache/arrow-datafusion/blob/3801d45fe5ea3d9b207488527b758a0264665263/datafusion/core/src/catalog/information_schema.rs#L563
+        interval_precision: None,
+    });
+    builder.append(XdbcTypeInfo {
+        type_name: "DOUBLE".to_string(),
+        data_type: XdbcDataType::XdbcDouble,
+        column_size: Some(53), // https://github.com/apache/arrow-datafusion/blob/3801d45fe5ea3d9b207488527b758a0264665263/datafusion/core/src/catalog/information_schema.rs#L568
+        literal_prefix: None,
+        literal_suffix: None,
+        create_params: None,
+        nullable: Nullable::NullabilityNullable,
+        case_sensitive: false,
+        searchable: Searchable::Full,
+        unsigned_attribute: Some(false),
+        fixed_prec_scale: false,
+        auto_increment: Some(false),
+        local_type_name: Some("DOUBLE".to_string()),
+        minimum_scale: None,
+        maximum_scale: None,
+        sql_data_type: XdbcDataType::XdbcDouble,
+        datetime_subcode: None,
+        num_prec_radix: Some(2), // https://github.com/apache/arrow-datafusion/blob/3801d45fe5ea3d9b207488527b758a0264665263/datafusion/core/src/catalog/information_schema.rs#L568
+        interval_precision: None,
+    });
+    // XDBC has no boolean type, so BOOLEAN is reported as a single BIT
+    builder.append(XdbcTypeInfo {
+        type_name: "BOOLEAN".to_string(),
+        data_type: XdbcDataType::XdbcBit,
+        column_size: None,
+        literal_prefix: None,
+        literal_suffix: None,
+        create_params: None,
+        nullable: Nullable::NullabilityNullable,
+        case_sensitive: false,
+        searchable: Searchable::Full,
+        unsigned_attribute: None,
+        fixed_prec_scale: false,
+        auto_increment: None,
+        local_type_name: Some("BOOLEAN".to_string()),
+        minimum_scale: None,
+        maximum_scale: None,
+        sql_data_type: XdbcDataType::XdbcBit,
+        datetime_subcode: None,
+        num_prec_radix: None,
+        interval_precision: None,
+    });
     builder.append(XdbcTypeInfo {
         type_name: "TIMESTAMP".to_string(),
         data_type: XdbcDataType::XdbcTimestamp,
@@ -27,6 +27,7 @@ use crate::{
     objectstore::{checker as os_checker, deleter as os_deleter, lister as os_lister},
     parquetfile::deleter as pf_deleter,
     retention::flagger as retention_flagger,
+    storage_usage::reporter as storage_usage_reporter,
 };
 
 use clap_blocks::garbage_collector::GarbageCollectorConfig;
@@ -45,6 +46,7 @@ mod objectstore;
 mod parquetfile;
 /// Logic for flagging parquet files for deletion based on retention settings
 mod retention;
+mod storage_usage;
 
 const BUFFER_SIZE: usize = 1000;
 
@@ -61,6 +63,7 @@ pub struct GarbageCollector {
     os_deleter: tokio::task::JoinHandle<Result<(), os_deleter::Error>>,
     pf_deleter: tokio::task::JoinHandle<Result<(), pf_deleter::Error>>,
     retention_flagger: tokio::task::JoinHandle<Result<(), retention_flagger::Error>>,
+    storage_usage_reporter: tokio::task::JoinHandle<Result<(), storage_usage_reporter::Error>>,
 }
 
 impl Debug for GarbageCollector {
@@ -76,6 +79,7 @@ impl GarbageCollector {
             object_store,
             sub_config,
             catalog,
+            metric_registry,
         } = config;
 
         let dry_run = sub_config.dry_run;
@@ -85,6 +89,7 @@ impl GarbageCollector {
             objectstore_sleep_interval_minutes = %sub_config.objectstore_sleep_interval_minutes,
             parquetfile_sleep_interval_minutes = %sub_config.parquetfile_sleep_interval_minutes,
             retention_sleep_interval_minutes = %sub_config.retention_sleep_interval_minutes,
+            storage_usage_sleep_interval_minutes = %sub_config.storage_usage_sleep_interval_minutes,
             "GarbageCollector starting"
         );
 
@@ -166,11 +171,18 @@ impl GarbageCollector {
         // flag_for_delete_by_retention() on the catalog then sleeps.
         let retention_flagger = tokio::spawn(retention_flagger::perform(
             shutdown.clone(),
-            catalog,
+            Arc::clone(&catalog),
             sub_config.retention_sleep_interval_minutes,
             sub_config.dry_run,
         ));
 
+        let storage_usage_reporter = tokio::spawn(storage_usage_reporter::perform(
+            shutdown.clone(),
+            catalog,
+            metric_registry,
+            sub_config.storage_usage_sleep_interval_minutes,
+        ));
+
         Ok(Self {
             shutdown,
             os_lister,
@@ -178,6 +190,7 @@ impl GarbageCollector {
             os_deleter,
             pf_deleter,
             retention_flagger,
+            storage_usage_reporter,
         })
     }
 
@@ -197,17 +210,27 @@ impl GarbageCollector {
             os_deleter,
             pf_deleter,
             retention_flagger,
+            storage_usage_reporter,
             shutdown: _,
         } = self;
 
-        let (os_lister, os_checker, os_deleter, pf_deleter, retention_flagger) = futures::join!(
+        let (
             os_lister,
             os_checker,
             os_deleter,
             pf_deleter,
-            retention_flagger
+            retention_flagger,
+            storage_usage_reporter,
+        ) = futures::join!(
+            os_lister,
+            os_checker,
+            os_deleter,
+            pf_deleter,
+            retention_flagger,
+            storage_usage_reporter
         );
 
+        storage_usage_reporter.context(StorageUsageReporterPanicSnafu)??;
         retention_flagger.context(ParquetFileDeleterPanicSnafu)??;
         pf_deleter.context(ParquetFileDeleterPanicSnafu)??;
         os_deleter.context(ObjectStoreDeleterPanicSnafu)??;
@@ -229,6 +252,9 @@ pub struct Config {
 
     /// The garbage collector specific configuration
     pub sub_config: GarbageCollectorConfig,
+
+    /// The registry to report the storage usage metrics to
+    pub metric_registry: Arc<metric::Registry>,
 }
 
 impl Debug for Config {
@@ -274,6 +300,14 @@ pub enum Error {
     ParquetFileRetentionFlagger { source: retention_flagger::Error },
     #[snafu(display("The parquet file retention flagger task panicked"))]
     ParquetFileRetentionFlaggerPanic { source: tokio::task::JoinError },
+
+    #[snafu(display("The storage usage reporter task failed"))]
+    #[snafu(context(false))]
+    StorageUsageReporter {
+        source: storage_usage_reporter::Error,
+    },
+    #[snafu(display("The storage usage reporter task panicked"))]
+    StorageUsageReporterPanic { source: tokio::task::JoinError },
 }
 
 #[allow(missing_docs)]
@@ -348,6 +382,7 @@ mod tests {
             object_store,
             catalog,
             sub_config,
+            metric_registry: Default::default(),
         }
     }
 
@@ -230,7 +230,7 @@ mod tests {
     use chrono::TimeZone;
     use data_types::{
         ColumnId, ColumnSet, CompactionLevel, NamespaceId, ParquetFile, ParquetFileId,
-        ParquetFileParams, PartitionId, TableId, Timestamp, TransitionPartitionId,
+        ParquetFileParams, PartitionId, StorageUsage, TableId, Timestamp, TransitionPartitionId,
     };
     use iox_catalog::{
         interface::Catalog,
@@ -532,6 +532,16 @@ mod tests {
             self.inner.list_by_table_not_to_delete(table_id).await
         }
 
+        async fn list_by_table_created_after(
+            &mut self,
+            table_id: TableId,
+            created_after: Timestamp,
+        ) -> iox_catalog::interface::Result<Vec<ParquetFile>> {
+            self.inner
+                .list_by_table_created_after(table_id, created_after)
+                .await
+        }
+
         async fn delete_old_ids_only(
             &mut self,
             older_than: Timestamp,
@@ -574,5 +584,20 @@ mod tests {
             self.create_upgrade_delete(delete, upgrade, create, target_level)
                 .await
         }
+
+        async fn storage_usage_by_namespace(
+            &mut self,
+            namespace_id: NamespaceId,
+        ) -> iox_catalog::interface::Result<Vec<StorageUsage>> {
+            self.inner.storage_usage_by_namespace(namespace_id).await
+        }
+
+        async fn storage_usage(&mut self) -> iox_catalog::interface::Result<Vec<StorageUsage>> {
+            self.inner.storage_usage().await
+        }
+
+        async fn fold_storage_usage(&mut self) -> iox_catalog::interface::Result<usize> {
+            self.inner.fold_storage_usage().await
+        }
     }
 }
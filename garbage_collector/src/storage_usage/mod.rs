@@ -0,0 +1,2 @@
+/// Logic for exposing the storage usage aggregated by the catalog as metrics
+pub(crate) mod reporter;
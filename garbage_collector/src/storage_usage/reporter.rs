@@ -0,0 +1,231 @@
+use data_types::{NamespaceId, StorageUsage, TableId};
+use iox_catalog::interface::{Catalog, SoftDeletedRows};
+use metric::{Attributes, Metric, U64Gauge};
+use observability_deps::tracing::*;
+use snafu::prelude::*;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    time::Duration,
+};
+use tokio::{select, time::sleep};
+use tokio_util::sync::CancellationToken;
+
+pub(crate) async fn perform(
+    shutdown: CancellationToken,
+    catalog: Arc<dyn Catalog>,
+    metric_registry: Arc<metric::Registry>,
+    sleep_interval_minutes: u64,
+) -> Result<()> {
+    let mut metrics = StorageUsageMetrics::new(&metric_registry);
+
+    loop {
+        // Failing to report the usage must not stop the garbage collection.
+        match metrics.report(catalog.as_ref()).await {
+            Ok(n) => info!(tables = n, "reported storage usage"),
+            Err(e) => warn!(error = %e, "failed to report storage usage"),
+        }
+
+        select! {
+            _ = shutdown.cancelled() => {
+                break
+            },
+            _ = sleep(Duration::from_secs(60 * sleep_interval_minutes)) => (),
+        }
+    }
+    Ok(())
+}
+
+/// Gauges mirroring the [`StorageUsage`] of each table and compaction level in the catalog.
+#[derive(Debug)]
+struct StorageUsageMetrics {
+    file_size_bytes: Metric<U64Gauge>,
+    file_count: Metric<U64Gauge>,
+    row_count: Metric<U64Gauge>,
+
+    /// The attributes set by the previous report, so that the gauges of the tables and
+    /// compaction levels that no longer have any files are reset.
+    reported: HashSet<Attributes>,
+}
+
+impl StorageUsageMetrics {
+    fn new(metric_registry: &metric::Registry) -> Self {
+        Self {
+            file_size_bytes: metric_registry.register_metric(
+                "catalog_storage_usage_bytes",
+                "total size of the parquet files not marked for deletion",
+            ),
+            file_count: metric_registry.register_metric(
+                "catalog_storage_usage_files",
+                "number of parquet files not marked for deletion",
+            ),
+            row_count: metric_registry.register_metric(
+                "catalog_storage_usage_rows",
+                "total number of rows in the parquet files not marked for deletion",
+            ),
+            reported: Default::default(),
+        }
+    }
+
+    /// Fold the storage usage changes recorded by the catalog, then read the storage usage and
+    /// update the gauges, returning the number of table and compaction level pairs with files.
+    async fn report(&mut self, catalog: &dyn Catalog) -> Result<usize> {
+        let mut repos = catalog.repositories().await;
+
+        // Keeps the changes the catalog reads along with the usage few.
+        let changes = repos
+            .parquet_files()
+            .fold_storage_usage()
+            .await
+            .context(CatalogSnafu)?;
+        debug!(changes, "folded storage usage changes");
+
+        // Soft-deleted namespaces keep using storage until their files are deleted.
+        let namespaces: HashMap<NamespaceId, String> = repos
+            .namespaces()
+            .list(SoftDeletedRows::AllRows)
+            .await
+            .context(CatalogSnafu)?
+            .into_iter()
+            .map(|n| (n.id, n.name))
+            .collect();
+        let tables: HashMap<TableId, String> = repos
+            .tables()
+            .list()
+            .await
+            .context(CatalogSnafu)?
+            .into_iter()
+            .map(|t| (t.id, t.name))
+            .collect();
+        let usage = repos
+            .parquet_files()
+            .storage_usage()
+            .await
+            .context(CatalogSnafu)?;
+
+        let mut reported = HashSet::with_capacity(usage.len());
+        for u in &usage {
+            let attributes = Attributes::from([
+                (
+                    "namespace",
+                    namespaces
+                        .get(&u.namespace_id)
+                        .cloned()
+                        .unwrap_or_else(|| u.namespace_id.to_string())
+                        .into(),
+                ),
+                (
+                    "table",
+                    tables
+                        .get(&u.table_id)
+                        .cloned()
+                        .unwrap_or_else(|| u.table_id.to_string())
+                        .into(),
+                ),
+                (
+                    "compaction_level",
+                    (u.compaction_level as i16).to_string().into(),
+                ),
+            ]);
+            self.set(&attributes, u);
+            reported.insert(attributes);
+        }
+
+        for attributes in self.reported.difference(&reported) {
+            self.file_size_bytes.recorder(attributes.clone()).set(0);
+            self.file_count.recorder(attributes.clone()).set(0);
+            self.row_count.recorder(attributes.clone()).set(0);
+        }
+        self.reported = reported;
+
+        Ok(usage.len())
+    }
+
+    fn set(&self, attributes: &Attributes, usage: &StorageUsage) {
+        let to_u64 = |v: i64| v.try_into().unwrap_or_default();
+
+        self.file_size_bytes
+            .recorder(attributes.clone())
+            .set(to_u64(usage.file_size_bytes));
+        self.file_count
+            .recorder(attributes.clone())
+            .set(to_u64(usage.file_count));
+        self.row_count
+            .recorder(attributes.clone())
+            .set(to_u64(usage.row_count));
+    }
+}
+
+#[derive(Debug, Snafu)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[snafu(display("Failed to read the storage usage from the catalog"))]
+    Catalog {
+        source: iox_catalog::interface::Error,
+    },
+}
+
+pub(crate) type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use iox_catalog::{
+        mem::MemCatalog,
+        test_helpers::{arbitrary_namespace, arbitrary_parquet_file_params, arbitrary_table},
+    };
+    use metric::{Observation, RawReporter};
+
+    #[tokio::test]
+    async fn test_report() {
+        let metric_registry = Arc::new(metric::Registry::default());
+        let catalog: Arc<dyn Catalog> = Arc::new(MemCatalog::new(Arc::clone(&metric_registry)));
+
+        let mut repos = catalog.repositories().await;
+        let namespace = arbitrary_namespace(&mut *repos, "ns").await;
+        let table = arbitrary_table(&mut *repos, "cpu", &namespace).await;
+        let partition = repos
+            .partitions()
+            .create_or_get("one".into(), table.id)
+            .await
+            .unwrap();
+        let file = repos
+            .parquet_files()
+            .create(arbitrary_parquet_file_params(
+                &namespace, &table, &partition,
+            ))
+            .await
+            .unwrap();
+        drop(repos);
+
+        let mut metrics = StorageUsageMetrics::new(&metric_registry);
+        assert_eq!(metrics.report(catalog.as_ref()).await.unwrap(), 1);
+
+        let attributes = Attributes::from(&[
+            ("namespace", "ns"),
+            ("table", "cpu"),
+            ("compaction_level", "0"),
+        ]);
+        let bytes = || {
+            let mut reporter = RawReporter::default();
+            metric_registry.report(&mut reporter);
+            reporter
+                .metric("catalog_storage_usage_bytes")
+                .unwrap()
+                .observation(attributes.clone())
+                .cloned()
+        };
+        assert_eq!(bytes(), Some(Observation::U64Gauge(1337)));
+
+        // Once the file is deleted, the gauge is reset.
+        catalog
+            .repositories()
+            .await
+            .parquet_files()
+            .create_upgrade_delete(&[file.id], &[], &[], file.compaction_level)
+            .await
+            .unwrap();
+        assert_eq!(metrics.report(catalog.as_ref()).await.unwrap(), 0);
+        assert_eq!(bytes(), Some(Observation::U64Gauge(0)));
+    }
+}
@@ -27,6 +27,7 @@ fn main() -> Result<()> {
 /// - `influxdata.iox.object_store.v1.rs`
 /// - `influxdata.iox.predicate.v1.rs`
 /// - `influxdata.iox.querier.v1.rs`
+/// - `influxdata.iox.rollup.v1.rs`
 /// - `influxdata.iox.schema.v1.rs`
 /// - `influxdata.iox.table.v1.rs`
 /// - `influxdata.iox.wal.v1.rs`
@@ -44,6 +45,7 @@ fn generate_grpc_types(root: &Path) -> Result<()> {
     let partition_template_path = root.join("influxdata/iox/partition_template/v1");
     let predicate_path = root.join("influxdata/iox/predicate/v1");
     let querier_path = root.join("influxdata/iox/querier/v1");
+    let rollup_path = root.join("influxdata/iox/rollup/v1");
     let schema_path = root.join("influxdata/iox/schema/v1");
     let storage_errors_path = root.join("influxdata/platform/errors");
     let storage_path = root.join("influxdata/platform/storage");
@@ -55,18 +57,21 @@ fn generate_grpc_types(root: &Path) -> Result<()> {
         catalog_path.join("parquet_file.proto"),
         catalog_path.join("partition_identifier.proto"),
         catalog_path.join("service.proto"),
+        catalog_path.join("storage_usage.proto"),
         compactor_path.join("service.proto"),
         delete_path.join("service.proto"),
         gossip_path.join("parquet_file.proto"),
         gossip_path.join("schema.proto"),
         ingester_path.join("parquet_metadata.proto"),
         ingester_path.join("persist.proto"),
+        ingester_path.join("replication.proto"),
         ingester_path.join("write.proto"),
         namespace_path.join("service.proto"),
         object_store_path.join("service.proto"),
         partition_template_path.join("template.proto"),
         predicate_path.join("predicate.proto"),
         querier_path.join("flight.proto"),
+        rollup_path.join("service.proto"),
         root.join("google/longrunning/operations.proto"),
         root.join("google/rpc/error_details.proto"),
         root.join("google/rpc/status.proto"),
@@ -192,6 +192,16 @@ pub mod influxdata {
             }
         }
 
+        pub mod rollup {
+            pub mod v1 {
+                include!(concat!(env!("OUT_DIR"), "/influxdata.iox.rollup.v1.rs"));
+                include!(concat!(
+                    env!("OUT_DIR"),
+                    "/influxdata.iox.rollup.v1.serde.rs"
+                ));
+            }
+        }
+
         pub mod schema {
             pub mod v1 {
                 include!(concat!(env!("OUT_DIR"), "/influxdata.iox.schema.v1.rs"));
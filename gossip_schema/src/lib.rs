@@ -211,6 +211,9 @@ mod tests {
             ),
             max_columns_per_table: 1,
             max_tables: 2,
+            max_write_bytes_per_second: None,
+            max_write_lines_per_second: None,
+            max_concurrent_queries: None,
             retention_period_ns: Some(1234),
         });
 
@@ -242,6 +245,9 @@ mod tests {
             partition_template: None,
             max_columns_per_table: 1,
             max_tables: 2,
+            max_write_bytes_per_second: None,
+            max_write_lines_per_second: None,
+            max_concurrent_queries: None,
             retention_period_ns: Some(1234),
         });
 
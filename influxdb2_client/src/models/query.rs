@@ -14,9 +14,12 @@ pub struct Query {
     pub r#extern: Option<File>,
     /// Query script to execute.
     pub query: String,
-    /// The type of query. Must be \"flux\".
+    /// The type of query. Must be \"flux\", unless querying IOx.
     #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
     pub r#type: Option<Type>,
+    /// The bucket to query, required by SQL and InfluxQL queries.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bucket: Option<String>,
     /// Dialect
     #[serde(skip_serializing_if = "Option::is_none")]
     pub dialect: Option<crate::models::ast::Dialect>,
@@ -51,12 +54,16 @@ impl Query {
     }
 }
 
-/// The type of query. Must be \"flux\".
+/// The type of query. Must be \"flux\", unless querying IOx.
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum Type {
     /// Query Type
     Flux,
+    /// An InfluxQL query, supported by IOx
+    Influxql,
+    /// A SQL query, supported by IOx
+    Sql,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
@@ -7,8 +7,12 @@
 //! [bind parameter]: https://docs.influxdata.com/influxdb/v1.8/tools/api/#bind-parameters
 //! [implementation]: https://github.com/influxdata/influxql/blob/df51a45762be9c1b578f01718fa92d286a843fe9/scanner.go#L57-L62
 
+use crate::expression::Expr;
 use crate::internal::ParseResult;
+use crate::literal::Literal;
+use crate::statement::Statement;
 use crate::string::double_quoted_string;
+use crate::visit_mut::{Recursion, VisitableMut, VisitorMut};
 use crate::{impl_tuple_clause, write_quoted_string};
 use nom::branch::alt;
 use nom::bytes::complete::tag;
@@ -16,6 +20,7 @@ use nom::character::complete::{alphanumeric1, char};
 use nom::combinator::{map, recognize};
 use nom::multi::many1_count;
 use nom::sequence::preceded;
+use std::collections::HashMap;
 use std::fmt;
 use std::fmt::{Display, Formatter, Write};
 
@@ -56,9 +61,52 @@ pub(crate) fn parameter(i: &str) -> ParseResult<&str, BindParameter> {
     )(i)
 }
 
+/// An error returned when a statement references a bind parameter that has
+/// no value.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MissingBindParameter(pub BindParameter);
+
+impl Display for MissingBindParameter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "missing value for bind parameter {}", self.0)
+    }
+}
+
+impl std::error::Error for MissingBindParameter {}
+
+/// Replace the bind parameters of `statement` with the values of the same
+/// name in `params`.
+///
+/// The values are substituted as literals of the syntax tree, rather than into
+/// the text of the query, so cannot alter the structure of the statement.
+pub fn replace_bind_parameters(
+    statement: &mut Statement,
+    params: &HashMap<String, Literal>,
+) -> Result<(), MissingBindParameter> {
+    struct Replace<'a>(&'a HashMap<String, Literal>);
+
+    impl<'a> VisitorMut for Replace<'a> {
+        type Error = MissingBindParameter;
+
+        fn pre_visit_expr(&mut self, n: &mut Expr) -> Result<Recursion, Self::Error> {
+            if let Expr::BindParameter(param) = n {
+                let value = self
+                    .0
+                    .get(param.as_str())
+                    .ok_or_else(|| MissingBindParameter(param.clone()))?;
+                *n = Expr::Literal(value.clone());
+            }
+            Ok(Recursion::Continue)
+        }
+    }
+
+    statement.accept(&mut Replace(params))
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::parse_statements;
 
     #[test]
     fn test_parameter() {
@@ -104,4 +152,39 @@ mod test {
         let got = BindParameter("quick_draw".into()).to_string();
         assert_eq!(got, "$quick_draw");
     }
+
+    #[test]
+    fn test_replace_bind_parameters() {
+        let replace = |q: &str| {
+            let mut statement = parse_statements(q).unwrap().pop().unwrap();
+            let params = HashMap::from([
+                ("host".to_string(), Literal::String("server'01".into())),
+                ("min value".to_string(), Literal::Integer(5)),
+            ]);
+            replace_bind_parameters(&mut statement, &params).map(|_| statement.to_string())
+        };
+
+        assert_eq!(
+            replace(r#"SELECT a FROM cpu WHERE host = $host AND a > $"min value""#).unwrap(),
+            r#"SELECT a FROM cpu WHERE host = 'server\'01' AND a > 5"#
+        );
+
+        // Parameters are replaced in subqueries and function calls
+        assert_eq!(
+            replace("SELECT max(a) FROM (SELECT a FROM cpu WHERE host = $host) WHERE a > $host")
+                .unwrap(),
+            r#"SELECT max(a) FROM (SELECT a FROM cpu WHERE host = 'server\'01') WHERE a > 'server\'01'"#
+        );
+
+        // ┌─────────────────────────────┐
+        // │       Fallible tests        │
+        // └─────────────────────────────┘
+
+        assert_eq!(
+            replace("SELECT a FROM cpu WHERE host = $region")
+                .unwrap_err()
+                .to_string(),
+            "missing value for bind parameter $region"
+        );
+    }
 }
@@ -25,7 +25,7 @@ use nom::branch::alt;
 use nom::bytes::complete::tag;
 use nom::character::complete::char;
 use nom::combinator::{map, opt, value};
-use nom::sequence::{delimited, pair, preceded, tuple};
+use nom::sequence::{delimited, pair, preceded, terminated, tuple};
 use nom::Offset;
 use std::fmt;
 use std::fmt::{Display, Formatter, Write};
@@ -37,6 +37,9 @@ pub struct SelectStatement {
     /// Expressions returned by the selection.
     pub fields: FieldList,
 
+    /// The measurement the selection is written to, if specified.
+    pub into: Option<IntoClause>,
+
     /// A list of measurements or subqueries used as the source data for the selection.
     pub from: FromMeasurementClause,
 
@@ -84,7 +87,13 @@ impl SelectStatement {
 
 impl Display for SelectStatement {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(f, "SELECT {} {}", self.fields, self.from)?;
+        write!(f, "SELECT {}", self.fields)?;
+
+        if let Some(into) = &self.into {
+            write!(f, " {into}")?;
+        }
+
+        write!(f, " {}", self.from)?;
 
         if let Some(where_clause) = &self.condition {
             write!(f, " {where_clause}")?;
@@ -133,6 +142,7 @@ pub(crate) fn select_statement(i: &str) -> ParseResult<&str, SelectStatement> {
             _, // SELECT
             _, // whitespace
             fields,
+            into,
             from,
             condition,
             group_by,
@@ -148,6 +158,7 @@ pub(crate) fn select_statement(i: &str) -> ParseResult<&str, SelectStatement> {
         keyword("SELECT"),
         ws0,
         field_list,
+        opt(preceded(ws0, into_clause)),
         preceded(ws0, from_clause),
         opt(preceded(ws0, where_clause)),
         opt(preceded(ws0, group_by_clause)),
@@ -164,6 +175,7 @@ pub(crate) fn select_statement(i: &str) -> ParseResult<&str, SelectStatement> {
         remaining,
         SelectStatement {
             fields,
+            into,
             from,
             condition,
             group_by,
@@ -178,6 +190,102 @@ pub(crate) fn select_statement(i: &str) -> ParseResult<&str, SelectStatement> {
     ))
 }
 
+/// Represents the measurement of an `INTO` clause.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IntoMeasurement {
+    /// The selection is written to the named measurement.
+    Name(Identifier),
+
+    /// The selection is written to the measurement it was read from,
+    /// expressed as `:MEASUREMENT`.
+    Backreference,
+}
+
+impl Display for IntoMeasurement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Name(name) => write!(f, "{name}"),
+            Self::Backreference => f.write_str(":MEASUREMENT"),
+        }
+    }
+}
+
+/// Represents the `INTO` clause of a `SELECT` statement, which writes the
+/// selection to a measurement rather than returning it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IntoClause {
+    /// An optional database name.
+    pub database: Option<Identifier>,
+
+    /// An optional retention policy.
+    pub retention_policy: Option<Identifier>,
+
+    /// The measurement written to.
+    pub measurement: IntoMeasurement,
+}
+
+impl Display for IntoClause {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("INTO ")?;
+        match (&self.database, &self.retention_policy) {
+            (None, None) => {}
+            (Some(db), None) => write!(f, "{db}..")?,
+            (None, Some(rp)) => write!(f, "{rp}.")?,
+            (Some(db), Some(rp)) => write!(f, "{db}.{rp}.")?,
+        }
+        write!(f, "{}", self.measurement)
+    }
+}
+
+/// Parse an `INTO` clause.
+///
+/// ```text
+/// into_clause      ::= "INTO" ( db_name "." policy_name? "." | policy_name "." )? into_measurement
+/// into_measurement ::= identifier | ":MEASUREMENT"
+/// ```
+fn into_clause(i: &str) -> ParseResult<&str, IntoClause> {
+    let (remaining_input, (opt_db_rp, measurement)) = preceded(
+        pair(keyword("INTO"), ws1),
+        expect(
+            "invalid INTO clause, expected identifier or :MEASUREMENT",
+            pair(
+                opt(alt((
+                    // database "." retention_policy "."
+                    map(
+                        pair(
+                            terminated(identifier, tag(".")),
+                            terminated(identifier, tag(".")),
+                        ),
+                        |(db, rp)| (Some(db), Some(rp)),
+                    ),
+                    // database ".."
+                    map(terminated(identifier, tag("..")), |db| (Some(db), None)),
+                    // retention_policy "."
+                    map(terminated(identifier, tag(".")), |rp| (None, Some(rp))),
+                ))),
+                alt((
+                    value(
+                        IntoMeasurement::Backreference,
+                        preceded(char(':'), keyword("MEASUREMENT")),
+                    ),
+                    map(identifier, IntoMeasurement::Name),
+                )),
+            ),
+        ),
+    )(i)?;
+
+    let (database, retention_policy) = opt_db_rp.unwrap_or_default();
+
+    Ok((
+        remaining_input,
+        IntoClause {
+            database,
+            retention_policy,
+            measurement,
+        },
+    ))
+}
+
 /// Represents a single measurement selection for a `FROM` clause.
 #[derive(Clone, Debug, PartialEq)]
 pub enum MeasurementSelection {
@@ -823,6 +931,14 @@ mod test {
         let (_, got) = select_statement("SELECT value FROM foo").unwrap();
         assert_eq!(got.to_string(), "SELECT value FROM foo");
 
+        let (_, got) =
+            select_statement("SELECT mean(value) INTO db.rp.:MEASUREMENT FROM /foo/ GROUP BY *")
+                .unwrap();
+        assert_eq!(
+            got.to_string(),
+            "SELECT mean(value) INTO db.rp.:MEASUREMENT FROM /foo/ GROUP BY *"
+        );
+
         let (_, got) =
             select_statement(r#"SELECT f1,/f2/, f3 AS "a field" FROM foo WHERE host =~ /c1/"#)
                 .unwrap();
@@ -1146,6 +1262,45 @@ mod test {
         assert_expect_error!(field_list("."), "invalid SELECT statement, expected field");
     }
 
+    #[test]
+    fn test_into_clause() {
+        let (_, got) = into_clause("INTO cpu_1h").unwrap();
+        assert_eq!(got.database, None);
+        assert_eq!(got.retention_policy, None);
+        assert_eq!(got.measurement, IntoMeasurement::Name("cpu_1h".into()));
+        assert_eq!(got.to_string(), "INTO cpu_1h");
+
+        let (_, got) = into_clause("INTO db..cpu_1h").unwrap();
+        assert_eq!(got.database, Some("db".into()));
+        assert_eq!(got.retention_policy, None);
+        assert_eq!(got.to_string(), "INTO db..cpu_1h");
+
+        let (_, got) = into_clause("INTO rp.cpu_1h").unwrap();
+        assert_eq!(got.database, None);
+        assert_eq!(got.retention_policy, Some("rp".into()));
+        assert_eq!(got.to_string(), "INTO rp.cpu_1h");
+
+        let (_, got) = into_clause("INTO db.rp.:MEASUREMENT").unwrap();
+        assert_eq!(got.database, Some("db".into()));
+        assert_eq!(got.retention_policy, Some("rp".into()));
+        assert_eq!(got.measurement, IntoMeasurement::Backreference);
+        assert_eq!(got.to_string(), "INTO db.rp.:MEASUREMENT");
+
+        let (_, got) = into_clause("into :measurement").unwrap();
+        assert_eq!(got.measurement, IntoMeasurement::Backreference);
+
+        // Fallible cases
+
+        assert_expect_error!(
+            into_clause("INTO /cpu/"),
+            "invalid INTO clause, expected identifier or :MEASUREMENT"
+        );
+        assert_expect_error!(
+            into_clause("INTO :foo"),
+            "invalid INTO clause, expected identifier or :MEASUREMENT"
+        );
+    }
+
     #[test]
     fn test_measurement_selection() {
         // measurement name expression
@@ -7,9 +7,11 @@ use crate::identifier::{identifier, Identifier};
 use crate::impl_tuple_clause;
 use crate::internal::{expect, ParseResult};
 use crate::keywords::keyword;
+use crate::show_cardinality::show_cardinality;
 use crate::show_field_keys::show_field_keys;
 use crate::show_measurements::show_measurements;
 use crate::show_retention_policies::show_retention_policies;
+use crate::show_series::show_series;
 use crate::show_tag_keys::show_tag_keys;
 use crate::show_tag_values::show_tag_values;
 use crate::statement::Statement;
@@ -23,8 +25,10 @@ pub(crate) fn show_statement(i: &str) -> ParseResult<&str, Statement> {
     preceded(
         pair(keyword("SHOW"), ws1),
         expect(
-            "invalid SHOW statement, expected DATABASES, FIELD, MEASUREMENTS, TAG, or RETENTION following SHOW",
+            "invalid SHOW statement, expected DATABASES, FIELD, MEASUREMENT, MEASUREMENTS, SERIES, TAG, or RETENTION following SHOW",
             alt((
+                // SHOW SERIES CARDINALITY, SHOW MEASUREMENT CARDINALITY, etc
+                map(show_cardinality, |s| Statement::ShowCardinality(Box::new(s))),
                 // SHOW DATABASES
                 map(show_databases, |s| Statement::ShowDatabases(Box::new(s))),
                 // SHOW FIELD KEYS
@@ -37,6 +41,8 @@ pub(crate) fn show_statement(i: &str) -> ParseResult<&str, Statement> {
                 map(show_retention_policies, |s| {
                     Statement::ShowRetentionPolicies(Box::new(s))
                 }),
+                // SHOW SERIES
+                map(show_series, |s| Statement::ShowSeries(Box::new(s))),
                 // SHOW TAG
                 show_tag,
             )),
@@ -123,6 +129,27 @@ mod test {
         let (_, got) = show_statement("SHOW TAG VALUES WITH KEY = some_key").unwrap();
         assert_eq!(got.to_string(), "SHOW TAG VALUES WITH KEY = some_key");
 
+        let (_, got) = show_statement("SHOW SERIES").unwrap();
+        assert_eq!(got.to_string(), "SHOW SERIES");
+
+        let (_, got) = show_statement("SHOW SERIES EXACT CARDINALITY").unwrap();
+        assert_eq!(got.to_string(), "SHOW SERIES EXACT CARDINALITY");
+
+        let (_, got) = show_statement("SHOW MEASUREMENT CARDINALITY").unwrap();
+        assert_eq!(got.to_string(), "SHOW MEASUREMENT CARDINALITY");
+
+        let (_, got) = show_statement("SHOW TAG KEY CARDINALITY").unwrap();
+        assert_eq!(got.to_string(), "SHOW TAG KEY CARDINALITY");
+
+        let (_, got) = show_statement("SHOW TAG VALUES CARDINALITY WITH KEY = some_key").unwrap();
+        assert_eq!(
+            got.to_string(),
+            "SHOW TAG VALUES CARDINALITY WITH KEY = some_key"
+        );
+
+        let (_, got) = show_statement("SHOW FIELD KEY CARDINALITY").unwrap();
+        assert_eq!(got.to_string(), "SHOW FIELD KEY CARDINALITY");
+
         // Fallible cases
 
         assert_expect_error!(
@@ -133,7 +160,7 @@ mod test {
         // Unsupported SHOW
         assert_expect_error!(
             show_statement("SHOW FOO"),
-            "invalid SHOW statement, expected DATABASES, FIELD, MEASUREMENTS, TAG, or RETENTION following SHOW"
+            "invalid SHOW statement, expected DATABASES, FIELD, MEASUREMENT, MEASUREMENTS, SERIES, TAG, or RETENTION following SHOW"
         );
     }
 }
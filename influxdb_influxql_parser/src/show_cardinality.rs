@@ -0,0 +1,243 @@
+//! Types and parsers for the `SHOW ... CARDINALITY` statements, which include
+//! [`SHOW SERIES CARDINALITY`][sql], `SHOW MEASUREMENT CARDINALITY`,
+//! `SHOW TAG KEY CARDINALITY`, `SHOW TAG VALUES CARDINALITY` and
+//! `SHOW FIELD KEY CARDINALITY`.
+//!
+//! [sql]: https://docs.influxdata.com/influxdb/v1.8/query_language/spec/#show-series-cardinality
+
+use crate::common::{
+    limit_clause, offset_clause, where_clause, ws1, LimitClause, OffsetClause, WhereClause,
+};
+use crate::internal::{expect, ParseResult};
+use crate::keywords::keyword;
+use crate::show::{on_clause, OnClause};
+use crate::show_tag_values::{with_key_clause, WithKeyClause};
+use crate::simple_from_clause::{show_from_clause, ShowFromClause};
+use nom::branch::alt;
+use nom::combinator::{map, opt, value};
+use nom::sequence::{preceded, tuple};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+/// Identifies what is counted by a `SHOW ... CARDINALITY` statement.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CardinalityKind {
+    /// Represents `SHOW SERIES CARDINALITY`.
+    Series,
+    /// Represents `SHOW MEASUREMENT CARDINALITY`.
+    Measurement,
+    /// Represents `SHOW TAG KEY CARDINALITY`.
+    TagKey,
+    /// Represents `SHOW TAG VALUES CARDINALITY`.
+    TagValues,
+    /// Represents `SHOW FIELD KEY CARDINALITY`.
+    FieldKey,
+}
+
+impl Display for CardinalityKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Series => "SERIES",
+            Self::Measurement => "MEASUREMENT",
+            Self::TagKey => "TAG KEY",
+            Self::TagValues => "TAG VALUES",
+            Self::FieldKey => "FIELD KEY",
+        })
+    }
+}
+
+/// Represents a `SHOW ... CARDINALITY` InfluxQL statement.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ShowCardinalityStatement {
+    /// What is counted by the statement.
+    pub kind: CardinalityKind,
+
+    /// `true` if the `EXACT` keyword was specified, requesting an
+    /// exact count rather than an estimate.
+    pub exact: bool,
+
+    /// The name of the database to query. If `None`, a default
+    /// database will be used.
+    pub database: Option<OnClause>,
+
+    /// The measurement or measurements to restrict what is counted.
+    pub from: Option<ShowFromClause>,
+
+    /// Represents the `WITH KEY` clause, which is present only for
+    /// [`CardinalityKind::TagValues`].
+    pub with_key: Option<WithKeyClause>,
+
+    /// A conditional expression to filter what is counted.
+    pub condition: Option<WhereClause>,
+
+    /// A value to restrict the number of rows returned.
+    pub limit: Option<LimitClause>,
+
+    /// A value to specify an offset to start retrieving rows.
+    pub offset: Option<OffsetClause>,
+}
+
+impl Display for ShowCardinalityStatement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "SHOW {}", self.kind)?;
+
+        if self.exact {
+            write!(f, " EXACT")?;
+        }
+
+        write!(f, " CARDINALITY")?;
+
+        if let Some(ref on_clause) = self.database {
+            write!(f, " {on_clause}")?;
+        }
+
+        if let Some(ref from_clause) = self.from {
+            write!(f, " {from_clause}")?;
+        }
+
+        if let Some(ref with_key) = self.with_key {
+            write!(f, " {with_key}")?;
+        }
+
+        if let Some(ref where_clause) = self.condition {
+            write!(f, " {where_clause}")?;
+        }
+
+        if let Some(ref limit) = self.limit {
+            write!(f, " {limit}")?;
+        }
+
+        if let Some(ref offset) = self.offset {
+            write!(f, " {offset}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Parse the `SERIES`, `MEASUREMENT`, `TAG KEY`, `TAG VALUES` or `FIELD KEY`
+/// tokens of a `SHOW ... CARDINALITY` statement.
+fn cardinality_kind(i: &str) -> ParseResult<&str, CardinalityKind> {
+    alt((
+        value(CardinalityKind::Series, keyword("SERIES")),
+        value(CardinalityKind::Measurement, keyword("MEASUREMENT")),
+        value(
+            CardinalityKind::TagKey,
+            tuple((keyword("TAG"), ws1, keyword("KEY"))),
+        ),
+        value(
+            CardinalityKind::TagValues,
+            tuple((keyword("TAG"), ws1, keyword("VALUES"))),
+        ),
+        value(
+            CardinalityKind::FieldKey,
+            tuple((keyword("FIELD"), ws1, keyword("KEY"))),
+        ),
+    ))(i)
+}
+
+/// Parse a `SHOW ... CARDINALITY` statement, starting from the token
+/// following `SHOW`.
+pub(crate) fn show_cardinality(i: &str) -> ParseResult<&str, ShowCardinalityStatement> {
+    let (i, (kind, exact, _)) = tuple((
+        cardinality_kind,
+        map(opt(preceded(ws1, keyword("EXACT"))), |v| v.is_some()),
+        preceded(ws1, keyword("CARDINALITY")),
+    ))(i)?;
+
+    let (i, (database, from)) = tuple((
+        opt(preceded(ws1, on_clause)),
+        opt(preceded(ws1, show_from_clause)),
+    ))(i)?;
+
+    let (i, with_key) = if kind == CardinalityKind::TagValues {
+        map(
+            expect(
+                "invalid SHOW TAG VALUES CARDINALITY statement, expected WITH KEY clause",
+                preceded(ws1, with_key_clause),
+            ),
+            Some,
+        )(i)?
+    } else {
+        (i, None)
+    };
+
+    let (remaining_input, (condition, limit, offset)) = tuple((
+        opt(preceded(ws1, where_clause)),
+        opt(preceded(ws1, limit_clause)),
+        opt(preceded(ws1, offset_clause)),
+    ))(i)?;
+
+    Ok((
+        remaining_input,
+        ShowCardinalityStatement {
+            kind,
+            exact,
+            database,
+            from,
+            with_key,
+            condition,
+            limit,
+            offset,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::assert_expect_error;
+
+    #[test]
+    fn test_show_cardinality() {
+        let (_, got) = show_cardinality("SERIES CARDINALITY").unwrap();
+        assert_eq!(got.kind, CardinalityKind::Series);
+        assert!(!got.exact);
+        assert_eq!(got.to_string(), "SHOW SERIES CARDINALITY");
+
+        let (_, got) = show_cardinality("SERIES EXACT CARDINALITY").unwrap();
+        assert!(got.exact);
+        assert_eq!(got.to_string(), "SHOW SERIES EXACT CARDINALITY");
+
+        let (_, got) = show_cardinality("MEASUREMENT CARDINALITY ON db").unwrap();
+        assert_eq!(got.kind, CardinalityKind::Measurement);
+        assert_eq!(got.to_string(), "SHOW MEASUREMENT CARDINALITY ON db");
+
+        let (_, got) = show_cardinality("TAG KEY EXACT CARDINALITY FROM /cpu/").unwrap();
+        assert_eq!(got.kind, CardinalityKind::TagKey);
+        assert_eq!(got.to_string(), "SHOW TAG KEY EXACT CARDINALITY FROM /cpu/");
+
+        let (_, got) = show_cardinality("TAG VALUES CARDINALITY WITH KEY = host").unwrap();
+        assert_eq!(got.kind, CardinalityKind::TagValues);
+        assert_eq!(
+            got.to_string(),
+            "SHOW TAG VALUES CARDINALITY WITH KEY = host"
+        );
+
+        let (_, got) = show_cardinality("FIELD KEY CARDINALITY").unwrap();
+        assert_eq!(got.kind, CardinalityKind::FieldKey);
+        assert_eq!(got.to_string(), "SHOW FIELD KEY CARDINALITY");
+
+        // all optional clauses
+        let (_, got) = show_cardinality(
+            "TAG VALUES EXACT CARDINALITY ON db FROM cpu WITH KEY =~ /host/ WHERE region = 'west' LIMIT 1 OFFSET 2",
+        )
+        .unwrap();
+        assert_eq!(
+            got.to_string(),
+            "SHOW TAG VALUES EXACT CARDINALITY ON db FROM cpu WITH KEY =~ /host/ WHERE region = 'west' LIMIT 1 OFFSET 2"
+        );
+
+        // Fallible cases
+
+        // Not a cardinality statement
+        show_cardinality("SERIES").unwrap_err();
+        show_cardinality("MEASUREMENTS").unwrap_err();
+        show_cardinality("TAG KEYS").unwrap_err();
+
+        assert_expect_error!(
+            show_cardinality("TAG VALUES CARDINALITY"),
+            "invalid SHOW TAG VALUES CARDINALITY statement, expected WITH KEY clause"
+        );
+    }
+}
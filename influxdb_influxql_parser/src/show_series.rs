@@ -0,0 +1,139 @@
+//! Types and parsers for the [`SHOW SERIES`][sql] statement.
+//!
+//! [sql]: https://docs.influxdata.com/influxdb/v1.8/query_language/explore-schema/#show-series
+
+use crate::common::{
+    limit_clause, offset_clause, where_clause, ws1, LimitClause, OffsetClause, WhereClause,
+};
+use crate::internal::ParseResult;
+use crate::keywords::keyword;
+use crate::show::{on_clause, OnClause};
+use crate::simple_from_clause::{show_from_clause, ShowFromClause};
+use nom::combinator::opt;
+use nom::sequence::{preceded, tuple};
+use std::fmt;
+use std::fmt::Formatter;
+
+/// Represents a `SHOW SERIES` InfluxQL statement.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ShowSeriesStatement {
+    /// The name of the database to query. If `None`, a default
+    /// database will be used.
+    pub database: Option<OnClause>,
+
+    /// The measurement or measurements to restrict which series
+    /// are retrieved.
+    pub from: Option<ShowFromClause>,
+
+    /// A conditional expression to filter the series.
+    pub condition: Option<WhereClause>,
+
+    /// A value to restrict the number of series returned.
+    pub limit: Option<LimitClause>,
+
+    /// A value to specify an offset to start retrieving series.
+    pub offset: Option<OffsetClause>,
+}
+
+impl fmt::Display for ShowSeriesStatement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "SHOW SERIES")?;
+
+        if let Some(ref on_clause) = self.database {
+            write!(f, " {on_clause}")?;
+        }
+
+        if let Some(ref expr) = self.from {
+            write!(f, " {expr}")?;
+        }
+
+        if let Some(ref cond) = self.condition {
+            write!(f, " {cond}")?;
+        }
+
+        if let Some(ref limit) = self.limit {
+            write!(f, " {limit}")?;
+        }
+
+        if let Some(ref offset) = self.offset {
+            write!(f, " {offset}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Parse a `SHOW SERIES` statement, starting from the `SERIES` token.
+pub(crate) fn show_series(i: &str) -> ParseResult<&str, ShowSeriesStatement> {
+    let (
+        remaining_input,
+        (
+            _, // "SERIES"
+            database,
+            from,
+            condition,
+            limit,
+            offset,
+        ),
+    ) = tuple((
+        keyword("SERIES"),
+        opt(preceded(ws1, on_clause)),
+        opt(preceded(ws1, show_from_clause)),
+        opt(preceded(ws1, where_clause)),
+        opt(preceded(ws1, limit_clause)),
+        opt(preceded(ws1, offset_clause)),
+    ))(i)?;
+
+    Ok((
+        remaining_input,
+        ShowSeriesStatement {
+            database,
+            from,
+            condition,
+            limit,
+            offset,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_show_series() {
+        // No optional clauses
+        let (_, got) = show_series("SERIES").unwrap();
+        assert_eq!(got.to_string(), "SHOW SERIES");
+
+        let (_, got) = show_series("SERIES ON db").unwrap();
+        assert_eq!(got.to_string(), "SHOW SERIES ON db");
+
+        // measurement selection using name
+        let (_, got) = show_series("SERIES FROM db..foo").unwrap();
+        assert_eq!(got.to_string(), "SHOW SERIES FROM db..foo");
+
+        // measurement selection using regex
+        let (_, got) = show_series("SERIES FROM /foo/").unwrap();
+        assert_eq!(got.to_string(), "SHOW SERIES FROM /foo/");
+
+        let (_, got) = show_series("SERIES WHERE foo = 'bar'").unwrap();
+        assert_eq!(got.to_string(), "SHOW SERIES WHERE foo = 'bar'");
+
+        let (_, got) = show_series("SERIES LIMIT 1").unwrap();
+        assert_eq!(got.to_string(), "SHOW SERIES LIMIT 1");
+
+        let (_, got) = show_series("SERIES OFFSET 2").unwrap();
+        assert_eq!(got.to_string(), "SHOW SERIES OFFSET 2");
+
+        // all optional clauses
+        let (_, got) =
+            show_series("SERIES ON db FROM /foo/ WHERE foo = 'bar' LIMIT 1 OFFSET 2").unwrap();
+        assert_eq!(
+            got.to_string(),
+            "SHOW SERIES ON db FROM /foo/ WHERE foo = 'bar' LIMIT 1 OFFSET 2"
+        );
+
+        // Fallible cases are tested by the various combinator functions
+    }
+}
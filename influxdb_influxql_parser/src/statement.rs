@@ -7,9 +7,11 @@ use crate::explain::{explain_statement, ExplainStatement};
 use crate::internal::ParseResult;
 use crate::select::{select_statement, SelectStatement};
 use crate::show::{show_statement, ShowDatabasesStatement};
+use crate::show_cardinality::ShowCardinalityStatement;
 use crate::show_field_keys::ShowFieldKeysStatement;
 use crate::show_measurements::ShowMeasurementsStatement;
 use crate::show_retention_policies::ShowRetentionPoliciesStatement;
+use crate::show_series::ShowSeriesStatement;
 use crate::show_tag_keys::ShowTagKeysStatement;
 use crate::show_tag_values::ShowTagValuesStatement;
 use nom::branch::alt;
@@ -29,12 +31,16 @@ pub enum Statement {
     Explain(Box<ExplainStatement>),
     /// Represents a `SELECT` statement.
     Select(Box<SelectStatement>),
+    /// Represents a `SHOW ... CARDINALITY` statement.
+    ShowCardinality(Box<ShowCardinalityStatement>),
     /// Represents a `SHOW DATABASES` statement.
     ShowDatabases(Box<ShowDatabasesStatement>),
     /// Represents a `SHOW MEASUREMENTS` statement.
     ShowMeasurements(Box<ShowMeasurementsStatement>),
     /// Represents a `SHOW RETENTION POLICIES` statement.
     ShowRetentionPolicies(Box<ShowRetentionPoliciesStatement>),
+    /// Represents a `SHOW SERIES` statement.
+    ShowSeries(Box<ShowSeriesStatement>),
     /// Represents a `SHOW TAG KEYS` statement.
     ShowTagKeys(Box<ShowTagKeysStatement>),
     /// Represents a `SHOW TAG VALUES` statement.
@@ -51,9 +57,11 @@ impl Display for Statement {
             Self::DropMeasurement(s) => Display::fmt(s, f),
             Self::Explain(s) => Display::fmt(s, f),
             Self::Select(s) => Display::fmt(s, f),
+            Self::ShowCardinality(s) => Display::fmt(s, f),
             Self::ShowDatabases(s) => Display::fmt(s, f),
             Self::ShowMeasurements(s) => Display::fmt(s, f),
             Self::ShowRetentionPolicies(s) => Display::fmt(s, f),
+            Self::ShowSeries(s) => Display::fmt(s, f),
             Self::ShowTagKeys(s) => Display::fmt(s, f),
             Self::ShowTagValues(s) => Display::fmt(s, f),
             Self::ShowFieldKeys(s) => Display::fmt(s, f),
@@ -37,16 +37,18 @@ use crate::expression::conditional::ConditionalExpression;
 use crate::expression::{Binary, Call, ConditionalBinary, VarRef};
 use crate::literal::Literal;
 use crate::select::{
-    Dimension, Field, FieldList, FillClause, FromMeasurementClause, GroupByClause,
+    Dimension, Field, FieldList, FillClause, FromMeasurementClause, GroupByClause, IntoClause,
     MeasurementSelection, SLimitClause, SOffsetClause, SelectStatement, TimeDimension,
     TimeZoneClause,
 };
 use crate::show::{OnClause, ShowDatabasesStatement};
+use crate::show_cardinality::ShowCardinalityStatement;
 use crate::show_field_keys::ShowFieldKeysStatement;
 use crate::show_measurements::{
     ExtendedOnClause, ShowMeasurementsStatement, WithMeasurementClause,
 };
 use crate::show_retention_policies::ShowRetentionPoliciesStatement;
+use crate::show_series::ShowSeriesStatement;
 use crate::show_tag_keys::ShowTagKeysStatement;
 use crate::show_tag_values::{ShowTagValuesStatement, WithKeyClause};
 use crate::simple_from_clause::{DeleteFromClause, ShowFromClause};
@@ -176,6 +178,22 @@ pub trait Visitor: Sized {
         Ok(self)
     }
 
+    /// Invoked before any children of the `SHOW ... CARDINALITY` statement are visited.
+    fn pre_visit_show_cardinality_statement(
+        self,
+        _n: &ShowCardinalityStatement,
+    ) -> Result<Recursion<Self>, Self::Error> {
+        Ok(Continue(self))
+    }
+
+    /// Invoked after all children of the `SHOW ... CARDINALITY` statement are visited.
+    fn post_visit_show_cardinality_statement(
+        self,
+        _n: &ShowCardinalityStatement,
+    ) -> Result<Self, Self::Error> {
+        Ok(self)
+    }
+
     /// Invoked before any children of the `SHOW DATABASES` statement are visited.
     fn pre_visit_show_databases_statement(
         self,
@@ -224,6 +242,22 @@ pub trait Visitor: Sized {
         Ok(self)
     }
 
+    /// Invoked before any children of the `SHOW SERIES` statement are visited.
+    fn pre_visit_show_series_statement(
+        self,
+        _n: &ShowSeriesStatement,
+    ) -> Result<Recursion<Self>, Self::Error> {
+        Ok(Continue(self))
+    }
+
+    /// Invoked after all children of the `SHOW SERIES` statement are visited.
+    fn post_visit_show_series_statement(
+        self,
+        _n: &ShowSeriesStatement,
+    ) -> Result<Self, Self::Error> {
+        Ok(self)
+    }
+
     /// Invoked before any children of the `SHOW TAG KEYS` statement are visited.
     fn pre_visit_show_tag_keys_statement(
         self,
@@ -482,6 +516,16 @@ pub trait Visitor: Sized {
         Ok(self)
     }
 
+    /// Invoked before any children of the `INTO` clause are visited.
+    fn pre_visit_select_into_clause(self, _n: &IntoClause) -> Result<Recursion<Self>, Self::Error> {
+        Ok(Continue(self))
+    }
+
+    /// Invoked after all children of the `INTO` clause are visited.
+    fn post_visit_select_into_clause(self, _n: &IntoClause) -> Result<Self, Self::Error> {
+        Ok(self)
+    }
+
     /// Invoked before any children of a `TZ` clause are visited.
     fn pre_visit_timezone_clause(
         self,
@@ -617,9 +661,11 @@ impl Visitable for Statement {
             Self::DropMeasurement(s) => s.accept(visitor),
             Self::Explain(s) => s.accept(visitor),
             Self::Select(s) => s.accept(visitor),
+            Self::ShowCardinality(s) => s.accept(visitor),
             Self::ShowDatabases(s) => s.accept(visitor),
             Self::ShowMeasurements(s) => s.accept(visitor),
             Self::ShowRetentionPolicies(s) => s.accept(visitor),
+            Self::ShowSeries(s) => s.accept(visitor),
             Self::ShowTagKeys(s) => s.accept(visitor),
             Self::ShowTagValues(s) => s.accept(visitor),
             Self::ShowFieldKeys(s) => s.accept(visitor),
@@ -734,6 +780,12 @@ impl Visitable for SelectStatement {
 
         let visitor = self.fields.accept(visitor)?;
 
+        let visitor = if let Some(into) = &self.into {
+            into.accept(visitor)
+        } else {
+            Ok(visitor)
+        }?;
+
         let visitor = self.from.accept(visitor)?;
 
         let visitor = if let Some(condition) = &self.condition {
@@ -794,6 +846,17 @@ impl Visitable for SelectStatement {
     }
 }
 
+impl Visitable for IntoClause {
+    fn accept<V: Visitor>(&self, visitor: V) -> Result<V, V::Error> {
+        let visitor = match visitor.pre_visit_select_into_clause(self)? {
+            Continue(visitor) => visitor,
+            Stop(visitor) => return Ok(visitor),
+        };
+
+        visitor.post_visit_select_into_clause(self)
+    }
+}
+
 impl Visitable for TimeZoneClause {
     fn accept<V: Visitor>(&self, visitor: V) -> Result<V, V::Error> {
         let visitor = match visitor.pre_visit_timezone_clause(self)? {
@@ -995,6 +1058,94 @@ impl Visitable for QualifiedMeasurementName {
     }
 }
 
+impl Visitable for ShowSeriesStatement {
+    fn accept<V: Visitor>(&self, visitor: V) -> Result<V, V::Error> {
+        let visitor = match visitor.pre_visit_show_series_statement(self)? {
+            Continue(visitor) => visitor,
+            Stop(visitor) => return Ok(visitor),
+        };
+
+        let visitor = if let Some(on_clause) = &self.database {
+            on_clause.accept(visitor)
+        } else {
+            Ok(visitor)
+        }?;
+
+        let visitor = if let Some(from) = &self.from {
+            from.accept(visitor)
+        } else {
+            Ok(visitor)
+        }?;
+
+        let visitor = if let Some(condition) = &self.condition {
+            condition.accept(visitor)
+        } else {
+            Ok(visitor)
+        }?;
+
+        let visitor = if let Some(limit) = &self.limit {
+            limit.accept(visitor)
+        } else {
+            Ok(visitor)
+        }?;
+
+        let visitor = if let Some(offset) = &self.offset {
+            offset.accept(visitor)
+        } else {
+            Ok(visitor)
+        }?;
+
+        visitor.post_visit_show_series_statement(self)
+    }
+}
+
+impl Visitable for ShowCardinalityStatement {
+    fn accept<V: Visitor>(&self, visitor: V) -> Result<V, V::Error> {
+        let visitor = match visitor.pre_visit_show_cardinality_statement(self)? {
+            Continue(visitor) => visitor,
+            Stop(visitor) => return Ok(visitor),
+        };
+
+        let visitor = if let Some(on_clause) = &self.database {
+            on_clause.accept(visitor)
+        } else {
+            Ok(visitor)
+        }?;
+
+        let visitor = if let Some(from) = &self.from {
+            from.accept(visitor)
+        } else {
+            Ok(visitor)
+        }?;
+
+        let visitor = if let Some(with_key) = &self.with_key {
+            with_key.accept(visitor)
+        } else {
+            Ok(visitor)
+        }?;
+
+        let visitor = if let Some(condition) = &self.condition {
+            condition.accept(visitor)
+        } else {
+            Ok(visitor)
+        }?;
+
+        let visitor = if let Some(limit) = &self.limit {
+            limit.accept(visitor)
+        } else {
+            Ok(visitor)
+        }?;
+
+        let visitor = if let Some(offset) = &self.offset {
+            offset.accept(visitor)
+        } else {
+            Ok(visitor)
+        }?;
+
+        visitor.post_visit_show_cardinality_statement(self)
+    }
+}
+
 impl Visitable for ShowTagKeysStatement {
     fn accept<V: Visitor>(&self, visitor: V) -> Result<V, V::Error> {
         let visitor = match visitor.pre_visit_show_tag_keys_statement(self)? {
@@ -1354,16 +1505,18 @@ mod test {
     use crate::expression::{Binary, Call, ConditionalBinary, VarRef};
     use crate::literal::Literal;
     use crate::select::{
-        Dimension, Field, FieldList, FillClause, FromMeasurementClause, GroupByClause,
+        Dimension, Field, FieldList, FillClause, FromMeasurementClause, GroupByClause, IntoClause,
         MeasurementSelection, SLimitClause, SOffsetClause, SelectStatement, TimeDimension,
         TimeZoneClause,
     };
     use crate::show::{OnClause, ShowDatabasesStatement};
+    use crate::show_cardinality::ShowCardinalityStatement;
     use crate::show_field_keys::ShowFieldKeysStatement;
     use crate::show_measurements::{
         ExtendedOnClause, ShowMeasurementsStatement, WithMeasurementClause,
     };
     use crate::show_retention_policies::ShowRetentionPoliciesStatement;
+    use crate::show_series::ShowSeriesStatement;
     use crate::show_tag_keys::ShowTagKeysStatement;
     use crate::show_tag_values::{ShowTagValuesStatement, WithKeyClause};
     use crate::simple_from_clause::{DeleteFromClause, ShowFromClause};
@@ -1413,12 +1566,14 @@ mod test {
         trace_visit!(drop_measurement_statement, DropMeasurementStatement);
         trace_visit!(explain_statement, ExplainStatement);
         trace_visit!(select_statement, SelectStatement);
+        trace_visit!(show_cardinality_statement, ShowCardinalityStatement);
         trace_visit!(show_databases_statement, ShowDatabasesStatement);
         trace_visit!(show_measurements_statement, ShowMeasurementsStatement);
         trace_visit!(
             show_retention_policies_statement,
             ShowRetentionPoliciesStatement
         );
+        trace_visit!(show_series_statement, ShowSeriesStatement);
         trace_visit!(show_tag_keys_statement, ShowTagKeysStatement);
         trace_visit!(show_tag_values_statement, ShowTagValuesStatement);
         trace_visit!(show_field_keys_statement, ShowFieldKeysStatement);
@@ -1440,6 +1595,7 @@ mod test {
         trace_visit!(offset_clause, OffsetClause);
         trace_visit!(slimit_clause, SLimitClause);
         trace_visit!(soffset_clause, SOffsetClause);
+        trace_visit!(select_into_clause, IntoClause);
         trace_visit!(timezone_clause, TimeZoneClause);
         trace_visit!(extended_on_clause, ExtendedOnClause);
         trace_visit!(on_clause, OnClause);
@@ -1497,6 +1653,7 @@ mod test {
             TZ('Australia/Hobart')
         "#
         ));
+        insta::assert_yaml_snapshot!(visit_statement!(r#"SELECT value INTO temp_copy FROM temp"#));
     }
 
     #[test]
@@ -1526,6 +1683,20 @@ mod test {
         insta::assert_yaml_snapshot!(visit_statement!("SHOW RETENTION POLICIES ON telegraf"));
     }
 
+    #[test]
+    fn test_show_series_statement() {
+        insta::assert_yaml_snapshot!(visit_statement!("SHOW SERIES"));
+        insta::assert_yaml_snapshot!(visit_statement!(
+            "SHOW SERIES ON telegraf FROM cpu WHERE host = \"west\" LIMIT 5 OFFSET 10"
+        ));
+    }
+
+    #[test]
+    fn test_show_cardinality_statement() {
+        insta::assert_yaml_snapshot!(visit_statement!("SHOW SERIES CARDINALITY"));
+        insta::assert_yaml_snapshot!(visit_statement!("SHOW TAG VALUES EXACT CARDINALITY ON telegraf FROM cpu WITH KEY = host WHERE host = \"west\" LIMIT 5 OFFSET 10"));
+    }
+
     #[test]
     fn test_show_tag_keys_statement() {
         insta::assert_yaml_snapshot!(visit_statement!("SHOW TAG KEYS"));
@@ -37,16 +37,18 @@ use crate::expression::conditional::ConditionalExpression;
 use crate::expression::{Binary, Call, ConditionalBinary, VarRef};
 use crate::literal::Literal;
 use crate::select::{
-    Dimension, Field, FieldList, FillClause, FromMeasurementClause, GroupByClause,
+    Dimension, Field, FieldList, FillClause, FromMeasurementClause, GroupByClause, IntoClause,
     MeasurementSelection, SLimitClause, SOffsetClause, SelectStatement, TimeDimension,
     TimeZoneClause,
 };
 use crate::show::{OnClause, ShowDatabasesStatement};
+use crate::show_cardinality::ShowCardinalityStatement;
 use crate::show_field_keys::ShowFieldKeysStatement;
 use crate::show_measurements::{
     ExtendedOnClause, ShowMeasurementsStatement, WithMeasurementClause,
 };
 use crate::show_retention_policies::ShowRetentionPoliciesStatement;
+use crate::show_series::ShowSeriesStatement;
 use crate::show_tag_keys::ShowTagKeysStatement;
 use crate::show_tag_values::{ShowTagValuesStatement, WithKeyClause};
 use crate::simple_from_clause::{DeleteFromClause, ShowFromClause};
@@ -183,6 +185,22 @@ pub trait VisitorMut: Sized {
         Ok(())
     }
 
+    /// Invoked before any children of the `SHOW ... CARDINALITY` statement are visited.
+    fn pre_visit_show_cardinality_statement(
+        &mut self,
+        _n: &mut ShowCardinalityStatement,
+    ) -> Result<Recursion, Self::Error> {
+        Ok(Continue)
+    }
+
+    /// Invoked after all children of the `SHOW ... CARDINALITY` statement are visited.
+    fn post_visit_show_cardinality_statement(
+        &mut self,
+        _n: &mut ShowCardinalityStatement,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
     /// Invoked before any children of the `SHOW DATABASES` statement are visited.
     fn pre_visit_show_databases_statement(
         &mut self,
@@ -231,6 +249,22 @@ pub trait VisitorMut: Sized {
         Ok(())
     }
 
+    /// Invoked before any children of the `SHOW SERIES` statement are visited.
+    fn pre_visit_show_series_statement(
+        &mut self,
+        _n: &mut ShowSeriesStatement,
+    ) -> Result<Recursion, Self::Error> {
+        Ok(Continue)
+    }
+
+    /// Invoked after all children of the `SHOW SERIES` statement are visited.
+    fn post_visit_show_series_statement(
+        &mut self,
+        _n: &mut ShowSeriesStatement,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
     /// Invoked before any children of the `SHOW TAG KEYS` statement are visited.
     fn pre_visit_show_tag_keys_statement(
         &mut self,
@@ -504,6 +538,19 @@ pub trait VisitorMut: Sized {
         Ok(())
     }
 
+    /// Invoked before any children of the `INTO` clause are visited.
+    fn pre_visit_select_into_clause(
+        &mut self,
+        _n: &mut IntoClause,
+    ) -> Result<Recursion, Self::Error> {
+        Ok(Continue)
+    }
+
+    /// Invoked after all children of the `INTO` clause are visited.
+    fn post_visit_select_into_clause(&mut self, _n: &mut IntoClause) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
     /// Invoked before any children of a `TZ` clause are visited.
     fn pre_visit_timezone_clause(
         &mut self,
@@ -647,9 +694,11 @@ impl VisitableMut for Statement {
             Self::DropMeasurement(s) => s.accept(visitor),
             Self::Explain(s) => s.accept(visitor),
             Self::Select(s) => s.accept(visitor),
+            Self::ShowCardinality(s) => s.accept(visitor),
             Self::ShowDatabases(s) => s.accept(visitor),
             Self::ShowMeasurements(s) => s.accept(visitor),
             Self::ShowRetentionPolicies(s) => s.accept(visitor),
+            Self::ShowSeries(s) => s.accept(visitor),
             Self::ShowTagKeys(s) => s.accept(visitor),
             Self::ShowTagValues(s) => s.accept(visitor),
             Self::ShowFieldKeys(s) => s.accept(visitor),
@@ -756,6 +805,10 @@ impl VisitableMut for SelectStatement {
 
         self.fields.accept(visitor)?;
 
+        if let Some(into) = &mut self.into {
+            into.accept(visitor)?;
+        }
+
         self.from.accept(visitor)?;
 
         if let Some(condition) = &mut self.condition {
@@ -798,6 +851,16 @@ impl VisitableMut for SelectStatement {
     }
 }
 
+impl VisitableMut for IntoClause {
+    fn accept<V: VisitorMut>(&mut self, visitor: &mut V) -> Result<(), V::Error> {
+        if let Stop = visitor.pre_visit_select_into_clause(self)? {
+            return Ok(());
+        };
+
+        visitor.post_visit_select_into_clause(self)
+    }
+}
+
 impl VisitableMut for TimeZoneClause {
     fn accept<V: VisitorMut>(&mut self, visitor: &mut V) -> Result<(), V::Error> {
         if let Stop = visitor.pre_visit_timezone_clause(self)? {
@@ -977,6 +1040,70 @@ impl VisitableMut for QualifiedMeasurementName {
     }
 }
 
+impl VisitableMut for ShowSeriesStatement {
+    fn accept<V: VisitorMut>(&mut self, visitor: &mut V) -> Result<(), V::Error> {
+        if let Stop = visitor.pre_visit_show_series_statement(self)? {
+            return Ok(());
+        };
+
+        if let Some(on_clause) = &mut self.database {
+            on_clause.accept(visitor)?;
+        }
+
+        if let Some(from) = &mut self.from {
+            from.accept(visitor)?;
+        }
+
+        if let Some(condition) = &mut self.condition {
+            condition.accept(visitor)?;
+        }
+
+        if let Some(limit) = &mut self.limit {
+            limit.accept(visitor)?;
+        }
+
+        if let Some(offset) = &mut self.offset {
+            offset.accept(visitor)?;
+        }
+
+        visitor.post_visit_show_series_statement(self)
+    }
+}
+
+impl VisitableMut for ShowCardinalityStatement {
+    fn accept<V: VisitorMut>(&mut self, visitor: &mut V) -> Result<(), V::Error> {
+        if let Stop = visitor.pre_visit_show_cardinality_statement(self)? {
+            return Ok(());
+        };
+
+        if let Some(on_clause) = &mut self.database {
+            on_clause.accept(visitor)?;
+        }
+
+        if let Some(from) = &mut self.from {
+            from.accept(visitor)?;
+        }
+
+        if let Some(with_key) = &mut self.with_key {
+            with_key.accept(visitor)?;
+        }
+
+        if let Some(condition) = &mut self.condition {
+            condition.accept(visitor)?;
+        }
+
+        if let Some(limit) = &mut self.limit {
+            limit.accept(visitor)?;
+        }
+
+        if let Some(offset) = &mut self.offset {
+            offset.accept(visitor)?;
+        }
+
+        visitor.post_visit_show_cardinality_statement(self)
+    }
+}
+
 impl VisitableMut for ShowTagKeysStatement {
     fn accept<V: VisitorMut>(&mut self, visitor: &mut V) -> Result<(), V::Error> {
         if let Stop = visitor.pre_visit_show_tag_keys_statement(self)? {
@@ -1292,16 +1419,18 @@ mod test {
     use crate::literal::Literal;
     use crate::parse_statements;
     use crate::select::{
-        Dimension, Field, FieldList, FillClause, FromMeasurementClause, GroupByClause,
+        Dimension, Field, FieldList, FillClause, FromMeasurementClause, GroupByClause, IntoClause,
         MeasurementSelection, SLimitClause, SOffsetClause, SelectStatement, TimeDimension,
         TimeZoneClause,
     };
     use crate::show::{OnClause, ShowDatabasesStatement};
+    use crate::show_cardinality::ShowCardinalityStatement;
     use crate::show_field_keys::ShowFieldKeysStatement;
     use crate::show_measurements::{
         ExtendedOnClause, ShowMeasurementsStatement, WithMeasurementClause,
     };
     use crate::show_retention_policies::ShowRetentionPoliciesStatement;
+    use crate::show_series::ShowSeriesStatement;
     use crate::show_tag_keys::ShowTagKeysStatement;
     use crate::show_tag_values::{ShowTagValuesStatement, WithKeyClause};
     use crate::simple_from_clause::{DeleteFromClause, ShowFromClause};
@@ -1349,12 +1478,14 @@ mod test {
         trace_visit!(drop_measurement_statement, DropMeasurementStatement);
         trace_visit!(explain_statement, ExplainStatement);
         trace_visit!(select_statement, SelectStatement);
+        trace_visit!(show_cardinality_statement, ShowCardinalityStatement);
         trace_visit!(show_databases_statement, ShowDatabasesStatement);
         trace_visit!(show_measurements_statement, ShowMeasurementsStatement);
         trace_visit!(
             show_retention_policies_statement,
             ShowRetentionPoliciesStatement
         );
+        trace_visit!(show_series_statement, ShowSeriesStatement);
         trace_visit!(show_tag_keys_statement, ShowTagKeysStatement);
         trace_visit!(show_tag_values_statement, ShowTagValuesStatement);
         trace_visit!(show_field_keys_statement, ShowFieldKeysStatement);
@@ -1376,6 +1507,7 @@ mod test {
         trace_visit!(offset_clause, OffsetClause);
         trace_visit!(slimit_clause, SLimitClause);
         trace_visit!(soffset_clause, SOffsetClause);
+        trace_visit!(select_into_clause, IntoClause);
         trace_visit!(timezone_clause, TimeZoneClause);
         trace_visit!(extended_on_clause, ExtendedOnClause);
         trace_visit!(on_clause, OnClause);
@@ -1435,6 +1567,7 @@ mod test {
             TZ('Australia/Hobart')
         "#
         ));
+        insta::assert_yaml_snapshot!(visit_statement!(r#"SELECT value INTO temp_copy FROM temp"#));
     }
 
     #[test]
@@ -1464,6 +1597,20 @@ mod test {
         insta::assert_yaml_snapshot!(visit_statement!("SHOW RETENTION POLICIES ON telegraf"));
     }
 
+    #[test]
+    fn test_show_series_statement() {
+        insta::assert_yaml_snapshot!(visit_statement!("SHOW SERIES"));
+        insta::assert_yaml_snapshot!(visit_statement!(
+            "SHOW SERIES ON telegraf FROM cpu WHERE host = \"west\" LIMIT 5 OFFSET 10"
+        ));
+    }
+
+    #[test]
+    fn test_show_cardinality_statement() {
+        insta::assert_yaml_snapshot!(visit_statement!("SHOW SERIES CARDINALITY"));
+        insta::assert_yaml_snapshot!(visit_statement!("SHOW TAG VALUES EXACT CARDINALITY ON telegraf FROM cpu WITH KEY = host WHERE host = \"west\" LIMIT 5 OFFSET 10"));
+    }
+
     #[test]
     fn test_show_tag_keys_statement() {
         insta::assert_yaml_snapshot!(visit_statement!("SHOW TAG KEYS"));
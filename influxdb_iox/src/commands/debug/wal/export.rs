@@ -0,0 +1,244 @@
+//! A module providing a CLI command to export a selection of the writes within
+//! WAL files as line protocol or parquet.
+use std::fs::{create_dir_all, OpenOptions};
+use std::future::Future;
+use std::path::PathBuf;
+
+use data_types::{NamespaceId, TableId};
+use observability_deps::tracing::info;
+use wal::{ClosedSegmentFileReader, WriteOpEntryDecoder};
+use wal_inspect::{
+    ExportFilter, LineProtoWriter, NamespaceDemultiplexer, ParquetWriter, TableBatchWriter,
+    WriteError,
+};
+
+use super::Error;
+
+/// The format to export writes in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ExportFormat {
+    /// Line protocol, with table IDs as measurement names
+    Lp,
+    /// A parquet file per table
+    Parquet,
+}
+
+/// A container for the possible arguments & flags of an `export` command.
+#[derive(Debug, clap::Parser)]
+pub struct Config {
+    /// The paths to the input WAL files, exported in the order given
+    #[clap(value_parser, required = true)]
+    inputs: Vec<PathBuf>,
+
+    /// Only export writes to the namespace with this ID
+    #[clap(long, value_parser)]
+    namespace_id: Option<i64>,
+
+    /// Only export writes to the table with this ID
+    #[clap(long, value_parser)]
+    table_id: Option<i64>,
+
+    /// Only export rows with a timestamp at or after this time, in nanoseconds
+    /// since the epoch
+    #[clap(long, value_parser, allow_hyphen_values = true)]
+    min_time: Option<i64>,
+
+    /// Only export rows with a timestamp at or before this time, in
+    /// nanoseconds since the epoch
+    #[clap(long, value_parser, allow_hyphen_values = true)]
+    max_time: Option<i64>,
+
+    /// The format to export the writes in
+    #[clap(long, value_enum, default_value_t = ExportFormat::Lp)]
+    format: ExportFormat,
+
+    /// The directory to write exported files to, one per namespace for line
+    /// protocol and one directory of files per namespace for parquet. Creates
+    /// the directory if it does not exist.
+    ///
+    /// When unspecified line protocol is written to stdout. Required for
+    /// parquet
+    #[clap(long, short, value_parser, required_if_eq("format", "parquet"))]
+    output_directory: Option<PathBuf>,
+
+    /// When enabled, pre-existing files will be overwritten
+    #[clap(long, short)]
+    force: bool,
+}
+
+impl Config {
+    fn filter(&self) -> Result<ExportFilter, Error> {
+        let time_range = match (self.min_time, self.max_time) {
+            (None, None) => None,
+            (min, max) => {
+                let min = min.unwrap_or(i64::MIN);
+                let max = max.unwrap_or(i64::MAX);
+                if max < min {
+                    return Err(Error::InvalidExportTimeRange { min, max });
+                }
+                Some(min..=max)
+            }
+        };
+
+        Ok(ExportFilter {
+            namespace: self.namespace_id.map(NamespaceId::new),
+            table: self.table_id.map(TableId::new),
+            time_range,
+        })
+    }
+}
+
+/// Executes the `export` command, writing the writes selected from the input
+/// WAL files in the requested format.
+pub async fn command(config: Config) -> Result<(), Error> {
+    let filter = config.filter()?;
+
+    match (config.format, config.output_directory) {
+        (ExportFormat::Lp, None) => {
+            let mut namespace_demux = NamespaceDemultiplexer::new(|_namespace_id| async {
+                let result: Result<_, WriteError> =
+                    Ok(LineProtoWriter::new(std::io::stdout(), None));
+                result
+            });
+            export_entries(&config.inputs, &filter, &mut namespace_demux).await
+        }
+        (ExportFormat::Lp, Some(d)) => {
+            create_dir_all(&d)?;
+            let force = config.force;
+            let mut namespace_demux = NamespaceDemultiplexer::new(move |namespace_id| {
+                let file_path = d.join(format!("namespace_id_{namespace_id}.lp"));
+                async move {
+                    info!(
+                        ?file_path,
+                        %namespace_id,
+                        "creating namespaced file for exported line protocol"
+                    );
+
+                    let mut open_options = OpenOptions::new().write(true).to_owned();
+                    if force {
+                        open_options.create(true).truncate(true);
+                    } else {
+                        open_options.create_new(true);
+                    }
+                    let result: Result<_, WriteError> =
+                        Ok(LineProtoWriter::new(open_options.open(&file_path)?, None));
+                    result
+                }
+            });
+            export_entries(&config.inputs, &filter, &mut namespace_demux).await
+        }
+        (ExportFormat::Parquet, Some(d)) => {
+            let force = config.force;
+            let mut namespace_demux = NamespaceDemultiplexer::new(move |namespace_id| {
+                let output_dir = d.join(format!("namespace_id_{namespace_id}"));
+                async move {
+                    let result: Result<_, WriteError> = Ok(ParquetWriter::new(output_dir, force));
+                    result
+                }
+            });
+            export_entries(&config.inputs, &filter, &mut namespace_demux).await?;
+
+            for (namespace_id, writer) in namespace_demux.into_inner() {
+                for file_path in writer.finish()? {
+                    info!(?file_path, %namespace_id, "wrote exported parquet file");
+                }
+            }
+            Ok(())
+        }
+        (ExportFormat::Parquet, None) => {
+            unreachable!("clap requires an output directory for parquet exports")
+        }
+    }
+}
+
+// Decodes the write op entries of each WAL file in `inputs` in turn, writing
+// the table batches selected by `filter` using the writer `namespace_demux`
+// returns for the namespace of the entry.
+//
+// Stops at the first entry that cannot be read or written.
+async fn export_entries<T, F, I>(
+    inputs: &[PathBuf],
+    filter: &ExportFilter,
+    namespace_demux: &mut NamespaceDemultiplexer<T, F>,
+) -> Result<(), Error>
+where
+    T: TableBatchWriter<WriteError = WriteError> + Send,
+    F: (Fn(NamespaceId) -> I) + Send + Sync,
+    I: Future<Output = Result<T, WriteError>> + Send,
+{
+    for input in inputs {
+        let decoder = WriteOpEntryDecoder::from(ClosedSegmentFileReader::from_path(input)?);
+
+        for entry_batch in decoder {
+            for entry in entry_batch? {
+                let namespace_id = entry.namespace;
+                let table_batches = filter.filter_entry(entry).map_err(WriteError::from)?;
+                if table_batches.is_empty() {
+                    continue;
+                }
+
+                namespace_demux
+                    .get(namespace_id)
+                    .await?
+                    .write_table_batches(table_batches.into_iter())?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use clap::Parser;
+
+    use super::*;
+
+    #[test]
+    fn test_config_filter() {
+        let config = Config::try_parse_from([
+            "export",
+            "1.dat",
+            "--namespace-id",
+            "1",
+            "--table-id",
+            "2",
+            "--min-time",
+            "-5",
+        ])
+        .unwrap();
+        assert_eq!(config.format, ExportFormat::Lp);
+        assert_eq!(
+            config.filter().unwrap(),
+            ExportFilter {
+                namespace: Some(NamespaceId::new(1)),
+                table: Some(TableId::new(2)),
+                time_range: Some(-5..=i64::MAX),
+            }
+        );
+
+        let config = Config::try_parse_from(["export", "1.dat"]).unwrap();
+        assert_eq!(config.filter().unwrap(), ExportFilter::default());
+
+        let config =
+            Config::try_parse_from(["export", "1.dat", "--min-time", "5", "--max-time", "4"])
+                .unwrap();
+        assert_matches!(
+            config.filter(),
+            Err(Error::InvalidExportTimeRange { min: 5, max: 4 })
+        );
+
+        // Parquet exports need somewhere to write their files.
+        assert!(Config::try_parse_from(["export", "1.dat", "--format", "parquet"]).is_err());
+        assert!(Config::try_parse_from([
+            "export",
+            "1.dat",
+            "--format",
+            "parquet",
+            "--output-directory",
+            "out"
+        ])
+        .is_ok());
+    }
+}
@@ -19,7 +19,7 @@ pub struct Config {
     sequence_number_range: Option<RangeInclusive<u64>>,
 }
 
-fn parse_sequence_number_range(s: &str) -> Result<RangeInclusive<u64>, String> {
+pub(super) fn parse_sequence_number_range(s: &str) -> Result<RangeInclusive<u64>, String> {
     let parts: Vec<&str> = s.split('-').collect();
     if parts.len() != 2 {
         return Err("sequence number range provided does not use format <START>-<END>".to_string());
@@ -5,8 +5,12 @@ use futures::Future;
 use influxdb_iox_client::connection::Connection;
 use thiserror::Error;
 
+mod export;
 mod inspect;
 mod regenerate_lp;
+mod restore;
+mod stats;
+mod verify;
 
 /// A command level error type to decorate WAL errors with some extra
 /// "human" context for the user
@@ -31,6 +35,27 @@ pub enum Error {
 
     #[error("errors occurred during inspection of the WAL file: {sources:?}")]
     IncompleteInspection { sources: Vec<wal::Error> },
+
+    #[error("invalid object store configuration: {0}")]
+    InvalidObjectStoreConfig(#[from] clap_blocks::object_store::ParseError),
+
+    #[error("object store request failed: {0}")]
+    ObjectStore(#[from] object_store::Error),
+
+    #[error("cannot restore into non-empty WAL directory {0:?}")]
+    WalDirectoryNotEmpty(std::path::PathBuf),
+
+    #[error("failed to write restored entries to the WAL: {0}")]
+    UnableToWriteRestoredOps(String),
+
+    #[error("damaged entries found in WAL files: {paths:?}")]
+    DamagedWalFiles { paths: Vec<std::path::PathBuf> },
+
+    #[error("invalid time range, max time {max} is less than min time {min}")]
+    InvalidExportTimeRange { min: i64, max: i64 },
+
+    #[error("failed to export writes from the WAL: {0}")]
+    UnableToExport(#[from] wal_inspect::WriteError),
 }
 
 /// A set of non-fatal errors which can occur during the regeneration of write
@@ -58,6 +83,18 @@ enum Command {
     /// looking up measurement names from IOx, the target host must implement
     /// the namespace and schema APIs
     RegenerateLp(regenerate_lp::Config),
+    /// Restore WAL files archived to object storage by an ingester into the
+    /// WAL directory of a fresh ingester, which replays them on startup
+    Restore(restore::Config),
+    /// Verify the checksum and decodability of every entry of WAL files,
+    /// reporting the offsets of any damaged entries
+    Verify(verify::Config),
+    /// Summarise the writes within WAL files, giving the op, row and byte
+    /// counts and sequence number range of each namespace and table
+    Stats(stats::Config),
+    /// Export the writes within WAL files to a namespace, table and time
+    /// range as line protocol or parquet
+    Export(export::Config),
 }
 
 /// Executes a WAL debugging subcommand as directed by the config
@@ -69,5 +106,9 @@ where
     match config.command {
         Command::Inspect(config) => inspect::command(config),
         Command::RegenerateLp(config) => regenerate_lp::command(connection, config).await,
+        Command::Restore(config) => restore::command(config).await,
+        Command::Verify(config) => verify::command(config),
+        Command::Stats(config) => stats::command(config),
+        Command::Export(config) => export::command(config).await,
     }
 }
@@ -0,0 +1,252 @@
+//! A module providing a CLI command to restore WAL files archived to object
+//! storage by an ingester.
+use std::{ops::RangeInclusive, path::PathBuf};
+
+use clap_blocks::object_store::{make_object_store, ObjectStoreConfig};
+use futures::TryStreamExt;
+use iox_time::Time;
+use object_store::{path::Path, DynObjectStore};
+use observability_deps::tracing::info;
+use wal::{ClosedSegmentFileReader, SequencedWalOp, Wal, WriteResult};
+
+use super::{inspect::parse_sequence_number_range, Error};
+
+/// A container for the possible arguments & flags of a `restore` command.
+#[derive(Debug, clap::Parser)]
+pub struct Config {
+    #[clap(flatten)]
+    object_store_config: ObjectStoreConfig,
+
+    /// The object storage path prefix the ingester archived its WAL files
+    /// under, as set with `--wal-archive-prefix`
+    #[clap(long, value_parser)]
+    prefix: String,
+
+    /// The WAL directory of the ingester to restore into. The directory must
+    /// be empty, and is created if it does not exist.
+    ///
+    /// The restored writes are replayed when the ingester is next started.
+    #[clap(value_parser)]
+    output_directory: PathBuf,
+
+    /// Only restore WAL files archived at or after this RFC3339 timestamp
+    #[clap(long, value_parser = parse_time)]
+    since: Option<Time>,
+
+    /// Only restore WAL files archived at or before this RFC3339 timestamp
+    #[clap(long, value_parser = parse_time)]
+    until: Option<Time>,
+
+    /// An optional range of sequence numbers to restrict the restore to, in
+    /// the format "%d-%d". Only entries that have a sequence number falling
+    /// within the range (inclusive) will be restored
+    #[clap(long, short, value_parser = parse_sequence_number_range)]
+    sequence_number_range: Option<RangeInclusive<u64>>,
+}
+
+fn parse_time(s: &str) -> Result<Time, String> {
+    Time::from_rfc3339(s).map_err(|e| format!("{s} isn't a valid RFC3339 timestamp: {e}"))
+}
+
+/// Executes the `restore` command with the provided configuration, writing
+/// the entries of archived WAL files into a fresh WAL directory.
+pub async fn command(config: Config) -> Result<(), Error> {
+    let store = make_object_store(&config.object_store_config)?;
+
+    let archived_at = config.since.map_or(i64::MIN, |t| t.timestamp_nanos())
+        ..=config.until.map_or(i64::MAX, |t| t.timestamp_nanos());
+
+    restore(
+        &*store,
+        &Path::from(config.prefix),
+        archived_at,
+        config.sequence_number_range,
+        config.output_directory,
+    )
+    .await
+}
+
+async fn restore(
+    store: &DynObjectStore,
+    prefix: &Path,
+    archived_at: RangeInclusive<i64>,
+    sequence_number_range: Option<RangeInclusive<u64>>,
+    output_directory: PathBuf,
+) -> Result<(), Error> {
+    // Refuse to mix restored writes with those of an existing WAL.
+    if output_directory.exists() && std::fs::read_dir(&output_directory)?.next().is_some() {
+        return Err(Error::WalDirectoryNotEmpty(output_directory));
+    }
+
+    // List the archived segments in the order they were archived in.
+    let mut segments = store
+        .list(Some(prefix))
+        .await?
+        .try_filter(|meta| {
+            let in_range = meta
+                .location
+                .filename()
+                .and_then(wal::parse_archived_segment_name)
+                .map_or(false, |(t, _id)| archived_at.contains(&t));
+            futures::future::ready(in_range)
+        })
+        .map_ok(|meta| meta.location)
+        .try_collect::<Vec<_>>()
+        .await?;
+    segments.sort();
+
+    let wal = Wal::new(&output_directory).await?;
+
+    // Archived segments are read back through a scratch file.
+    let scratch = tempfile::tempdir()?;
+    let scratch_path = scratch.path().join("segment");
+
+    let mut n_ops = 0;
+    for location in &segments {
+        let data = store.get(location).await?.bytes().await?;
+        std::fs::write(&scratch_path, data)?;
+
+        let mut last_write = None;
+        for batch in ClosedSegmentFileReader::from_path(&scratch_path)? {
+            for op in batch? {
+                if !in_sequence_number_range(&op, sequence_number_range.as_ref()) {
+                    continue;
+                }
+                n_ops += 1;
+                last_write = Some(wal.write_op(op));
+            }
+        }
+
+        // Wait for the ops of this segment to be written before reading the
+        // next, bounding the number of ops buffered in memory.
+        if let Some(mut rx) = last_write {
+            rx.changed()
+                .await
+                .expect("wal flush task stopped during restore");
+            if let Some(WriteResult::Err(e)) = &*rx.borrow() {
+                return Err(Error::UnableToWriteRestoredOps(e.clone()));
+            }
+        }
+
+        info!(%location, "restored archived wal segment");
+    }
+
+    info!(
+        n_segments = segments.len(),
+        n_ops,
+        output_directory = %output_directory.display(),
+        "wal restore complete"
+    );
+
+    Ok(())
+}
+
+fn in_sequence_number_range(op: &SequencedWalOp, range: Option<&RangeInclusive<u64>>) -> bool {
+    range.map_or(true, |range| {
+        op.table_write_sequence_numbers
+            .values()
+            .any(|seq| range.contains(seq))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use bytes::Bytes;
+    use data_types::TableId;
+    use generated_types::influxdata::iox::wal::v1::sequenced_wal_op::Op as WalOp;
+    use object_store::memory::InMemory;
+    use wal::SegmentId;
+
+    use super::*;
+
+    fn arbitrary_sequence_wal_op(seq_number: u64) -> SequencedWalOp {
+        SequencedWalOp {
+            table_write_sequence_numbers: [(TableId::new(0), seq_number)].into(),
+            op: WalOp::Write(Default::default()),
+        }
+    }
+
+    /// Write a segment containing ops with `seq_numbers` to a WAL in a new
+    /// temporary directory, returning the segment file contents.
+    async fn make_segment(seq_numbers: &[u64]) -> Bytes {
+        let dir = tempfile::tempdir().unwrap();
+        let wal = Wal::new(dir.path()).await.unwrap();
+        for &seq in seq_numbers {
+            wal.write_op(arbitrary_sequence_wal_op(seq))
+                .changed()
+                .await
+                .unwrap();
+        }
+        let (closed, _) = wal.rotate().unwrap();
+        wal.read_segment(closed.id()).unwrap().into()
+    }
+
+    /// Read back the sequence numbers of all ops in the WAL in `dir`.
+    async fn read_seq_numbers(dir: &std::path::Path) -> Vec<u64> {
+        let wal = Wal::new(dir).await.unwrap();
+        wal.closed_segments()
+            .iter()
+            .flat_map(|s| wal.reader_for_segment(s.id()).unwrap())
+            .flat_map(|batch| batch.unwrap())
+            .flat_map(|op| op.table_write_sequence_numbers.into_values())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_restore() {
+        let store: Arc<DynObjectStore> = Arc::new(InMemory::new());
+        let prefix = Path::from("archive");
+
+        // Archive segments out of segment id order
+        for (archived_at, id, seq_numbers) in [
+            (10, 1, &[1, 2][..]),
+            (20, 0, &[3][..]),
+            (30, 2, &[4, 5][..]),
+        ] {
+            let name = wal::archived_segment_name(archived_at, SegmentId::new(id));
+            store
+                .put(&prefix.child(name), make_segment(seq_numbers).await)
+                .await
+                .unwrap();
+        }
+        // And an unrelated object, which must be ignored
+        store
+            .put(&prefix.child("bananas"), Bytes::from_static(b"bananas"))
+            .await
+            .unwrap();
+
+        // Restore everything, in archival order
+        let dir = tempfile::tempdir().unwrap();
+        restore(
+            &*store,
+            &prefix,
+            i64::MIN..=i64::MAX,
+            None,
+            dir.path().into(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(read_seq_numbers(dir.path()).await, [1, 2, 3, 4, 5]);
+
+        // Restoring into a non-empty WAL directory fails
+        let err = restore(
+            &*store,
+            &prefix,
+            i64::MIN..=i64::MAX,
+            None,
+            dir.path().into(),
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, Error::WalDirectoryNotEmpty(_)));
+
+        // Restore by archival time and sequence number
+        let dir = tempfile::tempdir().unwrap();
+        restore(&*store, &prefix, 15..=30, Some(4..=4), dir.path().into())
+            .await
+            .unwrap();
+        assert_eq!(read_seq_numbers(dir.path()).await, [4]);
+    }
+}
@@ -0,0 +1,142 @@
+//! A module providing a CLI command to summarise the contents of WAL files.
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use wal::ClosedSegmentFileReader;
+use wal_inspect::SegmentStats;
+
+use super::Error;
+
+#[derive(Debug, clap::Parser)]
+pub struct Config {
+    /// The paths to the WAL files to summarise
+    #[clap(value_parser, required = true)]
+    inputs: Vec<PathBuf>,
+}
+
+/// Executes the `stats` command, printing the op, row and byte counts and the
+/// range of sequence numbers of the writes to each table within each input
+/// WAL file.
+///
+/// Reading a file stops at its first damaged entry, summarising the entries
+/// read up to that point.
+pub fn command(config: Config) -> Result<(), Error> {
+    let mut output = std::io::stdout();
+    let mut read_errors = vec![];
+
+    for input in config.inputs {
+        let reader = ClosedSegmentFileReader::from_path(&input)?;
+        let id = reader.id();
+
+        let mut stats = SegmentStats::default();
+        for entry in reader {
+            match entry {
+                Ok(ops) => stats.observe_entry(&ops),
+                Err(e) => {
+                    read_errors.push(e);
+                    break;
+                }
+            }
+        }
+
+        write_stats(&mut output, &input, id, &stats)?;
+    }
+
+    if read_errors.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::IncompleteInspection {
+            sources: read_errors,
+        })
+    }
+}
+
+fn write_stats<W: Write>(
+    output: &mut W,
+    input: &Path,
+    id: wal::SegmentId,
+    stats: &SegmentStats,
+) -> Result<(), std::io::Error> {
+    writeln!(
+        output,
+        "{}: segment {}, {} entries, {} ops ({} deletes, {} persists)",
+        input.display(),
+        id,
+        stats.entries,
+        stats.ops(),
+        stats.deletes,
+        stats.persists,
+    )?;
+
+    for ((namespace_id, table_id), table) in &stats.tables {
+        let sequence_numbers = table
+            .sequence_numbers
+            .as_ref()
+            .map_or_else(|| "-".to_string(), |r| format!("{}-{}", r.start(), r.end()));
+        writeln!(
+            output,
+            "  namespace {namespace_id} table {table_id}: {} ops, {} rows, {} bytes, \
+             sequence numbers {sequence_numbers}",
+            table.ops, table.rows, table.bytes,
+        )?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use data_types::{NamespaceId, TableId};
+    use wal::SegmentId;
+    use wal_inspect::TableStats;
+
+    use super::*;
+
+    #[test]
+    fn test_write_stats() {
+        let mut sink = Vec::<u8>::new();
+
+        write_stats(
+            &mut sink,
+            Path::new("wal/42.dat"),
+            SegmentId::new(42),
+            &SegmentStats {
+                entries: 2,
+                deletes: 0,
+                persists: 1,
+                tables: [
+                    (
+                        (NamespaceId::new(1), TableId::new(1)),
+                        TableStats {
+                            ops: 2,
+                            rows: 5,
+                            bytes: 120,
+                            sequence_numbers: Some(3..=7),
+                        },
+                    ),
+                    (
+                        (NamespaceId::new(2), TableId::new(3)),
+                        TableStats {
+                            ops: 1,
+                            rows: 1,
+                            bytes: 40,
+                            sequence_numbers: None,
+                        },
+                    ),
+                ]
+                .into_iter()
+                .collect(),
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            String::from_utf8(sink).unwrap(),
+            "wal/42.dat: segment 42, 2 entries, 4 ops (0 deletes, 1 persists)\n\
+             \x20 namespace 1 table 1: 2 ops, 5 rows, 120 bytes, sequence numbers 3-7\n\
+             \x20 namespace 2 table 3: 1 ops, 1 rows, 40 bytes, sequence numbers -\n"
+        );
+    }
+}
@@ -0,0 +1,119 @@
+//! A module providing a CLI command to verify the integrity of WAL files.
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use wal::ClosedSegmentFileReader;
+use wal_inspect::{verify_segment, SegmentVerification};
+
+use super::Error;
+
+#[derive(Debug, clap::Parser)]
+pub struct Config {
+    /// The paths to the WAL files to verify
+    #[clap(value_parser, required = true)]
+    inputs: Vec<PathBuf>,
+}
+
+/// Executes the `verify` command, checking the checksum and decodability of
+/// every entry of each input WAL file and reporting the offsets of any damaged
+/// entries.
+pub fn command(config: Config) -> Result<(), Error> {
+    let mut output = std::io::stdout();
+    let mut damaged = vec![];
+
+    for input in config.inputs {
+        let reader = ClosedSegmentFileReader::from_path(&input)?;
+        let file_len = std::fs::metadata(&input)?.len();
+
+        let verification = verify_segment(reader, file_len);
+        write_verification(&mut output, &input, &verification)?;
+
+        if !verification.is_ok() {
+            damaged.push(input);
+        }
+    }
+
+    if damaged.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::DamagedWalFiles { paths: damaged })
+    }
+}
+
+fn write_verification<W: Write>(
+    output: &mut W,
+    input: &Path,
+    verification: &SegmentVerification,
+) -> Result<(), std::io::Error> {
+    writeln!(
+        output,
+        "{}: segment {} ({}), {} valid entries, {}",
+        input.display(),
+        verification.id,
+        verification.codec,
+        verification.valid_entries,
+        if verification.is_ok() {
+            "OK"
+        } else {
+            "DAMAGED"
+        },
+    )?;
+
+    for entry in &verification.damaged_entries {
+        writeln!(
+            output,
+            "  damaged entry at offset {}: {}",
+            entry.offset, entry.error
+        )?;
+    }
+    if let Some(tail) = &verification.unreadable_tail {
+        writeln!(
+            output,
+            "  unreadable from offset {}: {}",
+            tail.offset, tail.error
+        )?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use wal::{SegmentCodec, SegmentId};
+    use wal_inspect::DamagedEntry;
+
+    use super::*;
+
+    #[test]
+    fn test_write_verification() {
+        let mut sink = Vec::<u8>::new();
+
+        write_verification(
+            &mut sink,
+            Path::new("wal/42.dat"),
+            &SegmentVerification {
+                id: SegmentId::new(42),
+                codec: SegmentCodec::Snappy,
+                valid_entries: 3,
+                damaged_entries: vec![DamagedEntry {
+                    offset: 16,
+                    error: "bananas".to_string(),
+                }],
+                unreadable_tail: Some(DamagedEntry {
+                    offset: 128,
+                    error: "2 trailing bytes".to_string(),
+                }),
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            String::from_utf8(sink).unwrap(),
+            "wal/42.dat: segment 42 (snappy), 3 valid entries, DAMAGED\n\
+             \x20 damaged entry at offset 16: bananas\n\
+             \x20 unreadable from offset 128: 2 trailing bytes\n"
+        );
+    }
+}
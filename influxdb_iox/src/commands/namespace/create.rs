@@ -38,6 +38,20 @@ pub struct ServiceProtectionLimitsArgs {
     /// The maximum number of columns to allow per table for this namespace
     #[clap(action, long = "max-columns-per-table", short = 'c')]
     max_columns_per_table: Option<i32>,
+
+    /// The maximum number of line protocol bytes per second that may be written to this
+    /// namespace
+    #[clap(action, long = "max-write-bytes-per-second")]
+    max_write_bytes_per_second: Option<i64>,
+
+    /// The maximum number of lines per second that may be written to this namespace
+    #[clap(action, long = "max-write-lines-per-second")]
+    max_write_lines_per_second: Option<i64>,
+
+    /// The maximum number of queries that may run concurrently against this namespace on each
+    /// querier
+    #[clap(action, long = "max-concurrent-queries")]
+    max_concurrent_queries: Option<i32>,
 }
 
 impl From<ServiceProtectionLimitsArgs> for Option<ServiceProtectionLimits> {
@@ -45,13 +59,24 @@ impl From<ServiceProtectionLimitsArgs> for Option<ServiceProtectionLimits> {
         let ServiceProtectionLimitsArgs {
             max_tables,
             max_columns_per_table,
+            max_write_bytes_per_second,
+            max_write_lines_per_second,
+            max_concurrent_queries,
         } = value;
-        if max_tables.is_none() && max_columns_per_table.is_none() {
+        if max_tables.is_none()
+            && max_columns_per_table.is_none()
+            && max_write_bytes_per_second.is_none()
+            && max_write_lines_per_second.is_none()
+            && max_concurrent_queries.is_none()
+        {
             return None;
         }
         Some(ServiceProtectionLimits {
             max_tables,
             max_columns_per_table,
+            max_write_bytes_per_second,
+            max_write_lines_per_second,
+            max_concurrent_queries,
         })
     }
 }
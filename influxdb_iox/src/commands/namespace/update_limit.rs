@@ -15,15 +15,21 @@ pub struct Config {
 
 #[derive(Debug, clap::Args)]
 #[clap(group(
-            // This arg group "limit" links the members of the below struct 
-            // named "max_tables" and "max_columns_per_table" together as 
+            // This arg group "limit" links the members of the below struct
+            // (such as "max_tables" and "max_columns_per_table") together as
             // mutually exclusive flags. As we specify all flags & commands
             // using clap-derive rather than the imperative builder, v3 only
             // properly supports this kind of behaviour in a macro code block.
             // NOTE: It takes the variable names and not the flag long names.
             clap::ArgGroup::new("limit")
                 .required(true)
-                .args(&["max_tables", "max_columns_per_table"])
+                .args(&[
+                    "max_tables",
+                    "max_columns_per_table",
+                    "max_write_bytes_per_second",
+                    "max_write_lines_per_second",
+                    "max_concurrent_queries",
+                ])
         ))]
 pub struct Args {
     /// The maximum number of tables to allow for this namespace
@@ -33,6 +39,21 @@ pub struct Args {
     /// The maximum number of columns to allow per table for this namespace
     #[clap(action, long = "max-columns-per-table", short = 'c', group = "limit")]
     max_columns_per_table: Option<i32>,
+
+    /// The maximum number of line protocol bytes per second that may be written to this
+    /// namespace, 0 to remove the limit
+    #[clap(action, long = "max-write-bytes-per-second", group = "limit")]
+    max_write_bytes_per_second: Option<i64>,
+
+    /// The maximum number of lines per second that may be written to this namespace, 0 to
+    /// remove the limit
+    #[clap(action, long = "max-write-lines-per-second", group = "limit")]
+    max_write_lines_per_second: Option<i64>,
+
+    /// The maximum number of queries that may run concurrently against this namespace on each
+    /// querier, 0 to remove the limit
+    #[clap(action, long = "max-concurrent-queries", group = "limit")]
+    max_concurrent_queries: Option<i32>,
 }
 
 impl From<Args> for LimitUpdate {
@@ -40,6 +61,9 @@ impl From<Args> for LimitUpdate {
         let Args {
             max_tables,
             max_columns_per_table,
+            max_write_bytes_per_second,
+            max_write_lines_per_second,
+            max_concurrent_queries,
         } = args;
 
         if let Some(n) = max_tables {
@@ -48,6 +72,15 @@ impl From<Args> for LimitUpdate {
         if let Some(n) = max_columns_per_table {
             return Self::MaxColumnsPerTable(n);
         }
+        if let Some(n) = max_write_bytes_per_second {
+            return Self::MaxWriteBytesPerSecond(n);
+        }
+        if let Some(n) = max_write_lines_per_second {
+            return Self::MaxWriteLinesPerSecond(n);
+        }
+        if let Some(n) = max_concurrent_queries {
+            return Self::MaxConcurrentQueries(n);
+        }
         unreachable!();
     }
 }
@@ -0,0 +1,79 @@
+use influxdb_iox_client::connection::Connection;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("JSON Serialization error: {0}")]
+    Serde(#[from] serde_json::Error),
+
+    #[error("Client error: {0}")]
+    ClientError(#[from] influxdb_iox_client::error::Error),
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Persist the data buffered by a particular ingester for a namespace, or a
+/// single table or partition within it.
+#[derive(Debug, clap::Parser)]
+pub struct Config {
+    /// The namespace to persist
+    #[clap(action)]
+    namespace: String,
+
+    /// Only persist the data buffered for this table
+    #[clap(long, action)]
+    table: Option<String>,
+
+    /// Only persist the data buffered for the partition with this key
+    #[clap(long, requires = "table", action)]
+    partition_key: Option<String>,
+
+    /// Return once the data is enqueued for persistence, rather than blocking
+    /// until it has persisted and printing the IDs of the parquet files
+    /// produced
+    #[clap(long, action)]
+    no_wait: bool,
+}
+
+pub async fn command(connection: Connection, config: Config) -> Result<()> {
+    let mut client = influxdb_iox_client::ingester::Client::new(connection);
+    let Config {
+        namespace,
+        table,
+        partition_key,
+        no_wait,
+    } = config;
+
+    let response = client
+        .persist_partitions(namespace, table, partition_key, !no_wait)
+        .await?;
+    println!("{}", serde_json::to_string_pretty(&response)?);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+
+    #[test]
+    fn test_partition_key_requires_table() {
+        assert!(Config::try_parse_from(["persist", "ns", "--partition-key", "2023"]).is_err());
+
+        let config = Config::try_parse_from([
+            "persist",
+            "ns",
+            "--table",
+            "bananas",
+            "--partition-key",
+            "2023",
+            "--no-wait",
+        ])
+        .unwrap();
+        assert_eq!(config.table.as_deref(), Some("bananas"));
+        assert_eq!(config.partition_key.as_deref(), Some("2023"));
+        assert!(config.no_wait);
+    }
+}
@@ -74,6 +74,7 @@ pub async fn command(connection: Connection, config: Config) -> Result<()> {
         table_id,
         columns,
         predicate,
+        aggregate: None,
         namespace_id,
     };
 
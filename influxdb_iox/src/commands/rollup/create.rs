@@ -0,0 +1,91 @@
+use std::time::Duration;
+
+use influxdb_iox_client::{connection::Connection, rollup::generated_types::QueryLanguage};
+
+use crate::commands::rollup::Result;
+
+/// The language the aggregates of a rollup rule are written in
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+enum Language {
+    Sql,
+    Influxql,
+}
+
+impl From<Language> for QueryLanguage {
+    fn from(value: Language) -> Self {
+        match value {
+            Language::Sql => Self::Sql,
+            Language::Influxql => Self::Influxql,
+        }
+    }
+}
+
+/// Create a rule continuously rolling up the data of a table into another table
+#[derive(Debug, clap::Parser)]
+pub struct Config {
+    /// The namespace of the rule
+    #[clap(action)]
+    database: String,
+
+    /// The name of the rule
+    #[clap(action)]
+    name: String,
+
+    /// The table to read raw data from
+    #[clap(action, long = "source")]
+    source_table: String,
+
+    /// The table to write the rolled up data to
+    #[clap(action, long = "target")]
+    target_table: String,
+
+    /// Comma separated list of aggregate expressions, such as
+    /// "mean(usage) AS usage_mean, max(usage) AS usage_max"
+    #[clap(action, long)]
+    aggregates: String,
+
+    /// The `GROUP BY time` interval of the rule, such as "1m" or "1h"
+    #[clap(long, value_parser = humantime::parse_duration)]
+    interval: Duration,
+
+    /// The language the aggregates are written in
+    #[clap(long, value_enum, default_value = "influxql")]
+    language: Language,
+
+    /// How long to keep the raw data of the source table, such as "7d".
+    ///
+    /// Raw data older than this is deleted once it has been rolled up, and kept until then,
+    /// instead of applying the retention period of the source table.
+    #[clap(long, value_parser = humantime::parse_duration)]
+    raw_retention: Option<Duration>,
+}
+
+pub async fn command(connection: Connection, config: Config) -> Result<()> {
+    let Config {
+        database,
+        name,
+        source_table,
+        target_table,
+        aggregates,
+        interval,
+        language,
+        raw_retention,
+    } = config;
+
+    let mut client = influxdb_iox_client::rollup::Client::new(connection);
+    let rule = client
+        .create_rollup_rule(
+            &database,
+            &name,
+            &source_table,
+            &target_table,
+            language.into(),
+            &aggregates,
+            interval.as_nanos() as i64,
+            raw_retention.map(|d| d.as_nanos() as i64),
+        )
+        .await?;
+    println!("{}", serde_json::to_string_pretty(&rule)?);
+
+    Ok(())
+}
@@ -0,0 +1,25 @@
+use influxdb_iox_client::connection::Connection;
+
+use crate::commands::rollup::Result;
+
+/// Delete a rollup rule. Data already rolled up into the target table is kept.
+#[derive(Debug, clap::Parser)]
+pub struct Config {
+    /// The namespace of the rule
+    #[clap(action)]
+    database: String,
+
+    /// The name of the rule to delete
+    #[clap(action)]
+    name: String,
+}
+
+pub async fn command(connection: Connection, config: Config) -> Result<()> {
+    let Config { database, name } = config;
+
+    let mut client = influxdb_iox_client::rollup::Client::new(connection);
+    client.delete_rollup_rule(&database, &name).await?;
+    println!("Deleted rollup rule {name:?}");
+
+    Ok(())
+}
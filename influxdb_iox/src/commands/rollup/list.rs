@@ -0,0 +1,21 @@
+use influxdb_iox_client::connection::Connection;
+
+use crate::commands::rollup::Result;
+
+/// List the rollup rules of a namespace
+#[derive(Debug, clap::Parser)]
+pub struct Config {
+    /// The namespace to list the rules of
+    #[clap(action)]
+    database: String,
+}
+
+pub async fn command(connection: Connection, config: Config) -> Result<()> {
+    let Config { database } = config;
+
+    let mut client = influxdb_iox_client::rollup::Client::new(connection);
+    let rules = client.get_rollup_rules(&database).await?;
+    println!("{}", serde_json::to_string_pretty(&rules)?);
+
+    Ok(())
+}
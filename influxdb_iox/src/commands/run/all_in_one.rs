@@ -497,10 +497,24 @@ impl Config {
         let ingester_config = IngesterConfig {
             wal_directory,
             wal_rotation_period_seconds,
+            wal_replay_strict: false,
+            wal_fsync_policy: Default::default(),
+            wal_fsync_interval_millis: 100,
+            wal_compression: Default::default(),
+            wal_archive_prefix: None,
+            wal_archive_retention_hours: None,
+            wal_replication_follower: None,
+            wal_replication_leader_id: None,
+            wal_replica_leader_ids: vec![],
+            wal_replica_takeover_timeout_seconds: 300,
             concurrent_query_limit,
             persist_max_parallelism,
             persist_queue_depth,
             persist_hot_partition_cost,
+            persist_cold_split_threshold_seconds: None,
+            partition_precreate_lookahead_seconds: None,
+            buffer_memory_limit_bytes: None,
+            buffer_memory_high_watermark_percent: 80,
             rpc_write_max_incoming_bytes: 1024 * 1024 * 1024, // 1GiB
             gossip_config: GossipConfig::disabled(),
         };
@@ -544,6 +558,7 @@ impl Config {
 
         let querier_config = QuerierConfig {
             authz_address,
+            single_tenant_deployment,
             num_query_threads: None, // will be ignored
             ingester_addresses,
             ram_pool_metadata_bytes: querier_ram_pool_metadata_bytes,
@@ -552,6 +567,10 @@ impl Config {
             exec_mem_pool_bytes,
             ingester_circuit_breaker_threshold: u64::MAX, // never for all-in-one-mode
             datafusion_config: Default::default(),
+            select_into_router_address: Some(format!("http://{router_http_bind_address}")),
+            create_database_router_address: Some(format!("http://{router_grpc_bind_address}")),
+            rollup_router_address: Some(format!("http://{router_http_bind_address}")),
+            rollup_interval: Duration::from_secs(60),
         };
 
         SpecializedConfig {
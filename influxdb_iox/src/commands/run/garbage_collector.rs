@@ -53,12 +53,13 @@ pub async fn command(config: Config) -> Result<()> {
     info!("starting garbage-collector");
 
     let server_type = Arc::new({
+        let metric_registry = Arc::clone(&metric_registry);
         let config = gc::Config {
             object_store,
             catalog,
             sub_config,
+            metric_registry: Arc::clone(&metric_registry),
         };
-        let metric_registry = Arc::clone(&metric_registry);
 
         gc::Server::start(metric_registry, config)
     });
@@ -5,6 +5,7 @@ use observability_deps::tracing::info;
 use thiserror::Error;
 
 mod create;
+mod retention;
 
 #[allow(clippy::enum_variant_names)]
 #[derive(Debug, Error)]
@@ -30,6 +31,9 @@ pub struct Config {
 enum Command {
     /// Create a new table
     Create(create::Config),
+
+    /// Update the retention period override of an existing table
+    Retention(retention::Config),
 }
 
 pub async fn command(connection: Connection, config: Config) -> Result<()> {
@@ -37,6 +41,10 @@ pub async fn command(connection: Connection, config: Config) -> Result<()> {
         Command::Create(config) => {
             info!("Creating table with config: {:?}", config);
             create::command(connection, config).await?;
+        }
+        Command::Retention(config) => {
+            info!("Updating table retention with config: {:?}", config);
+            retention::command(connection, config).await?;
         } // Deliberately not adding _ => so the compiler will direct people here to impl new
           // commands
     }
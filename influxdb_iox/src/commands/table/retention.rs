@@ -0,0 +1,45 @@
+use influxdb_iox_client::connection::Connection;
+
+use crate::commands::table::Result;
+
+/// Update the specified table's data retention period, overriding the
+/// retention period of its namespace
+#[derive(Debug, clap::Parser)]
+pub struct Config {
+    /// The namespace of the table
+    #[clap(action)]
+    database: String,
+
+    /// The table to update the retention period for
+    #[clap(action)]
+    table: String,
+
+    /// Num of hours of the retention period of this table. Default is 0 representing
+    /// the retention period of the namespace
+    #[clap(action, long = "retention-hours", short = 'r', default_value = "0")]
+    retention_hours: u32,
+}
+
+pub async fn command(connection: Connection, config: Config) -> Result<()> {
+    let Config {
+        database,
+        table,
+        retention_hours,
+    } = config;
+
+    // retention_hours = 0 means no override. Make it None/Null in the request.
+    let retention: Option<i64> = if retention_hours == 0 {
+        None
+    } else {
+        // we take retention from the user in hours, for ease of use, but it's stored as nanoseconds
+        // internally
+        Some(retention_hours as i64 * 60 * 60 * 1_000_000_000)
+    };
+    let mut client = influxdb_iox_client::table::Client::new(connection);
+    let table = client
+        .update_table_retention(&database, &table, retention)
+        .await?;
+    println!("{}", serde_json::to_string_pretty(&table)?);
+
+    Ok(())
+}
@@ -41,9 +41,11 @@ mod commands {
     pub mod debug;
     pub mod namespace;
     pub mod partition_template;
+    pub mod persist_ingester;
     pub mod query;
     pub mod query_ingester;
     pub mod remote;
+    pub mod rollup;
     pub mod run;
     pub mod sql;
     pub mod storage;
@@ -222,11 +224,17 @@ enum Command {
     /// Query the ingester only
     QueryIngester(commands::query_ingester::Config),
 
+    /// Persist the data buffered by an ingester
+    PersistIngester(commands::persist_ingester::Config),
+
     /// Various commands for namespace manipulation
     Namespace(commands::namespace::Config),
 
     /// Various commands for table manipulation
     Table(commands::table::Config),
+
+    /// Various commands for rollup rule manipulation
+    Rollup(commands::rollup::Config),
 }
 
 fn main() -> Result<(), std::io::Error> {
@@ -375,6 +383,14 @@ fn main() -> Result<(), std::io::Error> {
                     std::process::exit(ReturnCode::Failure as _)
                 }
             }
+            Some(Command::PersistIngester(config)) => {
+                let _tracing_guard = handle_init_logs(init_simple_logs(log_verbose_count));
+                let connection = connection(grpc_host).await;
+                if let Err(e) = commands::persist_ingester::command(connection, config).await {
+                    eprintln!("{e}");
+                    std::process::exit(ReturnCode::Failure as _)
+                }
+            }
             Some(Command::Namespace(config)) => {
                 let _tracing_guard = handle_init_logs(init_simple_logs(log_verbose_count));
                 let connection = connection(grpc_host).await;
@@ -391,6 +407,14 @@ fn main() -> Result<(), std::io::Error> {
                     std::process::exit(ReturnCode::Failure as _)
                 }
             }
+            Some(Command::Rollup(config)) => {
+                let _tracing_guard = handle_init_logs(init_simple_logs(log_verbose_count));
+                let connection = connection(grpc_host).await;
+                if let Err(e) = commands::rollup::command(connection, config).await {
+                    eprintln!("{e}");
+                    std::process::exit(ReturnCode::Failure as _)
+                }
+            }
         }
     });
 
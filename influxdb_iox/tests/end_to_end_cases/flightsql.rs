@@ -1,8 +1,8 @@
-use std::path::PathBuf;
+use std::{path::PathBuf, sync::Arc};
 
 use arrow::{
-    array::as_generic_binary_array,
-    datatypes::{DataType, Schema, TimeUnit},
+    array::{as_generic_binary_array, ArrayRef, Int32Array},
+    datatypes::{DataType, Field, Schema, TimeUnit},
     record_batch::RecordBatch,
 };
 use arrow_flight::{
@@ -158,6 +158,65 @@ async fn flightsql_prepared_query() {
     .await
 }
 
+#[tokio::test]
+async fn flightsql_prepared_query_with_params() {
+    test_helpers::maybe_start_logging();
+    let database_url = maybe_skip_integration!();
+
+    let table_name = "the_table";
+
+    // Set up the cluster  ====================================
+    let mut cluster = MiniCluster::create_shared(database_url).await;
+
+    StepTest::new(
+        &mut cluster,
+        vec![
+            Step::WriteLineProtocol(format!(
+                "{table_name},tag1=A,tag2=B val=42i 123456\n\
+                 {table_name},tag1=A,tag2=C val=43i 123457"
+            )),
+            Step::Custom(Box::new(move |state: &mut StepTestState| {
+                async move {
+                    let sql = format!("select * from {table_name} where val > $1");
+                    let mut client = flightsql_client(state.cluster());
+
+                    let handle = client.prepare(sql).await.unwrap();
+                    let parameter_schema = handle.get_parameter_schema();
+                    assert_eq!(
+                        parameter_schema.as_ref(),
+                        &Schema::new(vec![Field::new("$1", DataType::Int64, true)])
+                    );
+
+                    // values are cast to the type of the parameter
+                    let parameters = RecordBatch::try_from_iter([(
+                        "$1",
+                        Arc::new(Int32Array::from(vec![42])) as ArrayRef,
+                    )])
+                    .unwrap();
+                    let handle = handle.with_parameters(parameters);
+                    let stream = client.execute(handle).await.unwrap();
+
+                    let batches = collect_stream(stream).await;
+                    insta::assert_yaml_snapshot!(
+                        batches_to_sorted_lines(&batches),
+                        @r###"
+                    ---
+                    - +------+------+--------------------------------+-----+
+                    - "| tag1 | tag2 | time                           | val |"
+                    - +------+------+--------------------------------+-----+
+                    - "| A    | C    | 1970-01-01T00:00:00.000123457Z | 43  |"
+                    - +------+------+--------------------------------+-----+
+                    "###
+                    );
+                }
+                .boxed()
+            })),
+        ],
+    )
+    .run()
+    .await
+}
+
 #[tokio::test]
 async fn flightsql_get_sql_infos() {
     test_helpers::maybe_start_logging();
@@ -516,6 +575,7 @@ async fn flightsql_get_tables() {
                     - "| public       | information_schema | views       | VIEW       |"
                     - "| public       | iox                | the_table   | BASE TABLE |"
                     - "| public       | system             | queries     | BASE TABLE |"
+                    - "| public       | system             | storage     | BASE TABLE |"
                     - +--------------+--------------------+-------------+------------+
                     - "catalog:None"
                     - "db_schema_filter_pattern:None"
@@ -528,6 +588,7 @@ async fn flightsql_get_tables() {
                     - +--------------+----------------+------------+------------+
                     - "| public       | iox            | the_table  | BASE TABLE |"
                     - "| public       | system         | queries    | BASE TABLE |"
+                    - "| public       | system         | storage    | BASE TABLE |"
                     - +--------------+----------------+------------+------------+
                     - "catalog:None"
                     - "db_schema_filter_pattern:None"
@@ -694,6 +755,7 @@ async fn flightsql_get_tables_matches_information_schema() {
                     - "| public       | information_schema | views       | VIEW       |"
                     - "| public       | iox                | the_table   | BASE TABLE |"
                     - "| public       | system             | queries     | BASE TABLE |"
+                    - "| public       | system             | storage     | BASE TABLE |"
                     - +--------------+--------------------+-------------+------------+
                     "###
                     );
@@ -1116,10 +1178,27 @@ async fn flightsql_get_primary_keys() {
                         batches_to_sorted_lines(&batches),
                         @r###"
                     ---
-                    - ++
-                    - ++
+                    - +--------------+----------------+------------+-------------+----------+--------------+
+                    - "| catalog_name | db_schema_name | table_name | column_name | key_name | key_sequence |"
+                    - +--------------+----------------+------------+-------------+----------+--------------+
+                    - "| public       | iox            | the_table  | tag1        |          | 1            |"
+                    - "| public       | iox            | the_table  | tag2        |          | 2            |"
+                    - "| public       | iox            | the_table  | time        |          | 3            |"
+                    - +--------------+----------------+------------+-------------+----------+--------------+
                     "###
                     );
+
+                    // tables are filtered by schema
+                    let stream = client
+                        .get_primary_keys(
+                            None::<String>,
+                            Some("system"),
+                            table_name.to_string(),
+                        )
+                        .await
+                        .unwrap();
+                    let batches = collect_stream(stream).await;
+                    assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 0);
                 }
                 .boxed()
             })),
@@ -1161,6 +1240,9 @@ async fn flightsql_get_xdbc_type_info() {
                     - +-----------+-----------+-------------+----------------+----------------+---------------+----------+----------------+------------+--------------------+------------------+----------------+-----------------+---------------+---------------+---------------+------------------+----------------+--------------------+
                     - "| type_name | data_type | column_size | literal_prefix | literal_suffix | create_params | nullable | case_sensitive | searchable | unsigned_attribute | fixed_prec_scale | auto_increment | local_type_name | minimum_scale | maximum_scale | sql_data_type | datetime_subcode | num_prec_radix | interval_precision |"
                     - +-----------+-----------+-------------+----------------+----------------+---------------+----------+----------------+------------+--------------------+------------------+----------------+-----------------+---------------+---------------+---------------+------------------+----------------+--------------------+
+                    - "| BIGINT    | -5        | 64          |                |                |               | 1        | false          | 3          | false              | false            | false          | BIGINT          |               |               | -5            |                  | 2              |                    |"
+                    - "| BOOLEAN   | -7        |             |                |                |               | 1        | false          | 3          |                    | false            |                | BOOLEAN         |               |               | -7            |                  |                |                    |"
+                    - "| DOUBLE    | 8         | 53          |                |                |               | 1        | false          | 3          | false              | false            | false          | DOUBLE          |               |               | 8             |                  | 2              |                    |"
                     - "| FLOAT     | 6         | 24          |                |                |               | 1        | false          | 3          | false              | false            | false          | FLOAT           |               |               | 6             |                  | 2              |                    |"
                     - "| INTEGER   | 4         | 32          |                |                |               | 1        | false          | 3          | false              | false            | false          | INTEGER         |               |               | 4             |                  | 2              |                    |"
                     - "| INTERVAL  | 10        | 2147483647  | '              | '              |               | 1        | false          | 3          |                    | false            |                | INTERVAL        |               |               | 10            | 0                |                |                    |"
@@ -1457,7 +1539,8 @@ async fn jdbc_tests(jdbc_url: &str, table_name: &str) {
                                      public,  information_schema,  tables,  VIEW,  null,  null,  null,  null,  null,  null\n\
                                      public,  information_schema,  views,  VIEW,  null,  null,  null,  null,  null,  null\n\
                                      public,  iox,  the_table,  BASE TABLE,  null,  null,  null,  null,  null,  null\n\
-                                     public,  system,  queries,  BASE TABLE,  null,  null,  null,  null,  null,  null";
+                                     public,  system,  queries,  BASE TABLE,  null,  null,  null,  null,  null,  null\n\
+                                     public,  system,  storage,  BASE TABLE,  null,  null,  null,  null,  null,  null";
 
     // CommandGetTables output
     let expected_tables_with_filters = "**************\n\
@@ -1465,7 +1548,8 @@ async fn jdbc_tests(jdbc_url: &str, table_name: &str) {
                                         **************\n\
                                         TABLE_CAT,  TABLE_SCHEM,  TABLE_NAME,  TABLE_TYPE,  REMARKS,  TYPE_CAT,  TYPE_SCHEM,  TYPE_NAME,  SELF_REFERENCING_COL_NAME,  REF_GENERATION\n\
                                         ------------\n\
-                                        public,  system,  queries,  BASE TABLE,  null,  null,  null,  null,  null,  null";
+                                        public,  system,  queries,  BASE TABLE,  null,  null,  null,  null,  null,  null\n\
+                                        public,  system,  storage,  BASE TABLE,  null,  null,  null,  null,  null,  null";
 
     // CommandGetTableTypes output
     let expected_table_types = "**************\n\
@@ -1885,7 +1969,7 @@ dataDefinitionIgnoredInTransactions: true
 doesMaxRowSizeIncludeBlobs: true
 generatedKeyAlwaysReturned: false
 getCatalogSeparator: .
-getCatalogTerm: null
+getCatalogTerm: catalog
 getDatabaseMajorVersion: 10
 getDatabaseMinorVersion: 0
 getDatabaseProductName: InfluxDB IOx
@@ -1948,8 +2032,8 @@ storesUpperCaseQuotedIdentifiers: false
 supportsAlterTableWithAddColumn: false
 supportsAlterTableWithDropColumn: false
 supportsANSI92EntryLevelSQL: true
-supportsANSI92FullSQL: true
-supportsANSI92IntermediateSQL: true
+supportsANSI92FullSQL: false
+supportsANSI92IntermediateSQL: false
 supportsBatchUpdates: false
 supportsCatalogsInDataManipulation: true
 supportsCatalogsInIndexDefinitions: false
@@ -1970,7 +2054,7 @@ supportsGroupBy: true
 supportsGroupByBeyondSelect: true
 supportsGroupByUnrelated: true
 supportsIntegrityEnhancementFacility: false
-supportsLikeEscapeClause: true
+supportsLikeEscapeClause: false
 supportsLimitedOuterJoins: true
 supportsMinimumSQLGrammar: true
 supportsMixedCaseIdentifiers: false
@@ -2003,7 +2087,7 @@ supportsSubqueriesInComparisons: true
 supportsSubqueriesInExists: true
 supportsSubqueriesInIns: true
 supportsSubqueriesInQuantifieds: true
-supportsTableCorrelationNames: false
+supportsTableCorrelationNames: true
 supportsTransactionIsolationLevel: false
 supportsTransactions: false
 supportsUnion: true
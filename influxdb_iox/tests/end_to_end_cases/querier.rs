@@ -794,6 +794,7 @@ async fn iox_debug_header() {
                     "+---------------+--------------+------------+------------+",
                     "| table_catalog | table_schema | table_name | table_type |",
                     "+---------------+--------------+------------+------------+",
+                    "| public        | system       | storage    | BASE TABLE |",
                     "+---------------+--------------+------------+------------+",
                 ],
             },
@@ -806,6 +807,7 @@ async fn iox_debug_header() {
                     "| table_catalog | table_schema | table_name | table_type |",
                     "+---------------+--------------+------------+------------+",
                     "| public        | system       | queries    | BASE TABLE |",
+                    "| public        | system       | storage    | BASE TABLE |",
                     "+---------------+--------------+------------+------------+",
                 ],
             },
@@ -820,6 +822,7 @@ async fn iox_debug_header() {
                     "| public        | information_schema | tables      | VIEW       |",
                     "| public        | information_schema | views       | VIEW       |",
                     "| public        | iox                | the_table   | BASE TABLE |",
+                    "| public        | system             | storage     | BASE TABLE |",
                     "+---------------+--------------------+-------------+------------+",
                 ],
             },
@@ -835,6 +838,7 @@ async fn iox_debug_header() {
                     "| public        | information_schema | views       | VIEW       |",
                     "| public        | iox                | the_table   | BASE TABLE |",
                     "| public        | system             | queries     | BASE TABLE |",
+                    "| public        | system             | storage     | BASE TABLE |",
                     "+---------------+--------------------+-------------+------------+",
                 ],
             },
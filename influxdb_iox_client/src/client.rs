@@ -27,6 +27,9 @@ pub mod ingester;
 /// Client for namespace API
 pub mod namespace;
 
+/// Client for rollup API
+pub mod rollup;
+
 /// Client for schema API
 pub mod schema;
 
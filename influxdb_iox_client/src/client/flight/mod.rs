@@ -198,6 +198,7 @@ impl Client {
             query_type: QueryType::Sql.into(),
             flightsql_command: vec![],
             is_debug: false,
+            params: Default::default(),
         };
 
         self.do_get_with_read_info(request).await
@@ -216,6 +217,7 @@ impl Client {
             query_type: QueryType::InfluxQl.into(),
             flightsql_command: vec![],
             is_debug: false,
+            params: Default::default(),
         };
 
         self.do_get_with_read_info(request).await
@@ -23,9 +23,13 @@
 
 use std::sync::Arc;
 
-use arrow::datatypes::{Schema, SchemaRef};
+use arrow::{
+    datatypes::{Schema, SchemaRef},
+    record_batch::RecordBatch,
+};
 use arrow_flight::{
     decode::FlightRecordBatchStream,
+    encode::FlightDataEncoderBuilder,
     error::{FlightError, Result},
     sql::{
         ActionCreatePreparedStatementRequest, ActionCreatePreparedStatementResult, Any,
@@ -490,11 +494,17 @@ impl FlightSqlClient {
         ))
     }
 
-    /// Execute a SQL query on the server using [`CommandStatementQuery`]
+    /// Execute a prepared statement on the server using
+    /// [`CommandPreparedStatementQuery`]
     ///
-    /// This involves two round trips
+    /// This involves two round trips, plus one to bind the parameters
+    /// of the statement, if any
     ///
-    /// Step 1: send a [`CommandStatementQuery`] message to the
+    /// Step 0: send the parameters set with
+    /// [`PreparedStatement::with_parameters`] to the `DoPut` endpoint of
+    /// the FlightSQL server, which returns the handle to use from then on.
+    ///
+    /// Step 1: send a [`CommandPreparedStatementQuery`] message to the
     /// `GetFlightInfo` endpoint of the FlightSQL server to receive a
     /// FlightInfo descriptor.
     ///
@@ -509,13 +519,32 @@ impl FlightSqlClient {
             prepared_statement_handle,
             dataset_schema: _,
             parameter_schema: _,
+            parameters,
         } = statement;
-        // TODO handle parameters (via DoPut)
 
-        let cmd = CommandPreparedStatementQuery {
+        let mut cmd = CommandPreparedStatementQuery {
             prepared_statement_handle,
         };
 
+        if let Some(parameters) = parameters {
+            let descriptor = FlightDescriptor::new_cmd(cmd.as_any().encode_to_vec());
+            let flight_data = FlightDataEncoderBuilder::new()
+                .with_flight_descriptor(Some(descriptor))
+                .build(futures_util::stream::iter([Ok(parameters)]));
+
+            // the server may return a handle carrying the bound parameters,
+            // which is then used in place of the original one
+            let results: Vec<_> = self.inner.do_put(flight_data).await?.try_collect().await?;
+            if let Some(bound) = results
+                .into_iter()
+                .map(|result| result.app_metadata)
+                .filter(|handle| !handle.is_empty())
+                .last()
+            {
+                cmd.prepared_statement_handle = bound;
+            }
+        }
+
         self.do_get_with_cmd(cmd.as_any()).await
     }
 }
@@ -541,6 +570,9 @@ pub struct PreparedStatement {
 
     /// Schema of parameters, if any
     parameter_schema: SchemaRef,
+
+    /// Values to bind to the parameters on execution, if any
+    parameters: Option<RecordBatch>,
 }
 
 impl PreparedStatement {
@@ -556,6 +588,7 @@ impl PreparedStatement {
             prepared_statement_handle,
             dataset_schema,
             parameter_schema,
+            parameters: None,
         }
     }
 
@@ -568,4 +601,14 @@ impl PreparedStatement {
     pub fn get_parameter_schema(&self) -> SchemaRef {
         Arc::clone(&self.parameter_schema)
     }
+
+    /// Bind the values of `parameters`, a single row with a column per
+    /// parameter in the order of [`Self::get_parameter_schema`], when
+    /// executing the statement
+    pub fn with_parameters(self, parameters: RecordBatch) -> Self {
+        Self {
+            parameters: Some(parameters),
+            ..self
+        }
+    }
 }
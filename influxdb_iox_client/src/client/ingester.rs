@@ -21,12 +21,38 @@ impl Client {
         }
     }
 
-    /// Instruct the ingester to persist its data for the specified namespace to Parquet. Useful in
-    /// tests asserting on persisted data. May behave in unexpected ways if used concurrently with
-    /// writes and ingester WAL rotations.
+    /// Instruct the ingester to persist its data for the specified namespace to Parquet, blocking
+    /// until the data buffered at the time of the call has persisted. Useful in tests asserting on
+    /// persisted data.
     pub async fn persist(&mut self, namespace: String) -> Result<(), Error> {
-        self.inner.persist(PersistRequest { namespace }).await?;
+        self.persist_partitions(namespace, None, None, true).await?;
 
         Ok(())
     }
+
+    /// Instruct the ingester to persist its data for the specified namespace to Parquet,
+    /// optionally restricted to a single `table`, or a single partition of `table` identified by
+    /// `partition_key`.
+    ///
+    /// If `wait` is true, blocks until the data buffered for the selected partitions at the time
+    /// of the call has persisted, and returns the IDs of the parquet files produced.
+    pub async fn persist_partitions(
+        &mut self,
+        namespace: String,
+        table: Option<String>,
+        partition_key: Option<String>,
+        wait: bool,
+    ) -> Result<PersistResponse, Error> {
+        let response = self
+            .inner
+            .persist(PersistRequest {
+                namespace,
+                table,
+                partition_key,
+                no_wait: !wait,
+            })
+            .await?;
+
+        Ok(response.into_inner())
+    }
 }
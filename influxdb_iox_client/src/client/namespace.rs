@@ -8,6 +8,7 @@ use ::generated_types::google::OptionalField;
 /// Re-export generated_types
 pub mod generated_types {
     pub use generated_types::influxdata::iox::{
+        catalog::v1::StorageUsage,
         namespace::v1::{update_namespace_service_protection_limit_request::LimitUpdate, *},
         partition_template::v1::{template_part::*, *},
     };
@@ -108,6 +109,21 @@ impl Client {
         Ok(response.into_inner().namespace.unwrap_field("namespace")?)
     }
 
+    /// Get the object storage used by each table and compaction level of a namespace
+    pub async fn get_namespace_storage_usage(
+        &mut self,
+        namespace: &str,
+    ) -> Result<Vec<StorageUsage>, Error> {
+        let response = self
+            .inner
+            .get_namespace_storage_usage(GetNamespaceStorageUsageRequest {
+                name: namespace.to_string(),
+            })
+            .await?;
+
+        Ok(response.into_inner().usage)
+    }
+
     /// Delete a namespace
     pub async fn delete_namespace(&mut self, namespace: &str) -> Result<(), Error> {
         self.inner
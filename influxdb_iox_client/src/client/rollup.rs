@@ -0,0 +1,85 @@
+use client_util::connection::GrpcConnection;
+
+use self::generated_types::{rollup_service_client::RollupServiceClient, *};
+use crate::connection::Connection;
+use crate::error::Error;
+use ::generated_types::google::OptionalField;
+
+/// Re-export generated_types
+pub mod generated_types {
+    pub use generated_types::influxdata::iox::rollup::v1::*;
+}
+
+/// A basic client for working with rollup rules.
+#[derive(Debug, Clone)]
+pub struct Client {
+    inner: RollupServiceClient<GrpcConnection>,
+}
+
+impl Client {
+    /// Creates a new client with the provided connection
+    pub fn new(connection: Connection) -> Self {
+        Self {
+            inner: RollupServiceClient::new(connection.into_grpc_connection()),
+        }
+    }
+
+    /// Create a rollup rule in `namespace`, continuously rolling up `source_table` into
+    /// `target_table` with the given `aggregates`, grouped into windows of `interval_ns`.
+    ///
+    /// If `raw_retention_period_ns` is set, raw data of `source_table` older than that is deleted
+    /// once rolled up, and kept until then, instead of applying the retention period of the
+    /// table.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create_rollup_rule(
+        &mut self,
+        namespace: &str,
+        name: &str,
+        source_table: &str,
+        target_table: &str,
+        language: QueryLanguage,
+        aggregates: &str,
+        interval_ns: i64,
+        raw_retention_period_ns: Option<i64>,
+    ) -> Result<RollupRule, Error> {
+        let response = self
+            .inner
+            .create_rollup_rule(CreateRollupRuleRequest {
+                namespace: namespace.to_string(),
+                name: name.to_string(),
+                source_table: source_table.to_string(),
+                target_table: target_table.to_string(),
+                language: language.into(),
+                aggregates: aggregates.to_string(),
+                interval_ns,
+                raw_retention_period_ns,
+            })
+            .await?;
+
+        Ok(response.into_inner().rule.unwrap_field("rule")?)
+    }
+
+    /// List the rollup rules of `namespace`
+    pub async fn get_rollup_rules(&mut self, namespace: &str) -> Result<Vec<RollupRule>, Error> {
+        let response = self
+            .inner
+            .get_rollup_rules(GetRollupRulesRequest {
+                namespace: namespace.to_string(),
+            })
+            .await?;
+
+        Ok(response.into_inner().rules)
+    }
+
+    /// Delete the rollup rule `name` of `namespace`
+    pub async fn delete_rollup_rule(&mut self, namespace: &str, name: &str) -> Result<(), Error> {
+        self.inner
+            .delete_rollup_rule(DeleteRollupRuleRequest {
+                namespace: namespace.to_string(),
+                name: name.to_string(),
+            })
+            .await?;
+
+        Ok(())
+    }
+}
@@ -8,6 +8,7 @@ use ::generated_types::google::OptionalField;
 /// Re-export generated_types
 pub mod generated_types {
     pub use generated_types::influxdata::iox::{
+        catalog::v1::StorageUsage,
         partition_template::v1::{template_part::*, *},
         table::v1::*,
     };
@@ -45,4 +46,47 @@ impl Client {
 
         Ok(response.into_inner().table.unwrap_field("table")?)
     }
+
+    /// Update the retention period override for a table
+    ///
+    /// `retention_period_ns` is the the retention period in nanoseconds,
+    /// measured from `now()`. `None` removes the override so that the
+    /// namespace's retention period applies, and 0 is also mapped to `None` on
+    /// the server side.
+    ///
+    /// Negative retention periods are rejected, returning an error.
+    pub async fn update_table_retention(
+        &mut self,
+        namespace: &str,
+        table: &str,
+        retention_period_ns: Option<i64>,
+    ) -> Result<Table, Error> {
+        let response = self
+            .inner
+            .update_table_retention(UpdateTableRetentionRequest {
+                name: table.to_string(),
+                namespace: namespace.to_string(),
+                retention_period_ns,
+            })
+            .await?;
+
+        Ok(response.into_inner().table.unwrap_field("table")?)
+    }
+
+    /// Get the object storage used by each compaction level of a table
+    pub async fn get_table_storage_usage(
+        &mut self,
+        namespace: &str,
+        table: &str,
+    ) -> Result<Vec<StorageUsage>, Error> {
+        let response = self
+            .inner
+            .get_table_storage_usage(GetTableStorageUsageRequest {
+                name: table.to_string(),
+                namespace: namespace.to_string(),
+            })
+            .await?;
+
+        Ok(response.into_inner().usage)
+    }
 }
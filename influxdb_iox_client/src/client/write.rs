@@ -134,6 +134,39 @@ impl Client {
             )
         })?;
 
+        self.write_lp_stream_to_org_bucket(org_id, bucket_id, sources)
+            .await
+    }
+
+    /// Write the [LineProtocol] formatted string in `lp_data` to the
+    /// `org` and `bucket` of the V2 write API, leaving their mapping to a
+    /// namespace to the server.
+    ///
+    /// Returns the number of bytes which were written to the namespace.
+    ///
+    /// [LineProtocol]: https://docs.influxdata.com/influxdb/v2.0/reference/syntax/line-protocol/#data-types-and-format
+    pub async fn write_lp_to_org_bucket(
+        &mut self,
+        org: impl AsRef<str> + Send,
+        bucket: impl AsRef<str> + Send,
+        lp_data: impl Into<String> + Send,
+    ) -> Result<usize, Error> {
+        let sources = futures_util::stream::iter([lp_data.into()]);
+
+        self.write_lp_stream_to_org_bucket(org.as_ref(), bucket.as_ref(), sources)
+            .await
+    }
+
+    /// Write the stream of [LineProtocol] formatted strings in
+    /// `sources` to the `org_id` and `bucket_id` of the V2 write API.
+    ///
+    /// [LineProtocol]: https://docs.influxdata.com/influxdb/v2.0/reference/syntax/line-protocol/#data-types-and-format
+    async fn write_lp_stream_to_org_bucket(
+        &mut self,
+        org_id: &str,
+        bucket_id: &str,
+        sources: impl Stream<Item = String> + Send,
+    ) -> Result<usize, Error> {
         let max_concurrent_uploads: usize = self.max_concurrent_uploads.into();
         let max_request_payload_size_bytes = self.max_request_payload_size_bytes;
 
@@ -389,6 +422,27 @@ mod tests {
         assert_eq!(num_bytes, 22);
     }
 
+    #[tokio::test]
+    async fn test_write_lp_to_org_bucket() {
+        let mock = Arc::new(MockRequestMaker::new());
+
+        let data = "m,t=foo f=4";
+
+        // the bucket is passed as is, even if it contains underscores
+        let expected = vec![MockRequest {
+            org_id: "".into(),
+            bucket_id: "my_bucket".into(),
+            body: data.into(),
+        }];
+
+        let num_bytes = Client::new_with_maker(Arc::clone(&mock) as _)
+            .write_lp_to_org_bucket("", "my_bucket", data)
+            .await
+            .unwrap();
+        assert_eq!(expected, mock.requests());
+        assert_eq!(num_bytes, 11);
+    }
+
     #[derive(Debug, Clone, PartialEq)]
     struct MockRequest {
         org_id: String,
@@ -106,6 +106,7 @@ fn run_projection_bench(
                     table_id: table_id.get(),
                     columns: projection.clone(),
                     predicate: None,
+                    aggregate: None,
                 })
                 .await
                 .expect("query request failed");
@@ -162,6 +163,7 @@ fn run_concurrent_bench(
         table_id: table_id.get(),
         columns: vec![],
         predicate: None,
+        aggregate: None,
     });
 
     let ctx = Arc::new(ctx);
@@ -261,6 +263,7 @@ fn run_partition_prune_bench(
                     table_id: table_id.get(),
                     columns: vec![],
                     predicate: predicate.clone(),
+                    aggregate: None,
                 })
                 .await
                 .expect("query request failed");
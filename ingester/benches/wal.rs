@@ -66,9 +66,15 @@ fn wal_replay_bench(c: &mut Criterion) {
                 let persist = MockPersistQueue::default();
 
                 // Replay the wal into the NOP.
-                ingester::replay(&wal, &sink, Arc::new(persist), &metric::Registry::default())
-                    .await
-                    .expect("WAL replay error");
+                ingester::replay(
+                    &wal,
+                    &sink,
+                    Arc::new(persist),
+                    &metric::Registry::default(),
+                    ingester::WalReplayMode::Strict,
+                )
+                .await
+                .expect("WAL replay error");
             },
             // Use the WAL for one test invocation only, and re-create a new one
             // for the next iteration.
@@ -152,6 +152,16 @@ impl PartitionData {
         self.buffer.persist_cost_estimate()
     }
 
+    /// Return an estimate of the memory used by the data buffered in this
+    /// [`PartitionData`], in bytes.
+    ///
+    /// This value is inclusive of "hot" buffered data, and all currently
+    /// persisting data, which remains in memory until its persistence
+    /// completes.
+    pub(crate) fn buffered_bytes(&self) -> usize {
+        self.persisting.size() + self.buffer.persist_cost_estimate()
+    }
+
     /// Returns the number of rows currently buffered in this [`PartitionData`].
     ///
     /// The returned value will always match the row count of the data returned
@@ -361,6 +371,21 @@ impl PartitionData {
         fsm.into_sequence_number_set()
     }
 
+    /// Return the [`BatchIdent`] assigned by the most recent call to
+    /// [`Self::mark_persisting()`].
+    pub(crate) fn last_persisting_ident(&self) -> BatchIdent {
+        self.started_persistence_count
+    }
+
+    /// Returns true if a persist operation started at or before the one
+    /// identified by `ident` has not yet been marked as persisted.
+    ///
+    /// Persist operations may complete out of order, so a later persist
+    /// completing does not imply earlier persists are complete.
+    pub(crate) fn is_persisting_through(&self, ident: BatchIdent) -> bool {
+        self.persisting.oldest_ident().is_some_and(|v| v <= ident)
+    }
+
     pub(crate) fn partition_id(&self) -> &TransitionPartitionId {
         &self.partition_id
     }
@@ -513,8 +538,16 @@ mod tests {
         assert_eq!(p.started_persistence_count.get(), 0);
         assert_eq!(p.completed_persistence_count, 0);
 
+        // All the buffered data is in the "hot" buffer.
+        assert!(p.buffered_bytes() > 0);
+        assert_eq!(p.buffered_bytes(), p.persist_cost_estimate());
+
         // Begin persisting the partition.
         let persisting_data = p.mark_persisting().expect("must contain existing data");
+
+        // The persisting data remains in memory until persisted.
+        assert_eq!(p.persist_cost_estimate(), 0);
+        assert!(p.buffered_bytes() > 0);
         // And validate the data being persisted.
         assert_eq!(
             persisting_data.partition_id(),
@@ -570,6 +603,10 @@ mod tests {
         assert_eq!(p.started_persistence_count.get(), 1);
         assert_eq!(p.completed_persistence_count, 1);
 
+        // Only the second write remains buffered.
+        assert!(p.buffered_bytes() > 0);
+        assert_eq!(p.buffered_bytes(), p.persist_cost_estimate());
+
         // Querying the buffer should now return only the second write.
         {
             let data = p
@@ -889,6 +926,8 @@ mod tests {
         // adding it to the persisting queue ordered such that querying returns
         // the correctly ordered rows (newest rows last).
         let persisting_data3 = p.mark_persisting().unwrap();
+        assert_eq!(p.last_persisting_ident(), persisting_data3.batch_ident());
+        let ident2 = persisting_data2.batch_ident();
 
         // Buffer another write, and generate a snapshot by querying it.
         let mb = lp_to_mutable_batch(r#"bananas x=4 42"#).1;
@@ -935,6 +974,10 @@ mod tests {
         assert_eq!(set.len(), 1);
         assert!(set.contains(SequenceNumber::new(4)));
 
+        // The first batch is still persisting, so persistence of all the
+        // batches up to and including the second is not yet complete.
+        assert!(p.is_persisting_through(ident2));
+
         let data = p.get_query_data(&OwnedProjection::default()).unwrap();
         assert_batches_eq!(
             [
@@ -952,6 +995,7 @@ mod tests {
         let set = p.mark_persisted(persisting_data1);
         assert_eq!(set.len(), 1);
         assert!(set.contains(SequenceNumber::new(1)));
+        assert!(!p.is_persisting_through(p.last_persisting_ident()));
 
         // Assert only the buffered data remains
         let data = p.get_query_data(&OwnedProjection::default()).unwrap();
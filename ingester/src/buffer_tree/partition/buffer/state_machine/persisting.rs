@@ -22,6 +22,9 @@ pub(crate) struct Persisting {
     row_count: usize,
     timestamp_stats: TimestampMinMax,
     schema: Schema,
+
+    /// The memory used by the snapshots, in bytes.
+    size: usize,
 }
 
 impl Persisting {
@@ -43,11 +46,17 @@ impl Persisting {
         );
         debug_assert_eq!(schema, merge_record_batch_schemas(&snapshots));
 
+        let size = snapshots
+            .iter()
+            .map(RecordBatch::get_array_memory_size)
+            .sum();
+
         Self {
             snapshots,
             row_count,
             timestamp_stats,
             schema,
+            size,
         }
     }
 }
@@ -71,6 +80,11 @@ impl Queryable for Persisting {
 }
 
 impl BufferState<Persisting> {
+    /// Return the memory used by the persisting data, in bytes.
+    pub(crate) fn size(&self) -> usize {
+        self.state.size
+    }
+
     /// Consume `self` and all references to the buffered data, returning the owned
     /// [`SequenceNumberSet`] within it.
     pub(crate) fn into_sequence_number_set(self) -> SequenceNumberSet {
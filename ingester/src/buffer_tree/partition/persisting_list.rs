@@ -65,6 +65,7 @@ impl PersistingList {
                 // with, so skip merging schemas.
                 self.cached = Some(CachedStats {
                     rows: buffer.rows(),
+                    size: buffer.size(),
                     timestamps: buffer
                         .timestamp_stats()
                         .expect("persisting batch must contain timestamps"),
@@ -112,6 +113,11 @@ impl PersistingList {
         fsm
     }
 
+    /// Returns the [`BatchIdent`] of the oldest batch in this list, if any.
+    pub(crate) fn oldest_ident(&self) -> Option<BatchIdent> {
+        self.persisting.front().map(|(ident, _)| *ident)
+    }
+
     pub(crate) fn is_empty(&self) -> bool {
         self.persisting.is_empty()
     }
@@ -123,6 +129,13 @@ impl PersistingList {
         self.cached.as_ref().map(|v| v.rows).unwrap_or_default()
     }
 
+    /// Returns the memory used by all batches in this list, in bytes.
+    ///
+    /// This is an `O(1)` operation.
+    pub(crate) fn size(&self) -> usize {
+        self.cached.as_ref().map(|v| v.size).unwrap_or_default()
+    }
+
     /// Returns the timestamp min/max values across all batches in this list.
     ///
     /// This is an `O(1)` operation.
@@ -156,6 +169,7 @@ impl PersistingList {
 #[derive(Debug)]
 struct CachedStats {
     rows: usize,
+    size: usize,
     timestamps: TimestampMinMax,
 
     /// The merged schema of all the persisting batches.
@@ -183,12 +197,15 @@ impl CachedStats {
         let mut rows = v.rows();
         debug_assert!(rows > 0);
 
+        let mut size = v.size();
+
         let mut timestamps = v
             .timestamp_stats()
             .expect("unprojected batch should have timestamp");
 
         for buf in iter {
             rows += buf.rows();
+            size += buf.size();
             if let Some(v) = buf.schema() {
                 debug_assert!(buf.rows() > 0);
 
@@ -207,6 +224,7 @@ impl CachedStats {
 
         Some(Self {
             rows,
+            size,
             timestamps,
             schema: schema.build(),
         })
@@ -219,6 +237,7 @@ impl CachedStats {
         // the stats will be left in an inconsistent state.
 
         self.rows += buffer.rows();
+        self.size += buffer.size();
 
         let ts = buffer
             .timestamp_stats()
@@ -373,14 +392,15 @@ mod tests {
 
         // Generate a buffer with a single row.
         let first_batch = ident_oracle.next();
-        list.push(
-            first_batch,
-            buffer_with_lp(r#"bananas,tag=platanos great="yes" 42"#),
-        );
+        let first = buffer_with_lp(r#"bananas,tag=platanos great="yes" 42"#);
+        let first_size = first.size();
+        assert!(first_size > 0);
+        list.push(first_batch, first);
 
         // The statistics must now match the expected values.
         assert!(!list.is_empty());
         assert_eq!(list.rows(), 1);
+        assert_eq!(list.size(), first_size);
         assert_matches!(
             list.timestamp_stats(),
             Some(TimestampMinMax { min: 42, max: 42 })
@@ -389,14 +409,15 @@ mod tests {
 
         // Push another row.
         let second_batch = ident_oracle.next();
-        list.push(
-            second_batch,
-            buffer_with_lp(r#"bananas,another=yes great="definitely",incremental=true 4242"#),
-        );
+        let second =
+            buffer_with_lp(r#"bananas,another=yes great="definitely",incremental=true 4242"#);
+        let second_size = second.size();
+        list.push(second_batch, second);
 
         // The statistics must now match the expected values.
         assert!(!list.is_empty());
         assert_eq!(list.rows(), 2);
+        assert_eq!(list.size(), first_size + second_size);
         assert_matches!(
             list.timestamp_stats(),
             Some(TimestampMinMax { min: 42, max: 4242 })
@@ -412,6 +433,7 @@ mod tests {
         // The statistics must now match the second batch values.
         assert!(!list.is_empty());
         assert_eq!(list.rows(), 1);
+        assert_eq!(list.size(), second_size);
         assert_matches!(
             list.timestamp_stats(),
             Some(TimestampMinMax {
@@ -429,6 +451,7 @@ mod tests {
 
         assert!(list.is_empty());
         assert_eq!(list.rows(), 0);
+        assert_eq!(list.size(), 0);
         assert_matches!(list.timestamp_stats(), None);
         assert_matches!(list.schema(), None);
     }
@@ -123,6 +123,25 @@ impl<T> PartitionCache<T> {
         }
     }
 
+    /// Add `partitions` to the cache, such as partitions created in the
+    /// catalog ahead of the first write to them.
+    ///
+    /// Existing entries are left unchanged. As with the initial entries, each
+    /// entry is removed by the first lookup that hits it.
+    pub(crate) fn insert<P>(&self, partitions: P)
+    where
+        P: IntoIterator<Item = Partition>,
+    {
+        let mut entries = self.entries.lock();
+        for p in partitions {
+            entries
+                .entry(p.partition_key.clone())
+                .or_default()
+                .entry(p.table_id)
+                .or_insert_with(|| p.transition_partition_id());
+        }
+    }
+
     /// Search for a cached entry matching the `(partition_key, table_id)`
     /// tuple.
     fn find(
@@ -336,6 +355,38 @@ mod tests {
         assert!(!got.lock().partition_key().ptr_eq(&callers_partition_key));
     }
 
+    #[tokio::test]
+    async fn test_insert_hit() {
+        let cache = new_cache(MockPartitionProvider::default(), []);
+
+        cache.insert([Partition::new_in_memory_only(
+            ARBITRARY_CATALOG_PARTITION_ID,
+            ARBITRARY_TABLE_ID,
+            ARBITRARY_PARTITION_KEY.clone(),
+            Default::default(),
+            None,
+            Default::default(),
+        )]);
+
+        // The inserted partition is resolved without calling the inner
+        // provider.
+        let got = cache
+            .get_partition(
+                ARBITRARY_PARTITION_KEY.clone(),
+                ARBITRARY_NAMESPACE_ID,
+                defer_namespace_name_1_sec(),
+                ARBITRARY_TABLE_ID,
+                defer_table_metadata_1_sec(),
+            )
+            .await;
+
+        assert_eq!(
+            got.lock().partition_id(),
+            &*ARBITRARY_TRANSITION_PARTITION_ID
+        );
+        assert!(cache.entries.lock().is_empty());
+    }
+
     #[tokio::test]
     async fn test_miss_partition_key() {
         let other_key = PartitionKey::from("test");
@@ -20,5 +20,8 @@ pub(crate) use coalesce::*;
 mod old_filter;
 pub(crate) use old_filter::*;
 
+mod precreate;
+pub(crate) use precreate::*;
+
 #[cfg(test)]
 pub(crate) mod mock;
@@ -0,0 +1,335 @@
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use data_types::{
+    partition_template::{TablePartitionTemplateOverride, TemplatePart},
+    PartitionKey, TableId,
+};
+use iox_catalog::interface::Catalog;
+use iox_time::{SystemProvider, TimeProvider};
+use metric::U64Counter;
+use mutable_batch::{writer::Writer, MutableBatch, PartitionWrite};
+use observability_deps::tracing::*;
+use schema::TIME_COLUMN_NAME;
+use tokio::time::MissedTickBehavior;
+
+use super::PartitionCache;
+use crate::partition_iter::PartitionIter;
+
+/// The interval of time between predictions of the upcoming partitions of the
+/// buffered tables.
+const PRECREATE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Creates the catalog records of upcoming time-based partitions ahead of the
+/// first write to them.
+///
+/// Every [`PRECREATE_INTERVAL`], the partition key each table with buffered
+/// data will use `lookahead` from now is derived from its partition template.
+/// If it differs from the key in use now, the partitions are created in the
+/// catalog with a single batched upsert and added to the [`PartitionCache`],
+/// so that the first write to them does not incur a catalog round-trip.
+///
+/// This spreads the catalog load of the burst of new partitions created at a
+/// partition boundary (such as the start of a new day) over time, and moves
+/// it off the write path. The `lookahead` should exceed
+/// [`PRECREATE_INTERVAL`] for partitions to be created before they are
+/// written to.
+///
+/// The keys of tables partitioned by tag values cannot be predicted, and such
+/// tables are ignored.
+#[derive(Debug)]
+pub(crate) struct PartitionPreCreator<T, P, C = SystemProvider> {
+    buffer: T,
+    cache: Arc<PartitionCache<P>>,
+    catalog: Arc<dyn Catalog>,
+    lookahead: Duration,
+    time_provider: C,
+
+    /// The most recent partition key created for each table, used to skip
+    /// tables that have already had their upcoming partition created.
+    created: HashMap<TableId, PartitionKey>,
+
+    /// The number of partitions created ahead of time.
+    precreated_count: U64Counter,
+}
+
+impl<T, P> PartitionPreCreator<T, P> {
+    pub(crate) fn new(
+        buffer: T,
+        cache: Arc<PartitionCache<P>>,
+        catalog: Arc<dyn Catalog>,
+        lookahead: Duration,
+        metrics: &metric::Registry,
+    ) -> Self {
+        let precreated_count = metrics
+            .register_metric::<U64Counter>(
+                "ingester_partition_precreated",
+                "number of partitions created in the catalog ahead of the first write to them",
+            )
+            .recorder(&[]);
+
+        Self {
+            buffer,
+            cache,
+            catalog,
+            lookahead,
+            time_provider: Default::default(),
+            created: Default::default(),
+            precreated_count,
+        }
+    }
+}
+
+impl<T, P, C> PartitionPreCreator<T, P, C> {
+    #[cfg(test)]
+    fn with_time_provider<U>(self, time_provider: U) -> PartitionPreCreator<T, P, U>
+    where
+        U: TimeProvider,
+    {
+        PartitionPreCreator {
+            buffer: self.buffer,
+            cache: self.cache,
+            catalog: self.catalog,
+            lookahead: self.lookahead,
+            time_provider,
+            created: self.created,
+            precreated_count: self.precreated_count,
+        }
+    }
+}
+
+impl<T, P, C> PartitionPreCreator<T, P, C>
+where
+    T: PartitionIter + Sync + 'static,
+    P: Send + Sync,
+    C: TimeProvider,
+{
+    /// Create the upcoming partitions every [`PRECREATE_INTERVAL`], forever.
+    pub(crate) async fn run(mut self) {
+        let mut interval = tokio::time::interval(PRECREATE_INTERVAL);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            interval.tick().await;
+            self.precreate().await;
+        }
+    }
+
+    /// Create the upcoming partitions of the tables with buffered data that
+    /// have not yet been created.
+    async fn precreate(&mut self) {
+        let now = self.time_provider.now();
+        let Some(upcoming) = now.checked_add(self.lookahead) else {
+            return;
+        };
+
+        // Resolve the set of tables with buffered data.
+        let mut tables = HashMap::new();
+        for p in self.buffer.partition_iter() {
+            let p = p.lock();
+            tables
+                .entry(p.table_id())
+                .or_insert_with(|| Arc::clone(p.table()));
+        }
+
+        // Forget tables that no longer have buffered data.
+        self.created.retain(|id, _| tables.contains_key(id));
+
+        let mut want = vec![];
+        for (table_id, table) in tables {
+            let table = table.get().await;
+            let Some(key) = upcoming_key(
+                table.partition_template(),
+                now.timestamp_nanos(),
+                upcoming.timestamp_nanos(),
+            ) else {
+                continue;
+            };
+
+            if self.created.get(&table_id) != Some(&key) {
+                want.push((table_id, key));
+            }
+        }
+
+        if want.is_empty() {
+            return;
+        }
+
+        // Failures are retried at the next interval, and in the meantime the
+        // partitions are created on demand by the write path.
+        let partitions = match self
+            .catalog
+            .repositories()
+            .await
+            .partitions()
+            .create_or_get_many(&want)
+            .await
+        {
+            Ok(v) => v,
+            Err(e) => {
+                warn!(error=%e, n_partitions=want.len(), "failed to pre-create partitions");
+                return;
+            }
+        };
+
+        debug!(n_partitions = partitions.len(), "pre-created partitions");
+        self.precreated_count.inc(partitions.len() as u64);
+        self.cache.insert(partitions);
+        self.created.extend(want);
+    }
+}
+
+/// Return the partition key `template` derives for the timestamp `upcoming`,
+/// if it can be predicted and differs from the key derived for `now`.
+fn upcoming_key(
+    template: &TablePartitionTemplateOverride,
+    now: i64,
+    upcoming: i64,
+) -> Option<PartitionKey> {
+    // Keys containing tag values depend on the data written.
+    if template
+        .parts()
+        .any(|v| matches!(v, TemplatePart::TagValue(_)))
+    {
+        return None;
+    }
+
+    let upcoming = partition_key(template, upcoming)?;
+    (partition_key(template, now)? != upcoming).then_some(upcoming)
+}
+
+/// Derive the partition key of a row with timestamp `t` from `template`.
+fn partition_key(template: &TablePartitionTemplateOverride, t: i64) -> Option<PartitionKey> {
+    let mut batch = MutableBatch::new();
+    let mut writer = Writer::new(&mut batch, 1);
+    writer
+        .write_time(TIME_COLUMN_NAME, std::iter::once(t))
+        .expect("write to empty batch");
+    writer.commit();
+
+    PartitionWrite::partition(&batch, template)
+        .ok()?
+        .into_keys()
+        .next()
+}
+
+#[cfg(test)]
+mod tests {
+    use backoff::BackoffConfig;
+    use data_types::{partition_template::test_table_partition_override, NamespaceId};
+    use iox_catalog::{
+        mem::MemCatalog,
+        test_helpers::{arbitrary_namespace, arbitrary_table},
+    };
+    use iox_time::{MockProvider, Time};
+    use metric::{Attributes, Metric};
+    use parking_lot::Mutex;
+
+    use super::*;
+    use crate::{
+        buffer_tree::partition::{resolver::mock::MockPartitionProvider, PartitionData},
+        test_util::{defer_namespace_name_1_sec, defer_table_metadata_1_sec, PartitionDataBuilder},
+    };
+
+    /// 2023-01-01T23:30:00Z
+    const NOW: i64 = 1_672_615_800_000_000_000;
+
+    #[test]
+    fn test_upcoming_key() {
+        let day = TablePartitionTemplateOverride::default();
+        let hour = Duration::from_secs(60 * 60).as_nanos() as i64;
+
+        // The upcoming partition is still today's partition.
+        assert_eq!(upcoming_key(&day, NOW, NOW + hour / 4), None);
+
+        // The upcoming partition is tomorrow's partition.
+        assert_eq!(
+            upcoming_key(&day, NOW, NOW + hour),
+            Some(PartitionKey::from("2023-01-02"))
+        );
+
+        // Keys of templates with tag values cannot be predicted.
+        let tags = test_table_partition_override(vec![
+            TemplatePart::TagValue("region"),
+            TemplatePart::TimeFormat("%Y-%m-%d"),
+        ]);
+        assert_eq!(upcoming_key(&tags, NOW, NOW + hour), None);
+    }
+
+    #[tokio::test]
+    async fn test_precreate() {
+        let metrics = Arc::new(metric::Registry::default());
+        let catalog: Arc<dyn Catalog> = Arc::new(MemCatalog::new(Arc::clone(&metrics)));
+
+        let table_id = {
+            let mut repos = catalog.repositories().await;
+            let ns = arbitrary_namespace(&mut *repos, "ns-bananas").await;
+            arbitrary_table(&mut *repos, "bananas", &ns).await.id
+        };
+
+        let partition: Arc<Mutex<PartitionData>> = Arc::new(Mutex::new(
+            PartitionDataBuilder::new().with_table_id(table_id).build(),
+        ));
+
+        let cache = Arc::new(PartitionCache::new(
+            MockPartitionProvider::default(),
+            [],
+            Duration::from_secs(10_000_000),
+            Arc::clone(&catalog),
+            BackoffConfig::default(),
+            Arc::clone(&metrics),
+        ));
+
+        let time_provider = Arc::new(MockProvider::new(Time::from_timestamp_nanos(NOW)));
+        let mut precreator = PartitionPreCreator::new(
+            vec![partition],
+            Arc::clone(&cache),
+            Arc::clone(&catalog),
+            Duration::from_secs(60 * 60),
+            &metrics,
+        )
+        .with_time_provider(Arc::clone(&time_provider));
+
+        let precreated = || {
+            metrics
+                .get_instrument::<Metric<U64Counter>>("ingester_partition_precreated")
+                .expect("metric must exist")
+                .get_observer(&Attributes::from(&[]))
+                .expect("observer must exist")
+                .fetch()
+        };
+
+        // Tomorrow's partition is created in the catalog.
+        precreator.precreate().await;
+        assert_eq!(precreated(), 1);
+        let partitions = catalog
+            .repositories()
+            .await
+            .partitions()
+            .list_by_table_id(table_id)
+            .await
+            .unwrap();
+        assert_matches::assert_matches!(partitions.as_slice(), [p] => {
+            assert_eq!(p.partition_key, PartitionKey::from("2023-01-02"));
+        });
+
+        // And resolved from the cache.
+        let got = cache
+            .get_partition(
+                PartitionKey::from("2023-01-02"),
+                NamespaceId::new(1),
+                defer_namespace_name_1_sec(),
+                table_id,
+                defer_table_metadata_1_sec(),
+            )
+            .await;
+        assert_eq!(
+            got.lock().partition_id(),
+            &partitions[0].transition_partition_id()
+        );
+
+        // The partition is not created again.
+        time_provider.inc(Duration::from_secs(60));
+        precreator.precreate().await;
+        assert_eq!(precreated(), 1);
+    }
+}
@@ -23,6 +23,15 @@ pub(crate) enum IngestStateError {
 
     #[error("ingester disk full - persisting write-ahead log")]
     DiskFull = 1 << 2,
+
+    #[error("ingester write-ahead log fsync failed")]
+    WalSyncFailed = 1 << 3,
+
+    #[error("ingester buffered data exceeds memory limit - persisting buffered data")]
+    MemoryLimit = 1 << 4,
+
+    #[error("ingester fenced by its wal replication follower")]
+    ReplicationFenced = 1 << 5,
 }
 
 impl IngestStateError {
@@ -136,7 +145,11 @@ impl IngestState {
     /// the following precedence (ordered by highest priority to lowest):
     ///
     ///   1. [`IngestStateError::GracefulStop`]
-    ///   2. [`IngestStateError::PersistSaturated`].
+    ///   2. [`IngestStateError::WalSyncFailed`]
+    ///   3. [`IngestStateError::ReplicationFenced`]
+    ///   4. [`IngestStateError::DiskFull`]
+    ///   5. [`IngestStateError::MemoryLimit`]
+    ///   6. [`IngestStateError::PersistSaturated`].
     ///
     pub(crate) fn read(&self) -> Result<(), IngestStateError> {
         let current = self.state.load(Ordering::Relaxed);
@@ -162,10 +175,22 @@ fn as_err(state: usize) -> Result<(), IngestStateError> {
         return Err(IngestStateError::GracefulStop);
     }
 
+    if state & IngestStateError::WalSyncFailed.as_bits() != 0 {
+        return Err(IngestStateError::WalSyncFailed);
+    }
+
+    if state & IngestStateError::ReplicationFenced.as_bits() != 0 {
+        return Err(IngestStateError::ReplicationFenced);
+    }
+
     if state & IngestStateError::DiskFull.as_bits() != 0 {
         return Err(IngestStateError::DiskFull);
     }
 
+    if state & IngestStateError::MemoryLimit.as_bits() != 0 {
+        return Err(IngestStateError::MemoryLimit);
+    }
+
     if state & IngestStateError::PersistSaturated.as_bits() != 0 {
         return Err(IngestStateError::PersistSaturated);
     }
@@ -218,6 +243,32 @@ mod tests {
         // Un-setting the disk full state then shows the persist saturated state.
         state.unset(IngestStateError::DiskFull);
         assert_matches!(state.read(), Err(IngestStateError::PersistSaturated));
+
+        // A WAL fsync failure takes precedence over all but the shutdown state.
+        state.set(IngestStateError::DiskFull);
+        state.set(IngestStateError::WalSyncFailed);
+        assert_matches!(state.read(), Err(IngestStateError::WalSyncFailed));
+        state.set(IngestStateError::GracefulStop);
+        assert_matches!(state.read(), Err(IngestStateError::GracefulStop));
+
+        // The memory limit state takes precedence over only the persist
+        // saturated state.
+        state.unset(IngestStateError::GracefulStop);
+        state.unset(IngestStateError::WalSyncFailed);
+        state.set(IngestStateError::MemoryLimit);
+        assert_matches!(state.read(), Err(IngestStateError::DiskFull));
+        state.unset(IngestStateError::DiskFull);
+        assert_matches!(state.read(), Err(IngestStateError::MemoryLimit));
+        state.unset(IngestStateError::MemoryLimit);
+        assert_matches!(state.read(), Err(IngestStateError::PersistSaturated));
+
+        // Being fenced by the replication follower takes precedence over all
+        // but the shutdown and WAL fsync failure states.
+        state.set(IngestStateError::DiskFull);
+        state.set(IngestStateError::ReplicationFenced);
+        assert_matches!(state.read(), Err(IngestStateError::ReplicationFenced));
+        state.set(IngestStateError::WalSyncFailed);
+        assert_matches!(state.read(), Err(IngestStateError::WalSyncFailed));
     }
 
     /// A hand-rolled strategy to enumerate [`IngestStateError`] variants.
@@ -226,7 +277,10 @@ mod tests {
         prop_oneof![
             Just(IngestStateError::PersistSaturated),
             Just(IngestStateError::GracefulStop),
-            Just(IngestStateError::DiskFull)
+            Just(IngestStateError::DiskFull),
+            Just(IngestStateError::WalSyncFailed),
+            Just(IngestStateError::MemoryLimit),
+            Just(IngestStateError::ReplicationFenced)
         ]
     }
 
@@ -239,6 +293,8 @@ mod tests {
                 IngestStateError::PersistSaturated => {}
                 IngestStateError::GracefulStop => {}
                 IngestStateError::DiskFull => {}
+                IngestStateError::WalSyncFailed => {}
+                IngestStateError::MemoryLimit => {}
             }
         }
 
@@ -7,6 +7,9 @@ pub use wal_replay::*;
 mod graceful_shutdown;
 mod wal_replay;
 
+pub use wal::{FsyncPolicy, SegmentCodec, WalConfig};
+pub use wal_replay::WalReplayMode;
+
 use std::{net::SocketAddr, path::PathBuf, sync::Arc, time::Duration};
 
 use arrow_flight::flight_service_server::FlightService;
@@ -15,7 +18,10 @@ use futures::{future::Shared, Future, FutureExt};
 use generated_types::influxdata::iox::{
     catalog::v1::catalog_service_server::CatalogService,
     gossip::Topic,
-    ingester::v1::{persist_service_server::PersistService, write_service_server::WriteService},
+    ingester::v1::{
+        persist_service_server::PersistService, replication_service_server::ReplicationService,
+        write_service_server::WriteService,
+    },
 };
 use iox_catalog::interface::Catalog;
 use iox_query::exec::Executor;
@@ -32,7 +38,7 @@ use crate::{
         namespace::name_resolver::{NamespaceNameProvider, NamespaceNameResolver},
         partition::resolver::{
             CatalogPartitionResolver, CoalescePartitionResolver, OldPartitionBloomFilter,
-            PartitionCache, PartitionProvider,
+            PartitionCache, PartitionPreCreator, PartitionProvider,
         },
         table::metadata_resolver::{TableProvider, TableResolver},
         BufferTree,
@@ -42,7 +48,7 @@ use crate::{
     ingester_id::IngesterId,
     persist::{
         file_metrics::ParquetFileInstrumentation, handle::PersistHandle,
-        hot_partitions::HotPartitionPersister,
+        hot_partitions::HotPartitionPersister, memory_limit::MemoryLimiter,
     },
     query::{
         exec_instrumentation::QueryExecInstrumentation,
@@ -51,7 +57,15 @@ use crate::{
     server::grpc::GrpcDelegate,
     timestamp_oracle::TimestampOracle,
     wal::{
-        reference_tracker::WalReferenceHandle, rotate_task::periodic_rotation, wal_sink::WalSink,
+        archive::WalArchiver,
+        reference_tracker::{WalFileDeleter, WalReferenceHandle},
+        replication::{
+            periodic_heartbeat, periodic_takeover, FollowerClient, ReplicaStore, ReplicatingWal,
+            ReplicationReleaser,
+        },
+        rotate_task::periodic_rotation,
+        sync_failure::watch_sync_failure,
+        wal_sink::WalSink,
     },
 };
 
@@ -73,6 +87,8 @@ pub trait IngesterRpcInterface: Send + Sync + std::fmt::Debug {
     type WriteHandler: WriteService;
     /// The type of the [`PersistService`] implementation.
     type PersistHandler: PersistService;
+    /// The type of the [`ReplicationService`] implementation.
+    type ReplicationHandler: ReplicationService;
     /// The type of the [`FlightService`] implementation.
     type FlightHandler: FlightService;
 
@@ -88,6 +104,10 @@ pub trait IngesterRpcInterface: Send + Sync + std::fmt::Debug {
     /// handler implementation.
     fn persist_service(&self) -> Self::PersistHandler;
 
+    /// Acquire an opaque handle to the Ingester's [`ReplicationService`] RPC
+    /// handler implementation, serving the follower side of WAL replication.
+    fn replication_service(&self) -> Self::ReplicationHandler;
+
     /// Acquire an opaque handle to the Ingester's Arrow Flight
     /// [`FlightService`] RPC handler implementation, allowing at most
     /// `max_simultaneous_requests` queries to be running at any one time.
@@ -116,6 +136,37 @@ pub struct IngesterGuard<T> {
 
     /// An optional handle to the gossip sub-system, if running.
     gossip_handle: Option<GossipHandle>,
+
+    /// The handle of the periodic heartbeat task sending to the WAL
+    /// replication follower, if configured.
+    ///
+    /// Aborted on drop.
+    replication_heartbeat_task: Option<tokio::task::JoinHandle<()>>,
+
+    /// The handle of the periodic task taking over the replica WALs of timed
+    /// out replication leaders, if following any.
+    ///
+    /// Aborted on drop.
+    replica_takeover_task: Option<tokio::task::JoinHandle<()>>,
+
+    /// The handle of the task uploading WAL segments to object storage before
+    /// deleting them, if enabled.
+    ///
+    /// Aborted on drop.
+    wal_archive_task: Option<tokio::task::JoinHandle<()>>,
+
+    /// The handle of the task rejecting writes once an fsync of the WAL fails.
+    ///
+    /// Aborted on drop.
+    wal_sync_failure_task: tokio::task::JoinHandle<()>,
+
+    /// The task handle for the memory limit evaluation loop, if enabled.
+    memory_limit_task: Option<tokio::task::JoinHandle<()>>,
+
+    /// The handle of the periodic partition pre-creation task, if enabled.
+    ///
+    /// Aborted on drop.
+    partition_precreate_task: Option<tokio::task::JoinHandle<()>>,
 }
 
 impl<T> IngesterGuard<T>
@@ -141,6 +192,22 @@ impl<T> Drop for IngesterGuard<T> {
         self.rotation_task.abort();
         self.disk_metric_task.abort();
         self.graceful_shutdown_handler.abort();
+        if let Some(t) = &self.replication_heartbeat_task {
+            t.abort();
+        }
+        if let Some(t) = &self.replica_takeover_task {
+            t.abort();
+        }
+        if let Some(t) = &self.wal_archive_task {
+            t.abort();
+        }
+        self.wal_sync_failure_task.abort();
+        if let Some(t) = &self.memory_limit_task {
+            t.abort();
+        }
+        if let Some(t) = &self.partition_precreate_task {
+            t.abort();
+        }
     }
 }
 
@@ -165,6 +232,85 @@ pub enum GossipConfig {
     },
 }
 
+/// Configuration parameters for the optional archival of WAL segment files.
+#[derive(Debug, Default)]
+pub enum WalArchiveConfig {
+    /// Delete WAL segment files once all the data they contain is persisted.
+    #[default]
+    Disabled,
+
+    /// Upload each WAL segment file to object storage before it is deleted.
+    Enabled {
+        /// The object storage path prefix to store archived segment files
+        /// under. Each ingester instance must use its own prefix.
+        prefix: String,
+        /// Archived segment files older than this are removed from object
+        /// storage. When [`None`], archived segment files are retained
+        /// indefinitely.
+        retention: Option<Duration>,
+    },
+}
+
+/// Configuration parameters for the optional synchronous replication of the
+/// WAL to a follower ingester.
+#[derive(Debug, Default)]
+pub enum WalReplicationConfig {
+    /// Do not replicate the WAL.
+    #[default]
+    Disabled,
+
+    /// Replicate every WAL entry to the follower ingester at `follower_addr`,
+    /// acknowledging writes only once they are durable in both WALs.
+    Leader {
+        /// The gRPC address of the follower ingester.
+        follower_addr: String,
+        /// The identifier of this ingester's replica on the follower, which
+        /// must remain the same across restarts.
+        leader_id: String,
+    },
+}
+
+/// Configuration parameters for serving as the follower of WAL replication
+/// leaders.
+#[derive(Debug, Default)]
+pub enum WalReplicaConfig {
+    /// Reject all WAL replication requests.
+    #[default]
+    Disabled,
+
+    /// Keep a replica WAL for each of the leaders in `leader_ids`, rejecting
+    /// replication requests from any other leader.
+    Enabled {
+        /// The identifiers of the leaders to accept replication requests from.
+        leader_ids: Vec<String>,
+        /// The duration without contact from a leader after which its
+        /// unreleased writes are ingested by this ingester.
+        takeover_timeout: Duration,
+    },
+}
+
+/// Configuration parameters for the optional bound on the memory used by data
+/// buffered in the ingester.
+#[derive(Debug, Default)]
+pub enum MemoryLimitConfig {
+    /// Do not bound the memory used by buffered data.
+    #[default]
+    Disabled,
+
+    /// Persist the largest partitions once the buffered data exceeds
+    /// `high_watermark_bytes`, and reject writes once it exceeds
+    /// `hard_limit_bytes`.
+    Enabled {
+        /// The memory used by buffered data above which partitions are
+        /// persisted to release it.
+        high_watermark_bytes: usize,
+        /// The memory used by buffered data above which writes are rejected,
+        /// until it drops below `high_watermark_bytes`. Must be no less than
+        /// `high_watermark_bytes`.
+        hard_limit_bytes: usize,
+    },
+}
+
 /// Errors that occur during initialisation of an `ingester` instance.
 #[derive(Debug, Error)]
 pub enum InitError {
@@ -185,6 +331,14 @@ pub enum InitError {
     #[error(transparent)]
     WalReplay(Box<dyn std::error::Error>),
 
+    /// The address of the WAL replication follower is invalid.
+    #[error("invalid wal replication follower address: {0}")]
+    WalReplicationFollower(tonic::transport::Error),
+
+    /// An error loading the replica WALs kept for replication leaders.
+    #[error("failed to load wal replicas: {0}")]
+    WalReplicas(Box<dyn std::error::Error>),
+
     /// An error binding the UDP socket for gossip communication.
     #[error("failed to bind udp gossip socket: {0}")]
     GossipBind(std::io::Error),
@@ -203,7 +357,29 @@ pub enum InitError {
 ///
 /// These files are read and replayed fully before this function returns.
 ///
-/// Any error during replay is fatal.
+/// New writes are made durable according to the [`FsyncPolicy`] of
+/// `wal_config`, with new segment files compressed with its [`SegmentCodec`].
+/// Should an fsync of the WAL fail, all further writes are rejected until the
+/// ingester is restarted.
+///
+/// With [`WalReplayMode::Tolerant`], damaged entries and ops within the files
+/// are skipped, and the files containing them moved to a quarantine directory
+/// within `wal_directory` once the rest of their data is persisted. Any other
+/// error during replay is fatal, as is any damage with
+/// [`WalReplayMode::Strict`].
+///
+/// ## WAL Replication
+///
+/// With [`WalReplicationConfig::Leader`], every op committed to the WAL is
+/// also written to a replica WAL on the follower ingester, and a write is
+/// acknowledged only once it is durable in both. Segments of the replica are
+/// released as the leader deletes the corresponding segments of its own WAL.
+///
+/// With [`WalReplicaConfig::Enabled`], the ingester serves as a follower for
+/// the configured leaders, keeping their replica WALs within `wal_directory`.
+/// If a leader is not heard from for the takeover timeout, the follower fences
+/// it, rejecting its further requests so that it stops acknowledging writes,
+/// and ingests its unreleased writes as its own.
 ///
 /// ## Graceful Shutdown
 ///
@@ -261,6 +437,34 @@ pub enum InitError {
 /// Decreasing this value increases the frequency of persist operations, and
 /// usually decreases the size of the resulting parquet files.
 ///
+/// ## Memory Limit
+///
+/// The hot partition cost bounds the data buffered per partition, but not the
+/// total across all partitions. When [`MemoryLimitConfig::Enabled`], the
+/// memory used by all buffered data (including data being persisted) is
+/// evaluated periodically. Exceeding the high watermark enqueues the
+/// partitions with the most buffered data for persistence, and exceeding the
+/// hard limit rejects writes with a retryable error until the memory used
+/// drops back below the high watermark.
+///
+/// ## Cold Data Splitting
+///
+/// Late-arriving writes buffered alongside recent writes in the same partition
+/// would otherwise be persisted to a single file spanning both. When
+/// `persist_cold_split_threshold` is set, rows with a timestamp older than the
+/// threshold relative to the newest row being persisted are written to a
+/// separate parquet file, giving each file a tighter time range.
+///
+/// ## Partition Pre-Creation
+///
+/// The first write to a new partition resolves it through the catalog, so the
+/// many partitions created at a time-based partition boundary (such as the
+/// start of a new day) cause a burst of catalog load on the write path. When
+/// `partition_precreate_lookahead` is set, the partition each table with
+/// buffered data will write to that far in the future is periodically derived
+/// from its partition template, and created in the catalog ahead of time with
+/// a batched request.
+///
 /// [`MutableBatch::size_data()`]: mutable_batch::MutableBatch::size_data
 #[allow(clippy::too_many_arguments)]
 pub async fn new<F>(
@@ -268,13 +472,21 @@ pub async fn new<F>(
     metrics: Arc<metric::Registry>,
     persist_background_fetch_time: Duration,
     wal_directory: PathBuf,
+    wal_config: WalConfig,
     wal_rotation_period: Duration,
+    wal_replay_mode: WalReplayMode,
     persist_executor: Arc<Executor>,
     persist_workers: usize,
     persist_queue_depth: usize,
     persist_hot_partition_cost: usize,
+    persist_cold_split_threshold: Option<Duration>,
+    partition_precreate_lookahead: Option<Duration>,
+    memory_limit: MemoryLimitConfig,
     object_store: ParquetStorage,
     gossip: GossipConfig,
+    wal_archive: WalArchiveConfig,
+    wal_replication: WalReplicationConfig,
+    wal_replica: WalReplicaConfig,
     shutdown: F,
 ) -> Result<IngesterGuard<impl IngesterRpcInterface>, InitError>
 where
@@ -335,15 +547,15 @@ where
         Arc::clone(&metrics),
         old_style,
     );
-    let partition_provider = PartitionCache::new(
+    let partition_cache = Arc::new(PartitionCache::new(
         partition_provider,
         recent_partitions,
         persist_background_fetch_time,
         Arc::clone(&catalog),
         BackoffConfig::default(),
         Arc::clone(&metrics),
-    );
-    let partition_provider: Arc<dyn PartitionProvider> = Arc::new(partition_provider);
+    ));
+    let partition_provider: Arc<dyn PartitionProvider> = Arc::clone(&partition_cache);
 
     // Initialise the ingest pause signal, used to propagate error conditions
     // between subsystems such that they cause an error to be returned in the
@@ -351,13 +563,83 @@ where
     let ingest_state = Arc::new(IngestState::default());
 
     // Initialise the WAL
-    let wal = Wal::new(wal_directory.clone())
+    let wal = Wal::with_config(wal_directory.clone(), wal_config)
         .await
         .map_err(InitError::WalInit)?;
 
-    // Prepare the WAL segment reference tracker
+    // Lazily connect to the WAL replication follower, if configured.
+    let follower = match wal_replication {
+        WalReplicationConfig::Disabled => None,
+        WalReplicationConfig::Leader {
+            follower_addr,
+            leader_id,
+        } => {
+            info!(%follower_addr, %leader_id, "replicating wal to follower");
+            Some(
+                FollowerClient::new(leader_id, follower_addr, Arc::clone(&ingest_state))
+                    .map_err(InitError::WalReplicationFollower)?,
+            )
+        }
+    };
+
+    // Open the replica WALs of the leaders replicating to this ingester, if
+    // configured.
+    let replica_store = match wal_replica {
+        WalReplicaConfig::Disabled => None,
+        WalReplicaConfig::Enabled {
+            leader_ids,
+            takeover_timeout,
+        } => {
+            info!(
+                ?leader_ids,
+                ?takeover_timeout,
+                "following wal replication leaders"
+            );
+            let store = ReplicaStore::new(
+                &wal_directory,
+                wal_config,
+                leader_ids,
+                takeover_timeout,
+                &metrics,
+            );
+            store
+                .load()
+                .await
+                .map_err(|e| InitError::WalReplicas(e.into()))?;
+            Some(Arc::new(store))
+        }
+    };
+
+    // Prepare the WAL segment deleter, archiving segments before deletion if
+    // configured.
+    let (wal_deleter, wal_archive): (Box<dyn WalFileDeleter>, _) = match wal_archive {
+        WalArchiveConfig::Disabled => (Box::new(Arc::clone(&wal)), None),
+        WalArchiveConfig::Enabled { prefix, retention } => {
+            info!(%prefix, ?retention, "archiving wal segments to object storage");
+            let (archiver, task) = WalArchiver::new(
+                Arc::clone(&wal),
+                Arc::clone(object_store.object_store()),
+                object_store::path::Path::from(prefix),
+                retention,
+                &metrics,
+            );
+            let drain = archiver.drain();
+            (Box::new(archiver), Some((tokio::spawn(task.run()), drain)))
+        }
+    };
+
+    // And release deleted segments from the follower's replica of the WAL.
+    let wal_deleter: Box<dyn WalFileDeleter> = match &follower {
+        None => wal_deleter,
+        Some(follower) => Box::new(ReplicationReleaser::new(
+            wal_deleter,
+            Arc::clone(&wal),
+            follower.clone(),
+        )),
+    };
+
     let (wal_reference_handle, wal_reference_actor) =
-        WalReferenceHandle::new(Arc::clone(&wal), &metrics);
+        WalReferenceHandle::new(wal_deleter, &metrics);
 
     // Spawn the persist workers to compact partition data, convert it into
     // Parquet files, and upload them to object storage.
@@ -372,6 +654,7 @@ where
         // attributes as metrics, and notifies the WAL segment reference tracker of
         // completed persist actions.
         ParquetFileInstrumentation::new(wal_reference_handle.clone(), &metrics),
+        persist_cold_split_threshold,
         &metrics,
     );
     let persist_handle = Arc::new(persist_handle);
@@ -411,10 +694,24 @@ where
     let disk_metric_task = tokio::task::spawn(disk_metric_task.run());
 
     // Replay the WAL log files, if any.
-    let max_sequence_number =
-        wal_replay::replay(&wal, &buffer, Arc::clone(&persist_handle), &metrics)
-            .await
-            .map_err(|e| InitError::WalReplay(e.into()))?;
+    let max_sequence_number = wal_replay::replay(
+        &wal,
+        &buffer,
+        Arc::clone(&persist_handle),
+        &metrics,
+        wal_replay_mode,
+    )
+    .await
+    .map_err(|e| InitError::WalReplay(e.into()))?;
+
+    // All the replayed writes are now persisted, so none of the writes in the
+    // follower's replica of the WAL need ingesting should this ingester be
+    // lost.
+    if let Some(follower) = &follower {
+        if let Err(error) = follower.release_all().await {
+            warn!(%error, "failed to release replayed writes from wal follower");
+        }
+    }
 
     // Build the chain of DmlSink that forms the write path.
     let write_path = DmlSinkInstrumentation::new(
@@ -427,7 +724,7 @@ where
                         DmlSinkTracing::new(Arc::clone(&buffer), "buffer"),
                         &metrics,
                     ),
-                    Arc::clone(&wal),
+                    ReplicatingWal::new(Arc::clone(&wal), follower.clone()),
                     wal_reference_handle.clone(),
                 ),
                 "wal",
@@ -436,6 +733,7 @@ where
         ),
         &metrics,
     );
+    let write_path = Arc::new(write_path);
 
     // And the chain of QueryExec that forms the read path.
     let read_path = QueryResultInstrumentation::new(Arc::clone(&buffer), &metrics);
@@ -464,6 +762,67 @@ where
         max_sequence_number.map(|v| v.get()).unwrap_or(0),
     ));
 
+    // Send heartbeats to the WAL replication follower, if configured.
+    let replication_heartbeat_task = follower.map(|f| tokio::spawn(periodic_heartbeat(f)));
+
+    // Take over the replica WALs of replication leaders that stop contacting
+    // this ingester, if following any.
+    let replica_takeover_task = replica_store.as_ref().map(|store| {
+        tokio::spawn(periodic_takeover(
+            Arc::clone(store),
+            Arc::clone(&write_path),
+            Arc::clone(&timestamp),
+            Arc::clone(&ingest_state),
+        ))
+    });
+
+    // Bound the memory used by the buffered data, if configured.
+    let memory_limit_task = match memory_limit {
+        MemoryLimitConfig::Disabled => None,
+        MemoryLimitConfig::Enabled {
+            high_watermark_bytes,
+            hard_limit_bytes,
+        } => {
+            info!(
+                high_watermark_bytes,
+                hard_limit_bytes, "bounding memory used by buffered data"
+            );
+            Some(tokio::spawn(
+                MemoryLimiter::new(
+                    Arc::clone(&buffer),
+                    Arc::clone(&persist_handle),
+                    Arc::clone(&ingest_state),
+                    high_watermark_bytes,
+                    hard_limit_bytes,
+                    &metrics,
+                )
+                .run(),
+            ))
+        }
+    };
+
+    // Create upcoming partitions ahead of the first write to them, if
+    // configured.
+    let partition_precreate_task = partition_precreate_lookahead.map(|lookahead| {
+        info!(?lookahead, "pre-creating upcoming partitions");
+        tokio::spawn(
+            PartitionPreCreator::new(
+                Arc::clone(&buffer),
+                partition_cache,
+                Arc::clone(&catalog),
+                lookahead,
+                &metrics,
+            )
+            .run(),
+        )
+    });
+
+    // Reject writes should an fsync of the WAL fail.
+    let wal_sync_failure_task = tokio::spawn(watch_sync_failure(
+        wal.sync_failed(),
+        Arc::clone(&ingest_state),
+    ));
+
     let (shutdown_tx, shutdown_rx) = oneshot::channel();
     let shutdown_task = tokio::spawn(graceful_shutdown_handler(
         shutdown,
@@ -473,6 +832,7 @@ where
         Arc::clone(&persist_handle),
         Arc::clone(&wal),
         wal_reference_handle,
+        wal_archive.as_ref().map(|(_, drain)| drain.clone()),
     ));
 
     // Optionally start the gossip subsystem
@@ -500,7 +860,7 @@ where
 
     Ok(IngesterGuard {
         rpc: GrpcDelegate::new(
-            Arc::new(write_path),
+            write_path,
             Arc::new(read_path),
             timestamp,
             ingest_state,
@@ -509,11 +869,18 @@ where
             metrics,
             buffer,
             persist_handle,
+            replica_store,
         ),
         rotation_task,
         disk_metric_task,
         graceful_shutdown_handler: shutdown_task,
         shutdown_complete: shutdown_rx.shared(),
         gossip_handle,
+        replication_heartbeat_task,
+        replica_takeover_task,
+        wal_archive_task: wal_archive.map(|(task, _)| task),
+        wal_sync_failure_task,
+        memory_limit_task,
+        partition_precreate_task,
     })
 }
@@ -10,7 +10,7 @@ use crate::{
     partition_iter::PartitionIter,
     persist::{drain_buffer::persist_partitions, queue::PersistQueue},
     query::projection::OwnedProjection,
-    wal::reference_tracker::WalReferenceHandle,
+    wal::{archive::WalArchiveDrain, reference_tracker::WalReferenceHandle},
 };
 
 /// Defines how often the shutdown task polls the partition buffers for
@@ -43,6 +43,7 @@ pub(super) async fn graceful_shutdown_handler<F, T, P>(
     persist: P,
     wal: Arc<wal::Wal>,
     wal_reference_handle: WalReferenceHandle,
+    wal_archive: Option<WalArchiveDrain>,
 ) where
     F: Future<Output = CancellationToken> + Send,
     T: PartitionIter + Sync,
@@ -129,6 +130,13 @@ pub(super) async fn graceful_shutdown_handler<F, T, P>(
     // to drop to empty.
     empty_waker.await;
 
+    // And for the deleted segments to be archived and removed from disk, if
+    // archival is enabled.
+    if let Some(drain) = wal_archive {
+        info!("waiting for wal segments to be archived");
+        drain.wait().await;
+    }
+
     info!("persisted all data - stopping ingester");
 
     // Stop the RPC server (and therefore stop accepting new queries)
@@ -190,6 +198,7 @@ mod tests {
             WalReferenceHandle::new(Arc::clone(&wal), &metric::Registry::default());
         let persist = Arc::new(MockPersistQueue::new_with_observer(
             wal_reference_handle.clone(),
+            None,
         ));
         tokio::spawn(wal_reference_actor.run());
 
@@ -211,6 +220,7 @@ mod tests {
             Arc::clone(&persist),
             Arc::clone(&wal),
             wal_reference_handle,
+            None,
         )
         .await;
 
@@ -239,6 +249,7 @@ mod tests {
             WalReferenceHandle::new(Arc::clone(&wal), &metric::Registry::default());
         let persist = Arc::new(MockPersistQueue::new_with_observer(
             wal_reference_handle.clone(),
+            None,
         ));
         tokio::spawn(wal_reference_actor.run());
 
@@ -268,6 +279,7 @@ mod tests {
             Arc::clone(&persist),
             Arc::clone(&wal),
             wal_reference_handle,
+            None,
         ));
 
         // Wait a small duration of time for the first buffer emptiness check to
@@ -354,6 +366,7 @@ mod tests {
             WalReferenceHandle::new(Arc::clone(&wal), &metric::Registry::default());
         let persist = Arc::new(MockPersistQueue::new_with_observer(
             wal_reference_handle.clone(),
+            None,
         ));
         tokio::spawn(wal_reference_actor.run());
 
@@ -380,6 +393,7 @@ mod tests {
             Arc::clone(&persist),
             Arc::clone(&wal),
             wal_reference_handle.clone(),
+            None,
         ));
 
         // Wait for the shutdown to complete.
@@ -5,7 +5,7 @@ use mutable_batch_pb::decode::decode_database_batch;
 use observability_deps::tracing::*;
 use std::time::Instant;
 use thiserror::Error;
-use wal::{SequencedWalOp, Wal};
+use wal::{ClosedSegment, SequencedWalOp, Wal};
 
 use crate::{
     dml_payload::write::{PartitionedData, TableData, WriteOperation},
@@ -26,6 +26,10 @@ pub enum WalReplayError {
     #[error("failed to read wal entry: {0}")]
     ReadEntry(wal::Error),
 
+    /// An error moving a damaged segment file to the quarantine directory.
+    #[error("failed to quarantine damaged wal segment: {0}")]
+    Quarantine(wal::Error),
+
     /// An error converting the WAL entry into a [`IngestOp`].
     #[error("failed converting wal entry to ingest operation: {0}")]
     MapToDml(#[from] mutable_batch_pb::decode::Error),
@@ -38,17 +42,80 @@ pub enum WalReplayError {
     Apply(#[from] DmlError),
 }
 
-// TODO: tolerate WAL replay errors
-//
-// https://github.com/influxdata/influxdb_iox/issues/6283
+/// Controls how [`replay()`] handles damaged WAL segments.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WalReplayMode {
+    /// Skip damaged entries and ops, and any unreadable tail of a segment,
+    /// replaying everything that can still be read.
+    ///
+    /// Segments containing damage are moved to the WAL's
+    /// [`QUARANTINE_DIRECTORY`] once the data read from them is persisted,
+    /// instead of being deleted. The oldest quarantined segments are removed
+    /// once the directory holds more than [`MAX_QUARANTINE_BYTES`].
+    ///
+    /// An entry at the end of a segment that was only partially written, as
+    /// left by a crash during a write that was therefore never acknowledged,
+    /// is not considered damage.
+    ///
+    /// [`QUARANTINE_DIRECTORY`]: wal::QUARANTINE_DIRECTORY
+    #[default]
+    Tolerant,
+
+    /// Fail the replay on the first damaged segment, entry or op.
+    Strict,
+}
+
+/// The maximum total size of the segment files kept in the WAL's
+/// [`QUARANTINE_DIRECTORY`], beyond which the oldest are removed.
+///
+/// [`QUARANTINE_DIRECTORY`]: wal::QUARANTINE_DIRECTORY
+pub const MAX_QUARANTINE_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// The metrics recorded while replaying WAL segment files.
+#[derive(Debug)]
+struct ReplayMetrics {
+    /// Ops applied to the [`DmlSink`].
+    ok_ops: U64Counter,
+    /// Ops skipped as they contain no data.
+    empty_ops: U64Counter,
+    /// Ops skipped as they can not be decoded.
+    corrupt_ops: U64Counter,
+    /// Bytes of segment files that could not be replayed.
+    lost_bytes: U64Counter,
+}
+
+/// The outcome of replaying a single segment file.
+#[derive(Debug, Default)]
+struct ReplayedFile {
+    /// The highest sequence number observed in the file, or [`None`] if it
+    /// contained no ops.
+    max_sequence: Option<SequenceNumber>,
+    /// The number of ops skipped as they could not be decoded.
+    lost_ops: u64,
+    /// The number of entries skipped as they could not be read. The number
+    /// of ops they contained is unknown.
+    lost_entries: u64,
+    /// The number of bytes of the file that could not be replayed.
+    lost_bytes: u64,
+}
+
+impl ReplayedFile {
+    /// Returns true if any part of the file could not be replayed.
+    fn is_damaged(&self) -> bool {
+        self.lost_ops > 0 || self.lost_entries > 0 || self.lost_bytes > 0
+    }
+}
 
 /// Replay all the entries in `wal` to `sink`, returning the maximum observed
 /// [`SequenceNumber`].
+///
+/// Damaged segments are handled according to `mode`.
 pub async fn replay<T, P>(
     wal: &Wal,
     sink: &T,
     persist: P,
     metrics: &metric::Registry,
+    mode: WalReplayMode,
 ) -> Result<Option<SequenceNumber>, WalReplayError>
 where
     T: DmlSink + PartitionIter,
@@ -75,15 +142,30 @@ where
             "Number of WAL files that have started to be replayed",
         )
         .recorder(&[]);
+    let quarantined_file_count_metric = metrics
+        .register_metric::<U64Counter>(
+            "ingester_wal_replay_files_quarantined",
+            "Number of damaged WAL files moved to quarantine during replay",
+        )
+        .recorder(&[]);
     let op_count_metric = metrics.register_metric::<U64Counter>(
         "ingester_wal_replay_ops",
         "Number of operations replayed from the WAL",
     );
-    let ok_op_count_metric = op_count_metric.recorder(&[("outcome", "success")]);
-    let empty_op_count_metric = op_count_metric.recorder(&[("outcome", "skipped_empty")]);
+    let replay_metrics = ReplayMetrics {
+        ok_ops: op_count_metric.recorder(&[("outcome", "success")]),
+        empty_ops: op_count_metric.recorder(&[("outcome", "skipped_empty")]),
+        corrupt_ops: op_count_metric.recorder(&[("outcome", "skipped_corrupt")]),
+        lost_bytes: metrics
+            .register_metric::<U64Counter>(
+                "ingester_wal_replay_lost_bytes",
+                "Number of bytes of damaged WAL files that could not be replayed",
+            )
+            .recorder(&[]),
+    };
 
     let n_files = files.len();
-    info!(n_files, "found wal files for replay");
+    info!(n_files, ?mode, "found wal files for replay");
 
     // Replay each file, keeping track of the last observed sequence number.
     //
@@ -97,9 +179,27 @@ where
         file_count_metric.inc(1);
 
         // Read the segment
-        let reader = wal
-            .reader_for_segment(file.id())
-            .map_err(WalReplayError::OpenSegment)?;
+        let reader = match wal.reader_for_segment(file.id()) {
+            Ok(v) => v,
+            Err(e) if mode == WalReplayMode::Strict => return Err(WalReplayError::OpenSegment(e)),
+            Err(error) => {
+                // Nothing can be read from a segment with a damaged header.
+                warn!(
+                    file_number,
+                    n_files,
+                    file_id = %file.id(),
+                    size = file.size(),
+                    %error,
+                    "unable to open wal segment, skipping replay",
+                );
+                replay_metrics.lost_bytes.inc(file.size());
+
+                quarantine(wal, &file, file_number, n_files).await?;
+                quarantined_file_count_metric.inc(1);
+
+                continue;
+            }
+        };
 
         // Emit a log entry so progress can be tracked (and a problematic file
         // be identified should an explosion happen during replay).
@@ -112,9 +212,24 @@ where
         );
 
         // Replay this segment file
-        match replay_file(reader, sink, &ok_op_count_metric, &empty_op_count_metric).await? {
+        let replayed = replay_file(reader, file.size(), sink, mode, &replay_metrics).await?;
+        let damaged = replayed.is_damaged();
+        if damaged {
+            warn!(
+                file_number,
+                n_files,
+                file_id = %file.id(),
+                size = file.size(),
+                lost_ops = replayed.lost_ops,
+                lost_entries = replayed.lost_entries,
+                lost_bytes = replayed.lost_bytes,
+                "wal segment is damaged, data was lost during replay"
+            );
+        }
+
+        match replayed.max_sequence {
             v @ Some(_) => max_sequence = max_sequence.max(v),
-            None => {
+            None if !damaged => {
                 // This file was empty and should be deleted.
                 warn!(
                     file_number,
@@ -141,18 +256,32 @@ where
 
                 continue;
             }
+            // Nothing could be replayed from this damaged file, so there is
+            // nothing to persist.
+            None => {}
         };
 
-        info!(
-            file_number,
-            n_files,
-            file_id = %file.id(),
-            size = file.size(),
-            "persisting wal segment data"
-        );
+        if replayed.max_sequence.is_some() {
+            info!(
+                file_number,
+                n_files,
+                file_id = %file.id(),
+                size = file.size(),
+                "persisting wal segment data"
+            );
 
-        // Persist all the data that was replayed from the WAL segment.
-        persist_partitions(sink.partition_iter(), &persist).await;
+            // Persist all the data that was replayed from the WAL segment.
+            persist_partitions(sink.partition_iter(), &persist).await;
+        }
+
+        if damaged {
+            // Retain the damaged file for inspection, but remove it from the
+            // WAL so it is not replayed again.
+            quarantine(wal, &file, file_number, n_files).await?;
+            quarantined_file_count_metric.inc(1);
+
+            continue;
+        }
 
         // Drop the newly persisted data - it should not be replayed.
         wal.delete(file.id())
@@ -176,22 +305,114 @@ where
     Ok(max_sequence)
 }
 
+/// Move the damaged segment `file` to the WAL's quarantine directory, removing
+/// the oldest quarantined segments should it then exceed
+/// [`MAX_QUARANTINE_BYTES`].
+///
+/// A failure to remove old quarantined segments is logged, and does not fail
+/// the replay.
+async fn quarantine(
+    wal: &Wal,
+    file: &ClosedSegment,
+    file_number: usize,
+    n_files: usize,
+) -> Result<(), WalReplayError> {
+    let path = wal
+        .quarantine(file.id())
+        .await
+        .map_err(WalReplayError::Quarantine)?;
+
+    warn!(
+        file_number,
+        n_files,
+        file_id = %file.id(),
+        path = %path.display(),
+        "quarantined damaged wal segment"
+    );
+
+    match wal.trim_quarantine(MAX_QUARANTINE_BYTES) {
+        Ok(removed) => {
+            for path in removed {
+                warn!(
+                    path = %path.display(),
+                    max_bytes = MAX_QUARANTINE_BYTES,
+                    "removed oldest quarantined wal segment to bound quarantine size"
+                );
+            }
+        }
+        Err(error) => {
+            error!(%error, "failed to remove old quarantined wal segments");
+        }
+    }
+
+    Ok(())
+}
+
 /// Replay the entries in `file`, applying them to `buffer`. Returns the highest
-/// sequence number observed in the file, or [`None`] if the file was empty.
+/// sequence number observed in the file (or [`None`] if the file was empty),
+/// and the extent of any damage skipped over when `mode` is
+/// [`WalReplayMode::Tolerant`].
 async fn replay_file<T>(
-    file: wal::ClosedSegmentFileReader,
+    mut file: wal::ClosedSegmentFileReader,
+    file_size: u64,
     sink: &T,
-    ok_op_count_metric: &U64Counter,
-    empty_op_count_metric: &U64Counter,
-) -> Result<Option<SequenceNumber>, WalReplayError>
+    mode: WalReplayMode,
+    metrics: &ReplayMetrics,
+) -> Result<ReplayedFile, WalReplayError>
 where
     T: DmlSink,
 {
-    let mut max_sequence = None;
+    let mut replayed = ReplayedFile::default();
     let start = Instant::now();
 
-    for batch in file {
-        let ops = batch.map_err(WalReplayError::ReadEntry)?;
+    loop {
+        let bytes_read = file.bytes_read();
+        let Some(batch) = file.next() else {
+            break;
+        };
+
+        let ops = match batch {
+            Ok(v) => v,
+            // An entry that was only partially written is the last in the
+            // file, and its write was never acknowledged, so nothing is lost
+            // in either mode.
+            Err(error) if error.is_truncated_entry() => {
+                info!(
+                    file_id = %file.id(),
+                    offset = bytes_read,
+                    %error,
+                    "ignoring partially written entry at end of wal segment"
+                );
+                break;
+            }
+            Err(e) if mode == WalReplayMode::Strict => return Err(WalReplayError::ReadEntry(e)),
+            // The entry was read in full, but its contents are damaged. The
+            // entries following it can still be read.
+            Err(error) if file.bytes_read() > bytes_read => {
+                warn!(
+                    file_id = %file.id(),
+                    offset = bytes_read,
+                    %error,
+                    "skipping damaged wal entry"
+                );
+                replayed.lost_entries += 1;
+                replayed.lost_bytes += file.bytes_read() - bytes_read;
+                continue;
+            }
+            // The position of any following entries can not be determined,
+            // so the rest of the file is lost.
+            Err(error) => {
+                warn!(
+                    file_id = %file.id(),
+                    offset = bytes_read,
+                    %error,
+                    "skipping unreadable tail of wal segment"
+                );
+                replayed.lost_entries += 1;
+                replayed.lost_bytes += file_size.saturating_sub(bytes_read);
+                break;
+            }
+        };
 
         for op in ops {
             let SequencedWalOp {
@@ -209,13 +430,27 @@ where
             let mut op_max_sequence_number = None;
 
             // Reconstruct the ingest operation
-            let batches = decode_database_batch(&op)?;
+            let batches = match decode_database_batch(&op) {
+                Ok(v) => v,
+                Err(e) if mode == WalReplayMode::Strict => return Err(e.into()),
+                Err(error) => {
+                    warn!(
+                        file_id = %file.id(),
+                        namespace_id = op.database_id,
+                        %error,
+                        "skipping undecodable wal op"
+                    );
+                    metrics.corrupt_ops.inc(1);
+                    replayed.lost_ops += 1;
+                    continue;
+                }
+            };
             let namespace_id = NamespaceId::new(op.database_id);
             let partition_key = PartitionKey::from(op.partition_key);
 
             if batches.is_empty() {
                 warn!(%namespace_id, "encountered wal op containing no table data, skipping replay");
-                empty_op_count_metric.inc(1);
+                metrics.empty_ops.inc(1);
                 continue;
             }
 
@@ -231,7 +466,7 @@ where
                                 .expect("attempt to apply unsequenced wal op"),
                         );
 
-                        max_sequence = max_sequence.max(Some(sequence_number));
+                        replayed.max_sequence = replayed.max_sequence.max(Some(sequence_number));
                         op_min_sequence_number = op_min_sequence_number.min(Some(sequence_number));
                         op_max_sequence_number = op_min_sequence_number.max(Some(sequence_number));
 
@@ -262,14 +497,16 @@ where
                 .await
                 .map_err(Into::<DmlError>::into)?;
 
-            ok_op_count_metric.inc(1);
+            metrics.ok_ops.inc(1);
         }
     }
 
+    metrics.lost_bytes.inc(replayed.lost_bytes);
+
     // This file is complete, return the last observed sequence
     // number.
     debug!("wal file replayed in {:?}", start.elapsed());
-    Ok(max_sequence)
+    Ok(replayed)
 }
 
 #[cfg(test)]
@@ -465,9 +702,15 @@ mod tests {
         };
 
         let metrics = metric::Registry::default();
-        let max_sequence_number = replay(&wal, &mock_iter, Arc::clone(&persist), &metrics)
-            .await
-            .expect("failed to replay WAL");
+        let max_sequence_number = replay(
+            &wal,
+            &mock_iter,
+            Arc::clone(&persist),
+            &metrics,
+            WalReplayMode::Strict,
+        )
+        .await
+        .expect("failed to replay WAL");
 
         assert_eq!(max_sequence_number, Some(SequenceNumber::new(43)));
 
@@ -529,4 +772,260 @@ mod tests {
             .fetch();
         assert_eq!(ops, 1);
     }
+
+    /// Write `ops` to a new WAL in `dir` through a [`WalSink`], rotating the
+    /// segment after the first `rotate_after` ops.
+    async fn write_wal(dir: &std::path::Path, ops: &[WriteOperation], rotate_after: usize) {
+        let inner = Arc::new(
+            MockDmlSink::default()
+                .with_apply_return(ops.iter().map(|_| Ok(())).collect::<Vec<_>>()),
+        );
+        let wal = Wal::new(dir).await.expect("failed to initialise WAL");
+        let wal_sink = WalSink::new(
+            Arc::clone(&inner),
+            Arc::clone(&wal),
+            Arc::new(MockUnbufferedWriteNotifier::default()),
+        );
+
+        for (i, op) in ops.iter().enumerate() {
+            if i == rotate_after {
+                wal.rotate().expect("failed to rotate WAL file");
+            }
+            wal_sink
+                .apply(IngestOp::Write(op.clone()))
+                .await
+                .expect("wal should not error");
+        }
+    }
+
+    /// Build three ops, with increasing sequence numbers.
+    fn make_ops() -> [WriteOperation; 3] {
+        [24, 25, 26].map(|sequence_number| {
+            make_write_op(
+                &ARBITRARY_PARTITION_KEY,
+                ARBITRARY_NAMESPACE_ID,
+                &ARBITRARY_TABLE_NAME,
+                ARBITRARY_TABLE_ID,
+                sequence_number,
+                &format!(
+                    r#"{},region=Madrid temp={sequence_number} 4242424242"#,
+                    &*ARBITRARY_TABLE_NAME
+                ),
+                None,
+            )
+        })
+    }
+
+    /// Damage the first segment of the WAL in `dir`, which must contain two
+    /// entries, by invalidating the checksum of its first entry and appending
+    /// a partially written copy of its second entry.
+    ///
+    /// Returns the number of bytes damaged, which excludes the partially
+    /// written entry.
+    fn damage_first_segment(dir: &std::path::Path, wal: &Wal) -> u64 {
+        let segment = wal.closed_segments().remove(0);
+        let path = dir.join(format!("{}.dat", segment.id()));
+
+        let mut data = std::fs::read(&path).expect("failed to read segment file");
+        // The first entry starts after the 8 byte file type identifier and 8
+        // byte segment id, with a 4 byte checksum and 4 byte length.
+        data[16] ^= 0xFF;
+        let entry_len = u32::from_be_bytes(data[20..24].try_into().unwrap()) as u64;
+        // Followed by the second entry.
+        let second = 24 + entry_len as usize;
+        let partial = data[second..second + 12].to_vec();
+        data.extend_from_slice(&partial);
+        std::fs::write(&path, data).expect("failed to write segment file");
+
+        8 + entry_len
+    }
+
+    #[tokio::test]
+    async fn test_replay_damaged_segment() {
+        let dir = tempfile::tempdir().unwrap();
+        let [op1, op2, op3] = make_ops();
+
+        write_wal(dir.path(), &[op1, op2.clone(), op3.clone()], 2).await;
+
+        let wal = Wal::new(dir.path())
+            .await
+            .expect("failed to initialise WAL");
+        assert_eq!(wal.closed_segments().len(), 2);
+        let damaged_segment = wal.closed_segments()[0].id();
+        let damaged_bytes = damage_first_segment(dir.path(), &wal);
+
+        // Reinitialise the WAL to pick up the new segment sizes
+        drop(wal);
+        let wal = Wal::new(dir.path())
+            .await
+            .expect("failed to initialise WAL");
+
+        let persist = Arc::new(MockPersistQueue::default());
+        let mock_iter = MockIter {
+            sink: MockDmlSink::default().with_apply_return(vec![Ok(()), Ok(())]),
+            partitions: vec![],
+        };
+
+        let metrics = metric::Registry::default();
+        let max_sequence_number = replay(
+            &wal,
+            &mock_iter,
+            Arc::clone(&persist),
+            &metrics,
+            WalReplayMode::Tolerant,
+        )
+        .await
+        .expect("failed to replay WAL");
+
+        assert_eq!(max_sequence_number, Some(SequenceNumber::new(26)));
+
+        // The op in the damaged entry is skipped, and the rest replayed.
+        let ops = mock_iter.sink.get_calls();
+        assert_matches!(
+            &*ops,
+            &[
+                IngestOp::Write(ref w2),
+                IngestOp::Write(ref w3),
+            ] => {
+                assert_write_ops_eq(w2.clone(), op2);
+                assert_write_ops_eq(w3.clone(), op3);
+            }
+        );
+
+        Arc::try_unwrap(persist)
+            .expect("should be no more refs")
+            .join()
+            .await;
+
+        // The damaged segment was moved to the quarantine directory, and the
+        // other segments dropped, leaving only the segment opened by this WAL.
+        let wal = Wal::new(dir.path())
+            .await
+            .expect("failed to initialise WAL");
+        assert_eq!(wal.closed_segments().len(), 1);
+        assert!(dir
+            .path()
+            .join(wal::QUARANTINE_DIRECTORY)
+            .join(format!("{damaged_segment}.dat"))
+            .exists());
+
+        let files = metrics
+            .get_instrument::<Metric<U64Counter>>("ingester_wal_replay_files_quarantined")
+            .expect("file counter not found")
+            .get_observer(&Attributes::from([]))
+            .expect("attributes not found")
+            .fetch();
+        assert_eq!(files, 1);
+        let bytes = metrics
+            .get_instrument::<Metric<U64Counter>>("ingester_wal_replay_lost_bytes")
+            .expect("byte counter not found")
+            .get_observer(&Attributes::from([]))
+            .expect("attributes not found")
+            .fetch();
+        assert_eq!(bytes, damaged_bytes);
+        let ops = metrics
+            .get_instrument::<Metric<U64Counter>>("ingester_wal_replay_ops")
+            .expect("op counter not found")
+            .get_observer(&Attributes::from(&[("outcome", "success")]))
+            .expect("attributes not found")
+            .fetch();
+        assert_eq!(ops, 2);
+    }
+
+    #[tokio::test]
+    async fn test_replay_damaged_segment_strict() {
+        let dir = tempfile::tempdir().unwrap();
+        let [op1, op2, op3] = make_ops();
+
+        write_wal(dir.path(), &[op1, op2, op3], 2).await;
+
+        let wal = Wal::new(dir.path())
+            .await
+            .expect("failed to initialise WAL");
+        damage_first_segment(dir.path(), &wal);
+
+        let persist = Arc::new(MockPersistQueue::default());
+        let mock_iter = MockIter {
+            sink: MockDmlSink::default(),
+            partitions: vec![],
+        };
+
+        let err = replay(
+            &wal,
+            &mock_iter,
+            Arc::clone(&persist),
+            &metric::Registry::default(),
+            WalReplayMode::Strict,
+        )
+        .await
+        .expect_err("replay of damaged WAL should fail");
+        assert_matches!(err, WalReplayError::ReadEntry(_));
+
+        // Nothing was applied, and the damaged segment remains in place.
+        assert!(mock_iter.sink.get_calls().is_empty());
+        assert_eq!(wal.closed_segments().len(), 2);
+        assert!(!dir.path().join(wal::QUARANTINE_DIRECTORY).exists());
+    }
+
+    /// A partially written entry at the end of a segment, as left by a crash
+    /// mid-write, is not damage: the segment is replayed and dropped as usual.
+    #[tokio::test]
+    async fn test_replay_truncated_segment() {
+        let dir = tempfile::tempdir().unwrap();
+        let [op1, op2, op3] = make_ops();
+
+        write_wal(dir.path(), &[op1.clone(), op2.clone(), op3.clone()], 2).await;
+
+        let wal = Wal::new(dir.path())
+            .await
+            .expect("failed to initialise WAL");
+        let segment = wal.closed_segments().remove(0);
+        let path = dir.path().join(format!("{}.dat", segment.id()));
+        let mut data = std::fs::read(&path).expect("failed to read segment file");
+        // Append the first 12 bytes of the first entry, which starts after the
+        // 16 byte header.
+        let partial = data[16..28].to_vec();
+        data.extend_from_slice(&partial);
+        std::fs::write(&path, data).expect("failed to write segment file");
+
+        drop(wal);
+        let wal = Wal::new(dir.path())
+            .await
+            .expect("failed to initialise WAL");
+
+        let persist = Arc::new(MockPersistQueue::default());
+        let mock_iter = MockIter {
+            sink: MockDmlSink::default().with_apply_return(vec![Ok(()), Ok(()), Ok(())]),
+            partitions: vec![],
+        };
+
+        let metrics = metric::Registry::default();
+        let max_sequence_number = replay(
+            &wal,
+            &mock_iter,
+            Arc::clone(&persist),
+            &metrics,
+            WalReplayMode::Tolerant,
+        )
+        .await
+        .expect("failed to replay WAL");
+
+        assert_eq!(max_sequence_number, Some(SequenceNumber::new(26)));
+        assert_eq!(mock_iter.sink.get_calls().len(), 3);
+
+        Arc::try_unwrap(persist)
+            .expect("should be no more refs")
+            .join()
+            .await;
+
+        // Nothing was quarantined or lost.
+        assert!(!dir.path().join(wal::QUARANTINE_DIRECTORY).exists());
+        let bytes = metrics
+            .get_instrument::<Metric<U64Counter>>("ingester_wal_replay_lost_bytes")
+            .expect("byte counter not found")
+            .get_observer(&Attributes::from([]))
+            .expect("attributes not found")
+            .fetch();
+        assert_eq!(bytes, 0);
+    }
 }
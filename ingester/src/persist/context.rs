@@ -1,6 +1,9 @@
 use std::sync::Arc;
 
-use data_types::{NamespaceId, ParquetFile, PartitionKey, TableId, TransitionPartitionId};
+use data_types::{
+    sequence_number_set::SequenceNumberSet, NamespaceId, ParquetFile, ParquetFileId, PartitionKey,
+    TableId, TransitionPartitionId,
+};
 use observability_deps::tracing::*;
 use parking_lot::Mutex;
 use schema::sort::SortKey;
@@ -35,7 +38,7 @@ pub(super) enum PersistError {
 /// task.
 #[derive(Debug)]
 pub(super) struct PersistRequest {
-    complete: oneshot::Sender<()>,
+    complete: oneshot::Sender<Vec<ParquetFileId>>,
     partition: Arc<Mutex<PartitionData>>,
     data: PersistingData,
     enqueued_at: Instant,
@@ -50,7 +53,7 @@ impl PersistRequest {
         data: PersistingData,
         permit: OwnedSemaphorePermit,
         enqueued_at: Instant,
-    ) -> (Self, oneshot::Receiver<()>) {
+    ) -> (Self, oneshot::Receiver<Vec<ParquetFileId>>) {
         let (tx, rx) = oneshot::channel();
         (
             Self {
@@ -108,8 +111,8 @@ pub(super) struct Context {
     sort_key: SortKeyState,
 
     /// A notification signal to indicate to the caller that this partition has
-    /// persisted.
-    complete: oneshot::Sender<()>,
+    /// persisted, carrying the IDs of the resulting parquet files.
+    complete: oneshot::Sender<Vec<ParquetFileId>>,
 
     /// Timing statistics tracking the timestamp this persist job was first
     /// enqueued, and the timestamp this [`Context`] was constructed (signifying
@@ -216,11 +219,15 @@ impl Context {
     // Call [`PartitionData::mark_complete`] to finalise the persistence job,
     // emit a log for the user, and notify the observer of this persistence
     // task, if any.
-    pub(super) async fn mark_complete<O>(self, metadata: ParquetFile, completion_observer: &O)
+    //
+    // The persisted data MAY have been split across multiple `files`, each of
+    // which is dispatched to the observer separately.
+    pub(super) async fn mark_complete<O>(self, files: Vec<ParquetFile>, completion_observer: &O)
     where
         O: PersistCompletionObserver,
     {
-        let object_store_id = metadata.object_store_id;
+        let object_store_ids = files.iter().map(|f| f.object_store_id).collect::<Vec<_>>();
+        let parquet_file_ids = files.iter().map(|f| f.id).collect::<Vec<_>>();
 
         // Mark the partition as having completed persistence, causing it to
         // release the reference to the in-flight persistence data it is
@@ -232,16 +239,29 @@ impl Context {
         let sequence_numbers = self.partition.lock().mark_persisted(self.data);
         let n_writes = sequence_numbers.len();
 
-        // Dispatch the completion notification into the observer chain before
+        // Dispatch the completion notifications into the observer chain before
         // completing the persist operation.
+        //
+        // The sequence numbers of the persisted data are attached to the last
+        // file only, so that each is observed as persisted exactly once.
+        let mut files = files;
+        let last = files.pop().expect("persist must produce at least one file");
+        for metadata in files {
+            completion_observer
+                .persist_complete(Arc::new(CompletedPersist::new(
+                    metadata,
+                    SequenceNumberSet::default(),
+                )))
+                .await;
+        }
         completion_observer
-            .persist_complete(Arc::new(CompletedPersist::new(metadata, sequence_numbers)))
+            .persist_complete(Arc::new(CompletedPersist::new(last, sequence_numbers)))
             .await;
 
         let now = Instant::now();
 
         info!(
-            %object_store_id,
+            ?object_store_ids,
             namespace_id = %self.namespace_id,
             namespace_name = %self.namespace_name,
             table_id = %self.table_id,
@@ -260,8 +280,9 @@ impl Context {
         // notification is able to push into the queue immediately.
         drop(self.permit);
 
-        // Notify the observer of this persistence task, if any.
-        let _ = self.complete.send(());
+        // Notify the observer of this persistence task, if any, of the parquet
+        // files it produced.
+        let _ = self.complete.send(parquet_file_ids);
     }
 
     pub(super) fn enqueued_at(&self) -> Instant {
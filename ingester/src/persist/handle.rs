@@ -1,6 +1,7 @@
 use std::{sync::Arc, time::Duration};
 
 use async_trait::async_trait;
+use data_types::ParquetFileId;
 use iox_catalog::interface::Catalog;
 use iox_query::{exec::Executor, QueryChunk};
 use metric::{DurationHistogram, DurationHistogramOptions, U64Counter, U64Gauge, DURATION_MAX};
@@ -177,6 +178,7 @@ impl PersistHandle {
         store: ParquetStorage,
         catalog: Arc<dyn Catalog>,
         completion_observer: O,
+        cold_split_threshold: Option<Duration>,
         metrics: &metric::Registry,
     ) -> Self
     where
@@ -189,13 +191,19 @@ impl PersistHandle {
         );
 
         // Log the important configuration parameters of the persist subsystem.
-        info!(n_workers, persist_queue_depth, "initialised persist task");
+        info!(
+            n_workers,
+            persist_queue_depth,
+            ?cold_split_threshold,
+            "initialised persist task"
+        );
 
         let worker_state = Arc::new(SharedWorkerState {
             exec,
             store,
             catalog,
             completion_observer,
+            cold_split_threshold,
         });
 
         // Initialise a histogram to capture persist job duration & time spent
@@ -338,7 +346,7 @@ impl PersistQueue for PersistHandle {
     /// task as complete.
     ///
     /// Once all persistence related tasks for `data` are complete, the returned
-    /// channel publishes a notification.
+    /// channel publishes the IDs of the parquet files produced.
     ///
     /// Persist tasks may be re-ordered w.r.t their submission order for
     /// performance reasons.
@@ -356,7 +364,7 @@ impl PersistQueue for PersistHandle {
         &self,
         partition: Arc<Mutex<PartitionData>>,
         data: PersistingData,
-    ) -> oneshot::Receiver<()> {
+    ) -> oneshot::Receiver<Vec<ParquetFileId>> {
         let partition_id = data.partition_id().clone();
         debug!(%partition_id, "enqueuing persistence task");
 
@@ -562,6 +570,7 @@ mod tests {
             storage,
             catalog,
             Arc::new(MockCompletionObserver::default()),
+            None,
             &metrics,
         );
 
@@ -638,6 +647,7 @@ mod tests {
             storage,
             catalog,
             Arc::new(MockCompletionObserver::default()),
+            None,
             &metrics,
         );
 
@@ -725,6 +735,7 @@ mod tests {
             storage,
             catalog,
             Arc::new(MockCompletionObserver::default()),
+            None,
             &metrics,
         );
 
@@ -817,6 +828,7 @@ mod tests {
             storage,
             catalog,
             Arc::new(MockCompletionObserver::default()),
+            None,
             &metrics,
         );
 
@@ -903,6 +915,7 @@ mod tests {
             storage,
             catalog,
             NopObserver,
+            None,
             &metrics,
         );
         assert!(ingest_state.read().is_ok());
@@ -983,6 +996,7 @@ mod tests {
             storage,
             catalog,
             NopObserver,
+            None,
             &metrics,
         );
 
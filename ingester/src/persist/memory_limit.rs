@@ -0,0 +1,312 @@
+use std::{sync::Arc, time::Duration};
+
+use metric::{U64Counter, U64Gauge};
+use observability_deps::tracing::*;
+use tokio::time::MissedTickBehavior;
+
+use crate::{
+    ingest_state::{IngestState, IngestStateError},
+    partition_iter::PartitionIter,
+};
+
+use super::queue::PersistQueue;
+
+/// The interval of time between evaluations of the memory used by the data
+/// buffered in the ingester.
+const EVALUATE_MEMORY_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Bounds the total memory used by the data buffered across all partitions.
+///
+/// The memory used is periodically evaluated, inclusive of data that is
+/// currently persisting (which remains in memory until persisted).
+///
+/// # High Watermark
+///
+/// When the memory used reaches `high_watermark_bytes`, the partitions with
+/// the largest "hot" buffers are marked as persisting and enqueued for
+/// persistence, until enough buffered data will be released upon completion to
+/// bring the memory used back under the high watermark. Data that is already
+/// persisting is accounted for, so an ongoing persist does not cause more
+/// partitions to be persisted at each evaluation.
+///
+/// # Hard Limit
+///
+/// When the memory used reaches `hard_limit_bytes`,
+/// [`IngestStateError::MemoryLimit`] is set, rejecting all writes with a
+/// retryable error. Writes are accepted again once the memory used drops below
+/// the high watermark.
+#[derive(Debug)]
+pub(crate) struct MemoryLimiter<T, P> {
+    buffer: T,
+    persist_handle: P,
+    ingest_state: Arc<IngestState>,
+
+    high_watermark_bytes: usize,
+    hard_limit_bytes: usize,
+
+    /// The memory used by buffered data at the last evaluation.
+    buffered_bytes: U64Gauge,
+
+    /// The number of partitions persisted because the high watermark was
+    /// exceeded.
+    persist_count: U64Counter,
+}
+
+impl<T, P> MemoryLimiter<T, P>
+where
+    T: PartitionIter + Sync + 'static,
+    P: PersistQueue + Clone + Sync + 'static,
+{
+    pub(crate) fn new(
+        buffer: T,
+        persist_handle: P,
+        ingest_state: Arc<IngestState>,
+        high_watermark_bytes: usize,
+        hard_limit_bytes: usize,
+        metrics: &metric::Registry,
+    ) -> Self {
+        assert!(
+            high_watermark_bytes <= hard_limit_bytes,
+            "memory high watermark must not exceed the hard limit"
+        );
+
+        let buffered_bytes = metrics
+            .register_metric::<U64Gauge>(
+                "ingester_buffered_bytes",
+                "estimated memory used by the data buffered in the ingester, \
+                including data being persisted",
+            )
+            .recorder(&[]);
+        let persist_count = metrics
+            .register_metric::<U64Counter>(
+                "ingester_persist_memory_limit_enqueue_count",
+                "number of times persistence of a partition has been triggered \
+                because the memory used by buffered data exceeded the high watermark",
+            )
+            .recorder(&[]);
+
+        Self {
+            buffer,
+            persist_handle,
+            ingest_state,
+            high_watermark_bytes,
+            hard_limit_bytes,
+            buffered_bytes,
+            persist_count,
+        }
+    }
+
+    /// Evaluate the memory used by the buffered data every
+    /// [`EVALUATE_MEMORY_INTERVAL`], forever.
+    pub(crate) async fn run(self) {
+        let mut interval = tokio::time::interval(EVALUATE_MEMORY_INTERVAL);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            interval.tick().await;
+            self.evaluate();
+        }
+    }
+
+    /// Evaluate the memory used by the buffered data, persisting partitions
+    /// and setting or clearing [`IngestStateError::MemoryLimit`] as necessary.
+    fn evaluate(&self) {
+        let mut total = 0;
+        let mut persisting = 0;
+        let mut candidates = vec![];
+
+        for p in self.buffer.partition_iter() {
+            let (bytes, hot_bytes) = {
+                let guard = p.lock();
+                (guard.buffered_bytes(), guard.persist_cost_estimate())
+            };
+
+            total += bytes;
+            persisting += bytes - hot_bytes;
+            if hot_bytes > 0 {
+                candidates.push((hot_bytes, p));
+            }
+        }
+
+        self.buffered_bytes.set(total as u64);
+
+        if total >= self.hard_limit_bytes {
+            if self.ingest_state.set(IngestStateError::MemoryLimit) {
+                warn!(
+                    buffered_bytes = total,
+                    hard_limit_bytes = self.hard_limit_bytes,
+                    "buffered data exceeds memory limit, blocking ingest"
+                );
+            }
+        } else if total < self.high_watermark_bytes
+            && self.ingest_state.unset(IngestStateError::MemoryLimit)
+        {
+            info!(
+                buffered_bytes = total,
+                "buffered data within memory limit, resuming ingest"
+            );
+        }
+
+        // Persisting data is released once its persistence completes, so only
+        // persist the hot data that remains in excess of the high watermark.
+        let mut excess = total
+            .saturating_sub(self.high_watermark_bytes)
+            .saturating_sub(persisting);
+        if total < self.high_watermark_bytes || excess == 0 {
+            return;
+        }
+
+        // Persist the largest partitions first, releasing the most memory for
+        // the fewest persist operations.
+        candidates.sort_unstable_by(|a, b| b.0.cmp(&a.0));
+
+        for (hot_bytes, partition) in candidates {
+            // The partition may have been concurrently marked as persisting,
+            // in which case there is nothing to persist.
+            let Some(data) = partition.lock().mark_persisting() else {
+                continue;
+            };
+
+            info!(
+                partition_id = %data.partition_id(),
+                hot_bytes,
+                buffered_bytes = total,
+                "persisting partition to reduce buffered memory"
+            );
+
+            // Perform the enqueue in a separate task, to avoid blocking the
+            // evaluation if the persist system is saturated.
+            let persist_handle = self.persist_handle.clone();
+            tokio::spawn(async move {
+                // There is no need to await on the completion handle.
+                persist_handle.enqueue(partition, data).await;
+            });
+            self.persist_count.inc(1);
+
+            excess = excess.saturating_sub(hot_bytes);
+            if excess == 0 {
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use assert_matches::assert_matches;
+    use data_types::{PartitionKey, SequenceNumber};
+    use mutable_batch_lp::test_helpers::lp_to_mutable_batch;
+    use parking_lot::Mutex;
+    use test_helpers::timeout::FutureTimeout;
+
+    use super::*;
+    use crate::{
+        buffer_tree::partition::PartitionData,
+        persist::queue::mock::MockPersistQueue,
+        test_util::{PartitionDataBuilder, ARBITRARY_TABLE_NAME},
+    };
+
+    /// Build a partition with `n` rows buffered.
+    fn partition_with_rows(key: &str, n: usize) -> Arc<Mutex<PartitionData>> {
+        let mut p = PartitionDataBuilder::new()
+            .with_partition_key(PartitionKey::from(key))
+            .build();
+
+        for i in 0..n {
+            let mb = lp_to_mutable_batch(&format!(
+                r#"{},city=Hereford people={i},crisps="good" {i}"#,
+                &*ARBITRARY_TABLE_NAME
+            ))
+            .1;
+            p.buffer_write(mb, SequenceNumber::new(i as _))
+                .expect("write should succeed");
+        }
+
+        Arc::new(Mutex::new(p))
+    }
+
+    fn buffered_bytes(partitions: &[Arc<Mutex<PartitionData>>]) -> usize {
+        partitions.iter().map(|p| p.lock().buffered_bytes()).sum()
+    }
+
+    #[tokio::test]
+    async fn test_memory_limit() {
+        let partitions = vec![
+            partition_with_rows("small", 1),
+            partition_with_rows("large", 100),
+            partition_with_rows("medium", 10),
+        ];
+        let total = buffered_bytes(&partitions);
+
+        let metrics = metric::Registry::default();
+        let persist_handle = Arc::new(MockPersistQueue::default());
+        let ingest_state = Arc::new(IngestState::default());
+
+        // Exceed the high watermark by a single byte, which persisting the
+        // largest partition resolves.
+        let limiter = MemoryLimiter::new(
+            partitions.clone(),
+            Arc::clone(&persist_handle),
+            Arc::clone(&ingest_state),
+            total - 1,
+            total + 1,
+            &metrics,
+        );
+
+        limiter.evaluate();
+        tokio::task::yield_now().await;
+
+        assert_matches!(persist_handle.calls().as_slice(), [got] => {
+            assert!(Arc::ptr_eq(got, &partitions[1]));
+        });
+        assert_matches!(ingest_state.read(), Ok(()));
+        metric::assert_counter!(
+            metrics,
+            U64Counter,
+            "ingester_persist_memory_limit_enqueue_count",
+            value = 1,
+        );
+        let gauge = metrics
+            .get_instrument::<metric::Metric<U64Gauge>>("ingester_buffered_bytes")
+            .expect("failed to find metric")
+            .get_observer(&metric::Attributes::from(&[]))
+            .expect("failed to find metric attributes")
+            .fetch();
+        assert_eq!(gauge, total as u64);
+
+        // The persisting data is accounted for, and no further partitions are
+        // persisted while it persists.
+        limiter.evaluate();
+        tokio::task::yield_now().await;
+        assert_eq!(persist_handle.calls().len(), 1);
+
+        // Reaching the hard limit rejects writes, and persists everything.
+        let metrics = metric::Registry::default();
+        let limiter = MemoryLimiter::new(
+            partitions.clone(),
+            Arc::clone(&persist_handle),
+            Arc::clone(&ingest_state),
+            1,
+            1,
+            &metrics,
+        );
+
+        limiter.evaluate();
+        assert_matches!(ingest_state.read(), Err(IngestStateError::MemoryLimit));
+
+        // Once the persists complete, the memory is released and writes are
+        // accepted again.
+        async {
+            while buffered_bytes(&partitions) > 0 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        }
+        .with_timeout_panic(Duration::from_secs(5))
+        .await;
+
+        limiter.evaluate();
+        assert_matches!(ingest_state.read(), Ok(()));
+    }
+}
@@ -8,7 +8,9 @@ pub(crate) mod drain_buffer;
 pub(crate) mod file_metrics;
 pub(crate) mod handle;
 pub(crate) mod hot_partitions;
+pub(crate) mod memory_limit;
 pub mod queue;
+mod split;
 mod worker;
 
 #[cfg(test)]
@@ -60,6 +62,19 @@ mod tests {
     /// catalog such that the schema is set (by validating the schema) and the
     /// partition entry exists (by driving the buffer tree to create it).
     async fn partition_with_write(catalog: Arc<dyn Catalog>) -> Arc<Mutex<PartitionData>> {
+        partition_with_lp(
+            catalog,
+            &format!(
+                r#"{},region=Asturias temp=35 4242424242"#,
+                &*ARBITRARY_TABLE_NAME
+            ),
+        )
+        .await
+    }
+
+    /// Generate a [`PartitionData`] containing a write of `lp`, populating the
+    /// catalog as [`partition_with_write()`] does.
+    async fn partition_with_lp(catalog: Arc<dyn Catalog>, lp: &str) -> Arc<Mutex<PartitionData>> {
         // Create the namespace in the catalog and it's the schema
         let (namespace_id, table_id) =
             populate_catalog(&*catalog, &ARBITRARY_NAMESPACE_NAME, &ARBITRARY_TABLE_NAME).await;
@@ -79,10 +94,7 @@ mod tests {
             &ARBITRARY_TABLE_NAME,
             table_id,
             0,
-            &format!(
-                r#"{},region=Asturias temp=35 4242424242"#,
-                &*ARBITRARY_TABLE_NAME
-            ),
+            lp,
             None,
         );
 
@@ -183,6 +195,7 @@ mod tests {
             storage,
             Arc::clone(&catalog),
             Arc::clone(&completion_observer),
+            None,
             &metrics,
         );
         assert!(ingest_state.read().is_ok());
@@ -319,6 +332,7 @@ mod tests {
             storage,
             Arc::clone(&catalog),
             Arc::clone(&completion_observer),
+            None,
             &metrics,
         );
         assert!(ingest_state.read().is_ok());
@@ -456,4 +470,93 @@ mod tests {
 
         assert_eq!(file.size, *file_size_bytes as usize);
     }
+
+    /// Persisting data spanning more than the cold split threshold produces a
+    /// file for the cold rows, and a file for the live edge.
+    #[tokio::test]
+    async fn test_persist_integration_cold_split() {
+        maybe_start_logging();
+
+        let object_storage: Arc<dyn ObjectStore> = Arc::new(InMemory::default());
+        let storage = ParquetStorage::new(Arc::clone(&object_storage), StorageId::from("iox"));
+        let metrics = Arc::new(metric::Registry::default());
+        let catalog: Arc<dyn Catalog> = Arc::new(MemCatalog::new(Arc::clone(&metrics)));
+        let ingest_state = Arc::new(IngestState::default());
+        let completion_observer = Arc::new(MockCompletionObserver::default());
+
+        // Initialise the persist system, splitting rows more than 1 hour older
+        // than the newest row.
+        let handle = PersistHandle::new(
+            1,
+            2,
+            Arc::clone(&ingest_state),
+            Arc::new(Executor::new_testing()),
+            storage,
+            Arc::clone(&catalog),
+            Arc::clone(&completion_observer),
+            Some(Duration::from_secs(60 * 60)),
+            &metrics,
+        );
+
+        // Generate a partition with two late rows, and one recent row.
+        let hour = 60 * 60 * 1_000_000_000_i64;
+        let partition = partition_with_lp(
+            Arc::clone(&catalog),
+            &format!(
+                "{table},region=Asturias temp=35 {}\n\
+                {table},region=Madrid temp=20 {}\n\
+                {table},region=Asturias temp=36 {}",
+                10 * hour,
+                hour,
+                hour + 1,
+                table = &*ARBITRARY_TABLE_NAME,
+            ),
+        )
+        .await;
+        let partition_id = partition.lock().partition_id().clone();
+
+        let data = partition
+            .lock()
+            .mark_persisting()
+            .expect("partition with write should transition to persisting");
+
+        // Enqueue the persist job, and wait for it to complete.
+        let notify = handle.enqueue(Arc::clone(&partition), data).await;
+        let ids = notify
+            .with_timeout(Duration::from_secs(10))
+            .await
+            .expect("timeout waiting for completion notification")
+            .expect("worker task failed");
+        assert_eq!(ids.len(), 2);
+
+        // Each file is observed, with the sequence numbers of the write
+        // attached to exactly one of them.
+        assert_matches!(&completion_observer.calls().as_slice(), &[a, b] => {
+            assert_eq!(a.sequence_numbers().len(), 0);
+            assert_eq!(b.sequence_numbers().len(), 1);
+        });
+        assert_eq!(partition.lock().completed_persistence_count(), 1);
+
+        let mut files = catalog
+            .repositories()
+            .await
+            .parquet_files()
+            .list_by_partition_not_to_delete(&partition_id)
+            .await
+            .expect("query for parquet files failed");
+        files.sort_by_key(|f| f.min_time);
+
+        assert_matches!(&*files, [cold, live] => {
+            assert_eq!(cold.row_count, 2);
+            assert_eq!((cold.min_time.get(), cold.max_time.get()), (hour, hour + 1));
+            assert_eq!(live.row_count, 1);
+            assert_eq!((live.min_time.get(), live.max_time.get()), (10 * hour, 10 * hour));
+
+            let mut got = vec![cold.id, live.id];
+            got.sort();
+            let mut want = ids;
+            want.sort();
+            assert_eq!(got, want);
+        });
+    }
 }
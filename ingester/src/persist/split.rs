@@ -0,0 +1,136 @@
+use std::time::Duration;
+
+use arrow::{
+    array::{Array, BooleanArray, TimestampNanosecondArray},
+    compute::filter_record_batch,
+    record_batch::RecordBatch,
+};
+use schema::TIME_COLUMN_NAME;
+
+use crate::query_adaptor::QueryAdaptor;
+
+/// Split `data` by time into "cold" rows, with a timestamp more than
+/// `threshold` older than the newest row in `data`, and the rows at the live
+/// edge.
+///
+/// Persisting each half separately produces parquet files with tighter time
+/// ranges when late-arriving writes are buffered alongside recent writes. As
+/// all rows with the same timestamp end up in the same half, deduplication
+/// within each half remains correct.
+///
+/// Returns `data` unchanged if either half would be empty. Otherwise the cold
+/// half is returned first.
+pub(super) fn split_cold(data: QueryAdaptor, threshold: Duration) -> Vec<QueryAdaptor> {
+    let Some(ts) = data.ts_min_max() else {
+        return vec![data];
+    };
+
+    let threshold = i64::try_from(threshold.as_nanos()).unwrap_or(i64::MAX);
+    let cutoff = ts.max.saturating_sub(threshold);
+    if ts.min >= cutoff {
+        // There are no cold rows.
+        return vec![data];
+    }
+
+    let mut cold = vec![];
+    let mut live = vec![];
+    for batch in data.record_batches() {
+        let (c, l) = split_batch(batch, cutoff);
+        cold.extend(c);
+        live.extend(l);
+    }
+
+    let partition_id = data.partition_id();
+    vec![
+        QueryAdaptor::new(partition_id.clone(), cold),
+        QueryAdaptor::new(partition_id.clone(), live),
+    ]
+}
+
+/// Split `batch` into the rows with a timestamp before `cutoff`, and the rest,
+/// omitting empty batches.
+fn split_batch(batch: &RecordBatch, cutoff: i64) -> (Option<RecordBatch>, Option<RecordBatch>) {
+    let times = batch
+        .column_by_name(TIME_COLUMN_NAME)
+        .expect("persisting batch must have a time column")
+        .as_any()
+        .downcast_ref::<TimestampNanosecondArray>()
+        .expect("time column must be a nanosecond timestamp");
+
+    let is_cold = times
+        .iter()
+        .map(|t| Some(t.expect("time column must not contain nulls") < cutoff))
+        .collect::<BooleanArray>();
+    let is_live = is_cold
+        .iter()
+        .map(|v| v.map(|v| !v))
+        .collect::<BooleanArray>();
+
+    let filter = |mask: &BooleanArray| {
+        let v = filter_record_batch(batch, mask).expect("filter persisting batch by time");
+        (v.num_rows() > 0).then_some(v)
+    };
+
+    (filter(&is_cold), filter(&is_live))
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow_util::assert_batches_eq;
+    use mutable_batch_lp::test_helpers::lp_to_mutable_batch;
+    use schema::Projection;
+
+    use super::*;
+    use crate::test_util::ARBITRARY_TRANSITION_PARTITION_ID;
+
+    fn adaptor(lp: &[&str]) -> QueryAdaptor {
+        let batches = lp
+            .iter()
+            .map(|lp| {
+                lp_to_mutable_batch(lp)
+                    .1
+                    .to_arrow(Projection::All)
+                    .expect("failed to convert to arrow")
+            })
+            .collect();
+        QueryAdaptor::new(ARBITRARY_TRANSITION_PARTITION_ID.clone(), batches)
+    }
+
+    #[test]
+    fn test_split_cold() {
+        let data = adaptor(&[
+            "bananas,region=Asturias temp=35 1010\nbananas,region=Madrid temp=20 10",
+            "bananas,region=Asturias temp=36 20",
+        ]);
+
+        // Nothing is cold relative to the newest row.
+        let got = split_cold(data.clone(), Duration::from_nanos(1000));
+        assert_eq!(got.len(), 1);
+        assert_eq!(got[0].num_rows(), 3);
+
+        // The two old rows are split from the newest.
+        let got = split_cold(data, Duration::from_nanos(500));
+        assert_eq!(got.len(), 2);
+        assert_batches_eq!(
+            [
+                "+----------+------+--------------------------------+",
+                "| region   | temp | time                           |",
+                "+----------+------+--------------------------------+",
+                "| Madrid   | 20.0 | 1970-01-01T00:00:00.000000010Z |",
+                "| Asturias | 36.0 | 1970-01-01T00:00:00.000000020Z |",
+                "+----------+------+--------------------------------+",
+            ],
+            got[0].record_batches()
+        );
+        assert_batches_eq!(
+            [
+                "+----------+------+--------------------------------+",
+                "| region   | temp | time                           |",
+                "+----------+------+--------------------------------+",
+                "| Asturias | 35.0 | 1970-01-01T00:00:00.000001010Z |",
+                "+----------+------+--------------------------------+",
+            ],
+            got[1].record_batches()
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use std::{ops::ControlFlow, sync::Arc};
+use std::{ops::ControlFlow, sync::Arc, time::Duration};
 
 use async_channel::RecvError;
 use backoff::Backoff;
@@ -13,12 +13,13 @@ use schema::sort::SortKey;
 use tokio::{sync::mpsc, time::Instant};
 use uuid::Uuid;
 
-use crate::persist::compact::compact_persisting_batch;
+use crate::{persist::compact::compact_persisting_batch, query_adaptor::QueryAdaptor};
 
 use super::{
     compact::CompactedStream,
     completion_observer::PersistCompletionObserver,
     context::{Context, PersistError, PersistRequest},
+    split::split_cold,
 };
 
 /// State shared across workers.
@@ -28,6 +29,10 @@ pub(super) struct SharedWorkerState<O> {
     pub(super) store: ParquetStorage,
     pub(super) catalog: Arc<dyn Catalog>,
     pub(super) completion_observer: O,
+
+    /// When set, rows older than this duration before the newest row of the
+    /// persisting data are persisted to a separate parquet file.
+    pub(super) cold_split_threshold: Option<Duration>,
 }
 
 /// The worker routine that drives a [`PersistRequest`] to completion,
@@ -119,27 +124,38 @@ pub(super) async fn run_task<O>(
         let started_at = Instant::now();
         queue_duration.record(started_at.duration_since(ctx.enqueued_at()));
 
-        // Compact the data, generate the parquet file from the result, and
-        // upload it to object storage.
-        //
-        // If this process generated a new sort key that must be added to the
-        // catalog, attempt to update the catalog with a compare-and-swap
-        // operation; if this update fails due to a concurrent sort key update,
-        // the compaction must be redone with the new sort key and uploaded
-        // before continuing.
-        let parquet_table_data = loop {
-            match compact_and_upload(&mut ctx, &worker_state).await {
-                Ok(v) => break v,
-                Err(PersistError::ConcurrentSortKeyUpdate(_)) => continue,
-            };
+        // Split cold rows from the live edge of the data (if configured), so
+        // that each is persisted to a parquet file with a tighter time range.
+        let parts = match worker_state.cold_split_threshold {
+            Some(threshold) => split_cold(ctx.data().query_adaptor(), threshold),
+            None => vec![ctx.data().query_adaptor()],
         };
 
-        // Make the newly uploaded parquet file visible to other nodes.
-        let parquet_file = update_catalog_parquet(&ctx, &worker_state, &parquet_table_data).await;
+        let mut parquet_files = Vec::with_capacity(parts.len());
+        for data in parts {
+            // Compact the data, generate the parquet file from the result, and
+            // upload it to object storage.
+            //
+            // If this process generated a new sort key that must be added to
+            // the catalog, attempt to update the catalog with a
+            // compare-and-swap operation; if this update fails due to a
+            // concurrent sort key update, the compaction must be redone with
+            // the new sort key and uploaded before continuing.
+            let parquet_table_data = loop {
+                match compact_and_upload(&mut ctx, &worker_state, data.clone()).await {
+                    Ok(v) => break v,
+                    Err(PersistError::ConcurrentSortKeyUpdate(_)) => continue,
+                };
+            };
+
+            // Make the newly uploaded parquet file visible to other nodes.
+            parquet_files
+                .push(update_catalog_parquet(&ctx, &worker_state, &parquet_table_data).await);
+        }
 
         // And finally mark the persist job as complete and notify any
         // observers.
-        ctx.mark_complete(parquet_file, &worker_state.completion_observer)
+        ctx.mark_complete(parquet_files, &worker_state.completion_observer)
             .await;
 
         // Capture the time spent actively persisting.
@@ -148,8 +164,8 @@ pub(super) async fn run_task<O>(
     }
 }
 
-/// Run a compaction on the [`PersistingData`], generate a parquet file and
-/// upload it to object storage.
+/// Run a compaction on `data` from the [`PersistingData`], generate a parquet
+/// file and upload it to object storage.
 ///
 /// This function composes functionality from the smaller [`compact()`],
 /// [`upload()`], and [`update_catalog_sort_key()`] functions.
@@ -165,6 +181,7 @@ pub(super) async fn run_task<O>(
 async fn compact_and_upload<O>(
     ctx: &mut Context,
     worker_state: &SharedWorkerState<O>,
+    data: QueryAdaptor,
 ) -> Result<ParquetFileParams, PersistError>
 where
     O: Send + Sync,
@@ -175,7 +192,7 @@ where
     // THIS MUST BE DONE AFTER THE SORT KEY IS LOADED
     let (sort_key, columns) = fetch_column_map(ctx, worker_state, sort_key).await?;
 
-    let compacted = compact(ctx, worker_state, sort_key).await;
+    let compacted = compact(ctx, worker_state, sort_key, data).await;
     let (sort_key_update, parquet_table_data) =
         upload(ctx, worker_state, compacted, &columns).await;
 
@@ -193,12 +210,13 @@ where
     Ok(parquet_table_data)
 }
 
-/// Compact the data in `ctx` using sorted by the sort key returned from
+/// Compact `data` from `ctx` sorted by the sort key returned from
 /// [`Context::sort_key()`].
 async fn compact<O>(
     ctx: &Context,
     worker_state: &SharedWorkerState<O>,
     sort_key: Option<SortKey>,
+    data: QueryAdaptor,
 ) -> CompactedStream
 where
     O: Send + Sync,
@@ -214,7 +232,7 @@ where
         "compacting partition"
     );
 
-    assert!(!ctx.data().record_batches().is_empty());
+    assert!(!data.record_batches().is_empty());
 
     // Run a compaction sort the data and resolve any duplicate values.
     //
@@ -224,7 +242,7 @@ where
         &worker_state.exec,
         sort_key,
         ctx.table().get().await.name().clone(),
-        ctx.data().query_adaptor(),
+        data,
     )
     .await
     .expect("unable to compact persisting batch")
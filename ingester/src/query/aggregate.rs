@@ -0,0 +1,465 @@
+//! Evaluation of an [`Aggregate`] pushed down to the ingester over the buffered
+//! data of a single partition.
+//!
+//! The querier deduplicates and filters the rows returned by the ingester, so
+//! a partition can only be aggregated by the ingester when neither is needed
+//! to produce a correct result - partitions that cannot be aggregated are
+//! returned unaggregated instead.
+
+use std::{
+    collections::{BTreeMap, BTreeSet, HashSet},
+    sync::Arc,
+};
+
+use arrow::{
+    array::{Array, ArrayRef, DictionaryArray, Int64Array, StringArray, TimestampNanosecondArray},
+    compute::cast,
+    datatypes::{DataType, Int32Type},
+    record_batch::RecordBatch,
+};
+use data_types::TimestampRange;
+use datafusion::scalar::ScalarValue;
+use ingester_query_grpc::{Aggregate, AggregateFunction};
+use predicate::Predicate;
+use schema::{builder::SchemaBuilder, InfluxFieldType, TIME_COLUMN_NAME};
+use thiserror::Error;
+
+/// Errors aggregating the data of a partition.
+#[derive(Debug, Error)]
+pub(crate) enum AggregateError {
+    /// The aggregate groups by a column that is a field of the partition.
+    #[error("cannot group by field column {0}")]
+    GroupByField(String),
+}
+
+/// Evaluates an [`Aggregate`] over the data of each partition in a query
+/// response.
+#[derive(Debug)]
+pub(crate) struct PartitionAggregator {
+    aggregate: Aggregate,
+
+    /// The time range of the query predicate, applied to the rows before
+    /// aggregating them.
+    range: Option<TimestampRange>,
+}
+
+impl PartitionAggregator {
+    /// Construct a [`PartitionAggregator`] evaluating `aggregate` over the
+    /// rows matching `predicate`.
+    ///
+    /// Returns [`None`] if `predicate` contains expressions that are not
+    /// evaluated by the ingester, as aggregating the unfiltered rows would
+    /// produce an incorrect result.
+    pub(crate) fn new(aggregate: Aggregate, predicate: Option<&Predicate>) -> Option<Self> {
+        let range = match predicate {
+            Some(p) if !p.exprs.is_empty() || !p.value_expr.is_empty() => return None,
+            Some(p) => p.range,
+            None => None,
+        };
+
+        Some(Self { aggregate, range })
+    }
+
+    /// Aggregate the rows in `batches`, the data of a single partition.
+    ///
+    /// Returns [`None`] if the rows cannot be aggregated, either because they
+    /// contain more than one row for the same series and timestamp (which the
+    /// querier must deduplicate first), or because the aggregate function is
+    /// not defined for the type of one of the field columns, and an error if
+    /// the aggregate groups by a field column.
+    pub(crate) fn aggregate(
+        &self,
+        batches: &[RecordBatch],
+    ) -> Result<Option<RecordBatch>, AggregateError> {
+        let function = self.aggregate.function;
+
+        // Discover the tag and field columns across all batches, which may
+        // have differing schemas.
+        let mut tags = BTreeSet::new();
+        let mut fields = BTreeMap::new();
+        for batch in batches {
+            for f in batch.schema().fields() {
+                match f.data_type() {
+                    _ if f.name() == TIME_COLUMN_NAME => {}
+                    DataType::Dictionary(_, _) => {
+                        tags.insert(f.name().clone());
+                    }
+                    t => {
+                        let Ok(t) = InfluxFieldType::try_from(t.clone()) else {
+                            return Ok(None);
+                        };
+                        fields.insert(f.name().clone(), t);
+                    }
+                }
+            }
+        }
+
+        if let Some(name) = self
+            .aggregate
+            .group_by
+            .iter()
+            .find(|v| fields.contains_key(*v))
+        {
+            return Err(AggregateError::GroupByField(name.clone()));
+        }
+
+        Ok(self.aggregate_rows(function, batches, tags, fields))
+    }
+
+    /// Aggregate the rows in `batches` with the given `tags` and `fields`.
+    fn aggregate_rows(
+        &self,
+        function: AggregateFunction,
+        batches: &[RecordBatch],
+        tags: BTreeSet<String>,
+        fields: BTreeMap<String, InfluxFieldType>,
+    ) -> Option<RecordBatch> {
+        if function == AggregateFunction::Sum
+            && fields
+                .values()
+                .any(|t| matches!(t, InfluxFieldType::String | InfluxFieldType::Boolean))
+        {
+            return None;
+        }
+
+        let tags = tags.into_iter().collect::<Vec<_>>();
+        let fields = fields.into_iter().collect::<Vec<_>>();
+
+        let mut seen = HashSet::new();
+        let mut groups: BTreeMap<(Vec<Option<String>>, i64), Vec<Accumulator>> = BTreeMap::new();
+        for batch in batches {
+            let times = batch
+                .column_by_name(TIME_COLUMN_NAME)?
+                .as_any()
+                .downcast_ref::<TimestampNanosecondArray>()?;
+
+            let tag_values = tags
+                .iter()
+                .map(|name| {
+                    batch
+                        .column_by_name(name)
+                        .map(|v| cast(v, &DataType::Utf8).expect("tag must be castable to utf8"))
+                })
+                .collect::<Vec<_>>();
+            let field_values = fields
+                .iter()
+                .map(|(name, _)| batch.column_by_name(name))
+                .collect::<Vec<_>>();
+
+            for row in 0..batch.num_rows() {
+                let t = times.value(row);
+                if self.range.map_or(false, |r| !r.contains(t)) {
+                    continue;
+                }
+
+                let series = tag_values
+                    .iter()
+                    .map(|v| string_value(v.as_ref(), row))
+                    .collect::<Vec<_>>();
+
+                let group = self
+                    .aggregate
+                    .group_by
+                    .iter()
+                    .map(|name| match tags.binary_search(name) {
+                        Ok(idx) => series[idx].clone(),
+                        Err(_) => None,
+                    })
+                    .collect::<Vec<_>>();
+
+                // A duplicate primary key requires deduplication by the
+                // querier before aggregating.
+                if !seen.insert((series, t)) {
+                    return None;
+                }
+
+                let window = t - t.rem_euclid(self.aggregate.window_nanos);
+                let acc = groups
+                    .entry((group, window))
+                    .or_insert_with(|| vec![Accumulator::default(); fields.len()]);
+
+                for (acc, array) in acc.iter_mut().zip(&field_values) {
+                    let Some(array) = array else { continue };
+                    let v = ScalarValue::try_from_array(array, row)
+                        .expect("field value must be convertible to scalar");
+                    if !v.is_null() {
+                        acc.update(function, t, v);
+                    }
+                }
+            }
+        }
+
+        // Build the output schema, with the group columns, each field column
+        // and the window start time.
+        let mut builder = SchemaBuilder::new();
+        for name in &self.aggregate.group_by {
+            builder.tag(name);
+        }
+        for (name, t) in &fields {
+            let t = match function {
+                AggregateFunction::Count => InfluxFieldType::Integer,
+                _ => *t,
+            };
+            builder.influx_field(name, t);
+        }
+        // The group by columns are neither the time column, nor duplicated
+        // (see `Aggregate::try_from()`), nor fields (checked above).
+        let schema = builder
+            .timestamp()
+            .build()
+            .expect("aggregate schema must be valid")
+            .as_arrow();
+
+        if groups.is_empty() {
+            return Some(RecordBatch::new_empty(schema));
+        }
+
+        let mut columns: Vec<ArrayRef> = Vec::with_capacity(schema.fields().len());
+        for idx in 0..self.aggregate.group_by.len() {
+            columns.push(Arc::new(
+                groups
+                    .keys()
+                    .map(|(group, _)| group[idx].as_deref())
+                    .collect::<DictionaryArray<Int32Type>>(),
+            ));
+        }
+        for (idx, (_, t)) in fields.iter().enumerate() {
+            let values = groups.values().map(|acc| &acc[idx]);
+            let array: ArrayRef = match function {
+                AggregateFunction::Count => {
+                    Arc::new(values.map(|acc| acc.count).collect::<Int64Array>())
+                }
+                _ => {
+                    let null = ScalarValue::try_from(&DataType::from(*t))
+                        .expect("field type must have a null scalar");
+                    ScalarValue::iter_to_array(
+                        values.map(|acc| acc.value.clone().unwrap_or_else(|| null.clone())),
+                    )
+                    .expect("field values must have a consistent type")
+                }
+            };
+            columns.push(array);
+        }
+        columns.push(Arc::new(TimestampNanosecondArray::from(
+            groups.keys().map(|(_, t)| *t).collect::<Vec<_>>(),
+        )));
+
+        Some(RecordBatch::try_new(schema, columns).expect("aggregate output must match schema"))
+    }
+}
+
+/// Read the value of the UTF-8 `array` at `row`, if any.
+fn string_value(array: Option<&ArrayRef>, row: usize) -> Option<String> {
+    let array = array?.as_any().downcast_ref::<StringArray>()?;
+    array.is_valid(row).then(|| array.value(row).to_string())
+}
+
+/// The aggregate state of a single field column within a group.
+#[derive(Debug, Clone, Default)]
+struct Accumulator {
+    /// The number of non-null values observed.
+    count: i64,
+
+    /// The aggregate value of the non-null values observed.
+    value: Option<ScalarValue>,
+
+    /// The timestamp of the current `value`, used to evaluate
+    /// [`AggregateFunction::Last`].
+    time: i64,
+}
+
+impl Accumulator {
+    fn update(&mut self, function: AggregateFunction, t: i64, v: ScalarValue) {
+        self.count += 1;
+
+        let replace = match (&self.value, function) {
+            (_, AggregateFunction::Count) => false,
+            (None, _) => true,
+            (Some(current), AggregateFunction::Sum) => {
+                self.value = Some(sum(current, &v));
+                false
+            }
+            (Some(current), AggregateFunction::Min) => v < *current,
+            (Some(current), AggregateFunction::Max) => v > *current,
+            (Some(_), AggregateFunction::Last) => t >= self.time,
+        };
+
+        if replace {
+            self.value = Some(v);
+            self.time = t;
+        }
+    }
+}
+
+/// Add two non-null numeric field values of the same type.
+fn sum(a: &ScalarValue, b: &ScalarValue) -> ScalarValue {
+    match (a, b) {
+        (ScalarValue::Float64(Some(a)), ScalarValue::Float64(Some(b))) => {
+            ScalarValue::Float64(Some(a + b))
+        }
+        (ScalarValue::Int64(Some(a)), ScalarValue::Int64(Some(b))) => {
+            ScalarValue::Int64(Some(a.wrapping_add(*b)))
+        }
+        (ScalarValue::UInt64(Some(a)), ScalarValue::UInt64(Some(b))) => {
+            ScalarValue::UInt64(Some(a.wrapping_add(*b)))
+        }
+        (a, b) => unreachable!("cannot sum field values {a:?} and {b:?}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow_util::assert_batches_eq;
+    use assert_matches::assert_matches;
+    use mutable_batch_lp::test_helpers::lp_to_mutable_batch;
+    use schema::Projection;
+
+    use super::*;
+
+    fn batch(lp: &str) -> RecordBatch {
+        lp_to_mutable_batch(lp)
+            .1
+            .to_arrow(Projection::All)
+            .expect("failed to convert to arrow")
+    }
+
+    fn aggregator(function: AggregateFunction, group_by: &[&str]) -> PartitionAggregator {
+        PartitionAggregator::new(
+            Aggregate {
+                function,
+                group_by: group_by.iter().map(ToString::to_string).collect(),
+                window_nanos: 10,
+            },
+            None,
+        )
+        .expect("no predicate must be evaluable")
+    }
+
+    #[test]
+    fn test_aggregate() {
+        let batches = [
+            batch(
+                "bananas,region=Asturias,city=Gijon temp=35,count=1i 1\n\
+                 bananas,region=Asturias,city=Oviedo temp=37,count=2i 2\n\
+                 bananas,region=Madrid temp=20 3",
+            ),
+            // A batch with a different schema, in the second window.
+            batch("bananas,region=Asturias count=4i 12"),
+        ];
+
+        assert_batches_eq!(
+            [
+                "+----------+-------+------+--------------------------------+",
+                "| region   | count | temp | time                           |",
+                "+----------+-------+------+--------------------------------+",
+                "| Asturias | 3     | 72.0 | 1970-01-01T00:00:00Z           |",
+                "| Asturias | 4     |      | 1970-01-01T00:00:00.000000010Z |",
+                "| Madrid   |       | 20.0 | 1970-01-01T00:00:00Z           |",
+                "+----------+-------+------+--------------------------------+",
+            ],
+            &[aggregator(AggregateFunction::Sum, &["region"])
+                .aggregate(&batches)
+                .unwrap()
+                .unwrap()]
+        );
+
+        assert_batches_eq!(
+            [
+                "+-------+------+--------------------------------+",
+                "| count | temp | time                           |",
+                "+-------+------+--------------------------------+",
+                "| 2     | 3    | 1970-01-01T00:00:00Z           |",
+                "| 1     | 0    | 1970-01-01T00:00:00.000000010Z |",
+                "+-------+------+--------------------------------+",
+            ],
+            &[aggregator(AggregateFunction::Count, &[])
+                .aggregate(&batches)
+                .unwrap()
+                .unwrap()]
+        );
+
+        assert_batches_eq!(
+            [
+                "+-------+------+--------------------------------+",
+                "| count | temp | time                           |",
+                "+-------+------+--------------------------------+",
+                "| 2     | 20.0 | 1970-01-01T00:00:00Z           |",
+                "| 4     |      | 1970-01-01T00:00:00.000000010Z |",
+                "+-------+------+--------------------------------+",
+            ],
+            &[aggregator(AggregateFunction::Last, &[])
+                .aggregate(&batches)
+                .unwrap()
+                .unwrap()]
+        );
+    }
+
+    #[test]
+    fn test_aggregate_time_range() {
+        let batches = [batch(
+            "bananas,region=Asturias temp=35 1\nbananas,region=Asturias temp=37 2",
+        )];
+
+        let agg = PartitionAggregator::new(
+            Aggregate {
+                function: AggregateFunction::Max,
+                group_by: vec![],
+                window_nanos: 10,
+            },
+            Some(&Predicate::new().with_range(0, 2)),
+        )
+        .unwrap();
+
+        assert_batches_eq!(
+            [
+                "+------+----------------------+",
+                "| temp | time                 |",
+                "+------+----------------------+",
+                "| 35.0 | 1970-01-01T00:00:00Z |",
+                "+------+----------------------+",
+            ],
+            &[agg.aggregate(&batches).unwrap().unwrap()]
+        );
+    }
+
+    #[test]
+    fn test_not_aggregated() {
+        // Rows for the same series and timestamp must be deduplicated by the
+        // querier.
+        let batches = [
+            batch("bananas,region=Asturias temp=35 1"),
+            batch("bananas,region=Asturias temp=37 1"),
+        ];
+        assert!(aggregator(AggregateFunction::Max, &[])
+            .aggregate(&batches)
+            .unwrap()
+            .is_none());
+
+        // String fields cannot be summed.
+        let batches = [batch(r#"bananas,region=Asturias name="bananas" 1"#)];
+        assert!(aggregator(AggregateFunction::Sum, &[])
+            .aggregate(&batches)
+            .unwrap()
+            .is_none());
+
+        // Expressions in the predicate are not evaluated by the ingester.
+        assert!(PartitionAggregator::new(
+            Aggregate {
+                function: AggregateFunction::Count,
+                group_by: vec![],
+                window_nanos: 10,
+            },
+            Some(&Predicate::new().with_expr(datafusion::prelude::col("temp"))),
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_group_by_field() {
+        let batches = [batch("bananas,region=Asturias temp=35 1")];
+        let err = aggregator(AggregateFunction::Max, &["temp"])
+            .aggregate(&batches)
+            .unwrap_err();
+        assert_matches!(err, AggregateError::GroupByField(name) if name == "temp");
+    }
+}
@@ -3,6 +3,7 @@
 mod r#trait;
 pub(crate) use r#trait::*;
 
+pub(crate) mod aggregate;
 pub(crate) mod projection;
 
 // Response types
@@ -2,6 +2,7 @@
 
 mod persist;
 mod query;
+mod replication;
 mod rpc_write;
 
 use std::{fmt::Debug, sync::Arc};
@@ -18,9 +19,10 @@ use crate::{
     persist::queue::PersistQueue,
     query::{response::QueryResponse, QueryExec},
     timestamp_oracle::TimestampOracle,
+    wal::replication::ReplicaStore,
 };
 
-use self::{persist::PersistHandler, rpc_write::RpcWrite};
+use self::{persist::PersistHandler, replication::ReplicationHandler, rpc_write::RpcWrite};
 
 /// This type is responsible for injecting internal dependencies that SHOULD NOT
 /// leak outside of the ingester crate into public gRPC handlers.
@@ -38,6 +40,7 @@ pub(crate) struct GrpcDelegate<D, Q, T, P> {
     metrics: Arc<metric::Registry>,
     buffer: Arc<T>,
     persist_handle: Arc<P>,
+    replica_store: Option<Arc<ReplicaStore>>,
 }
 
 impl<D, Q, T, P> GrpcDelegate<D, Q, T, P>
@@ -59,6 +62,7 @@ where
         metrics: Arc<metric::Registry>,
         buffer: Arc<T>,
         persist_handle: Arc<P>,
+        replica_store: Option<Arc<ReplicaStore>>,
     ) -> Self {
         Self {
             dml_sink,
@@ -70,6 +74,7 @@ where
             metrics,
             buffer,
             persist_handle,
+            replica_store,
         }
     }
 }
@@ -86,6 +91,7 @@ where
     type CatalogHandler = CatalogService;
     type WriteHandler = RpcWrite<Arc<D>>;
     type PersistHandler = PersistHandler<Arc<T>, Arc<P>>;
+    type ReplicationHandler = ReplicationHandler;
     type FlightHandler = query::FlightService<Arc<Q>>;
 
     /// Acquire a [`CatalogService`] gRPC service implementation.
@@ -117,6 +123,13 @@ where
         )
     }
 
+    /// Return a [`ReplicationService`] gRPC implementation.
+    ///
+    /// [`ReplicationService`]: generated_types::influxdata::iox::ingester::v1::replication_service_server::ReplicationService.
+    fn replication_service(&self) -> Self::ReplicationHandler {
+        ReplicationHandler::new(self.replica_store.clone())
+    }
+
     /// Return an Arrow [`FlightService`] gRPC implementation.
     ///
     /// [`FlightService`]: arrow_flight::flight_service_server::FlightService
@@ -1,14 +1,17 @@
-use crate::{
-    partition_iter::PartitionIter,
-    persist::{drain_buffer::persist_partitions, queue::PersistQueue},
-};
+use crate::{partition_iter::PartitionIter, persist::queue::PersistQueue};
+use data_types::PartitionKey;
 use generated_types::influxdata::iox::ingester::v1::{
     self as proto, persist_service_server::PersistService,
 };
 use iox_catalog::interface::{Catalog, SoftDeletedRows};
-use std::sync::Arc;
+use observability_deps::tracing::*;
+use std::{sync::Arc, time::Duration};
 use tonic::{Request, Response};
 
+/// The interval between checks for the completion of persist jobs that were
+/// outstanding when a waiting persist call was made.
+const OUTSTANDING_PERSIST_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
 #[derive(Debug)]
 pub(crate) struct PersistHandler<T, P> {
     buffer: T,
@@ -36,33 +39,305 @@ where
     T: PartitionIter + Sync + 'static,
     P: PersistQueue + Clone + Sync + 'static,
 {
-    /// Handle the RPC request to persist immediately. Will block until the data has persisted,
-    /// which is useful in tests asserting on persisted data. May behave in unexpected ways if used
-    /// concurrently with writes and ingester WAL rotations.
+    /// Handle the RPC request to persist the buffered data of a namespace,
+    /// table or partition immediately.
+    ///
+    /// Unless the request asks not to wait, blocks until the data buffered for
+    /// the selected partitions at the time of the call has persisted,
+    /// including data that was already being persisted when the call was made.
     async fn persist(
         &self,
         request: Request<proto::PersistRequest>,
     ) -> Result<Response<proto::PersistResponse>, tonic::Status> {
         let request = request.into_inner();
 
-        let namespace = self
-            .catalog
-            .repositories()
+        if request.partition_key.is_some() && request.table.is_none() {
+            return Err(tonic::Status::invalid_argument(
+                "persisting a partition requires a table to be specified",
+            ));
+        }
+
+        let (namespace_id, table_id) = {
+            let mut repos = self.catalog.repositories().await;
+
+            let namespace = repos
+                .namespaces()
+                .get_by_name(&request.namespace, SoftDeletedRows::AllRows)
+                .await
+                .map_err(|e| tonic::Status::internal(e.to_string()))?
+                .ok_or_else(|| tonic::Status::not_found(&request.namespace))?;
+
+            let table_id = match &request.table {
+                Some(name) => Some(
+                    repos
+                        .tables()
+                        .get_by_namespace_and_name(namespace.id, name)
+                        .await
+                        .map_err(|e| tonic::Status::internal(e.to_string()))?
+                        .ok_or_else(|| tonic::Status::not_found(name))?
+                        .id,
+                ),
+                None => None,
+            };
+
+            (namespace.id, table_id)
+        };
+        let partition_key = request.partition_key.map(PartitionKey::from);
+
+        let partitions = self.buffer.partition_iter().filter(|p| {
+            let p = p.lock();
+            p.namespace_id() == namespace_id
+                && table_id.map_or(true, |v| p.table_id() == v)
+                && partition_key
+                    .as_ref()
+                    .map_or(true, |v| p.partition_key() == v)
+        });
+
+        // Mark each selected partition as persisting, recording the most
+        // recent persist job started for it, which covers both the job
+        // enqueued here and any outstanding jobs started before this call.
+        let mut notifications = vec![];
+        let mut selected = vec![];
+        for p in partitions {
+            let (data, last_ident) = {
+                let mut guard = p.lock();
+                (guard.mark_persisting(), guard.last_persisting_ident())
+            };
+
+            if let Some(data) = data {
+                notifications.push(self.persist_handle.enqueue(Arc::clone(&p), data).await);
+            }
+            selected.push((p, last_ident));
+        }
+
+        let partitions_enqueued = notifications.len() as u64;
+        debug!(
+            namespace_id = %namespace_id,
+            ?table_id,
+            ?partition_key,
+            partitions_enqueued,
+            no_wait = request.no_wait,
+            "enqueued partitions for persistence"
+        );
+
+        if request.no_wait {
+            return Ok(Response::new(proto::PersistResponse {
+                partitions_enqueued,
+                parquet_file_ids: vec![],
+            }));
+        }
+
+        // Wait for the persist jobs enqueued above to complete.
+        let mut parquet_file_ids = Vec::with_capacity(notifications.len());
+        for n in notifications {
+            let ids = n
+                .await
+                .map_err(|_| tonic::Status::internal("persist job did not complete"))?;
+            parquet_file_ids.extend(ids.into_iter().map(|v| v.get()));
+        }
+
+        // Persist jobs may complete out of order, so also wait for any persist
+        // jobs that were outstanding for the selected partitions when this
+        // call was made.
+        for (p, last_ident) in selected {
+            loop {
+                let persisting = p.lock().is_persisting_through(last_ident);
+                if !persisting {
+                    break;
+                }
+                tokio::time::sleep(OUTSTANDING_PERSIST_POLL_INTERVAL).await;
+            }
+        }
+
+        Ok(Response::new(proto::PersistResponse {
+            partitions_enqueued,
+            parquet_file_ids,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use data_types::{NamespaceId, SequenceNumber, TableId};
+    use iox_catalog::{
+        mem::MemCatalog,
+        test_helpers::{arbitrary_namespace, arbitrary_table},
+    };
+    use mutable_batch_lp::test_helpers::lp_to_mutable_batch;
+    use parking_lot::Mutex;
+    use test_helpers::timeout::FutureTimeout;
+
+    use super::*;
+    use crate::{
+        buffer_tree::partition::PartitionData, persist::queue::mock::MockPersistQueue,
+        test_util::PartitionDataBuilder,
+    };
+
+    const NAMESPACE_NAME: &str = "ns-bananas";
+
+    /// Build a partition with a single buffered row.
+    fn partition(
+        namespace_id: NamespaceId,
+        table_id: TableId,
+        key: &str,
+    ) -> Arc<Mutex<PartitionData>> {
+        let mut p = PartitionDataBuilder::new()
+            .with_namespace_id(namespace_id)
+            .with_table_id(table_id)
+            .with_partition_key(PartitionKey::from(key))
+            .build();
+
+        let mb = lp_to_mutable_batch(r#"bananas,city=Hereford people=1 42"#).1;
+        p.buffer_write(mb, SequenceNumber::new(1))
+            .expect("write should succeed");
+
+        Arc::new(Mutex::new(p))
+    }
+
+    fn request(
+        table: Option<&str>,
+        partition_key: Option<&str>,
+        wait: bool,
+    ) -> Request<proto::PersistRequest> {
+        Request::new(proto::PersistRequest {
+            namespace: NAMESPACE_NAME.to_string(),
+            table: table.map(ToString::to_string),
+            partition_key: partition_key.map(ToString::to_string),
+            no_wait: !wait,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_persist_filters() {
+        let metrics = Arc::new(metric::Registry::default());
+        let catalog: Arc<dyn Catalog> = Arc::new(MemCatalog::new(Arc::clone(&metrics)));
+
+        let (namespace_id, bananas_id, platanos_id) = {
+            let mut repos = catalog.repositories().await;
+            let ns = arbitrary_namespace(&mut *repos, NAMESPACE_NAME).await;
+            let bananas = arbitrary_table(&mut *repos, "bananas", &ns).await;
+            let platanos = arbitrary_table(&mut *repos, "platanos", &ns).await;
+            (ns.id, bananas.id, platanos.id)
+        };
+
+        let partitions = vec![
+            partition(namespace_id, bananas_id, "2023-01-01"),
+            partition(namespace_id, bananas_id, "2023-01-02"),
+            partition(namespace_id, platanos_id, "2023-01-01"),
+            partition(
+                NamespaceId::new(namespace_id.get() + 1),
+                bananas_id,
+                "2023-01-01",
+            ),
+        ];
+
+        let persist_handle = Arc::new(MockPersistQueue::default());
+        let handler = PersistHandler::new(
+            partitions.clone(),
+            Arc::clone(&persist_handle),
+            Arc::clone(&catalog),
+        );
+
+        // A partition key cannot be specified without a table.
+        let err = handler
+            .persist(request(None, Some("2023-01-01"), true))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+
+        // Unknown tables are rejected.
+        let err = handler
+            .persist(request(Some("unknown"), None, true))
             .await
-            .namespaces()
-            .get_by_name(&request.namespace, SoftDeletedRows::AllRows)
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::NotFound);
+
+        // Persist a single partition, waiting for it to complete.
+        let resp = handler
+            .persist(request(Some("bananas"), Some("2023-01-02"), true))
+            .with_timeout_panic(Duration::from_secs(5))
             .await
-            .map_err(|e| tonic::Status::internal(e.to_string()))?
-            .ok_or_else(|| tonic::Status::not_found(&request.namespace))?;
-
-        persist_partitions(
-            self.buffer
-                .partition_iter()
-                .filter(|p| p.lock().namespace_id() == namespace.id),
-            &self.persist_handle,
-        )
-        .await;
-
-        Ok(Response::new(proto::PersistResponse {}))
+            .expect("persist should succeed")
+            .into_inner();
+        assert_eq!(resp.partitions_enqueued, 1);
+        assert_eq!(resp.parquet_file_ids, [42]);
+        assert_matches!(persist_handle.calls().as_slice(), [p] => {
+            assert!(Arc::ptr_eq(p, &partitions[1]));
+        });
+        assert_eq!(partitions[1].lock().buffered_bytes(), 0);
+
+        // Persist the remainder of the table without waiting.
+        let resp = handler
+            .persist(request(Some("bananas"), None, false))
+            .await
+            .expect("persist should succeed")
+            .into_inner();
+        assert_eq!(resp.partitions_enqueued, 1);
+        assert!(resp.parquet_file_ids.is_empty());
+        assert_matches!(persist_handle.calls().as_slice(), [_, p] => {
+            assert!(Arc::ptr_eq(p, &partitions[0]));
+        });
+
+        // Persist the whole namespace, which leaves the partition of the other
+        // namespace alone.
+        let resp = handler
+            .persist(request(None, None, true))
+            .with_timeout_panic(Duration::from_secs(5))
+            .await
+            .expect("persist should succeed")
+            .into_inner();
+        assert_eq!(resp.partitions_enqueued, 1);
+        assert_matches!(persist_handle.calls().as_slice(), [_, _, p] => {
+            assert!(Arc::ptr_eq(p, &partitions[2]));
+        });
+        assert!(partitions[3].lock().buffered_bytes() > 0);
+    }
+
+    /// Waiting for a persist also waits for the persist jobs for the selected
+    /// partitions that were outstanding at the time of the call.
+    #[tokio::test]
+    async fn test_persist_waits_for_outstanding() {
+        let metrics = Arc::new(metric::Registry::default());
+        let catalog: Arc<dyn Catalog> = Arc::new(MemCatalog::new(Arc::clone(&metrics)));
+
+        let (namespace_id, table_id) = {
+            let mut repos = catalog.repositories().await;
+            let ns = arbitrary_namespace(&mut *repos, NAMESPACE_NAME).await;
+            let table = arbitrary_table(&mut *repos, "bananas", &ns).await;
+            (ns.id, table.id)
+        };
+
+        let p = partition(namespace_id, table_id, "2023-01-01");
+
+        // Start a persist job outside of the handler, which completes after a
+        // delay.
+        let data = p.lock().mark_persisting().expect("must have data");
+        let outstanding = tokio::spawn({
+            let p = Arc::clone(&p);
+            async move {
+                tokio::time::sleep(Duration::from_millis(200)).await;
+                p.lock().mark_persisted(data);
+            }
+        });
+
+        let handler = PersistHandler::new(
+            vec![Arc::clone(&p)],
+            Arc::new(MockPersistQueue::default()),
+            Arc::clone(&catalog),
+        );
+
+        // There is no new data to persist, but the call must not return until
+        // the outstanding persist job has completed.
+        let resp = handler
+            .persist(request(Some("bananas"), None, true))
+            .with_timeout_panic(Duration::from_secs(5))
+            .await
+            .expect("persist should succeed")
+            .into_inner();
+        assert_eq!(resp.partitions_enqueued, 0);
+        assert_eq!(p.lock().buffered_bytes(), 0);
+
+        outstanding.await.expect("outstanding persist panic");
     }
 }
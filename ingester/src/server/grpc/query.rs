@@ -27,7 +27,10 @@ use instrumentation::FlightFrameEncodeInstrumentation;
 
 use crate::{
     ingester_id::IngesterId,
-    query::{projection::OwnedProjection, response::QueryResponse, QueryError, QueryExec},
+    query::{
+        aggregate::PartitionAggregator, projection::OwnedProjection, response::QueryResponse,
+        QueryError, QueryExec,
+    },
 };
 
 /// Error states for the query RPC handler.
@@ -207,6 +210,18 @@ where
             None
         };
 
+        // Aggregate the data of each partition before returning it, if the
+        // request asks for it and the predicate allows it.
+        let aggregator = match request.aggregate {
+            Some(a) => {
+                let a = ingester_query_grpc::Aggregate::try_from(a)
+                    .map_err(|e| Error::from(e.scope("aggregate")))?;
+                debug!(aggregate=?a, "received query aggregate");
+                PartitionAggregator::new(a, predicate.as_ref())
+            }
+            None => None,
+        };
+
         let projection = OwnedProjection::from(request.columns);
 
         let response = match self
@@ -235,6 +250,7 @@ where
 
         let output = encode_response(
             response,
+            aggregator.map(Arc::new),
             self.ingester_id,
             query_recorder.child_span("serialise response"),
             Arc::clone(&self.query_request_frame_encoding_duration),
@@ -311,6 +327,8 @@ fn encode_partition(
     // [`PartitionData`]: crate::buffer_tree::partition::PartitionData
     // [`PartitionResponse`]: crate::query::partition_response::PartitionResponse
     completed_persistence_count: u64,
+    // True if the data of the partition was aggregated by the ingester.
+    aggregated: bool,
     ingester_id: IngesterId,
 ) -> Result<FlightData, FlightError> {
     use proto::ingester_query_response_metadata::PartitionIdentifier;
@@ -329,6 +347,7 @@ fn encode_partition(
         partition_identifier: Some(partition_identifier),
         ingester_uuid: ingester_id.to_string(),
         completed_persistence_count,
+        aggregated,
     };
     prost::Message::encode(&app_metadata, &mut bytes)
         .map_err(|e| FlightError::from_external_error(Box::new(e)))?;
@@ -353,8 +372,13 @@ fn build_none_flight_msg() -> Vec<u8> {
 }
 
 /// Converts a QueryResponse into a stream of Arrow Flight [`FlightData`] response frames.
+///
+/// If an `aggregator` is provided, the data of each partition is aggregated
+/// before it is encoded, where possible. An aggregate that is invalid for the
+/// data of a partition ends the stream with an `InvalidArgument` error.
 fn encode_response(
     response: QueryResponse,
+    aggregator: Option<Arc<PartitionAggregator>>,
     ingester_id: IngesterId,
     span: Option<Span>,
     frame_encoding_duration_metric: Arc<DurationHistogram>,
@@ -365,9 +389,29 @@ fn encode_response(
         let partition_id = partition.id().clone();
         let completed_persistence_count = partition.completed_persistence_count();
 
+        let batches = partition.into_record_batches();
+        let (batches, aggregated, error) = match aggregator.as_ref().map(|a| a.aggregate(&batches))
+        {
+            Some(Ok(Some(v))) => (vec![v], true, None),
+            Some(Err(e)) => (vec![], false, Some(e)),
+            Some(Ok(None)) | None => (batches, false, None),
+        };
+
         // prefix payload data w/ metadata for that particular partition
         let head = futures::stream::once(async move {
-            encode_partition(partition_id, completed_persistence_count, ingester_id)
+            if let Some(e) = error {
+                debug!(error=%e, "invalid query aggregate");
+                return Err(FlightError::Tonic(tonic::Status::invalid_argument(
+                    e.to_string(),
+                )));
+            }
+
+            encode_partition(
+                partition_id,
+                completed_persistence_count,
+                aggregated,
+                ingester_id,
+            )
         });
 
         // An output vector of FlightDataEncoder streams, each entry stream with
@@ -377,7 +421,7 @@ fn encode_response(
         // schema across all batches (1 stream).
         let mut output = Vec::with_capacity(1);
 
-        let mut batch_iter = partition.into_record_batches().into_iter().peekable();
+        let mut batch_iter = batches.into_iter().peekable();
 
         // While there are more batches to process.
         while let Some(schema) = batch_iter.peek().map(|v| v.schema()) {
@@ -410,10 +454,13 @@ mod tests {
     };
     use arrow::array::{Float64Array, Int32Array};
     use arrow_flight::decode::{DecodedPayload, FlightRecordBatchStream};
+    use arrow_util::assert_batches_eq;
     use assert_matches::assert_matches;
     use bytes::Bytes;
     use data_types::PartitionId;
+    use mutable_batch_lp::test_helpers::lp_to_mutable_batch;
     use proto::ingester_query_response_metadata::PartitionIdentifier;
+    use schema::Projection;
     use tonic::Code;
     use trace::{ctx::SpanContext, RingBufferTraceCollector, TraceCollector};
 
@@ -457,6 +504,7 @@ mod tests {
             )),
             ingester_uuid: ingester_id.to_string(),
             completed_persistence_count: 42,
+            aggregated: false,
         };
         assert_eq!(md_actual, md_expected);
     }
@@ -498,10 +546,146 @@ mod tests {
             partition_identifier: Some(PartitionIdentifier::CatalogId(2)),
             ingester_uuid: ingester_id.to_string(),
             completed_persistence_count: 42,
+            aggregated: false,
         };
         assert_eq!(md_actual, md_expected);
     }
 
+    #[tokio::test]
+    async fn test_aggregate_pushdown() {
+        let ingester_id = IngesterId::new();
+        let batch = lp_to_mutable_batch(
+            "bananas,region=Asturias temp=35 1\nbananas,region=Asturias temp=37 2",
+        )
+        .1
+        .to_arrow(Projection::All)
+        .unwrap();
+
+        let flight = FlightService::new(
+            MockQueryExec::default().with_result(Ok(QueryResponse::new(PartitionStream::new(
+                futures::stream::iter([PartitionResponse::new(
+                    vec![batch],
+                    ARBITRARY_TRANSITION_PARTITION_ID.clone(),
+                    42,
+                )]),
+            )))),
+            ingester_id,
+            100,
+            &metric::Registry::default(),
+        );
+
+        let request = proto::IngesterQueryRequest {
+            aggregate: Some(proto::Aggregate {
+                function: proto::aggregate::Function::Max.into(),
+                group_by: vec!["region".to_string()],
+                window_nanos: 10,
+            }),
+            ..Default::default()
+        };
+        let req = tonic::Request::new(Ticket {
+            ticket: request.encode_to_vec().into(),
+        });
+        let response_stream = flight
+            .do_get(req)
+            .await
+            .unwrap()
+            .into_inner()
+            .map_err(FlightError::Tonic);
+        let flight_data = FlightRecordBatchStream::new_from_flight_data(response_stream)
+            .into_inner()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+
+        let md_actual =
+            proto::IngesterQueryResponseMetadata::decode(flight_data[0].app_metadata()).unwrap();
+        assert!(md_actual.aggregated);
+
+        let batches = flight_data
+            .into_iter()
+            .filter_map(|v| match v.payload {
+                DecodedPayload::RecordBatch(b) => Some(b),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_batches_eq!(
+            [
+                "+----------+------+----------------------+",
+                "| region   | temp | time                 |",
+                "+----------+------+----------------------+",
+                "| Asturias | 37.0 | 1970-01-01T00:00:00Z |",
+                "+----------+------+----------------------+",
+            ],
+            &batches
+        );
+    }
+
+    #[tokio::test]
+    async fn test_aggregate_group_by_field() {
+        let batch = lp_to_mutable_batch("bananas,region=Asturias temp=35 1")
+            .1
+            .to_arrow(Projection::All)
+            .unwrap();
+
+        let flight = FlightService::new(
+            MockQueryExec::default().with_result(Ok(QueryResponse::new(PartitionStream::new(
+                futures::stream::iter([PartitionResponse::new(
+                    vec![batch],
+                    ARBITRARY_TRANSITION_PARTITION_ID.clone(),
+                    42,
+                )]),
+            )))),
+            IngesterId::new(),
+            100,
+            &metric::Registry::default(),
+        );
+
+        let request = proto::IngesterQueryRequest {
+            aggregate: Some(proto::Aggregate {
+                function: proto::aggregate::Function::Max.into(),
+                group_by: vec!["temp".to_string()],
+                window_nanos: 10,
+            }),
+            ..Default::default()
+        };
+        let req = tonic::Request::new(Ticket {
+            ticket: request.encode_to_vec().into(),
+        });
+        let err = flight
+            .do_get(req)
+            .await
+            .unwrap()
+            .into_inner()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn test_aggregate_invalid() {
+        let flight = FlightService::new(
+            MockQueryExec::default(),
+            IngesterId::new(),
+            100,
+            &metric::Registry::default(),
+        );
+
+        let request = proto::IngesterQueryRequest {
+            aggregate: Some(proto::Aggregate {
+                function: proto::aggregate::Function::Unspecified.into(),
+                group_by: vec![],
+                window_nanos: 10,
+            }),
+            ..Default::default()
+        };
+        let req = tonic::Request::new(Ticket {
+            ticket: request.encode_to_vec().into(),
+        });
+        let err = flight.do_get(req).await.map(|_| ()).unwrap_err();
+        assert_eq!(err.code(), Code::InvalidArgument);
+    }
+
     #[tokio::test]
     async fn limits_concurrent_queries() {
         let mut flight = FlightService::new(
@@ -560,7 +744,13 @@ mod tests {
         let query_span = span_ctx.child("query span");
 
         // test with encode_response
-        let call_chain = encode_response(query_response, ingester_id, Some(query_span), histogram);
+        let call_chain = encode_response(
+            query_response,
+            None,
+            ingester_id,
+            Some(query_span),
+            histogram,
+        );
         call_chain.collect::<Vec<_>>().await;
 
         let spans = trace_collector.spans();
@@ -636,6 +826,7 @@ mod tests {
             )),
             ingester_uuid: ingester_id.to_string(),
             completed_persistence_count: 42,
+            aggregated: false,
         };
         assert_eq!(md_actual, md_expected);
 
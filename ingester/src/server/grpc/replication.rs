@@ -0,0 +1,91 @@
+use std::sync::Arc;
+
+use data_types::sequence_number_set::SequenceNumberSet;
+use generated_types::influxdata::iox::ingester::v1::{
+    self as proto, replication_service_server::ReplicationService,
+};
+use tonic::{Request, Response};
+use wal::SequencedWalOp;
+
+use crate::wal::replication::{ReplicaError, ReplicaStore};
+
+/// Serves the follower side of WAL replication, writing the ops replicated by
+/// the configured leader ingesters to their replica WALs.
+///
+/// All requests are rejected if this ingester is not configured as a
+/// replication follower.
+#[derive(Debug)]
+pub(crate) struct ReplicationHandler {
+    store: Option<Arc<ReplicaStore>>,
+}
+
+impl ReplicationHandler {
+    pub(crate) fn new(store: Option<Arc<ReplicaStore>>) -> Self {
+        Self { store }
+    }
+
+    fn store(&self) -> Result<&ReplicaStore, tonic::Status> {
+        self.store.as_deref().ok_or_else(|| {
+            tonic::Status::unimplemented("wal replication is not enabled on this ingester")
+        })
+    }
+}
+
+impl From<ReplicaError> for tonic::Status {
+    fn from(e: ReplicaError) -> Self {
+        match e {
+            ReplicaError::InvalidLeaderId(_) | ReplicaError::UnsupportedOp(_) => {
+                Self::invalid_argument(e.to_string())
+            }
+            ReplicaError::UnknownLeader(_) => Self::permission_denied(e.to_string()),
+            // A leader treats this code as being fenced.
+            ReplicaError::Fenced(_) => Self::failed_precondition(e.to_string()),
+            ReplicaError::Io(_) | ReplicaError::Wal(_) | ReplicaError::Write(_) => {
+                Self::internal(e.to_string())
+            }
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl ReplicationService for ReplicationHandler {
+    /// Write the replicated ops to the replica WAL of the leader, returning
+    /// once they are durable.
+    async fn replicate(
+        &self,
+        request: Request<proto::ReplicateRequest>,
+    ) -> Result<Response<proto::ReplicateResponse>, tonic::Status> {
+        let store = self.store()?;
+        let request = request.into_inner();
+
+        let ops = request
+            .ops
+            .into_iter()
+            .map(SequencedWalOp::try_from)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| tonic::Status::invalid_argument(e.to_string()))?;
+
+        store.replicate(&request.leader_id, ops).await?;
+
+        Ok(Response::new(proto::ReplicateResponse {}))
+    }
+
+    /// Remove the ops persisted by the leader from its replica WAL.
+    async fn release(
+        &self,
+        request: Request<proto::ReleaseRequest>,
+    ) -> Result<Response<proto::ReleaseResponse>, tonic::Status> {
+        let store = self.store()?;
+        let request = request.into_inner();
+
+        if request.all {
+            store.release_all(&request.leader_id).await?;
+        } else {
+            let persisted = SequenceNumberSet::try_from(&*request.persisted_sequence_numbers)
+                .map_err(tonic::Status::invalid_argument)?;
+            store.release(&request.leader_id, persisted).await?;
+        }
+
+        Ok(Response::new(proto::ReleaseResponse {}))
+    }
+}
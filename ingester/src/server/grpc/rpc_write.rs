@@ -54,7 +54,10 @@ impl From<RpcError> for tonic::Status {
             RpcError::Decode(_) | RpcError::NoPayload | RpcError::NoTables => Code::InvalidArgument,
             RpcError::SystemState(IngestStateError::PersistSaturated) => Code::ResourceExhausted,
             RpcError::SystemState(IngestStateError::DiskFull) => Code::ResourceExhausted,
+            RpcError::SystemState(IngestStateError::MemoryLimit) => Code::ResourceExhausted,
             RpcError::SystemState(IngestStateError::GracefulStop) => Code::FailedPrecondition,
+            RpcError::SystemState(IngestStateError::WalSyncFailed) => Code::Internal,
+            RpcError::SystemState(IngestStateError::ReplicationFenced) => Code::FailedPrecondition,
         };
 
         Self::new(code, e.to_string())
@@ -0,0 +1,467 @@
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use backoff::Backoff;
+use bytes::Bytes;
+use futures::TryStreamExt;
+use iox_time::{SystemProvider, TimeProvider};
+use metric::U64Counter;
+use object_store::{path::Path, DynObjectStore};
+use observability_deps::tracing::*;
+use thiserror::Error;
+use tokio::sync::{mpsc, watch};
+use wal::SegmentId;
+
+use super::reference_tracker::WalFileDeleter;
+
+/// How often archived segments older than the retention period are removed
+/// from object storage.
+const EXPIRE_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+/// Errors archiving a single WAL segment file.
+#[derive(Debug, Error)]
+pub(crate) enum ArchiveError {
+    /// The segment file could not be read, and is left on disk.
+    #[error("failed to read wal segment for archival: {0}")]
+    Read(wal::Error),
+
+    /// The segment file was archived, but could not be removed from disk.
+    #[error("failed to delete archived wal segment: {0}")]
+    Delete(wal::Error),
+}
+
+/// A [`WalFileDeleter`] that queues each WAL segment file for upload to object
+/// storage by a [`WalArchiveTask`], which deletes it from the local disk once
+/// uploaded.
+///
+/// Queueing never blocks, so an unavailable object store holds back only the
+/// deletion of segment files, and not the [`WalReferenceActor`] that releases
+/// them.
+///
+/// Segments still queued when the ingester stops are left on disk, and so are
+/// replayed at startup; a graceful shutdown waits for the queue to drain (see
+/// [`WalArchiveDrain`]).
+///
+/// [`WalReferenceActor`]: super::reference_tracker::WalReferenceActor
+#[derive(Debug)]
+pub(crate) struct WalArchiver {
+    tx: mpsc::UnboundedSender<SegmentId>,
+
+    /// The number of queued segments not yet archived.
+    pending: Arc<watch::Sender<usize>>,
+}
+
+impl WalArchiver {
+    /// Initialise a [`WalArchiver`] uploading the segment files of `wal` to
+    /// `store` under `prefix`, and the [`WalArchiveTask`] performing the
+    /// uploads, which the caller must spawn.
+    pub(crate) fn new(
+        wal: Arc<wal::Wal>,
+        store: Arc<DynObjectStore>,
+        prefix: Path,
+        retention: Option<Duration>,
+        metrics: &metric::Registry,
+    ) -> (Self, WalArchiveTask) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let pending = Arc::new(watch::Sender::new(0));
+        let task = WalArchiveTask::new(
+            wal,
+            store,
+            prefix,
+            retention,
+            rx,
+            Arc::clone(&pending),
+            metrics,
+        );
+        (Self { tx, pending }, task)
+    }
+
+    /// Return a [`WalArchiveDrain`] to wait for the segments queued by this
+    /// [`WalArchiver`] to be archived.
+    pub(crate) fn drain(&self) -> WalArchiveDrain {
+        WalArchiveDrain(self.pending.subscribe())
+    }
+}
+
+#[async_trait]
+impl WalFileDeleter for WalArchiver {
+    async fn delete_file(&self, id: SegmentId) {
+        self.pending.send_modify(|n| *n += 1);
+        if self.tx.send(id).is_err() {
+            warn!(%id, "wal archive task stopped, leaving segment on disk");
+            self.pending.send_modify(|n| *n -= 1);
+        }
+    }
+}
+
+/// A handle to wait for a [`WalArchiveTask`] to archive all the segments
+/// queued so far.
+#[derive(Debug, Clone)]
+pub(crate) struct WalArchiveDrain(watch::Receiver<usize>);
+
+impl WalArchiveDrain {
+    /// Wait until no segments are queued for archival, or the archiver is
+    /// dropped.
+    pub(crate) async fn wait(mut self) {
+        let _ = self.0.wait_for(|pending| *pending == 0).await;
+    }
+}
+
+/// Uploads the WAL segment files queued by a [`WalArchiver`] to object
+/// storage, deleting each from the local disk only once it is uploaded.
+///
+/// Segments are archived under `prefix`, named by
+/// [`wal::archived_segment_name()`] so they can be listed in the order they
+/// were archived in. Uploads are retried until they succeed, holding back the
+/// archival of any following segments while object storage is unavailable.
+///
+/// When a retention period is configured, archived segments older than it are
+/// periodically removed from object storage.
+#[derive(Debug)]
+pub(crate) struct WalArchiveTask<P = SystemProvider> {
+    wal: Arc<wal::Wal>,
+    store: Arc<DynObjectStore>,
+    prefix: Path,
+    retention: Option<Duration>,
+    time_provider: P,
+    rx: mpsc::UnboundedReceiver<SegmentId>,
+    pending: Arc<watch::Sender<usize>>,
+
+    archived_files: U64Counter,
+    archived_bytes: U64Counter,
+    archive_errors: U64Counter,
+    expired_files: U64Counter,
+}
+
+impl WalArchiveTask {
+    fn new(
+        wal: Arc<wal::Wal>,
+        store: Arc<DynObjectStore>,
+        prefix: Path,
+        retention: Option<Duration>,
+        rx: mpsc::UnboundedReceiver<SegmentId>,
+        pending: Arc<watch::Sender<usize>>,
+        metrics: &metric::Registry,
+    ) -> Self {
+        let archived_files = metrics
+            .register_metric::<U64Counter>(
+                "ingester_wal_archived_segments",
+                "number of wal segment files uploaded to object storage",
+            )
+            .recorder(&[]);
+        let archived_bytes = metrics
+            .register_metric::<U64Counter>(
+                "ingester_wal_archived_bytes",
+                "number of bytes of wal segment files uploaded to object storage",
+            )
+            .recorder(&[]);
+        let archive_errors = metrics
+            .register_metric::<U64Counter>(
+                "ingester_wal_archive_errors",
+                "number of wal segment files that could not be read for archival, or \
+                deleted once archived",
+            )
+            .recorder(&[]);
+        let expired_files = metrics
+            .register_metric::<U64Counter>(
+                "ingester_wal_archive_expired_segments",
+                "number of archived wal segment files removed from object storage once \
+                older than the retention period",
+            )
+            .recorder(&[]);
+
+        Self {
+            wal,
+            store,
+            prefix,
+            retention,
+            time_provider: Default::default(),
+            rx,
+            pending,
+            archived_files,
+            archived_bytes,
+            archive_errors,
+            expired_files,
+        }
+    }
+}
+
+impl<P> WalArchiveTask<P> {
+    #[cfg(test)]
+    fn with_time_provider<U>(self, time_provider: U) -> WalArchiveTask<U>
+    where
+        U: TimeProvider,
+    {
+        WalArchiveTask {
+            wal: self.wal,
+            store: self.store,
+            prefix: self.prefix,
+            retention: self.retention,
+            time_provider,
+            rx: self.rx,
+            pending: self.pending,
+            archived_files: self.archived_files,
+            archived_bytes: self.archived_bytes,
+            archive_errors: self.archive_errors,
+            expired_files: self.expired_files,
+        }
+    }
+}
+
+impl<P> WalArchiveTask<P>
+where
+    P: TimeProvider,
+{
+    /// Archive queued segments until the [`WalArchiver`] is dropped, and
+    /// expire archived segments every [`EXPIRE_INTERVAL`] if a retention
+    /// period is configured.
+    pub(crate) async fn run(mut self) {
+        let mut expire_interval = tokio::time::interval(EXPIRE_INTERVAL);
+
+        loop {
+            tokio::select! {
+                id = self.rx.recv() => {
+                    let Some(id) = id else {
+                        debug!("stopping wal archive task");
+                        return;
+                    };
+                    if let Err(error) = self.archive(id).await {
+                        error!(%error, %id, "failed to archive wal segment");
+                        self.archive_errors.inc(1);
+                    }
+                    self.pending.send_modify(|n| *n -= 1);
+                }
+                _ = expire_interval.tick(), if self.retention.is_some() => {
+                    self.expire().await;
+                }
+            }
+        }
+    }
+
+    /// Upload the segment file with the specified [`SegmentId`] to object
+    /// storage, retrying until it succeeds, and then delete it from disk.
+    async fn archive(&self, id: SegmentId) -> Result<(), ArchiveError> {
+        let data = Bytes::from(self.wal.read_segment(id).map_err(ArchiveError::Read)?);
+        let size = data.len();
+
+        let location = self.prefix.child(wal::archived_segment_name(
+            self.time_provider.now().timestamp_nanos(),
+            id,
+        ));
+
+        Backoff::new(&Default::default())
+            .retry_all_errors("archive wal segment", || {
+                let data = data.clone();
+                let location = &location;
+                async move { self.store.put(location, data).await }
+            })
+            .await
+            .expect("retry forever");
+
+        self.archived_files.inc(1);
+        self.archived_bytes.inc(size as u64);
+
+        info!(%id, %location, size, "archived wal segment");
+
+        self.wal.delete(id).await.map_err(ArchiveError::Delete)
+    }
+
+    /// Remove the archived segments older than the retention period from
+    /// object storage.
+    ///
+    /// Failures are logged and the remaining segments left in place, to be
+    /// retried on the next expiry.
+    async fn expire(&self) {
+        let Some(retention) = self.retention else {
+            return;
+        };
+        let Some(cutoff) = self.time_provider.now().checked_sub(retention) else {
+            return;
+        };
+        let cutoff = cutoff.timestamp_nanos();
+
+        let listed = match self.store.list(Some(&self.prefix)).await {
+            Ok(v) => v.try_collect::<Vec<_>>().await,
+            Err(e) => Err(e),
+        };
+        let listed = match listed {
+            Ok(v) => v,
+            Err(error) => {
+                warn!(%error, prefix = %self.prefix, "failed to list archived wal segments");
+                return;
+            }
+        };
+
+        for meta in listed {
+            let archived_at = meta
+                .location
+                .filename()
+                .and_then(wal::parse_archived_segment_name)
+                .map(|(archived_at, _id)| archived_at);
+
+            // Leave any objects not written by the archiver alone.
+            if !matches!(archived_at, Some(t) if t < cutoff) {
+                continue;
+            }
+
+            match self.store.delete(&meta.location).await {
+                Ok(()) => {
+                    self.expired_files.inc(1);
+                    info!(location = %meta.location, "removed expired archived wal segment");
+                }
+                Err(error) => {
+                    warn!(
+                        %error,
+                        location = %meta.location,
+                        "failed to remove expired archived wal segment"
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use iox_time::{MockProvider, Time};
+    use metric::{Attributes, Metric};
+    use object_store::memory::InMemory;
+    use test_helpers::timeout::FutureTimeout;
+
+    use super::*;
+
+    /// Return the names of the objects in `store`, in name order.
+    async fn list(store: &DynObjectStore) -> Vec<String> {
+        let mut names = store
+            .list(None)
+            .await
+            .expect("listing object storage failed")
+            .map_ok(|meta| meta.location.to_string())
+            .try_collect::<Vec<_>>()
+            .await
+            .expect("failed to list object store files");
+        names.sort();
+        names
+    }
+
+    #[tokio::test]
+    async fn test_archive_and_expire() {
+        let dir = tempfile::tempdir().unwrap();
+        let wal = wal::Wal::new(dir.path())
+            .await
+            .expect("failed to initialise WAL");
+
+        let (first, _) = wal.rotate().expect("failed to rotate WAL");
+        let (second, _) = wal.rotate().expect("failed to rotate WAL");
+        let first_data = wal.read_segment(first.id()).unwrap();
+
+        let store: Arc<DynObjectStore> = Arc::new(InMemory::new());
+        let time_provider = Arc::new(MockProvider::new(Time::from_timestamp_nanos(1_000)));
+        let metrics = metric::Registry::default();
+
+        let (_archiver, task) = WalArchiver::new(
+            Arc::clone(&wal),
+            Arc::clone(&store),
+            Path::from("wal_archive"),
+            Some(Duration::from_secs(60 * 60)),
+            &metrics,
+        );
+        let task = task.with_time_provider(Arc::clone(&time_provider));
+
+        // Archiving the first segment uploads it, and deletes it locally.
+        task.archive(first.id()).await.unwrap();
+
+        let first_name = format!(
+            "wal_archive/{}",
+            wal::archived_segment_name(1_000, first.id())
+        );
+        assert_eq!(list(&*store).await, [first_name.clone()]);
+        let got = store
+            .get(&Path::from(first_name))
+            .await
+            .unwrap()
+            .bytes()
+            .await
+            .unwrap();
+        assert_eq!(&*got, &*first_data);
+        assert!(!wal.closed_segments().iter().any(|s| s.id() == first.id()));
+
+        // A segment that cannot be read is reported, rather than archived.
+        assert_matches!(task.archive(first.id()).await, Err(ArchiveError::Read(_)));
+
+        // Expiring before the retention period elapses retains the first
+        // segment.
+        task.expire().await;
+        assert_eq!(list(&*store).await.len(), 1);
+
+        // Expiring after the retention period removes only the segments
+        // archived before it.
+        let now = time_provider.inc(Duration::from_secs(2 * 60 * 60));
+        task.archive(second.id()).await.unwrap();
+        task.expire().await;
+
+        assert_eq!(
+            list(&*store).await,
+            [format!(
+                "wal_archive/{}",
+                wal::archived_segment_name(now.timestamp_nanos(), second.id())
+            )]
+        );
+
+        let get = |name: &'static str| {
+            metrics
+                .get_instrument::<Metric<U64Counter>>(name)
+                .expect("metric not found")
+                .get_observer(&Attributes::from([]))
+                .expect("attributes not found")
+                .fetch()
+        };
+        assert_eq!(get("ingester_wal_archived_segments"), 2);
+        assert_eq!(get("ingester_wal_archive_expired_segments"), 1);
+        assert_eq!(
+            get("ingester_wal_archived_bytes"),
+            first_data.len() as u64 + second.size()
+        );
+    }
+
+    /// Segments passed to the [`WalArchiver`] are archived by the task in the
+    /// background, observable through a [`WalArchiveDrain`], and the task
+    /// stops once the archiver is dropped.
+    #[tokio::test]
+    async fn test_delete_file_queues_archival() {
+        let dir = tempfile::tempdir().unwrap();
+        let wal = wal::Wal::new(dir.path())
+            .await
+            .expect("failed to initialise WAL");
+        let (segment, _) = wal.rotate().expect("failed to rotate WAL");
+
+        let store: Arc<DynObjectStore> = Arc::new(InMemory::new());
+        let metrics = metric::Registry::default();
+
+        let (archiver, task) = WalArchiver::new(
+            Arc::clone(&wal),
+            Arc::clone(&store),
+            Path::from("wal_archive"),
+            None,
+            &metrics,
+        );
+
+        let task = tokio::spawn(task.run());
+
+        archiver.delete_file(segment.id()).await;
+        archiver
+            .drain()
+            .wait()
+            .with_timeout_panic(Duration::from_secs(5))
+            .await;
+
+        assert_eq!(list(&*store).await.len(), 1);
+        assert!(!wal.closed_segments().iter().any(|s| s.id() == segment.id()));
+
+        drop(archiver);
+        task.with_timeout_panic(Duration::from_secs(5))
+            .await
+            .unwrap();
+    }
+}
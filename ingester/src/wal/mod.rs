@@ -4,7 +4,10 @@
 //! [`DmlSink`]: crate::dml_sink::DmlSink
 //! [`IngestOp`]: crate::dml_payload::IngestOp
 
+pub(crate) mod archive;
 pub(crate) mod reference_tracker;
+pub(crate) mod replication;
 pub(crate) mod rotate_task;
+pub(crate) mod sync_failure;
 mod traits;
 pub(crate) mod wal_sink;
@@ -18,3 +18,10 @@ impl WalFileDeleter for Arc<wal::Wal> {
         self.delete(id).await.expect("failed to drop wal segment");
     }
 }
+
+#[async_trait]
+impl WalFileDeleter for Box<dyn WalFileDeleter> {
+    async fn delete_file(&self, id: SegmentId) {
+        (**self).delete_file(id).await
+    }
+}
@@ -0,0 +1,816 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use data_types::{sequence_number_set::SequenceNumberSet, NamespaceId, PartitionKey, TableId};
+use generated_types::influxdata::iox::wal::v1::sequenced_wal_op::Op;
+use iox_time::{SystemProvider, Time, TimeProvider};
+use metric::U64Counter;
+use mutable_batch_pb::decode::decode_database_batch;
+use observability_deps::tracing::*;
+use parking_lot::Mutex;
+use thiserror::Error;
+use tokio::sync::RwLock;
+use wal::{SegmentId, SequencedWalOp, Wal, WalConfig, WriteResult};
+
+use crate::{
+    dml_payload::write::{PartitionedData, TableData, WriteOperation},
+    dml_payload::IngestOp,
+    dml_sink::DmlSink,
+    ingest_state::IngestState,
+    timestamp_oracle::TimestampOracle,
+};
+
+use super::{segment_sequence_numbers, HEARTBEAT_INTERVAL};
+
+/// The directory within the WAL directory of a follower that replica WALs are
+/// kept in, with one subdirectory per leader.
+pub(crate) const REPLICA_DIRECTORY: &str = "replicas";
+
+/// Errors returned by a [`ReplicaStore`].
+#[derive(Debug, Error)]
+pub(crate) enum ReplicaError {
+    /// The leader identifier cannot be used as a directory name.
+    #[error("invalid replication leader id: {0:?}")]
+    InvalidLeaderId(String),
+
+    /// The leader is not one this ingester is configured to follow.
+    #[error("unknown replication leader id: {0:?}")]
+    UnknownLeader(String),
+
+    /// The replica of the leader has been taken over, and the leader must
+    /// restart and replay its own WAL before replicating again.
+    #[error("replication leader {0:?} is fenced after its replica was taken over")]
+    Fenced(String),
+
+    /// The leader replicated an op other than a write.
+    #[error("unsupported replicated wal op: {0}")]
+    UnsupportedOp(&'static str),
+
+    /// An error reading the replica directory.
+    #[error("failed to read replica directory: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// An error opening, rotating or deleting from a replica WAL.
+    #[error("replica wal error: {0}")]
+    Wal(#[from] wal::Error),
+
+    /// The replicated ops could not be made durable.
+    #[error("failed to write replicated ops: {0}")]
+    Write(String),
+}
+
+/// The replica WAL of a single leader.
+#[derive(Debug)]
+struct Replica {
+    wal: Arc<Wal>,
+
+    /// The closed segments of `wal`, with the sequence numbers they contain
+    /// that are yet to be released by the leader.
+    segments: Mutex<Vec<(SegmentId, SequenceNumberSet)>>,
+
+    /// When the leader was last heard from, or [`None`] once the replica has
+    /// been taken over.
+    ///
+    /// A taken over replica fences the leader, rejecting all its requests
+    /// until it releases all its ops after restarting.
+    last_seen: Mutex<Option<Time>>,
+
+    /// Held shared by requests from the leader and exclusively by a takeover,
+    /// ensuring a takeover observes every op acknowledged to the leader.
+    gate: RwLock<()>,
+}
+
+impl Replica {
+    /// Record contact from the leader at `now`, returning
+    /// [`ReplicaError::Fenced`] if the replica has been taken over.
+    fn touch(&self, leader_id: &str, now: Time) -> Result<(), ReplicaError> {
+        let mut last_seen = self.last_seen.lock();
+        if last_seen.is_none() {
+            return Err(ReplicaError::Fenced(leader_id.to_string()));
+        }
+        *last_seen = Some(now);
+        Ok(())
+    }
+
+    fn is_expired(&self, now: Time, timeout: Duration) -> bool {
+        self.last_seen
+            .lock()
+            .and_then(|t| now.checked_duration_since(t))
+            .map_or(false, |d| d > timeout)
+    }
+
+    /// Close the open segment of the replica WAL, tracking the sequence
+    /// numbers it contains.
+    fn rotate(&self) -> Result<(), ReplicaError> {
+        let (closed, set) = self.wal.rotate()?;
+        self.segments.lock().push((closed.id(), set));
+        Ok(())
+    }
+}
+
+/// The follower side of WAL replication, maintaining a replica WAL for each
+/// configured leader that replicates to this ingester.
+///
+/// Replicated ops are written to the replica WAL of the leader under
+/// [`REPLICA_DIRECTORY`] and removed once the leader releases them after
+/// persisting them. A leader that is not heard from for longer than the
+/// takeover timeout is assumed lost, and the ops it has not released are
+/// re-ingested by this ingester (see [`periodic_takeover()`]), fencing the
+/// leader first so that it cannot acknowledge further writes.
+#[derive(Debug)]
+pub(crate) struct ReplicaStore<P = SystemProvider> {
+    directory: PathBuf,
+    wal_config: WalConfig,
+    leader_ids: HashSet<String>,
+    replicas: tokio::sync::Mutex<HashMap<String, Arc<Replica>>>,
+    takeover_timeout: Duration,
+    time_provider: P,
+
+    replicated_ops: U64Counter,
+    takeovers: U64Counter,
+    takeover_ops: U64Counter,
+    takeover_skipped_ops: U64Counter,
+}
+
+impl ReplicaStore {
+    /// Initialise a [`ReplicaStore`] keeping replica WALs for the leaders in
+    /// `leader_ids` within `wal_directory`, written as specified by
+    /// `wal_config`, and taking over the replica of any leader not heard from
+    /// for `takeover_timeout`.
+    pub(crate) fn new(
+        wal_directory: &Path,
+        wal_config: WalConfig,
+        leader_ids: impl IntoIterator<Item = String>,
+        takeover_timeout: Duration,
+        metrics: &metric::Registry,
+    ) -> Self {
+        let replicated_ops = metrics
+            .register_metric::<U64Counter>(
+                "ingester_wal_replicated_ops",
+                "number of wal ops written to replica wals on behalf of a replication leader",
+            )
+            .recorder(&[]);
+        let takeovers = metrics
+            .register_metric::<U64Counter>(
+                "ingester_wal_replica_takeovers",
+                "number of times the replica wal of a timed out replication leader was replayed",
+            )
+            .recorder(&[]);
+        let takeover_ops = metrics
+            .register_metric::<U64Counter>(
+                "ingester_wal_replica_takeover_ops",
+                "number of unpersisted ops of timed out replication leaders ingested",
+            )
+            .recorder(&[]);
+        let takeover_skipped_ops = metrics
+            .register_metric::<U64Counter>(
+                "ingester_wal_replica_takeover_skipped_ops",
+                "number of ops of timed out replication leaders that could not be ingested",
+            )
+            .recorder(&[]);
+
+        Self {
+            directory: wal_directory.join(REPLICA_DIRECTORY),
+            wal_config,
+            leader_ids: leader_ids.into_iter().collect(),
+            replicas: Default::default(),
+            takeover_timeout,
+            time_provider: Default::default(),
+            replicated_ops,
+            takeovers,
+            takeover_ops,
+            takeover_skipped_ops,
+        }
+    }
+}
+
+impl<P> ReplicaStore<P> {
+    #[cfg(test)]
+    fn with_time_provider<U>(self, time_provider: U) -> ReplicaStore<U>
+    where
+        U: TimeProvider,
+    {
+        ReplicaStore {
+            directory: self.directory,
+            wal_config: self.wal_config,
+            leader_ids: self.leader_ids,
+            replicas: self.replicas,
+            takeover_timeout: self.takeover_timeout,
+            time_provider,
+            replicated_ops: self.replicated_ops,
+            takeovers: self.takeovers,
+            takeover_ops: self.takeover_ops,
+            takeover_skipped_ops: self.takeover_skipped_ops,
+        }
+    }
+}
+
+impl<P> ReplicaStore<P>
+where
+    P: TimeProvider,
+{
+    /// Open the replica WALs left on disk by a previous run.
+    ///
+    /// Their leaders are given the takeover timeout from now to make contact.
+    /// The replicas of leaders that are no longer configured are left on disk
+    /// untouched.
+    pub(crate) async fn load(&self) -> Result<(), ReplicaError> {
+        if !self.directory.exists() {
+            return Ok(());
+        }
+
+        for entry in std::fs::read_dir(&self.directory)? {
+            let entry = entry?;
+            if !entry.file_type()?.is_dir() {
+                continue;
+            }
+            let leader_id = entry.file_name().to_string_lossy().into_owned();
+            if !self.leader_ids.contains(&leader_id) {
+                warn!(
+                    leader_id,
+                    "ignoring wal replica of unconfigured replication leader"
+                );
+                continue;
+            }
+            self.replica(&leader_id).await?;
+            info!(leader_id, "loaded wal replica");
+        }
+
+        Ok(())
+    }
+
+    /// Return the replica of `leader_id`, opening it if necessary.
+    async fn replica(&self, leader_id: &str) -> Result<Arc<Replica>, ReplicaError> {
+        if leader_id.is_empty()
+            || !leader_id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(ReplicaError::InvalidLeaderId(leader_id.to_string()));
+        }
+        if !self.leader_ids.contains(leader_id) {
+            return Err(ReplicaError::UnknownLeader(leader_id.to_string()));
+        }
+
+        let mut replicas = self.replicas.lock().await;
+        if let Some(v) = replicas.get(leader_id) {
+            return Ok(Arc::clone(v));
+        }
+
+        let wal = Wal::with_config(self.directory.join(leader_id), self.wal_config).await?;
+
+        // Index the segments left by a previous run, dropping those that hold
+        // no ops.
+        let mut segments = Vec::new();
+        for segment in wal.closed_segments() {
+            let set = segment_sequence_numbers(&wal, segment.id());
+            if set.is_empty() {
+                wal.delete(segment.id()).await?;
+            } else {
+                segments.push((segment.id(), set));
+            }
+        }
+
+        let replica = Arc::new(Replica {
+            wal,
+            segments: Mutex::new(segments),
+            last_seen: Mutex::new(Some(self.time_provider.now())),
+            gate: RwLock::new(()),
+        });
+        replicas.insert(leader_id.to_string(), Arc::clone(&replica));
+
+        Ok(replica)
+    }
+
+    /// Write `ops` to the replica WAL of `leader_id`, returning once they are
+    /// durable.
+    ///
+    /// An empty `ops` records a heartbeat from the leader. Only write ops are
+    /// accepted, as only they can be ingested should the replica be taken
+    /// over.
+    pub(crate) async fn replicate(
+        &self,
+        leader_id: &str,
+        ops: Vec<SequencedWalOp>,
+    ) -> Result<(), ReplicaError> {
+        if let Some(op) = ops.iter().find(|op| !matches!(op.op, Op::Write(_))) {
+            return Err(ReplicaError::UnsupportedOp(op_name(&op.op)));
+        }
+
+        let replica = self.replica(leader_id).await?;
+        let _gate = replica.gate.read().await;
+        replica.touch(leader_id, self.time_provider.now())?;
+
+        let n_ops = ops.len();
+        // Ops are flushed in order, so the last op being durable implies all
+        // are.
+        let last = ops.into_iter().map(|op| replica.wal.write_op(op)).last();
+        let Some(mut write_result) = last else {
+            return Ok(());
+        };
+
+        write_result
+            .changed()
+            .await
+            .expect("unable to get WAL write result");
+        if let Some(WriteResult::Err(e)) = &*write_result.borrow() {
+            return Err(ReplicaError::Write(e.clone()));
+        }
+
+        self.replicated_ops.inc(n_ops as u64);
+        Ok(())
+    }
+
+    /// Remove the ops with sequence numbers in `persisted` from the replica WAL
+    /// of `leader_id`.
+    pub(crate) async fn release(
+        &self,
+        leader_id: &str,
+        persisted: SequenceNumberSet,
+    ) -> Result<(), ReplicaError> {
+        self.release_with(leader_id, false, move |set| set.remove_set(&persisted))
+            .await
+    }
+
+    /// Remove all ops replicated so far from the replica WAL of `leader_id`.
+    ///
+    /// Called by a leader once it has replayed its own WAL at startup, this
+    /// lifts the fence of a taken over replica.
+    pub(crate) async fn release_all(&self, leader_id: &str) -> Result<(), ReplicaError> {
+        self.release_with(leader_id, true, |set| *set = SequenceNumberSet::default())
+            .await
+    }
+
+    async fn release_with<F>(
+        &self,
+        leader_id: &str,
+        unfence: bool,
+        f: F,
+    ) -> Result<(), ReplicaError>
+    where
+        F: Fn(&mut SequenceNumberSet) + Send,
+    {
+        if !self.leader_ids.contains(leader_id) {
+            return Err(ReplicaError::UnknownLeader(leader_id.to_string()));
+        }
+
+        // There is nothing to release for a leader without a replica.
+        let Some(replica) = self.replicas.lock().await.get(leader_id).cloned() else {
+            return Ok(());
+        };
+        let _gate = replica.gate.read().await;
+        if unfence {
+            *replica.last_seen.lock() = Some(self.time_provider.now());
+        } else {
+            replica.touch(leader_id, self.time_provider.now())?;
+        }
+
+        // Close the open segment so the ops within it can be released too.
+        replica.rotate()?;
+
+        let released = {
+            let mut segments = replica.segments.lock();
+            segments.iter_mut().for_each(|(_, set)| f(set));
+            let (released, retained) = std::mem::take(&mut *segments)
+                .into_iter()
+                .partition::<Vec<_>, _>(|(_, set)| set.is_empty());
+            *segments = retained;
+            released
+        };
+
+        for (id, _) in released {
+            replica.wal.delete(id).await?;
+            debug!(leader_id, %id, "released replica wal segment");
+        }
+
+        Ok(())
+    }
+
+    /// Re-ingest the unreleased ops of every leader that has not been heard
+    /// from for longer than the takeover timeout, applying them to `sink` with
+    /// sequence numbers from `timestamp`.
+    pub(crate) async fn take_over_expired<T>(&self, sink: &T, timestamp: &TimestampOracle)
+    where
+        T: DmlSink,
+    {
+        let replicas = self
+            .replicas
+            .lock()
+            .await
+            .iter()
+            .map(|(id, replica)| (id.clone(), Arc::clone(replica)))
+            .collect::<Vec<_>>();
+
+        for (leader_id, replica) in replicas {
+            if !replica.is_expired(self.time_provider.now(), self.takeover_timeout) {
+                continue;
+            }
+
+            // Wait for in-flight requests from the leader, which may have
+            // refreshed its liveness, and block new ones until the leader is
+            // fenced.
+            let _gate = replica.gate.write().await;
+            if !replica.is_expired(self.time_provider.now(), self.takeover_timeout) {
+                continue;
+            }
+
+            if let Err(error) = replica.rotate() {
+                warn!(%error, leader_id, "failed to rotate replica wal for takeover");
+                continue;
+            }
+
+            // Fence the leader before replaying any of its writes: from here on
+            // every request from it is rejected, so it can no longer
+            // acknowledge writes that would not be ingested here.
+            *replica.last_seen.lock() = None;
+
+            warn!(
+                leader_id,
+                timeout = ?self.takeover_timeout,
+                "replication leader timed out, ingesting unpersisted writes from replica wal"
+            );
+            self.takeovers.inc(1);
+
+            let segments = std::mem::take(&mut *replica.segments.lock());
+            for (id, _) in segments {
+                self.replay_segment(&replica.wal, id, sink, timestamp).await;
+
+                if let Err(error) = replica.wal.delete(id).await {
+                    warn!(%error, leader_id, %id, "failed to delete replayed replica wal segment");
+                }
+            }
+
+            info!(leader_id, "replica wal takeover complete");
+        }
+    }
+
+    /// Apply the write ops in the segment `id` of `wal` to `sink` as new writes
+    /// sequenced by `timestamp`.
+    ///
+    /// Ops that cannot be read, decoded or applied are logged, counted and
+    /// skipped.
+    async fn replay_segment<T>(
+        &self,
+        wal: &Wal,
+        id: SegmentId,
+        sink: &T,
+        timestamp: &TimestampOracle,
+    ) where
+        T: DmlSink,
+    {
+        let reader = match wal.reader_for_segment(id) {
+            Ok(v) => v,
+            Err(error) => {
+                warn!(%error, %id, "failed to open replica wal segment");
+                return;
+            }
+        };
+
+        for batch in reader {
+            let ops = match batch {
+                Ok(v) => v,
+                Err(error) => {
+                    warn!(%error, %id, "failed to read replica wal segment entry");
+                    break;
+                }
+            };
+
+            for op in ops {
+                let op = match resequence(op, timestamp) {
+                    Ok(Some(v)) => v,
+                    Ok(None) => continue,
+                    Err(error) => {
+                        warn!(%error, %id, "skipping unreplayable replica wal op");
+                        self.takeover_skipped_ops.inc(1);
+                        continue;
+                    }
+                };
+
+                match sink.apply(op).await {
+                    Ok(()) => self.takeover_ops.inc(1),
+                    Err(error) => {
+                        warn!(%error, %id, "failed to apply replica wal op");
+                        self.takeover_skipped_ops.inc(1);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Errors converting a replicated op into an [`IngestOp`].
+#[derive(Debug, Error)]
+enum ResequenceError {
+    #[error("unsupported replicated wal op: {0}")]
+    UnsupportedOp(&'static str),
+
+    #[error("failed to decode replicated write: {0}")]
+    Decode(#[from] mutable_batch_pb::decode::Error),
+}
+
+/// Return a human readable name for the type of `op`.
+fn op_name(op: &Op) -> &'static str {
+    match op {
+        Op::Write(_) => "write",
+        Op::Delete(_) => "delete",
+        Op::Persist(_) => "persist",
+    }
+}
+
+/// Convert a replicated op into an [`IngestOp`], assigning each table write a
+/// new sequence number from `timestamp`.
+///
+/// Returns [`None`] for ops that contain no table data.
+fn resequence(
+    op: SequencedWalOp,
+    timestamp: &TimestampOracle,
+) -> Result<Option<IngestOp>, ResequenceError> {
+    let op = match op.op {
+        Op::Write(w) => w,
+        v => return Err(ResequenceError::UnsupportedOp(op_name(&v))),
+    };
+
+    let batches = decode_database_batch(&op)?;
+    if batches.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some(IngestOp::Write(WriteOperation::new(
+        NamespaceId::new(op.database_id),
+        batches
+            .into_iter()
+            .map(|(k, v)| {
+                let table_id = TableId::new(k);
+                (
+                    table_id,
+                    TableData::new(table_id, PartitionedData::new(timestamp.next(), v)),
+                )
+            })
+            .collect(),
+        PartitionKey::from(op.partition_key),
+        None,
+    ))))
+}
+
+/// Periodically take over the replica WALs of timed out leaders, applying their
+/// unpersisted writes to `sink`.
+///
+/// Takeovers are deferred while `ingest_state` rejects writes.
+pub(crate) async fn periodic_takeover<T, P>(
+    store: Arc<ReplicaStore<P>>,
+    sink: T,
+    timestamp: Arc<TimestampOracle>,
+    ingest_state: Arc<IngestState>,
+) where
+    T: DmlSink,
+    P: TimeProvider,
+{
+    let mut interval = tokio::time::interval(HEARTBEAT_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        if ingest_state.read().is_err() {
+            continue;
+        }
+
+        store.take_over_expired(&sink, &timestamp).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use data_types::SequenceNumber;
+    use generated_types::influxdata::iox::wal::v1::PersistOp;
+    use iox_time::MockProvider;
+    use metric::{Attributes, Metric};
+
+    use super::*;
+    use crate::{
+        dml_payload::encode::encode_write_op,
+        dml_sink::mock_sink::MockDmlSink,
+        test_util::{
+            make_write_op, ARBITRARY_NAMESPACE_ID, ARBITRARY_PARTITION_KEY, ARBITRARY_TABLE_ID,
+            ARBITRARY_TABLE_NAME,
+        },
+    };
+
+    const TIMEOUT: Duration = Duration::from_secs(60);
+
+    fn new_store(dir: &Path, metrics: &metric::Registry) -> ReplicaStore {
+        ReplicaStore::new(
+            dir,
+            WalConfig::default(),
+            ["leader".to_string()],
+            TIMEOUT,
+            metrics,
+        )
+    }
+
+    /// Build a replicated op writing a single row, sequenced as `seq`.
+    fn replicated_op(seq: u64) -> SequencedWalOp {
+        let op = make_write_op(
+            &ARBITRARY_PARTITION_KEY,
+            ARBITRARY_NAMESPACE_ID,
+            &ARBITRARY_TABLE_NAME,
+            ARBITRARY_TABLE_ID,
+            seq,
+            &format!(
+                r#"{},city=Madrid day="sun",temp=55 {seq}"#,
+                &*ARBITRARY_TABLE_NAME
+            ),
+            None,
+        );
+        SequencedWalOp {
+            table_write_sequence_numbers: [(ARBITRARY_TABLE_ID, seq)].into(),
+            op: Op::Write(encode_write_op(ARBITRARY_NAMESPACE_ID, &op)),
+        }
+    }
+
+    fn set(seq: &[u64]) -> SequenceNumberSet {
+        seq.iter().copied().map(SequenceNumber::new).collect()
+    }
+
+    async fn segment_count<P>(store: &ReplicaStore<P>, leader_id: &str) -> usize {
+        let replica = Arc::clone(store.replicas.lock().await.get(leader_id).unwrap());
+        let n = replica.wal.closed_segments().len();
+        n
+    }
+
+    #[tokio::test]
+    async fn test_replicate_and_release() {
+        let dir = tempfile::tempdir().unwrap();
+        let metrics = metric::Registry::default();
+        let store = new_store(dir.path(), &metrics);
+
+        assert_matches!(
+            store.replicate("../bananas", vec![replicated_op(1)]).await,
+            Err(ReplicaError::InvalidLeaderId(_))
+        );
+
+        // Only the configured leaders may replicate.
+        assert_matches!(
+            store.replicate("platanos", vec![replicated_op(1)]).await,
+            Err(ReplicaError::UnknownLeader(_))
+        );
+        assert_matches!(
+            store.release_all("platanos").await,
+            Err(ReplicaError::UnknownLeader(_))
+        );
+        assert!(store.replicas.lock().await.get("platanos").is_none());
+
+        // And only write ops are accepted.
+        let persist = SequencedWalOp {
+            table_write_sequence_numbers: Default::default(),
+            op: Op::Persist(PersistOp::default()),
+        };
+        assert_matches!(
+            store
+                .replicate("leader", vec![replicated_op(1), persist])
+                .await,
+            Err(ReplicaError::UnsupportedOp("persist"))
+        );
+
+        store
+            .replicate("leader", vec![replicated_op(1), replicated_op(2)])
+            .await
+            .unwrap();
+        store
+            .replicate("leader", vec![replicated_op(3)])
+            .await
+            .unwrap();
+
+        // Releasing some of the ops retains the segment holding the rest.
+        store.release("leader", set(&[1, 2])).await.unwrap();
+        assert_eq!(segment_count(&store, "leader").await, 1);
+
+        // The replica is loaded from disk by a new store.
+        let reloaded = new_store(dir.path(), &metrics);
+        reloaded.load().await.unwrap();
+        let replica = Arc::clone(reloaded.replicas.lock().await.get("leader").unwrap());
+        assert_eq!(replica.segments.lock().len(), 1);
+        assert_eq!(replica.segments.lock()[0].1, set(&[3]));
+        drop(replica);
+
+        // Releasing the remaining op removes the segment.
+        reloaded.release("leader", set(&[3])).await.unwrap();
+        assert_eq!(segment_count(&reloaded, "leader").await, 0);
+
+        // The replica of a leader that is no longer configured is not loaded.
+        let reconfigured = ReplicaStore::new(
+            dir.path(),
+            WalConfig::default(),
+            ["platanos".to_string()],
+            TIMEOUT,
+            &metrics,
+        );
+        reconfigured.load().await.unwrap();
+        assert!(reconfigured.replicas.lock().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_take_over_expired() {
+        let dir = tempfile::tempdir().unwrap();
+        let time_provider = Arc::new(MockProvider::new(Time::from_timestamp_nanos(0)));
+        let metrics = metric::Registry::default();
+        let store = new_store(dir.path(), &metrics).with_time_provider(Arc::clone(&time_provider));
+
+        store
+            .replicate("leader", vec![replicated_op(1), replicated_op(2)])
+            .await
+            .unwrap();
+        store.release("leader", set(&[1])).await.unwrap();
+
+        let sink = MockDmlSink::default().with_apply_return([Ok(())]);
+        let timestamp = TimestampOracle::new(41);
+
+        // Nothing is taken over before the timeout elapses.
+        time_provider.inc(TIMEOUT);
+        store.take_over_expired(&sink, &timestamp).await;
+        assert!(sink.get_calls().is_empty());
+
+        // A heartbeat resets the timeout.
+        store.replicate("leader", vec![]).await.unwrap();
+        time_provider.inc(TIMEOUT);
+        store.take_over_expired(&sink, &timestamp).await;
+        assert!(sink.get_calls().is_empty());
+
+        // Once expired, the unreleased op is ingested with a new sequence
+        // number and removed from the replica.
+        time_provider.inc(Duration::from_secs(1));
+        store.take_over_expired(&sink, &timestamp).await;
+        assert_matches!(sink.get_calls().as_slice(), [IngestOp::Write(w)] => {
+            let (_, data) = w.tables().next().unwrap();
+            assert_eq!(data.partitioned_data().sequence_number(), SequenceNumber::new(42));
+        });
+        assert_eq!(segment_count(&store, "leader").await, 0);
+
+        // The leader is fenced, and its requests rejected.
+        assert_matches!(
+            store.replicate("leader", vec![replicated_op(3)]).await,
+            Err(ReplicaError::Fenced(_))
+        );
+        assert_matches!(
+            store.release("leader", set(&[3])).await,
+            Err(ReplicaError::Fenced(_))
+        );
+
+        // A taken over replica is not taken over again.
+        time_provider.inc(TIMEOUT * 2);
+        store.take_over_expired(&sink, &timestamp).await;
+        assert_eq!(sink.get_calls().len(), 1);
+
+        // Until the restarted leader releases all its ops, lifting the fence.
+        store.release_all("leader").await.unwrap();
+        store
+            .replicate("leader", vec![replicated_op(3)])
+            .await
+            .unwrap();
+    }
+
+    /// Ops that cannot be ingested are skipped and counted during a takeover,
+    /// rather than aborting it.
+    #[tokio::test]
+    async fn test_take_over_skips_unsupported_ops() {
+        let dir = tempfile::tempdir().unwrap();
+        let time_provider = Arc::new(MockProvider::new(Time::from_timestamp_nanos(0)));
+        let metrics = metric::Registry::default();
+        let store = new_store(dir.path(), &metrics).with_time_provider(Arc::clone(&time_provider));
+
+        store.replicate("leader", vec![]).await.unwrap();
+
+        // Write a non-write op to the replica WAL directly, as a peer running
+        // an older version could have.
+        let replica = Arc::clone(store.replicas.lock().await.get("leader").unwrap());
+        let mut write_result = replica.wal.write_op(SequencedWalOp {
+            table_write_sequence_numbers: Default::default(),
+            op: Op::Persist(PersistOp::default()),
+        });
+        write_result.changed().await.unwrap();
+        drop(replica);
+        store
+            .replicate("leader", vec![replicated_op(1)])
+            .await
+            .unwrap();
+
+        let sink = MockDmlSink::default().with_apply_return([Ok(())]);
+        let timestamp = TimestampOracle::new(0);
+
+        time_provider.inc(TIMEOUT * 2);
+        store.take_over_expired(&sink, &timestamp).await;
+        assert_eq!(sink.get_calls().len(), 1);
+
+        let skipped = metrics
+            .get_instrument::<Metric<U64Counter>>("ingester_wal_replica_takeover_skipped_ops")
+            .unwrap()
+            .get_observer(&Attributes::from(&[]))
+            .unwrap()
+            .fetch();
+        assert_eq!(skipped, 1);
+    }
+}
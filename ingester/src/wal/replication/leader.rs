@@ -0,0 +1,311 @@
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use data_types::sequence_number_set::SequenceNumberSet;
+use generated_types::influxdata::iox::{
+    ingester::v1::{
+        replication_service_client::ReplicationServiceClient, ReleaseRequest, ReplicateRequest,
+    },
+    wal::v1::SequencedWalOp as ProtoSequencedWalOp,
+};
+use observability_deps::tracing::*;
+use tokio::sync::watch::{self, Receiver};
+use tonic::{
+    transport::{Channel, Endpoint},
+    Code,
+};
+use wal::{SegmentId, WriteResult};
+
+use crate::{
+    dml_payload::IngestOp,
+    ingest_state::{IngestState, IngestStateError},
+    wal::{reference_tracker::WalFileDeleter, traits::WalAppender, wal_sink::sequenced_wal_op},
+};
+
+use super::{segment_sequence_numbers, HEARTBEAT_INTERVAL};
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// The maximum duration of a single request to the follower.
+///
+/// A write waits for its replication request to complete, so this bounds the
+/// time a write is blocked by an unresponsive follower.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A client of the replication service of a follower ingester, identifying
+/// itself as the leader `leader_id`.
+///
+/// Should the follower report that it has fenced this leader after taking over
+/// its replica, [`IngestStateError::ReplicationFenced`] is set in
+/// `ingest_state`, rejecting all further writes until this ingester is
+/// restarted.
+#[derive(Debug, Clone)]
+pub(crate) struct FollowerClient {
+    leader_id: String,
+    client: ReplicationServiceClient<Channel>,
+    ingest_state: Arc<IngestState>,
+}
+
+impl FollowerClient {
+    /// Lazily connect to the follower at `addr`.
+    pub(crate) fn new(
+        leader_id: String,
+        addr: String,
+        ingest_state: Arc<IngestState>,
+    ) -> Result<Self, tonic::transport::Error> {
+        let channel = Endpoint::from_shared(addr)?
+            .connect_timeout(CONNECT_TIMEOUT)
+            .timeout(REQUEST_TIMEOUT)
+            .connect_lazy();
+
+        Ok(Self {
+            leader_id,
+            client: ReplicationServiceClient::new(channel),
+            ingest_state,
+        })
+    }
+
+    /// Stop accepting writes if `result` reports that the follower has fenced
+    /// this leader.
+    fn check_fenced<T>(&self, result: Result<T, tonic::Status>) -> Result<(), tonic::Status> {
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) if e.code() == Code::FailedPrecondition => {
+                if self.ingest_state.set(IngestStateError::ReplicationFenced) {
+                    error!(
+                        error = %e.message(),
+                        "fenced by wal replication follower - rejecting all writes until the \
+                        ingester is restarted"
+                    );
+                }
+                Err(e)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Write `ops` to the replica WAL of this leader, returning once they are
+    /// durable on the follower.
+    async fn replicate(&self, ops: Vec<ProtoSequencedWalOp>) -> Result<(), tonic::Status> {
+        let result = self
+            .client
+            .clone()
+            .replicate(ReplicateRequest {
+                leader_id: self.leader_id.clone(),
+                ops,
+            })
+            .await;
+        self.check_fenced(result)
+    }
+
+    /// Release the ops with sequence numbers in `persisted` from the replica
+    /// WAL of this leader.
+    async fn release(&self, persisted: SequenceNumberSet) -> Result<(), tonic::Status> {
+        let result = self
+            .client
+            .clone()
+            .release(ReleaseRequest {
+                leader_id: self.leader_id.clone(),
+                persisted_sequence_numbers: persisted.to_bytes(),
+                all: false,
+            })
+            .await;
+        self.check_fenced(result)
+    }
+
+    /// Release every op replicated so far from the replica WAL of this leader.
+    ///
+    /// Called once the leader has replayed and persisted the contents of its
+    /// own WAL at startup, lifting any fence set by the follower.
+    pub(crate) async fn release_all(&self) -> Result<(), tonic::Status> {
+        self.client
+            .clone()
+            .release(ReleaseRequest {
+                leader_id: self.leader_id.clone(),
+                persisted_sequence_numbers: Vec::new(),
+                all: true,
+            })
+            .await
+            .map(|_| ())
+    }
+}
+
+/// A [`WalAppender`] that commits each op to the local write-ahead log and,
+/// when a follower is configured, to the replica WAL on the follower.
+///
+/// The returned write result is only set once the op is durable in both, and
+/// is an error if either write fails.
+#[derive(Debug)]
+pub(crate) struct ReplicatingWal {
+    wal: Arc<wal::Wal>,
+    follower: Option<FollowerClient>,
+}
+
+impl ReplicatingWal {
+    pub(crate) fn new(wal: Arc<wal::Wal>, follower: Option<FollowerClient>) -> Self {
+        Self { wal, follower }
+    }
+}
+
+impl WalAppender for ReplicatingWal {
+    fn append(&self, op: &IngestOp) -> Receiver<Option<WriteResult>> {
+        let Some(follower) = self.follower.clone() else {
+            return self.wal.append(op);
+        };
+
+        let op = sequenced_wal_op(op);
+        let mut local = self.wal.write_op(op.clone());
+
+        let (tx, rx) = watch::channel(None);
+        tokio::spawn(async move {
+            let (local_changed, remote) =
+                tokio::join!(local.changed(), follower.replicate(vec![op.into()]));
+            local_changed.expect("unable to get WAL write result");
+
+            let local_result = local.borrow().clone();
+            let result = match (local_result, remote) {
+                (Some(WriteResult::Ok(summary)), Ok(())) => WriteResult::Ok(summary),
+                (Some(WriteResult::Err(e)), _) => WriteResult::Err(e),
+                (Some(WriteResult::Ok(_)), Err(e)) => WriteResult::Err(format!(
+                    "failed to replicate wal op to follower: {}",
+                    e.message()
+                )),
+                (None, _) => unreachable!("WAL should always return result"),
+            };
+
+            // The caller may have stopped waiting for the result.
+            let _ = tx.send(Some(result));
+        });
+
+        rx
+    }
+}
+
+/// A [`WalFileDeleter`] decorator that, after deleting a segment, releases the
+/// ops it contained from the replica WAL on the follower.
+///
+/// A failed release is logged and otherwise ignored; the ops remain in the
+/// replica and are only ingested again by the follower if this ingester is
+/// lost.
+#[derive(Debug)]
+pub(crate) struct ReplicationReleaser<D> {
+    inner: D,
+    wal: Arc<wal::Wal>,
+    follower: FollowerClient,
+}
+
+impl<D> ReplicationReleaser<D> {
+    pub(crate) fn new(inner: D, wal: Arc<wal::Wal>, follower: FollowerClient) -> Self {
+        Self {
+            inner,
+            wal,
+            follower,
+        }
+    }
+}
+
+#[async_trait]
+impl<D> WalFileDeleter for ReplicationReleaser<D>
+where
+    D: WalFileDeleter,
+{
+    async fn delete_file(&self, id: SegmentId) {
+        let persisted = segment_sequence_numbers(&self.wal, id);
+
+        self.inner.delete_file(id).await;
+
+        if let Err(error) = self.follower.release(persisted).await {
+            warn!(%error, %id, "failed to release wal segment from follower replica");
+        }
+    }
+}
+
+/// Periodically inform the follower that this leader is alive, so that it does
+/// not take over the replica WAL while there are no writes to replicate.
+pub(crate) async fn periodic_heartbeat(follower: FollowerClient) {
+    let mut interval = tokio::time::interval(HEARTBEAT_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        if let Err(error) = follower.replicate(vec![]).await {
+            warn!(%error, "failed to send heartbeat to wal replication follower");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use super::*;
+    use crate::test_util::{
+        make_write_op, ARBITRARY_NAMESPACE_ID, ARBITRARY_PARTITION_KEY, ARBITRARY_TABLE_ID,
+        ARBITRARY_TABLE_NAME,
+    };
+
+    /// A write is not reported durable if the follower cannot be reached, even
+    /// though it is committed to the local WAL.
+    #[tokio::test]
+    async fn test_append_unreachable_follower() {
+        let dir = tempfile::tempdir().unwrap();
+        let wal = wal::Wal::new(dir.path()).await.unwrap();
+
+        let follower = FollowerClient::new(
+            "leader".to_string(),
+            "http://127.0.0.1:1".to_string(),
+            Default::default(),
+        )
+        .unwrap();
+        let appender = ReplicatingWal::new(Arc::clone(&wal), Some(follower));
+
+        let op = IngestOp::Write(make_write_op(
+            &ARBITRARY_PARTITION_KEY,
+            ARBITRARY_NAMESPACE_ID,
+            &ARBITRARY_TABLE_NAME,
+            ARBITRARY_TABLE_ID,
+            42,
+            &format!(
+                r#"{},city=Madrid day="sun",temp=55 22"#,
+                &*ARBITRARY_TABLE_NAME
+            ),
+            None,
+        ));
+
+        let mut write_result = appender.append(&op);
+        write_result.changed().await.unwrap();
+        assert_matches!(
+            &*write_result.borrow(),
+            Some(WriteResult::Err(e)) if e.starts_with("failed to replicate wal op to follower")
+        );
+
+        let (_, set) = wal.rotate().unwrap();
+        assert_eq!(set.len(), 1);
+    }
+
+    /// A follower reporting this leader as fenced stops all further writes.
+    #[tokio::test]
+    async fn test_fenced() {
+        let ingest_state = Arc::new(IngestState::default());
+        let follower = FollowerClient::new(
+            "leader".to_string(),
+            "http://127.0.0.1:1".to_string(),
+            Arc::clone(&ingest_state),
+        )
+        .unwrap();
+
+        // Other errors do not affect the ingest state.
+        assert!(follower
+            .check_fenced::<()>(Err(tonic::Status::unavailable("bananas")))
+            .is_err());
+        assert_matches!(ingest_state.read(), Ok(()));
+
+        assert!(follower
+            .check_fenced::<()>(Err(tonic::Status::failed_precondition("fenced")))
+            .is_err());
+        assert_matches!(
+            ingest_state.read(),
+            Err(IngestStateError::ReplicationFenced)
+        );
+    }
+}
@@ -0,0 +1,66 @@
+//! Synchronous replication of the write-ahead log to a follower ingester.
+//!
+//! A leader ingester streams every [`SequencedWalOp`] it commits to its own
+//! WAL to a single follower ingester, and acknowledges a write only once both
+//! WALs have made it durable. The follower keeps a replica WAL per leader,
+//! which it releases as the leader persists the data it contains.
+//!
+//! Only if a leader stops contacting its follower for longer than the takeover
+//! timeout does the follower replay the replica WAL, re-ingesting the
+//! unpersisted writes of the leader as its own. Unlike replicating writes at
+//! the router, the data of a healthy leader is therefore persisted only once.
+//!
+//! Before replaying, the follower fences the leader, rejecting its requests
+//! until it restarts. A leader that was merely partitioned from its follower
+//! therefore cannot acknowledge writes the follower does not ingest, and stops
+//! accepting writes altogether once it learns it is fenced.
+//!
+//! [`SequencedWalOp`]: wal::SequencedWalOp
+
+mod follower;
+mod leader;
+
+pub(crate) use follower::*;
+pub(crate) use leader::*;
+
+use std::time::Duration;
+
+use data_types::{sequence_number_set::SequenceNumberSet, SequenceNumber};
+use observability_deps::tracing::*;
+use wal::SegmentId;
+
+/// How often a leader informs its follower that it is alive, in the absence of
+/// writes to replicate.
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Read the sequence numbers of all ops in the closed segment `id` of `wal`.
+///
+/// Reading stops at the first damaged entry, returning the sequence numbers
+/// read up to that point.
+fn segment_sequence_numbers(wal: &wal::Wal, id: SegmentId) -> SequenceNumberSet {
+    let mut set = SequenceNumberSet::default();
+
+    let reader = match wal.reader_for_segment(id) {
+        Ok(v) => v,
+        Err(error) => {
+            warn!(%error, %id, "failed to open wal segment to read sequence numbers");
+            return set;
+        }
+    };
+
+    for batch in reader {
+        match batch {
+            Ok(ops) => set.extend(
+                ops.into_iter()
+                    .flat_map(|op| op.table_write_sequence_numbers.into_values())
+                    .map(SequenceNumber::new),
+            ),
+            Err(error) => {
+                warn!(%error, %id, "failed to read wal segment sequence numbers");
+                break;
+            }
+        }
+    }
+
+    set
+}
@@ -29,16 +29,23 @@ pub(crate) async fn periodic_rotation<T, P>(
         interval.tick().await;
         info!("rotating wal file");
 
-        let (stats, ids) = wal.rotate().expect("failed to rotate WAL");
-        debug!(
-            closed_id = %stats.id(),
-            segment_bytes = stats.size(),
-            n_ops = ids.len(),
-            "rotated wal"
-        );
-        wal_reference_handle
-            .enqueue_rotated_file(stats.id(), ids)
-            .await;
+        // A failed rotation (such as after a WAL fsync failure, which also
+        // causes writes to be rejected) still persists the buffered data below,
+        // leaving the segment to be replayed after a restart.
+        match wal.rotate() {
+            Ok((stats, ids)) => {
+                debug!(
+                    closed_id = %stats.id(),
+                    segment_bytes = stats.size(),
+                    n_ops = ids.len(),
+                    "rotated wal"
+                );
+                wal_reference_handle
+                    .enqueue_rotated_file(stats.id(), ids)
+                    .await;
+            }
+            Err(error) => error!(%error, "failed to rotate wal"),
+        }
 
         // Do not block the ticker while partitions are persisted to ensure
         // timely ticking.
@@ -77,10 +84,7 @@ pub(crate) async fn periodic_rotation<T, P>(
 
                 persist_partitions(iter, &persist).await;
 
-                debug!(
-                    closed_id = %stats.id(),
-                    "partitions persisted"
-                );
+                debug!("partitions persisted");
             }
         });
     }
@@ -92,6 +96,7 @@ mod tests {
 
     use assert_matches::assert_matches;
     use async_trait::async_trait;
+    use data_types::ParquetFileId;
     use parking_lot::Mutex;
     use tempfile::tempdir;
     use test_helpers::timeout::FutureTimeout;
@@ -249,7 +254,7 @@ mod tests {
         /// Observed PartitionData instances.
         calls: Mutex<Vec<Arc<Mutex<PartitionData>>>>,
         // The tx handles that callers are blocked waiting on.
-        tx: Mutex<Vec<oneshot::Sender<()>>>,
+        tx: Mutex<Vec<oneshot::Sender<Vec<ParquetFileId>>>>,
     }
 
     #[async_trait]
@@ -259,7 +264,7 @@ mod tests {
             &self,
             partition: Arc<Mutex<PartitionData>>,
             _data: PersistingData,
-        ) -> oneshot::Receiver<()> {
+        ) -> oneshot::Receiver<Vec<ParquetFileId>> {
             self.calls.lock().push(Arc::clone(&partition));
             let (tx, rx) = oneshot::channel();
             self.tx.lock().push(tx);
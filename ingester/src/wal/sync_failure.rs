@@ -0,0 +1,50 @@
+use std::sync::Arc;
+
+use observability_deps::tracing::*;
+use tokio::sync::watch;
+
+use crate::ingest_state::{IngestState, IngestStateError};
+
+/// Set [`IngestStateError::WalSyncFailed`] once `sync_failed` reports a failed
+/// fsync of the WAL, rejecting all further writes.
+///
+/// The error is never unset: after a failed fsync it is unknown which writes
+/// are durable, so the ingester must be restarted to replay its WAL.
+pub(crate) async fn watch_sync_failure(
+    mut sync_failed: watch::Receiver<bool>,
+    ingest_state: Arc<IngestState>,
+) {
+    if sync_failed.wait_for(|failed| *failed).await.is_err() {
+        // The WAL has been dropped.
+        return;
+    }
+
+    error!("wal fsync failed - rejecting all writes until the ingester is restarted");
+    ingest_state.set(IngestStateError::WalSyncFailed);
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use test_helpers::timeout::FutureTimeout;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_watch_sync_failure() {
+        let (tx, rx) = watch::channel(false);
+        let ingest_state = Arc::new(IngestState::default());
+
+        let task = tokio::spawn(watch_sync_failure(rx, Arc::clone(&ingest_state)));
+
+        tokio::task::yield_now().await;
+        assert_matches!(ingest_state.read(), Ok(()));
+
+        tx.send_replace(true);
+        task.with_timeout_panic(std::time::Duration::from_secs(5))
+            .await
+            .unwrap();
+
+        assert_matches!(ingest_state.read(), Err(IngestStateError::WalSyncFailed));
+    }
+}
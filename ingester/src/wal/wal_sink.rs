@@ -117,27 +117,32 @@ where
 
 impl WalAppender for Arc<wal::Wal> {
     fn append(&self, op: &IngestOp) -> Receiver<Option<WriteResult>> {
-        let namespace_id = op.namespace();
-
-        let (wal_op, partition_sequence_numbers) = match op {
-            IngestOp::Write(w) => {
-                let partition_sequence_numbers = w
-                    .tables()
-                    .map(|(table_id, data)| {
-                        (*table_id, data.partitioned_data().sequence_number().get())
-                    })
-                    .collect::<HashMap<TableId, u64>>();
-                (
-                    Op::Write(encode_write_op(namespace_id, w)),
-                    partition_sequence_numbers,
-                )
-            }
-        };
-
-        self.write_op(SequencedWalOp {
-            table_write_sequence_numbers: partition_sequence_numbers,
-            op: wal_op,
-        })
+        self.write_op(sequenced_wal_op(op))
+    }
+}
+
+/// Convert `op` into the [`SequencedWalOp`] committed to the write-ahead log.
+pub(super) fn sequenced_wal_op(op: &IngestOp) -> SequencedWalOp {
+    let namespace_id = op.namespace();
+
+    let (wal_op, partition_sequence_numbers) = match op {
+        IngestOp::Write(w) => {
+            let partition_sequence_numbers = w
+                .tables()
+                .map(|(table_id, data)| {
+                    (*table_id, data.partitioned_data().sequence_number().get())
+                })
+                .collect::<HashMap<TableId, u64>>();
+            (
+                Op::Write(encode_write_op(namespace_id, w)),
+                partition_sequence_numbers,
+            )
+        }
+    };
+
+    SequencedWalOp {
+        table_write_sequence_numbers: partition_sequence_numbers,
+        op: wal_op,
     }
 }
 
@@ -43,6 +43,7 @@ async fn write_persist() {
             table_id,
             columns: vec![],
             predicate: None,
+            aggregate: None,
         })
         .await
         .expect("query request failed");
@@ -66,6 +67,7 @@ async fn write_persist() {
             table_id,
             columns: vec![],
             predicate: None,
+            aggregate: None,
         })
         .await
         .expect("query request failed");
@@ -232,6 +234,7 @@ async fn wal_replay() {
                 table_id: ctx.table_id(namespace_name, "bananas").await.get(),
                 columns: vec![],
                 predicate: None,
+                aggregate: None,
             })
             .await
             .expect("query request failed");
@@ -330,6 +333,7 @@ async fn graceful_shutdown() {
             table_id: ctx.table_id(namespace_name, "bananas").await.get(),
             columns: vec![],
             predicate: None,
+            aggregate: None,
         })
         .await
         .expect("query request failed");
@@ -435,6 +439,7 @@ async fn wal_reference_dropping() {
             table_id: ctx.table_id(TEST_NAMESPACE_NAME, "bananas").await.get(),
             columns: vec![],
             predicate: None,
+            aggregate: None,
         })
         .await
         .expect("query request failed");
@@ -521,6 +526,7 @@ async fn write_tracing() {
             table_id,
             columns: vec![],
             predicate: None,
+            aggregate: None,
         })
         .await
         .expect("query request failed");
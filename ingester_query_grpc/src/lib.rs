@@ -29,6 +29,7 @@ use datafusion_proto::bytes::Serializeable;
 use predicate::{Predicate, ValueExpr};
 use prost::Message;
 use snafu::{ResultExt, Snafu};
+use std::collections::HashSet;
 
 /// This module imports the generated protobuf code into a Rust module
 /// hierarchy that matches the namespace hierarchy of the protobuf
@@ -133,6 +134,9 @@ pub struct IngesterQueryRequest {
 
     /// Predicate for filtering
     pub predicate: Option<Predicate>,
+
+    /// Aggregate to evaluate over the buffered data of each partition
+    pub aggregate: Option<Aggregate>,
 }
 
 impl IngesterQueryRequest {
@@ -148,6 +152,129 @@ impl IngesterQueryRequest {
             table_id,
             columns,
             predicate,
+            aggregate: None,
+        }
+    }
+
+    /// Ask the ingester to aggregate the buffered data of each partition
+    /// before returning it.
+    pub fn with_aggregate(mut self, aggregate: Aggregate) -> Self {
+        self.aggregate = Some(aggregate);
+        self
+    }
+}
+
+/// An aggregate function evaluated by the ingester.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AggregateFunction {
+    /// The number of non-null values.
+    Count,
+    /// The sum of the values of a numeric field.
+    Sum,
+    /// The smallest value.
+    Min,
+    /// The largest value.
+    Max,
+    /// The non-null value with the largest timestamp.
+    Last,
+}
+
+/// An aggregate over the field columns of a table, grouped by tag values and
+/// epoch-aligned time windows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Aggregate {
+    /// The function applied to each field column.
+    pub function: AggregateFunction,
+
+    /// The tag columns to group rows by, in addition to the time window.
+    pub group_by: Vec<String>,
+
+    /// The width of the time windows in nanoseconds, always greater than
+    /// zero.
+    pub window_nanos: i64,
+}
+
+impl TryFrom<proto::Aggregate> for Aggregate {
+    type Error = FieldViolation;
+
+    fn try_from(proto: proto::Aggregate) -> Result<Self, Self::Error> {
+        use proto::aggregate::Function;
+
+        let proto::Aggregate {
+            function,
+            group_by,
+            window_nanos,
+        } = proto;
+
+        let function = match Function::from_i32(function) {
+            Some(Function::Count) => AggregateFunction::Count,
+            Some(Function::Sum) => AggregateFunction::Sum,
+            Some(Function::Min) => AggregateFunction::Min,
+            Some(Function::Max) => AggregateFunction::Max,
+            Some(Function::Last) => AggregateFunction::Last,
+            Some(Function::Unspecified) | None => {
+                return Err(FieldViolation {
+                    field: "function".to_string(),
+                    description: format!("Unknown aggregate function: {function}"),
+                })
+            }
+        };
+
+        if window_nanos <= 0 {
+            return Err(FieldViolation {
+                field: "window_nanos".to_string(),
+                description: "Window must be greater than zero".to_string(),
+            });
+        }
+
+        let mut seen = HashSet::with_capacity(group_by.len());
+        for name in &group_by {
+            let description = if name.is_empty() {
+                "Group by column name must not be empty".to_string()
+            } else if name == "time" {
+                "Cannot group by the time column, use window_nanos instead".to_string()
+            } else if !seen.insert(name) {
+                format!("Duplicate group by column: {name}")
+            } else {
+                continue;
+            };
+
+            return Err(FieldViolation {
+                field: "group_by".to_string(),
+                description,
+            });
+        }
+
+        Ok(Self {
+            function,
+            group_by,
+            window_nanos,
+        })
+    }
+}
+
+impl From<Aggregate> for proto::Aggregate {
+    fn from(aggregate: Aggregate) -> Self {
+        use proto::aggregate::Function;
+
+        let Aggregate {
+            function,
+            group_by,
+            window_nanos,
+        } = aggregate;
+
+        let function = match function {
+            AggregateFunction::Count => Function::Count,
+            AggregateFunction::Sum => Function::Sum,
+            AggregateFunction::Min => Function::Min,
+            AggregateFunction::Max => Function::Max,
+            AggregateFunction::Last => Function::Last,
+        };
+
+        Self {
+            function: function.into(),
+            group_by,
+            window_nanos,
         }
     }
 }
@@ -161,13 +288,21 @@ impl TryFrom<proto::IngesterQueryRequest> for IngesterQueryRequest {
             table_id,
             columns,
             predicate,
+            aggregate,
         } = proto;
 
         let namespace_id = NamespaceId::new(namespace_id);
         let table_id = TableId::new(table_id);
         let predicate = predicate.map(TryInto::try_into).transpose()?;
+        let aggregate = aggregate
+            .map(TryInto::try_into)
+            .transpose()
+            .map_err(|e: FieldViolation| e.scope("aggregate"))?;
 
-        Ok(Self::new(namespace_id, table_id, columns, predicate))
+        Ok(Self {
+            aggregate,
+            ..Self::new(namespace_id, table_id, columns, predicate)
+        })
     }
 }
 
@@ -180,6 +315,7 @@ impl TryFrom<IngesterQueryRequest> for proto::IngesterQueryRequest {
             table_id,
             columns,
             predicate,
+            aggregate,
         } = query;
 
         Ok(Self {
@@ -187,6 +323,7 @@ impl TryFrom<IngesterQueryRequest> for proto::IngesterQueryRequest {
             table_id: table_id.get(),
             columns,
             predicate: predicate.map(TryInto::try_into).transpose()?,
+            aggregate: aggregate.map(Into::into),
         })
     }
 }
@@ -515,7 +652,12 @@ mod tests {
             TableId::new(1337),
             vec!["usage".into(), "time".into()],
             Some(rust_predicate),
-        );
+        )
+        .with_aggregate(Aggregate {
+            function: AggregateFunction::Last,
+            group_by: vec!["region".into()],
+            window_nanos: 60_000_000_000,
+        });
 
         let proto_query: proto::IngesterQueryRequest = rust_query.clone().try_into().unwrap();
 
@@ -524,6 +666,34 @@ mod tests {
         assert_eq!(rust_query, rust_query_converted);
     }
 
+    #[test]
+    fn aggregate_validation() {
+        let aggregate = |function: proto::aggregate::Function, window_nanos| proto::Aggregate {
+            function: function.into(),
+            group_by: vec![],
+            window_nanos,
+        };
+
+        let err = Aggregate::try_from(aggregate(proto::aggregate::Function::Unspecified, 10))
+            .unwrap_err();
+        assert_eq!(err.field, "function");
+
+        let err = Aggregate::try_from(aggregate(proto::aggregate::Function::Sum, 0)).unwrap_err();
+        assert_eq!(err.field, "window_nanos");
+
+        let got = Aggregate::try_from(aggregate(proto::aggregate::Function::Sum, 10)).unwrap();
+        assert_eq!(got.function, AggregateFunction::Sum);
+
+        for group_by in [vec![""], vec!["time"], vec!["region", "region"]] {
+            let err = Aggregate::try_from(proto::Aggregate {
+                group_by: group_by.into_iter().map(ToString::to_string).collect(),
+                ..aggregate(proto::aggregate::Function::Sum, 10)
+            })
+            .unwrap_err();
+            assert_eq!(err.field, "group_by");
+        }
+    }
+
     #[test]
     fn query2_round_trip() {
         let rust_query = IngesterQueryRequest2::new(
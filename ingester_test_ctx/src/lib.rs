@@ -30,7 +30,10 @@ use futures::{stream::FuturesUnordered, FutureExt, StreamExt, TryStreamExt};
 use generated_types::influxdata::iox::ingester::v1::{
     write_service_server::WriteService, WriteRequest,
 };
-use ingester::{GossipConfig, IngesterGuard, IngesterRpcInterface};
+use ingester::{
+    GossipConfig, IngesterGuard, IngesterRpcInterface, MemoryLimitConfig, WalArchiveConfig,
+    WalConfig, WalReplayMode, WalReplicaConfig, WalReplicationConfig,
+};
 use ingester_query_grpc::influxdata::iox::ingester::v1::IngesterQueryRequest;
 use iox_catalog::{
     interface::{Catalog, SoftDeletedRows},
@@ -162,13 +165,21 @@ impl TestContextBuilder {
             Arc::clone(&metrics),
             persist_background_fetch_time,
             dir.path().to_owned(),
+            WalConfig::default(),
             wal_rotation_period,
+            WalReplayMode::Strict,
             persist_executor,
             persist_workers,
             max_persist_queue_depth,
             persist_hot_partition_cost,
+            None,
+            None,
+            MemoryLimitConfig::default(),
             storage.clone(),
             GossipConfig::default(),
+            WalArchiveConfig::default(),
+            WalReplicationConfig::default(),
+            WalReplicaConfig::default(),
             shutdown_rx.map(|v| v.expect("shutdown sender dropped without calling shutdown")),
         )
         .await
@@ -237,6 +248,9 @@ where
                         tables: Default::default(),
                         max_columns_per_table: iox_catalog::DEFAULT_MAX_COLUMNS_PER_TABLE as usize,
                         max_tables: iox_catalog::DEFAULT_MAX_TABLES as usize,
+                        max_write_bytes_per_second: None,
+                        max_write_lines_per_second: None,
+                        max_concurrent_queries: None,
                         retention_period_ns,
                         partition_template: partition_template.unwrap_or_default(),
                     },
@@ -409,9 +423,6 @@ where
     }
 
     /// Request `namespace` be persisted and block for its completion.
-    ///
-    /// Be aware of the unreliable caveats of this interface (see proto
-    /// definition).
     pub async fn persist(&self, namespace: impl Into<String> + Send) {
         use generated_types::influxdata::iox::ingester::v1::{
             self as proto, persist_service_server::PersistService,
@@ -421,7 +432,12 @@ where
         self.ingester
             .rpc()
             .persist_service()
-            .persist(Request::new(proto::PersistRequest { namespace }))
+            .persist(Request::new(proto::PersistRequest {
+                namespace,
+                table: None,
+                partition_key: None,
+                no_wait: false,
+            }))
             .await
             .expect("failed to invoke persist");
     }
@@ -0,0 +1,4 @@
+-- Add an optional per-table retention period that overrides the namespace retention period.
+ALTER TABLE
+    IF EXISTS table_name
+    ADD COLUMN retention_period_ns BIGINT;
@@ -0,0 +1,17 @@
+-- Rollup rules declare how the raw data of a table is continuously downsampled into another table
+-- of the same namespace.
+CREATE TABLE IF NOT EXISTS rollup_rule (
+    id BIGINT GENERATED ALWAYS AS IDENTITY,
+    namespace_id BIGINT NOT NULL REFERENCES namespace (id) ON DELETE CASCADE,
+    name VARCHAR NOT NULL,
+    source_table VARCHAR NOT NULL,
+    target_table VARCHAR NOT NULL,
+    language SMALLINT NOT NULL,
+    aggregates TEXT NOT NULL,
+    interval_ns BIGINT NOT NULL,
+    covered_until BIGINT,
+    PRIMARY KEY (id),
+    CONSTRAINT rollup_rule_unique UNIQUE (namespace_id, name)
+);
+
+CREATE INDEX IF NOT EXISTS rollup_rule_namespace_idx ON rollup_rule (namespace_id);
@@ -0,0 +1,8 @@
+-- Add optional per-namespace write rate and query concurrency quotas.
+--
+-- NULL means the namespace is not limited.
+ALTER TABLE
+    IF EXISTS namespace
+    ADD COLUMN max_write_bytes_per_second BIGINT,
+    ADD COLUMN max_write_lines_per_second BIGINT,
+    ADD COLUMN max_concurrent_queries INT;
@@ -0,0 +1,101 @@
+-- Aggregated object storage usage of the parquet files not marked for deletion, per table and
+-- compaction level, maintained by triggers on parquet_file.
+CREATE TABLE IF NOT EXISTS storage_usage (
+    namespace_id BIGINT NOT NULL REFERENCES namespace (id) ON DELETE CASCADE,
+    table_id BIGINT NOT NULL REFERENCES table_name (id) ON DELETE CASCADE,
+    compaction_level SMALLINT NOT NULL,
+    file_count BIGINT NOT NULL,
+    file_size_bytes BIGINT NOT NULL,
+    row_count BIGINT NOT NULL,
+    PRIMARY KEY (namespace_id, table_id, compaction_level)
+);
+
+INSERT INTO storage_usage
+    (namespace_id, table_id, compaction_level, file_count, file_size_bytes, row_count)
+SELECT namespace_id, table_id, compaction_level, COUNT(*), SUM(file_size_bytes), SUM(row_count)
+FROM parquet_file
+WHERE to_delete IS NULL
+GROUP BY namespace_id, table_id, compaction_level;
+
+CREATE OR REPLACE FUNCTION add_storage_usage(
+    _namespace_id BIGINT,
+    _table_id BIGINT,
+    _compaction_level SMALLINT,
+    _file_count BIGINT,
+    _file_size_bytes BIGINT,
+    _row_count BIGINT
+)
+    RETURNS VOID
+    LANGUAGE PLPGSQL
+    AS
+$$
+BEGIN
+    -- Removals never create rows, so that parquet files deleted along with their namespace or
+    -- table do not resurrect its usage.
+    IF _file_count < 0 THEN
+        UPDATE storage_usage
+        SET file_count = file_count + _file_count,
+            file_size_bytes = file_size_bytes + _file_size_bytes,
+            row_count = row_count + _row_count
+        WHERE namespace_id = _namespace_id
+          AND table_id = _table_id
+          AND compaction_level = _compaction_level;
+    ELSE
+        INSERT INTO storage_usage
+            (namespace_id, table_id, compaction_level, file_count, file_size_bytes, row_count)
+        VALUES
+            (_namespace_id, _table_id, _compaction_level, _file_count, _file_size_bytes, _row_count)
+        ON CONFLICT (namespace_id, table_id, compaction_level) DO UPDATE
+        SET file_count = storage_usage.file_count + _file_count,
+            file_size_bytes = storage_usage.file_size_bytes + _file_size_bytes,
+            row_count = storage_usage.row_count + _row_count;
+    END IF;
+END;
+$$ ;
+
+-- A file is accounted for while it is not marked for deletion, at its current compaction level.
+CREATE OR REPLACE FUNCTION update_storage_usage()
+    RETURNS TRIGGER
+    LANGUAGE PLPGSQL
+    AS
+$$
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        IF NEW.to_delete IS NULL THEN
+            PERFORM add_storage_usage(
+                NEW.namespace_id, NEW.table_id, NEW.compaction_level,
+                1, NEW.file_size_bytes, NEW.row_count
+            );
+        END IF;
+    ELSIF TG_OP = 'DELETE' THEN
+        IF OLD.to_delete IS NULL THEN
+            PERFORM add_storage_usage(
+                OLD.namespace_id, OLD.table_id, OLD.compaction_level,
+                -1, -OLD.file_size_bytes, -OLD.row_count
+            );
+        END IF;
+    ELSIF (OLD.to_delete IS NULL) <> (NEW.to_delete IS NULL)
+        OR OLD.compaction_level <> NEW.compaction_level THEN
+        IF OLD.to_delete IS NULL THEN
+            PERFORM add_storage_usage(
+                OLD.namespace_id, OLD.table_id, OLD.compaction_level,
+                -1, -OLD.file_size_bytes, -OLD.row_count
+            );
+        END IF;
+        IF NEW.to_delete IS NULL THEN
+            PERFORM add_storage_usage(
+                NEW.namespace_id, NEW.table_id, NEW.compaction_level,
+                1, NEW.file_size_bytes, NEW.row_count
+            );
+        END IF;
+    END IF;
+
+    RETURN NULL;
+END;
+$$ ;
+
+CREATE TRIGGER update_storage_usage
+    AFTER INSERT OR UPDATE OR DELETE
+    ON parquet_file
+    FOR EACH ROW
+    EXECUTE PROCEDURE update_storage_usage();
@@ -0,0 +1,5 @@
+-- Record the error of the last failed run of a rollup rule, so failures are visible when listing
+-- the rules. NULL means the last run succeeded.
+ALTER TABLE
+    IF EXISTS rollup_rule
+    ADD COLUMN last_error TEXT;
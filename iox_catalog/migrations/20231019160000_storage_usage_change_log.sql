@@ -0,0 +1,111 @@
+-- Record changes to the storage usage in an append-only log, folded into storage_usage
+-- periodically, instead of updating storage_usage from a row-level trigger on parquet_file.
+--
+-- The row-level trigger updated the storage_usage row of the table and compaction level of every
+-- file created, upgraded or deleted, holding its row lock until the transaction committed. This
+-- serialised all concurrent commits of files of the same table and compaction level, such as
+-- those of ingesters persisting the same table. Appending to the log takes no row locks that
+-- writers contend on.
+DROP TRIGGER IF EXISTS update_storage_usage ON parquet_file;
+DROP FUNCTION IF EXISTS update_storage_usage();
+DROP FUNCTION IF EXISTS add_storage_usage(BIGINT, BIGINT, SMALLINT, BIGINT, BIGINT, BIGINT);
+
+-- Changes to storage_usage not yet folded into it. The storage usage of a table and compaction
+-- level is the sum of its storage_usage row and its changes.
+CREATE TABLE IF NOT EXISTS storage_usage_change (
+    namespace_id BIGINT NOT NULL,
+    table_id BIGINT NOT NULL,
+    compaction_level SMALLINT NOT NULL,
+    file_count BIGINT NOT NULL,
+    file_size_bytes BIGINT NOT NULL,
+    row_count BIGINT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS storage_usage_change_namespace_idx
+    ON storage_usage_change (namespace_id);
+
+-- A file is accounted for while it is not marked for deletion, at its current compaction level.
+-- The triggers run once per statement, recording a single change per table and compaction level
+-- for all the files of the statement.
+CREATE OR REPLACE FUNCTION record_storage_usage_insert()
+    RETURNS TRIGGER
+    LANGUAGE PLPGSQL
+    AS
+$$
+BEGIN
+    INSERT INTO storage_usage_change
+        (namespace_id, table_id, compaction_level, file_count, file_size_bytes, row_count)
+    SELECT namespace_id, table_id, compaction_level, COUNT(*), SUM(file_size_bytes), SUM(row_count)
+    FROM new_files
+    WHERE to_delete IS NULL
+    GROUP BY namespace_id, table_id, compaction_level;
+
+    RETURN NULL;
+END;
+$$ ;
+
+CREATE OR REPLACE FUNCTION record_storage_usage_delete()
+    RETURNS TRIGGER
+    LANGUAGE PLPGSQL
+    AS
+$$
+BEGIN
+    INSERT INTO storage_usage_change
+        (namespace_id, table_id, compaction_level, file_count, file_size_bytes, row_count)
+    SELECT namespace_id, table_id, compaction_level, -COUNT(*), -SUM(file_size_bytes), -SUM(row_count)
+    FROM old_files
+    WHERE to_delete IS NULL
+    GROUP BY namespace_id, table_id, compaction_level;
+
+    RETURN NULL;
+END;
+$$ ;
+
+CREATE OR REPLACE FUNCTION record_storage_usage_update()
+    RETURNS TRIGGER
+    LANGUAGE PLPGSQL
+    AS
+$$
+BEGIN
+    INSERT INTO storage_usage_change
+        (namespace_id, table_id, compaction_level, file_count, file_size_bytes, row_count)
+    SELECT namespace_id, table_id, compaction_level,
+        SUM(file_count), SUM(file_size_bytes), SUM(row_count)
+    FROM (
+        SELECT namespace_id, table_id, compaction_level,
+            -1 AS file_count, -file_size_bytes AS file_size_bytes, -row_count AS row_count
+        FROM old_files
+        WHERE to_delete IS NULL
+        UNION ALL
+        SELECT namespace_id, table_id, compaction_level,
+            1 AS file_count, file_size_bytes, row_count
+        FROM new_files
+        WHERE to_delete IS NULL
+    ) AS changes
+    GROUP BY namespace_id, table_id, compaction_level
+    HAVING SUM(file_count) <> 0 OR SUM(file_size_bytes) <> 0 OR SUM(row_count) <> 0;
+
+    RETURN NULL;
+END;
+$$ ;
+
+CREATE TRIGGER record_storage_usage_insert
+    AFTER INSERT
+    ON parquet_file
+    REFERENCING NEW TABLE AS new_files
+    FOR EACH STATEMENT
+    EXECUTE PROCEDURE record_storage_usage_insert();
+
+CREATE TRIGGER record_storage_usage_delete
+    AFTER DELETE
+    ON parquet_file
+    REFERENCING OLD TABLE AS old_files
+    FOR EACH STATEMENT
+    EXECUTE PROCEDURE record_storage_usage_delete();
+
+CREATE TRIGGER record_storage_usage_update
+    AFTER UPDATE
+    ON parquet_file
+    REFERENCING OLD TABLE AS old_files NEW TABLE AS new_files
+    FOR EACH STATEMENT
+    EXECUTE PROCEDURE record_storage_usage_update();
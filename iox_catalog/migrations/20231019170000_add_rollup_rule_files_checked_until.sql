@@ -0,0 +1,27 @@
+-- Record the creation time up to which a rollup rule has checked the files of its source table,
+-- so each run only lists the files persisted since the previous one. NULL means the files have
+-- not been checked since data was first rolled up.
+-- IOX_NO_TRANSACTION
+ALTER TABLE
+    IF EXISTS rollup_rule
+    ADD COLUMN IF NOT EXISTS files_checked_until BIGINT;
+
+-- IOX_STEP_BOUNDARY
+
+-- By default we often only have 5min to finish our statements. The `CREATE INDEX CONCURRENTLY` however takes longer.
+-- IOX_NO_TRANSACTION
+SET statement_timeout TO '60min';
+
+-- IOX_STEP_BOUNDARY
+
+-- remove potentially invalid index
+-- IOX_NO_TRANSACTION
+DROP INDEX CONCURRENTLY IF EXISTS parquet_file_table_created_idx;
+
+-- IOX_STEP_BOUNDARY
+
+-- Lists the files of a table created after a given time without scanning the older ones.
+-- IOX_NO_TRANSACTION
+CREATE INDEX CONCURRENTLY parquet_file_table_created_idx
+    ON parquet_file (table_id, created_at)
+    WHERE to_delete IS NULL;
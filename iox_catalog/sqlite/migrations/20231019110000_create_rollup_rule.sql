@@ -0,0 +1,23 @@
+-- Rollup rules declare how the raw data of a table is continuously downsampled into another table
+-- of the same namespace.
+create table if not exists rollup_rule
+(
+    id            INTEGER
+        constraint rollup_rule_pkey
+            primary key autoincrement,
+    namespace_id  numeric  not null
+        references namespace
+            on delete cascade,
+    name          varchar  not null,
+    source_table  varchar  not null,
+    target_table  varchar  not null,
+    language      smallint not null,
+    aggregates    text     not null,
+    interval_ns   numeric  not null,
+    covered_until numeric,
+    constraint rollup_rule_unique
+        unique (namespace_id, name)
+);
+
+create index if not exists rollup_rule_namespace_idx
+    on rollup_rule (namespace_id);
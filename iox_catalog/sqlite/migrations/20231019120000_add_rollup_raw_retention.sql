@@ -0,0 +1,7 @@
+-- Add an optional retention period for the raw data of the source table of a rollup rule.
+--
+-- Raw data older than this period is only deleted once the rule has rolled it up, and the
+-- namespace / table retention period no longer applies to the source table.
+ALTER TABLE
+    rollup_rule
+ADD COLUMN raw_retention_period_ns numeric;
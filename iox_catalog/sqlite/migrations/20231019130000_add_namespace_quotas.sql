@@ -0,0 +1,14 @@
+-- Add optional per-namespace write rate and query concurrency quotas.
+--
+-- NULL means the namespace is not limited.
+ALTER TABLE
+    namespace
+ADD COLUMN max_write_bytes_per_second numeric;
+
+ALTER TABLE
+    namespace
+ADD COLUMN max_write_lines_per_second numeric;
+
+ALTER TABLE
+    namespace
+ADD COLUMN max_concurrent_queries INT;
@@ -0,0 +1,88 @@
+-- Aggregated object storage usage of the parquet files not marked for deletion, per table and
+-- compaction level, maintained by triggers on parquet_file.
+create table if not exists storage_usage
+(
+    namespace_id     numeric  not null
+        references namespace
+            on delete cascade,
+    table_id         numeric  not null
+        references table_name
+            on delete cascade,
+    compaction_level smallint not null,
+    file_count       numeric  not null,
+    file_size_bytes  numeric  not null,
+    row_count        numeric  not null,
+    primary key (namespace_id, table_id, compaction_level)
+);
+
+insert into storage_usage
+    (namespace_id, table_id, compaction_level, file_count, file_size_bytes, row_count)
+select namespace_id, table_id, compaction_level, count(*), sum(file_size_bytes), sum(row_count)
+from parquet_file
+where to_delete is null
+group by namespace_id, table_id, compaction_level;
+
+-- A file is accounted for while it is not marked for deletion, at its current compaction level.
+create trigger if not exists storage_usage_insert
+    after insert
+    on parquet_file
+    for each row
+    when NEW.to_delete IS NULL
+begin
+    INSERT INTO storage_usage
+        (namespace_id, table_id, compaction_level, file_count, file_size_bytes, row_count)
+    VALUES
+        (NEW.namespace_id, NEW.table_id, NEW.compaction_level, 1, NEW.file_size_bytes, NEW.row_count)
+    ON CONFLICT (namespace_id, table_id, compaction_level) DO UPDATE
+        SET file_count = storage_usage.file_count + 1,
+            file_size_bytes = storage_usage.file_size_bytes + NEW.file_size_bytes,
+            row_count = storage_usage.row_count + NEW.row_count;
+end;
+
+create trigger if not exists storage_usage_update_remove
+    after update
+    on parquet_file
+    for each row
+    when OLD.to_delete IS NULL
+        AND (NEW.to_delete IS NOT NULL OR NEW.compaction_level <> OLD.compaction_level)
+begin
+    UPDATE storage_usage
+    SET file_count = file_count - 1,
+        file_size_bytes = file_size_bytes - OLD.file_size_bytes,
+        row_count = row_count - OLD.row_count
+    WHERE namespace_id = OLD.namespace_id
+      AND table_id = OLD.table_id
+      AND compaction_level = OLD.compaction_level;
+end;
+
+create trigger if not exists storage_usage_update_add
+    after update
+    on parquet_file
+    for each row
+    when NEW.to_delete IS NULL
+        AND (OLD.to_delete IS NOT NULL OR NEW.compaction_level <> OLD.compaction_level)
+begin
+    INSERT INTO storage_usage
+        (namespace_id, table_id, compaction_level, file_count, file_size_bytes, row_count)
+    VALUES
+        (NEW.namespace_id, NEW.table_id, NEW.compaction_level, 1, NEW.file_size_bytes, NEW.row_count)
+    ON CONFLICT (namespace_id, table_id, compaction_level) DO UPDATE
+        SET file_count = storage_usage.file_count + 1,
+            file_size_bytes = storage_usage.file_size_bytes + NEW.file_size_bytes,
+            row_count = storage_usage.row_count + NEW.row_count;
+end;
+
+create trigger if not exists storage_usage_delete
+    after delete
+    on parquet_file
+    for each row
+    when OLD.to_delete IS NULL
+begin
+    UPDATE storage_usage
+    SET file_count = file_count - 1,
+        file_size_bytes = file_size_bytes - OLD.file_size_bytes,
+        row_count = row_count - OLD.row_count
+    WHERE namespace_id = OLD.namespace_id
+      AND table_id = OLD.table_id
+      AND compaction_level = OLD.compaction_level;
+end;
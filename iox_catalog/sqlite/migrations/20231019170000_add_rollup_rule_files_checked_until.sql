@@ -0,0 +1,10 @@
+-- Record the creation time up to which a rollup rule has checked the files of its source table,
+-- so each run only lists the files persisted since the previous one. NULL means the files have
+-- not been checked since data was first rolled up.
+ALTER TABLE
+    rollup_rule
+ADD COLUMN files_checked_until BIGINT;
+
+CREATE INDEX IF NOT EXISTS parquet_file_table_created_idx
+    ON parquet_file (table_id, created_at)
+    WHERE to_delete IS NULL;
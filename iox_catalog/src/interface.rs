@@ -5,8 +5,9 @@ use data_types::{
     partition_template::{NamespacePartitionTemplateOverride, TablePartitionTemplateOverride},
     Column, ColumnType, ColumnsByName, CompactionLevel, Namespace, NamespaceId, NamespaceName,
     NamespaceSchema, NamespaceServiceProtectionLimitsOverride, ParquetFile, ParquetFileId,
-    ParquetFileParams, Partition, PartitionHashId, PartitionId, PartitionKey, SkippedCompaction,
-    SortedColumnSet, Table, TableId, TableSchema, Timestamp, TransitionPartitionId,
+    ParquetFileParams, Partition, PartitionHashId, PartitionId, PartitionKey, RollupRule,
+    RollupRuleId, RollupRuleParams, SkippedCompaction, SortedColumnSet, StorageUsage, Table,
+    TableId, TableSchema, Timestamp, TransitionPartitionId,
 };
 use iox_time::TimeProvider;
 use snafu::{OptionExt, Snafu};
@@ -148,6 +149,15 @@ pub enum Error {
 
     #[snafu(display("could not delete namespace: {source}"))]
     CouldNotDeleteNamespace { source: sqlx::Error },
+
+    #[snafu(display("rollup rule {} already exists in namespace {}", name, namespace_id))]
+    RollupRuleNameExists {
+        name: String,
+        namespace_id: NamespaceId,
+    },
+
+    #[snafu(display("rollup rule {} not found", id))]
+    RollupRuleNotFound { id: RollupRuleId },
 }
 
 /// A specialized `Error` for Catalog errors
@@ -245,6 +255,9 @@ pub trait RepoCollection: Send + Sync + Debug {
 
     /// Repository for [Parquet files](data_types::ParquetFile).
     fn parquet_files(&mut self) -> &mut dyn ParquetFileRepo;
+
+    /// Repository for [rollup rules](data_types::RollupRule).
+    fn rollup_rules(&mut self) -> &mut dyn RollupRuleRepo;
 }
 
 /// Functions for working with namespaces in the catalog
@@ -293,6 +306,30 @@ pub trait NamespaceRepo: Send + Sync {
 
     /// Update the limit on the number of columns that can exist per table in a given namespace.
     async fn update_column_limit(&mut self, name: &str, new_max: i32) -> Result<Namespace>;
+
+    /// Update the limit on the number of line protocol bytes per second that may be written to a
+    /// given namespace. [`None`] removes the limit.
+    async fn update_write_bytes_limit(
+        &mut self,
+        name: &str,
+        new_max: Option<i64>,
+    ) -> Result<Namespace>;
+
+    /// Update the limit on the number of lines per second that may be written to a given
+    /// namespace. [`None`] removes the limit.
+    async fn update_write_lines_limit(
+        &mut self,
+        name: &str,
+        new_max: Option<i64>,
+    ) -> Result<Namespace>;
+
+    /// Update the limit on the number of queries that may run concurrently against a given
+    /// namespace. [`None`] removes the limit.
+    async fn update_query_concurrency_limit(
+        &mut self,
+        name: &str,
+        new_max: Option<i32>,
+    ) -> Result<Namespace>;
 }
 
 /// Functions for working with tables in the catalog
@@ -322,6 +359,16 @@ pub trait TableRepo: Send + Sync {
 
     /// List all tables.
     async fn list(&mut self) -> Result<Vec<Table>>;
+
+    /// Update the retention period override for a table.
+    ///
+    /// Specify `None` for `retention_period_ns` to remove the override and use the namespace's
+    /// retention period.
+    async fn update_retention_period(
+        &mut self,
+        table_id: TableId,
+        retention_period_ns: Option<i64>,
+    ) -> Result<Table>;
 }
 
 /// Functions for working with columns in the catalog
@@ -369,6 +416,20 @@ pub trait PartitionRepo: Send + Sync {
     /// create or get a partition record for the given partition key and table
     async fn create_or_get(&mut self, key: PartitionKey, table_id: TableId) -> Result<Partition>;
 
+    /// Perform a bulk create or get of partition records for the given
+    /// `(table, partition key)` pairs, which may span multiple tables.
+    ///
+    /// Duplicate pairs are ignored. The output order is undefined, and exactly
+    /// one partition is returned for each distinct pair.
+    ///
+    /// Implementations make no guarantees as to the atomicity of the batch -
+    /// a batch may partially commit, in which case an error MUST be returned
+    /// by the implementation.
+    async fn create_or_get_many(
+        &mut self,
+        partitions: &[(TableId, PartitionKey)],
+    ) -> Result<Vec<Partition>>;
+
     /// get partition by ID
     async fn get_by_id(&mut self, partition_id: PartitionId) -> Result<Option<Partition>>;
 
@@ -480,7 +541,11 @@ pub trait ParquetFileRepo: Send + Sync {
     /// This is mostly useful for testing and will likely not succeed in production.
     async fn list_all(&mut self) -> Result<Vec<ParquetFile>>;
 
-    /// Flag all parquet files for deletion that are older than their namespace's retention period.
+    /// Flag all parquet files for deletion that are older than their table's retention period,
+    /// or their namespace's retention period if the table does not override it.
+    ///
+    /// Files of the source table of a rollup rule with a raw retention period are instead only
+    /// flagged once they are older than that period and have been rolled up.
     async fn flag_for_delete_by_retention(&mut self) -> Result<Vec<ParquetFileId>>;
 
     /// List all parquet files within a given namespace that are NOT marked as
@@ -494,6 +559,14 @@ pub trait ParquetFileRepo: Send + Sync {
     /// [`to_delete`](ParquetFile::to_delete).
     async fn list_by_table_not_to_delete(&mut self, table_id: TableId) -> Result<Vec<ParquetFile>>;
 
+    /// List all parquet files within a given table that are NOT marked as
+    /// [`to_delete`](ParquetFile::to_delete) and were created after `created_after`.
+    async fn list_by_table_created_after(
+        &mut self,
+        table_id: TableId,
+        created_after: Timestamp,
+    ) -> Result<Vec<ParquetFile>>;
+
     /// Delete parquet files that were marked to be deleted earlier than the specified time.
     ///
     /// Returns the deleted IDs only.
@@ -532,6 +605,77 @@ pub trait ParquetFileRepo: Send + Sync {
         create: &[ParquetFileParams],
         target_level: CompactionLevel,
     ) -> Result<Vec<ParquetFileId>>;
+
+    /// List the [`StorageUsage`] of each table and compaction level of the given namespace, sorted
+    /// by table and compaction level.
+    ///
+    /// The SQL catalogs maintain this aggregate as files are created, upgraded and deleted, so this
+    /// does not scan the parquet files. The Postgres catalog records the changes to the aggregate
+    /// in a log, which is read along with it until [`fold_storage_usage`] is called.
+    ///
+    /// [`fold_storage_usage`]: Self::fold_storage_usage
+    async fn storage_usage_by_namespace(
+        &mut self,
+        namespace_id: NamespaceId,
+    ) -> Result<Vec<StorageUsage>>;
+
+    /// List the [`StorageUsage`] of each table and compaction level of all namespaces, sorted by
+    /// namespace, table and compaction level.
+    async fn storage_usage(&mut self) -> Result<Vec<StorageUsage>>;
+
+    /// Fold the changes to the storage usage recorded since the last call into the aggregate,
+    /// returning the number of changes folded.
+    ///
+    /// This does not change the reported [`StorageUsage`], and keeps reading it cheap.
+    async fn fold_storage_usage(&mut self) -> Result<usize>;
+}
+
+/// Functions for working with rollup rules in the catalog
+#[async_trait]
+pub trait RollupRuleRepo: Send + Sync {
+    /// Creates the rollup rule in the catalog. If one in the same namespace with the same name
+    /// already exists, an error is returned.
+    async fn create(&mut self, rollup_rule_params: RollupRuleParams) -> Result<RollupRule>;
+
+    /// get rollup rule by namespace ID and name
+    async fn get_by_namespace_and_name(
+        &mut self,
+        namespace_id: NamespaceId,
+        name: &str,
+    ) -> Result<Option<RollupRule>>;
+
+    /// Lists all rollup rules in the catalog for the given namespace id.
+    async fn list_by_namespace_id(&mut self, namespace_id: NamespaceId) -> Result<Vec<RollupRule>>;
+
+    /// List all rollup rules.
+    async fn list(&mut self) -> Result<Vec<RollupRule>>;
+
+    /// Record that the source table of the rule has been rolled up until `covered_until`
+    /// (exclusive).
+    async fn update_covered_until(
+        &mut self,
+        id: RollupRuleId,
+        covered_until: Timestamp,
+    ) -> Result<RollupRule>;
+
+    /// Record that the files of the source table of the rule created until `files_checked_until`
+    /// have been checked for data to roll up.
+    async fn update_files_checked_until(
+        &mut self,
+        id: RollupRuleId,
+        files_checked_until: Timestamp,
+    ) -> Result<RollupRule>;
+
+    /// Record the error of the last run of the rule, or clear it with `None` after a successful
+    /// run.
+    async fn update_last_error(
+        &mut self,
+        id: RollupRuleId,
+        last_error: Option<String>,
+    ) -> Result<RollupRule>;
+
+    /// Delete the rollup rule.
+    async fn delete(&mut self, id: RollupRuleId) -> Result<()>;
 }
 
 /// Gets the namespace schema including all tables and columns.
@@ -738,7 +882,7 @@ pub(crate) mod test_helpers {
     use super::*;
     use ::test_helpers::assert_error;
     use assert_matches::assert_matches;
-    use data_types::{ColumnId, CompactionLevel};
+    use data_types::{ColumnId, CompactionLevel, RollupQueryLanguage};
     use futures::Future;
     use generated_types::influxdata::iox::partition_template::v1 as proto;
     use metric::{Attributes, DurationHistogram, Metric};
@@ -761,6 +905,9 @@ pub(crate) mod test_helpers {
         test_list_schemas(clean_state().await).await;
         test_list_schemas_soft_deleted_rows(clean_state().await).await;
         test_delete_namespace(clean_state().await).await;
+        test_rollup_rules(clean_state().await).await;
+        test_tiered_retention(clean_state().await).await;
+        test_storage_usage(clean_state().await).await;
 
         let catalog = clean_state().await;
         test_namespace(Arc::clone(&catalog)).await;
@@ -778,9 +925,21 @@ pub(crate) mod test_helpers {
         test_partition(Arc::clone(&catalog)).await;
         assert_metric_hit(&catalog.metrics(), "partition_create_or_get");
 
+        let catalog = clean_state().await;
+        test_partition_create_or_get_many(Arc::clone(&catalog)).await;
+        assert_metric_hit(&catalog.metrics(), "partition_create_or_get_many");
+
         let catalog = clean_state().await;
         test_parquet_file(Arc::clone(&catalog)).await;
         assert_metric_hit(&catalog.metrics(), "parquet_create");
+
+        let catalog = clean_state().await;
+        test_rollup_rules(Arc::clone(&catalog)).await;
+        assert_metric_hit(&catalog.metrics(), "rollup_rule_create");
+
+        let catalog = clean_state().await;
+        test_storage_usage(Arc::clone(&catalog)).await;
+        assert_metric_hit(&catalog.metrics(), "parquet_storage_usage_by_namespace");
     }
 
     async fn test_setup(catalog: Arc<dyn Catalog>) {
@@ -881,6 +1040,49 @@ pub(crate) mod test_helpers {
             .expect("namespace should be updateable");
         assert_eq!(NEW_COLUMN_LIMIT, modified.max_columns_per_table);
 
+        // quotas are unlimited by default
+        assert_eq!(modified.max_write_bytes_per_second, None);
+        assert_eq!(modified.max_write_lines_per_second, None);
+        assert_eq!(modified.max_concurrent_queries, None);
+
+        let modified = repos
+            .namespaces()
+            .update_write_bytes_limit(namespace_name.as_str(), Some(1024 * 1024))
+            .await
+            .expect("namespace should be updateable");
+        assert_eq!(modified.max_write_bytes_per_second, Some(1024 * 1024));
+
+        let modified = repos
+            .namespaces()
+            .update_write_lines_limit(namespace_name.as_str(), Some(10_000))
+            .await
+            .expect("namespace should be updateable");
+        assert_eq!(modified.max_write_lines_per_second, Some(10_000));
+
+        let modified = repos
+            .namespaces()
+            .update_query_concurrency_limit(namespace_name.as_str(), Some(4))
+            .await
+            .expect("namespace should be updateable");
+        assert_eq!(modified.max_concurrent_queries, Some(4));
+        // previously set quotas are retained
+        assert_eq!(modified.max_write_bytes_per_second, Some(1024 * 1024));
+        assert_eq!(modified.max_write_lines_per_second, Some(10_000));
+
+        let modified = repos
+            .namespaces()
+            .update_write_bytes_limit(namespace_name.as_str(), None)
+            .await
+            .expect("namespace should be updateable");
+        assert_eq!(modified.max_write_bytes_per_second, None);
+
+        let err = repos
+            .namespaces()
+            .update_query_concurrency_limit("does_not_exist", Some(1))
+            .await
+            .expect_err("namespace should not exist");
+        assert!(matches!(err, Error::NamespaceNotFoundByName { .. }));
+
         const NEW_RETENTION_PERIOD_NS: i64 = 5 * 60 * 60 * 1000 * 1000 * 1000;
         let modified = repos
             .namespaces()
@@ -1242,6 +1444,42 @@ pub(crate) mod test_helpers {
         expected.sort_by_key(|t| t.id);
         assert_eq!(&list, &expected);
 
+        // test updating the table retention period override
+        let table = repos
+            .tables()
+            .get_by_namespace_and_name(namespace.id, "test_table")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(table.retention_period_ns, None);
+        let updated = repos
+            .tables()
+            .update_retention_period(table.id, Some(42))
+            .await
+            .expect("table should be updateable");
+        assert_eq!(updated.retention_period_ns, Some(42));
+        assert_eq!(
+            repos
+                .tables()
+                .get_by_id(table.id)
+                .await
+                .unwrap()
+                .unwrap()
+                .retention_period_ns,
+            Some(42)
+        );
+        let updated = repos
+            .tables()
+            .update_retention_period(table.id, None)
+            .await
+            .expect("table should be updateable");
+        assert_eq!(updated.retention_period_ns, None);
+        let err = repos
+            .tables()
+            .update_retention_period(TableId::new(i64::MAX), Some(42))
+            .await;
+        assert_error!(err, Error::TableNotFound { id } if id == TableId::new(i64::MAX));
+
         // test per-namespace table limits
         let latest = repos
             .namespaces()
@@ -2022,6 +2260,65 @@ pub(crate) mod test_helpers {
             .expect("delete namespace should succeed");
     }
 
+    async fn test_partition_create_or_get_many(catalog: Arc<dyn Catalog>) {
+        let mut repos = catalog.repositories().await;
+        let namespace = arbitrary_namespace(&mut *repos, "namespace_partition_many_test").await;
+        let table = arbitrary_table(&mut *repos, "test_table", &namespace).await;
+        let other_table = arbitrary_table(&mut *repos, "other_table", &namespace).await;
+
+        let existing = repos
+            .partitions()
+            .create_or_get("2023-01-01".into(), table.id)
+            .await
+            .expect("failed to create partition");
+
+        // An empty batch is a no-op.
+        let got = repos
+            .partitions()
+            .create_or_get_many(&[])
+            .await
+            .expect("failed to create partitions");
+        assert!(got.is_empty());
+
+        // Existing partitions are returned, new partitions are created across
+        // tables, and duplicate pairs are ignored.
+        let want = [
+            (table.id, "2023-01-01"),
+            (table.id, "2023-01-02"),
+            (table.id, "2023-01-03"),
+            (other_table.id, "2023-01-02"),
+        ]
+        .map(|(table_id, key)| (table_id, PartitionKey::from(key)));
+        let mut got = repos
+            .partitions()
+            .create_or_get_many(&[
+                want[1].clone(),
+                want[3].clone(),
+                want[0].clone(),
+                want[2].clone(),
+                want[1].clone(),
+            ])
+            .await
+            .expect("failed to create partitions");
+        got.sort_by(|a, b| (a.table_id, &a.partition_key).cmp(&(b.table_id, &b.partition_key)));
+
+        assert_eq!(got.len(), 4);
+        assert_eq!(got[0], existing);
+        for (p, (table_id, key)) in got.iter().zip(&want) {
+            assert_eq!(p.table_id, *table_id);
+            assert_eq!(&p.partition_key, key);
+            assert_eq!(p.hash_id(), Some(&PartitionHashId::new(*table_id, key)));
+        }
+
+        // The created partitions are visible to subsequent lookups.
+        let listed = repos
+            .partitions()
+            .list_by_table_id(table.id)
+            .await
+            .expect("failed to list partitions");
+        assert_eq!(listed.len(), 3);
+    }
+
     /// tests many interactions with the catalog and parquet files. See the individual conditions
     /// herein
     async fn test_parquet_file(catalog: Arc<dyn Catalog>) {
@@ -2157,6 +2454,23 @@ pub(crate) mod test_helpers {
             .unwrap();
         assert_eq!(files, vec![other_file.clone()]);
 
+        // test list_by_table_created_after
+        let files = repos
+            .parquet_files()
+            .list_by_table_created_after(
+                other_table.id,
+                Timestamp::new(other_file.created_at.get() - 1),
+            )
+            .await
+            .unwrap();
+        assert_eq!(files, vec![other_file.clone()]);
+        let files = repos
+            .parquet_files()
+            .list_by_table_created_after(other_table.id, other_file.created_at)
+            .await
+            .unwrap();
+        assert_eq!(files, vec![]);
+
         // test list_all
         let files = repos.parquet_files().list_all().await.unwrap();
         assert_eq!(vec![other_file.clone()], files);
@@ -2378,6 +2692,32 @@ pub(crate) mod test_helpers {
             .unwrap();
         assert_eq!(ids.len(), 0); // none left
 
+        // a table retention period overrides the namespace retention period: files within the
+        // namespace retention period are flagged once the table has a shorter retention period
+        repos
+            .tables()
+            .update_retention_period(f5.table_id, Some(30 * 60 * 1_000_000_000)) // 30 minutes
+            .await
+            .unwrap();
+        let ids = repos
+            .parquet_files()
+            .flag_for_delete_by_retention()
+            .await
+            .unwrap();
+        assert!(ids.contains(&f5.id));
+        let f5 = repos
+            .parquet_files()
+            .get_by_object_store_id(f5.object_store_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_matches!(f5.to_delete, Some(_)); // f5 is > 30min old
+        repos
+            .tables()
+            .update_retention_period(f5.table_id, None)
+            .await
+            .unwrap();
+
         // test create_update_delete
         let f6_params = ParquetFileParams {
             object_store_id: Uuid::new_v4(),
@@ -3253,6 +3593,422 @@ pub(crate) mod test_helpers {
         (namespace, schema)
     }
 
+    async fn test_rollup_rules(catalog: Arc<dyn Catalog>) {
+        let mut repos = catalog.repositories().await;
+        let namespace_1 = arbitrary_namespace(&mut *repos, "namespace_rollup_test_1").await;
+        let namespace_2 = arbitrary_namespace(&mut *repos, "namespace_rollup_test_2").await;
+
+        let params = RollupRuleParams {
+            namespace_id: namespace_1.id,
+            name: "cpu_1h".to_string(),
+            source_table: "cpu".to_string(),
+            target_table: "cpu_1h".to_string(),
+            language: RollupQueryLanguage::Sql,
+            aggregates: "avg(usage) AS usage".to_string(),
+            interval_ns: Duration::from_secs(60 * 60).as_nanos() as i64,
+            raw_retention_period_ns: None,
+        };
+
+        let rule_1 = repos.rollup_rules().create(params.clone()).await.unwrap();
+        assert!(rule_1.id > RollupRuleId::new(0));
+        assert_eq!(rule_1.namespace_id, namespace_1.id);
+        assert_eq!(rule_1.name, "cpu_1h");
+        assert_eq!(rule_1.source_table, "cpu");
+        assert_eq!(rule_1.target_table, "cpu_1h");
+        assert_eq!(rule_1.language, RollupQueryLanguage::Sql);
+        assert_eq!(rule_1.aggregates, "avg(usage) AS usage");
+        assert_eq!(rule_1.interval_ns, params.interval_ns);
+        assert_eq!(rule_1.covered_until, None);
+        assert_eq!(rule_1.last_error, None);
+
+        // rule names are unique per namespace
+        let err = repos.rollup_rules().create(params.clone()).await;
+        assert_error!(
+            err,
+            Error::RollupRuleNameExists { ref name, namespace_id }
+                if name == "cpu_1h" && namespace_id == namespace_1.id
+        );
+
+        // ... but can be reused in another namespace
+        let rule_2 = repos
+            .rollup_rules()
+            .create(RollupRuleParams {
+                namespace_id: namespace_2.id,
+                language: RollupQueryLanguage::InfluxQl,
+                aggregates: "mean(usage) AS usage".to_string(),
+                ..params.clone()
+            })
+            .await
+            .unwrap();
+        assert_ne!(rule_1.id, rule_2.id);
+        assert_eq!(rule_2.language, RollupQueryLanguage::InfluxQl);
+
+        // rules can't be created in a namespace that doesn't exist
+        let err = repos
+            .rollup_rules()
+            .create(RollupRuleParams {
+                namespace_id: NamespaceId::new(i64::MAX),
+                ..params.clone()
+            })
+            .await;
+        assert_error!(err, Error::NamespaceNotFoundById { .. });
+
+        // lookups
+        assert_eq!(
+            repos
+                .rollup_rules()
+                .get_by_namespace_and_name(namespace_1.id, "cpu_1h")
+                .await
+                .unwrap(),
+            Some(rule_1.clone())
+        );
+        assert!(repos
+            .rollup_rules()
+            .get_by_namespace_and_name(namespace_1.id, "does_not_exist")
+            .await
+            .unwrap()
+            .is_none());
+        assert_eq!(
+            repos
+                .rollup_rules()
+                .list_by_namespace_id(namespace_1.id)
+                .await
+                .unwrap(),
+            vec![rule_1.clone()]
+        );
+        let mut all = repos.rollup_rules().list().await.unwrap();
+        all.sort_by_key(|r| r.id);
+        assert_eq!(all, vec![rule_1.clone(), rule_2.clone()]);
+
+        // advance the watermark
+        let covered_until = Timestamp::new(params.interval_ns * 3);
+        let updated = repos
+            .rollup_rules()
+            .update_covered_until(rule_1.id, covered_until)
+            .await
+            .unwrap();
+        assert_eq!(updated.covered_until, Some(covered_until));
+        assert_eq!(
+            repos
+                .rollup_rules()
+                .get_by_namespace_and_name(namespace_1.id, "cpu_1h")
+                .await
+                .unwrap()
+                .unwrap()
+                .covered_until,
+            Some(covered_until)
+        );
+        let err = repos
+            .rollup_rules()
+            .update_covered_until(RollupRuleId::new(i64::MAX), covered_until)
+            .await;
+        assert_error!(err, Error::RollupRuleNotFound { .. });
+
+        // record the checked files
+        let files_checked_until = Timestamp::new(42);
+        let updated = repos
+            .rollup_rules()
+            .update_files_checked_until(rule_1.id, files_checked_until)
+            .await
+            .unwrap();
+        assert_eq!(updated.files_checked_until, Some(files_checked_until));
+        assert_eq!(updated.covered_until, Some(covered_until));
+        let err = repos
+            .rollup_rules()
+            .update_files_checked_until(RollupRuleId::new(i64::MAX), files_checked_until)
+            .await;
+        assert_error!(err, Error::RollupRuleNotFound { .. });
+
+        // record and clear the error of the last run
+        let updated = repos
+            .rollup_rules()
+            .update_last_error(rule_1.id, Some("write failed".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(updated.last_error.as_deref(), Some("write failed"));
+        assert_eq!(updated.covered_until, Some(covered_until));
+        let updated = repos
+            .rollup_rules()
+            .update_last_error(rule_1.id, None)
+            .await
+            .unwrap();
+        assert_eq!(updated.last_error, None);
+        let err = repos
+            .rollup_rules()
+            .update_last_error(RollupRuleId::new(i64::MAX), None)
+            .await;
+        assert_error!(err, Error::RollupRuleNotFound { .. });
+
+        // delete
+        repos.rollup_rules().delete(rule_1.id).await.unwrap();
+        assert!(repos
+            .rollup_rules()
+            .list_by_namespace_id(namespace_1.id)
+            .await
+            .unwrap()
+            .is_empty());
+        let err = repos.rollup_rules().delete(rule_1.id).await;
+        assert_error!(err, Error::RollupRuleNotFound { .. });
+        assert_eq!(repos.rollup_rules().list().await.unwrap(), vec![rule_2]);
+    }
+
+    async fn test_tiered_retention(catalog: Arc<dyn Catalog>) {
+        const MINUTE: i64 = 60 * 1_000_000_000;
+
+        let mut repos = catalog.repositories().await;
+        let namespace = arbitrary_namespace(&mut *repos, "namespace_tiered_retention").await;
+        repos
+            .namespaces()
+            .update_retention_period(&namespace.name, Some(60 * MINUTE)) // 1 hour
+            .await
+            .unwrap();
+        let raw = arbitrary_table(&mut *repos, "raw", &namespace).await;
+        let other = arbitrary_table(&mut *repos, "other", &namespace).await;
+        let raw_partition = repos
+            .partitions()
+            .create_or_get("one".into(), raw.id)
+            .await
+            .unwrap();
+        let other_partition = repos
+            .partitions()
+            .create_or_get("one".into(), other.id)
+            .await
+            .unwrap();
+
+        let now = catalog.time_provider().now().timestamp_nanos();
+        let file_params =
+            |table: &Table, partition: &Partition, minutes_ago: i64| ParquetFileParams {
+                max_time: Timestamp::new(now - minutes_ago * MINUTE),
+                ..arbitrary_parquet_file_params(&namespace, table, partition)
+            };
+        let old_params = file_params(&raw, &raw_partition, 180);
+        let mid_params = file_params(&raw, &raw_partition, 150);
+        let other_params = file_params(&other, &other_partition, 90);
+        let old = repos.parquet_files().create(old_params).await.unwrap();
+        let mid = repos.parquet_files().create(mid_params).await.unwrap();
+        let other_file = repos.parquet_files().create(other_params).await.unwrap();
+
+        let rule = repos
+            .rollup_rules()
+            .create(RollupRuleParams {
+                namespace_id: namespace.id,
+                name: "raw_1m".to_string(),
+                source_table: "raw".to_string(),
+                target_table: "raw_1m".to_string(),
+                language: RollupQueryLanguage::Sql,
+                aggregates: "avg(value) AS value".to_string(),
+                interval_ns: MINUTE,
+                raw_retention_period_ns: Some(120 * MINUTE), // 2 hours
+            })
+            .await
+            .unwrap();
+
+        // nothing of the raw table has been rolled up yet, so only the file of the other table
+        // is flagged by the namespace retention period
+        let ids = repos
+            .parquet_files()
+            .flag_for_delete_by_retention()
+            .await
+            .unwrap();
+        assert_eq!(ids, vec![other_file.id]);
+
+        // once rolled up, raw data older than the raw retention period is flagged
+        repos
+            .rollup_rules()
+            .update_covered_until(rule.id, Timestamp::new(now - 160 * MINUTE))
+            .await
+            .unwrap();
+        let ids = repos
+            .parquet_files()
+            .flag_for_delete_by_retention()
+            .await
+            .unwrap();
+        assert_eq!(ids, vec![old.id]);
+
+        // the middle file is older than the raw retention period but has not been rolled up
+        let ids = repos
+            .parquet_files()
+            .flag_for_delete_by_retention()
+            .await
+            .unwrap();
+        assert!(ids.is_empty());
+        let mid = repos
+            .parquet_files()
+            .get_by_object_store_id(mid.object_store_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_matches!(mid.to_delete, None);
+
+        // without a raw retention period, the rule does not affect retention
+        repos.rollup_rules().delete(rule.id).await.unwrap();
+        let ids = repos
+            .parquet_files()
+            .flag_for_delete_by_retention()
+            .await
+            .unwrap();
+        assert_eq!(ids, vec![mid.id]);
+    }
+
+    async fn test_storage_usage(catalog: Arc<dyn Catalog>) {
+        let mut repos = catalog.repositories().await;
+        let namespace = arbitrary_namespace(&mut *repos, "namespace_storage_usage").await;
+        let other_namespace = arbitrary_namespace(&mut *repos, "other_storage_usage").await;
+        let table = arbitrary_table(&mut *repos, "table", &namespace).await;
+        let other_table = arbitrary_table(&mut *repos, "table", &other_namespace).await;
+        let partition = repos
+            .partitions()
+            .create_or_get("one".into(), table.id)
+            .await
+            .unwrap();
+        let other_partition = repos
+            .partitions()
+            .create_or_get("one".into(), other_table.id)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            repos
+                .parquet_files()
+                .storage_usage_by_namespace(namespace.id)
+                .await
+                .unwrap(),
+            vec![]
+        );
+
+        let params = ParquetFileParams {
+            file_size_bytes: 100,
+            row_count: 10,
+            ..arbitrary_parquet_file_params(&namespace, &table, &partition)
+        };
+        let f1 = repos.parquet_files().create(params.clone()).await.unwrap();
+        let f2 = repos
+            .parquet_files()
+            .create(ParquetFileParams {
+                object_store_id: Uuid::new_v4(),
+                ..params.clone()
+            })
+            .await
+            .unwrap();
+        let f3 = repos
+            .parquet_files()
+            .create(ParquetFileParams {
+                object_store_id: Uuid::new_v4(),
+                file_size_bytes: 1_000,
+                row_count: 1,
+                ..params.clone()
+            })
+            .await
+            .unwrap();
+        repos
+            .parquet_files()
+            .create(ParquetFileParams {
+                file_size_bytes: 7,
+                row_count: 7,
+                ..arbitrary_parquet_file_params(&other_namespace, &other_table, &other_partition)
+            })
+            .await
+            .unwrap();
+
+        let usage = |compaction_level, file_count, file_size_bytes, row_count| StorageUsage {
+            namespace_id: namespace.id,
+            table_id: table.id,
+            compaction_level,
+            file_count,
+            file_size_bytes,
+            row_count,
+        };
+        let other_usage = StorageUsage {
+            namespace_id: other_namespace.id,
+            table_id: other_table.id,
+            compaction_level: CompactionLevel::Initial,
+            file_count: 1,
+            file_size_bytes: 7,
+            row_count: 7,
+        };
+
+        assert_eq!(
+            repos
+                .parquet_files()
+                .storage_usage_by_namespace(namespace.id)
+                .await
+                .unwrap(),
+            vec![usage(CompactionLevel::Initial, 3, 1_200, 21)]
+        );
+
+        // Folding the changes does not change the usage.
+        repos.parquet_files().fold_storage_usage().await.unwrap();
+        assert_eq!(
+            repos
+                .parquet_files()
+                .storage_usage_by_namespace(namespace.id)
+                .await
+                .unwrap(),
+            vec![usage(CompactionLevel::Initial, 3, 1_200, 21)]
+        );
+
+        // Compact f1 and f2 into a single L1 file and upgrade f3: the deleted files are no longer
+        // accounted for and the upgraded one moves to its new level.
+        repos
+            .parquet_files()
+            .create_upgrade_delete(
+                &[f1.id, f2.id],
+                &[f3.id],
+                &[ParquetFileParams {
+                    object_store_id: Uuid::new_v4(),
+                    file_size_bytes: 150,
+                    row_count: 20,
+                    compaction_level: CompactionLevel::FileNonOverlapped,
+                    ..params.clone()
+                }],
+                CompactionLevel::FileNonOverlapped,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            repos
+                .parquet_files()
+                .storage_usage_by_namespace(namespace.id)
+                .await
+                .unwrap(),
+            vec![usage(CompactionLevel::FileNonOverlapped, 2, 1_150, 21)]
+        );
+        assert_eq!(
+            repos.parquet_files().storage_usage().await.unwrap(),
+            vec![
+                usage(CompactionLevel::FileNonOverlapped, 2, 1_150, 21),
+                other_usage,
+            ]
+        );
+        repos.parquet_files().fold_storage_usage().await.unwrap();
+        assert_eq!(
+            repos.parquet_files().storage_usage().await.unwrap(),
+            vec![
+                usage(CompactionLevel::FileNonOverlapped, 2, 1_150, 21),
+                other_usage,
+            ]
+        );
+
+        // Removing the files marked for deletion does not change the usage.
+        let older_than = Timestamp::new(
+            (catalog.time_provider().now() + Duration::from_secs(100)).timestamp_nanos(),
+        );
+        repos
+            .parquet_files()
+            .delete_old_ids_only(older_than)
+            .await
+            .unwrap();
+        assert_eq!(
+            repos
+                .parquet_files()
+                .storage_usage_by_namespace(namespace.id)
+                .await
+                .unwrap(),
+            vec![usage(CompactionLevel::FileNonOverlapped, 2, 1_150, 21)]
+        );
+    }
+
     async fn test_list_schemas(catalog: Arc<dyn Catalog>) {
         let mut repos = catalog.repositories().await;
 
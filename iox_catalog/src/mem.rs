@@ -5,8 +5,8 @@ use crate::interface::{verify_sort_key_length, MAX_PARQUET_FILES_SELECTED_ONCE_F
 use crate::{
     interface::{
         CasFailure, Catalog, ColumnRepo, ColumnTypeMismatchSnafu, Error, NamespaceRepo,
-        ParquetFileRepo, PartitionRepo, RepoCollection, Result, SoftDeletedRows, TableRepo,
-        MAX_PARQUET_FILES_SELECTED_ONCE_FOR_RETENTION,
+        ParquetFileRepo, PartitionRepo, RepoCollection, Result, RollupRuleRepo, SoftDeletedRows,
+        TableRepo, MAX_PARQUET_FILES_SELECTED_ONCE_FOR_RETENTION,
     },
     metrics::MetricDecorator,
     DEFAULT_MAX_COLUMNS_PER_TABLE, DEFAULT_MAX_TABLES,
@@ -19,14 +19,15 @@ use data_types::{
     },
     Column, ColumnId, ColumnType, CompactionLevel, Namespace, NamespaceId, NamespaceName,
     NamespaceServiceProtectionLimitsOverride, ParquetFile, ParquetFileId, ParquetFileParams,
-    Partition, PartitionHashId, PartitionId, PartitionKey, SkippedCompaction, Table, TableId,
-    Timestamp, TransitionPartitionId,
+    Partition, PartitionHashId, PartitionId, PartitionKey, RollupRule, RollupRuleId,
+    RollupRuleParams, SkippedCompaction, StorageUsage, Table, TableId, Timestamp,
+    TransitionPartitionId,
 };
 use iox_time::{SystemProvider, TimeProvider};
 use snafu::ensure;
 use sqlx::types::Uuid;
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeSet, HashMap, HashSet},
     fmt::{Display, Formatter},
     sync::Arc,
 };
@@ -72,6 +73,7 @@ struct MemCollections {
     partitions: Vec<Partition>,
     skipped_compactions: Vec<SkippedCompaction>,
     parquet_files: Vec<ParquetFile>,
+    rollup_rules: Vec<RollupRule>,
 }
 
 /// transaction bound to an in-memory catalog.
@@ -141,6 +143,10 @@ impl RepoCollection for MemTxn {
     fn parquet_files(&mut self) -> &mut dyn ParquetFileRepo {
         self
     }
+
+    fn rollup_rules(&mut self) -> &mut dyn RollupRuleRepo {
+        self
+    }
 }
 
 #[async_trait]
@@ -162,12 +168,21 @@ impl NamespaceRepo for MemTxn {
 
         let max_tables = service_protection_limits.and_then(|l| l.max_tables);
         let max_columns_per_table = service_protection_limits.and_then(|l| l.max_columns_per_table);
+        let max_write_bytes_per_second =
+            service_protection_limits.and_then(|l| l.max_write_bytes_per_second);
+        let max_write_lines_per_second =
+            service_protection_limits.and_then(|l| l.max_write_lines_per_second);
+        let max_concurrent_queries =
+            service_protection_limits.and_then(|l| l.max_concurrent_queries);
 
         let namespace = Namespace {
             id: NamespaceId::new(stage.namespaces.len() as i64 + 1),
             name: name.to_string(),
             max_tables: max_tables.unwrap_or(DEFAULT_MAX_TABLES),
             max_columns_per_table: max_columns_per_table.unwrap_or(DEFAULT_MAX_COLUMNS_PER_TABLE),
+            max_write_bytes_per_second,
+            max_write_lines_per_second,
+            max_concurrent_queries,
             retention_period_ns,
             deleted_at: None,
             partition_template: partition_template.unwrap_or_default(),
@@ -251,6 +266,57 @@ impl NamespaceRepo for MemTxn {
         }
     }
 
+    async fn update_write_bytes_limit(
+        &mut self,
+        name: &str,
+        new_max: Option<i64>,
+    ) -> Result<Namespace> {
+        let stage = self.stage();
+        match stage.namespaces.iter_mut().find(|n| n.name == name) {
+            Some(n) => {
+                n.max_write_bytes_per_second = new_max;
+                Ok(n.clone())
+            }
+            None => Err(Error::NamespaceNotFoundByName {
+                name: name.to_string(),
+            }),
+        }
+    }
+
+    async fn update_write_lines_limit(
+        &mut self,
+        name: &str,
+        new_max: Option<i64>,
+    ) -> Result<Namespace> {
+        let stage = self.stage();
+        match stage.namespaces.iter_mut().find(|n| n.name == name) {
+            Some(n) => {
+                n.max_write_lines_per_second = new_max;
+                Ok(n.clone())
+            }
+            None => Err(Error::NamespaceNotFoundByName {
+                name: name.to_string(),
+            }),
+        }
+    }
+
+    async fn update_query_concurrency_limit(
+        &mut self,
+        name: &str,
+        new_max: Option<i32>,
+    ) -> Result<Namespace> {
+        let stage = self.stage();
+        match stage.namespaces.iter_mut().find(|n| n.name == name) {
+            Some(n) => {
+                n.max_concurrent_queries = new_max;
+                Ok(n.clone())
+            }
+            None => Err(Error::NamespaceNotFoundByName {
+                name: name.to_string(),
+            }),
+        }
+    }
+
     async fn update_retention_period(
         &mut self,
         name: &str,
@@ -325,6 +391,7 @@ impl TableRepo for MemTxn {
                         namespace_id,
                         name: name.to_string(),
                         partition_template,
+                        retention_period_ns: None,
                     };
                     stage.tables.push(table);
                     stage.tables.last().unwrap()
@@ -385,6 +452,21 @@ impl TableRepo for MemTxn {
         let stage = self.stage();
         Ok(stage.tables.clone())
     }
+
+    async fn update_retention_period(
+        &mut self,
+        table_id: TableId,
+        retention_period_ns: Option<i64>,
+    ) -> Result<Table> {
+        let stage = self.stage();
+        match stage.tables.iter_mut().find(|t| t.id == table_id) {
+            Some(t) => {
+                t.retention_period_ns = retention_period_ns;
+                Ok(t.clone())
+            }
+            None => Err(Error::TableNotFound { id: table_id }),
+        }
+    }
 }
 
 #[async_trait]
@@ -578,6 +660,20 @@ impl PartitionRepo for MemTxn {
         Ok(partition.clone())
     }
 
+    async fn create_or_get_many(
+        &mut self,
+        partitions: &[(TableId, PartitionKey)],
+    ) -> Result<Vec<Partition>> {
+        let partitions = partitions.iter().collect::<BTreeSet<_>>();
+
+        let mut out = Vec::with_capacity(partitions.len());
+        for (table_id, key) in partitions {
+            out.push(PartitionRepo::create_or_get(self, key.clone(), *table_id).await?);
+        }
+
+        Ok(out)
+    }
+
     async fn get_by_id(&mut self, partition_id: PartitionId) -> Result<Option<Partition>> {
         let stage = self.stage();
 
@@ -826,27 +922,67 @@ impl ParquetFileRepo for MemTxn {
         let now = Timestamp::from(self.time_provider.now());
         let stage = self.stage();
 
+        // tiered retention of the source tables of rollup rules with a raw retention period:
+        // the longest raw retention period and the watermark every such rule has reached
+        let mut tiered_retention: HashMap<TableId, (i64, Option<Timestamp>)> = HashMap::new();
+        for rule in &stage.rollup_rules {
+            let Some(raw_retention) = rule.raw_retention_period_ns else {
+                continue;
+            };
+            let Some(table) = stage
+                .tables
+                .iter()
+                .find(|t| t.namespace_id == rule.namespace_id && t.name == rule.source_table)
+            else {
+                continue;
+            };
+            tiered_retention
+                .entry(table.id)
+                .and_modify(|(retention, covered_until)| {
+                    *retention = (*retention).max(raw_retention);
+                    *covered_until = (*covered_until).min(rule.covered_until);
+                })
+                .or_insert((raw_retention, rule.covered_until));
+        }
+
         Ok(stage
             .parquet_files
             .iter_mut()
             // don't flag if already flagged for deletion
             .filter(|f| f.to_delete.is_none())
             .filter_map(|f| {
+                // raw data is only deleted once rolled up
+                if let Some((retention, covered_until)) = tiered_retention.get(&f.table_id) {
+                    let covered = covered_until.map_or(false, |c| f.max_time < c);
+                    return if covered && f.max_time < now - *retention {
+                        f.to_delete = Some(now);
+                        Some(f.id)
+                    } else {
+                        None
+                    };
+                }
+
                 // table retention, if it exists, overrides namespace retention
-                // TODO - include check of table retention period once implemented
-                stage
-                    .namespaces
+                let table_retention = stage
+                    .tables
                     .iter()
-                    .find(|n| n.id == f.namespace_id)
-                    .and_then(|ns| {
-                        ns.retention_period_ns.and_then(|rp| {
-                            if f.max_time < now - rp {
-                                f.to_delete = Some(now);
-                                Some(f.id)
-                            } else {
-                                None
-                            }
-                        })
+                    .find(|t| t.id == f.table_id)
+                    .and_then(|t| t.retention_period_ns);
+                table_retention
+                    .or_else(|| {
+                        stage
+                            .namespaces
+                            .iter()
+                            .find(|n| n.id == f.namespace_id)
+                            .and_then(|ns| ns.retention_period_ns)
+                    })
+                    .and_then(|rp| {
+                        if f.max_time < now - rp {
+                            f.to_delete = Some(now);
+                            Some(f.id)
+                        } else {
+                            None
+                        }
                     })
             })
             .take(MAX_PARQUET_FILES_SELECTED_ONCE_FOR_RETENTION as usize)
@@ -885,6 +1021,24 @@ impl ParquetFileRepo for MemTxn {
         Ok(parquet_files)
     }
 
+    async fn list_by_table_created_after(
+        &mut self,
+        table_id: TableId,
+        created_after: Timestamp,
+    ) -> Result<Vec<ParquetFile>> {
+        let stage = self.stage();
+
+        let parquet_files: Vec<_> = stage
+            .parquet_files
+            .iter()
+            .filter(|f| {
+                table_id == f.table_id && f.to_delete.is_none() && f.created_at > created_after
+            })
+            .cloned()
+            .collect();
+        Ok(parquet_files)
+    }
+
     async fn delete_old_ids_only(&mut self, older_than: Timestamp) -> Result<Vec<ParquetFileId>> {
         let stage = self.stage();
 
@@ -997,6 +1151,154 @@ impl ParquetFileRepo for MemTxn {
 
         Ok(ids)
     }
+
+    async fn storage_usage_by_namespace(
+        &mut self,
+        namespace_id: NamespaceId,
+    ) -> Result<Vec<StorageUsage>> {
+        let stage = self.stage();
+
+        Ok(StorageUsage::from_parquet_files(
+            stage
+                .parquet_files
+                .iter()
+                .filter(|f| f.namespace_id == namespace_id),
+        ))
+    }
+
+    async fn storage_usage(&mut self) -> Result<Vec<StorageUsage>> {
+        let stage = self.stage();
+
+        Ok(StorageUsage::from_parquet_files(&stage.parquet_files))
+    }
+
+    async fn fold_storage_usage(&mut self) -> Result<usize> {
+        // The usage is computed from the parquet files when read.
+        Ok(0)
+    }
+}
+
+#[async_trait]
+impl RollupRuleRepo for MemTxn {
+    async fn create(&mut self, rollup_rule_params: RollupRuleParams) -> Result<RollupRule> {
+        let stage = self.stage();
+
+        if !stage
+            .namespaces
+            .iter()
+            .any(|n| n.id == rollup_rule_params.namespace_id)
+        {
+            return Err(Error::NamespaceNotFoundById {
+                id: rollup_rule_params.namespace_id,
+            });
+        }
+
+        if stage.rollup_rules.iter().any(|r| {
+            r.namespace_id == rollup_rule_params.namespace_id && r.name == rollup_rule_params.name
+        }) {
+            return Err(Error::RollupRuleNameExists {
+                name: rollup_rule_params.name,
+                namespace_id: rollup_rule_params.namespace_id,
+            });
+        }
+
+        let id = stage
+            .rollup_rules
+            .iter()
+            .map(|r| r.id.get())
+            .max()
+            .unwrap_or_default()
+            + 1;
+        let rule = RollupRule::from_params(rollup_rule_params, RollupRuleId::new(id));
+        stage.rollup_rules.push(rule.clone());
+
+        Ok(rule)
+    }
+
+    async fn get_by_namespace_and_name(
+        &mut self,
+        namespace_id: NamespaceId,
+        name: &str,
+    ) -> Result<Option<RollupRule>> {
+        let stage = self.stage();
+
+        Ok(stage
+            .rollup_rules
+            .iter()
+            .find(|r| r.namespace_id == namespace_id && r.name == name)
+            .cloned())
+    }
+
+    async fn list_by_namespace_id(&mut self, namespace_id: NamespaceId) -> Result<Vec<RollupRule>> {
+        let stage = self.stage();
+
+        Ok(stage
+            .rollup_rules
+            .iter()
+            .filter(|r| r.namespace_id == namespace_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn list(&mut self) -> Result<Vec<RollupRule>> {
+        let stage = self.stage();
+        Ok(stage.rollup_rules.clone())
+    }
+
+    async fn update_covered_until(
+        &mut self,
+        id: RollupRuleId,
+        covered_until: Timestamp,
+    ) -> Result<RollupRule> {
+        let stage = self.stage();
+        match stage.rollup_rules.iter_mut().find(|r| r.id == id) {
+            Some(r) => {
+                r.covered_until = Some(covered_until);
+                Ok(r.clone())
+            }
+            None => Err(Error::RollupRuleNotFound { id }),
+        }
+    }
+
+    async fn update_files_checked_until(
+        &mut self,
+        id: RollupRuleId,
+        files_checked_until: Timestamp,
+    ) -> Result<RollupRule> {
+        let stage = self.stage();
+        match stage.rollup_rules.iter_mut().find(|r| r.id == id) {
+            Some(r) => {
+                r.files_checked_until = Some(files_checked_until);
+                Ok(r.clone())
+            }
+            None => Err(Error::RollupRuleNotFound { id }),
+        }
+    }
+
+    async fn update_last_error(
+        &mut self,
+        id: RollupRuleId,
+        last_error: Option<String>,
+    ) -> Result<RollupRule> {
+        let stage = self.stage();
+        match stage.rollup_rules.iter_mut().find(|r| r.id == id) {
+            Some(r) => {
+                r.last_error = last_error;
+                Ok(r.clone())
+            }
+            None => Err(Error::RollupRuleNotFound { id }),
+        }
+    }
+
+    async fn delete(&mut self, id: RollupRuleId) -> Result<()> {
+        let stage = self.stage();
+        let len = stage.rollup_rules.len();
+        stage.rollup_rules.retain(|r| r.id != id);
+        if stage.rollup_rules.len() == len {
+            return Err(Error::RollupRuleNotFound { id });
+        }
+        Ok(())
+    }
 }
 
 fn filter_namespace_soft_delete<'a>(
@@ -2,15 +2,16 @@
 
 use crate::interface::{
     CasFailure, ColumnRepo, NamespaceRepo, ParquetFileRepo, PartitionRepo, RepoCollection, Result,
-    SoftDeletedRows, TableRepo,
+    RollupRuleRepo, SoftDeletedRows, TableRepo,
 };
 use async_trait::async_trait;
 use data_types::{
     partition_template::{NamespacePartitionTemplateOverride, TablePartitionTemplateOverride},
     Column, ColumnType, CompactionLevel, Namespace, NamespaceId, NamespaceName,
     NamespaceServiceProtectionLimitsOverride, ParquetFile, ParquetFileId, ParquetFileParams,
-    Partition, PartitionHashId, PartitionId, PartitionKey, SkippedCompaction, SortedColumnSet,
-    Table, TableId, Timestamp, TransitionPartitionId,
+    Partition, PartitionHashId, PartitionId, PartitionKey, RollupRule, RollupRuleId,
+    RollupRuleParams, SkippedCompaction, SortedColumnSet, StorageUsage, Table, TableId, Timestamp,
+    TransitionPartitionId,
 };
 use iox_time::{SystemProvider, TimeProvider};
 use metric::{DurationHistogram, Metric};
@@ -43,7 +44,13 @@ impl<T> MetricDecorator<T> {
 
 impl<T, P> RepoCollection for MetricDecorator<T, P>
 where
-    T: NamespaceRepo + TableRepo + ColumnRepo + PartitionRepo + ParquetFileRepo + Debug,
+    T: NamespaceRepo
+        + TableRepo
+        + ColumnRepo
+        + PartitionRepo
+        + ParquetFileRepo
+        + RollupRuleRepo
+        + Debug,
     P: TimeProvider,
 {
     fn namespaces(&mut self) -> &mut dyn NamespaceRepo {
@@ -65,6 +72,10 @@ where
     fn parquet_files(&mut self) -> &mut dyn ParquetFileRepo {
         self
     }
+
+    fn rollup_rules(&mut self) -> &mut dyn RollupRuleRepo {
+        self
+    }
 }
 
 /// Emit a trait impl for `impl_trait` that delegates calls to the inner
@@ -141,6 +152,9 @@ decorate!(
         "namespace_soft_delete" = soft_delete(&mut self, name: &str) -> Result<()>;
         "namespace_update_table_limit" = update_table_limit(&mut self, name: &str, new_max: i32) -> Result<Namespace>;
         "namespace_update_column_limit" = update_column_limit(&mut self, name: &str, new_max: i32) -> Result<Namespace>;
+        "namespace_update_write_bytes_limit" = update_write_bytes_limit(&mut self, name: &str, new_max: Option<i64>) -> Result<Namespace>;
+        "namespace_update_write_lines_limit" = update_write_lines_limit(&mut self, name: &str, new_max: Option<i64>) -> Result<Namespace>;
+        "namespace_update_query_concurrency_limit" = update_query_concurrency_limit(&mut self, name: &str, new_max: Option<i32>) -> Result<Namespace>;
     ]
 );
 
@@ -152,6 +166,7 @@ decorate!(
         "table_get_by_namespace_and_name" = get_by_namespace_and_name(&mut self, namespace_id: NamespaceId, name: &str) -> Result<Option<Table>>;
         "table_list_by_namespace_id" = list_by_namespace_id(&mut self, namespace_id: NamespaceId) -> Result<Vec<Table>>;
         "table_list" = list(&mut self) -> Result<Vec<Table>>;
+        "table_update_retention_period" = update_retention_period(&mut self, table_id: TableId, retention_period_ns: Option<i64>) -> Result<Table>;
     ]
 );
 
@@ -170,6 +185,7 @@ decorate!(
     impl_trait = PartitionRepo,
     methods = [
         "partition_create_or_get" = create_or_get(&mut self, key: PartitionKey, table_id: TableId) -> Result<Partition>;
+        "partition_create_or_get_many" = create_or_get_many(&mut self, partitions: &[(TableId, PartitionKey)]) -> Result<Vec<Partition>>;
         "partition_get_by_id" = get_by_id(&mut self, partition_id: PartitionId) -> Result<Option<Partition>>;
         "partition_get_by_id_batch" = get_by_id_batch(&mut self, partition_ids: Vec<PartitionId>) -> Result<Vec<Partition>>;
         "partition_get_by_hash_id" = get_by_hash_id(&mut self, partition_hash_id: &PartitionHashId) -> Result<Option<Partition>>;
@@ -195,10 +211,28 @@ decorate!(
         "parquet_flag_for_delete_by_retention" = flag_for_delete_by_retention(&mut self) -> Result<Vec<ParquetFileId>>;
         "parquet_list_by_namespace_not_to_delete" = list_by_namespace_not_to_delete(&mut self, namespace_id: NamespaceId) -> Result<Vec<ParquetFile>>;
         "parquet_list_by_table_not_to_delete" = list_by_table_not_to_delete(&mut self, table_id: TableId) -> Result<Vec<ParquetFile>>;
+        "parquet_list_by_table_created_after" = list_by_table_created_after(&mut self, table_id: TableId, created_after: Timestamp) -> Result<Vec<ParquetFile>>;
         "parquet_delete_old_ids_only" = delete_old_ids_only(&mut self, older_than: Timestamp) -> Result<Vec<ParquetFileId>>;
         "parquet_list_by_partition_not_to_delete" = list_by_partition_not_to_delete(&mut self, partition_id: &TransitionPartitionId) -> Result<Vec<ParquetFile>>;
         "parquet_get_by_object_store_id" = get_by_object_store_id(&mut self, object_store_id: Uuid) -> Result<Option<ParquetFile>>;
         "parquet_exists_by_object_store_id_batch" = exists_by_object_store_id_batch(&mut self, object_store_ids: Vec<Uuid>) -> Result<Vec<Uuid>>;
         "parquet_create_upgrade_delete" = create_upgrade_delete(&mut self, delete: &[ParquetFileId], upgrade: &[ParquetFileId], create: &[ParquetFileParams], target_level: CompactionLevel) -> Result<Vec<ParquetFileId>>;
+        "parquet_storage_usage_by_namespace" = storage_usage_by_namespace(&mut self, namespace_id: NamespaceId) -> Result<Vec<StorageUsage>>;
+        "parquet_storage_usage" = storage_usage(&mut self) -> Result<Vec<StorageUsage>>;
+        "parquet_fold_storage_usage" = fold_storage_usage(&mut self) -> Result<usize>;
+    ]
+);
+
+decorate!(
+    impl_trait = RollupRuleRepo,
+    methods = [
+        "rollup_rule_create" = create(&mut self, rollup_rule_params: RollupRuleParams) -> Result<RollupRule>;
+        "rollup_rule_get_by_namespace_and_name" = get_by_namespace_and_name(&mut self, namespace_id: NamespaceId, name: &str) -> Result<Option<RollupRule>>;
+        "rollup_rule_list_by_namespace_id" = list_by_namespace_id(&mut self, namespace_id: NamespaceId) -> Result<Vec<RollupRule>>;
+        "rollup_rule_list" = list(&mut self) -> Result<Vec<RollupRule>>;
+        "rollup_rule_update_covered_until" = update_covered_until(&mut self, id: RollupRuleId, covered_until: Timestamp) -> Result<RollupRule>;
+        "rollup_rule_update_files_checked_until" = update_files_checked_until(&mut self, id: RollupRuleId, files_checked_until: Timestamp) -> Result<RollupRule>;
+        "rollup_rule_update_last_error" = update_last_error(&mut self, id: RollupRuleId, last_error: Option<String>) -> Result<RollupRule>;
+        "rollup_rule_delete" = delete(&mut self, id: RollupRuleId) -> Result<()>;
     ]
 );
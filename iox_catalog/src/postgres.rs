@@ -4,8 +4,8 @@ use crate::interface::{verify_sort_key_length, MAX_PARQUET_FILES_SELECTED_ONCE_F
 use crate::{
     interface::{
         self, CasFailure, Catalog, ColumnRepo, ColumnTypeMismatchSnafu, Error, NamespaceRepo,
-        ParquetFileRepo, PartitionRepo, RepoCollection, Result, SoftDeletedRows, TableRepo,
-        MAX_PARQUET_FILES_SELECTED_ONCE_FOR_RETENTION,
+        ParquetFileRepo, PartitionRepo, RepoCollection, Result, RollupRuleRepo, SoftDeletedRows,
+        TableRepo, MAX_PARQUET_FILES_SELECTED_ONCE_FOR_RETENTION,
     },
     kafkaless_transition::{
         SHARED_QUERY_POOL, SHARED_QUERY_POOL_ID, SHARED_TOPIC_ID, SHARED_TOPIC_NAME,
@@ -23,8 +23,9 @@ use data_types::{
     },
     Column, ColumnType, CompactionLevel, Namespace, NamespaceId, NamespaceName,
     NamespaceServiceProtectionLimitsOverride, ParquetFile, ParquetFileId, ParquetFileParams,
-    Partition, PartitionHashId, PartitionId, PartitionKey, SkippedCompaction, Table, TableId,
-    Timestamp, TransitionPartitionId,
+    Partition, PartitionHashId, PartitionId, PartitionKey, RollupRule, RollupRuleId,
+    RollupRuleParams, SkippedCompaction, StorageUsage, Table, TableId, Timestamp,
+    TransitionPartitionId,
 };
 use iox_time::{SystemProvider, TimeProvider};
 use metric::{Attributes, Instrument, MetricKind};
@@ -39,7 +40,7 @@ use sqlx::{
 };
 use sqlx_hotswap_pool::HotSwapPool;
 use std::borrow::Cow;
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::{collections::HashMap, fmt::Display, str::FromStr, sync::Arc, time::Duration};
 
@@ -617,6 +618,10 @@ impl RepoCollection for PostgresTxn {
     fn parquet_files(&mut self) -> &mut dyn ParquetFileRepo {
         self
     }
+
+    fn rollup_rules(&mut self) -> &mut dyn RollupRuleRepo {
+        self
+    }
 }
 
 async fn insert_column_with_connection<'q, E>(
@@ -684,15 +689,23 @@ impl NamespaceRepo for PostgresTxn {
     ) -> Result<Namespace> {
         let max_tables = service_protection_limits.and_then(|l| l.max_tables);
         let max_columns_per_table = service_protection_limits.and_then(|l| l.max_columns_per_table);
+        let max_write_bytes_per_second =
+            service_protection_limits.and_then(|l| l.max_write_bytes_per_second);
+        let max_write_lines_per_second =
+            service_protection_limits.and_then(|l| l.max_write_lines_per_second);
+        let max_concurrent_queries =
+            service_protection_limits.and_then(|l| l.max_concurrent_queries);
 
         let rec = sqlx::query_as::<_, Namespace>(
             r#"
 INSERT INTO namespace (
-    name, topic_id, query_pool_id, retention_period_ns, max_tables, max_columns_per_table, partition_template
+    name, topic_id, query_pool_id, retention_period_ns, max_tables, max_columns_per_table, partition_template,
+    max_write_bytes_per_second, max_write_lines_per_second, max_concurrent_queries
 )
-VALUES ( $1, $2, $3, $4, $5, $6, $7 )
-RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table, deleted_at,
-          partition_template;
+VALUES ( $1, $2, $3, $4, $5, $6, $7, $8, $9, $10 )
+RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table,
+          max_write_bytes_per_second, max_write_lines_per_second, max_concurrent_queries,
+          deleted_at, partition_template;
             "#,
         )
         .bind(name.as_str()) // $1
@@ -701,7 +714,10 @@ RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table, dele
         .bind(retention_period_ns) // $4
         .bind(max_tables.unwrap_or(DEFAULT_MAX_TABLES)) // $5
         .bind(max_columns_per_table.unwrap_or(DEFAULT_MAX_COLUMNS_PER_TABLE)) // $6
-        .bind(partition_template); // $7
+        .bind(partition_template) // $7
+        .bind(max_write_bytes_per_second) // $8
+        .bind(max_write_lines_per_second) // $9
+        .bind(max_concurrent_queries); // $10
 
         let rec = rec.fetch_one(&mut self.inner).await.map_err(|e| {
             if is_unique_violation(&e) {
@@ -722,8 +738,9 @@ RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table, dele
         let rec = sqlx::query_as::<_, Namespace>(
             format!(
                 r#"
-SELECT id, name, retention_period_ns, max_tables, max_columns_per_table, deleted_at,
-       partition_template
+SELECT id, name, retention_period_ns, max_tables, max_columns_per_table,
+       max_write_bytes_per_second, max_write_lines_per_second, max_concurrent_queries,
+       deleted_at, partition_template
 FROM namespace
 WHERE {v};
                 "#,
@@ -746,8 +763,9 @@ WHERE {v};
         let rec = sqlx::query_as::<_, Namespace>(
             format!(
                 r#"
-SELECT id, name, retention_period_ns, max_tables, max_columns_per_table, deleted_at,
-       partition_template
+SELECT id, name, retention_period_ns, max_tables, max_columns_per_table,
+       max_write_bytes_per_second, max_write_lines_per_second, max_concurrent_queries,
+       deleted_at, partition_template
 FROM namespace
 WHERE id=$1 AND {v};
                 "#,
@@ -776,8 +794,9 @@ WHERE id=$1 AND {v};
         let rec = sqlx::query_as::<_, Namespace>(
             format!(
                 r#"
-SELECT id, name, retention_period_ns, max_tables, max_columns_per_table, deleted_at,
-       partition_template
+SELECT id, name, retention_period_ns, max_tables, max_columns_per_table,
+       max_write_bytes_per_second, max_write_lines_per_second, max_concurrent_queries,
+       deleted_at, partition_template
 FROM namespace
 WHERE name=$1 AND {v};
                 "#,
@@ -817,8 +836,9 @@ WHERE name=$1 AND {v};
 UPDATE namespace
 SET max_tables = $1
 WHERE name = $2
-RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table, deleted_at,
-          partition_template;
+RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table,
+          max_write_bytes_per_second, max_write_lines_per_second, max_concurrent_queries,
+          deleted_at, partition_template;
         "#,
         )
         .bind(new_max)
@@ -842,8 +862,99 @@ RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table, dele
 UPDATE namespace
 SET max_columns_per_table = $1
 WHERE name = $2
-RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table, deleted_at,
-          partition_template;
+RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table,
+          max_write_bytes_per_second, max_write_lines_per_second, max_concurrent_queries,
+          deleted_at, partition_template;
+        "#,
+        )
+        .bind(new_max)
+        .bind(name)
+        .fetch_one(&mut self.inner)
+        .await;
+
+        let namespace = rec.map_err(|e| match e {
+            sqlx::Error::RowNotFound => Error::NamespaceNotFoundByName {
+                name: name.to_string(),
+            },
+            _ => Error::SqlxError { source: e },
+        })?;
+
+        Ok(namespace)
+    }
+
+    async fn update_write_bytes_limit(
+        &mut self,
+        name: &str,
+        new_max: Option<i64>,
+    ) -> Result<Namespace> {
+        let rec = sqlx::query_as::<_, Namespace>(
+            r#"
+UPDATE namespace
+SET max_write_bytes_per_second = $1
+WHERE name = $2
+RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table,
+          max_write_bytes_per_second, max_write_lines_per_second, max_concurrent_queries,
+          deleted_at, partition_template;
+        "#,
+        )
+        .bind(new_max)
+        .bind(name)
+        .fetch_one(&mut self.inner)
+        .await;
+
+        let namespace = rec.map_err(|e| match e {
+            sqlx::Error::RowNotFound => Error::NamespaceNotFoundByName {
+                name: name.to_string(),
+            },
+            _ => Error::SqlxError { source: e },
+        })?;
+
+        Ok(namespace)
+    }
+
+    async fn update_write_lines_limit(
+        &mut self,
+        name: &str,
+        new_max: Option<i64>,
+    ) -> Result<Namespace> {
+        let rec = sqlx::query_as::<_, Namespace>(
+            r#"
+UPDATE namespace
+SET max_write_lines_per_second = $1
+WHERE name = $2
+RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table,
+          max_write_bytes_per_second, max_write_lines_per_second, max_concurrent_queries,
+          deleted_at, partition_template;
+        "#,
+        )
+        .bind(new_max)
+        .bind(name)
+        .fetch_one(&mut self.inner)
+        .await;
+
+        let namespace = rec.map_err(|e| match e {
+            sqlx::Error::RowNotFound => Error::NamespaceNotFoundByName {
+                name: name.to_string(),
+            },
+            _ => Error::SqlxError { source: e },
+        })?;
+
+        Ok(namespace)
+    }
+
+    async fn update_query_concurrency_limit(
+        &mut self,
+        name: &str,
+        new_max: Option<i32>,
+    ) -> Result<Namespace> {
+        let rec = sqlx::query_as::<_, Namespace>(
+            r#"
+UPDATE namespace
+SET max_concurrent_queries = $1
+WHERE name = $2
+RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table,
+          max_write_bytes_per_second, max_write_lines_per_second, max_concurrent_queries,
+          deleted_at, partition_template;
         "#,
         )
         .bind(new_max)
@@ -871,8 +982,9 @@ RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table, dele
 UPDATE namespace
 SET retention_period_ns = $1
 WHERE name = $2
-RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table, deleted_at,
-          partition_template;
+RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table,
+          max_write_bytes_per_second, max_write_lines_per_second, max_concurrent_queries,
+          deleted_at, partition_template;
         "#,
         )
         .bind(retention_period_ns) // $1
@@ -1040,6 +1152,31 @@ WHERE namespace_id = $1;
 
         Ok(rec)
     }
+    async fn update_retention_period(
+        &mut self,
+        table_id: TableId,
+        retention_period_ns: Option<i64>,
+    ) -> Result<Table> {
+        let rec = sqlx::query_as::<_, Table>(
+            r#"
+UPDATE table_name
+SET retention_period_ns = $1
+WHERE id = $2
+RETURNING *;
+        "#,
+        )
+        .bind(retention_period_ns) // $1
+        .bind(table_id) // $2
+        .fetch_one(&mut self.inner)
+        .await;
+
+        let table = rec.map_err(|e| match e {
+            sqlx::Error::RowNotFound => Error::TableNotFound { id: table_id },
+            _ => Error::SqlxError { source: e },
+        })?;
+
+        Ok(table)
+    }
 }
 
 #[async_trait]
@@ -1185,6 +1322,60 @@ RETURNING id, hash_id, table_id, partition_key, sort_key, sort_key_ids, new_file
         Ok(v)
     }
 
+    async fn create_or_get_many(
+        &mut self,
+        partitions: &[(TableId, PartitionKey)],
+    ) -> Result<Vec<Partition>> {
+        // Deduplicate the partitions, as an upsert cannot affect the same row
+        // twice within a single statement.
+        let partitions = partitions.iter().collect::<BTreeSet<_>>();
+        let hash_ids = partitions
+            .iter()
+            .map(|(table_id, key)| PartitionHashId::new(*table_id, key))
+            .collect::<Vec<_>>();
+        let v_table_id = partitions
+            .iter()
+            .map(|(table_id, _)| table_id.get())
+            .collect::<Vec<_>>();
+        let v_key = partitions
+            .iter()
+            .map(|(_, key)| key.inner())
+            .collect::<Vec<_>>();
+        let v_hash_id = hash_ids.iter().map(|v| v.as_bytes()).collect::<Vec<_>>();
+
+        // As with the bulk column upsert, the `ORDER BY` in this statement
+        // avoids deadlocks between concurrent upserts of overlapping batches.
+        let out = sqlx::query_as::<_, Partition>(
+            r#"
+INSERT INTO partition
+    (partition_key, shard_id, table_id, hash_id, sort_key, sort_key_ids)
+SELECT partition_key, $1, table_id, hash_id, '{}', '{}'
+FROM UNNEST($2, $3, $4) as a(table_id, partition_key, hash_id)
+ORDER BY table_id, partition_key
+ON CONFLICT ON CONSTRAINT partition_key_unique
+DO UPDATE SET partition_key = partition.partition_key
+RETURNING id, hash_id, table_id, partition_key, sort_key, sort_key_ids, new_file_at;
+        "#,
+        )
+        .bind(TRANSITION_SHARD_ID) // $1
+        .bind(&v_table_id) // $2
+        .bind(&v_key) // $3
+        .bind(&v_hash_id) // $4
+        .fetch_all(&mut self.inner)
+        .await
+        .map_err(|e| {
+            if is_fk_violation(&e) {
+                Error::ForeignKeyViolation { source: e }
+            } else {
+                Error::SqlxError { source: e }
+            }
+        })?;
+
+        assert_eq!(partitions.len(), out.len());
+
+        Ok(out)
+    }
+
     async fn get_by_id(&mut self, partition_id: PartitionId) -> Result<Option<Partition>> {
         let rec = sqlx::query_as::<_, Partition>(
             r#"
@@ -1553,16 +1744,46 @@ FROM parquet_file;
 
     async fn flag_for_delete_by_retention(&mut self) -> Result<Vec<ParquetFileId>> {
         let flagged_at = Timestamp::from(self.time_provider.now());
-        // TODO - include check of table retention period once implemented
+        // table retention, if it exists, overrides namespace retention
+        //
+        // The source table of a rollup rule with a raw retention period is subject to tiered
+        // retention instead: its files are flagged once older than the (longest) raw retention
+        // period AND rolled up by every such rule. Until then they are kept, regardless of the
+        // table or namespace retention period.
         let flagged = sqlx::query(
             r#"
-WITH parquet_file_ids as (
+WITH tiered_retention as (
+    SELECT table_name.id AS table_id,
+           MAX(rollup_rule.raw_retention_period_ns) AS retention_period_ns,
+           CASE WHEN COUNT(rollup_rule.covered_until) < COUNT(*) THEN NULL
+                ELSE MIN(rollup_rule.covered_until)
+           END AS covered_until
+    FROM rollup_rule, table_name
+    WHERE rollup_rule.raw_retention_period_ns IS NOT NULL
+    AND table_name.namespace_id = rollup_rule.namespace_id
+    AND table_name.name = rollup_rule.source_table
+    GROUP BY table_name.id
+),
+parquet_file_ids as (
     SELECT parquet_file.id
-    FROM namespace, parquet_file
-    WHERE namespace.retention_period_ns IS NOT NULL
-    AND parquet_file.to_delete IS NULL
-    AND parquet_file.max_time < $1 - namespace.retention_period_ns
+    FROM namespace, table_name, parquet_file
+    LEFT JOIN tiered_retention ON tiered_retention.table_id = parquet_file.table_id
+    WHERE parquet_file.to_delete IS NULL
     AND namespace.id = parquet_file.namespace_id
+    AND table_name.id = parquet_file.table_id
+    AND (
+        (
+            tiered_retention.table_id IS NULL
+            AND COALESCE(table_name.retention_period_ns, namespace.retention_period_ns)
+                IS NOT NULL
+            AND parquet_file.max_time
+                < $1 - COALESCE(table_name.retention_period_ns, namespace.retention_period_ns)
+        ) OR (
+            tiered_retention.table_id IS NOT NULL
+            AND parquet_file.max_time < $1 - tiered_retention.retention_period_ns
+            AND parquet_file.max_time < tiered_retention.covered_until
+        )
+    )
     LIMIT $2
 )
 UPDATE parquet_file
@@ -1620,6 +1841,27 @@ WHERE table_id = $1 AND to_delete IS NULL;
         .map_err(|e| Error::SqlxError { source: e })
     }
 
+    async fn list_by_table_created_after(
+        &mut self,
+        table_id: TableId,
+        created_after: Timestamp,
+    ) -> Result<Vec<ParquetFile>> {
+        sqlx::query_as::<_, ParquetFile>(
+            r#"
+SELECT id, namespace_id, table_id, partition_id, partition_hash_id, object_store_id,
+       min_time, max_time, to_delete, file_size_bytes, row_count, compaction_level, created_at,
+       column_set, max_l0_created_at
+FROM parquet_file
+WHERE table_id = $1 AND to_delete IS NULL AND created_at > $2;
+             "#,
+        )
+        .bind(table_id) // $1
+        .bind(created_after) // $2
+        .fetch_all(&mut self.inner)
+        .await
+        .map_err(|e| Error::SqlxError { source: e })
+    }
+
     async fn delete_old_ids_only(&mut self, older_than: Timestamp) -> Result<Vec<ParquetFileId>> {
         // see https://www.crunchydata.com/blog/simulating-update-or-delete-with-limit-in-postgres-ctes-to-the-rescue
         let deleted = sqlx::query(
@@ -1769,6 +2011,289 @@ WHERE object_store_id = ANY($1);
             .map_err(|source| Error::FailedToCommit { source })?;
         Ok(ids)
     }
+
+    async fn storage_usage_by_namespace(
+        &mut self,
+        namespace_id: NamespaceId,
+    ) -> Result<Vec<StorageUsage>> {
+        // The usage is the aggregate plus the changes not yet folded into it, ignoring the changes
+        // of tables deleted since they were recorded.
+        sqlx::query_as::<_, StorageUsage>(
+            r#"
+SELECT namespace_id, table_id, compaction_level,
+    SUM(file_count)::BIGINT AS file_count,
+    SUM(file_size_bytes)::BIGINT AS file_size_bytes,
+    SUM(row_count)::BIGINT AS row_count
+FROM (
+    SELECT namespace_id, table_id, compaction_level, file_count, file_size_bytes, row_count
+    FROM storage_usage
+    WHERE namespace_id = $1
+    UNION ALL
+    SELECT namespace_id, table_id, compaction_level, file_count, file_size_bytes, row_count
+    FROM storage_usage_change
+    WHERE namespace_id = $1
+    AND EXISTS (SELECT 1 FROM table_name WHERE table_name.id = storage_usage_change.table_id)
+) AS usage
+GROUP BY namespace_id, table_id, compaction_level
+HAVING SUM(file_count) > 0
+ORDER BY table_id, compaction_level;
+            "#,
+        )
+        .bind(namespace_id) // $1
+        .fetch_all(&mut self.inner)
+        .await
+        .map_err(|e| Error::SqlxError { source: e })
+    }
+
+    async fn storage_usage(&mut self) -> Result<Vec<StorageUsage>> {
+        sqlx::query_as::<_, StorageUsage>(
+            r#"
+SELECT namespace_id, table_id, compaction_level,
+    SUM(file_count)::BIGINT AS file_count,
+    SUM(file_size_bytes)::BIGINT AS file_size_bytes,
+    SUM(row_count)::BIGINT AS row_count
+FROM (
+    SELECT namespace_id, table_id, compaction_level, file_count, file_size_bytes, row_count
+    FROM storage_usage
+    UNION ALL
+    SELECT namespace_id, table_id, compaction_level, file_count, file_size_bytes, row_count
+    FROM storage_usage_change
+    WHERE EXISTS (SELECT 1 FROM table_name WHERE table_name.id = storage_usage_change.table_id)
+) AS usage
+GROUP BY namespace_id, table_id, compaction_level
+HAVING SUM(file_count) > 0
+ORDER BY namespace_id, table_id, compaction_level;
+            "#,
+        )
+        .fetch_all(&mut self.inner)
+        .await
+        .map_err(|e| Error::SqlxError { source: e })
+    }
+
+    async fn fold_storage_usage(&mut self) -> Result<usize> {
+        // Deleting the changes and adding them to the aggregate in one statement keeps the usage
+        // read by concurrent transactions unchanged. The aggregate rows are upserted in a fixed
+        // order so concurrent folds cannot deadlock.
+        let changes = sqlx::query_scalar::<_, i64>(
+            r#"
+WITH changes AS (
+    DELETE FROM storage_usage_change
+    RETURNING namespace_id, table_id, compaction_level, file_count, file_size_bytes, row_count
+), folded AS (
+    INSERT INTO storage_usage
+        (namespace_id, table_id, compaction_level, file_count, file_size_bytes, row_count)
+    SELECT namespace_id, table_id, compaction_level,
+        SUM(file_count), SUM(file_size_bytes), SUM(row_count)
+    FROM changes
+    WHERE EXISTS (SELECT 1 FROM table_name WHERE table_name.id = changes.table_id)
+    GROUP BY namespace_id, table_id, compaction_level
+    ORDER BY namespace_id, table_id, compaction_level
+    ON CONFLICT (namespace_id, table_id, compaction_level) DO UPDATE
+    SET file_count = storage_usage.file_count + EXCLUDED.file_count,
+        file_size_bytes = storage_usage.file_size_bytes + EXCLUDED.file_size_bytes,
+        row_count = storage_usage.row_count + EXCLUDED.row_count
+)
+SELECT COUNT(*) AS changes FROM changes;
+            "#,
+        )
+        .fetch_one(&mut self.inner)
+        .await
+        .map_err(|e| Error::SqlxError { source: e })?;
+
+        Ok(changes as usize)
+    }
+}
+
+#[async_trait]
+impl RollupRuleRepo for PostgresTxn {
+    async fn create(&mut self, rollup_rule_params: RollupRuleParams) -> Result<RollupRule> {
+        let RollupRuleParams {
+            namespace_id,
+            name,
+            source_table,
+            target_table,
+            language,
+            aggregates,
+            interval_ns,
+            raw_retention_period_ns,
+        } = rollup_rule_params;
+
+        let rec = sqlx::query_as::<_, RollupRule>(
+            r#"
+INSERT INTO rollup_rule
+    ( namespace_id, name, source_table, target_table, language, aggregates, interval_ns,
+      raw_retention_period_ns )
+VALUES
+    ( $1, $2, $3, $4, $5, $6, $7, $8 )
+RETURNING *;
+        "#,
+        )
+        .bind(namespace_id) // $1
+        .bind(&name) // $2
+        .bind(source_table) // $3
+        .bind(target_table) // $4
+        .bind(language) // $5
+        .bind(aggregates) // $6
+        .bind(interval_ns) // $7
+        .bind(raw_retention_period_ns) // $8
+        .fetch_one(&mut self.inner)
+        .await
+        .map_err(|e| {
+            if is_unique_violation(&e) {
+                Error::RollupRuleNameExists { name, namespace_id }
+            } else if is_fk_violation(&e) {
+                Error::NamespaceNotFoundById { id: namespace_id }
+            } else {
+                Error::SqlxError { source: e }
+            }
+        })?;
+
+        Ok(rec)
+    }
+
+    async fn get_by_namespace_and_name(
+        &mut self,
+        namespace_id: NamespaceId,
+        name: &str,
+    ) -> Result<Option<RollupRule>> {
+        let rec = sqlx::query_as::<_, RollupRule>(
+            r#"
+SELECT *
+FROM rollup_rule
+WHERE namespace_id = $1 AND name = $2;
+            "#,
+        )
+        .bind(namespace_id) // $1
+        .bind(name) // $2
+        .fetch_one(&mut self.inner)
+        .await;
+
+        if let Err(sqlx::Error::RowNotFound) = rec {
+            return Ok(None);
+        }
+
+        let rule = rec.map_err(|e| Error::SqlxError { source: e })?;
+
+        Ok(Some(rule))
+    }
+
+    async fn list_by_namespace_id(&mut self, namespace_id: NamespaceId) -> Result<Vec<RollupRule>> {
+        sqlx::query_as::<_, RollupRule>(
+            r#"
+SELECT *
+FROM rollup_rule
+WHERE namespace_id = $1;
+            "#,
+        )
+        .bind(namespace_id) // $1
+        .fetch_all(&mut self.inner)
+        .await
+        .map_err(|e| Error::SqlxError { source: e })
+    }
+
+    async fn list(&mut self) -> Result<Vec<RollupRule>> {
+        sqlx::query_as::<_, RollupRule>("SELECT * FROM rollup_rule;")
+            .fetch_all(&mut self.inner)
+            .await
+            .map_err(|e| Error::SqlxError { source: e })
+    }
+
+    async fn update_covered_until(
+        &mut self,
+        id: RollupRuleId,
+        covered_until: Timestamp,
+    ) -> Result<RollupRule> {
+        let rec = sqlx::query_as::<_, RollupRule>(
+            r#"
+UPDATE rollup_rule
+SET covered_until = $1
+WHERE id = $2
+RETURNING *;
+        "#,
+        )
+        .bind(covered_until) // $1
+        .bind(id) // $2
+        .fetch_one(&mut self.inner)
+        .await;
+
+        let rule = rec.map_err(|e| match e {
+            sqlx::Error::RowNotFound => Error::RollupRuleNotFound { id },
+            _ => Error::SqlxError { source: e },
+        })?;
+
+        Ok(rule)
+    }
+
+    async fn update_files_checked_until(
+        &mut self,
+        id: RollupRuleId,
+        files_checked_until: Timestamp,
+    ) -> Result<RollupRule> {
+        let rec = sqlx::query_as::<_, RollupRule>(
+            r#"
+UPDATE rollup_rule
+SET files_checked_until = $1
+WHERE id = $2
+RETURNING *;
+        "#,
+        )
+        .bind(files_checked_until) // $1
+        .bind(id) // $2
+        .fetch_one(&mut self.inner)
+        .await;
+
+        let rule = rec.map_err(|e| match e {
+            sqlx::Error::RowNotFound => Error::RollupRuleNotFound { id },
+            _ => Error::SqlxError { source: e },
+        })?;
+
+        Ok(rule)
+    }
+
+    async fn update_last_error(
+        &mut self,
+        id: RollupRuleId,
+        last_error: Option<String>,
+    ) -> Result<RollupRule> {
+        let rec = sqlx::query_as::<_, RollupRule>(
+            r#"
+UPDATE rollup_rule
+SET last_error = $1
+WHERE id = $2
+RETURNING *;
+        "#,
+        )
+        .bind(last_error) // $1
+        .bind(id) // $2
+        .fetch_one(&mut self.inner)
+        .await;
+
+        let rule = rec.map_err(|e| match e {
+            sqlx::Error::RowNotFound => Error::RollupRuleNotFound { id },
+            _ => Error::SqlxError { source: e },
+        })?;
+
+        Ok(rule)
+    }
+
+    async fn delete(&mut self, id: RollupRuleId) -> Result<()> {
+        let rec = sqlx::query(
+            r#"
+DELETE FROM rollup_rule
+WHERE id = $1
+RETURNING id;
+        "#,
+        )
+        .bind(id) // $1
+        .fetch_one(&mut self.inner)
+        .await;
+
+        match rec {
+            Ok(_) => Ok(()),
+            Err(sqlx::Error::RowNotFound) => Err(Error::RollupRuleNotFound { id }),
+            Err(e) => Err(Error::SqlxError { source: e }),
+        }
+    }
 }
 
 // The following three functions are helpers to the create_upgrade_delete method.
@@ -2687,8 +3212,9 @@ INSERT INTO namespace (
     name, topic_id, query_pool_id, retention_period_ns, max_tables, partition_template
 )
 VALUES ( $1, $2, $3, $4, $5, NULL )
-RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table, deleted_at,
-          partition_template;
+RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table,
+          max_write_bytes_per_second, max_write_lines_per_second, max_concurrent_queries,
+          deleted_at, partition_template;
             "#,
         )
         .bind(namespace_name) // $1
@@ -4,7 +4,7 @@ use crate::{
     interface::{
         self, verify_sort_key_length, CasFailure, Catalog, ColumnRepo, ColumnTypeMismatchSnafu,
         Error, NamespaceRepo, ParquetFileRepo, PartitionRepo, RepoCollection, Result,
-        SoftDeletedRows, TableRepo, MAX_PARQUET_FILES_SELECTED_ONCE_FOR_RETENTION,
+        RollupRuleRepo, SoftDeletedRows, TableRepo, MAX_PARQUET_FILES_SELECTED_ONCE_FOR_RETENTION,
     },
     kafkaless_transition::{
         SHARED_QUERY_POOL, SHARED_QUERY_POOL_ID, SHARED_TOPIC_ID, SHARED_TOPIC_NAME,
@@ -20,12 +20,16 @@ use data_types::{
     },
     Column, ColumnId, ColumnSet, ColumnType, CompactionLevel, Namespace, NamespaceId,
     NamespaceName, NamespaceServiceProtectionLimitsOverride, ParquetFile, ParquetFileId,
-    ParquetFileParams, Partition, PartitionHashId, PartitionId, PartitionKey, SkippedCompaction,
-    SortedColumnSet, Table, TableId, Timestamp, TransitionPartitionId,
+    ParquetFileParams, Partition, PartitionHashId, PartitionId, PartitionKey, RollupRule,
+    RollupRuleId, RollupRuleParams, SkippedCompaction, SortedColumnSet, StorageUsage, Table,
+    TableId, Timestamp, TransitionPartitionId,
 };
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, fmt::Display};
-use std::{collections::HashSet, fmt::Write};
+use std::{
+    collections::{BTreeSet, HashSet},
+    fmt::Write,
+};
 
 use crate::interface::MAX_PARQUET_FILES_SELECTED_ONCE_FOR_DELETE;
 use iox_time::{SystemProvider, TimeProvider};
@@ -260,6 +264,10 @@ impl RepoCollection for SqliteTxn {
     fn parquet_files(&mut self) -> &mut dyn ParquetFileRepo {
         self
     }
+
+    fn rollup_rules(&mut self) -> &mut dyn RollupRuleRepo {
+        self
+    }
 }
 
 #[async_trait]
@@ -273,13 +281,23 @@ impl NamespaceRepo for SqliteTxn {
     ) -> Result<Namespace> {
         let max_tables = service_protection_limits.and_then(|l| l.max_tables);
         let max_columns_per_table = service_protection_limits.and_then(|l| l.max_columns_per_table);
+        let max_write_bytes_per_second =
+            service_protection_limits.and_then(|l| l.max_write_bytes_per_second);
+        let max_write_lines_per_second =
+            service_protection_limits.and_then(|l| l.max_write_lines_per_second);
+        let max_concurrent_queries =
+            service_protection_limits.and_then(|l| l.max_concurrent_queries);
 
         let rec = sqlx::query_as::<_, Namespace>(
             r#"
-INSERT INTO namespace ( name, topic_id, query_pool_id, retention_period_ns, max_tables, max_columns_per_table, partition_template )
-VALUES ( $1, $2, $3, $4, $5, $6, $7 )
-RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table, deleted_at,
-          partition_template;
+INSERT INTO namespace (
+    name, topic_id, query_pool_id, retention_period_ns, max_tables, max_columns_per_table, partition_template,
+    max_write_bytes_per_second, max_write_lines_per_second, max_concurrent_queries
+)
+VALUES ( $1, $2, $3, $4, $5, $6, $7, $8, $9, $10 )
+RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table,
+          max_write_bytes_per_second, max_write_lines_per_second, max_concurrent_queries,
+          deleted_at, partition_template;
             "#,
         )
         .bind(name.as_str()) // $1
@@ -288,7 +306,10 @@ RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table, dele
         .bind(retention_period_ns) // $4
         .bind(max_tables.unwrap_or(DEFAULT_MAX_TABLES)) // $5
         .bind(max_columns_per_table.unwrap_or(DEFAULT_MAX_COLUMNS_PER_TABLE)) // $6
-        .bind(partition_template); // $7
+        .bind(partition_template) // $7
+        .bind(max_write_bytes_per_second) // $8
+        .bind(max_write_lines_per_second) // $9
+        .bind(max_concurrent_queries); // $10
 
         let rec = rec.fetch_one(self.inner.get_mut()).await.map_err(|e| {
             if is_unique_violation(&e) {
@@ -309,8 +330,9 @@ RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table, dele
         let rec = sqlx::query_as::<_, Namespace>(
             format!(
                 r#"
-SELECT id, name, retention_period_ns, max_tables, max_columns_per_table, deleted_at,
-       partition_template
+SELECT id, name, retention_period_ns, max_tables, max_columns_per_table,
+       max_write_bytes_per_second, max_write_lines_per_second, max_concurrent_queries,
+       deleted_at, partition_template
 FROM namespace
 WHERE {v};
                 "#,
@@ -333,8 +355,9 @@ WHERE {v};
         let rec = sqlx::query_as::<_, Namespace>(
             format!(
                 r#"
-SELECT id, name, retention_period_ns, max_tables, max_columns_per_table, deleted_at,
-       partition_template
+SELECT id, name, retention_period_ns, max_tables, max_columns_per_table,
+       max_write_bytes_per_second, max_write_lines_per_second, max_concurrent_queries,
+       deleted_at, partition_template
 FROM namespace
 WHERE id=$1 AND {v};
                 "#,
@@ -363,8 +386,9 @@ WHERE id=$1 AND {v};
         let rec = sqlx::query_as::<_, Namespace>(
             format!(
                 r#"
-SELECT id, name, retention_period_ns, max_tables, max_columns_per_table, deleted_at,
-       partition_template
+SELECT id, name, retention_period_ns, max_tables, max_columns_per_table,
+       max_write_bytes_per_second, max_write_lines_per_second, max_concurrent_queries,
+       deleted_at, partition_template
 FROM namespace
 WHERE name=$1 AND {v};
                 "#,
@@ -404,8 +428,9 @@ WHERE name=$1 AND {v};
 UPDATE namespace
 SET max_tables = $1
 WHERE name = $2
-RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table, deleted_at,
-          partition_template;
+RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table,
+          max_write_bytes_per_second, max_write_lines_per_second, max_concurrent_queries,
+          deleted_at, partition_template;
         "#,
         )
         .bind(new_max)
@@ -429,8 +454,99 @@ RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table, dele
 UPDATE namespace
 SET max_columns_per_table = $1
 WHERE name = $2
-RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table, deleted_at,
-          partition_template;
+RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table,
+          max_write_bytes_per_second, max_write_lines_per_second, max_concurrent_queries,
+          deleted_at, partition_template;
+        "#,
+        )
+        .bind(new_max)
+        .bind(name)
+        .fetch_one(self.inner.get_mut())
+        .await;
+
+        let namespace = rec.map_err(|e| match e {
+            sqlx::Error::RowNotFound => Error::NamespaceNotFoundByName {
+                name: name.to_string(),
+            },
+            _ => Error::SqlxError { source: e },
+        })?;
+
+        Ok(namespace)
+    }
+
+    async fn update_write_bytes_limit(
+        &mut self,
+        name: &str,
+        new_max: Option<i64>,
+    ) -> Result<Namespace> {
+        let rec = sqlx::query_as::<_, Namespace>(
+            r#"
+UPDATE namespace
+SET max_write_bytes_per_second = $1
+WHERE name = $2
+RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table,
+          max_write_bytes_per_second, max_write_lines_per_second, max_concurrent_queries,
+          deleted_at, partition_template;
+        "#,
+        )
+        .bind(new_max)
+        .bind(name)
+        .fetch_one(self.inner.get_mut())
+        .await;
+
+        let namespace = rec.map_err(|e| match e {
+            sqlx::Error::RowNotFound => Error::NamespaceNotFoundByName {
+                name: name.to_string(),
+            },
+            _ => Error::SqlxError { source: e },
+        })?;
+
+        Ok(namespace)
+    }
+
+    async fn update_write_lines_limit(
+        &mut self,
+        name: &str,
+        new_max: Option<i64>,
+    ) -> Result<Namespace> {
+        let rec = sqlx::query_as::<_, Namespace>(
+            r#"
+UPDATE namespace
+SET max_write_lines_per_second = $1
+WHERE name = $2
+RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table,
+          max_write_bytes_per_second, max_write_lines_per_second, max_concurrent_queries,
+          deleted_at, partition_template;
+        "#,
+        )
+        .bind(new_max)
+        .bind(name)
+        .fetch_one(self.inner.get_mut())
+        .await;
+
+        let namespace = rec.map_err(|e| match e {
+            sqlx::Error::RowNotFound => Error::NamespaceNotFoundByName {
+                name: name.to_string(),
+            },
+            _ => Error::SqlxError { source: e },
+        })?;
+
+        Ok(namespace)
+    }
+
+    async fn update_query_concurrency_limit(
+        &mut self,
+        name: &str,
+        new_max: Option<i32>,
+    ) -> Result<Namespace> {
+        let rec = sqlx::query_as::<_, Namespace>(
+            r#"
+UPDATE namespace
+SET max_concurrent_queries = $1
+WHERE name = $2
+RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table,
+          max_write_bytes_per_second, max_write_lines_per_second, max_concurrent_queries,
+          deleted_at, partition_template;
         "#,
         )
         .bind(new_max)
@@ -458,8 +574,9 @@ RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table, dele
 UPDATE namespace
 SET retention_period_ns = $1
 WHERE name = $2
-RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table, deleted_at,
-          partition_template;
+RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table,
+          max_write_bytes_per_second, max_write_lines_per_second, max_concurrent_queries,
+          deleted_at, partition_template;
             "#,
         )
         .bind(retention_period_ns) // $1
@@ -687,6 +804,31 @@ WHERE namespace_id = $1;
 
         Ok(rec)
     }
+    async fn update_retention_period(
+        &mut self,
+        table_id: TableId,
+        retention_period_ns: Option<i64>,
+    ) -> Result<Table> {
+        let rec = sqlx::query_as::<_, Table>(
+            r#"
+UPDATE table_name
+SET retention_period_ns = $1
+WHERE id = $2
+RETURNING *;
+        "#,
+        )
+        .bind(retention_period_ns) // $1
+        .bind(table_id) // $2
+        .fetch_one(self.inner.get_mut())
+        .await;
+
+        let table = rec.map_err(|e| match e {
+            sqlx::Error::RowNotFound => Error::TableNotFound { id: table_id },
+            _ => Error::SqlxError { source: e },
+        })?;
+
+        Ok(table)
+    }
 }
 
 #[async_trait]
@@ -877,6 +1019,22 @@ RETURNING id, hash_id, table_id, partition_key, sort_key, sort_key_ids, new_file
         Ok(v.into())
     }
 
+    async fn create_or_get_many(
+        &mut self,
+        partitions: &[(TableId, PartitionKey)],
+    ) -> Result<Vec<Partition>> {
+        // The SQLite catalog is accessed in-process, so upserting each
+        // partition individually does not incur additional round-trips.
+        let partitions = partitions.iter().collect::<BTreeSet<_>>();
+
+        let mut out = Vec::with_capacity(partitions.len());
+        for (table_id, key) in partitions {
+            out.push(PartitionRepo::create_or_get(self, key.clone(), *table_id).await?);
+        }
+
+        Ok(out)
+    }
+
     async fn get_by_id(&mut self, partition_id: PartitionId) -> Result<Option<Partition>> {
         let rec = sqlx::query_as::<_, PartitionPod>(
             r#"
@@ -1312,16 +1470,46 @@ FROM parquet_file;
 
     async fn flag_for_delete_by_retention(&mut self) -> Result<Vec<ParquetFileId>> {
         let flagged_at = Timestamp::from(self.time_provider.now());
-        // TODO - include check of table retention period once implemented
+        // table retention, if it exists, overrides namespace retention
+        //
+        // The source table of a rollup rule with a raw retention period is subject to tiered
+        // retention instead: its files are flagged once older than the (longest) raw retention
+        // period AND rolled up by every such rule. Until then they are kept, regardless of the
+        // table or namespace retention period.
         let flagged = sqlx::query(
             r#"
-WITH parquet_file_ids as (
+WITH tiered_retention as (
+    SELECT table_name.id AS table_id,
+           MAX(rollup_rule.raw_retention_period_ns) AS retention_period_ns,
+           CASE WHEN COUNT(rollup_rule.covered_until) < COUNT(*) THEN NULL
+                ELSE MIN(rollup_rule.covered_until)
+           END AS covered_until
+    FROM rollup_rule, table_name
+    WHERE rollup_rule.raw_retention_period_ns IS NOT NULL
+    AND table_name.namespace_id = rollup_rule.namespace_id
+    AND table_name.name = rollup_rule.source_table
+    GROUP BY table_name.id
+),
+parquet_file_ids as (
     SELECT parquet_file.id
-    FROM namespace, parquet_file
-    WHERE namespace.retention_period_ns IS NOT NULL
-    AND parquet_file.to_delete IS NULL
-    AND parquet_file.max_time < $1 - namespace.retention_period_ns
+    FROM namespace, table_name, parquet_file
+    LEFT JOIN tiered_retention ON tiered_retention.table_id = parquet_file.table_id
+    WHERE parquet_file.to_delete IS NULL
     AND namespace.id = parquet_file.namespace_id
+    AND table_name.id = parquet_file.table_id
+    AND (
+        (
+            tiered_retention.table_id IS NULL
+            AND COALESCE(table_name.retention_period_ns, namespace.retention_period_ns)
+                IS NOT NULL
+            AND parquet_file.max_time
+                < $1 - COALESCE(table_name.retention_period_ns, namespace.retention_period_ns)
+        ) OR (
+            tiered_retention.table_id IS NOT NULL
+            AND parquet_file.max_time < $1 - tiered_retention.retention_period_ns
+            AND parquet_file.max_time < tiered_retention.covered_until
+        )
+    )
     LIMIT $2
 )
 UPDATE parquet_file
@@ -1387,6 +1575,30 @@ WHERE table_id = $1 AND to_delete IS NULL;
         .collect())
     }
 
+    async fn list_by_table_created_after(
+        &mut self,
+        table_id: TableId,
+        created_after: Timestamp,
+    ) -> Result<Vec<ParquetFile>> {
+        Ok(sqlx::query_as::<_, ParquetFilePod>(
+            r#"
+SELECT id, namespace_id, table_id, partition_id, partition_hash_id, object_store_id,
+       min_time, max_time, to_delete, file_size_bytes,
+       row_count, compaction_level, created_at, column_set, max_l0_created_at
+FROM parquet_file
+WHERE table_id = $1 AND to_delete IS NULL AND created_at > $2;
+             "#,
+        )
+        .bind(table_id) // $1
+        .bind(created_after) // $2
+        .fetch_all(self.inner.get_mut())
+        .await
+        .map_err(|e| Error::SqlxError { source: e })?
+        .into_iter()
+        .map(Into::into)
+        .collect())
+    }
+
     async fn delete_old_ids_only(&mut self, older_than: Timestamp) -> Result<Vec<ParquetFileId>> {
         // see https://www.crunchydata.com/blog/simulating-update-or-delete-with-limit-in-sqlite-ctes-to-the-rescue
         let deleted = sqlx::query(
@@ -1547,6 +1759,235 @@ WHERE object_store_id IN ({v});",
 
         Ok(ids)
     }
+
+    async fn storage_usage_by_namespace(
+        &mut self,
+        namespace_id: NamespaceId,
+    ) -> Result<Vec<StorageUsage>> {
+        sqlx::query_as::<_, StorageUsage>(
+            r#"
+SELECT namespace_id, table_id, compaction_level, file_count, file_size_bytes, row_count
+FROM storage_usage
+WHERE namespace_id = $1 AND file_count > 0
+ORDER BY table_id, compaction_level;
+            "#,
+        )
+        .bind(namespace_id) // $1
+        .fetch_all(self.inner.get_mut())
+        .await
+        .map_err(|e| Error::SqlxError { source: e })
+    }
+
+    async fn storage_usage(&mut self) -> Result<Vec<StorageUsage>> {
+        sqlx::query_as::<_, StorageUsage>(
+            r#"
+SELECT namespace_id, table_id, compaction_level, file_count, file_size_bytes, row_count
+FROM storage_usage
+WHERE file_count > 0
+ORDER BY namespace_id, table_id, compaction_level;
+            "#,
+        )
+        .fetch_all(self.inner.get_mut())
+        .await
+        .map_err(|e| Error::SqlxError { source: e })
+    }
+
+    async fn fold_storage_usage(&mut self) -> Result<usize> {
+        // The triggers update storage_usage directly, as SQLite serialises all writes anyway.
+        Ok(0)
+    }
+}
+
+#[async_trait]
+impl RollupRuleRepo for SqliteTxn {
+    async fn create(&mut self, rollup_rule_params: RollupRuleParams) -> Result<RollupRule> {
+        let RollupRuleParams {
+            namespace_id,
+            name,
+            source_table,
+            target_table,
+            language,
+            aggregates,
+            interval_ns,
+            raw_retention_period_ns,
+        } = rollup_rule_params;
+
+        let rec = sqlx::query_as::<_, RollupRule>(
+            r#"
+INSERT INTO rollup_rule
+    ( namespace_id, name, source_table, target_table, language, aggregates, interval_ns,
+      raw_retention_period_ns )
+VALUES
+    ( $1, $2, $3, $4, $5, $6, $7, $8 )
+RETURNING *;
+        "#,
+        )
+        .bind(namespace_id) // $1
+        .bind(&name) // $2
+        .bind(source_table) // $3
+        .bind(target_table) // $4
+        .bind(language) // $5
+        .bind(aggregates) // $6
+        .bind(interval_ns) // $7
+        .bind(raw_retention_period_ns) // $8
+        .fetch_one(self.inner.get_mut())
+        .await
+        .map_err(|e| {
+            if is_unique_violation(&e) {
+                Error::RollupRuleNameExists { name, namespace_id }
+            } else if is_fk_violation(&e) {
+                Error::NamespaceNotFoundById { id: namespace_id }
+            } else {
+                Error::SqlxError { source: e }
+            }
+        })?;
+
+        Ok(rec)
+    }
+
+    async fn get_by_namespace_and_name(
+        &mut self,
+        namespace_id: NamespaceId,
+        name: &str,
+    ) -> Result<Option<RollupRule>> {
+        let rec = sqlx::query_as::<_, RollupRule>(
+            r#"
+SELECT *
+FROM rollup_rule
+WHERE namespace_id = $1 AND name = $2;
+            "#,
+        )
+        .bind(namespace_id) // $1
+        .bind(name) // $2
+        .fetch_one(self.inner.get_mut())
+        .await;
+
+        if let Err(sqlx::Error::RowNotFound) = rec {
+            return Ok(None);
+        }
+
+        let rule = rec.map_err(|e| Error::SqlxError { source: e })?;
+
+        Ok(Some(rule))
+    }
+
+    async fn list_by_namespace_id(&mut self, namespace_id: NamespaceId) -> Result<Vec<RollupRule>> {
+        sqlx::query_as::<_, RollupRule>(
+            r#"
+SELECT *
+FROM rollup_rule
+WHERE namespace_id = $1;
+            "#,
+        )
+        .bind(namespace_id) // $1
+        .fetch_all(self.inner.get_mut())
+        .await
+        .map_err(|e| Error::SqlxError { source: e })
+    }
+
+    async fn list(&mut self) -> Result<Vec<RollupRule>> {
+        sqlx::query_as::<_, RollupRule>("SELECT * FROM rollup_rule;")
+            .fetch_all(self.inner.get_mut())
+            .await
+            .map_err(|e| Error::SqlxError { source: e })
+    }
+
+    async fn update_covered_until(
+        &mut self,
+        id: RollupRuleId,
+        covered_until: Timestamp,
+    ) -> Result<RollupRule> {
+        let rec = sqlx::query_as::<_, RollupRule>(
+            r#"
+UPDATE rollup_rule
+SET covered_until = $1
+WHERE id = $2
+RETURNING *;
+        "#,
+        )
+        .bind(covered_until) // $1
+        .bind(id) // $2
+        .fetch_one(self.inner.get_mut())
+        .await;
+
+        let rule = rec.map_err(|e| match e {
+            sqlx::Error::RowNotFound => Error::RollupRuleNotFound { id },
+            _ => Error::SqlxError { source: e },
+        })?;
+
+        Ok(rule)
+    }
+
+    async fn update_files_checked_until(
+        &mut self,
+        id: RollupRuleId,
+        files_checked_until: Timestamp,
+    ) -> Result<RollupRule> {
+        let rec = sqlx::query_as::<_, RollupRule>(
+            r#"
+UPDATE rollup_rule
+SET files_checked_until = $1
+WHERE id = $2
+RETURNING *;
+        "#,
+        )
+        .bind(files_checked_until) // $1
+        .bind(id) // $2
+        .fetch_one(self.inner.get_mut())
+        .await;
+
+        let rule = rec.map_err(|e| match e {
+            sqlx::Error::RowNotFound => Error::RollupRuleNotFound { id },
+            _ => Error::SqlxError { source: e },
+        })?;
+
+        Ok(rule)
+    }
+
+    async fn update_last_error(
+        &mut self,
+        id: RollupRuleId,
+        last_error: Option<String>,
+    ) -> Result<RollupRule> {
+        let rec = sqlx::query_as::<_, RollupRule>(
+            r#"
+UPDATE rollup_rule
+SET last_error = $1
+WHERE id = $2
+RETURNING *;
+        "#,
+        )
+        .bind(last_error) // $1
+        .bind(id) // $2
+        .fetch_one(self.inner.get_mut())
+        .await;
+
+        let rule = rec.map_err(|e| match e {
+            sqlx::Error::RowNotFound => Error::RollupRuleNotFound { id },
+            _ => Error::SqlxError { source: e },
+        })?;
+
+        Ok(rule)
+    }
+
+    async fn delete(&mut self, id: RollupRuleId) -> Result<()> {
+        let rec = sqlx::query(
+            r#"
+DELETE FROM rollup_rule
+WHERE id = $1
+RETURNING id;
+        "#,
+        )
+        .bind(id) // $1
+        .fetch_one(self.inner.get_mut())
+        .await;
+
+        match rec {
+            Ok(_) => Ok(()),
+            Err(sqlx::Error::RowNotFound) => Err(Error::RollupRuleNotFound { id }),
+            Err(e) => Err(Error::SqlxError { source: e }),
+        }
+    }
 }
 
 // The following three functions are helpers to the create_upgrade_delete method.
@@ -2109,8 +2550,9 @@ INSERT INTO namespace (
     name, topic_id, query_pool_id, retention_period_ns, max_tables, partition_template
 )
 VALUES ( $1, $2, $3, $4, $5, NULL )
-RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table, deleted_at,
-          partition_template;
+RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table,
+          max_write_bytes_per_second, max_write_lines_per_second, max_concurrent_queries,
+          deleted_at, partition_template;
             "#,
         )
         .bind(namespace_name) // $1
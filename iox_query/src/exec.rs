@@ -398,7 +398,9 @@ mod tests {
         },
     };
     use futures::{stream::BoxStream, Stream, StreamExt};
+    use iox_time::{MockProvider, Time, TimeProvider};
     use metric::{Observation, RawReporter};
+    use std::time::Duration;
     use stringset::StringSet;
     use tokio::sync::Barrier;
 
@@ -613,6 +615,26 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_time_provider() {
+        let exec = Executor::new_testing();
+        let time_provider = Arc::new(MockProvider::new(Time::from_timestamp_nanos(42)));
+        let ctx = exec
+            .new_execution_config(ExecutorType::Query)
+            .with_time_provider(Arc::clone(&time_provider) as _)
+            .build();
+        assert_eq!(ctx.time_provider().now(), Time::from_timestamp_nanos(42));
+
+        // child contexts share the time of the query
+        let child = ctx.child_ctx("child");
+        time_provider.inc(Duration::from_secs(1));
+        assert_eq!(child.time_provider().now(), time_provider.now());
+
+        // defaults to the system time
+        let ctx = exec.new_context(ExecutorType::Query);
+        assert!(ctx.time_provider().now().timestamp_nanos() > 42);
+    }
+
     /// return a set for testing
     fn to_set(strs: &[&str]) -> StringSetRef {
         StringSetRef::new(strs.iter().map(|s| s.to_string()).collect::<StringSet>())
@@ -54,6 +54,7 @@ use datafusion::{
 use datafusion_util::config::{iox_session_config, DEFAULT_CATALOG};
 use executor::DedicatedExecutor;
 use futures::{Stream, StreamExt, TryStreamExt};
+use iox_time::{SystemProvider, TimeProvider};
 use observability_deps::tracing::{debug, warn};
 use query_functions::{register_scalar_functions, selectors::register_selector_aggregates};
 use std::{fmt, num::NonZeroUsize, sync::Arc};
@@ -180,6 +181,9 @@ pub struct IOxSessionConfig {
 
     /// Span context from which to create spans for this query
     span_ctx: Option<SpanContext>,
+
+    /// Source of the current time for the query, such as for `now()`
+    time_provider: Arc<dyn TimeProvider>,
 }
 
 impl fmt::Debug for IOxSessionConfig {
@@ -202,6 +206,7 @@ impl IOxSessionConfig {
             runtime,
             default_catalog: None,
             span_ctx: None,
+            time_provider: Arc::new(SystemProvider::new()),
         }
     }
 
@@ -226,6 +231,14 @@ impl IOxSessionConfig {
         Self { span_ctx, ..self }
     }
 
+    /// Set the source of the current time for this query
+    pub fn with_time_provider(self, time_provider: Arc<dyn TimeProvider>) -> Self {
+        Self {
+            time_provider,
+            ..self
+        }
+    }
+
     /// Set DataFusion [config option].
     ///
     /// May be used to set [IOx-specific] option as well.
@@ -268,7 +281,7 @@ impl IOxSessionConfig {
             inner.register_catalog(DEFAULT_CATALOG, default_catalog);
         }
 
-        IOxSessionContext::new(inner, self.exec, recorder)
+        IOxSessionContext::new(inner, self.exec, recorder, self.time_provider)
     }
 }
 
@@ -297,6 +310,9 @@ pub struct IOxSessionContext {
 
     /// Span context from which to create spans for this query
     recorder: SpanRecorder,
+
+    /// Source of the current time for the query, such as for `now()`
+    time_provider: Arc<dyn TimeProvider>,
 }
 
 impl fmt::Debug for IOxSessionContext {
@@ -305,6 +321,7 @@ impl fmt::Debug for IOxSessionContext {
             .field("inner", &"<DataFusion ExecutionContext>")
             .field("exec", &self.exec)
             .field("recorder", &self.recorder)
+            .field("time_provider", &self.time_provider)
             .finish()
     }
 }
@@ -319,6 +336,7 @@ impl IOxSessionContext {
             inner: SessionContext::default(),
             exec: DedicatedExecutor::new_testing(),
             recorder: SpanRecorder::default(),
+            time_provider: Arc::new(SystemProvider::new()),
         }
     }
 
@@ -327,11 +345,13 @@ impl IOxSessionContext {
         inner: SessionContext,
         exec: DedicatedExecutor,
         recorder: SpanRecorder,
+        time_provider: Arc<dyn TimeProvider>,
     ) -> Self {
         Self {
             inner,
             exec,
             recorder,
+            time_provider,
         }
     }
 
@@ -340,6 +360,11 @@ impl IOxSessionContext {
         &self.inner
     }
 
+    /// Returns the source of the current time for this query
+    pub fn time_provider(&self) -> &Arc<dyn TimeProvider> {
+        &self.time_provider
+    }
+
     /// Plan a SQL statement. This assumes that any tables referenced
     /// in the SQL have been registered with this context. Use
     /// `create_physical_plan` to actually execute the query.
@@ -667,6 +692,7 @@ impl IOxSessionContext {
             self.inner.clone(),
             self.exec.clone(),
             self.recorder.child(name),
+            Arc::clone(&self.time_provider),
         )
     }
 
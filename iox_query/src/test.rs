@@ -156,7 +156,7 @@ impl QueryNamespace for TestDatabase {
             .collect::<Vec<_>>())
     }
 
-    fn retention_time_ns(&self) -> Option<i64> {
+    fn retention_time_ns(&self, _table_name: &str) -> Option<i64> {
         self.retention_time_ns
     }
 
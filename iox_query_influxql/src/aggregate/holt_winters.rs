@@ -0,0 +1,653 @@
+use crate::error;
+use arrow::array::{as_list_array, Array, ArrayRef, BooleanArray, Float64Array, Int64Array};
+use arrow::compute::cast;
+use arrow::datatypes::{DataType, Field, Fields, TimeUnit};
+use datafusion::common::{downcast_value, DataFusionError, Result, ScalarValue};
+use datafusion::logical_expr::{Accumulator, Signature, TypeSignature, Volatility};
+use once_cell::sync::Lazy;
+use std::sync::Arc;
+
+/// The name of the holt_winters aggregate function.
+pub(super) const NAME: &str = "holt_winters";
+
+/// Valid signatures for the holt_winters aggregate function.
+///
+/// The arguments are the value and time of each point, followed by the
+/// number of points to forecast, the seasonal pattern length, the interval
+/// between points, in nanoseconds, and whether to include the fitted values.
+pub(super) static SIGNATURE: Lazy<Signature> = Lazy::new(|| {
+    Signature::one_of(
+        crate::NUMERICS
+            .iter()
+            .map(|dt| {
+                TypeSignature::Exact(vec![
+                    dt.clone(),
+                    DataType::Timestamp(TimeUnit::Nanosecond, None),
+                    DataType::Int64,
+                    DataType::Int64,
+                    DataType::Int64,
+                    DataType::Boolean,
+                ])
+            })
+            .collect(),
+        Volatility::Immutable,
+    )
+});
+
+/// The fields of each point produced by the holt_winters aggregate.
+static POINT_FIELDS: Lazy<Fields> = Lazy::new(|| {
+    Fields::from(vec![
+        Field::new(
+            "time",
+            DataType::Timestamp(TimeUnit::Nanosecond, None),
+            false,
+        ),
+        Field::new("value", DataType::Float64, false),
+    ])
+});
+
+/// Calculate the return type given the function signature. The aggregate
+/// produces a list of points, which the planner unnests into rows.
+pub(super) fn return_type(_: &[DataType]) -> Result<Arc<DataType>> {
+    Ok(Arc::new(DataType::List(Arc::new(Field::new(
+        "item",
+        DataType::Struct(POINT_FIELDS.clone()),
+        true,
+    )))))
+}
+
+/// Create a new accumulator for the data type.
+pub(super) fn accumulator(_: &DataType) -> Result<Box<dyn Accumulator>> {
+    Ok(Box::new(HoltWintersAccumulator::default()))
+}
+
+/// Calculate the intermediate merge state for the aggregator, which is
+/// the timestamps and values of the points, followed by the arguments.
+pub(super) fn state_type(_: &DataType) -> Result<Arc<Vec<DataType>>> {
+    Ok(Arc::new(vec![
+        DataType::List(Arc::new(Field::new("item", DataType::Int64, true))),
+        DataType::List(Arc::new(Field::new("item", DataType::Float64, true))),
+        DataType::Int64,
+        DataType::Int64,
+        DataType::Int64,
+        DataType::Boolean,
+    ]))
+}
+
+/// The constant arguments of the holt_winters function.
+#[derive(Debug, Clone, Copy)]
+struct Config {
+    /// The number of points to forecast.
+    h: i64,
+    /// The length of the seasonal pattern.
+    m: i64,
+    /// The interval between points, in nanoseconds.
+    interval: i64,
+    /// `true` when the fitted values are included in the output.
+    include_fit: bool,
+}
+
+/// Accumulator forecasting the points of a series using the Holt-Winters
+/// method, as implemented by InfluxQL.
+///
+/// See: <https://github.com/influxdata/influxdb/blob/98361e207349a3643bcc332d54b009818fe7585f/query/functions.go#L113>
+#[derive(Debug, Default)]
+struct HoltWintersAccumulator {
+    /// The timestamp and value of each point.
+    points: Vec<(i64, f64)>,
+    config: Option<Config>,
+}
+
+impl HoltWintersAccumulator {
+    fn update(&mut self, times: &ArrayRef, values: &ArrayRef) -> Result<()> {
+        let times = cast(times, &DataType::Int64)?;
+        let times = downcast_value!(times, Int64Array);
+        let values = cast(values, &DataType::Float64)?;
+        let values = downcast_value!(values, Float64Array);
+
+        self.points.extend(
+            times
+                .iter()
+                .zip(values.iter())
+                .filter_map(|(t, v)| Some((t?, v?))),
+        );
+        Ok(())
+    }
+}
+
+impl Accumulator for HoltWintersAccumulator {
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        assert_eq!(values.len(), 6);
+
+        // INVARIANT:
+        // The planner guarantees that the last four arguments are always
+        // literals.
+        if self.config.is_none() && !values[0].is_empty() {
+            let arg = |idx: usize| match ScalarValue::try_from_array(&values[idx], 0)? {
+                ScalarValue::Int64(Some(v)) => Ok(v),
+                v => error::internal(format!("invalid argument ({v}) for HOLT_WINTERS")),
+            };
+            let include_fit = match ScalarValue::try_from_array(&values[5], 0)? {
+                ScalarValue::Boolean(Some(v)) => v,
+                v => return error::internal(format!("invalid argument ({v}) for HOLT_WINTERS")),
+            };
+            self.config = Some(Config {
+                h: arg(2)?,
+                m: arg(3)?,
+                interval: arg(4)?,
+                include_fit,
+            });
+        }
+
+        self.update(&values[1], &values[0])
+    }
+
+    fn evaluate(&self) -> Result<ScalarValue> {
+        let points = match self.config {
+            Some(config) => {
+                let mut points = self.points.clone();
+                points.sort_by_key(|(t, _)| *t);
+                HoltWinters::new(config).forecast(&points)
+            }
+            None => vec![],
+        };
+
+        Ok(ScalarValue::new_list(
+            Some(
+                points
+                    .into_iter()
+                    .map(|(t, v)| {
+                        ScalarValue::Struct(
+                            Some(vec![
+                                ScalarValue::TimestampNanosecond(Some(t), None),
+                                ScalarValue::Float64(Some(v)),
+                            ]),
+                            POINT_FIELDS.clone(),
+                        )
+                    })
+                    .collect(),
+            ),
+            DataType::Struct(POINT_FIELDS.clone()),
+        ))
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self) + self.points.capacity() * std::mem::size_of::<(i64, f64)>()
+    }
+
+    fn state(&self) -> Result<Vec<ScalarValue>> {
+        let (times, values): (Vec<_>, Vec<_>) = self
+            .points
+            .iter()
+            .map(|(t, v)| (ScalarValue::Int64(Some(*t)), ScalarValue::Float64(Some(*v))))
+            .unzip();
+        Ok(vec![
+            ScalarValue::new_list(Some(times), DataType::Int64),
+            ScalarValue::new_list(Some(values), DataType::Float64),
+            ScalarValue::Int64(self.config.map(|c| c.h)),
+            ScalarValue::Int64(self.config.map(|c| c.m)),
+            ScalarValue::Int64(self.config.map(|c| c.interval)),
+            ScalarValue::Boolean(self.config.map(|c| c.include_fit)),
+        ])
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        assert_eq!(states.len(), 6);
+
+        if self.config.is_none() {
+            let h = downcast_value!(states[2], Int64Array);
+            let m = downcast_value!(states[3], Int64Array);
+            let interval = downcast_value!(states[4], Int64Array);
+            let include_fit = downcast_value!(states[5], BooleanArray);
+            self.config = (0..h.len()).find_map(|idx| {
+                Some(Config {
+                    h: h.is_valid(idx).then(|| h.value(idx))?,
+                    m: m.value(idx),
+                    interval: interval.value(idx),
+                    include_fit: include_fit.value(idx),
+                })
+            });
+        }
+
+        let times = as_list_array(&states[0]);
+        let values = as_list_array(&states[1]);
+        for idx in 0..times.len() {
+            self.update(&times.value(idx), &values.value(idx))?;
+        }
+        Ok(())
+    }
+}
+
+/// Arbitrary weight for initializing some initial guesses, in the
+/// range [0, 1].
+const HW_WEIGHT: f64 = 0.5;
+/// Epsilon value for the minimization process.
+const HW_DEFAULT_EPSILON: f64 = 1.0e-4;
+/// The grid of initial guesses for each of the alpha, beta, gamma and phi
+/// parameters. The grid is N^4, so N is kept small.
+const HW_GUESSES: [f64; 2] = [0.3, 0.7];
+
+/// The Holt-Winters forecasting method of InfluxQL, including its choice
+/// of initial values and the Nelder-Mead optimization of the parameters.
+#[derive(Debug)]
+struct HoltWinters {
+    config: Config,
+    seasonal: bool,
+    /// The values of the series, with `NaN` for missing intervals.
+    y: Vec<f64>,
+}
+
+impl HoltWinters {
+    fn new(config: Config) -> Self {
+        Self {
+            config,
+            seasonal: config.m >= 2,
+            y: vec![],
+        }
+    }
+
+    /// Round `t` to the nearest interval.
+    fn round_time(&self, t: i64) -> i64 {
+        let interval = self.config.interval;
+        if t % interval > interval / 2 {
+            (t / interval + 1) * interval
+        } else {
+            (t / interval) * interval
+        }
+    }
+
+    /// Forecast the series of `points`, which must be sorted by time.
+    fn forecast(mut self, points: &[(i64, f64)]) -> Vec<(i64, f64)> {
+        let Config {
+            h,
+            m,
+            interval,
+            include_fit,
+        } = self.config;
+        let m = m as usize;
+        if points.len() < 2 || self.seasonal && points.len() < m || h <= 0 || interval <= 0 {
+            return vec![];
+        }
+
+        // Fill in the values, with NaN for missing intervals.
+        let (first_time, first_value) = points[0];
+        let (last_time, _) = points[points.len() - 1];
+        let start = self.round_time(first_time);
+        if self.round_time(last_time) <= start {
+            return vec![];
+        }
+        self.y.push(first_value);
+        let mut t = start;
+        for &(time, value) in &points[1..] {
+            let rounded = self.round_time(time);
+            if rounded <= t {
+                // Drop values that occur for the same interval.
+                continue;
+            }
+            t += interval;
+            while rounded != t {
+                self.y.push(f64::NAN);
+                t += interval;
+            }
+            self.y.push(value);
+        }
+        if self.seasonal && self.y.len() < m {
+            return vec![];
+        }
+
+        // Starting guesses. Missing values are skipped, as these are only
+        // guesses.
+        let l0 = if self.seasonal {
+            self.y[..m]
+                .iter()
+                .filter(|v| !v.is_nan())
+                .map(|v| v / m as f64)
+                .sum()
+        } else {
+            HW_WEIGHT * self.y[0]
+        };
+
+        let b0 = if self.seasonal {
+            (0..m)
+                .take_while(|i| m + i < self.y.len())
+                .filter(|&i| !self.y[i].is_nan() && !self.y[m + i].is_nan())
+                .map(|i| (self.y[m + i] - self.y[i]) / (m * m) as f64)
+                .sum()
+        } else if !self.y[1].is_nan() {
+            HW_WEIGHT * (self.y[1] - self.y[0])
+        } else {
+            0.0
+        };
+
+        let mut parameters = vec![0.0, 0.0, 0.0, 0.0, l0, b0];
+        if self.seasonal {
+            parameters.extend(
+                self.y[..m]
+                    .iter()
+                    .map(|v| if v.is_nan() { 0.0 } else { v / l0 }),
+            );
+        }
+
+        // Determine the best fit for the parameters.
+        let mut best: Option<(f64, Vec<f64>)> = None;
+        for alpha in HW_GUESSES {
+            for beta in HW_GUESSES {
+                for gamma in HW_GUESSES {
+                    for phi in HW_GUESSES {
+                        parameters[..4].copy_from_slice(&[alpha, beta, gamma, phi]);
+                        let (sse, params) = nelder_mead::optimize(
+                            |params| self.sse(params),
+                            &parameters,
+                            HW_DEFAULT_EPSILON,
+                            1.0,
+                        );
+                        if best.as_ref().map_or(true, |(min, _)| sse < *min) {
+                            best = Some((sse, params));
+                        }
+                    }
+                }
+            }
+        }
+        let Some((_, mut params)) = best else {
+            return vec![];
+        };
+
+        let forecasted = self.forecast_values(h as usize, &mut params);
+        if include_fit {
+            forecasted
+                .into_iter()
+                .enumerate()
+                .filter(|(_, v)| !v.is_nan())
+                .map(|(i, v)| (first_time + interval * i as i64, v))
+                .collect()
+        } else {
+            forecasted[self.y.len()..]
+                .iter()
+                .enumerate()
+                .filter(|(_, v)| !v.is_nan())
+                .map(|(i, v)| (last_time + interval * (i as i64 + 1), *v))
+                .collect()
+        }
+    }
+
+    /// Compute the fitted values of the series, followed by `h` forecast
+    /// values, constraining `params` in place.
+    fn forecast_values(&self, h: usize, params: &mut [f64]) -> Vec<f64> {
+        // Constrain alpha, beta, gamma and phi to the range [0, 1].
+        for p in &mut params[..4] {
+            *p = p.clamp(0.0, 1.0);
+        }
+        let (alpha, beta, gamma, phi) = (params[0], params[1], params[2], params[3]);
+        let mut phi_h = phi;
+        let mut y_t = self.y[0];
+        let mut l_t = params[4];
+        let mut b_t = params[5];
+
+        // The seasonals are a ring buffer of past sT values.
+        let seasonals = &mut params[6..];
+        let m = seasonals.len();
+        if m == 1 {
+            seasonals[0] = 1.0;
+        }
+        // Season index offset
+        let mut so = m.saturating_sub(1);
+
+        let l = self.y.len();
+        let mut forecasted = Vec::with_capacity(l + h);
+        forecasted.push(y_t);
+        for t in 1..l + h {
+            let (s_tm, s_tmh) = if self.seasonal {
+                let hm = t % m;
+                (
+                    seasonals[(t + so - m) % m],
+                    seasonals[(t + so + hm - m) % m],
+                )
+            } else {
+                (1.0, 1.0)
+            };
+
+            let l_tp = l_t;
+            let b_tp = b_t;
+            l_t = alpha * (y_t / s_tm) + (1.0 - alpha) * (l_tp + phi * b_tp);
+            b_t = beta * (l_t - l_tp) + (1.0 - beta) * phi * b_tp;
+            let s_t = gamma * (y_t / (l_tp + phi * b_tp)) + (1.0 - gamma) * s_tm;
+            y_t = (l_t + phi_h * b_t) * s_tmh;
+            phi_h += phi.powi(t as i32);
+
+            if self.seasonal {
+                seasonals[(t + so) % m] = s_t;
+                so += 1;
+            }
+
+            forecasted.push(y_t);
+        }
+        forecasted
+    }
+
+    /// Compute the sum squared error of the fitted values for `params`.
+    fn sse(&self, params: &mut [f64]) -> f64 {
+        let forecasted = self.forecast_values(0, params);
+        let mut sse = 0.0;
+        for (f, y) in forecasted.iter().zip(&self.y) {
+            // Skip missing values, as they cannot be used to compute an error.
+            if y.is_nan() {
+                continue;
+            }
+            if f.is_nan() {
+                // Penalize forecasted NaNs.
+                return f64::INFINITY;
+            }
+            sse += (f - y) * (f - y);
+        }
+        sse
+    }
+}
+
+/// The Nelder-Mead minimization of InfluxQL.
+///
+/// See: <https://github.com/influxdata/influxdb/blob/98361e207349a3643bcc332d54b009818fe7585f/query/neldermead/neldermead.go>
+mod nelder_mead {
+    const MAX_ITERATIONS: usize = 1000;
+    /// Reflection coefficient
+    const ALPHA: f64 = 1.0;
+    /// Contraction coefficient
+    const BETA: f64 = 0.5;
+    /// Expansion coefficient
+    const GAMMA: f64 = 2.0;
+
+    /// Minimize `objfunc` from `start`, returning the minimum value and the
+    /// parameters producing it.
+    ///
+    /// As in InfluxQL, `objfunc` may modify the parameters it is evaluated
+    /// with, such as to constrain them.
+    pub(super) fn optimize(
+        mut objfunc: impl FnMut(&mut [f64]) -> f64,
+        start: &[f64],
+        epsilon: f64,
+        scale: f64,
+    ) -> (f64, Vec<f64>) {
+        let n = start.len();
+        let nf = n as f64;
+
+        // Create the initial simplex, with one vertex at the start.
+        let pn = scale * ((nf + 1.0).sqrt() - 1.0 + nf) / (nf * 2f64.sqrt());
+        let qn = scale * ((nf + 1.0).sqrt() - 1.0) / (nf * 2f64.sqrt());
+        let mut v = vec![start.to_vec()];
+        for i in 1..=n {
+            v.push(
+                start
+                    .iter()
+                    .enumerate()
+                    .map(|(j, s)| if i - 1 == j { pn + s } else { qn + s })
+                    .collect(),
+            );
+        }
+
+        // The value of the function at each vertex.
+        let mut f = v.iter_mut().map(|v| objfunc(v)).collect::<Vec<_>>();
+
+        for _ in 0..MAX_ITERATIONS {
+            // Find the indexes of the largest and smallest values.
+            let (mut vg, mut vs) = (0, 0);
+            for (i, fi) in f.iter().enumerate() {
+                if *fi > f[vg] {
+                    vg = i;
+                }
+                if *fi < f[vs] {
+                    vs = i;
+                }
+            }
+            // Find the index of the second largest value.
+            let mut vh = vs;
+            for (i, fi) in f.iter().enumerate() {
+                if *fi > f[vh] && *fi < f[vg] {
+                    vh = i;
+                }
+            }
+
+            // Calculate the centroid, excluding the largest value.
+            let vm = (0..n)
+                .map(|i| (0..=n).filter(|&m| m != vg).map(|m| v[m][i]).sum::<f64>() / nf)
+                .collect::<Vec<_>>();
+
+            // Reflect vg to a new vertex.
+            let mut vr = (0..n)
+                .map(|i| vm[i] + ALPHA * (vm[i] - v[vg][i]))
+                .collect::<Vec<_>>();
+            let fr = objfunc(&mut vr);
+
+            if fr < f[vh] && fr >= f[vs] {
+                v[vg].copy_from_slice(&vr);
+                f[vg] = fr;
+            }
+
+            // Investigate a step further in this direction.
+            if fr < f[vs] {
+                let mut ve = (0..n)
+                    .map(|i| vm[i] + GAMMA * (vr[i] - vm[i]))
+                    .collect::<Vec<_>>();
+                let fe = objfunc(&mut ve);
+
+                if fe < fr {
+                    v[vg].copy_from_slice(&ve);
+                    f[vg] = fe;
+                } else {
+                    v[vg].copy_from_slice(&vr);
+                    f[vg] = fr;
+                }
+            }
+
+            // Check if a contraction is necessary.
+            if fr >= f[vh] {
+                let mut vc = if fr < f[vg] {
+                    // Outside contraction
+                    (0..n)
+                        .map(|i| vm[i] + BETA * (vr[i] - vm[i]))
+                        .collect::<Vec<_>>()
+                } else {
+                    // Inside contraction
+                    (0..n)
+                        .map(|i| vm[i] - BETA * (vm[i] - v[vg][i]))
+                        .collect::<Vec<_>>()
+                };
+                let fc = objfunc(&mut vc);
+
+                if fc < f[vg] {
+                    v[vg].copy_from_slice(&vc);
+                    f[vg] = fc;
+                } else {
+                    // The contraction is not successful, so halve the distance
+                    // from vs to all the vertices of the simplex.
+                    let smallest = v[vs].clone();
+                    for (row, vertex) in v.iter_mut().enumerate() {
+                        if row != vs {
+                            for (x, s) in vertex.iter_mut().zip(&smallest) {
+                                *x = s + (*x - s) / 2.0;
+                            }
+                        }
+                    }
+                    f[vg] = objfunc(&mut v[vg]);
+                    f[vh] = objfunc(&mut v[vh]);
+                }
+            }
+
+            // Test for convergence.
+            let favg = f.iter().sum::<f64>() / (nf + 1.0);
+            let s = f.iter().map(|f| (f - favg).powi(2) / nf).sum::<f64>();
+            if s.sqrt() < epsilon {
+                break;
+            }
+        }
+
+        // Find the index of the smallest value.
+        let mut vs = 0;
+        for (i, fi) in f.iter().enumerate() {
+            if *fi < f[vs] {
+                vs = i;
+            }
+        }
+
+        // The parameters are returned as they were before evaluating the
+        // function for the minimum.
+        let min = objfunc(&mut v[vs].clone());
+        (min, v.swap_remove(vs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INTERVAL: i64 = 10;
+
+    fn forecast(points: &[(i64, f64)], h: i64, m: i64, include_fit: bool) -> Vec<(i64, f64)> {
+        HoltWinters::new(Config {
+            h,
+            m,
+            interval: INTERVAL,
+            include_fit,
+        })
+        .forecast(points)
+    }
+
+    #[test]
+    fn test_forecast() {
+        let points = (0..10)
+            .map(|i| (i * INTERVAL, (i % 3) as f64 + i as f64 + 1.0))
+            .collect::<Vec<_>>();
+
+        let got = forecast(&points, 4, 3, false);
+        assert_eq!(
+            got.iter().map(|(t, _)| *t).collect::<Vec<_>>(),
+            vec![100, 110, 120, 130]
+        );
+        assert!(got.iter().all(|(_, v)| v.is_finite()));
+
+        let got = forecast(&points, 4, 3, true);
+        assert_eq!(
+            got.iter().map(|(t, _)| *t).collect::<Vec<_>>(),
+            (0..14).map(|i| i * INTERVAL).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_forecast_missing_intervals() {
+        // The missing point at 20 is forecast when fitting.
+        let points = vec![(0, 1.0), (10, 2.0), (30, 4.0), (40, 5.0)];
+        let got = forecast(&points, 1, 0, true);
+        assert_eq!(
+            got.iter().map(|(t, _)| *t).collect::<Vec<_>>(),
+            vec![0, 10, 20, 30, 40, 50]
+        );
+    }
+
+    #[test]
+    fn test_forecast_insufficient_points() {
+        assert!(forecast(&[], 2, 0, false).is_empty());
+        assert!(forecast(&[(0, 1.0)], 2, 0, false).is_empty());
+        // Fewer points than the seasonal pattern length
+        assert!(forecast(&[(0, 1.0), (10, 2.0)], 2, 3, false).is_empty());
+        // All points in the same interval
+        assert!(forecast(&[(0, 1.0), (1, 2.0)], 2, 0, false).is_empty());
+    }
+}
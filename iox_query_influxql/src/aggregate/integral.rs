@@ -0,0 +1,150 @@
+use crate::error;
+use arrow::array::{as_list_array, Array, ArrayRef, Float64Array, Int64Array};
+use arrow::compute::cast;
+use arrow::datatypes::{DataType, Field, IntervalUnit, TimeUnit};
+use datafusion::common::{downcast_value, DataFusionError, Result, ScalarValue};
+use datafusion::logical_expr::{Accumulator, Signature, TypeSignature, Volatility};
+use once_cell::sync::Lazy;
+use std::sync::Arc;
+
+/// The name of the integral aggregate function.
+pub(super) const NAME: &str = "integral";
+
+/// Valid signatures for the integral aggregate function.
+pub(super) static SIGNATURE: Lazy<Signature> = Lazy::new(|| {
+    Signature::one_of(
+        crate::NUMERICS
+            .iter()
+            .map(|dt| {
+                TypeSignature::Exact(vec![
+                    dt.clone(),
+                    DataType::Interval(IntervalUnit::MonthDayNano),
+                    DataType::Timestamp(TimeUnit::Nanosecond, None),
+                ])
+            })
+            .collect(),
+        Volatility::Immutable,
+    )
+});
+
+/// Calculate the return type given the function signature.
+pub(super) fn return_type(_: &[DataType]) -> Result<Arc<DataType>> {
+    Ok(Arc::new(DataType::Float64))
+}
+
+/// Create a new accumulator for the data type.
+pub(super) fn accumulator(_: &DataType) -> Result<Box<dyn Accumulator>> {
+    Ok(Box::new(IntegralAccumulator::default()))
+}
+
+/// Calculate the intermediate merge state for the aggregator, which is
+/// the timestamps and values of the points, followed by the unit.
+pub(super) fn state_type(_: &DataType) -> Result<Arc<Vec<DataType>>> {
+    Ok(Arc::new(vec![
+        DataType::List(Arc::new(Field::new("item", DataType::Int64, true))),
+        DataType::List(Arc::new(Field::new("item", DataType::Float64, true))),
+        DataType::Int64,
+    ]))
+}
+
+/// Accumulator computing the area under the curve of the points, using
+/// the trapezoidal rule, in multiples of the unit duration.
+///
+/// The points are not interpolated at the boundaries of a `GROUP BY time`
+/// interval like InfluxQL does, so the planner rejects `integral` with
+/// `GROUP BY time`.
+#[derive(Debug, Default)]
+struct IntegralAccumulator {
+    /// The timestamp and value of each point.
+    points: Vec<(i64, f64)>,
+    /// The unit duration, in nanoseconds.
+    unit: Option<i64>,
+}
+
+impl IntegralAccumulator {
+    fn update(&mut self, times: &ArrayRef, values: &ArrayRef) -> Result<()> {
+        let times = cast(times, &DataType::Int64)?;
+        let times = downcast_value!(times, Int64Array);
+        let values = cast(values, &DataType::Float64)?;
+        let values = downcast_value!(values, Float64Array);
+
+        self.points.extend(
+            times
+                .iter()
+                .zip(values.iter())
+                .filter_map(|(t, v)| Some((t?, v?))),
+        );
+        Ok(())
+    }
+}
+
+impl Accumulator for IntegralAccumulator {
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        assert_eq!(values.len(), 3);
+
+        // INVARIANT:
+        // The planner guarantees that the second argument is always a duration
+        // literal.
+        if self.unit.is_none() && !values[1].is_empty() {
+            self.unit = match ScalarValue::try_from_array(&values[1], 0)? {
+                ScalarValue::IntervalMonthDayNano(Some(unit)) => Some(unit as i64),
+                v => return error::internal(format!("invalid unit ({v}) for INTEGRAL")),
+            };
+        }
+
+        self.update(&values[2], &values[0])
+    }
+
+    fn evaluate(&self) -> Result<ScalarValue> {
+        if self.points.is_empty() {
+            return Ok(ScalarValue::Float64(None));
+        }
+
+        let mut points = self.points.clone();
+        points.sort_by_key(|(t, _)| *t);
+
+        let area = points
+            .windows(2)
+            .map(|w| {
+                let ((t0, v0), (t1, v1)) = (w[0], w[1]);
+                (v0 + v1) / 2.0 * (t1 - t0) as f64
+            })
+            .sum::<f64>();
+        let unit = self.unit.unwrap_or(1_000_000_000);
+
+        Ok(ScalarValue::Float64(Some(area / unit as f64)))
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self) + self.points.capacity() * std::mem::size_of::<(i64, f64)>()
+    }
+
+    fn state(&self) -> Result<Vec<ScalarValue>> {
+        let (times, values): (Vec<_>, Vec<_>) = self
+            .points
+            .iter()
+            .map(|(t, v)| (ScalarValue::Int64(Some(*t)), ScalarValue::Float64(Some(*v))))
+            .unzip();
+        Ok(vec![
+            ScalarValue::new_list(Some(times), DataType::Int64),
+            ScalarValue::new_list(Some(values), DataType::Float64),
+            ScalarValue::Int64(self.unit),
+        ])
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        assert_eq!(states.len(), 3);
+
+        let units = downcast_value!(states[2], Int64Array);
+        if self.unit.is_none() {
+            self.unit = units.iter().flatten().next();
+        }
+
+        let times = as_list_array(&states[0]);
+        let values = as_list_array(&states[1]);
+        for idx in 0..times.len() {
+            self.update(&times.value(idx), &values.value(idx))?;
+        }
+        Ok(())
+    }
+}
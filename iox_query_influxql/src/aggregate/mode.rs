@@ -0,0 +1,130 @@
+use arrow::array::{as_list_array, Array, ArrayRef, Int64Array};
+use arrow::datatypes::{DataType, Field};
+use datafusion::common::{downcast_value, DataFusionError, Result, ScalarValue};
+use datafusion::logical_expr::{Accumulator, Signature, TypeSignature, Volatility};
+use once_cell::sync::Lazy;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// The name of the mode aggregate function.
+pub(super) const NAME: &str = "mode";
+
+/// Valid signatures for the mode aggregate function.
+pub(super) static SIGNATURE: Lazy<Signature> = Lazy::new(|| {
+    Signature::one_of(
+        crate::NUMERICS
+            .iter()
+            .chain(&[DataType::Utf8, DataType::Boolean])
+            .map(|dt| TypeSignature::Exact(vec![dt.clone()]))
+            .collect(),
+        Volatility::Immutable,
+    )
+});
+
+/// Calculate the return type given the function signature. Mode
+/// always returns the same type as the input column.
+pub(super) fn return_type(signature: &[DataType]) -> Result<Arc<DataType>> {
+    Ok(Arc::new(signature[0].clone()))
+}
+
+/// Create a new accumulator for the data type.
+pub(super) fn accumulator(dt: &DataType) -> Result<Box<dyn Accumulator>> {
+    Ok(Box::new(ModeAccumulator::new(dt.clone())))
+}
+
+/// Calculate the intermediate merge state for the aggregator, which is
+/// the list of distinct values and the number of times each was seen.
+pub(super) fn state_type(dt: &DataType) -> Result<Arc<Vec<DataType>>> {
+    Ok(Arc::new(vec![
+        DataType::List(Arc::new(Field::new("item", dt.clone(), true))),
+        DataType::List(Arc::new(Field::new("item", DataType::Int64, true))),
+    ]))
+}
+
+#[derive(Debug)]
+struct ModeAccumulator {
+    data_type: DataType,
+    counts: HashMap<ScalarValue, i64>,
+}
+
+impl ModeAccumulator {
+    fn new(data_type: DataType) -> Self {
+        Self {
+            data_type,
+            counts: HashMap::new(),
+        }
+    }
+
+    fn update(&mut self, values: &ArrayRef, counts: Option<&Int64Array>) -> Result<()> {
+        assert_eq!(values.data_type(), &self.data_type);
+
+        for idx in 0..values.len() {
+            if values.is_valid(idx) {
+                let n = counts.map_or(1, |c| c.value(idx));
+                *self
+                    .counts
+                    .entry(ScalarValue::try_from_array(values, idx)?)
+                    .or_default() += n;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Accumulator for ModeAccumulator {
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        assert_eq!(values.len(), 1);
+
+        self.update(&values[0], None)
+    }
+
+    /// Returns the most frequent value. As with InfluxQL, when several values
+    /// are equally frequent the smallest is returned.
+    fn evaluate(&self) -> Result<ScalarValue> {
+        match self.counts.iter().max_by(|(a, a_count), (b, b_count)| {
+            a_count
+                .cmp(b_count)
+                .then_with(|| b.partial_cmp(a).unwrap_or(Ordering::Equal))
+        }) {
+            Some((value, _)) => Ok(value.clone()),
+            None => ScalarValue::try_from(&self.data_type),
+        }
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self)
+            + self
+                .counts
+                .keys()
+                .map(|v| v.size() + std::mem::size_of::<i64>())
+                .sum::<usize>()
+    }
+
+    fn state(&self) -> Result<Vec<ScalarValue>> {
+        let (values, counts): (Vec<_>, Vec<_>) = self
+            .counts
+            .iter()
+            .map(|(v, n)| (v.clone(), ScalarValue::Int64(Some(*n))))
+            .unzip();
+        Ok(vec![
+            ScalarValue::new_list(Some(values), self.data_type.clone()),
+            ScalarValue::new_list(Some(counts), DataType::Int64),
+        ])
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        assert_eq!(states.len(), 2);
+
+        let values = as_list_array(&states[0]);
+        let counts = as_list_array(&states[1]);
+        for idx in 0..values.len() {
+            let counts = counts.value(idx);
+            self.update(
+                &values.value(idx),
+                Some(downcast_value!(counts, Int64Array)),
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,98 @@
+use arrow::array::{Array, ArrayRef};
+use arrow::datatypes::DataType;
+use datafusion::common::{Result, ScalarValue};
+use datafusion::logical_expr::{Accumulator, Signature, TypeSignature, Volatility};
+use once_cell::sync::Lazy;
+use std::sync::Arc;
+
+/// The name of the spread aggregate function.
+pub(super) const NAME: &str = "spread";
+
+/// Valid signatures for the spread aggregate function.
+pub(super) static SIGNATURE: Lazy<Signature> = Lazy::new(|| {
+    Signature::one_of(
+        crate::NUMERICS
+            .iter()
+            .map(|dt| TypeSignature::Exact(vec![dt.clone()]))
+            .collect(),
+        Volatility::Immutable,
+    )
+});
+
+/// Calculate the return type given the function signature. Spread
+/// always returns the same type as the input column.
+pub(super) fn return_type(signature: &[DataType]) -> Result<Arc<DataType>> {
+    Ok(Arc::new(signature[0].clone()))
+}
+
+/// Create a new accumulator for the data type.
+pub(super) fn accumulator(dt: &DataType) -> Result<Box<dyn Accumulator>> {
+    Ok(Box::new(SpreadAccumulator::try_new(dt)?))
+}
+
+/// Calculate the intermediate merge state for the aggregator, which is
+/// the minimum and maximum values seen.
+pub(super) fn state_type(dt: &DataType) -> Result<Arc<Vec<DataType>>> {
+    Ok(Arc::new(vec![dt.clone(), dt.clone()]))
+}
+
+#[derive(Debug)]
+struct SpreadAccumulator {
+    min: ScalarValue,
+    max: ScalarValue,
+}
+
+impl SpreadAccumulator {
+    fn try_new(data_type: &DataType) -> Result<Self> {
+        Ok(Self {
+            min: ScalarValue::try_from(data_type)?,
+            max: ScalarValue::try_from(data_type)?,
+        })
+    }
+
+    fn update(&mut self, array: &ArrayRef) -> Result<()> {
+        for idx in 0..array.len() {
+            if array.is_null(idx) {
+                continue;
+            }
+            let v = ScalarValue::try_from_array(array, idx)?;
+            if self.min.is_null() || v < self.min {
+                self.min = v.clone();
+            }
+            if self.max.is_null() || v > self.max {
+                self.max = v;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Accumulator for SpreadAccumulator {
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        assert_eq!(values.len(), 1);
+
+        self.update(&values[0])
+    }
+
+    fn evaluate(&self) -> Result<ScalarValue> {
+        if self.min.is_null() {
+            return Ok(self.min.clone());
+        }
+        self.max.sub(&self.min)
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self) + self.min.size() + self.max.size()
+    }
+
+    fn state(&self) -> Result<Vec<ScalarValue>> {
+        Ok(vec![self.min.clone(), self.max.clone()])
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        assert_eq!(states.len(), 2);
+
+        self.update(&states[0])?;
+        self.update(&states[1])
+    }
+}
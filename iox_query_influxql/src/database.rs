@@ -0,0 +1,281 @@
+//! Mapping of InfluxQL databases and retention policies onto IOx namespaces.
+//!
+//! A database `db` with retention policy `rp` is stored in the namespace
+//! `db/rp`, mirroring the router's V1 write API. The default retention policy
+//! of a database maps to the namespace `db`.
+
+use std::collections::BTreeSet;
+use std::convert::Infallible;
+
+use influxdb_influxql_parser::common::QualifiedMeasurementName;
+use influxdb_influxql_parser::show::OnClause;
+use influxdb_influxql_parser::show_measurements::{ExtendedOnClause, WithMeasurementClause};
+use influxdb_influxql_parser::simple_from_clause::ShowFromClause;
+use influxdb_influxql_parser::statement::Statement;
+use influxdb_influxql_parser::visit::{Recursion, Visitable, Visitor};
+
+/// Separates the database from the retention policy in a namespace name.
+pub const NAMESPACE_RP_SEPARATOR: char = '/';
+
+/// The name of the retention policy stored in the namespace named after its
+/// database.
+pub const DEFAULT_RETENTION_POLICY: &str = "autogen";
+
+/// The name of the namespace storing the retention policy `rp` of the
+/// database `db`.
+///
+/// An unspecified or empty retention policy, and the names `autogen` and
+/// `default`, select the default retention policy. Some clients send an empty
+/// retention policy as `''`.
+pub fn namespace_name(db: &str, rp: Option<&str>) -> String {
+    match rp.map(str::to_lowercase).as_deref() {
+        None | Some("") | Some("''") | Some(DEFAULT_RETENTION_POLICY) | Some("default") => {
+            db.to_owned()
+        }
+        Some(_) => format!(
+            "{db}{NAMESPACE_RP_SEPARATOR}{rp}",
+            rp = rp.expect("matched above")
+        ),
+    }
+}
+
+/// The name of the database stored in the namespace `namespace`.
+pub fn database_name(namespace: &str) -> &str {
+    namespace
+        .split_once(NAMESPACE_RP_SEPARATOR)
+        .map_or(namespace, |(db, _)| db)
+}
+
+/// The name of the retention policy stored in the namespace `namespace`.
+pub fn retention_policy_name(namespace: &str) -> &str {
+    namespace
+        .split_once(NAMESPACE_RP_SEPARATOR)
+        .map_or(DEFAULT_RETENTION_POLICY, |(_, rp)| rp)
+}
+
+/// The namespaces read by an InfluxQL statement, as returned by
+/// [`statement_namespaces`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct StatementNamespaces {
+    /// Set when the statement reads the namespace it is planned against.
+    pub default: bool,
+
+    /// The other namespaces named by the database and retention policy
+    /// qualifiers and `ON` clauses of the statement.
+    pub named: BTreeSet<String>,
+
+    /// Set when the statement lists the names of all namespaces, such as
+    /// `SHOW DATABASES`.
+    pub list: bool,
+
+    /// Set when the statement reads the namespaces storing the default
+    /// retention policy of every database, such as `SHOW MEASUREMENTS ON *`.
+    pub all_databases: bool,
+
+    /// Set when the statement reads every namespace, such as
+    /// `SHOW MEASUREMENTS ON *.*`.
+    pub all_retention_policies: bool,
+}
+
+impl StatementNamespaces {
+    /// Record the namespace of the database `db` and retention policy `rp`,
+    /// either of which default to those of `namespace`.
+    fn add(&mut self, namespace: Option<&str>, db: Option<&str>, rp: Option<&str>) {
+        if db.is_none() && rp.is_none() {
+            self.default = true;
+            return;
+        }
+
+        match db.or_else(|| namespace.map(database_name)) {
+            Some(db) => {
+                let name = namespace_name(db, rp);
+                if Some(name.as_str()) == namespace {
+                    self.default = true;
+                } else {
+                    self.named.insert(name);
+                }
+            }
+            // The planner reports the missing database.
+            None => self.default = true,
+        }
+    }
+
+    /// Record the namespaces of the measurements of `from`, or of the `ON`
+    /// clause if there is no `FROM` clause.
+    fn add_from(
+        &mut self,
+        namespace: Option<&str>,
+        on: Option<&OnClause>,
+        from: Option<&ShowFromClause>,
+    ) {
+        let on = on.map(|db| db.as_str());
+        match from {
+            Some(from) => from.iter().for_each(|qn| {
+                self.add(
+                    namespace,
+                    qn.database.as_ref().map(|db| db.as_str()).or(on),
+                    qn.retention_policy.as_ref().map(|rp| rp.as_str()),
+                )
+            }),
+            None => self.add(namespace, on, None),
+        }
+    }
+}
+
+/// Returns the namespaces read by `statement` when it is planned against the
+/// namespace `namespace`.
+///
+/// `CREATE DATABASE` statements read no namespaces.
+pub fn statement_namespaces(statement: &Statement, namespace: Option<&str>) -> StatementNamespaces {
+    struct Qualifiers<'a>(StatementNamespaces, Option<&'a str>);
+    impl<'a> Visitor for Qualifiers<'a> {
+        type Error = Infallible;
+
+        fn pre_visit_qualified_measurement_name(
+            mut self,
+            qn: &QualifiedMeasurementName,
+        ) -> Result<Recursion<Self>, Self::Error> {
+            self.0.add(
+                self.1,
+                qn.database.as_ref().map(|db| db.as_str()),
+                qn.retention_policy.as_ref().map(|rp| rp.as_str()),
+            );
+            Ok(Recursion::Continue(self))
+        }
+    }
+
+    let mut out = StatementNamespaces::default();
+    match statement {
+        Statement::CreateDatabase(_) => {}
+        Statement::ShowDatabases(_) | Statement::ShowRetentionPolicies(_) => out.list = true,
+        Statement::Select(_) | Statement::Explain(_) => {
+            out = statement
+                .accept(Qualifiers(out, namespace))
+                .expect("visitor is infallible")
+                .0;
+        }
+        Statement::ShowCardinality(s) => {
+            out.add_from(namespace, s.database.as_ref(), s.from.as_ref())
+        }
+        Statement::ShowFieldKeys(s) => {
+            out.add_from(namespace, s.database.as_ref(), s.from.as_ref())
+        }
+        Statement::ShowSeries(s) => out.add_from(namespace, s.database.as_ref(), s.from.as_ref()),
+        Statement::ShowTagKeys(s) => out.add_from(namespace, s.database.as_ref(), s.from.as_ref()),
+        Statement::ShowTagValues(s) => {
+            out.add_from(namespace, s.database.as_ref(), s.from.as_ref())
+        }
+        Statement::ShowMeasurements(s) => {
+            let (on_db, on_rp) = match &s.on {
+                Some(ExtendedOnClause::Database(db)) => (Some(db.as_str()), None),
+                Some(ExtendedOnClause::DatabaseRetentionPolicy(db, rp)) => {
+                    (Some(db.as_str()), Some(rp.as_str()))
+                }
+                _ => (None, None),
+            };
+            let qn = match &s.with_measurement {
+                Some(WithMeasurementClause::Equals(qn) | WithMeasurementClause::Regex(qn))
+                    if qn.database.is_some() || qn.retention_policy.is_some() =>
+                {
+                    Some(qn)
+                }
+                _ => None,
+            };
+            match (qn, &s.on) {
+                // A qualified measurement name takes precedence over the ON clause.
+                (Some(qn), _) => out.add(
+                    namespace,
+                    qn.database.as_ref().map(|db| db.as_str()).or(on_db),
+                    qn.retention_policy.as_ref().map(|rp| rp.as_str()),
+                ),
+                (None, Some(ExtendedOnClause::AllDatabases)) => {
+                    out.list = true;
+                    out.all_databases = true;
+                }
+                (None, Some(ExtendedOnClause::AllDatabasesAndRetentionPolicies)) => {
+                    out.list = true;
+                    out.all_retention_policies = true;
+                }
+                (None, _) => out.add(namespace, on_db, on_rp),
+            }
+        }
+        // Rejected by the planner, which only needs the default namespace.
+        Statement::Delete(_) | Statement::DropMeasurement(_) => out.default = true,
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use influxdb_influxql_parser::parse_statements;
+
+    #[test]
+    fn test_namespace_name() {
+        assert_eq!(namespace_name("bananas", None), "bananas");
+        assert_eq!(namespace_name("bananas", Some("")), "bananas");
+        assert_eq!(namespace_name("bananas", Some("''")), "bananas");
+        assert_eq!(namespace_name("bananas", Some("autogen")), "bananas");
+        assert_eq!(namespace_name("bananas", Some("Default")), "bananas");
+        assert_eq!(namespace_name("bananas", Some("Ripe")), "bananas/Ripe");
+    }
+
+    #[test]
+    fn test_database_and_retention_policy_name() {
+        assert_eq!(database_name("bananas"), "bananas");
+        assert_eq!(retention_policy_name("bananas"), "autogen");
+        assert_eq!(database_name("bananas/ripe"), "bananas");
+        assert_eq!(retention_policy_name("bananas/ripe"), "ripe");
+    }
+
+    #[test]
+    fn test_statement_namespaces() {
+        fn namespaces(q: &str) -> StatementNamespaces {
+            let statement = parse_statements(q).unwrap().pop().unwrap();
+            statement_namespaces(&statement, Some("bananas"))
+        }
+        fn named(names: &[&str]) -> BTreeSet<String> {
+            names.iter().map(|n| n.to_string()).collect()
+        }
+
+        let got = namespaces("SELECT a FROM cpu");
+        assert!(got.default);
+        assert!(got.named.is_empty());
+
+        // Qualifiers name other namespaces, in subqueries too.
+        let got = namespaces("SELECT a FROM apples..cpu, (SELECT a FROM ripe.cpu)");
+        assert!(!got.default);
+        assert_eq!(got.named, named(&["apples", "bananas/ripe"]));
+
+        // Qualifiers naming the namespace of the statement read the default.
+        let got = namespaces("SELECT a FROM bananas.autogen.cpu");
+        assert!(got.default);
+        assert!(got.named.is_empty());
+
+        // The ON clause applies to unqualified measurements.
+        let got = namespaces("SHOW TAG KEYS ON apples FROM cpu, green.mem");
+        assert!(!got.default);
+        assert_eq!(got.named, named(&["apples", "apples/green"]));
+        let got = namespaces("SHOW FIELD KEYS ON apples");
+        assert_eq!(got.named, named(&["apples"]));
+        let got = namespaces("SHOW MEASUREMENTS ON apples.green");
+        assert_eq!(got.named, named(&["apples/green"]));
+        let got = namespaces("SHOW MEASUREMENTS ON apples WITH MEASUREMENT = green.cpu");
+        assert_eq!(got.named, named(&["apples/green"]));
+        let got = namespaces("SHOW SERIES");
+        assert!(got.default);
+
+        // Statements listing namespaces.
+        let got = namespaces("SHOW DATABASES");
+        assert!(got.list && !got.default);
+        let got = namespaces("SHOW RETENTION POLICIES ON apples");
+        assert!(got.list && !got.default);
+        let got = namespaces("SHOW MEASUREMENTS ON *");
+        assert!(got.list && got.all_databases && !got.all_retention_policies);
+        let got = namespaces("SHOW MEASUREMENTS ON *.*");
+        assert!(got.list && got.all_retention_policies);
+
+        let got = namespaces("CREATE DATABASE apples");
+        assert_eq!(got, StatementNamespaces::default());
+    }
+}
@@ -0,0 +1,57 @@
+//! Values of the bind parameters of an InfluxQL query.
+
+use std::collections::HashMap;
+
+use influxdb_influxql_parser::literal::Literal;
+use serde::{Deserialize, Serialize};
+
+/// The values of the bind parameters of an InfluxQL query, keyed by name.
+pub type StatementParams = HashMap<String, ParamValue>;
+
+/// The value of an InfluxQL bind parameter.
+///
+/// Values are encoded as plain JSON values, like the `params` argument of
+/// the InfluxDB V1 query API.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ParamValue {
+    Boolean(bool),
+    Integer(i64),
+    Unsigned(u64),
+    Float(f64),
+    String(String),
+}
+
+impl From<ParamValue> for Literal {
+    fn from(value: ParamValue) -> Self {
+        match value {
+            ParamValue::Boolean(v) => Self::Boolean(v),
+            ParamValue::Integer(v) => Self::Integer(v),
+            ParamValue::Unsigned(v) => Self::Unsigned(v),
+            ParamValue::Float(v) => Self::Float(v),
+            ParamValue::String(v) => Self::String(v),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_params_from_json() {
+        let params: StatementParams = serde_json::from_str(
+            r#"{"b": true, "i": -1, "u": 18446744073709551615, "f": 1.5, "s": "cpu0"}"#,
+        )
+        .unwrap();
+
+        assert_eq!(params["b"], ParamValue::Boolean(true));
+        assert_eq!(params["i"], ParamValue::Integer(-1));
+        assert_eq!(params["u"], ParamValue::Unsigned(u64::MAX));
+        assert_eq!(params["f"], ParamValue::Float(1.5));
+        assert_eq!(params["s"], ParamValue::String("cpu0".into()));
+
+        // Values other than scalars are rejected
+        serde_json::from_str::<StatementParams>(r#"{"a": [1]}"#).unwrap_err();
+    }
+}
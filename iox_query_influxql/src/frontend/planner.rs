@@ -1,7 +1,12 @@
 use arrow::datatypes::SchemaRef;
+use async_trait::async_trait;
+use datafusion::catalog::schema::SchemaProvider as DataFusionSchemaProvider;
 use datafusion::physical_expr::execution_props::ExecutionProps;
+use influxdb_influxql_parser::create::CreateDatabaseStatement;
+use influxdb_influxql_parser::show_cardinality::ShowCardinalityStatement;
 use influxdb_influxql_parser::show_field_keys::ShowFieldKeysStatement;
 use influxdb_influxql_parser::show_measurements::ShowMeasurementsStatement;
+use influxdb_influxql_parser::show_series::ShowSeriesStatement;
 use influxdb_influxql_parser::show_tag_keys::ShowTagKeysStatement;
 use influxdb_influxql_parser::show_tag_values::ShowTagValuesStatement;
 use std::any::Any;
@@ -11,6 +16,8 @@ use std::fmt::Debug;
 use std::ops::Deref;
 use std::sync::Arc;
 
+use crate::database;
+use crate::frontend::params::StatementParams;
 use crate::plan::{parse_regex, InfluxQLToLogicalPlan, SchemaProvider};
 use datafusion::common::Statistics;
 use datafusion::datasource::provider_as_source;
@@ -25,16 +32,58 @@ use datafusion::{
     physical_plan::ExecutionPlan,
 };
 use influxdb_influxql_parser::common::MeasurementName;
+use influxdb_influxql_parser::parameter::replace_bind_parameters;
 use influxdb_influxql_parser::parse_statements;
 use influxdb_influxql_parser::statement::Statement;
+use influxdb_influxql_parser::timestamp::Timestamp;
 use influxdb_influxql_parser::visit::{Visitable, Visitor};
 use iox_query::exec::IOxSessionContext;
 use observability_deps::tracing::debug;
 use schema::Schema;
 
+/// Access to the namespaces an InfluxQL statement references besides the
+/// namespace it is planned against.
+#[async_trait]
+pub trait NamespaceProvider: Debug + Send + Sync {
+    /// The names of the namespaces listed by statements such as
+    /// `SHOW DATABASES`.
+    async fn namespace_names(&self) -> Result<Vec<String>>;
+
+    /// The tables of the namespace `name`, or `None` if it does not exist.
+    async fn namespace_schema(
+        &self,
+        name: &str,
+    ) -> Result<Option<Arc<dyn DataFusionSchemaProvider>>>;
+
+    /// Create the namespace `name` for a `CREATE DATABASE` statement.
+    ///
+    /// Succeeds if the namespace already exists with the same retention
+    /// period.
+    async fn create_namespace(&self, name: &str, retention_period_ns: Option<i64>) -> Result<()>;
+}
+
+/// The key of the measurement `measurement` of the namespace `namespace`,
+/// when it is not the namespace of the query.
+fn qualified_table_key(namespace: &str, measurement: &str) -> String {
+    format!("{namespace}::{measurement}")
+}
+
 struct ContextSchemaProvider<'a> {
     state: &'a SessionState,
+    /// The time of `now()`, taken from the time provider of the query
+    /// context once per statement.
+    now: Timestamp,
     tables: HashMap<String, (Arc<dyn TableSource>, Schema)>,
+    /// The name of the namespace of the query, if known.
+    namespace: Option<String>,
+    /// The names of all namespaces, if listed by the statement. When listed,
+    /// these replace the namespace of the query, which may not exist.
+    namespace_names: Option<Vec<String>>,
+    /// The table keys of the other namespaces read by the statement.
+    namespace_tables: HashMap<String, Vec<String>>,
+    /// The namespace and measurement name of the tables of other namespaces,
+    /// by table key.
+    qualified_tables: HashMap<String, (String, String)>,
 }
 
 impl<'a> SchemaProvider for ContextSchemaProvider<'a> {
@@ -54,7 +103,11 @@ impl<'a> SchemaProvider for ContextSchemaProvider<'a> {
     }
 
     fn table_names(&self) -> Vec<&'_ str> {
-        self.tables.keys().map(|k| k.as_str()).collect::<Vec<_>>()
+        self.tables
+            .keys()
+            .filter(|k| !self.qualified_tables.contains_key(*k))
+            .map(|k| k.as_str())
+            .collect::<Vec<_>>()
     }
 
     fn table_exists(&self, name: &str) -> bool {
@@ -68,6 +121,75 @@ impl<'a> SchemaProvider for ContextSchemaProvider<'a> {
     fn execution_props(&self) -> &ExecutionProps {
         self.state.execution_props()
     }
+
+    fn now(&self) -> Timestamp {
+        self.now
+    }
+
+    fn namespace_name(&self) -> Option<&'_ str> {
+        self.namespace.as_deref()
+    }
+
+    fn namespace_names(&self) -> Vec<&'_ str> {
+        let listed = match &self.namespace_names {
+            Some(names) => names.iter().collect::<Vec<_>>(),
+            None => self.namespace.iter().collect(),
+        };
+        let mut names = listed
+            .into_iter()
+            .chain(self.namespace_tables.keys())
+            .map(|n| n.as_str())
+            .collect::<Vec<_>>();
+        names.sort_unstable();
+        names.dedup();
+        names
+    }
+
+    fn namespace_table_names(&self, name: &str) -> Option<Vec<&'_ str>> {
+        if self.namespace.as_deref() == Some(name) {
+            return Some(self.table_names());
+        }
+        self.namespace_tables
+            .get(name)
+            .map(|tables| tables.iter().map(|t| t.as_str()).collect())
+    }
+
+    fn table_namespace(&self, name: &str) -> Option<&'_ str> {
+        match self.qualified_tables.get(name) {
+            Some((namespace, _)) => Some(namespace),
+            None => self.namespace.as_deref(),
+        }
+    }
+
+    fn measurement_name<'b>(&'b self, name: &'b str) -> &'b str {
+        self.qualified_tables
+            .get(name)
+            .map_or(name, |(_, measurement)| measurement)
+    }
+}
+
+/// Load the tables of `schema` named in `table_names` into `tables`, under the
+/// key returned by `key`.
+async fn load_tables(
+    tables: &mut HashMap<String, (Arc<dyn TableSource>, Schema)>,
+    schema: &dyn DataFusionSchemaProvider,
+    table_names: &HashSet<String>,
+    key: impl Fn(&str) -> String + Send + Sync,
+) -> Result<()> {
+    use std::collections::hash_map::Entry;
+
+    for table_name in table_names {
+        if let Entry::Vacant(v) = tables.entry(key(table_name)) {
+            if let Some(table) = schema.table(table_name).await {
+                let schema = Schema::try_from(table.schema())
+                    .map_err(|err| {
+                        DataFusionError::Internal(format!("unable to convert DataFusion schema for measurement {table_name} to IOx schema: {err}"))
+                    })?;
+                v.insert((provider_as_source(table), schema));
+            }
+        }
+    }
+    Ok(())
 }
 
 /// A physical operator that overrides the `schema` API,
@@ -137,23 +259,54 @@ impl DisplayAs for SchemaExec {
 
 /// Create plans for running InfluxQL queries against databases
 #[derive(Debug, Default)]
-pub struct InfluxQLQueryPlanner {}
+pub struct InfluxQLQueryPlanner {
+    /// The name of the namespace of the default catalog of the query context.
+    namespace: Option<String>,
+    /// The namespaces referenced by database qualifiers and `ON` clauses.
+    namespaces: Option<Arc<dyn NamespaceProvider>>,
+}
 
 impl InfluxQLQueryPlanner {
     pub fn new() -> Self {
         Self::default()
     }
 
+    /// Set the name of the namespace queried through the default catalog of
+    /// the query context, used by statements that do not specify a database.
+    pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = Some(namespace.into());
+        self
+    }
+
+    /// Resolve the databases referenced by statements, and create those of
+    /// `CREATE DATABASE` statements, with `namespaces`.
+    ///
+    /// Without a [`NamespaceProvider`], statements may only query the
+    /// namespace of the query context.
+    pub fn with_namespace_provider(mut self, namespaces: Arc<dyn NamespaceProvider>) -> Self {
+        self.namespaces = Some(namespaces);
+        self
+    }
+
     /// Plan an InfluxQL query against the catalogs registered with `ctx`, and return a
     /// DataFusion physical execution plan that runs on the query executor.
+    ///
+    /// The bind parameters of `query` are replaced with the values of `params`.
     pub async fn query(
         &self,
         query: &str,
+        params: StatementParams,
         ctx: &IOxSessionContext,
     ) -> Result<Arc<dyn ExecutionPlan>> {
         debug!(text=%query, "planning InfluxQL query");
 
-        let statement = self.query_to_statement(query)?;
+        let mut statement = self.query_to_statement(query)?;
+        let params = params
+            .into_iter()
+            .map(|(name, value)| (name, value.into()))
+            .collect();
+        replace_bind_parameters(&mut statement, &params)
+            .map_err(|e| DataFusionError::Plan(e.to_string()))?;
         let logical_plan = self.statement_to_plan(statement, ctx).await?;
 
         let input = ctx.create_physical_plan(&logical_plan).await?;
@@ -177,43 +330,90 @@ impl InfluxQLQueryPlanner {
         statement: Statement,
         ctx: &IOxSessionContext,
     ) -> Result<LogicalPlan> {
-        use std::collections::hash_map::Entry;
+        if let Statement::CreateDatabase(create) = &statement {
+            self.create_database(create).await?;
+        }
 
         let session_cfg = ctx.inner().copied_config();
         let cfg = session_cfg.options();
         let schema = ctx
             .inner()
             .catalog(&cfg.catalog.default_catalog)
-            .ok_or_else(|| {
-                DataFusionError::Plan(format!(
-                    "failed to resolve catalog: {}",
-                    cfg.catalog.default_catalog
-                ))
-            })?
-            .schema(&cfg.catalog.default_schema)
-            .ok_or_else(|| {
-                DataFusionError::Plan(format!(
-                    "failed to resolve schema: {}",
-                    cfg.catalog.default_schema
-                ))
-            })?;
-        let names = schema.table_names();
-        let query_tables = find_all_measurements(&statement, &names)?;
+            .and_then(|catalog| catalog.schema(&cfg.catalog.default_schema));
+
+        let used = database::statement_namespaces(&statement, self.namespace.as_deref());
+        if used.default && schema.is_none() {
+            return Err(DataFusionError::Plan(match &self.namespace {
+                Some(namespace) => format!("database not found: {namespace}"),
+                None => "database name required".to_owned(),
+            }));
+        }
 
         let mut sp = ContextSchemaProvider {
             state: &ctx.inner().state(),
-            tables: HashMap::with_capacity(query_tables.len()),
+            now: ctx.time_provider().now().date_time().into(),
+            tables: HashMap::new(),
+            namespace: self.namespace.clone(),
+            namespace_names: None,
+            namespace_tables: HashMap::new(),
+            qualified_tables: HashMap::new(),
         };
 
-        for table_name in &query_tables {
-            if let Entry::Vacant(v) = sp.tables.entry(table_name.to_string()) {
-                if let Some(table) = schema.table(table_name).await {
-                    let schema = Schema::try_from(table.schema())
-                        .map_err(|err| {
-                            DataFusionError::Internal(format!("unable to convert DataFusion schema for measurement {table_name} to IOx schema: {err}"))
-                        })?;
-                    v.insert((provider_as_source(table), schema));
+        if let Some(schema) = &schema {
+            let names = schema.table_names();
+            let query_tables = find_all_measurements(&statement, &names)?;
+            load_tables(
+                &mut sp.tables,
+                schema.as_ref(),
+                &query_tables,
+                str::to_owned,
+            )
+            .await?;
+        }
+
+        if let Some(namespaces) = &self.namespaces {
+            if used.list {
+                sp.namespace_names = Some(namespaces.namespace_names().await?);
+            }
+
+            let mut other = used.named.clone();
+            if used.all_databases || used.all_retention_policies {
+                other.extend(
+                    sp.namespace_names
+                        .iter()
+                        .flatten()
+                        .filter(|ns| {
+                            used.all_retention_policies
+                                || database::database_name(ns) == ns.as_str()
+                        })
+                        .cloned(),
+                );
+            }
+            if let Some(namespace) = &self.namespace {
+                other.remove(namespace);
+            }
+
+            for namespace in other {
+                let Some(schema) = namespaces.namespace_schema(&namespace).await? else {
+                    continue;
+                };
+                let names = schema.table_names();
+                let query_tables = find_all_measurements(&statement, &names)?;
+                load_tables(&mut sp.tables, schema.as_ref(), &query_tables, |table| {
+                    qualified_table_key(&namespace, table)
+                })
+                .await?;
+
+                let mut keys = Vec::with_capacity(query_tables.len());
+                for table in query_tables {
+                    let key = qualified_table_key(&namespace, &table);
+                    if sp.tables.contains_key(&key) {
+                        sp.qualified_tables
+                            .insert(key.clone(), (namespace.clone(), table));
+                        keys.push(key);
+                    }
                 }
+                sp.namespace_tables.insert(namespace, keys);
             }
         }
 
@@ -223,6 +423,28 @@ impl InfluxQLQueryPlanner {
         Ok(logical_plan)
     }
 
+    /// Create the namespace of the database of `create`.
+    ///
+    /// The namespace is created when the statement is planned, as the plan of
+    /// a `CREATE DATABASE` statement produces no rows.
+    async fn create_database(&self, create: &CreateDatabaseStatement) -> Result<()> {
+        let Some(namespaces) = &self.namespaces else {
+            return Err(DataFusionError::NotImplemented(
+                "CREATE DATABASE is not supported by this planner".to_owned(),
+            ));
+        };
+
+        let name = database::namespace_name(
+            create.name.as_str(),
+            create.retention_name.as_ref().map(|rp| rp.as_str()),
+        );
+        // A zero duration is an infinite retention period.
+        let retention_period_ns = create.duration.map(|d| *d).filter(|d| *d > 0);
+        namespaces
+            .create_namespace(&name, retention_period_ns)
+            .await
+    }
+
     fn query_to_statement(&self, query: &str) -> Result<Statement> {
         let mut statements =
             parse_statements(query).map_err(|e| DataFusionError::Plan(e.to_string()))?;
@@ -311,6 +533,28 @@ fn find_all_measurements(stmt: &Statement, tables: &[String]) -> Result<HashSet<
 
             Ok(self)
         }
+
+        fn post_visit_show_series_statement(
+            self,
+            ss: &ShowSeriesStatement,
+        ) -> Result<Self, Self::Error> {
+            if ss.from.is_none() {
+                self.0.extend(self.1.iter().cloned());
+            }
+
+            Ok(self)
+        }
+
+        fn post_visit_show_cardinality_statement(
+            self,
+            sc: &ShowCardinalityStatement,
+        ) -> Result<Self, Self::Error> {
+            if sc.from.is_none() {
+                self.0.extend(self.1.iter().cloned());
+            }
+
+            Ok(self)
+        }
     }
 
     let mut m = HashSet::new();
@@ -398,6 +642,14 @@ mod test {
         assert_eq!(find("SHOW TAG KEYS"), vec!["bar", "foo", "foobar"]);
         assert_eq!(find("SHOW TAG KEYS FROM /^foo/"), vec!["foo", "foobar"]);
 
+        // Find all measurements in `SHOW SERIES` and `SHOW ... CARDINALITY`
+        assert_eq!(find("SHOW SERIES"), vec!["bar", "foo", "foobar"]);
+        assert_eq!(find("SHOW SERIES FROM /^foo/"), vec!["foo", "foobar"]);
+        assert_eq!(
+            find("SHOW MEASUREMENT CARDINALITY"),
+            vec!["bar", "foo", "foobar"]
+        );
+
         // Finds no measurements
         assert!(find("SELECT * FROM none").is_empty());
         assert!(find("SELECT * FROM (SELECT * FROM none)").is_empty());
@@ -18,6 +18,7 @@ use arrow::datatypes::DataType;
 use workspace_hack as _;
 
 mod aggregate;
+pub mod database;
 mod error;
 pub mod frontend;
 pub mod plan;
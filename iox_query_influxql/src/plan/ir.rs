@@ -100,6 +100,7 @@ impl From<Select> for SelectStatement {
                     })
                     .collect(),
             ),
+            into: None,
             from: FromMeasurementClause::new(
                 value
                     .from
@@ -1,7 +1,6 @@
 mod select;
 
-use crate::aggregate::PERCENTILE;
-use crate::error;
+use crate::aggregate::{HOLT_WINTERS, INTEGRAL, MODE, PERCENTILE, SPREAD};
 use crate::plan::ir::{DataSource, Field, Interval, Select, SelectQuery};
 use crate::plan::planner::select::{
     fields_to_exprs_no_nulls, make_tag_key_column_meta, plan_with_sort, ProjectionInfo, Selector,
@@ -10,18 +9,27 @@ use crate::plan::planner::select::{
 use crate::plan::planner_time_range_expression::time_range_to_df_expr;
 use crate::plan::rewriter::{find_table_names, rewrite_statement, ProjectionType};
 use crate::plan::udf::{
-    cumulative_sum, derivative, difference, find_window_udfs, moving_average,
-    non_negative_derivative, non_negative_difference,
+    chande_momentum_oscillator, cumulative_sum, derivative, difference,
+    double_exponential_moving_average, elapsed, exponential_moving_average, find_window_udfs,
+    kaufmans_adaptive_moving_average, kaufmans_efficiency_ratio, moving_average,
+    non_negative_derivative, non_negative_difference, relative_strength_index,
+    triple_exponential_derivative, triple_exponential_moving_average,
+};
+use crate::plan::util::{
+    binary_operator_to_df_operator, database_table_names, fill_null_literal, rebase_expr, IQLSchema,
 };
-use crate::plan::util::{binary_operator_to_df_operator, rebase_expr, IQLSchema};
 use crate::plan::var_ref::var_ref_data_type_to_data_type;
 use crate::plan::{planner_rewrite_expression, udf};
 use crate::window::{
-    CUMULATIVE_SUM, DERIVATIVE, DIFFERENCE, MOVING_AVERAGE, NON_NEGATIVE_DERIVATIVE,
-    NON_NEGATIVE_DIFFERENCE, PERCENT_ROW_NUMBER,
+    CHANDE_MOMENTUM_OSCILLATOR, CUMULATIVE_SUM, DERIVATIVE, DIFFERENCE,
+    DOUBLE_EXPONENTIAL_MOVING_AVERAGE, ELAPSED, EXPONENTIAL_MOVING_AVERAGE,
+    KAUFMANS_ADAPTIVE_MOVING_AVERAGE, KAUFMANS_EFFICIENCY_RATIO, MOVING_AVERAGE,
+    NON_NEGATIVE_DERIVATIVE, NON_NEGATIVE_DIFFERENCE, PERCENT_ROW_NUMBER, RELATIVE_STRENGTH_INDEX,
+    TRIPLE_EXPONENTIAL_DERIVATIVE, TRIPLE_EXPONENTIAL_MOVING_AVERAGE,
 };
+use crate::{database, error};
 use arrow::array::{
-    BooleanArray, DictionaryArray, Int32Array, Int64Array, StringArray, StringBuilder,
+    ArrayRef, BooleanArray, DictionaryArray, Int32Array, Int64Array, StringArray, StringBuilder,
     StringDictionaryBuilder,
 };
 use arrow::datatypes::{DataType, Field as ArrowField, Int32Type, Schema as ArrowSchema};
@@ -45,7 +53,9 @@ use datafusion::logical_expr::{
 };
 use datafusion::optimizer::utils::conjunction;
 use datafusion::physical_expr::execution_props::ExecutionProps;
-use datafusion::prelude::{cast, sum, when, Column};
+use datafusion::prelude::{
+    approx_distinct, cast, concat, count, count_distinct, random, replace, sum, when, Column,
+};
 use datafusion_util::{lit_dict, AsExpr};
 use generated_types::influxdata::iox::querier::v1::InfluxQlMetadata;
 use influxdb_influxql_parser::common::{LimitClause, OffsetClause, OrderByClause};
@@ -59,11 +69,13 @@ use influxdb_influxql_parser::functions::{
     is_aggregate_function, is_now_function, is_scalar_math_function,
 };
 use influxdb_influxql_parser::select::{FillClause, GroupByClause};
+use influxdb_influxql_parser::show_cardinality::{CardinalityKind, ShowCardinalityStatement};
 use influxdb_influxql_parser::show_field_keys::ShowFieldKeysStatement;
 use influxdb_influxql_parser::show_measurements::{
-    ShowMeasurementsStatement, WithMeasurementClause,
+    ExtendedOnClause, ShowMeasurementsStatement, WithMeasurementClause,
 };
 use influxdb_influxql_parser::show_retention_policies::ShowRetentionPoliciesStatement;
+use influxdb_influxql_parser::show_series::ShowSeriesStatement;
 use influxdb_influxql_parser::show_tag_keys::ShowTagKeysStatement;
 use influxdb_influxql_parser::show_tag_values::{ShowTagValuesStatement, WithKeyClause};
 use influxdb_influxql_parser::simple_from_clause::ShowFromClause;
@@ -103,6 +115,12 @@ use super::util::contains_expr;
 /// The column index of the measurement column.
 const MEASUREMENT_COLUMN_INDEX: u32 = 0;
 
+/// The name of the series key column produced by `SHOW SERIES`.
+const SERIES_KEY_COL: &str = "key";
+
+/// The name of the column produced by `SHOW ... CARDINALITY` statements.
+const CARDINALITY_COUNT_COL: &str = "count";
+
 /// The `SchemaProvider` trait allows the InfluxQL query planner to obtain
 /// meta-data about tables referenced in InfluxQL statements.
 pub trait SchemaProvider {
@@ -127,6 +145,40 @@ pub trait SchemaProvider {
     fn table_schema(&self, name: &str) -> Option<Schema>;
 
     fn execution_props(&self) -> &ExecutionProps;
+
+    /// The time of `now()` in the statement, including in its time range.
+    fn now(&self) -> Timestamp;
+
+    /// The name of the namespace queried by statements that do not specify a
+    /// database, if any.
+    fn namespace_name(&self) -> Option<&'_ str> {
+        None
+    }
+
+    /// The names of all namespaces the query may list, such as for
+    /// `SHOW DATABASES`.
+    fn namespace_names(&self) -> Vec<&'_ str> {
+        self.namespace_name().into_iter().collect()
+    }
+
+    /// The tables of the namespace `name`, or `None` if it does not exist.
+    ///
+    /// The returned names identify the tables for the other methods of this
+    /// trait, and are mapped to measurement names with
+    /// [`measurement_name`](Self::measurement_name).
+    fn namespace_table_names(&self, name: &str) -> Option<Vec<&'_ str>> {
+        (self.namespace_name() == Some(name)).then(|| self.table_names())
+    }
+
+    /// The namespace storing the table `name`, if known.
+    fn table_namespace(&self, _name: &str) -> Option<&'_ str> {
+        self.namespace_name()
+    }
+
+    /// The name of the measurement stored in the table `name`.
+    fn measurement_name<'b>(&'b self, name: &'b str) -> &'b str {
+        name
+    }
 }
 
 /// Informs the planner which rules should be applied when transforming
@@ -443,20 +495,24 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
 
     pub fn statement_to_plan(&self, statement: Statement) -> Result<LogicalPlan> {
         match statement {
-            Statement::CreateDatabase(_) => error::not_implemented("CREATE DATABASE"),
+            Statement::CreateDatabase(_) => self.create_database_to_plan(),
             Statement::Delete(_) => error::not_implemented("DELETE"),
             Statement::DropMeasurement(_) => error::not_implemented("DROP MEASUREMENT"),
             Statement::Explain(explain) => self.explain_statement_to_plan(*explain),
             Statement::Select(select) => {
                 self.select_query_to_plan(&self.rewrite_select_statement(*select)?)
             }
-            Statement::ShowDatabases(_) => error::not_implemented("SHOW DATABASES"),
+            Statement::ShowCardinality(show_cardinality) => {
+                self.show_cardinality_to_plan(*show_cardinality)
+            }
+            Statement::ShowDatabases(_) => self.show_databases_to_plan(),
             Statement::ShowMeasurements(show_measurements) => {
                 self.show_measurements_to_plan(*show_measurements)
             }
             Statement::ShowRetentionPolicies(show_retention_policies) => {
                 self.show_retention_policies_to_plan(*show_retention_policies)
             }
+            Statement::ShowSeries(show_series) => self.show_series_to_plan(*show_series),
             Statement::ShowTagKeys(show_tag_keys) => self.show_tag_keys_to_plan(*show_tag_keys),
             Statement::ShowTagValues(show_tag_values) => {
                 self.show_tag_values_to_plan(*show_tag_values)
@@ -467,6 +523,74 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
         }
     }
 
+    /// Plan a `CREATE DATABASE` statement, which produces no output.
+    ///
+    /// Like other DDL statements, the namespace is created before the statement is planned.
+    fn create_database_to_plan(&self) -> Result<LogicalPlan> {
+        let schema = ArrowSchema::new(vec![ArrowField::new(
+            INFLUXQL_MEASUREMENT_COLUMN_NAME,
+            (&InfluxColumnType::Tag).into(),
+            false,
+        )]);
+        let plan = LogicalPlan::EmptyRelation(EmptyRelation {
+            produce_one_row: false,
+            schema: schema.to_dfschema_ref()?,
+        });
+        plan_with_metadata(
+            plan,
+            &InfluxQlMetadata {
+                measurement_column_index: MEASUREMENT_COLUMN_INDEX,
+                tag_key_columns: vec![],
+            },
+        )
+    }
+
+    /// Plan a `SHOW DATABASES` statement, listing the databases of the namespaces returned by
+    /// [`SchemaProvider::namespace_names`].
+    fn show_databases_to_plan(&self) -> Result<LogicalPlan> {
+        let output_schema = Arc::new(ArrowSchema::new(vec![
+            ArrowField::new(
+                INFLUXQL_MEASUREMENT_COLUMN_NAME,
+                (&InfluxColumnType::Tag).into(),
+                false,
+            ),
+            ArrowField::new(
+                "name",
+                (&InfluxColumnType::Field(InfluxFieldType::String)).into(),
+                false,
+            ),
+        ]));
+
+        let databases = self
+            .s
+            .namespace_names()
+            .into_iter()
+            .map(database::database_name)
+            .sorted()
+            .dedup()
+            .collect::<Vec<_>>();
+        let record_batch = RecordBatch::try_new(
+            Arc::clone(&output_schema),
+            vec![
+                Arc::new(DictionaryArray::try_new(
+                    Int32Array::from(vec![0; databases.len()]),
+                    Arc::new(StringArray::from(vec![Some("databases")])),
+                )?),
+                Arc::new(StringArray::from(databases)),
+            ],
+        )?;
+        let table = Arc::new(MemTable::try_new(output_schema, vec![vec![record_batch]])?);
+        let plan =
+            LogicalPlanBuilder::scan("databases", provider_as_source(table), None)?.build()?;
+        plan_with_metadata(
+            plan,
+            &InfluxQlMetadata {
+                measurement_column_index: MEASUREMENT_COLUMN_INDEX,
+                tag_key_columns: vec![],
+            },
+        )
+    }
+
     fn explain_statement_to_plan(&self, explain: ExplainStatement) -> Result<LogicalPlan> {
         let plan = self.select_query_to_plan(&self.rewrite_select_statement(*explain.select)?)?;
         let plan = Arc::new(plan);
@@ -553,11 +677,7 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
 
             let plan = self.project_select(&ctx, plan, &fields, &group_by_tag_set)?;
 
-            // TODO(sgc): Handle FILL(N) and FILL(previous)
-            //
-            // See: https://github.com/influxdata/influxdb_iox/issues/8042
-
-            plans.push((table_name, plan));
+            plans.push((self.s.measurement_name(table_name), plan));
         }
 
         let plan = {
@@ -790,6 +910,7 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             ProjectionType::WindowAggregateMixed => error::not_implemented("mixed window-aggregate and aggregate columns, such as DIFFERENCE(MEAN(col)), MEAN(col)"),
             ProjectionType::Selector{..} => self.project_select_selector(ctx, input, fields, group_by_tag_set),
             ProjectionType::TopBottomSelector => self.project_select_top_bottom_selector(ctx, input, fields, group_by_tag_set),
+            ProjectionType::HoltWinters => self.project_select_holt_winters(ctx, input, fields, group_by_tag_set),
         }
     }
 
@@ -943,8 +1064,6 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
     /// type. These a queries that include a single FIRST, LAST, MAX, MIN,
     /// PERCENTILE, or SAMPLE function call, possibly requesting additional
     /// tags or fields.
-    ///
-    /// N.B SAMPLE is not yet implemented.
     fn project_select_selector(
         &self,
         ctx: &Context<'_>,
@@ -1008,8 +1127,19 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
 
                 (idx, field_key, plan)
             }
-            (_, Selector::Sample { field_key: _, n: _ }) => {
-                return error::not_implemented("sample selector function")
+            (idx, Selector::Sample { field_key, n }) => {
+                let plan = LogicalPlanBuilder::from(input)
+                    .filter(field_key.as_expr().is_not_null())?
+                    .build()?;
+                let plan = self.select_first(
+                    ctx,
+                    plan,
+                    SelectorWindowOrderBy::Random,
+                    group_by_tag_set,
+                    n,
+                )?;
+
+                (idx, field_key, plan)
             }
 
             (_, s) => {
@@ -1111,6 +1241,116 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
         project(plan, select_exprs)
     }
 
+    /// Plan "HoltWinters" SELECT queries. These are queries that forecast the
+    /// output of a nested aggregate with the HOLT_WINTERS or HOLT_WINTERS_WITH_FIT
+    /// function, producing the forecast points of each series as rows.
+    fn project_select_holt_winters(
+        &self,
+        ctx: &Context<'_>,
+        input: LogicalPlan,
+        fields: &[Field],
+        group_by_tag_set: &[&str],
+    ) -> Result<LogicalPlan> {
+        let Some(interval) = ctx.interval else {
+            return error::internal("holt_winters requires a GROUP BY interval")
+        };
+
+        let Some((hw_index, call)) = fields.iter().enumerate().find_map(|(idx, f)| match &f.expr {
+            IQLExpr::Call(c) if c.name == "holt_winters" || c.name == "holt_winters_with_fit" => {
+                Some((idx, c))
+            }
+            _ => None,
+        }) else {
+            return error::not_implemented("holt_winters in an expression")
+        };
+
+        let lit_integer = |expr: &IQLExpr| match expr {
+            IQLExpr::Literal(Literal::Integer(v)) => Ok(*v),
+            _ => error::internal(format!("expected integer argument in {}()", call.name)),
+        };
+        let n = lit_integer(&call.args[1])?;
+        let s = lit_integer(&call.args[2])?;
+        let include_fit = call.name == "holt_winters_with_fit";
+
+        // Plan the nested aggregate, which produces the points to forecast.
+        let mut fields_vec = fields.to_vec();
+        fields_vec[hw_index].expr = call.args[0].clone();
+        let plan = self.project_select_aggregate(ctx, input, &fields_vec, group_by_tag_set)?;
+
+        let Some(time_column_index) = find_time_column_index(fields) else {
+            return error::internal("unable to find time column")
+        };
+        let time_column = fields[time_column_index].name.as_str();
+        let value_column = fields[hw_index].name.as_str();
+
+        let schema = Arc::clone(plan.schema());
+        if schema
+            .field_with_unqualified_name(value_column)?
+            .data_type()
+            == &DataType::Null
+        {
+            // The nested aggregate refers to a field that does not exist in
+            // the current table, so there are no points to forecast.
+            return LogicalPlanBuilder::from(plan).filter(lit(false))?.build();
+        }
+
+        // The remaining columns are the measurement and tags, which identify
+        // the series. Tags that do not exist in the current table are `NULL`
+        // and are projected as such.
+        let series_exprs = schema
+            .fields()
+            .iter()
+            .filter(|f| {
+                f.name() != time_column
+                    && f.name() != value_column
+                    && f.data_type() != &DataType::Null
+            })
+            .map(|f| f.name().as_expr())
+            .collect::<Vec<_>>();
+
+        let holt_winters = Expr::AggregateUDF(expr::AggregateUDF::new(
+            HOLT_WINTERS.clone(),
+            vec![
+                value_column.as_expr(),
+                time_column.as_expr(),
+                lit(n),
+                lit(s),
+                lit(interval.duration),
+                lit(include_fit),
+            ],
+            None,
+            None,
+        ))
+        .alias(value_column);
+
+        let plan = LogicalPlanBuilder::from(plan)
+            .filter(value_column.as_expr().is_not_null())?
+            .aggregate(series_exprs, vec![holt_winters])?
+            .unnest_column(value_column)?
+            .filter(value_column.as_expr().is_not_null())?
+            .build()?;
+
+        let select_exprs = schema
+            .fields()
+            .iter()
+            .map(|f| {
+                let name = f.name().as_str();
+                if name == time_column {
+                    value_column.as_expr().field("time").alias(name)
+                } else if name == value_column {
+                    value_column.as_expr().field("value").alias(name)
+                } else if f.data_type() == &DataType::Null {
+                    lit(ScalarValue::Null).alias(name)
+                } else {
+                    name.as_expr()
+                }
+            })
+            .collect::<Vec<_>>();
+
+        // Wrap the plan in a `LogicalPlan::Projection` from the select expressions
+        project(plan, select_exprs)
+    }
+
     fn select_aggregate(
         &self,
         ctx: &Context<'_>,
@@ -1294,6 +1534,11 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
         // * `literal` value
         // * `linear`
         //
+        // Without a `GROUP BY TIME` clause, each series is aggregated into a single window, so
+        // there is no previous value to carry forward or to interpolate from, and `FILL(previous)`
+        // and `FILL(linear)` project `NULL`, as InfluxDB 1.x does. `FILL(<value>)` is applied to
+        // the aggregates below for either kind of query.
+        //
         let plan = if ctx.group_by.and_then(|gb| gb.time_dimension()).is_some()
             && fill_option != FillClause::None
         {
@@ -1309,6 +1554,10 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             plan
         };
 
+        // Aggregates of columns that do not exist in the current table are projected
+        // as `NULL`, and must also be filled, unless none of the columns exist.
+        let fill_missing_columns = !aggr_exprs.is_empty();
+
         // Combine the aggregate columns and group by expressions, which represents
         // the final projection from the aggregate operator.
         let aggr_projection_exprs = [aggr_group_by_exprs, aggr_exprs].concat();
@@ -1331,8 +1580,9 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
         // refer to the columns from the aggregate projection
         let select_exprs_post_aggr = select_exprs
             .iter()
+            .zip(fields)
             .zip(should_fill_expr.iter().zip(should_zero_fill_expr))
-            .map(|(expr, (should_fill, should_zero_fill))| {
+            .map(|((expr, field), (should_fill, should_zero_fill))| {
                 // This implements the `FILL(<value>)` strategy, by coalescing any aggregate
                 // expressions to `<value>` when they are `NULL`.
                 let fill_if_null = match (fill_if_null, should_fill, should_zero_fill) {
@@ -1341,6 +1591,14 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
                     _ => None,
                 };
 
+                if let (Some(value), true, true) =
+                    (&fill_if_null, *should_fill, fill_missing_columns)
+                {
+                    if let Some(expr) = fill_null_literal(expr, value, field.data_type)? {
+                        return Ok(expr);
+                    }
+                }
+
                 rebase_expr(expr, &aggr_projection_exprs, &fill_if_null, &plan)
             })
             .collect::<Result<Vec<Expr>>>()?;
@@ -1411,6 +1669,7 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             SelectorWindowOrderBy::FieldDesc(id) => {
                 vec![id.as_expr().sort(false, false), ctx.time_sort_expr()]
             }
+            SelectorWindowOrderBy::Random => vec![random().sort(true, false)],
         };
 
         let window_expr = Expr::WindowFunction(WindowFunction::new(
@@ -1465,96 +1724,90 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             }
         }
 
-        match udf::WindowFunction::try_from_scalar_udf(Arc::clone(&fun)) {
-            Some(udf::WindowFunction::MovingAverage) => Ok(Expr::WindowFunction(WindowFunction {
-                fun: MOVING_AVERAGE.clone(),
-                args,
-                partition_by,
-                order_by,
-                window_frame: WindowFrame {
-                    units: WindowFrameUnits::Rows,
-                    start_bound: WindowFrameBound::Preceding(ScalarValue::Null),
-                    end_bound: WindowFrameBound::Following(ScalarValue::Null),
-                },
-            })
-            .alias(alias)),
-            Some(udf::WindowFunction::Difference) => Ok(Expr::WindowFunction(WindowFunction {
-                fun: DIFFERENCE.clone(),
-                args,
-                partition_by,
-                order_by,
-                window_frame: WindowFrame {
-                    units: WindowFrameUnits::Rows,
-                    start_bound: WindowFrameBound::Preceding(ScalarValue::Null),
-                    end_bound: WindowFrameBound::Following(ScalarValue::Null),
-                },
-            })
-            .alias(alias)),
+        fn elapsed_unit(args: &Vec<Expr>) -> Result<ScalarValue> {
+            if args.len() > 1 {
+                if let Expr::Literal(v) = &args[1] {
+                    Ok(v.clone())
+                } else {
+                    error::internal(format!("udf_to_expr: unexpected expression: {}", args[1]))
+                }
+            } else {
+                Ok(ScalarValue::new_interval_mdn(0, 0, 1)) // 1ns
+            }
+        }
+
+        let (fun, args) = match udf::WindowFunction::try_from_scalar_udf(Arc::clone(&fun)) {
+            Some(udf::WindowFunction::MovingAverage) => (MOVING_AVERAGE.clone(), args),
+            Some(udf::WindowFunction::Difference) => (DIFFERENCE.clone(), args),
             Some(udf::WindowFunction::NonNegativeDifference) => {
-                Ok(Expr::WindowFunction(WindowFunction {
-                    fun: NON_NEGATIVE_DIFFERENCE.clone(),
-                    args,
-                    partition_by,
-                    order_by,
-                    window_frame: WindowFrame {
-                        units: WindowFrameUnits::Rows,
-                        start_bound: WindowFrameBound::Preceding(ScalarValue::Null),
-                        end_bound: WindowFrameBound::Following(ScalarValue::Null),
-                    },
-                })
-                .alias(alias))
+                (NON_NEGATIVE_DIFFERENCE.clone(), args)
             }
-            Some(udf::WindowFunction::Derivative) => Ok(Expr::WindowFunction(WindowFunction {
-                fun: DERIVATIVE.clone(),
-                args: vec![
+            Some(udf::WindowFunction::Derivative) => (
+                DERIVATIVE.clone(),
+                vec![
                     args[0].clone(),
                     lit(derivative_unit(ctx, &args)?),
                     "time".as_expr(),
                 ],
-                partition_by,
-                order_by,
-                window_frame: WindowFrame {
-                    units: WindowFrameUnits::Rows,
-                    start_bound: WindowFrameBound::Preceding(ScalarValue::Null),
-                    end_bound: WindowFrameBound::Following(ScalarValue::Null),
-                },
-            })
-            .alias(alias)),
-            Some(udf::WindowFunction::NonNegativeDerivative) => {
-                Ok(Expr::WindowFunction(WindowFunction {
-                    fun: NON_NEGATIVE_DERIVATIVE.clone(),
-                    args: vec![
-                        args[0].clone(),
-                        lit(derivative_unit(ctx, &args)?),
-                        "time".as_expr(),
-                    ],
-                    partition_by,
-                    order_by,
-                    window_frame: WindowFrame {
-                        units: WindowFrameUnits::Rows,
-                        start_bound: WindowFrameBound::Preceding(ScalarValue::Null),
-                        end_bound: WindowFrameBound::Following(ScalarValue::Null),
-                    },
-                })
-                .alias(alias))
+            ),
+            Some(udf::WindowFunction::NonNegativeDerivative) => (
+                NON_NEGATIVE_DERIVATIVE.clone(),
+                vec![
+                    args[0].clone(),
+                    lit(derivative_unit(ctx, &args)?),
+                    "time".as_expr(),
+                ],
+            ),
+            Some(udf::WindowFunction::CumulativeSum) => (CUMULATIVE_SUM.clone(), args),
+            Some(udf::WindowFunction::Elapsed) => (
+                ELAPSED.clone(),
+                vec![args[0].clone(), lit(elapsed_unit(&args)?), "time".as_expr()],
+            ),
+            Some(udf::WindowFunction::ExponentialMovingAverage) => {
+                (EXPONENTIAL_MOVING_AVERAGE.clone(), args)
             }
-            Some(udf::WindowFunction::CumulativeSum) => Ok(Expr::WindowFunction(WindowFunction {
-                fun: CUMULATIVE_SUM.clone(),
-                args,
-                partition_by,
-                order_by,
-                window_frame: WindowFrame {
-                    units: WindowFrameUnits::Rows,
-                    start_bound: WindowFrameBound::Preceding(ScalarValue::Null),
-                    end_bound: WindowFrameBound::Following(ScalarValue::Null),
-                },
-            })
-            .alias(alias)),
-            None => error::internal(format!(
-                "unexpected user-defined window function: {}",
-                fun.name
-            )),
-        }
+            Some(udf::WindowFunction::DoubleExponentialMovingAverage) => {
+                (DOUBLE_EXPONENTIAL_MOVING_AVERAGE.clone(), args)
+            }
+            Some(udf::WindowFunction::TripleExponentialMovingAverage) => {
+                (TRIPLE_EXPONENTIAL_MOVING_AVERAGE.clone(), args)
+            }
+            Some(udf::WindowFunction::TripleExponentialDerivative) => {
+                (TRIPLE_EXPONENTIAL_DERIVATIVE.clone(), args)
+            }
+            Some(udf::WindowFunction::RelativeStrengthIndex) => {
+                (RELATIVE_STRENGTH_INDEX.clone(), args)
+            }
+            Some(udf::WindowFunction::KaufmansEfficiencyRatio) => {
+                (KAUFMANS_EFFICIENCY_RATIO.clone(), args)
+            }
+            Some(udf::WindowFunction::KaufmansAdaptiveMovingAverage) => {
+                (KAUFMANS_ADAPTIVE_MOVING_AVERAGE.clone(), args)
+            }
+            Some(udf::WindowFunction::ChandeMomentumOscillator) => {
+                (CHANDE_MOMENTUM_OSCILLATOR.clone(), args)
+            }
+            None => {
+                return error::internal(format!(
+                    "unexpected user-defined window function: {}",
+                    fun.name
+                ))
+            }
+        };
+
+        // All window functions are evaluated over every row of the partition.
+        Ok(Expr::WindowFunction(WindowFunction {
+            fun,
+            args,
+            partition_by,
+            order_by,
+            window_frame: WindowFrame {
+                units: WindowFrameUnits::Rows,
+                start_bound: WindowFrameBound::Preceding(ScalarValue::Null),
+                end_bound: WindowFrameBound::Following(ScalarValue::Null),
+            },
+        })
+        .alias(alias))
     }
 
     /// Generate a plan that partitions the input data into groups, first omitting a specified
@@ -1832,7 +2085,8 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
                     },
                 })
             }
-            IQLExpr::BindParameter(_) => error::not_implemented("parameter"),
+            // Bind parameters are replaced with their values before planning.
+            IQLExpr::BindParameter(_) => error::internal("unexpected bind parameter"),
             IQLExpr::Literal(val) => match val {
                 Literal::Integer(v) => Ok(lit(*v)),
                 Literal::Unsigned(v) => Ok(lit(*v)),
@@ -1889,14 +2143,27 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             return self.scalar_math_func_to_df_expr(scope, call, schema);
         }
 
+        if is_now_function(&call.name) {
+            if !call.args.is_empty() {
+                let got = call.args.len();
+                return error::query(format!(
+                    "invalid number of arguments for now: expected 0, got {got}"
+                ));
+            }
+
+            // `now()` is the same time used to resolve `now()` in the time
+            // range of the `WHERE` clause.
+            let now = self.s.now();
+            return Ok(lit(ScalarValue::TimestampNanosecond(
+                Some(now.timestamp_nanos()),
+                None,
+            )));
+        }
+
         match scope {
             ExprScope::Where => {
-                if is_now_function(&call.name) {
-                    error::not_implemented("now")
-                } else {
-                    let name = &call.name;
-                    error::query(format!("invalid function call in condition: {name}"))
-                }
+                let name = &call.name;
+                error::query(format!("invalid function call in condition: {name}"))
             }
             ExprScope::Projection => self.function_to_df_expr(scope, call, schema),
         }
@@ -1935,6 +2202,25 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             }
         }
 
+        /// Return the value of an integer literal argument, which has been
+        /// validated by the rewriter.
+        fn integer_arg(name: &str, expr: Expr) -> Result<i64> {
+            match expr {
+                Expr::Literal(ScalarValue::Int64(Some(v))) => Ok(v),
+                Expr::Literal(ScalarValue::UInt64(Some(v))) => Ok(v as i64),
+                _ => error::query(format!("{name} expects integer argument")),
+            }
+        }
+
+        /// Return the value of a string literal argument, which has been
+        /// validated by the rewriter.
+        fn string_arg(name: &str, expr: Expr) -> Result<String> {
+            match expr {
+                Expr::Literal(ScalarValue::Utf8(Some(v))) => Ok(v),
+                _ => error::query(format!("{name} expects string argument")),
+            }
+        }
+
         let Call { name, args } = call;
 
         match name.as_str() {
@@ -1992,6 +2278,44 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
                     None,
                 )))
             }
+            name @ ("mode" | "spread") => {
+                let expr = self.expr_to_df_expr(scope, &args[0], schema)?;
+                if let Expr::Literal(ScalarValue::Null) = expr {
+                    return Ok(expr);
+                }
+
+                check_arg_count(name, args, 1)?;
+                let udaf = match name {
+                    "mode" => MODE.clone(),
+                    "spread" => SPREAD.clone(),
+                    _ => unreachable!(),
+                };
+                Ok(Expr::AggregateUDF(expr::AggregateUDF::new(
+                    udaf,
+                    vec![expr],
+                    None,
+                    None,
+                )))
+            }
+            "integral" => {
+                let expr = self.expr_to_df_expr(scope, &args[0], schema)?;
+                if let Expr::Literal(ScalarValue::Null) = expr {
+                    return Ok(expr);
+                }
+
+                check_arg_count_range(name, args, 1, 2)?;
+                let unit = if args.len() > 1 {
+                    self.expr_to_df_expr(scope, &args[1], schema)?
+                } else {
+                    lit(ScalarValue::new_interval_mdn(0, 0, 1_000_000_000)) // 1s
+                };
+                Ok(Expr::AggregateUDF(expr::AggregateUDF::new(
+                    INTEGRAL.clone(),
+                    vec![expr, unit, "time".as_expr()],
+                    None,
+                    None,
+                )))
+            }
             name @ ("first" | "last" | "min" | "max") => {
                 let expr = self.expr_to_df_expr(scope, &args[0], schema)?;
                 if let Expr::Literal(ScalarValue::Null) = expr {
@@ -2098,6 +2422,93 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
 
                 Ok(cumulative_sum(vec![arg0]))
             }
+            "elapsed" => {
+                check_arg_count_range(name, args, 1, 2)?;
+
+                // arg0 should be a column or function
+                let arg0 = self.expr_to_df_expr(scope, &args[0], schema)?;
+                if let Expr::Literal(ScalarValue::Null) = arg0 {
+                    return Ok(arg0);
+                }
+                let mut eargs = vec![arg0];
+                if args.len() > 1 {
+                    let arg1 = self.expr_to_df_expr(scope, &args[1], schema)?;
+                    eargs.push(arg1);
+                }
+
+                Ok(elapsed(eargs))
+            }
+            name @ ("exponential_moving_average"
+            | "double_exponential_moving_average"
+            | "triple_exponential_moving_average"
+            | "triple_exponential_derivative"
+            | "relative_strength_index"
+            | "chande_momentum_oscillator") => {
+                check_arg_count_range(name, args, 2, 4)?;
+
+                // arg0 should be a column or function
+                let arg0 = self.expr_to_df_expr(scope, &args[0], schema)?;
+                if let Expr::Literal(ScalarValue::Null) = arg0 {
+                    return Ok(arg0);
+                }
+
+                // arg1 is the period and the optional arg2 is the hold period,
+                // which defaults to the warm up count of the indicator.
+                let period = integer_arg(name, self.expr_to_df_expr(scope, &args[1], schema)?)?;
+                let hold_period = match args.get(2) {
+                    Some(arg) => integer_arg(name, self.expr_to_df_expr(scope, arg, schema)?)?,
+                    None => -1,
+                };
+
+                // The optional arg3 is the warmup type, which defaults to
+                // 'none' for the chande_momentum_oscillator function.
+                let warmup = match (args.get(3), name) {
+                    (Some(arg), _) => string_arg(name, self.expr_to_df_expr(scope, arg, schema)?)?,
+                    (None, "chande_momentum_oscillator") => "none".to_owned(),
+                    (None, _) => "exponential".to_owned(),
+                };
+
+                let eargs = vec![arg0, lit(period), lit(hold_period), lit(warmup)];
+                Ok(match name {
+                    "exponential_moving_average" => exponential_moving_average(eargs),
+                    "double_exponential_moving_average" => double_exponential_moving_average(eargs),
+                    "triple_exponential_moving_average" => triple_exponential_moving_average(eargs),
+                    "triple_exponential_derivative" => triple_exponential_derivative(eargs),
+                    "relative_strength_index" => relative_strength_index(eargs),
+                    "chande_momentum_oscillator" => chande_momentum_oscillator(eargs),
+                    _ => unreachable!(),
+                })
+            }
+            name @ ("kaufmans_efficiency_ratio" | "kaufmans_adaptive_moving_average") => {
+                check_arg_count_range(name, args, 2, 3)?;
+
+                // arg0 should be a column or function
+                let arg0 = self.expr_to_df_expr(scope, &args[0], schema)?;
+                if let Expr::Literal(ScalarValue::Null) = arg0 {
+                    return Ok(arg0);
+                }
+
+                let period = integer_arg(name, self.expr_to_df_expr(scope, &args[1], schema)?)?;
+                let hold_period = match args.get(2) {
+                    Some(arg) => integer_arg(name, self.expr_to_df_expr(scope, arg, schema)?)?,
+                    None => -1,
+                };
+
+                let eargs = vec![arg0, lit(period), lit(hold_period)];
+                Ok(match name {
+                    "kaufmans_efficiency_ratio" => kaufmans_efficiency_ratio(eargs),
+                    "kaufmans_adaptive_moving_average" => kaufmans_adaptive_moving_average(eargs),
+                    _ => unreachable!(),
+                })
+            }
+            // The SAMPLE function is handled as a `ProjectionType::Selector`
+            // query when it is the only function in the projection.
+            "sample" => error::not_implemented("sample with other aggregate functions"),
+            // The HOLT_WINTERS function is handled as a `ProjectionType::HoltWinters`
+            // query.
+            "holt_winters" | "holt_winters_with_fit" => {
+                error::not_implemented(format!("{name} with other functions"))
+            }
             // The TOP/BOTTOM function is handled as a `ProjectionType::TopBottomSelector`
             // query, so the planner only needs to project the single column
             // argument.
@@ -2187,7 +2598,7 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
         cutoff: MetadataCutoff,
         schema: &IQLSchema<'_>,
     ) -> Result<LogicalPlan> {
-        let start_time = Timestamp::from(self.s.execution_props().query_execution_start_time);
+        let start_time = self.s.now();
 
         let (cond, time_range) = condition
             .as_ref()
@@ -2249,7 +2660,8 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             let table_ref = TableReference::bare(table_name.to_owned());
             Some((
                 LogicalPlanBuilder::scan(table_ref, source, None)?.build()?,
-                vec![lit_dict(table_name).alias(INFLUXQL_MEASUREMENT_COLUMN_NAME)],
+                vec![lit_dict(self.s.measurement_name(table_name))
+                    .alias(INFLUXQL_MEASUREMENT_COLUMN_NAME)],
             ))
         } else {
             None
@@ -2257,116 +2669,131 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
     }
 
     /// Expand tables from `FROM` clause in metadata queries.
-    fn expand_show_from_clause(&self, from: Option<ShowFromClause>) -> Result<Vec<String>> {
-        match from {
-            None => {
-                let mut tables = self
-                    .s
-                    .table_names()
+    ///
+    /// Measurements without a database qualifier are read from the database of the `ON`
+    /// clause, `on`, if specified.
+    fn expand_show_from_clause(
+        &self,
+        on: Option<&str>,
+        from: Option<ShowFromClause>,
+    ) -> Result<Vec<String>> {
+        let mut out =
+            match from {
+                None => database_table_names(self.s, on, None)?
                     .into_iter()
                     .map(|s| s.to_owned())
-                    .collect::<Vec<_>>();
-                tables.sort();
-                Ok(tables)
-            }
-            Some(from) => {
-                let all_tables = self.s.table_names().into_iter().collect::<HashSet<_>>();
-                let mut out = HashSet::new();
-                for qualified_name in from.iter() {
-                    if qualified_name.database.is_some() {
-                        return error::not_implemented("database name in from clause");
-                    }
-                    if qualified_name.retention_policy.is_some() {
-                        return error::not_implemented("retention policy in from clause");
-                    }
-                    match &qualified_name.name {
-                        MeasurementName::Name(name) => {
-                            let name = name.as_str();
-                            if all_tables.contains(name) {
-                                out.insert(name);
+                    .collect::<Vec<_>>(),
+                Some(from) => {
+                    let mut out = HashSet::new();
+                    for qualified_name in from.iter() {
+                        let tables = database_table_names(
+                            self.s,
+                            qualified_name
+                                .database
+                                .as_ref()
+                                .map(|db| db.as_str())
+                                .or(on),
+                            qualified_name
+                                .retention_policy
+                                .as_ref()
+                                .map(|rp| rp.as_str()),
+                        )?;
+                        match &qualified_name.name {
+                            MeasurementName::Name(name) => {
+                                out.extend(tables.into_iter().filter(|table| {
+                                    self.s.measurement_name(table) == name.as_str()
+                                }));
                             }
-                        }
-                        MeasurementName::Regex(regex) => {
-                            let regex = parse_regex(regex)?;
-                            for name in &all_tables {
-                                if regex.is_match(name) {
-                                    out.insert(name);
-                                }
+                            MeasurementName::Regex(regex) => {
+                                let regex = parse_regex(regex)?;
+                                out.extend(tables.into_iter().filter(|table| {
+                                    regex.is_match(self.s.measurement_name(table))
+                                }));
                             }
                         }
                     }
-                }
 
-                let mut out = out.into_iter().map(|s| s.to_owned()).collect::<Vec<_>>();
-                out.sort();
-                Ok(out)
-            }
-        }
+                    out.into_iter().map(|s| s.to_owned()).collect::<Vec<_>>()
+                }
+            };
+        out.sort_by(|a, b| (self.s.measurement_name(a), a).cmp(&(self.s.measurement_name(b), b)));
+        Ok(out)
     }
 
+    /// Expand tables from the `ON` and `WITH MEASUREMENT` clauses of `SHOW MEASUREMENTS`.
+    ///
+    /// A database or retention policy qualifying the measurement of the `WITH MEASUREMENT`
+    /// clause takes precedence over the `ON` clause.
     fn expand_with_measurement_clause(
         &self,
+        on: Option<ExtendedOnClause>,
         with_measurement: Option<WithMeasurementClause>,
     ) -> Result<Vec<String>> {
-        match with_measurement {
-            Some(
-                WithMeasurementClause::Equals(qualified_name)
-                | WithMeasurementClause::Regex(qualified_name),
-            ) if qualified_name.database.is_some() => {
-                error::not_implemented("database name in from clause")
+        let (qualified_name, is_regex) = match with_measurement {
+            Some(WithMeasurementClause::Equals(qualified_name)) => (Some(qualified_name), false),
+            Some(WithMeasurementClause::Regex(qualified_name)) => (Some(qualified_name), true),
+            None => (None, false),
+        };
+
+        let (on_db, on_rp) = match &on {
+            Some(ExtendedOnClause::Database(db)) => (Some(db.as_str()), None),
+            Some(ExtendedOnClause::DatabaseRetentionPolicy(db, rp)) => {
+                (Some(db.as_str()), Some(rp.as_str()))
             }
-            Some(
-                WithMeasurementClause::Equals(qualified_name)
-                | WithMeasurementClause::Regex(qualified_name),
-            ) if qualified_name.retention_policy.is_some() => {
-                error::not_implemented("retention policy in from clause")
-            }
-            Some(WithMeasurementClause::Equals(qualified_name)) => match qualified_name.name {
-                MeasurementName::Name(n) => {
-                    let names = self.s.table_names();
-                    let tables = if names.into_iter().any(|table| table == n.as_str()) {
-                        vec![n.as_str().to_owned()]
-                    } else {
-                        vec![]
-                    };
-                    Ok(tables)
-                }
-                MeasurementName::Regex(_) => error::query("expected string but got regex"),
-            },
-            Some(WithMeasurementClause::Regex(qualified_name)) => match &qualified_name.name {
-                MeasurementName::Name(_) => error::query("expected regex but got string"),
-                MeasurementName::Regex(regex) => {
-                    let regex = parse_regex(regex)?;
-                    let mut tables = self
-                        .s
-                        .table_names()
-                        .into_iter()
-                        .filter(|s| regex.is_match(s))
-                        .map(|s| s.to_owned())
-                        .collect::<Vec<_>>();
-                    tables.sort();
-                    Ok(tables)
-                }
-            },
-            None => {
-                let mut tables = self
-                    .s
-                    .table_names()
+            _ => (None, None),
+        };
+        let tables = match (&qualified_name, &on) {
+            (Some(qn), _) if qn.database.is_some() || qn.retention_policy.is_some() => {
+                database_table_names(
+                    self.s,
+                    qn.database.as_ref().map(|db| db.as_str()).or(on_db),
+                    qn.retention_policy.as_ref().map(|rp| rp.as_str()),
+                )?
+            }
+            (_, Some(ExtendedOnClause::AllDatabases)) => self.all_namespace_tables(false),
+            (_, Some(ExtendedOnClause::AllDatabasesAndRetentionPolicies)) => {
+                self.all_namespace_tables(true)
+            }
+            _ => database_table_names(self.s, on_db, on_rp)?,
+        };
+
+        let mut tables = match qualified_name.map(|qn| qn.name) {
+            Some(MeasurementName::Name(n)) if !is_regex => tables
+                .into_iter()
+                .filter(|table| self.s.measurement_name(table) == n.as_str())
+                .map(|s| s.to_owned())
+                .collect::<Vec<_>>(),
+            Some(MeasurementName::Regex(regex)) if is_regex => {
+                let regex = parse_regex(&regex)?;
+                tables
                     .into_iter()
+                    .filter(|table| regex.is_match(self.s.measurement_name(table)))
                     .map(|s| s.to_owned())
-                    .collect::<Vec<_>>();
-                tables.sort();
-                Ok(tables)
+                    .collect::<Vec<_>>()
             }
-        }
+            Some(MeasurementName::Regex(_)) => {
+                return error::query("expected string but got regex")
+            }
+            Some(MeasurementName::Name(_)) => return error::query("expected regex but got string"),
+            None => tables.into_iter().map(|s| s.to_owned()).collect::<Vec<_>>(),
+        };
+        tables
+            .sort_by(|a, b| (self.s.measurement_name(a), a).cmp(&(self.s.measurement_name(b), b)));
+        Ok(tables)
     }
 
-    fn show_tag_keys_to_plan(&self, show_tag_keys: ShowTagKeysStatement) -> Result<LogicalPlan> {
-        if show_tag_keys.database.is_some() {
-            // How do we handle this? Do we need to perform cross-namespace queries here?
-            return error::not_implemented("SHOW TAG KEYS ON <database>");
-        }
+    /// Returns the tables of all namespaces storing the default retention policy of a
+    /// database, or of all namespaces if `all_retention_policies` is set.
+    fn all_namespace_tables(&self, all_retention_policies: bool) -> Vec<&str> {
+        self.s
+            .namespace_names()
+            .into_iter()
+            .filter(|ns| all_retention_policies || database::database_name(ns) == *ns)
+            .flat_map(|ns| self.s.namespace_table_names(ns).unwrap_or_default())
+            .collect()
+    }
 
+    fn show_tag_keys_to_plan(&self, show_tag_keys: ShowTagKeysStatement) -> Result<LogicalPlan> {
         let tag_key_col = "tagKey";
         let output_schema = Arc::new(ArrowSchema::new(vec![
             ArrowField::new(
@@ -2377,7 +2804,10 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             ArrowField::new(tag_key_col, (&InfluxColumnType::Tag).into(), false),
         ]));
 
-        let tables = self.expand_show_from_clause(show_tag_keys.from)?;
+        let tables = self.expand_show_from_clause(
+            show_tag_keys.database.as_ref().map(|db| db.as_str()),
+            show_tag_keys.from,
+        )?;
 
         let plan = match show_tag_keys.condition {
             Some(condition) => {
@@ -2482,7 +2912,7 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
                                 continue;
                             }
                         }
-                        measurement_names_builder.append_value(&table);
+                        measurement_names_builder.append_value(self.s.measurement_name(&table));
                         tag_key_builder.append_value(f.name());
                     }
                 }
@@ -2529,11 +2959,6 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
         &self,
         show_field_keys: ShowFieldKeysStatement,
     ) -> Result<LogicalPlan> {
-        if show_field_keys.database.is_some() {
-            // How do we handle this? Do we need to perform cross-namespace queries here?
-            return error::not_implemented("SHOW FIELD KEYS ON <database>");
-        }
-
         let field_key_col = "fieldKey";
         let output_schema = Arc::new(ArrowSchema::new(vec![
             ArrowField::new(INFLUXQL_MEASUREMENT_COLUMN_NAME, DataType::Utf8, false),
@@ -2541,7 +2966,10 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             ArrowField::new("fieldType", DataType::Utf8, false),
         ]));
 
-        let tables = self.expand_show_from_clause(show_field_keys.from)?;
+        let tables = self.expand_show_from_clause(
+            show_field_keys.database.as_ref().map(|db| db.as_str()),
+            show_field_keys.from,
+        )?;
 
         let mut measurement_names_builder = StringBuilder::new();
         let mut field_key_builder = StringBuilder::new();
@@ -2562,7 +2990,7 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
                     InfluxFieldType::String => "string",
                     InfluxFieldType::Boolean => "boolean",
                 };
-                measurement_names_builder.append_value(&table);
+                measurement_names_builder.append_value(self.s.measurement_name(&table));
                 field_key_builder.append_value(f.name());
                 field_type_builder.append_value(t);
             }
@@ -2607,11 +3035,6 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
         &self,
         show_tag_values: ShowTagValuesStatement,
     ) -> Result<LogicalPlan> {
-        if show_tag_values.database.is_some() {
-            // How do we handle this? Do we need to perform cross-namespace queries here?
-            return error::not_implemented("SHOW TAG VALUES ON <database>");
-        }
-
         let key_col = "key";
         let value_col = "value";
         let output_schema = Arc::new(ArrowSchema::new(vec![
@@ -2620,7 +3043,10 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             ArrowField::new(value_col, DataType::Utf8, false),
         ]));
 
-        let tables = self.expand_show_from_clause(show_tag_values.from)?;
+        let tables = self.expand_show_from_clause(
+            show_tag_values.database.as_ref().map(|db| db.as_str()),
+            show_tag_values.from,
+        )?;
         let metadata_cutoff = self.metadata_cutoff();
 
         let mut union_plan = None;
@@ -2709,23 +3135,45 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
         &self,
         show_measurements: ShowMeasurementsStatement,
     ) -> Result<LogicalPlan> {
-        if show_measurements.on.is_some() {
-            // How do we handle this? Do we need to perform cross-namespace queries here?
-            return error::not_implemented("SHOW MEASUREMENTS ON <database>");
-        }
-
-        let tables = self.expand_with_measurement_clause(show_measurements.with_measurement)?;
+        // Like InfluxDB, the measurements of `ON *` and `ON *.*` are listed
+        // with the database and retention policy they are stored in.
+        let all_databases = matches!(
+            show_measurements.on,
+            Some(
+                ExtendedOnClause::AllDatabases | ExtendedOnClause::AllDatabasesAndRetentionPolicies
+            )
+        );
+        let tables = self.expand_with_measurement_clause(
+            show_measurements.on,
+            show_measurements.with_measurement,
+        )?;
 
         let name_col = "name";
-        let output_schema = Arc::new(ArrowSchema::new(vec![
+        let database_col = "database";
+        let retention_policy_col = "retention_policy";
+        let mut output_fields = vec![
             ArrowField::new(
                 INFLUXQL_MEASUREMENT_COLUMN_NAME,
                 (&InfluxColumnType::Tag).into(),
                 false,
             ),
             ArrowField::new(name_col, (&InfluxColumnType::Tag).into(), false),
-        ]));
+        ];
+        if all_databases {
+            output_fields.extend([
+                ArrowField::new(database_col, (&InfluxColumnType::Tag).into(), false),
+                ArrowField::new(retention_policy_col, (&InfluxColumnType::Tag).into(), false),
+            ]);
+        }
+        let output_schema = Arc::new(ArrowSchema::new(output_fields));
         let dummy_measurement_name = "measurements";
+        let table_database = |table: &str| {
+            let namespace = self.s.table_namespace(table).unwrap_or_default();
+            (
+                database::database_name(namespace),
+                database::retention_policy_name(namespace),
+            )
+        };
 
         let plan = match show_measurements.condition {
             Some(condition) => {
@@ -2743,13 +3191,20 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
                     let plan =
                         self.plan_where_clause(plan, &condition, metadata_cutoff, &schema)?;
 
+                    let mut exprs = vec![
+                        lit_dict(dummy_measurement_name).alias(INFLUXQL_MEASUREMENT_COLUMN_NAME),
+                        lit_dict(self.s.measurement_name(&table)).alias(name_col),
+                    ];
+                    if all_databases {
+                        let (db, rp) = table_database(&table);
+                        exprs.extend([
+                            lit_dict(db).alias(database_col),
+                            lit_dict(rp).alias(retention_policy_col),
+                        ]);
+                    }
                     let plan = LogicalPlanBuilder::from(plan)
                         .limit(0, Some(1))?
-                        .project([
-                            lit_dict(dummy_measurement_name)
-                                .alias(INFLUXQL_MEASUREMENT_COLUMN_NAME),
-                            lit_dict(&table).alias(name_col),
-                        ])?
+                        .project(exprs)?
                         .build()?;
 
                     union_plan = match union_plan {
@@ -2767,13 +3222,19 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
                         schema: output_schema.to_dfschema_ref()?,
                     }),
                 };
-                LogicalPlanBuilder::from(plan)
-                    .sort([
-                        Expr::Column(Column::new_unqualified(INFLUXQL_MEASUREMENT_COLUMN_NAME))
+                let mut sort_exprs = vec![
+                    Expr::Column(Column::new_unqualified(INFLUXQL_MEASUREMENT_COLUMN_NAME))
+                        .sort(true, false),
+                    Expr::Column(Column::new_unqualified(name_col)).sort(true, false),
+                ];
+                if all_databases {
+                    sort_exprs.extend([
+                        Expr::Column(Column::new_unqualified(database_col)).sort(true, false),
+                        Expr::Column(Column::new_unqualified(retention_policy_col))
                             .sort(true, false),
-                        Expr::Column(Column::new_unqualified(name_col)).sort(true, false),
-                    ])?
-                    .build()?
+                    ]);
+                }
+                LogicalPlanBuilder::from(plan).sort(sort_exprs)?.build()?
             }
             None => {
                 debug!("`SHOW MEASUREMENTS` w/o WHERE-clause, use cheap metadata scan",);
@@ -2781,9 +3242,26 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
                 let mut dummy_measurement_names_builder =
                     StringDictionaryBuilder::<Int32Type>::new();
                 let mut name_builder = StringDictionaryBuilder::<Int32Type>::new();
+                let mut database_builder = StringDictionaryBuilder::<Int32Type>::new();
+                let mut retention_policy_builder = StringDictionaryBuilder::<Int32Type>::new();
                 for table in tables {
                     dummy_measurement_names_builder.append_value(dummy_measurement_name);
-                    name_builder.append_value(table);
+                    name_builder.append_value(self.s.measurement_name(&table));
+                    if all_databases {
+                        let (db, rp) = table_database(&table);
+                        database_builder.append_value(db);
+                        retention_policy_builder.append_value(rp);
+                    }
+                }
+                let mut columns: Vec<ArrayRef> = vec![
+                    Arc::new(dummy_measurement_names_builder.finish()),
+                    Arc::new(name_builder.finish()),
+                ];
+                if all_databases {
+                    columns.extend([
+                        Arc::new(database_builder.finish()) as ArrayRef,
+                        Arc::new(retention_policy_builder.finish()),
+                    ]);
                 }
                 LogicalPlanBuilder::scan(
                     "measurements",
@@ -2791,10 +3269,7 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
                         Arc::clone(&output_schema),
                         vec![vec![RecordBatch::try_new(
                             Arc::clone(&output_schema),
-                            vec![
-                                Arc::new(dummy_measurement_names_builder.finish()),
-                                Arc::new(name_builder.finish()),
-                            ],
+                            columns,
                         )?]],
                     )?)),
                     None,
@@ -2823,16 +3298,327 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
         Ok(plan)
     }
 
-    /// A limited implementation of SHOW RETENTION POLICIES that assumes
-    /// any database has a single, default, retention policy.
+    /// Returns a plan which produces the distinct series keys of the tables selected by
+    /// `from`, filtered by `condition`, or `None` if no table is selected.
+    ///
+    /// The plan has two columns, the measurement name and the series `key`, which is the
+    /// measurement name followed by the sorted, non-null `tag=value` pairs of the series.
+    fn series_key_plan(
+        &self,
+        on: Option<&str>,
+        from: Option<ShowFromClause>,
+        condition: Option<WhereClause>,
+    ) -> Result<Option<LogicalPlan>> {
+        let tables = self.expand_show_from_clause(on, from)?;
+        let metadata_cutoff = self.metadata_cutoff();
+
+        let mut union_plan = None;
+        for table in tables {
+            let Some(table_schema) = self.s.table_schema(&table) else {continue;};
+            let Some((plan, measurement_expr)) = self.create_table_ref(&table)? else {continue;};
+
+            let ds = DataSource::Table(table.clone());
+            let schema = IQLSchema::new_from_ds_schema(plan.schema(), ds.schema(self.s)?)?;
+            let plan = self.plan_where_clause(plan, &condition, metadata_cutoff, &schema)?;
+
+            let mut tags = table_schema
+                .tags_iter()
+                .map(|f| f.name().as_str())
+                .collect::<Vec<_>>();
+            tags.sort_unstable();
+
+            // Tags without a value for a given series are omitted from the key,
+            // which relies on CONCAT ignoring NULL arguments.
+            let key_expr = concat(
+                &iter::once(lit(escape_measurement(&self.s.measurement_name(&table))))
+                    .chain(tags.into_iter().map(|tag| {
+                        let tag_col = cast(Expr::Column(Column::from_name(tag)), DataType::Utf8);
+                        when(
+                            tag_col.clone().is_not_null(),
+                            concat(&[
+                                lit(format!(",{}=", escape_tag(tag))),
+                                escape_tag_expr(tag_col),
+                            ]),
+                        )
+                        .end()
+                    }))
+                    .collect::<Result<Vec<_>, _>>()?,
+            );
+
+            let plan = LogicalPlanBuilder::from(plan)
+                .project(
+                    measurement_expr
+                        .into_iter()
+                        .chain([key_expr.alias(SERIES_KEY_COL)]),
+                )?
+                .distinct()?
+                .build()?;
+
+            union_plan = match union_plan {
+                Some(union_plan) => {
+                    Some(LogicalPlanBuilder::from(union_plan).union(plan)?.build()?)
+                }
+                None => Some(plan),
+            };
+        }
+
+        Ok(union_plan)
+    }
+
+    fn show_series_to_plan(&self, show_series: ShowSeriesStatement) -> Result<LogicalPlan> {
+        let plan = match self.series_key_plan(
+            show_series.database.as_ref().map(|db| db.as_str()),
+            show_series.from,
+            show_series.condition,
+        )? {
+            Some(plan) => plan,
+            None => LogicalPlan::EmptyRelation(EmptyRelation {
+                produce_one_row: false,
+                schema: ArrowSchema::new(vec![
+                    ArrowField::new(
+                        INFLUXQL_MEASUREMENT_COLUMN_NAME,
+                        (&InfluxColumnType::Tag).into(),
+                        false,
+                    ),
+                    ArrowField::new(SERIES_KEY_COL, DataType::Utf8, false),
+                ])
+                .to_dfschema_ref()?,
+            }),
+        };
+        let plan = LogicalPlanBuilder::from(plan)
+            .sort([
+                Expr::Column(Column::new_unqualified(INFLUXQL_MEASUREMENT_COLUMN_NAME))
+                    .sort(true, false),
+                Expr::Column(Column::new_unqualified(SERIES_KEY_COL)).sort(true, false),
+            ])?
+            .build()?;
+        let plan = plan_with_metadata(
+            plan,
+            &InfluxQlMetadata {
+                measurement_column_index: MEASUREMENT_COLUMN_INDEX,
+                tag_key_columns: vec![],
+            },
+        )?;
+        let plan = self.limit(
+            plan,
+            show_series.offset,
+            show_series.limit,
+            vec![Expr::Column(Column::new_unqualified(SERIES_KEY_COL)).sort(true, false)],
+            true,
+            &[],
+            &[],
+        )?;
+
+        Ok(plan)
+    }
+
+    /// Plan a `SHOW ... CARDINALITY` statement.
+    ///
+    /// The cardinality is reported for each measurement, with the exception of
+    /// `SHOW MEASUREMENT CARDINALITY`, which reports a single count. Without the `EXACT`
+    /// keyword, series and measurement cardinality is estimated using HyperLogLog.
+    fn show_cardinality_to_plan(
+        &self,
+        show_cardinality: ShowCardinalityStatement,
+    ) -> Result<LogicalPlan> {
+        let ShowCardinalityStatement {
+            kind,
+            exact,
+            database,
+            from,
+            with_key,
+            condition,
+            limit,
+            offset,
+        } = show_cardinality;
+
+        let on = database.as_ref().map(|db| db.as_str());
+        let measurement_col =
+            Expr::Column(Column::new_unqualified(INFLUXQL_MEASUREMENT_COLUMN_NAME));
+        let count_expr = |col: &str| {
+            let col = Expr::Column(Column::new_unqualified(col));
+            if exact {
+                count_distinct(col)
+            } else {
+                cast(approx_distinct(col), DataType::Int64)
+            }
+        };
+
+        let (plan, group_expr, aggr_expr) = match kind {
+            CardinalityKind::Series => {
+                let Some(plan) = self.series_key_plan(on, from, condition)? else {
+                    return self.empty_cardinality_plan();
+                };
+                (
+                    plan,
+                    vec![measurement_col.clone()],
+                    count_expr(SERIES_KEY_COL),
+                )
+            }
+            CardinalityKind::Measurement => {
+                let plan = match condition {
+                    Some(condition) => match self.series_key_plan(on, from, Some(condition))? {
+                        Some(plan) => plan,
+                        None => return self.empty_cardinality_plan(),
+                    },
+                    None => {
+                        debug!("`SHOW MEASUREMENT CARDINALITY` w/o WHERE-clause, use metadata");
+
+                        let schema = Arc::new(ArrowSchema::new(vec![ArrowField::new(
+                            INFLUXQL_MEASUREMENT_COLUMN_NAME,
+                            (&InfluxColumnType::Tag).into(),
+                            false,
+                        )]));
+                        let mut measurement_names_builder =
+                            StringDictionaryBuilder::<Int32Type>::new();
+                        for table in self.expand_show_from_clause(on, from)? {
+                            measurement_names_builder.append_value(self.s.measurement_name(&table));
+                        }
+                        LogicalPlanBuilder::scan(
+                            "measurements",
+                            provider_as_source(Arc::new(MemTable::try_new(
+                                Arc::clone(&schema),
+                                vec![vec![RecordBatch::try_new(
+                                    Arc::clone(&schema),
+                                    vec![Arc::new(measurement_names_builder.finish())],
+                                )?]],
+                            )?)),
+                            None,
+                        )?
+                        .build()?
+                    }
+                };
+                (plan, vec![], count_expr(INFLUXQL_MEASUREMENT_COLUMN_NAME))
+            }
+            CardinalityKind::TagKey => {
+                let plan = self.show_tag_keys_to_plan(ShowTagKeysStatement {
+                    database: database.clone(),
+                    from,
+                    condition,
+                    limit: None,
+                    offset: None,
+                })?;
+                (plan, vec![measurement_col.clone()], count(lit(1)))
+            }
+            CardinalityKind::TagValues => {
+                let Some(with_key) = with_key else {
+                    return error::query("SHOW TAG VALUES CARDINALITY requires a WITH KEY clause");
+                };
+                // The tag values plan produces distinct key / value pairs, so
+                // counting the rows yields an exact result.
+                let plan = self.show_tag_values_to_plan(ShowTagValuesStatement {
+                    database: database.clone(),
+                    from,
+                    with_key,
+                    condition,
+                    limit: None,
+                    offset: None,
+                })?;
+                (plan, vec![measurement_col.clone()], count(lit(1)))
+            }
+            CardinalityKind::FieldKey => {
+                if condition.is_some() {
+                    return error::not_implemented("SHOW FIELD KEY CARDINALITY with WHERE clause");
+                }
+                let plan = self.show_field_keys_to_plan(ShowFieldKeysStatement {
+                    database: database.clone(),
+                    from,
+                    limit: None,
+                    offset: None,
+                })?;
+                (plan, vec![measurement_col.clone()], count(lit(1)))
+            }
+        };
+
+        let plan = LogicalPlanBuilder::from(plan)
+            .aggregate(group_expr.clone(), [aggr_expr.alias(CARDINALITY_COUNT_COL)])?
+            .build()?;
+
+        let plan = if group_expr.is_empty() {
+            LogicalPlanBuilder::from(plan)
+                .project([
+                    lit_dict("measurements").alias(INFLUXQL_MEASUREMENT_COLUMN_NAME),
+                    Expr::Column(Column::new_unqualified(CARDINALITY_COUNT_COL)),
+                ])?
+                .build()?
+        } else {
+            LogicalPlanBuilder::from(plan)
+                .sort([measurement_col.sort(true, false)])?
+                .build()?
+        };
+        let plan = plan_with_metadata(
+            plan,
+            &InfluxQlMetadata {
+                measurement_column_index: MEASUREMENT_COLUMN_INDEX,
+                tag_key_columns: vec![],
+            },
+        )?;
+        let plan = self.limit(plan, offset, limit, vec![], false, &[], &[])?;
+
+        Ok(plan)
+    }
+
+    /// Returns an empty plan with the schema of a `SHOW ... CARDINALITY` statement.
+    fn empty_cardinality_plan(&self) -> Result<LogicalPlan> {
+        let plan = LogicalPlan::EmptyRelation(EmptyRelation {
+            produce_one_row: false,
+            schema: ArrowSchema::new(vec![
+                ArrowField::new(
+                    INFLUXQL_MEASUREMENT_COLUMN_NAME,
+                    (&InfluxColumnType::Tag).into(),
+                    false,
+                ),
+                ArrowField::new(CARDINALITY_COUNT_COL, DataType::Int64, false),
+            ])
+            .to_dfschema_ref()?,
+        });
+        plan_with_metadata(
+            plan,
+            &InfluxQlMetadata {
+                measurement_column_index: MEASUREMENT_COLUMN_INDEX,
+                tag_key_columns: vec![],
+            },
+        )
+    }
+
+    /// Plan a `SHOW RETENTION POLICIES` statement, listing a retention policy for each
+    /// namespace of the database.
+    ///
+    /// The duration and shard group duration of the retention policies are not reported.
     fn show_retention_policies_to_plan(
         &self,
         show_retention_policies: ShowRetentionPoliciesStatement,
     ) -> Result<LogicalPlan> {
-        if show_retention_policies.database.is_some() {
-            // This syntax is not yet handled.
-            return error::not_implemented("SHOW RETENTION POLICIES ON <database>");
-        }
+        let db = show_retention_policies
+            .database
+            .as_ref()
+            .map(|db| db.as_str())
+            .or_else(|| self.s.namespace_name().map(database::database_name));
+        let retention_policies = match db {
+            Some(db) => {
+                let rps = self
+                    .s
+                    .namespace_names()
+                    .into_iter()
+                    .filter(|ns| database::database_name(ns) == db)
+                    .map(database::retention_policy_name)
+                    .sorted()
+                    .dedup()
+                    .collect::<Vec<_>>();
+                if rps.is_empty() && show_retention_policies.database.is_some() {
+                    return error::query(format!("database not found: {db}"));
+                }
+                rps
+            }
+            None => vec![],
+        };
+        // Without the namespaces of the database, assume a single, default, retention policy.
+        let retention_policies = if retention_policies.is_empty() {
+            vec![database::DEFAULT_RETENTION_POLICY]
+        } else {
+            retention_policies
+        };
+        let n = retention_policies.len();
 
         let output_schema = Arc::new(ArrowSchema::new(vec![
             ArrowField::new(
@@ -2870,14 +3656,19 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             Arc::clone(&output_schema),
             vec![
                 Arc::new(DictionaryArray::try_new(
-                    Int32Array::from(vec![0]),
+                    Int32Array::from(vec![0; n]),
                     Arc::new(StringArray::from(vec![Some("retention_policies")])),
                 )?),
-                Arc::new(StringArray::from(vec![Some("autogen")])),
-                Arc::new(StringArray::from(vec![Some("0s")])),
-                Arc::new(StringArray::from(vec![Some("168h0m0s")])),
-                Arc::new(Int64Array::from(vec![1])),
-                Arc::new(BooleanArray::from(vec![true])),
+                Arc::new(StringArray::from(retention_policies.clone())),
+                Arc::new(StringArray::from(vec!["0s"; n])),
+                Arc::new(StringArray::from(vec!["168h0m0s"; n])),
+                Arc::new(Int64Array::from(vec![1; n])),
+                Arc::new(BooleanArray::from(
+                    retention_policies
+                        .iter()
+                        .map(|rp| *rp == database::DEFAULT_RETENTION_POLICY)
+                        .collect::<Vec<_>>(),
+                )),
             ],
         )?;
         let table = Arc::new(MemTable::try_new(output_schema, vec![vec![record_batch]])?);
@@ -3143,6 +3934,24 @@ fn plan_with_metadata(plan: LogicalPlan, metadata: &InfluxQlMetadata) -> Result<
     set_schema(&plan, metadata)
 }
 
+/// Escape a measurement name for a series key, like InfluxDB.
+fn escape_measurement(name: &str) -> String {
+    name.replace(',', "\\,").replace(' ', "\\ ")
+}
+
+/// Escape a tag key or value for a series key, like InfluxDB.
+fn escape_tag(s: &str) -> String {
+    escape_measurement(s).replace('=', "\\=")
+}
+
+/// The expression escaping the tag values of `expr` for a series key, like
+/// [`escape_tag`].
+fn escape_tag_expr(expr: Expr) -> Expr {
+    [",", " ", "="]
+        .into_iter()
+        .fold(expr, |expr, c| replace(expr, lit(c), lit(format!("\\{c}"))))
+}
+
 /// A utility function that checks whether `f` is an
 /// aggregate field or not. An aggregate field is one that contains at least one
 /// call to an aggregate function.
@@ -3338,6 +4147,27 @@ mod test {
                 .build()
                 .unwrap(),
         ]);
+        // Tables of other namespaces, referenced with database and retention policy qualifiers
+        sp.add_namespace_schema(
+            "my_db/ripe",
+            SchemaBuilder::new()
+                .measurement("data")
+                .timestamp()
+                .tag("foo")
+                .influx_field("f64_field", InfluxFieldType::Float)
+                .build()
+                .unwrap(),
+        );
+        sp.add_namespace_schema(
+            "other_db",
+            SchemaBuilder::new()
+                .measurement("data")
+                .timestamp()
+                .tag("bar")
+                .influx_field("f64_field", InfluxFieldType::Float)
+                .build()
+                .unwrap(),
+        );
 
         let iox_ctx = IOxSessionContext::with_testing();
         let planner = InfluxQLToLogicalPlan::new(&sp, &iox_ctx);
@@ -3434,15 +4264,85 @@ mod test {
     /// It is expected certain statements will be unsupported, indefinitely.
     #[test]
     fn test_unsupported_statements() {
-        assert_snapshot!(plan("CREATE DATABASE foo"), @"This feature is not implemented: CREATE DATABASE");
         assert_snapshot!(plan("DELETE FROM foo"), @"This feature is not implemented: DELETE");
         assert_snapshot!(plan("DROP MEASUREMENT foo"), @"This feature is not implemented: DROP MEASUREMENT");
-        assert_snapshot!(plan("SHOW DATABASES"), @"This feature is not implemented: SHOW DATABASES");
+    }
+
+    /// Verify statements referencing databases and retention policies, which are mapped to
+    /// namespaces.
+    #[test]
+    fn test_databases() {
+        assert_snapshot!(plan("SHOW DATABASES"), @r###"
+        TableScan: databases [iox::measurement:Dictionary(Int32, Utf8), name:Utf8]
+        "###);
+        // The namespace is created before the statement is planned.
+        assert_snapshot!(plan("CREATE DATABASE foo"), @"EmptyRelation [iox::measurement:Dictionary(Int32, Utf8)]");
+
+        // A database qualifier selects the table of another namespace, which is reported
+        // with its measurement name.
+        let got = plan("SELECT f64_field FROM other_db..data");
+        assert!(got.contains("TableScan: other_db::data"), "{got}");
+        assert!(
+            got.contains(r#"Dictionary(Int32, Utf8("data")) AS iox::measurement"#),
+            "{got}"
+        );
+
+        // A retention policy qualifier applies to the database of the query.
+        let got = plan("SELECT f64_field FROM ripe.data");
+        assert!(got.contains("TableScan: my_db/ripe::data"), "{got}");
+        let got = plan("SELECT f64_field FROM my_db.autogen.data");
+        assert!(got.contains("TableScan: data "), "{got}");
+        assert!(!got.contains("::data"), "{got}");
+
+        // Measurements of several namespaces are combined.
+        let got = plan("SELECT f64_field FROM data, my_db.ripe.data, other_db..data");
+        assert!(got.contains("TableScan: data "), "{got}");
+        assert!(got.contains("TableScan: my_db/ripe::data"), "{got}");
+        assert!(got.contains("TableScan: other_db::data"), "{got}");
+        let got = plan("SELECT f64_field FROM (SELECT f64_field FROM other_db..data)");
+        assert!(got.contains("TableScan: other_db::data"), "{got}");
+
+        // Fallible
+
+        assert_snapshot!(plan("SELECT f64_field FROM nope..data"), @"Error during planning: database not found: nope");
+        assert_snapshot!(plan("SELECT f64_field FROM green.data"), @"Error during planning: retention policy not found: green");
     }
 
     mod metadata_queries {
         use super::*;
 
+        #[test]
+        fn test_show_on_database() {
+            assert_snapshot!(plan("SHOW MEASUREMENTS ON other_db"), @"TableScan: measurements [iox::measurement:Dictionary(Int32, Utf8), name:Dictionary(Int32, Utf8)]");
+            assert_snapshot!(plan("SHOW MEASUREMENTS ON *.*"), @"TableScan: measurements [iox::measurement:Dictionary(Int32, Utf8), name:Dictionary(Int32, Utf8), database:Dictionary(Int32, Utf8), retention_policy:Dictionary(Int32, Utf8)]");
+            let got = plan("SHOW MEASUREMENTS ON my_db.ripe WHERE foo = 'some_foo'");
+            assert!(got.contains("TableScan: my_db/ripe::data"), "{got}");
+            assert!(
+                got.contains(r#"Dictionary(Int32, Utf8("data")) AS name"#),
+                "{got}"
+            );
+            let got = plan("SHOW MEASUREMENTS ON * WHERE bar = 'some_bar'");
+            assert!(got.contains("TableScan: other_db::data"), "{got}");
+            assert!(
+                got.contains(r#"Dictionary(Int32, Utf8("other_db")) AS database"#),
+                "{got}"
+            );
+            assert!(!got.contains("my_db/ripe::data"), "{got}");
+
+            // Measurements without a database qualifier are read from the database of the ON clause.
+            let got = plan("SHOW TAG KEYS ON other_db FROM data WHERE bar = 'some_bar'");
+            assert!(got.contains("TableScan: other_db::data"), "{got}");
+            let got = plan("SHOW TAG VALUES ON other_db FROM data WITH KEY = bar");
+            assert!(got.contains("TableScan: other_db::data"), "{got}");
+            let got = plan("SHOW TAG VALUES ON other_db FROM my_db..data WITH KEY = bar");
+            assert!(got.contains("TableScan: data "), "{got}");
+            let got = plan("SHOW FIELD KEYS ON my_db FROM ripe.data");
+            assert!(got.contains("TableScan: field_keys"), "{got}");
+
+            assert_snapshot!(plan("SHOW FIELD KEYS ON nope"), @"Error during planning: database not found: nope");
+            assert_snapshot!(plan("SHOW TAG KEYS FROM green.data"), @"Error during planning: retention policy not found: green");
+        }
+
         #[test]
         fn test_show_field_keys() {
             assert_snapshot!(plan("SHOW FIELD KEYS"), @"TableScan: field_keys [iox::measurement:Utf8, fieldKey:Utf8, fieldType:Utf8]");
@@ -3797,14 +4697,61 @@ mod test {
             "###);
         }
 
+        #[test]
+        fn test_show_series() {
+            let got = plan("SHOW SERIES FROM data");
+            assert!(got.contains("Distinct:"), "{got}");
+            assert!(got.contains(" AS key"), "{got}");
+            let got = plan("SHOW SERIES ON other_db");
+            assert!(got.contains("TableScan: other_db::data"), "{got}");
+            assert!(got.contains(r#"Utf8("data")"#), "{got}");
+        }
+
+        #[test]
+        fn test_series_key_escaping() {
+            assert_eq!(escape_measurement("cpu load,1=2"), r"cpu\ load\,1=2");
+            assert_eq!(escape_tag("host name,a=b"), r"host\ name\,a\=b");
+            assert_eq!(escape_tag("server01"), "server01");
+
+            // tag values are escaped by the plan
+            let got = plan("SHOW SERIES FROM data");
+            assert!(got.contains(r#"replace("#), "{got}");
+        }
+
+        #[test]
+        fn test_show_cardinality() {
+            let got = plan("SHOW SERIES EXACT CARDINALITY FROM data");
+            assert!(got.contains("COUNT(DISTINCT key)"), "{got}");
+            let got = plan("SHOW SERIES CARDINALITY FROM data");
+            assert!(got.contains("APPROX_DISTINCT(key)"), "{got}");
+
+            // uses the catalog, rather than scanning the data
+            let got = plan("SHOW MEASUREMENT EXACT CARDINALITY");
+            assert!(got.contains("TableScan: measurements"), "{got}");
+            let got = plan("SHOW MEASUREMENT EXACT CARDINALITY WHERE foo = 'some_foo'");
+            assert!(got.contains("TableScan: data"), "{got}");
+
+            let got = plan("SHOW TAG KEY CARDINALITY");
+            assert!(got.contains("TableScan: tag_keys"), "{got}");
+            let got = plan("SHOW TAG VALUES CARDINALITY WITH KEY = bar");
+            assert!(got.contains("AS value"), "{got}");
+            let got = plan("SHOW FIELD KEY CARDINALITY");
+            assert!(got.contains("TableScan: field_keys"), "{got}");
+
+            let got = plan("SHOW SERIES CARDINALITY ON other_db");
+            assert!(got.contains("TableScan: other_db::data"), "{got}");
+            assert_snapshot!(plan("SHOW FIELD KEY CARDINALITY WHERE foo = 'some_foo'"), @"This feature is not implemented: SHOW FIELD KEY CARDINALITY with WHERE clause");
+        }
+
         #[test]
         fn test_show_retention_policies() {
             assert_snapshot!(plan("SHOW RETENTION POLICIES"), @r###"
             TableScan: retention policies [iox::measurement:Dictionary(Int32, Utf8), name:Utf8, duration:Utf8, shardGroupDuration:Utf8, replicaN:Int64, default:Boolean]
             "###);
             assert_snapshot!(plan("SHOW RETENTION POLICIES ON my_db"), @r###"
-            This feature is not implemented: SHOW RETENTION POLICIES ON <database>
+            TableScan: retention policies [iox::measurement:Dictionary(Int32, Utf8), name:Utf8, duration:Utf8, shardGroupDuration:Utf8, replicaN:Int64, default:Boolean]
             "###);
+            assert_snapshot!(plan("SHOW RETENTION POLICIES ON nope"), @"Error during planning: database not found: nope");
         }
     }
 
@@ -4151,6 +5098,28 @@ mod test {
                 "###);
             }
 
+            #[test]
+            fn test_elapsed() {
+                assert_snapshot!(plan("SELECT ELAPSED(usage_idle) FROM cpu"), @r###"
+                Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), elapsed:Int64;N]
+                  Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, time, elapsed [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), elapsed:Int64;N]
+                    Filter: NOT elapsed IS NULL [time:Timestamp(Nanosecond, None), elapsed:Int64;N]
+                      Projection: cpu.time AS time, elapsed(cpu.usage_idle) AS elapsed [time:Timestamp(Nanosecond, None), elapsed:Int64;N]
+                        WindowAggr: windowExpr=[[elapsed(cpu.usage_idle, IntervalMonthDayNano("1"), cpu.time) ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING AS elapsed(cpu.usage_idle)]] [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N, elapsed(cpu.usage_idle):Int64;N]
+                          TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                "###);
+
+                // explicit unit
+                assert_snapshot!(plan("SELECT ELAPSED(usage_idle, 1s) FROM cpu"), @r###"
+                Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), elapsed:Int64;N]
+                  Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, time, elapsed [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), elapsed:Int64;N]
+                    Filter: NOT elapsed IS NULL [time:Timestamp(Nanosecond, None), elapsed:Int64;N]
+                      Projection: cpu.time AS time, elapsed(cpu.usage_idle,IntervalMonthDayNano("1000000000")) AS elapsed [time:Timestamp(Nanosecond, None), elapsed:Int64;N]
+                        WindowAggr: windowExpr=[[elapsed(cpu.usage_idle, IntervalMonthDayNano("1000000000"), cpu.time) ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING AS elapsed(cpu.usage_idle,IntervalMonthDayNano("1000000000"))]] [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N, elapsed(cpu.usage_idle,IntervalMonthDayNano("1000000000")):Int64;N]
+                          TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                "###);
+            }
+
             #[test]
             fn test_not_implemented() {
                 assert_snapshot!(plan("SELECT DIFFERENCE(MEAN(usage_idle)), MEAN(usage_idle) FROM cpu GROUP BY TIME(10s)"), @"This feature is not implemented: mixed window-aggregate and aggregate columns, such as DIFFERENCE(MEAN(col)), MEAN(col)");
@@ -4388,6 +5357,120 @@ mod test {
             "###);
         }
 
+        #[test]
+        fn test_spread() {
+            assert_snapshot!(plan("SELECT spread(usage_idle) FROM cpu"), @r###"
+            Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), spread:Float64;N]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, TimestampNanosecond(0, None) AS time, spread(cpu.usage_idle) AS spread [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), spread:Float64;N]
+                Aggregate: groupBy=[[]], aggr=[[spread(cpu.usage_idle)]] [spread(cpu.usage_idle):Float64;N]
+                  TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+
+            assert_snapshot!(plan("SELECT spread(usage_idle) FROM cpu WHERE time >= 0 AND time < 60000000000 GROUP BY time(10s), cpu FILL(none)"), @r###"
+            Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, cpu:Dictionary(Int32, Utf8);N, spread:Float64;N]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, time, cpu.cpu AS cpu, spread(cpu.usage_idle) AS spread [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, cpu:Dictionary(Int32, Utf8);N, spread:Float64;N]
+                Aggregate: groupBy=[[date_bin(IntervalMonthDayNano("10000000000"), cpu.time, TimestampNanosecond(0, None)) AS time, cpu.cpu]], aggr=[[spread(cpu.usage_idle)]] [time:Timestamp(Nanosecond, None);N, cpu:Dictionary(Int32, Utf8);N, spread(cpu.usage_idle):Float64;N]
+                  Filter: cpu.time >= TimestampNanosecond(0, None) AND cpu.time <= TimestampNanosecond(59999999999, None) [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                    TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+        }
+
+        #[test]
+        fn test_mode() {
+            assert_snapshot!(plan("SELECT mode(usage_idle) FROM cpu"), @r###"
+            Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), mode:Float64;N]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, TimestampNanosecond(0, None) AS time, mode(cpu.usage_idle) AS mode [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), mode:Float64;N]
+                Aggregate: groupBy=[[]], aggr=[[mode(cpu.usage_idle)]] [mode(cpu.usage_idle):Float64;N]
+                  TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+
+            assert_snapshot!(plan("SELECT mode(usage_idle) FROM cpu GROUP BY cpu"), @r###"
+            Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, mode:Float64;N]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, TimestampNanosecond(0, None) AS time, cpu.cpu AS cpu, mode(cpu.usage_idle) AS mode [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, mode:Float64;N]
+                Aggregate: groupBy=[[cpu.cpu]], aggr=[[mode(cpu.usage_idle)]] [cpu:Dictionary(Int32, Utf8);N, mode(cpu.usage_idle):Float64;N]
+                  TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+        }
+
+        #[test]
+        fn test_integral() {
+            // default unit of 1s
+            assert_snapshot!(plan("SELECT integral(usage_idle) FROM cpu"), @r###"
+            Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), integral:Float64;N]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, TimestampNanosecond(0, None) AS time, integral(cpu.usage_idle,IntervalMonthDayNano("1000000000"),cpu.time) AS integral [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), integral:Float64;N]
+                Aggregate: groupBy=[[]], aggr=[[integral(cpu.usage_idle, IntervalMonthDayNano("1000000000"), cpu.time)]] [integral(cpu.usage_idle,IntervalMonthDayNano("1000000000"),cpu.time):Float64;N]
+                  TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+
+            // explicit unit
+            assert_snapshot!(plan("SELECT integral(usage_idle, 1m) FROM cpu"), @r###"
+            Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), integral:Float64;N]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, TimestampNanosecond(0, None) AS time, integral(cpu.usage_idle,IntervalMonthDayNano("60000000000"),cpu.time) AS integral [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), integral:Float64;N]
+                Aggregate: groupBy=[[]], aggr=[[integral(cpu.usage_idle, IntervalMonthDayNano("60000000000"), cpu.time)]] [integral(cpu.usage_idle,IntervalMonthDayNano("60000000000"),cpu.time):Float64;N]
+                  TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+
+            // not interpolated at the boundaries of the intervals
+            assert_snapshot!(plan("SELECT integral(usage_idle, 1m) FROM cpu WHERE time >= 0 AND time < 60000000000 GROUP BY time(10s) FILL(none)"), @"This feature is not implemented: integral with GROUP BY time");
+        }
+
+        #[test]
+        fn test_sample() {
+            assert_snapshot!(plan("SELECT sample(usage_idle, 2) FROM cpu"), @r###"
+            Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), sample:Float64;N]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, cpu.time AS time, cpu.usage_idle AS sample [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), sample:Float64;N]
+                Filter: ROW_NUMBER() ORDER BY [random() ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW <= Int64(2) [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N, ROW_NUMBER() ORDER BY [random() ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW:UInt64;N]
+                  WindowAggr: windowExpr=[[ROW_NUMBER() ORDER BY [random() ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS ROW_NUMBER() ORDER BY [random() ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW]] [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N, ROW_NUMBER() ORDER BY [random() ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW:UInt64;N]
+                    Filter: cpu.usage_idle IS NOT NULL [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                      TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+
+            assert_snapshot!(plan("SELECT sample(usage_idle, 2) FROM cpu GROUP BY cpu"), @r###"
+            Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, sample:Float64;N]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, cpu.time AS time, cpu.cpu AS cpu, cpu.usage_idle AS sample [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, sample:Float64;N]
+                Filter: ROW_NUMBER() PARTITION BY [cpu] ORDER BY [random() ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW <= Int64(2) [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N, ROW_NUMBER() PARTITION BY [cpu] ORDER BY [random() ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW:UInt64;N]
+                  WindowAggr: windowExpr=[[ROW_NUMBER() PARTITION BY [cpu.cpu] ORDER BY [random() ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS ROW_NUMBER() PARTITION BY [cpu] ORDER BY [random() ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW]] [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N, ROW_NUMBER() PARTITION BY [cpu] ORDER BY [random() ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW:UInt64;N]
+                    Filter: cpu.usage_idle IS NOT NULL [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                      TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+        }
+
+        #[test]
+        fn test_holt_winters() {
+            assert_snapshot!(plan("SELECT holt_winters(mean(usage_idle), 2, 0) FROM cpu WHERE time >= 0 AND time < 60000000000 GROUP BY time(10s) FILL(none)"), @r###"
+            Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), holt_winters:Float64]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, (holt_winters)[time] AS time, (holt_winters)[value] AS holt_winters [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), holt_winters:Float64]
+                Filter: holt_winters IS NOT NULL [holt_winters:Struct([Field { name: "time", data_type: Timestamp(Nanosecond, None), nullable: false, dict_id: 0, dict_is_ordered: false, metadata: {} }, Field { name: "value", data_type: Float64, nullable: false, dict_id: 0, dict_is_ordered: false, metadata: {} }]);N]
+                  Unnest: holt_winters [holt_winters:Struct([Field { name: "time", data_type: Timestamp(Nanosecond, None), nullable: false, dict_id: 0, dict_is_ordered: false, metadata: {} }, Field { name: "value", data_type: Float64, nullable: false, dict_id: 0, dict_is_ordered: false, metadata: {} }]);N]
+                    Aggregate: groupBy=[[]], aggr=[[holt_winters(holt_winters, time, Int64(2), Int64(0), Int64(10000000000), Boolean(false)) AS holt_winters]] [holt_winters:List(Field { name: "item", data_type: Struct([Field { name: "time", data_type: Timestamp(Nanosecond, None), nullable: false, dict_id: 0, dict_is_ordered: false, metadata: {} }, Field { name: "value", data_type: Float64, nullable: false, dict_id: 0, dict_is_ordered: false, metadata: {} }]), nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} });N]
+                      Filter: holt_winters IS NOT NULL [time:Timestamp(Nanosecond, None);N, holt_winters:Float64;N]
+                        Projection: time, AVG(cpu.usage_idle) AS holt_winters [time:Timestamp(Nanosecond, None);N, holt_winters:Float64;N]
+                          Aggregate: groupBy=[[date_bin(IntervalMonthDayNano("10000000000"), cpu.time, TimestampNanosecond(0, None)) AS time]], aggr=[[AVG(cpu.usage_idle)]] [time:Timestamp(Nanosecond, None);N, AVG(cpu.usage_idle):Float64;N]
+                            Filter: cpu.time >= TimestampNanosecond(0, None) AND cpu.time <= TimestampNanosecond(59999999999, None) [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                              TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+
+            // forecast each series, including the fitted values
+            assert_snapshot!(plan("SELECT holt_winters_with_fit(mean(usage_idle), 2, 3) FROM cpu WHERE time >= 0 AND time < 60000000000 GROUP BY time(10s), cpu FILL(none)"), @r###"
+            Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, holt_winters_with_fit:Float64]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, (holt_winters_with_fit)[time] AS time, cpu, (holt_winters_with_fit)[value] AS holt_winters_with_fit [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, holt_winters_with_fit:Float64]
+                Filter: holt_winters_with_fit IS NOT NULL [cpu:Dictionary(Int32, Utf8);N, holt_winters_with_fit:Struct([Field { name: "time", data_type: Timestamp(Nanosecond, None), nullable: false, dict_id: 0, dict_is_ordered: false, metadata: {} }, Field { name: "value", data_type: Float64, nullable: false, dict_id: 0, dict_is_ordered: false, metadata: {} }]);N]
+                  Unnest: holt_winters_with_fit [cpu:Dictionary(Int32, Utf8);N, holt_winters_with_fit:Struct([Field { name: "time", data_type: Timestamp(Nanosecond, None), nullable: false, dict_id: 0, dict_is_ordered: false, metadata: {} }, Field { name: "value", data_type: Float64, nullable: false, dict_id: 0, dict_is_ordered: false, metadata: {} }]);N]
+                    Aggregate: groupBy=[[cpu]], aggr=[[holt_winters(holt_winters_with_fit, time, Int64(2), Int64(3), Int64(10000000000), Boolean(true)) AS holt_winters_with_fit]] [cpu:Dictionary(Int32, Utf8);N, holt_winters_with_fit:List(Field { name: "item", data_type: Struct([Field { name: "time", data_type: Timestamp(Nanosecond, None), nullable: false, dict_id: 0, dict_is_ordered: false, metadata: {} }, Field { name: "value", data_type: Float64, nullable: false, dict_id: 0, dict_is_ordered: false, metadata: {} }]), nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} });N]
+                      Filter: holt_winters_with_fit IS NOT NULL [time:Timestamp(Nanosecond, None);N, cpu:Dictionary(Int32, Utf8);N, holt_winters_with_fit:Float64;N]
+                        Projection: time, cpu.cpu AS cpu, AVG(cpu.usage_idle) AS holt_winters_with_fit [time:Timestamp(Nanosecond, None);N, cpu:Dictionary(Int32, Utf8);N, holt_winters_with_fit:Float64;N]
+                          Aggregate: groupBy=[[date_bin(IntervalMonthDayNano("10000000000"), cpu.time, TimestampNanosecond(0, None)) AS time, cpu.cpu]], aggr=[[AVG(cpu.usage_idle)]] [time:Timestamp(Nanosecond, None);N, cpu:Dictionary(Int32, Utf8);N, AVG(cpu.usage_idle):Float64;N]
+                            Filter: cpu.time >= TimestampNanosecond(0, None) AND cpu.time <= TimestampNanosecond(59999999999, None) [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                              TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+
+            // Fallible
+
+            // holt_winters is not supported in an expression or with other functions
+            assert_snapshot!(plan("SELECT holt_winters(mean(usage_idle), 2, 0) * 2 FROM cpu GROUP BY time(10s)"), @"This feature is not implemented: holt_winters in an expression");
+            assert_snapshot!(plan("SELECT holt_winters(mean(usage_idle), 2, 0), mean(usage_idle) FROM cpu GROUP BY time(10s)"), @"This feature is not implemented: holt_winters combined with other functions");
+        }
+
         #[test]
         fn test_top() {
             assert_snapshot!(plan("SELECT top(usage_idle,10) FROM cpu"), @r###"
@@ -4624,6 +5707,20 @@ mod test {
             assert_snapshot!(plan("SELECT foo, f64_field FROM data where time != 0"), @"Error during planning: invalid time comparison operator: !=")
         }
 
+        /// `now()` outside of a time range is the start time of the query.
+        #[test]
+        fn test_now_in_where() {
+            let got = plan("SELECT foo, f64_field FROM data WHERE (time + 1s) > now()");
+            assert!(
+                got.contains("TimestampNanosecond(1672531200000000000, None)"),
+                "{got}"
+            );
+
+            // fallible
+
+            assert_snapshot!(plan("SELECT foo, f64_field FROM data WHERE (time + 1s) > now(1)"), @"Error during planning: invalid number of arguments for now: expected 0, got 1");
+        }
+
         #[test]
         fn test_regex_in_where() {
             test_helpers::maybe_start_logging();
@@ -5163,6 +6260,65 @@ mod test {
                 "###);
             }
 
+            #[test]
+            fn group_by_time_gapfill_fills_missing_columns() {
+                // Fields that do not exist in one of the measurements are projected as
+                // the FILL value, rather than NULL.
+                let got = plan("SELECT MEAN(usage_idle), MEAN(bytes_free) FROM cpu, disk GROUP BY TIME(10s) FILL(3)");
+                assert!(got.contains("Float64(3) AS mean,"), "{got}");
+                assert!(got.contains("Float64(3) AS mean_1"), "{got}");
+
+                // FILL(null) continues to project NULL
+                let got = plan("SELECT MEAN(usage_idle), MEAN(bytes_free) FROM cpu, disk GROUP BY TIME(10s) FILL(null)");
+                assert!(!got.contains("Float64(3)"), "{got}");
+            }
+
+            #[test]
+            fn group_by_time_gapfill_fills_previous_and_linear_for_each_measurement() {
+                let got = plan("SELECT MEAN(usage_idle), MEAN(bytes_free) FROM cpu, disk GROUP BY TIME(10s) FILL(previous)");
+                assert!(got.contains("aggr=[[LOCF(AVG(cpu.usage_idle))]]"), "{got}");
+                assert!(got.contains("aggr=[[LOCF(AVG(disk.bytes_free))]]"), "{got}");
+
+                let got = plan("SELECT MEAN(usage_idle), MEAN(bytes_free) FROM cpu, disk GROUP BY TIME(10s) FILL(linear)");
+                assert!(
+                    got.contains("aggr=[[INTERPOLATE(AVG(cpu.usage_idle))]]"),
+                    "{got}"
+                );
+                assert!(
+                    got.contains("aggr=[[INTERPOLATE(AVG(disk.bytes_free))]]"),
+                    "{got}"
+                );
+            }
+
+            #[test]
+            fn fill_without_group_by_time() {
+                // FILL(<value>) coalesces the aggregates of each series
+                let got = plan("SELECT MEAN(usage_idle) FROM cpu GROUP BY cpu FILL(3)");
+                assert!(!got.contains("GapFill"), "{got}");
+                assert!(
+                    got.contains("coalesce_struct(AVG(cpu.usage_idle), Float64(3)) AS mean"),
+                    "{got}"
+                );
+
+                // and the fields missing from a measurement
+                let got = plan("SELECT MEAN(usage_idle), MEAN(bytes_free) FROM cpu, disk FILL(3)");
+                assert!(got.contains("Float64(3) AS mean,"), "{got}");
+                assert!(got.contains("Float64(3) AS mean_1"), "{got}");
+
+                // Each series has a single window, so FILL(previous) and FILL(linear)
+                // have no value to fill from and project NULL.
+                for fill in ["previous", "linear"] {
+                    let got = plan(format!("SELECT MEAN(usage_idle), MEAN(bytes_free) FROM cpu, disk GROUP BY cpu FILL({fill})"));
+                    assert!(!got.contains("GapFill"), "{got}");
+                    assert!(!got.contains("coalesce_struct(AVG"), "{got}");
+                    assert!(
+                        !got.contains("LOCF") && !got.contains("INTERPOLATE"),
+                        "{got}"
+                    );
+                    assert!(got.contains("AVG(cpu.usage_idle) AS mean"), "{got}");
+                }
+            }
+
             #[test]
             fn with_limit_or_offset() {
                 assert_snapshot!(plan("SELECT COUNT(f64_field) FROM data GROUP BY foo LIMIT 1"), @r###"
@@ -5310,6 +6466,19 @@ mod test {
             "###);
         }
 
+        /// The output of `SELECT ... INTO` is planned as for `SELECT`, and written by the caller.
+        #[test]
+        fn test_select_into() {
+            assert_eq!(
+                plan("SELECT f64_field INTO data_copy FROM data GROUP BY foo"),
+                plan("SELECT f64_field FROM data GROUP BY foo")
+            );
+            assert_eq!(
+                plan("SELECT f64_field INTO db.rp.:MEASUREMENT FROM data"),
+                plan("SELECT f64_field FROM data")
+            );
+        }
+
         #[test]
         fn test_select_single_measurement_group_by() {
             // Sort should be cpu, time
@@ -431,4 +431,6 @@ impl<'a> Display for Selector<'a> {
 pub(super) enum SelectorWindowOrderBy<'a> {
     FieldAsc(&'a Identifier),
     FieldDesc(&'a Identifier),
+    /// Order the rows randomly, used by the SAMPLE selector.
+    Random,
 }
@@ -23,7 +23,6 @@ use influxdb_influxql_parser::select::{
 use influxdb_influxql_parser::time_range::{
     duration_expr_to_nanoseconds, split_cond, ReduceContext, TimeRange,
 };
-use influxdb_influxql_parser::timestamp::Timestamp;
 use itertools::Itertools;
 use schema::InfluxColumnType;
 use std::collections::{BTreeSet, HashMap, HashSet};
@@ -98,12 +97,18 @@ impl RewriteSelect {
     fn rewrite(&self, s: &dyn SchemaProvider, stmt: &SelectStatement) -> Result<Select> {
         check_features(stmt)?;
 
+        // The output of a `SELECT ... INTO` statement is written by the caller, so only the
+        // outermost statement may specify a target.
+        if self.is_subquery() && stmt.into.is_some() {
+            return error::query("INTO clause is not allowed in a subquery");
+        }
+
         let from = self.expand_from(s, stmt)?;
         let tag_set = from_tag_set(s, &from);
         let (fields, group_by) = self.expand_projection(s, stmt, &from, &tag_set)?;
         let condition = self.condition_resolve_types(s, stmt, &from)?;
 
-        let now = Timestamp::from(s.execution_props().query_execution_start_time);
+        let now = s.now();
         let rc = ReduceContext {
             now: Some(now),
             tz: stmt.timezone.map(|tz| *tz),
@@ -346,26 +351,32 @@ impl RewriteSelect {
         let mut new_from = Vec::new();
         for ms in stmt.from.iter() {
             match ms {
-                MeasurementSelection::Name(qmn) => match qmn {
-                    QualifiedMeasurementName {
-                        name: MeasurementName::Name(name),
-                        ..
-                    } => {
-                        if s.table_exists(name) {
-                            new_from.push(DataSource::Table(name.deref().to_owned()))
-                        }
-                    }
-                    QualifiedMeasurementName {
-                        name: MeasurementName::Regex(re),
-                        ..
-                    } => {
-                        let re = util::parse_regex(re)?;
-                        s.table_names()
+                MeasurementSelection::Name(QualifiedMeasurementName {
+                    database,
+                    retention_policy,
+                    name,
+                }) => {
+                    let tables = util::database_table_names(
+                        s,
+                        database.as_ref().map(|db| db.as_str()),
+                        retention_policy.as_ref().map(|rp| rp.as_str()),
+                    )?;
+                    match name {
+                        MeasurementName::Name(name) => tables
                             .into_iter()
-                            .filter(|table| re.is_match(table))
-                            .for_each(|table| new_from.push(DataSource::Table(table.to_owned())));
+                            .filter(|table| s.measurement_name(table) == name.as_str())
+                            .for_each(|table| new_from.push(DataSource::Table(table.to_owned()))),
+                        MeasurementName::Regex(re) => {
+                            let re = util::parse_regex(re)?;
+                            tables
+                                .into_iter()
+                                .filter(|table| re.is_match(s.measurement_name(table)))
+                                .for_each(|table| {
+                                    new_from.push(DataSource::Table(table.to_owned()))
+                                });
+                        }
                     }
-                },
+                }
                 MeasurementSelection::Subquery(q) => {
                     new_from.push(DataSource::Subquery(Box::new(self.rewrite_subquery(s, q)?)))
                 }
@@ -939,6 +950,10 @@ struct FieldChecker {
     /// `true` if the projection contains an invocation of the `TOP` or `BOTTOM` function.
     has_top_bottom: bool,
 
+    /// `true` if the projection contains an invocation of the `HOLT_WINTERS` or
+    /// `HOLT_WINTERS_WITH_FIT` function.
+    has_holt_winters: bool,
+
     /// `true` when one or more projections do not contain an aggregate expression.
     has_non_aggregate_fields: bool,
 
@@ -996,6 +1011,10 @@ impl FieldChecker {
                     "selector functions top and bottom cannot be combined with other functions",
                 )
             }
+            // The holt_winters function and its nested aggregate
+            3.. if self.has_holt_winters => {
+                return error::not_implemented("holt_winters combined with other functions")
+            }
             _ => {}
         }
 
@@ -1027,6 +1046,8 @@ impl FieldChecker {
 
         let projection_type = if self.has_top_bottom {
             ProjectionType::TopBottomSelector
+        } else if self.has_holt_winters {
+            ProjectionType::HoltWinters
         } else if self.has_group_by_time {
             if self.window_count > 0 {
                 if self.window_count == self.aggregate_count + self.selector_count {
@@ -1457,6 +1478,12 @@ impl FieldChecker {
         self.inc_aggregate_count();
         check_exp_args!(name, 1, 2, args);
 
+        // InfluxQL interpolates the value at the boundaries of each interval from the points
+        // of the neighbouring intervals, which an aggregate over a single group cannot do.
+        if self.has_group_by_time {
+            return error::not_implemented(format!("{name} with GROUP BY time"));
+        }
+
         match args.get(1) {
             Some(Expr::Literal(Literal::Duration(d))) if **d <= 0 => {
                 return error::query(format!("duration argument must be positive, got {d}"))
@@ -1485,6 +1512,7 @@ impl FieldChecker {
 
     fn check_holt_winters(&mut self, name: &str, args: &[Expr]) -> Result<()> {
         self.inc_aggregate_count();
+        self.has_holt_winters = true;
         check_exp_args!(name, 3, args);
 
         let v = lit_integer!(name, args, 1);
@@ -1579,6 +1607,9 @@ pub(crate) enum ProjectionType {
     },
     /// A query that projects the `top` or `bottom` selector function.
     TopBottomSelector,
+    /// A query that projects the `holt_winters` or `holt_winters_with_fit`
+    /// function of a nested aggregate.
+    HoltWinters,
 }
 
 /// Holds high-level information as the result of analysing
@@ -1760,6 +1791,12 @@ mod test {
 
         let info = select_statement_info(&parse_select("SELECT top(foo, 3) FROM cpu")).unwrap();
         assert_matches!(info.projection_type, ProjectionType::TopBottomSelector);
+
+        let info = select_statement_info(&parse_select(
+            "SELECT holt_winters(mean(foo), 2, 3) FROM cpu GROUP BY TIME(10s)",
+        ))
+        .unwrap();
+        assert_matches!(info.projection_type, ProjectionType::HoltWinters);
     }
 
     /// Verify all the aggregate, window-like and selector functions are handled
@@ -1949,6 +1986,8 @@ mod test {
         assert_error!(select_statement_info(&sel), DataFusionError::Plan(ref s) if s == "duration argument must be positive, got -2s");
         let sel = parse_select("SELECT integral(foo, 2) FROM cpu");
         assert_error!(select_statement_info(&sel), DataFusionError::Plan(ref s) if s == "second argument to integral must be a duration, got Literal(Integer(2))");
+        let sel = parse_select("SELECT integral(foo) FROM cpu GROUP BY time(10s)");
+        assert_error!(select_statement_info(&sel), DataFusionError::NotImplemented(ref s) if s == "integral with GROUP BY time");
 
         // count_hll
         let sel = parse_select("SELECT count_hll(foo) FROM cpu");
@@ -1975,6 +2014,13 @@ mod test {
         assert_error!(select_statement_info(&sel), DataFusionError::Plan(ref s) if s == "holt_winters N argument must be greater than 0, got 0");
         let sel = parse_select("SELECT holt_winters(foo, 1, -3) FROM cpu GROUP BY time(30s)");
         assert_error!(select_statement_info(&sel), DataFusionError::Plan(ref s) if s == "holt_winters S argument cannot be negative, got -3");
+        let sel = parse_select(
+            "SELECT holt_winters(mean(foo), 2, 3), mean(foo) FROM cpu GROUP BY time(30s)",
+        );
+        assert_error!(
+            select_statement_info(&sel),
+            DataFusionError::NotImplemented(_)
+        );
 
         // max, min, first, last
         for name in [
@@ -2456,6 +2502,14 @@ mod test {
                 err.to_string(),
                 "This feature is not implemented: SLIMIT or SOFFSET"
             );
+
+            let stmt =
+                parse_select("SELECT usage_idle FROM (SELECT usage_idle INTO cpu_copy FROM cpu)");
+            let err = rewrite_select_statement(&namespace, &stmt).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "Error during planning: INTO clause is not allowed in a subquery"
+            );
         }
 
         /// Verify subqueries
@@ -12,6 +12,7 @@ use datafusion::physical_expr::execution_props::ExecutionProps;
 use influxdb_influxql_parser::parse_statements;
 use influxdb_influxql_parser::select::SelectStatement;
 use influxdb_influxql_parser::statement::Statement;
+use influxdb_influxql_parser::timestamp::Timestamp;
 use itertools::Itertools;
 use schema::{Schema, SchemaBuilder};
 use std::collections::HashMap;
@@ -132,9 +133,16 @@ pub(crate) mod database {
     }
 }
 
+/// The namespace queried by statements planned with [`MockSchemaProvider`].
+pub(crate) const MOCK_NAMESPACE: &str = "my_db";
+
 pub(crate) struct MockSchemaProvider {
     execution_props: ExecutionProps,
+    now: Timestamp,
     tables: HashMap<String, (Arc<dyn TableSource>, Schema)>,
+    /// The namespace and measurement name of the tables of namespaces other
+    /// than [`MOCK_NAMESPACE`].
+    qualified_tables: HashMap<String, (String, String)>,
 }
 
 impl Default for MockSchemaProvider {
@@ -149,7 +157,9 @@ impl Default for MockSchemaProvider {
         execution_props.query_execution_start_time = start_time;
         let mut res = Self {
             execution_props,
+            now: start_time.into(),
             tables: HashMap::new(),
+            qualified_tables: HashMap::new(),
         };
         res.add_schemas(database::schemas());
         res
@@ -169,6 +179,20 @@ impl MockSchemaProvider {
     pub(crate) fn add_schemas(&mut self, schemas: impl IntoIterator<Item = Schema>) {
         schemas.into_iter().for_each(|s| self.add_schema(s));
     }
+
+    /// Add a table with `schema` to the namespace `namespace`, other than
+    /// [`MOCK_NAMESPACE`].
+    pub(crate) fn add_namespace_schema(&mut self, namespace: &str, schema: Schema) {
+        let schema = schema.sort_fields_by_name();
+
+        let measurement = schema.measurement().unwrap().clone();
+        let table_name = format!("{namespace}::{measurement}");
+        let s = Arc::new(EmptyTable::new(schema.as_arrow()));
+        self.tables
+            .insert(table_name.clone(), (provider_as_source(s), schema));
+        self.qualified_tables
+            .insert(table_name, (namespace.to_owned(), measurement));
+    }
 }
 
 impl SchemaProvider for MockSchemaProvider {
@@ -190,6 +214,7 @@ impl SchemaProvider for MockSchemaProvider {
     fn table_names(&self) -> Vec<&'_ str> {
         self.tables
             .keys()
+            .filter(|k| !self.qualified_tables.contains_key(*k))
             .map(|k| k.as_str())
             .sorted()
             .collect::<Vec<_>>()
@@ -202,4 +227,50 @@ impl SchemaProvider for MockSchemaProvider {
     fn execution_props(&self) -> &ExecutionProps {
         &self.execution_props
     }
+
+    fn now(&self) -> Timestamp {
+        self.now
+    }
+
+    fn namespace_name(&self) -> Option<&'_ str> {
+        Some(MOCK_NAMESPACE)
+    }
+
+    fn namespace_names(&self) -> Vec<&'_ str> {
+        self.qualified_tables
+            .values()
+            .map(|(ns, _)| ns.as_str())
+            .chain([MOCK_NAMESPACE])
+            .sorted()
+            .dedup()
+            .collect()
+    }
+
+    fn namespace_table_names(&self, name: &str) -> Option<Vec<&'_ str>> {
+        if name == MOCK_NAMESPACE {
+            return Some(self.table_names());
+        }
+        let tables = self
+            .qualified_tables
+            .iter()
+            .filter(|(_, (ns, _))| ns == name)
+            .map(|(k, _)| k.as_str())
+            .sorted()
+            .collect::<Vec<_>>();
+        (!tables.is_empty()).then_some(tables)
+    }
+
+    fn table_namespace(&self, name: &str) -> Option<&'_ str> {
+        match self.qualified_tables.get(name) {
+            Some((ns, _)) => Some(ns),
+            None => Some(MOCK_NAMESPACE),
+        }
+    }
+
+    fn measurement_name<'b>(&'b self, name: &'b str) -> &'b str {
+        match self.qualified_tables.get(name) {
+            Some((_, measurement)) => measurement,
+            None => name,
+        }
+    }
 }
@@ -22,6 +22,15 @@ pub(super) enum WindowFunction {
     Derivative,
     NonNegativeDerivative,
     CumulativeSum,
+    Elapsed,
+    ExponentialMovingAverage,
+    DoubleExponentialMovingAverage,
+    TripleExponentialMovingAverage,
+    TripleExponentialDerivative,
+    RelativeStrengthIndex,
+    KaufmansEfficiencyRatio,
+    KaufmansAdaptiveMovingAverage,
+    ChandeMomentumOscillator,
 }
 
 impl WindowFunction {
@@ -34,6 +43,19 @@ impl WindowFunction {
             DERIVATIVE_UDF_NAME => Some(Self::Derivative),
             NON_NEGATIVE_DERIVATIVE_UDF_NAME => Some(Self::NonNegativeDerivative),
             CUMULATIVE_SUM_UDF_NAME => Some(Self::CumulativeSum),
+            ELAPSED_UDF_NAME => Some(Self::Elapsed),
+            EXPONENTIAL_MOVING_AVERAGE_UDF_NAME => Some(Self::ExponentialMovingAverage),
+            DOUBLE_EXPONENTIAL_MOVING_AVERAGE_UDF_NAME => {
+                Some(Self::DoubleExponentialMovingAverage)
+            }
+            TRIPLE_EXPONENTIAL_MOVING_AVERAGE_UDF_NAME => {
+                Some(Self::TripleExponentialMovingAverage)
+            }
+            TRIPLE_EXPONENTIAL_DERIVATIVE_UDF_NAME => Some(Self::TripleExponentialDerivative),
+            RELATIVE_STRENGTH_INDEX_UDF_NAME => Some(Self::RelativeStrengthIndex),
+            KAUFMANS_EFFICIENCY_RATIO_UDF_NAME => Some(Self::KaufmansEfficiencyRatio),
+            KAUFMANS_ADAPTIVE_MOVING_AVERAGE_UDF_NAME => Some(Self::KaufmansAdaptiveMovingAverage),
+            CHANDE_MOMENTUM_OSCILLATOR_UDF_NAME => Some(Self::ChandeMomentumOscillator),
             _ => None,
         }
     }
@@ -209,6 +231,273 @@ static CUMULATIVE_SUM: Lazy<Arc<ScalarUDF>> = Lazy::new(|| {
     ))
 });
 
+const ELAPSED_UDF_NAME: &str = "elapsed";
+
+/// Create an expression to represent the `ELAPSED` function.
+pub(crate) fn elapsed(args: Vec<Expr>) -> Expr {
+    ELAPSED.call(args)
+}
+
+/// Definition of the `ELAPSED` function.
+static ELAPSED: Lazy<Arc<ScalarUDF>> = Lazy::new(|| {
+    let return_type_fn: ReturnTypeFunction = Arc::new(|_| Ok(Arc::new(DataType::Int64)));
+    Arc::new(ScalarUDF::new(
+        ELAPSED_UDF_NAME,
+        &Signature::one_of(
+            NUMERICS
+                .iter()
+                .chain(&[DataType::Utf8, DataType::Boolean])
+                .flat_map(|dt| {
+                    vec![
+                        TypeSignature::Exact(vec![dt.clone()]),
+                        TypeSignature::Exact(vec![
+                            dt.clone(),
+                            DataType::Duration(TimeUnit::Nanosecond),
+                        ]),
+                    ]
+                })
+                .collect(),
+            Volatility::Immutable,
+        ),
+        &return_type_fn,
+        &stand_in_impl(ELAPSED_UDF_NAME),
+    ))
+});
+
+const EXPONENTIAL_MOVING_AVERAGE_UDF_NAME: &str = "exponential_moving_average";
+
+/// Create an expression to represent the `EXPONENTIAL_MOVING_AVERAGE` function.
+pub(crate) fn exponential_moving_average(args: Vec<Expr>) -> Expr {
+    EXPONENTIAL_MOVING_AVERAGE.call(args)
+}
+
+/// Definition of the `EXPONENTIAL_MOVING_AVERAGE` function.
+static EXPONENTIAL_MOVING_AVERAGE: Lazy<Arc<ScalarUDF>> = Lazy::new(|| {
+    let return_type_fn: ReturnTypeFunction = Arc::new(|_| Ok(Arc::new(DataType::Float64)));
+    Arc::new(ScalarUDF::new(
+        EXPONENTIAL_MOVING_AVERAGE_UDF_NAME,
+        &Signature::one_of(
+            NUMERICS
+                .iter()
+                .map(|dt| {
+                    TypeSignature::Exact(vec![
+                        dt.clone(),
+                        DataType::Int64,
+                        DataType::Int64,
+                        DataType::Utf8,
+                    ])
+                })
+                .collect(),
+            Volatility::Immutable,
+        ),
+        &return_type_fn,
+        &stand_in_impl(EXPONENTIAL_MOVING_AVERAGE_UDF_NAME),
+    ))
+});
+
+const DOUBLE_EXPONENTIAL_MOVING_AVERAGE_UDF_NAME: &str = "double_exponential_moving_average";
+
+/// Create an expression to represent the `DOUBLE_EXPONENTIAL_MOVING_AVERAGE` function.
+pub(crate) fn double_exponential_moving_average(args: Vec<Expr>) -> Expr {
+    DOUBLE_EXPONENTIAL_MOVING_AVERAGE.call(args)
+}
+
+/// Definition of the `DOUBLE_EXPONENTIAL_MOVING_AVERAGE` function.
+static DOUBLE_EXPONENTIAL_MOVING_AVERAGE: Lazy<Arc<ScalarUDF>> = Lazy::new(|| {
+    let return_type_fn: ReturnTypeFunction = Arc::new(|_| Ok(Arc::new(DataType::Float64)));
+    Arc::new(ScalarUDF::new(
+        DOUBLE_EXPONENTIAL_MOVING_AVERAGE_UDF_NAME,
+        &Signature::one_of(
+            NUMERICS
+                .iter()
+                .map(|dt| {
+                    TypeSignature::Exact(vec![
+                        dt.clone(),
+                        DataType::Int64,
+                        DataType::Int64,
+                        DataType::Utf8,
+                    ])
+                })
+                .collect(),
+            Volatility::Immutable,
+        ),
+        &return_type_fn,
+        &stand_in_impl(DOUBLE_EXPONENTIAL_MOVING_AVERAGE_UDF_NAME),
+    ))
+});
+
+const TRIPLE_EXPONENTIAL_MOVING_AVERAGE_UDF_NAME: &str = "triple_exponential_moving_average";
+
+/// Create an expression to represent the `TRIPLE_EXPONENTIAL_MOVING_AVERAGE` function.
+pub(crate) fn triple_exponential_moving_average(args: Vec<Expr>) -> Expr {
+    TRIPLE_EXPONENTIAL_MOVING_AVERAGE.call(args)
+}
+
+/// Definition of the `TRIPLE_EXPONENTIAL_MOVING_AVERAGE` function.
+static TRIPLE_EXPONENTIAL_MOVING_AVERAGE: Lazy<Arc<ScalarUDF>> = Lazy::new(|| {
+    let return_type_fn: ReturnTypeFunction = Arc::new(|_| Ok(Arc::new(DataType::Float64)));
+    Arc::new(ScalarUDF::new(
+        TRIPLE_EXPONENTIAL_MOVING_AVERAGE_UDF_NAME,
+        &Signature::one_of(
+            NUMERICS
+                .iter()
+                .map(|dt| {
+                    TypeSignature::Exact(vec![
+                        dt.clone(),
+                        DataType::Int64,
+                        DataType::Int64,
+                        DataType::Utf8,
+                    ])
+                })
+                .collect(),
+            Volatility::Immutable,
+        ),
+        &return_type_fn,
+        &stand_in_impl(TRIPLE_EXPONENTIAL_MOVING_AVERAGE_UDF_NAME),
+    ))
+});
+
+const TRIPLE_EXPONENTIAL_DERIVATIVE_UDF_NAME: &str = "triple_exponential_derivative";
+
+/// Create an expression to represent the `TRIPLE_EXPONENTIAL_DERIVATIVE` function.
+pub(crate) fn triple_exponential_derivative(args: Vec<Expr>) -> Expr {
+    TRIPLE_EXPONENTIAL_DERIVATIVE.call(args)
+}
+
+/// Definition of the `TRIPLE_EXPONENTIAL_DERIVATIVE` function.
+static TRIPLE_EXPONENTIAL_DERIVATIVE: Lazy<Arc<ScalarUDF>> = Lazy::new(|| {
+    let return_type_fn: ReturnTypeFunction = Arc::new(|_| Ok(Arc::new(DataType::Float64)));
+    Arc::new(ScalarUDF::new(
+        TRIPLE_EXPONENTIAL_DERIVATIVE_UDF_NAME,
+        &Signature::one_of(
+            NUMERICS
+                .iter()
+                .map(|dt| {
+                    TypeSignature::Exact(vec![
+                        dt.clone(),
+                        DataType::Int64,
+                        DataType::Int64,
+                        DataType::Utf8,
+                    ])
+                })
+                .collect(),
+            Volatility::Immutable,
+        ),
+        &return_type_fn,
+        &stand_in_impl(TRIPLE_EXPONENTIAL_DERIVATIVE_UDF_NAME),
+    ))
+});
+
+const RELATIVE_STRENGTH_INDEX_UDF_NAME: &str = "relative_strength_index";
+
+/// Create an expression to represent the `RELATIVE_STRENGTH_INDEX` function.
+pub(crate) fn relative_strength_index(args: Vec<Expr>) -> Expr {
+    RELATIVE_STRENGTH_INDEX.call(args)
+}
+
+/// Definition of the `RELATIVE_STRENGTH_INDEX` function.
+static RELATIVE_STRENGTH_INDEX: Lazy<Arc<ScalarUDF>> = Lazy::new(|| {
+    let return_type_fn: ReturnTypeFunction = Arc::new(|_| Ok(Arc::new(DataType::Float64)));
+    Arc::new(ScalarUDF::new(
+        RELATIVE_STRENGTH_INDEX_UDF_NAME,
+        &Signature::one_of(
+            NUMERICS
+                .iter()
+                .map(|dt| {
+                    TypeSignature::Exact(vec![
+                        dt.clone(),
+                        DataType::Int64,
+                        DataType::Int64,
+                        DataType::Utf8,
+                    ])
+                })
+                .collect(),
+            Volatility::Immutable,
+        ),
+        &return_type_fn,
+        &stand_in_impl(RELATIVE_STRENGTH_INDEX_UDF_NAME),
+    ))
+});
+
+const KAUFMANS_EFFICIENCY_RATIO_UDF_NAME: &str = "kaufmans_efficiency_ratio";
+
+/// Create an expression to represent the `KAUFMANS_EFFICIENCY_RATIO` function.
+pub(crate) fn kaufmans_efficiency_ratio(args: Vec<Expr>) -> Expr {
+    KAUFMANS_EFFICIENCY_RATIO.call(args)
+}
+
+/// Definition of the `KAUFMANS_EFFICIENCY_RATIO` function.
+static KAUFMANS_EFFICIENCY_RATIO: Lazy<Arc<ScalarUDF>> = Lazy::new(|| {
+    let return_type_fn: ReturnTypeFunction = Arc::new(|_| Ok(Arc::new(DataType::Float64)));
+    Arc::new(ScalarUDF::new(
+        KAUFMANS_EFFICIENCY_RATIO_UDF_NAME,
+        &Signature::one_of(
+            NUMERICS
+                .iter()
+                .map(|dt| TypeSignature::Exact(vec![dt.clone(), DataType::Int64, DataType::Int64]))
+                .collect(),
+            Volatility::Immutable,
+        ),
+        &return_type_fn,
+        &stand_in_impl(KAUFMANS_EFFICIENCY_RATIO_UDF_NAME),
+    ))
+});
+
+const KAUFMANS_ADAPTIVE_MOVING_AVERAGE_UDF_NAME: &str = "kaufmans_adaptive_moving_average";
+
+/// Create an expression to represent the `KAUFMANS_ADAPTIVE_MOVING_AVERAGE` function.
+pub(crate) fn kaufmans_adaptive_moving_average(args: Vec<Expr>) -> Expr {
+    KAUFMANS_ADAPTIVE_MOVING_AVERAGE.call(args)
+}
+
+/// Definition of the `KAUFMANS_ADAPTIVE_MOVING_AVERAGE` function.
+static KAUFMANS_ADAPTIVE_MOVING_AVERAGE: Lazy<Arc<ScalarUDF>> = Lazy::new(|| {
+    let return_type_fn: ReturnTypeFunction = Arc::new(|_| Ok(Arc::new(DataType::Float64)));
+    Arc::new(ScalarUDF::new(
+        KAUFMANS_ADAPTIVE_MOVING_AVERAGE_UDF_NAME,
+        &Signature::one_of(
+            NUMERICS
+                .iter()
+                .map(|dt| TypeSignature::Exact(vec![dt.clone(), DataType::Int64, DataType::Int64]))
+                .collect(),
+            Volatility::Immutable,
+        ),
+        &return_type_fn,
+        &stand_in_impl(KAUFMANS_ADAPTIVE_MOVING_AVERAGE_UDF_NAME),
+    ))
+});
+
+const CHANDE_MOMENTUM_OSCILLATOR_UDF_NAME: &str = "chande_momentum_oscillator";
+
+/// Create an expression to represent the `CHANDE_MOMENTUM_OSCILLATOR` function.
+pub(crate) fn chande_momentum_oscillator(args: Vec<Expr>) -> Expr {
+    CHANDE_MOMENTUM_OSCILLATOR.call(args)
+}
+
+/// Definition of the `CHANDE_MOMENTUM_OSCILLATOR` function.
+static CHANDE_MOMENTUM_OSCILLATOR: Lazy<Arc<ScalarUDF>> = Lazy::new(|| {
+    let return_type_fn: ReturnTypeFunction = Arc::new(|_| Ok(Arc::new(DataType::Float64)));
+    Arc::new(ScalarUDF::new(
+        CHANDE_MOMENTUM_OSCILLATOR_UDF_NAME,
+        &Signature::one_of(
+            NUMERICS
+                .iter()
+                .map(|dt| {
+                    TypeSignature::Exact(vec![
+                        dt.clone(),
+                        DataType::Int64,
+                        DataType::Int64,
+                        DataType::Utf8,
+                    ])
+                })
+                .collect(),
+            Volatility::Immutable,
+        ),
+        &return_type_fn,
+        &stand_in_impl(CHANDE_MOMENTUM_OSCILLATOR_UDF_NAME),
+    ))
+});
+
 /// Returns an implementation that always returns an error.
 fn stand_in_impl(name: &'static str) -> ScalarFunctionImplementation {
     Arc::new(move |_| error::internal(format!("{name} should not exist in the final logical plan")))
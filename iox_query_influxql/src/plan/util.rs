@@ -1,7 +1,8 @@
-use crate::error;
+use crate::{database, error};
 use arrow::datatypes::{DataType, TimeUnit};
 use datafusion::common::tree_node::{Transformed, TreeNode, VisitRecursion};
 use datafusion::common::{DFSchemaRef, Result};
+use datafusion::logical_expr::expr::Alias;
 use datafusion::logical_expr::utils::expr_as_column_expr;
 use datafusion::logical_expr::{lit, Expr, ExprSchemable, LogicalPlan, Operator};
 use datafusion::scalar::ScalarValue;
@@ -14,6 +15,7 @@ use schema::InfluxColumnType;
 use std::sync::Arc;
 
 use super::ir::{DataSourceSchema, Field};
+use super::SchemaProvider;
 
 pub(in crate::plan) fn binary_operator_to_df_operator(op: BinaryOperator) -> Operator {
     match op {
@@ -93,6 +95,33 @@ pub(crate) fn parse_regex(re: &Regex) -> Result<regex::Regex> {
         .map_err(|e| error::map::query(format!("invalid regular expression '{re}': {e}")))
 }
 
+/// Returns the tables of the namespace storing the database `db` and retention policy `rp`.
+///
+/// The database defaults to that of the namespace of the query, and the retention policy to the
+/// default retention policy of the database. Without either, the tables of the namespace of the
+/// query are returned.
+pub(in crate::plan) fn database_table_names<'a>(
+    s: &'a dyn SchemaProvider,
+    db: Option<&str>,
+    rp: Option<&str>,
+) -> Result<Vec<&'a str>> {
+    if db.is_none() && rp.is_none() {
+        return Ok(s.table_names());
+    }
+
+    let Some(db) = db.or_else(|| s.namespace_name().map(database::database_name)) else {
+        return error::query("database name required");
+    };
+    let namespace = database::namespace_name(db, rp);
+    match (s.namespace_table_names(&namespace), rp) {
+        (Some(tables), _) => Ok(tables),
+        (None, Some(rp)) if namespace != db => {
+            error::query(format!("retention policy not found: {rp}"))
+        }
+        (None, _) => error::query(format!("database not found: {db}")),
+    }
+}
+
 /// Returns `n` as a scalar value of the specified `data_type`.
 fn number_to_scalar(n: &Number, data_type: &DataType) -> Result<ScalarValue> {
     Ok(match (n, data_type) {
@@ -172,6 +201,39 @@ pub(crate) fn rebase_expr(
     }
 }
 
+/// Returns the `FILL(<value>)` literal to project in place of `expr`, if
+/// `expr` is a `NULL` literal.
+///
+/// A field that refers to a column that does not exist in the current table
+/// is projected as `NULL`, so must be replaced with `value` to be consistent
+/// with the tables of a multi-measurement query where the column exists.
+pub(crate) fn fill_null_literal(
+    expr: &Expr,
+    value: &Number,
+    data_type: Option<InfluxColumnType>,
+) -> Result<Option<Expr>> {
+    let (inner, alias) = match expr {
+        Expr::Alias(Alias { expr, name }) => (expr.as_ref(), Some(name)),
+        expr => (expr, None),
+    };
+
+    if !matches!(inner, Expr::Literal(ScalarValue::Null)) {
+        return Ok(None);
+    }
+
+    let data_type = match (data_type, value) {
+        (Some(dt @ InfluxColumnType::Field(_)), _) => (&dt).into(),
+        (_, Number::Integer(_)) => DataType::Int64,
+        (_, Number::Float(_)) => DataType::Float64,
+    };
+    let expr = lit(number_to_scalar(value, &data_type)?);
+
+    Ok(Some(match alias {
+        Some(name) => expr.alias(name),
+        None => expr,
+    }))
+}
+
 pub(crate) fn contains_expr(expr: &Expr, needle: &Expr) -> bool {
     let mut found = false;
     expr.apply(&mut |expr| {
@@ -6,13 +6,32 @@ use datafusion::logical_expr::{
 use once_cell::sync::Lazy;
 use std::sync::Arc;
 
+mod chande_momentum_oscillator;
 mod cumulative_sum;
 mod derivative;
 mod difference;
+mod elapsed;
+mod exponential_moving_average;
+mod indicator;
+mod kaufmans;
 mod moving_average;
 mod non_negative;
 mod percent_row_number;
 
+/// Definition of the `CHANDE_MOMENTUM_OSCILLATOR` user-defined window function.
+pub(crate) static CHANDE_MOMENTUM_OSCILLATOR: Lazy<WindowFunction> = Lazy::new(|| {
+    let return_type: ReturnTypeFunction = Arc::new(chande_momentum_oscillator::return_type);
+    let partition_evaluator_factory: PartitionEvaluatorFactory =
+        Arc::new(chande_momentum_oscillator::partition_evaluator_factory);
+
+    WindowFunction::WindowUDF(Arc::new(WindowUDF::new(
+        chande_momentum_oscillator::NAME,
+        &chande_momentum_oscillator::SIGNATURE,
+        &return_type,
+        &partition_evaluator_factory,
+    )))
+});
+
 /// Definition of the `CUMULATIVE_SUM` user-defined window function.
 pub(crate) static CUMULATIVE_SUM: Lazy<WindowFunction> = Lazy::new(|| {
     let return_type: ReturnTypeFunction = Arc::new(cumulative_sum::return_type);
@@ -55,6 +74,80 @@ pub(crate) static DIFFERENCE: Lazy<WindowFunction> = Lazy::new(|| {
     )))
 });
 
+/// Definition of the `DOUBLE_EXPONENTIAL_MOVING_AVERAGE` user-defined window function.
+pub(crate) static DOUBLE_EXPONENTIAL_MOVING_AVERAGE: Lazy<WindowFunction> = Lazy::new(|| {
+    let kind = exponential_moving_average::Kind::Double;
+    let return_type: ReturnTypeFunction = Arc::new(exponential_moving_average::return_type);
+    let partition_evaluator_factory: PartitionEvaluatorFactory =
+        Arc::new(move || exponential_moving_average::partition_evaluator_factory(kind));
+
+    WindowFunction::WindowUDF(Arc::new(WindowUDF::new(
+        kind.name(),
+        &exponential_moving_average::SIGNATURE,
+        &return_type,
+        &partition_evaluator_factory,
+    )))
+});
+
+/// Definition of the `ELAPSED` user-defined window function.
+pub(crate) static ELAPSED: Lazy<WindowFunction> = Lazy::new(|| {
+    let return_type: ReturnTypeFunction = Arc::new(elapsed::return_type);
+    let partition_evaluator_factory: PartitionEvaluatorFactory =
+        Arc::new(elapsed::partition_evaluator_factory);
+
+    WindowFunction::WindowUDF(Arc::new(WindowUDF::new(
+        elapsed::NAME,
+        &elapsed::SIGNATURE,
+        &return_type,
+        &partition_evaluator_factory,
+    )))
+});
+
+/// Definition of the `EXPONENTIAL_MOVING_AVERAGE` user-defined window function.
+pub(crate) static EXPONENTIAL_MOVING_AVERAGE: Lazy<WindowFunction> = Lazy::new(|| {
+    let kind = exponential_moving_average::Kind::Single;
+    let return_type: ReturnTypeFunction = Arc::new(exponential_moving_average::return_type);
+    let partition_evaluator_factory: PartitionEvaluatorFactory =
+        Arc::new(move || exponential_moving_average::partition_evaluator_factory(kind));
+
+    WindowFunction::WindowUDF(Arc::new(WindowUDF::new(
+        kind.name(),
+        &exponential_moving_average::SIGNATURE,
+        &return_type,
+        &partition_evaluator_factory,
+    )))
+});
+
+/// Definition of the `KAUFMANS_ADAPTIVE_MOVING_AVERAGE` user-defined window function.
+pub(crate) static KAUFMANS_ADAPTIVE_MOVING_AVERAGE: Lazy<WindowFunction> = Lazy::new(|| {
+    let kind = kaufmans::Kind::AdaptiveMovingAverage;
+    let return_type: ReturnTypeFunction = Arc::new(kaufmans::return_type);
+    let partition_evaluator_factory: PartitionEvaluatorFactory =
+        Arc::new(move || kaufmans::partition_evaluator_factory(kind));
+
+    WindowFunction::WindowUDF(Arc::new(WindowUDF::new(
+        kind.name(),
+        &kaufmans::SIGNATURE,
+        &return_type,
+        &partition_evaluator_factory,
+    )))
+});
+
+/// Definition of the `KAUFMANS_EFFICIENCY_RATIO` user-defined window function.
+pub(crate) static KAUFMANS_EFFICIENCY_RATIO: Lazy<WindowFunction> = Lazy::new(|| {
+    let kind = kaufmans::Kind::EfficiencyRatio;
+    let return_type: ReturnTypeFunction = Arc::new(kaufmans::return_type);
+    let partition_evaluator_factory: PartitionEvaluatorFactory =
+        Arc::new(move || kaufmans::partition_evaluator_factory(kind));
+
+    WindowFunction::WindowUDF(Arc::new(WindowUDF::new(
+        kind.name(),
+        &kaufmans::SIGNATURE,
+        &return_type,
+        &partition_evaluator_factory,
+    )))
+});
+
 /// Definition of the `MOVING_AVERAGE` user-defined window function.
 pub(crate) static MOVING_AVERAGE: Lazy<WindowFunction> = Lazy::new(|| {
     let return_type: ReturnTypeFunction = Arc::new(moving_average::return_type);
@@ -120,3 +213,48 @@ pub(crate) static PERCENT_ROW_NUMBER: Lazy<WindowFunction> = Lazy::new(|| {
         &partition_evaluator_factory,
     )))
 });
+
+/// Definition of the `RELATIVE_STRENGTH_INDEX` user-defined window function.
+pub(crate) static RELATIVE_STRENGTH_INDEX: Lazy<WindowFunction> = Lazy::new(|| {
+    let kind = exponential_moving_average::Kind::RelativeStrengthIndex;
+    let return_type: ReturnTypeFunction = Arc::new(exponential_moving_average::return_type);
+    let partition_evaluator_factory: PartitionEvaluatorFactory =
+        Arc::new(move || exponential_moving_average::partition_evaluator_factory(kind));
+
+    WindowFunction::WindowUDF(Arc::new(WindowUDF::new(
+        kind.name(),
+        &exponential_moving_average::SIGNATURE,
+        &return_type,
+        &partition_evaluator_factory,
+    )))
+});
+
+/// Definition of the `TRIPLE_EXPONENTIAL_DERIVATIVE` user-defined window function.
+pub(crate) static TRIPLE_EXPONENTIAL_DERIVATIVE: Lazy<WindowFunction> = Lazy::new(|| {
+    let kind = exponential_moving_average::Kind::TripleDerivative;
+    let return_type: ReturnTypeFunction = Arc::new(exponential_moving_average::return_type);
+    let partition_evaluator_factory: PartitionEvaluatorFactory =
+        Arc::new(move || exponential_moving_average::partition_evaluator_factory(kind));
+
+    WindowFunction::WindowUDF(Arc::new(WindowUDF::new(
+        kind.name(),
+        &exponential_moving_average::SIGNATURE,
+        &return_type,
+        &partition_evaluator_factory,
+    )))
+});
+
+/// Definition of the `TRIPLE_EXPONENTIAL_MOVING_AVERAGE` user-defined window function.
+pub(crate) static TRIPLE_EXPONENTIAL_MOVING_AVERAGE: Lazy<WindowFunction> = Lazy::new(|| {
+    let kind = exponential_moving_average::Kind::Triple;
+    let return_type: ReturnTypeFunction = Arc::new(exponential_moving_average::return_type);
+    let partition_evaluator_factory: PartitionEvaluatorFactory =
+        Arc::new(move || exponential_moving_average::partition_evaluator_factory(kind));
+
+    WindowFunction::WindowUDF(Arc::new(WindowUDF::new(
+        kind.name(),
+        &exponential_moving_average::SIGNATURE,
+        &return_type,
+        &partition_evaluator_factory,
+    )))
+});
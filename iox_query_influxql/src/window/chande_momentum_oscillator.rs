@@ -0,0 +1,143 @@
+use crate::window::indicator::{
+    evaluate, integer_arg, warmup_arg, GainsAndLosses, Indicator, Warmup, Window,
+};
+use crate::NUMERICS;
+use arrow::array::{Array, ArrayRef};
+use arrow::datatypes::DataType;
+use datafusion::common::Result;
+use datafusion::logical_expr::{PartitionEvaluator, Signature, TypeSignature, Volatility};
+use once_cell::sync::Lazy;
+use std::sync::Arc;
+
+/// The name of the chande_momentum_oscillator window function.
+pub(super) const NAME: &str = "chande_momentum_oscillator";
+
+/// Valid signatures for the chande_momentum_oscillator window function.
+///
+/// The arguments are the input values, the period, the hold period and the
+/// warmup type.
+pub(super) static SIGNATURE: Lazy<Signature> = Lazy::new(|| {
+    Signature::one_of(
+        NUMERICS
+            .iter()
+            .map(|dt| {
+                TypeSignature::Exact(vec![
+                    dt.clone(),
+                    DataType::Int64,
+                    DataType::Int64,
+                    DataType::Utf8,
+                ])
+            })
+            .collect(),
+        Volatility::Immutable,
+    )
+});
+
+/// Calculate the return type given the function signature.
+pub(super) fn return_type(_: &[DataType]) -> Result<Arc<DataType>> {
+    Ok(Arc::new(DataType::Float64))
+}
+
+/// Create a new partition_evaluator_factory.
+pub(super) fn partition_evaluator_factory() -> Result<Box<dyn PartitionEvaluator>> {
+    Ok(Box::new(CmoPartitionEvaluator {}))
+}
+
+/// PartitionEvaluator which returns the Chande momentum oscillator of the
+/// input data.
+#[derive(Debug)]
+struct CmoPartitionEvaluator {}
+
+impl PartitionEvaluator for CmoPartitionEvaluator {
+    fn evaluate_all(&mut self, values: &[ArrayRef], _num_rows: usize) -> Result<Arc<dyn Array>> {
+        assert_eq!(values.len(), 4);
+
+        let period = integer_arg(&values[1])? as usize;
+        let hold_period = integer_arg(&values[2])?;
+        let warmup = warmup_arg(&values[3])?;
+
+        // Without a warmup type the oscillator is computed from the raw
+        // changes over the period, otherwise the changes are averaged.
+        let array = &values[0];
+        match warmup {
+            Warmup::None => evaluate(array, hold_period, Cmo::new(period)),
+            _ => evaluate(array, hold_period, Cmos::new(period, warmup)),
+        }
+    }
+
+    fn uses_window_frame(&self) -> bool {
+        false
+    }
+
+    fn include_rank(&self) -> bool {
+        false
+    }
+}
+
+/// Compute the oscillator from the total upward and downward movement.
+fn oscillator(up: f64, down: f64) -> f64 {
+    100.0 * (up - down) / (up + down)
+}
+
+/// The Chande momentum oscillator, computed from the changes over the
+/// most recent `period` values.
+#[derive(Debug)]
+struct Cmo {
+    period: usize,
+    window: Window,
+}
+
+impl Cmo {
+    fn new(period: usize) -> Self {
+        Self {
+            period,
+            window: Window::new(period),
+        }
+    }
+}
+
+impl Indicator for Cmo {
+    fn add(&mut self, v: f64) -> f64 {
+        self.window.add(v);
+        let (up, down) = self.window.changes().fold((0.0, 0.0), |(up, down), c| {
+            if c > 0.0 {
+                (up + c, down)
+            } else {
+                (up, down - c)
+            }
+        });
+        oscillator(up, down)
+    }
+
+    fn warm_count(&self) -> usize {
+        self.period
+    }
+}
+
+/// The Chande momentum oscillator, computed from the averaged gains and
+/// losses of the series.
+#[derive(Debug)]
+struct Cmos {
+    period: usize,
+    gains_and_losses: GainsAndLosses,
+}
+
+impl Cmos {
+    fn new(period: usize, warmup: Warmup) -> Self {
+        Self {
+            period,
+            gains_and_losses: GainsAndLosses::new(period, warmup),
+        }
+    }
+}
+
+impl Indicator for Cmos {
+    fn add(&mut self, v: f64) -> f64 {
+        let (gain, loss) = self.gains_and_losses.add(v);
+        oscillator(gain, loss)
+    }
+
+    fn warm_count(&self) -> usize {
+        self.period
+    }
+}
@@ -0,0 +1,255 @@
+use crate::window::indicator::{
+    evaluate, integer_arg, warmup_arg, Ema, GainsAndLosses, Indicator, Warmup,
+};
+use crate::NUMERICS;
+use arrow::array::{Array, ArrayRef};
+use arrow::datatypes::DataType;
+use datafusion::common::Result;
+use datafusion::logical_expr::{PartitionEvaluator, Signature, TypeSignature, Volatility};
+use once_cell::sync::Lazy;
+use std::sync::Arc;
+
+/// Valid signatures for the exponential moving average window functions.
+///
+/// The arguments are the input values, the period, the hold period and the
+/// warmup type.
+pub(super) static SIGNATURE: Lazy<Signature> = Lazy::new(|| {
+    Signature::one_of(
+        NUMERICS
+            .iter()
+            .map(|dt| {
+                TypeSignature::Exact(vec![
+                    dt.clone(),
+                    DataType::Int64,
+                    DataType::Int64,
+                    DataType::Utf8,
+                ])
+            })
+            .collect(),
+        Volatility::Immutable,
+    )
+});
+
+/// Calculate the return type given the function signature.
+pub(super) fn return_type(_: &[DataType]) -> Result<Arc<DataType>> {
+    Ok(Arc::new(DataType::Float64))
+}
+
+/// The indicators derived from exponential moving averages.
+#[derive(Debug, Clone, Copy)]
+pub(super) enum Kind {
+    /// `exponential_moving_average`
+    Single,
+    /// `double_exponential_moving_average`
+    Double,
+    /// `triple_exponential_moving_average`
+    Triple,
+    /// `triple_exponential_derivative`
+    TripleDerivative,
+    /// `relative_strength_index`
+    RelativeStrengthIndex,
+}
+
+impl Kind {
+    /// The name of the window function computing this indicator.
+    pub(super) fn name(&self) -> &'static str {
+        match self {
+            Self::Single => "exponential_moving_average",
+            Self::Double => "double_exponential_moving_average",
+            Self::Triple => "triple_exponential_moving_average",
+            Self::TripleDerivative => "triple_exponential_derivative",
+            Self::RelativeStrengthIndex => "relative_strength_index",
+        }
+    }
+}
+
+/// Create a new partition_evaluator_factory.
+pub(super) fn partition_evaluator_factory(kind: Kind) -> Result<Box<dyn PartitionEvaluator>> {
+    Ok(Box::new(EmaPartitionEvaluator { kind }))
+}
+
+/// PartitionEvaluator which returns an exponential moving average, or an
+/// indicator derived from one, of the input data.
+#[derive(Debug)]
+struct EmaPartitionEvaluator {
+    kind: Kind,
+}
+
+impl PartitionEvaluator for EmaPartitionEvaluator {
+    fn evaluate_all(&mut self, values: &[ArrayRef], _num_rows: usize) -> Result<Arc<dyn Array>> {
+        assert_eq!(values.len(), 4);
+
+        let period = integer_arg(&values[1])? as usize;
+        let hold_period = integer_arg(&values[2])?;
+        let warmup = warmup_arg(&values[3])?;
+
+        let array = &values[0];
+        match self.kind {
+            Kind::Single => evaluate(array, hold_period, Ema::new(period, warmup)),
+            Kind::Double => evaluate(array, hold_period, Dema::new(period, warmup)),
+            Kind::Triple => evaluate(array, hold_period, Tema::new(period, warmup)),
+            Kind::TripleDerivative => evaluate(array, hold_period, Trix::new(period, warmup)),
+            Kind::RelativeStrengthIndex => evaluate(array, hold_period, Rsi::new(period, warmup)),
+        }
+    }
+
+    fn uses_window_frame(&self) -> bool {
+        false
+    }
+
+    fn include_rank(&self) -> bool {
+        false
+    }
+}
+
+/// A double exponential moving average.
+#[derive(Debug)]
+struct Dema {
+    ema1: Ema,
+    ema2: Ema,
+    warmup: Warmup,
+}
+
+impl Dema {
+    fn new(period: usize, warmup: Warmup) -> Self {
+        Self {
+            ema1: Ema::new(period, warmup),
+            ema2: Ema::new(period, warmup),
+            warmup,
+        }
+    }
+}
+
+impl Indicator for Dema {
+    fn add(&mut self, v: f64) -> f64 {
+        let avg1 = self.ema1.add(v);
+        let avg2 = if self.ema1.warmed() || self.warmup == Warmup::Exponential {
+            self.ema2.add(avg1)
+        } else {
+            avg1
+        };
+        2.0 * avg1 - avg2
+    }
+
+    fn warm_count(&self) -> usize {
+        match self.warmup {
+            Warmup::Simple => self.ema1.warm_count() * 2,
+            _ => self.ema1.warm_count(),
+        }
+    }
+}
+
+/// A triple exponential moving average.
+#[derive(Debug)]
+struct Tema {
+    ema1: Ema,
+    ema2: Ema,
+    ema3: Ema,
+    warmup: Warmup,
+}
+
+impl Tema {
+    fn new(period: usize, warmup: Warmup) -> Self {
+        Self {
+            ema1: Ema::new(period, warmup),
+            ema2: Ema::new(period, warmup),
+            ema3: Ema::new(period, warmup),
+            warmup,
+        }
+    }
+}
+
+impl Indicator for Tema {
+    fn add(&mut self, v: f64) -> f64 {
+        let exponential = self.warmup == Warmup::Exponential;
+
+        let avg1 = self.ema1.add(v);
+        let (avg2, avg3) = if self.ema1.warmed() || exponential {
+            let avg2 = self.ema2.add(avg1);
+            if self.ema2.warmed() || exponential {
+                (avg2, self.ema3.add(avg2))
+            } else {
+                (avg2, avg2)
+            }
+        } else {
+            (avg1, avg1)
+        };
+        3.0 * avg1 - 3.0 * avg2 + avg3
+    }
+
+    fn warm_count(&self) -> usize {
+        match self.warmup {
+            Warmup::Simple => self.ema1.warm_count() * 3,
+            _ => self.ema1.warm_count(),
+        }
+    }
+}
+
+/// The percentage rate of change of a triple exponential moving average.
+#[derive(Debug)]
+struct Trix {
+    ema1: Ema,
+    ema2: Ema,
+    ema3: Ema,
+    warmup: Warmup,
+    last: Option<f64>,
+}
+
+impl Trix {
+    fn new(period: usize, warmup: Warmup) -> Self {
+        Self {
+            ema1: Ema::new(period, warmup),
+            ema2: Ema::new(period, warmup),
+            ema3: Ema::new(period, warmup),
+            warmup,
+            last: None,
+        }
+    }
+}
+
+impl Indicator for Trix {
+    fn add(&mut self, v: f64) -> f64 {
+        let exponential = self.warmup == Warmup::Exponential;
+
+        let mut v = self.ema1.add(v);
+        if self.ema1.warmed() || exponential {
+            v = self.ema2.add(v);
+            if self.ema2.warmed() || exponential {
+                v = self.ema3.add(v);
+            }
+        }
+
+        let change = self.last.map_or(f64::NAN, |last| (v - last) / last);
+        self.last = Some(v);
+        change * 100.0
+    }
+
+    fn warm_count(&self) -> usize {
+        match self.warmup {
+            Warmup::Simple => self.ema1.warm_count() * 3 + 1,
+            _ => self.ema1.warm_count() + 1,
+        }
+    }
+}
+
+/// The relative strength index, comparing the averaged gains and losses of
+/// the series.
+#[derive(Debug)]
+struct Rsi(GainsAndLosses);
+
+impl Rsi {
+    fn new(period: usize, warmup: Warmup) -> Self {
+        Self(GainsAndLosses::new(period, warmup))
+    }
+}
+
+impl Indicator for Rsi {
+    fn add(&mut self, v: f64) -> f64 {
+        let (gain, loss) = self.0.add(v);
+        100.0 - (100.0 / (1.0 + gain / loss))
+    }
+
+    fn warm_count(&self) -> usize {
+        self.0.warm_count()
+    }
+}
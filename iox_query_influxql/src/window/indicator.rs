@@ -0,0 +1,278 @@
+//! Common support for the technical analysis window functions, such as
+//! `exponential_moving_average`, which transform a series of values one
+//! point at a time.
+//!
+//! The algorithms follow those used by InfluxDB 1.x, which are provided by
+//! the [gota](https://github.com/phemmer/gota) library.
+
+use crate::error;
+use arrow::array::{ArrayRef, Float64Array, Int64Array, StringArray};
+use arrow::compute::cast;
+use arrow::datatypes::DataType;
+use datafusion::common::{downcast_value, DataFusionError, Result};
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+/// A technical analysis indicator, computed from a series of values.
+pub(super) trait Indicator {
+    /// Add the next value of the series, returning the new value of the
+    /// indicator.
+    fn add(&mut self, v: f64) -> f64;
+
+    /// The number of values that must be added before the indicator
+    /// produces meaningful output.
+    fn warm_count(&self) -> usize;
+}
+
+/// How an indicator derived from an exponential moving average computes its
+/// output before enough values have been seen to fill its period.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Warmup {
+    /// Average the values seen so far exponentially.
+    Exponential,
+    /// Average the values seen so far with a simple moving average.
+    Simple,
+    /// Do not average the values seen so far.
+    None,
+}
+
+/// Read the integer argument passed as the constant column `array`.
+///
+/// INVARIANT:
+/// The planner guarantees that the argument is always an integer literal.
+pub(super) fn integer_arg(array: &ArrayRef) -> Result<i64> {
+    Ok(downcast_value!(array, Int64Array).value(0))
+}
+
+/// Read the warmup type argument passed as the constant column `array`.
+///
+/// INVARIANT:
+/// The planner guarantees that the argument is always a string literal,
+/// validated by the rewriter.
+pub(super) fn warmup_arg(array: &ArrayRef) -> Result<Warmup> {
+    match downcast_value!(array, StringArray).value(0) {
+        "exponential" => Ok(Warmup::Exponential),
+        "simple" => Ok(Warmup::Simple),
+        "none" => Ok(Warmup::None),
+        warmup => error::internal(format!("unexpected warmup type: {warmup}")),
+    }
+}
+
+/// Compute `indicator` for each value of `array`.
+///
+/// The output is `NULL` for `NULL` input values and for the first
+/// `hold_period` values of the series, which defaults to the warm up count
+/// of the indicator when `-1`.
+pub(super) fn evaluate(
+    array: &ArrayRef,
+    hold_period: i64,
+    mut indicator: impl Indicator,
+) -> Result<ArrayRef> {
+    let hold_period = usize::try_from(hold_period).unwrap_or_else(|_| indicator.warm_count());
+
+    let array = cast(array, &DataType::Float64)?;
+    let array = downcast_value!(array, Float64Array);
+
+    let mut count = 0;
+    let output = array
+        .iter()
+        .map(|v| {
+            let v = indicator.add(v?);
+            count += 1;
+            (count > hold_period).then_some(v)
+        })
+        .collect::<Float64Array>();
+    Ok(Arc::new(output))
+}
+
+/// An exponential moving average over `period` values.
+#[derive(Debug, Clone)]
+pub(super) struct Ema {
+    period: usize,
+    alpha: f64,
+    warmup: Warmup,
+    count: usize,
+    last: f64,
+}
+
+impl Ema {
+    pub(super) fn new(period: usize, warmup: Warmup) -> Self {
+        Self::with_alpha(period, 2.0 / (period as f64 + 1.0), warmup)
+    }
+
+    /// Create an exponential moving average with a smoothing factor of
+    /// `alpha`, rather than one derived from the period.
+    pub(super) fn with_alpha(period: usize, alpha: f64, warmup: Warmup) -> Self {
+        Self {
+            period,
+            alpha,
+            warmup,
+            count: 0,
+            last: 0.0,
+        }
+    }
+
+    /// Returns `true` once `period` values have been seen.
+    pub(super) fn warmed(&self) -> bool {
+        self.count == self.period
+    }
+}
+
+impl Indicator for Ema {
+    fn add(&mut self, v: f64) -> f64 {
+        self.last = if self.count == 0 {
+            v
+        } else if !self.warmed() && self.warmup == Warmup::Simple {
+            (self.last * self.count as f64 + v) / (self.count + 1) as f64
+        } else {
+            (v - self.last) * self.alpha + self.last
+        };
+
+        if self.count < self.period {
+            self.count += 1;
+        }
+        self.last
+    }
+
+    fn warm_count(&self) -> usize {
+        self.period.saturating_sub(1)
+    }
+}
+
+/// Tracks the gains and losses between consecutive values of a series,
+/// averaged by a pair of exponential moving averages.
+#[derive(Debug, Clone)]
+pub(super) struct GainsAndLosses {
+    gains: Ema,
+    losses: Ema,
+    last: Option<f64>,
+}
+
+impl GainsAndLosses {
+    pub(super) fn new(period: usize, warmup: Warmup) -> Self {
+        let ema = Ema::with_alpha(period + 1, 1.0 / period as f64, warmup);
+        Self {
+            gains: ema.clone(),
+            losses: ema,
+            last: None,
+        }
+    }
+
+    /// Add the next value of the series, returning the averaged gain and loss.
+    pub(super) fn add(&mut self, v: f64) -> (f64, f64) {
+        let (gain, loss) = match self.last {
+            Some(last) if v > last => (v - last, 0.0),
+            Some(last) => (0.0, last - v),
+            None => (0.0, 0.0),
+        };
+        self.last = Some(v);
+        (self.gains.add(gain), self.losses.add(loss))
+    }
+
+    pub(super) fn warm_count(&self) -> usize {
+        self.gains.warm_count()
+    }
+}
+
+/// Tracks the most recent values of a series, spanning `period` changes.
+#[derive(Debug, Clone)]
+pub(super) struct Window {
+    period: usize,
+    values: VecDeque<f64>,
+}
+
+impl Window {
+    pub(super) fn new(period: usize) -> Self {
+        Self {
+            period,
+            values: VecDeque::with_capacity(period + 1),
+        }
+    }
+
+    /// Add the next value of the series, retaining the most recent
+    /// `period + 1` values, so that `period` changes are tracked.
+    pub(super) fn add(&mut self, v: f64) {
+        self.values.push_back(v);
+        if self.values.len() > self.period + 1 {
+            self.values.pop_front();
+        }
+    }
+
+    /// Returns `true` once `period` changes have been seen.
+    pub(super) fn warmed(&self) -> bool {
+        self.values.len() == self.period + 1
+    }
+
+    /// The changes between consecutive values of the window.
+    pub(super) fn changes(&self) -> impl Iterator<Item = f64> + '_ {
+        self.values
+            .iter()
+            .zip(self.values.iter().skip(1))
+            .map(|(prev, v)| v - prev)
+    }
+
+    /// The change between the oldest and most recent values of the window.
+    pub(super) fn net_change(&self) -> f64 {
+        match (self.values.front(), self.values.back()) {
+            (Some(first), Some(last)) => last - first,
+            _ => 0.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::Array;
+
+    fn values<I: Indicator>(mut indicator: I, input: &[f64]) -> Vec<f64> {
+        input.iter().map(|v| indicator.add(*v)).collect()
+    }
+
+    #[test]
+    fn test_ema() {
+        // alpha = 2 / (3 + 1)
+        let got = values(Ema::new(3, Warmup::Exponential), &[1.0, 3.0, 5.0, 7.0]);
+        assert_eq!(got, [1.0, 2.0, 3.5, 5.25]);
+
+        let got = values(Ema::new(3, Warmup::Simple), &[1.0, 3.0, 5.0, 7.0]);
+        assert_eq!(got, [1.0, 2.0, 3.0, 5.0]);
+    }
+
+    #[test]
+    fn test_evaluate_hold_period() {
+        let array: ArrayRef = Arc::new(Int64Array::from(vec![Some(1), None, Some(3), Some(5)]));
+
+        // The default hold period is the warm up count of the indicator.
+        let got = evaluate(&array, -1, Ema::new(3, Warmup::Exponential)).unwrap();
+        assert_eq!(
+            got.as_any()
+                .downcast_ref::<Float64Array>()
+                .unwrap()
+                .iter()
+                .collect::<Vec<_>>(),
+            [None, None, None, Some(3.5)]
+        );
+
+        let got = evaluate(&array, 0, Ema::new(3, Warmup::Exponential)).unwrap();
+        assert_eq!(
+            got.as_any()
+                .downcast_ref::<Float64Array>()
+                .unwrap()
+                .iter()
+                .collect::<Vec<_>>(),
+            [Some(1.0), None, Some(2.0), Some(3.5)]
+        );
+    }
+
+    #[test]
+    fn test_window() {
+        let mut window = Window::new(2);
+        for v in [1.0, 4.0, 2.0, 5.0] {
+            window.add(v);
+        }
+        assert!(window.warmed());
+        assert_eq!(window.changes().collect::<Vec<_>>(), [-2.0, 3.0]);
+        assert_eq!(window.net_change(), 1.0);
+    }
+}
@@ -0,0 +1,156 @@
+use crate::window::indicator::{evaluate, integer_arg, Indicator, Window};
+use crate::NUMERICS;
+use arrow::array::{Array, ArrayRef};
+use arrow::datatypes::DataType;
+use datafusion::common::Result;
+use datafusion::logical_expr::{PartitionEvaluator, Signature, TypeSignature, Volatility};
+use once_cell::sync::Lazy;
+use std::sync::Arc;
+
+/// Valid signatures for the Kaufman window functions.
+///
+/// The arguments are the input values, the period and the hold period.
+pub(super) static SIGNATURE: Lazy<Signature> = Lazy::new(|| {
+    Signature::one_of(
+        NUMERICS
+            .iter()
+            .map(|dt| TypeSignature::Exact(vec![dt.clone(), DataType::Int64, DataType::Int64]))
+            .collect(),
+        Volatility::Immutable,
+    )
+});
+
+/// Calculate the return type given the function signature.
+pub(super) fn return_type(_: &[DataType]) -> Result<Arc<DataType>> {
+    Ok(Arc::new(DataType::Float64))
+}
+
+/// The indicators developed by Perry Kaufman.
+#[derive(Debug, Clone, Copy)]
+pub(super) enum Kind {
+    /// `kaufmans_efficiency_ratio`
+    EfficiencyRatio,
+    /// `kaufmans_adaptive_moving_average`
+    AdaptiveMovingAverage,
+}
+
+impl Kind {
+    /// The name of the window function computing this indicator.
+    pub(super) fn name(&self) -> &'static str {
+        match self {
+            Self::EfficiencyRatio => "kaufmans_efficiency_ratio",
+            Self::AdaptiveMovingAverage => "kaufmans_adaptive_moving_average",
+        }
+    }
+}
+
+/// Create a new partition_evaluator_factory.
+pub(super) fn partition_evaluator_factory(kind: Kind) -> Result<Box<dyn PartitionEvaluator>> {
+    Ok(Box::new(KaufmansPartitionEvaluator { kind }))
+}
+
+/// PartitionEvaluator which returns a Kaufman indicator of the input data.
+#[derive(Debug)]
+struct KaufmansPartitionEvaluator {
+    kind: Kind,
+}
+
+impl PartitionEvaluator for KaufmansPartitionEvaluator {
+    fn evaluate_all(&mut self, values: &[ArrayRef], _num_rows: usize) -> Result<Arc<dyn Array>> {
+        assert_eq!(values.len(), 3);
+
+        let period = integer_arg(&values[1])? as usize;
+        let hold_period = integer_arg(&values[2])?;
+
+        let array = &values[0];
+        match self.kind {
+            Kind::EfficiencyRatio => evaluate(array, hold_period, Ker::new(period)),
+            Kind::AdaptiveMovingAverage => evaluate(array, hold_period, Kama::new(period)),
+        }
+    }
+
+    fn uses_window_frame(&self) -> bool {
+        false
+    }
+
+    fn include_rank(&self) -> bool {
+        false
+    }
+}
+
+/// Kaufman's efficiency ratio, the net change of the series over the period
+/// relative to the sum of the individual changes.
+#[derive(Debug)]
+struct Ker {
+    period: usize,
+    window: Window,
+}
+
+impl Ker {
+    fn new(period: usize) -> Self {
+        Self {
+            period,
+            window: Window::new(period),
+        }
+    }
+
+    /// The efficiency ratio of the values currently in the window.
+    fn ratio(&self) -> f64 {
+        let direction = self.window.net_change().abs();
+        let volatility = self.window.changes().map(f64::abs).sum::<f64>();
+        if direction == 0.0 || volatility == 0.0 {
+            0.0
+        } else {
+            direction / volatility
+        }
+    }
+}
+
+impl Indicator for Ker {
+    fn add(&mut self, v: f64) -> f64 {
+        self.window.add(v);
+        self.ratio()
+    }
+
+    fn warm_count(&self) -> usize {
+        self.period
+    }
+}
+
+/// Kaufman's adaptive moving average, an exponential moving average whose
+/// smoothing factor is scaled by the efficiency ratio.
+#[derive(Debug)]
+struct Kama {
+    ker: Ker,
+    last: f64,
+}
+
+impl Kama {
+    /// The smoothing factor of the fastest and slowest moving averages.
+    const FAST: f64 = 2.0 / (2.0 + 1.0);
+    const SLOW: f64 = 2.0 / (30.0 + 1.0);
+
+    fn new(period: usize) -> Self {
+        Self {
+            ker: Ker::new(period),
+            last: 0.0,
+        }
+    }
+}
+
+impl Indicator for Kama {
+    fn add(&mut self, v: f64) -> f64 {
+        let er = self.ker.add(v);
+        if !self.ker.window.warmed() {
+            self.last = v;
+        } else {
+            let sc = (er * (Self::FAST - Self::SLOW) + Self::SLOW).powi(2);
+            self.last += sc * (v - self.last);
+        }
+        self.last
+    }
+
+    fn warm_count(&self) -> usize {
+        self.ker.warm_count()
+    }
+}
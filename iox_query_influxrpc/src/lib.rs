@@ -23,7 +23,7 @@ use datafusion::{
     common::DFSchemaRef,
     error::DataFusionError,
     logical_expr::{utils::exprlist_to_columns, ExprSchemable, LogicalPlan, LogicalPlanBuilder},
-    prelude::{when, Column, Expr},
+    prelude::{cast, count, lit, sum, when, Column, Expr},
 };
 use datafusion_util::{
     config::{DEFAULT_CATALOG, DEFAULT_SCHEMA},
@@ -68,6 +68,9 @@ mod scan_plan;
 
 const CONCURRENT_TABLE_JOBS: usize = 10;
 
+/// The name of the output column of series cardinality plans.
+const SERIES_COLUMN_NAME: &str = "series";
+
 #[derive(Debug, Snafu)]
 pub enum Error {
     #[snafu(display(
@@ -836,6 +839,66 @@ impl InfluxRpcPlanner {
         Ok(SeriesSetPlans::new(plans))
     }
 
+    /// Creates a plan that counts the series, the distinct combinations of
+    /// measurement, tag set and field with at least one value, which pass the
+    /// conditions specified by `predicate`.
+    ///
+    /// The output is a single row with a single `Int64` column. Returns
+    /// `None` if no table contains rows that could pass the predicate.
+    ///
+    /// Only the distinct tag sets of each table are materialised, rather
+    /// than every point of every series as `read_filter` does.
+    ///
+    /// The created plan looks like:
+    ///
+    /// ```text
+    ///  Aggregate(sum(series))
+    ///    Union
+    ///      (series_cardinality_plan of each table)
+    /// ```
+    pub async fn series_cardinality(
+        &self,
+        namespace: Arc<dyn QueryNamespace>,
+        rpc_predicate: InfluxRpcPredicate,
+    ) -> Result<Option<LogicalPlan>> {
+        let ctx = self.ctx.child_ctx("series_cardinality planning");
+        debug!(?rpc_predicate, "planning series_cardinality");
+
+        let table_predicates = rpc_predicate
+            .table_predicates(self.meta.as_ref())
+            .context(CreatingPredicatesSnafu)?;
+
+        let plans = create_plans(
+            namespace,
+            &table_predicates,
+            ctx,
+            Arc::clone(&self.meta),
+            |table_name, predicate, chunks, schema| {
+                Self::series_cardinality_plan(table_name, schema, predicate, chunks)
+            },
+        )
+        .await?;
+
+        let mut plans = plans.into_iter().flatten();
+        let Some(first) = plans.next() else {
+            return Ok(None);
+        };
+        let plan = plans
+            .try_fold(LogicalPlanBuilder::from(first), |builder, plan| {
+                builder.union(plan)
+            })
+            .context(BuildingPlanSnafu)?
+            .aggregate(
+                Vec::<Expr>::new(),
+                [sum(SERIES_COLUMN_NAME.as_expr()).alias(SERIES_COLUMN_NAME)],
+            )
+            .context(BuildingPlanSnafu)?
+            .build()
+            .context(BuildingPlanSnafu)?;
+
+        Ok(Some(plan))
+    }
+
     /// Creates a DataFusion LogicalPlan that returns column *names* as a
     /// single column of Strings for a specific table
     ///
@@ -1062,6 +1125,69 @@ impl InfluxRpcPlanner {
         Ok(ss_plan)
     }
 
+    /// Creates a plan counting the series of a table, returning None if the
+    /// predicate excludes all fields of the table.
+    ///
+    /// The created plan looks like:
+    ///
+    /// ```text
+    ///  Projection((count(field1) > 0) + ... + (count(fieldN) > 0) as series)
+    ///    GroupBy(tags; count(field1), ..., count(fieldN))
+    ///      Projection(tags, fields)
+    ///        Filter(predicate)
+    ///          Scan
+    /// ```
+    fn series_cardinality_plan(
+        table_name: &str,
+        schema: &Schema,
+        predicate: &Predicate,
+        chunks: Vec<Arc<dyn QueryChunk>>,
+    ) -> Result<Option<LogicalPlan>> {
+        let scan_and_filter = ScanPlanBuilder::new(Arc::from(table_name), schema)
+            .with_predicate(predicate)
+            .with_chunks(chunks)
+            .build()?;
+
+        let schema = scan_and_filter.provider.iox_schema();
+
+        let (field_names, field_exprs): (Vec<_>, Vec<_>) = filtered_fields_iter(schema, predicate)
+            .map(|field| (field.name, field.expr))
+            .unzip();
+        if field_names.is_empty() {
+            return Ok(None);
+        }
+
+        let tags: Vec<Expr> = schema
+            .tags_iter()
+            .map(|field| field.name().as_expr())
+            .collect();
+
+        // A series only exists for the fields with at least one value.
+        let series = field_names
+            .iter()
+            .map(|name| cast(name.as_expr().gt(lit(0_i64)), DataType::Int64))
+            .reduce(|a, b| a + b)
+            .expect("at least one field");
+
+        let plan = scan_and_filter
+            .plan_builder
+            .project(tags.iter().cloned().chain(field_exprs))
+            .context(BuildingPlanSnafu)?
+            .aggregate(
+                tags,
+                field_names
+                    .iter()
+                    .map(|name| count(name.as_expr()).alias(*name)),
+            )
+            .context(BuildingPlanSnafu)?
+            .project([series.alias(SERIES_COLUMN_NAME)])
+            .context(BuildingPlanSnafu)?
+            .build()
+            .context(BuildingPlanSnafu)?;
+
+        Ok(Some(plan))
+    }
+
     /// Creates a GroupedSeriesSet plan that produces an output table
     /// with one row per tagset and the values aggregated using a
     /// specific function.
@@ -1306,7 +1432,7 @@ fn table_chunk_stream<'a>(
             let namespace = Arc::clone(&namespace);
 
             async move {
-                let predicate = match namespace.retention_time_ns() {
+                let predicate = match namespace.retention_time_ns(table_name) {
                     Some(ret) => predicate.clone().with_retention(ret),
                     None => predicate.clone(),
                 };
@@ -2461,6 +2587,21 @@ mod tests {
         .await
     }
 
+    #[tokio::test]
+    async fn test_predicate_series_cardinality() {
+        run_test(|test_db, rpc_predicate| {
+            async move {
+                InfluxRpcPlanner::new(test_db.new_query_context(None))
+                    .await
+                    .series_cardinality(test_db, rpc_predicate)
+                    .await
+                    .expect("creating plan");
+            }
+            .boxed()
+        })
+        .await
+    }
+
     #[tokio::test]
     async fn test_issue_7848() {
         maybe_start_logging();
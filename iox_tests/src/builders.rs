@@ -126,6 +126,7 @@ impl TableBuilder {
                 namespace_id: NamespaceId::new(0),
                 name: "table".to_string(),
                 partition_template: Default::default(),
+                retention_period_ns: None,
             },
         }
     }
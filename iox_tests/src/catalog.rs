@@ -373,6 +373,7 @@ impl TestTable {
     pub async fn catalog_schema(&self) -> TableSchema {
         TableSchema {
             id: self.table.id,
+            retention_period_ns: self.table.retention_period_ns,
             partition_template: Default::default(),
             columns: self.catalog_columns().await,
         }
@@ -101,6 +101,10 @@ pub struct HttpApiError {
 
     /// Human-readable message.
     msg: String,
+
+    /// The number of seconds after which the request may be retried, sent in
+    /// the `Retry-After` header.
+    retry_after_secs: Option<u64>,
 }
 
 impl HttpApiError {
@@ -109,9 +113,16 @@ impl HttpApiError {
         Self {
             code: code.into(),
             msg: msg.into(),
+            retry_after_secs: None,
         }
     }
 
+    /// Advise the client to retry the request after `secs` seconds.
+    pub fn with_retry_after_secs(mut self, secs: Option<u64>) -> Self {
+        self.retry_after_secs = secs;
+        self
+    }
+
     /// Generate response body for this error.
     fn body(&self) -> Body {
         let json = serde_json::json!({
@@ -125,11 +136,13 @@ impl HttpApiError {
 
     /// Generate response for this error.
     pub fn response(&self) -> Response<Body> {
-        Response::builder()
+        let mut builder = Response::builder()
             .status(self.code.status_code())
-            .header("content-type", "application/json")
-            .body(self.body())
-            .unwrap()
+            .header("content-type", "application/json");
+        if let Some(secs) = self.retry_after_secs {
+            builder = builder.header("retry-after", secs);
+        }
+        builder.body(self.body()).unwrap()
     }
 
     /// Check if the error is an internal server error.
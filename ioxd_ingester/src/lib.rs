@@ -16,16 +16,22 @@ use workspace_hack as _;
 
 use arrow_flight::flight_service_server::FlightServiceServer;
 use async_trait::async_trait;
-use clap_blocks::ingester::IngesterConfig;
+use clap_blocks::ingester::{IngesterConfig, WalCompression, WalFsyncPolicy};
 use futures::FutureExt;
 use generated_types::influxdata::iox::{
     catalog::v1::catalog_service_server::CatalogServiceServer,
     ingester::v1::{
-        persist_service_server::PersistServiceServer, write_service_server::WriteServiceServer,
+        persist_service_server::PersistServiceServer,
+        replication_service_server::ReplicationServiceServer,
+        write_service_server::WriteServiceServer,
     },
 };
 use hyper::{Body, Request, Response};
-use ingester::{GossipConfig, IngesterGuard, IngesterRpcInterface};
+use ingester::{
+    FsyncPolicy, GossipConfig, IngesterGuard, IngesterRpcInterface, MemoryLimitConfig,
+    SegmentCodec, WalArchiveConfig, WalConfig, WalReplayMode, WalReplicaConfig,
+    WalReplicationConfig,
+};
 use iox_catalog::interface::Catalog;
 use iox_query::exec::Executor;
 use ioxd_common::{
@@ -139,6 +145,11 @@ impl<I: IngesterRpcInterface + Sync + Send + Debug + 'static> ServerType for Ing
             builder,
             PersistServiceServer::new(self.server.rpc().persist_service())
         );
+        add_service!(
+            builder,
+            ReplicationServiceServer::new(self.server.rpc().replication_service())
+                .max_decoding_message_size(self.max_incoming_msg_bytes)
+        );
         add_service!(
             builder,
             FlightServiceServer::new(
@@ -218,18 +229,91 @@ pub async fn create_ingester_server_type(
         },
     };
 
+    let wal_config = WalConfig {
+        fsync: match ingester_config.wal_fsync_policy {
+            WalFsyncPolicy::PerBatch => FsyncPolicy::PerBatch,
+            WalFsyncPolicy::Interval => FsyncPolicy::Interval(Duration::from_millis(
+                ingester_config.wal_fsync_interval_millis,
+            )),
+            WalFsyncPolicy::Off => FsyncPolicy::Off,
+        },
+        codec: match ingester_config.wal_compression {
+            WalCompression::Snappy => SegmentCodec::Snappy,
+            WalCompression::Zstd => SegmentCodec::Zstd,
+            WalCompression::Uncompressed => SegmentCodec::Uncompressed,
+        },
+    };
+
+    let wal_archive = match &ingester_config.wal_archive_prefix {
+        None => WalArchiveConfig::Disabled,
+        Some(prefix) => WalArchiveConfig::Enabled {
+            prefix: prefix.clone(),
+            retention: ingester_config
+                .wal_archive_retention_hours
+                .map(|hours| Duration::from_secs(hours * 60 * 60)),
+        },
+    };
+
+    let wal_replication = match (
+        &ingester_config.wal_replication_follower,
+        &ingester_config.wal_replication_leader_id,
+    ) {
+        (Some(follower_addr), Some(leader_id)) => WalReplicationConfig::Leader {
+            follower_addr: follower_addr.clone(),
+            leader_id: leader_id.clone(),
+        },
+        _ => WalReplicationConfig::Disabled,
+    };
+
+    let wal_replica = if ingester_config.wal_replica_leader_ids.is_empty() {
+        WalReplicaConfig::Disabled
+    } else {
+        WalReplicaConfig::Enabled {
+            leader_ids: ingester_config.wal_replica_leader_ids.clone(),
+            takeover_timeout: Duration::from_secs(
+                ingester_config.wal_replica_takeover_timeout_seconds,
+            ),
+        }
+    };
+
+    let memory_limit = match ingester_config.buffer_memory_limit_bytes {
+        Some(hard_limit_bytes) => MemoryLimitConfig::Enabled {
+            high_watermark_bytes: (hard_limit_bytes as u128
+                * ingester_config.buffer_memory_high_watermark_percent as u128
+                / 100) as usize,
+            hard_limit_bytes,
+        },
+        None => MemoryLimitConfig::Disabled,
+    };
+
     let grpc = ingester::new(
         catalog,
         Arc::clone(&metrics),
         PERSIST_BACKGROUND_FETCH_TIME,
         ingester_config.wal_directory.clone(),
+        wal_config,
         Duration::from_secs(ingester_config.wal_rotation_period_seconds),
+        if ingester_config.wal_replay_strict {
+            WalReplayMode::Strict
+        } else {
+            WalReplayMode::Tolerant
+        },
         exec,
         ingester_config.persist_max_parallelism,
         ingester_config.persist_queue_depth,
         ingester_config.persist_hot_partition_cost,
+        ingester_config
+            .persist_cold_split_threshold_seconds
+            .map(Duration::from_secs),
+        ingester_config
+            .partition_precreate_lookahead_seconds
+            .map(Duration::from_secs),
+        memory_limit,
         object_store,
         gossip,
+        wal_archive,
+        wal_replication,
+        wal_replica,
         shutdown_rx.map(|v| v.expect("shutdown sender dropped without calling shutdown")),
     )
     .await?;
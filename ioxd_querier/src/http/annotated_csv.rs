@@ -0,0 +1,365 @@
+//! Encoding of query output as [annotated CSV], the result format of the
+//! InfluxDB 2.x query API.
+//!
+//! [annotated CSV]:
+//!     https://docs.influxdata.com/influxdb/v2.6/reference/syntax/annotated-csv/
+
+use arrow::{
+    array::ArrayRef,
+    compute::cast,
+    datatypes::{DataType, Schema},
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use serde::Deserialize;
+use serde_json::Value;
+use thiserror::Error;
+
+use super::series::{string_value, value, SeriesError, SeriesKey};
+
+/// The name of the column holding the measurement name of InfluxQL output.
+const MEASUREMENT_COLUMN: &str = "_measurement";
+
+/// Errors encoding query output as annotated CSV.
+#[derive(Debug, Error)]
+pub(crate) enum EncodeError {
+    /// The series of the output could not be determined.
+    #[error(transparent)]
+    Series(#[from] SeriesError),
+
+    /// A column of the output could not be read.
+    #[error("error reading query output: {0}")]
+    Arrow(#[from] ArrowError),
+
+    /// Writing the CSV output failed.
+    #[error("error writing CSV: {0}")]
+    Csv(#[from] csv::Error),
+}
+
+/// An annotation row describing the columns of a table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum Annotation {
+    Group,
+    Datatype,
+    Default,
+}
+
+/// The CSV dialect of a query response.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub(crate) struct Dialect {
+    /// Emit a header row naming the columns of each table.
+    pub(crate) header: bool,
+    /// The single character separating cells.
+    pub(crate) delimiter: String,
+    /// The annotation rows preceding the header of each table.
+    pub(crate) annotations: Vec<Annotation>,
+}
+
+impl Default for Dialect {
+    fn default() -> Self {
+        Self {
+            header: true,
+            delimiter: ",".to_string(),
+            annotations: vec![Annotation::Group, Annotation::Datatype, Annotation::Default],
+        }
+    }
+}
+
+/// The columns of the output, known once its first row is written.
+#[derive(Debug)]
+struct Layout {
+    key: SeriesKey,
+    /// The indexes of the columns not identifying the series of a row.
+    value_columns: Vec<usize>,
+}
+
+/// Incrementally encodes the record batches of a query as annotated CSV.
+///
+/// Each series of InfluxQL output is written as a separate table, with the
+/// measurement name and `GROUP BY` tag values as group key columns. SQL output
+/// is written as a single table.
+#[derive(Debug)]
+pub(crate) struct AnnotatedCsvEncoder {
+    writer: csv::Writer<Vec<u8>>,
+    header: bool,
+    annotations: Vec<Annotation>,
+    layout: Option<Layout>,
+    /// The index and series key values of the table being written.
+    table: Option<(usize, Vec<String>)>,
+}
+
+impl AnnotatedCsvEncoder {
+    /// Create an encoder writing `dialect`, separating cells by `delimiter`.
+    pub(crate) fn new(dialect: Dialect, delimiter: u8) -> Self {
+        let writer = csv::WriterBuilder::new()
+            .delimiter(delimiter)
+            .flexible(true)
+            .terminator(csv::Terminator::CRLF)
+            .from_writer(vec![]);
+
+        Self {
+            writer,
+            header: dialect.header,
+            annotations: dialect.annotations,
+            layout: None,
+            table: None,
+        }
+    }
+
+    /// Encode the rows of `batch`, returning the CSV output.
+    pub(crate) fn write_batch(&mut self, batch: &RecordBatch) -> Result<Vec<u8>, EncodeError> {
+        if batch.num_rows() == 0 {
+            return Ok(vec![]);
+        }
+
+        if self.layout.is_none() {
+            let layout = self.write_table_header(&batch.schema())?;
+            self.layout = Some(layout);
+        }
+        let Self {
+            writer,
+            annotations,
+            layout,
+            table,
+            ..
+        } = self;
+        let layout = layout.as_ref().expect("layout initialised above");
+
+        // With a default annotation the result name is elided from each row.
+        let result = if annotations.contains(&Annotation::Default) {
+            ""
+        } else {
+            "_result"
+        };
+
+        let key_columns = layout
+            .key
+            .measurement
+            .into_iter()
+            .chain(layout.key.tags.iter().map(|tk| tk.column_index as usize))
+            .map(|i| cast(batch.column(i), &DataType::Utf8))
+            .collect::<Result<Vec<ArrayRef>, _>>()?;
+
+        for row in 0..batch.num_rows() {
+            let key = key_columns
+                .iter()
+                .map(|values| string_value(values, row).to_string())
+                .collect::<Vec<_>>();
+
+            let table_id = match table {
+                Some((id, table_key)) if *table_key == key => *id,
+                Some((id, table_key)) => {
+                    *id += 1;
+                    *table_key = key;
+                    *id
+                }
+                None => {
+                    *table = Some((0, key));
+                    0
+                }
+            };
+
+            let mut record = vec![String::new(), result.to_string(), table_id.to_string()];
+            for i in &layout.value_columns {
+                record.push(cell(value(batch.column(*i), row, None)?));
+            }
+            if let Some((_, key)) = table {
+                record.extend(key.iter().cloned());
+            }
+            writer.write_record(&record)?;
+        }
+
+        Ok(self.take_output()?)
+    }
+
+    /// Encode `msg` as an error table, returning the CSV output.
+    pub(crate) fn write_error(&mut self, msg: &str) -> Result<Vec<u8>, EncodeError> {
+        self.writer.flush()?;
+        if self.layout.is_some() {
+            // Tables with differing columns are separated by an empty line.
+            self.writer.get_mut().extend_from_slice(b"\r\n");
+        }
+
+        self.writer
+            .write_record(["#datatype", "string", "string"])?;
+        self.writer.write_record(["#group", "true", "true"])?;
+        self.writer.write_record(["#default", "", ""])?;
+        self.writer.write_record(["", "error", "reference"])?;
+        self.writer.write_record(["", msg, ""])?;
+
+        Ok(self.take_output()?)
+    }
+
+    /// Write the annotations and header of the table holding rows of `schema`.
+    fn write_table_header(&mut self, schema: &Schema) -> Result<Layout, EncodeError> {
+        let key = SeriesKey::try_new(schema)?;
+        let value_columns = (0..schema.fields().len())
+            .filter(|i| {
+                Some(*i) != key.measurement
+                    && !key.tags.iter().any(|tk| tk.column_index as usize == *i)
+            })
+            .collect::<Vec<_>>();
+
+        // Value columns, followed by the group key columns.
+        let mut columns = vec![];
+        for i in &value_columns {
+            let field = schema.field(*i);
+            columns.push((field.name().as_str(), datatype(field.data_type()), false));
+        }
+        if key.measurement.is_some() {
+            columns.push((MEASUREMENT_COLUMN, "string", true));
+        }
+        for tk in &key.tags {
+            columns.push((tk.tag_key.as_str(), "string", true));
+        }
+
+        for annotation in [Annotation::Group, Annotation::Datatype, Annotation::Default] {
+            if !self.annotations.contains(&annotation) {
+                continue;
+            }
+            let record = match annotation {
+                Annotation::Group => ["#group", "false", "false"]
+                    .into_iter()
+                    .chain(
+                        columns
+                            .iter()
+                            .map(|(_, _, group)| if *group { "true" } else { "false" }),
+                    )
+                    .collect::<Vec<_>>(),
+                Annotation::Datatype => ["#datatype", "string", "long"]
+                    .into_iter()
+                    .chain(columns.iter().map(|(_, datatype, _)| *datatype))
+                    .collect(),
+                Annotation::Default => ["#default", "_result", ""]
+                    .into_iter()
+                    .chain(columns.iter().map(|_| ""))
+                    .collect(),
+            };
+            self.writer.write_record(record)?;
+        }
+
+        if self.header {
+            self.writer.write_record(
+                ["", "result", "table"]
+                    .into_iter()
+                    .chain(columns.iter().map(|(name, _, _)| *name)),
+            )?;
+        }
+
+        Ok(Layout { key, value_columns })
+    }
+
+    fn take_output(&mut self) -> Result<Vec<u8>, csv::Error> {
+        self.writer.flush()?;
+        Ok(std::mem::take(self.writer.get_mut()))
+    }
+}
+
+/// The annotated CSV datatype of values of `data_type`.
+fn datatype(data_type: &DataType) -> &'static str {
+    match data_type {
+        DataType::Float64 => "double",
+        DataType::Int64 => "long",
+        DataType::UInt64 => "unsignedLong",
+        DataType::Boolean => "boolean",
+        DataType::Timestamp(_, _) => "dateTime:RFC3339",
+        _ => "string",
+    }
+}
+
+fn cell(value: Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s,
+        v => v.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::{
+        array::{BooleanArray, Float64Array},
+        datatypes::Field,
+    };
+
+    use super::*;
+
+    fn batch(values: Vec<Option<f64>>) -> RecordBatch {
+        let n = values.len();
+        RecordBatch::try_new(
+            Arc::new(Schema::new(vec![
+                Field::new("value", DataType::Float64, true),
+                Field::new("ok", DataType::Boolean, false),
+            ])),
+            vec![
+                Arc::new(Float64Array::from(values)),
+                Arc::new(BooleanArray::from(vec![true; n])),
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_single_table() {
+        let mut encoder = AnnotatedCsvEncoder::new(Dialect::default(), b',');
+
+        let first = encoder.write_batch(&batch(vec![Some(1.5), None])).unwrap();
+        let second = encoder.write_batch(&batch(vec![Some(2.0)])).unwrap();
+
+        assert_eq!(
+            String::from_utf8(first).unwrap(),
+            "#group,false,false,false,false\r\n\
+             #datatype,string,long,double,boolean\r\n\
+             #default,_result,,,\r\n\
+             ,result,table,value,ok\r\n\
+             ,,0,1.5,true\r\n\
+             ,,0,,true\r\n"
+        );
+        assert_eq!(String::from_utf8(second).unwrap(), ",,0,2.0,true\r\n");
+    }
+
+    #[test]
+    fn test_dialect() {
+        let dialect = Dialect {
+            header: false,
+            delimiter: ";".to_string(),
+            annotations: vec![Annotation::Datatype],
+        };
+        let mut encoder = AnnotatedCsvEncoder::new(dialect, b';');
+
+        let got = encoder.write_batch(&batch(vec![Some(1.5)])).unwrap();
+        assert_eq!(
+            String::from_utf8(got).unwrap(),
+            "#datatype;string;long;double;boolean\r\n\
+             ;_result;0;1.5;true\r\n"
+        );
+    }
+
+    #[test]
+    fn test_error_table() {
+        let mut encoder = AnnotatedCsvEncoder::new(Dialect::default(), b',');
+        encoder.write_batch(&batch(vec![Some(1.5)])).unwrap();
+
+        let got = encoder.write_error("it broke, badly").unwrap();
+        assert_eq!(
+            String::from_utf8(got).unwrap(),
+            "\r\n\
+             #datatype,string,string\r\n\
+             #group,true,true\r\n\
+             #default,,\r\n\
+             ,error,reference\r\n\
+             ,\"it broke, badly\",\r\n"
+        );
+    }
+
+    #[test]
+    fn test_empty_batch() {
+        let mut encoder = AnnotatedCsvEncoder::new(Dialect::default(), b',');
+        assert!(encoder.write_batch(&batch(vec![])).unwrap().is_empty());
+        assert!(encoder.layout.is_none());
+    }
+}
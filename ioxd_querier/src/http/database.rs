@@ -0,0 +1,99 @@
+//! Mapping of the InfluxQL databases and retention policies of a request onto
+//! IOx namespaces.
+//!
+//! A database `db` with retention policy `rp` is stored in the namespace
+//! `db/rp`, mirroring the router's V1 write API, see
+//! [`iox_query_influxql::database`].
+
+use data_types::NamespaceName;
+
+use super::Error;
+
+/// Derive the namespace name of the database `db` and retention policy `rp`.
+pub(super) fn namespace_name(
+    db: Option<&str>,
+    rp: Option<&str>,
+) -> Result<NamespaceName<'static>, Error> {
+    let db = db
+        .filter(|db| !db.is_empty())
+        .ok_or(Error::MissingDatabase)?;
+
+    Ok(NamespaceName::new(
+        iox_query_influxql::database::namespace_name(db, rp),
+    )?)
+}
+
+/// A database and retention policy, either of which may be left to a default.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(super) struct DatabaseRef {
+    pub(super) db: Option<String>,
+    pub(super) rp: Option<String>,
+}
+
+impl DatabaseRef {
+    /// Fill in the database and retention policy from `default`, if not
+    /// set.
+    ///
+    /// The retention policy of `default` only applies if the database is
+    /// taken from `default` too, as naming a database selects its default
+    /// retention policy.
+    pub(super) fn or(self, default: &Self) -> Self {
+        match self.db {
+            Some(_) => self,
+            None => Self {
+                db: default.db.clone(),
+                rp: self.rp.or_else(|| default.rp.clone()),
+            },
+        }
+    }
+
+    /// Derive the namespace name of the database and retention policy.
+    pub(super) fn namespace(&self) -> Result<NamespaceName<'static>, Error> {
+        namespace_name(self.db.as_deref(), self.rp.as_deref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_namespace_name() {
+        let name = |db, rp| namespace_name(Some(db), rp).unwrap().to_string();
+
+        assert_eq!(name("bananas", None), "bananas");
+        assert_eq!(name("bananas", Some("autogen")), "bananas");
+        assert_eq!(name("bananas", Some("ripe")), "bananas/ripe");
+        assert!(matches!(
+            namespace_name(Some(""), None),
+            Err(Error::MissingDatabase)
+        ));
+    }
+
+    #[test]
+    fn test_database_ref_or() {
+        let default = DatabaseRef {
+            db: Some("bananas".to_owned()),
+            rp: Some("ripe".to_owned()),
+        };
+
+        let got = DatabaseRef::default().or(&default);
+        assert_eq!(got.namespace().unwrap().as_str(), "bananas/ripe");
+
+        // A database selects its default retention policy.
+        let got = DatabaseRef {
+            db: Some("apples".to_owned()),
+            rp: None,
+        }
+        .or(&default);
+        assert_eq!(got.namespace().unwrap().as_str(), "apples");
+
+        // A retention policy applies to the default database.
+        let got = DatabaseRef {
+            db: None,
+            rp: Some("green".to_owned()),
+        }
+        .or(&default);
+        assert_eq!(got.namespace().unwrap().as_str(), "bananas/green");
+    }
+}
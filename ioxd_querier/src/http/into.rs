@@ -0,0 +1,156 @@
+//! Writing the output of InfluxQL `SELECT ... INTO` statements back into a
+//! namespace.
+
+use std::collections::{BTreeMap, HashSet};
+
+use arrow::{compute::cast, datatypes::DataType, error::ArrowError, record_batch::RecordBatch};
+use influxdb_influxql_parser::select::IntoMeasurement;
+use rollup::{batch_to_lp, RollupWriter, WriteError};
+use serde_json::Value;
+use thiserror::Error;
+
+use super::series::{string_value, Epoch, Series, SeriesError, SeriesKey};
+
+/// Errors writing the output of a `SELECT ... INTO` statement.
+#[derive(Debug, Error)]
+pub(crate) enum IntoError {
+    /// The series key of the output could not be read.
+    #[error(transparent)]
+    Series(#[from] SeriesError),
+
+    /// The measurement names of the output could not be read.
+    #[error("error reading query output: {0}")]
+    Arrow(#[from] ArrowError),
+
+    /// The output has no measurement names to write to `:MEASUREMENT`.
+    #[error("query output has no measurement names for :MEASUREMENT")]
+    NoMeasurement,
+
+    /// The output could not be converted or written.
+    #[error(transparent)]
+    Write(#[from] WriteError),
+}
+
+/// Write the output `batches` of a `SELECT ... INTO` statement into
+/// `measurement` of `namespace`, returning the number of points written.
+///
+/// The `GROUP BY` tags of the output are written as tags, and all other
+/// columns as fields.
+pub(crate) async fn write_into(
+    writer: &dyn RollupWriter,
+    namespace: &str,
+    measurement: &IntoMeasurement,
+    batches: &[RecordBatch],
+) -> Result<usize, IntoError> {
+    let lp = into_lp(measurement, batches)?;
+    let written = lp.lines().count();
+    if written > 0 {
+        writer.write_lp(namespace, lp).await?;
+    }
+    Ok(written)
+}
+
+/// The result of a `SELECT ... INTO` statement that wrote `written` points,
+/// as returned by InfluxDB.
+pub(crate) fn written_series(written: usize, epoch: Option<Epoch>) -> Series {
+    let time = match epoch {
+        Some(_) => Value::from(0),
+        None => Value::from("1970-01-01T00:00:00Z"),
+    };
+
+    Series {
+        name: "result".to_string(),
+        tags: BTreeMap::new(),
+        columns: vec!["time".to_string(), "written".to_string()],
+        values: vec![vec![time, Value::from(written)]],
+        partial: false,
+    }
+}
+
+/// Convert `batches` into line protocol for `measurement`.
+fn into_lp(measurement: &IntoMeasurement, batches: &[RecordBatch]) -> Result<String, IntoError> {
+    let mut lp = String::new();
+    for batch in batches {
+        let schema = batch.schema();
+        let key = SeriesKey::try_new(&schema)?;
+        let tags = key
+            .tags
+            .iter()
+            .map(|tk| schema.field(tk.column_index as usize).name().clone())
+            .collect::<HashSet<_>>();
+
+        match measurement {
+            IntoMeasurement::Name(name) => lp.push_str(&batch_to_lp(name.as_str(), &tags, batch)?),
+            IntoMeasurement::Backreference => {
+                let index = key.measurement.ok_or(IntoError::NoMeasurement)?;
+                let names = cast(batch.column(index), &DataType::Utf8)?;
+
+                // The output is ordered by measurement, so write each run of
+                // rows sharing a measurement name at once.
+                let mut start = 0;
+                for row in 1..=batch.num_rows() {
+                    if row < batch.num_rows()
+                        && string_value(&names, row) == string_value(&names, start)
+                    {
+                        continue;
+                    }
+                    lp.push_str(&batch_to_lp(
+                        string_value(&names, start),
+                        &tags,
+                        &batch.slice(start, row - start),
+                    )?);
+                    start = row;
+                }
+            }
+        }
+    }
+    Ok(lp)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::array::{ArrayRef, Float64Array, StringArray, TimestampNanosecondArray};
+
+    use super::*;
+
+    #[test]
+    fn test_into_lp_backreference() {
+        let batch = RecordBatch::try_from_iter(vec![
+            (
+                "iox::measurement",
+                Arc::new(StringArray::from(vec!["cpu", "cpu", "mem"])) as ArrayRef,
+            ),
+            (
+                "time",
+                Arc::new(TimestampNanosecondArray::from(vec![0, 10, 20])) as ArrayRef,
+            ),
+            (
+                "usage",
+                Arc::new(Float64Array::from(vec![Some(1.5), None, Some(2.0)])) as ArrayRef,
+            ),
+        ])
+        .unwrap();
+
+        let lp = into_lp(&IntoMeasurement::Backreference, &[batch.clone()]).unwrap();
+        // rows without fields are skipped
+        assert_eq!(lp, "cpu usage=1.5 0\nmem usage=2 20\n");
+
+        let lp = into_lp(&IntoMeasurement::Name("copy".into()), &[batch]).unwrap();
+        assert_eq!(lp, "copy usage=1.5 0\ncopy usage=2 20\n");
+    }
+
+    #[test]
+    fn test_written_series() {
+        let series = written_series(3, None);
+        assert_eq!(series.columns, ["time", "written"]);
+        assert_eq!(
+            series.values,
+            [[Value::from("1970-01-01T00:00:00Z"), Value::from(3)]]
+        );
+
+        let series = written_series(3, Some(Epoch::Nanosecond));
+        assert_eq!(series.values, [[Value::from(0), Value::from(3)]]);
+    }
+}
@@ -0,0 +1,264 @@
+//! HTTP service implementations for the querier.
+
+mod annotated_csv;
+mod database;
+mod into;
+mod msgpack;
+mod series;
+mod v1;
+mod v2;
+
+use std::sync::Arc;
+
+use authz::{Action, Authorizer, Permission, Resource};
+use data_types::{NamespaceName, NamespaceNameError, OrgBucketMappingError};
+use datafusion::error::DataFusionError;
+use futures::StreamExt;
+use hyper::{Body, Method, Request, Response, StatusCode};
+use ioxd_common::http::error::{HttpApiError, HttpApiErrorSource};
+use rollup::RollupWriter;
+use service_common::QueryNamespaceProvider;
+use thiserror::Error;
+
+/// Errors returned by the querier HTTP request handler.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// The requested path has no registered handler.
+    #[error("not found")]
+    NoHandler,
+
+    /// The request parameters could not be decoded.
+    #[error("invalid query parameters: {0}")]
+    InvalidParams(#[from] serde_urlencoded::de::Error),
+
+    /// The request did not specify a query.
+    #[error("missing required parameter \"q\"")]
+    MissingQuery,
+
+    /// The request did not specify a database.
+    #[error("database name required")]
+    MissingDatabase,
+
+    /// The database (and retention policy) do not form a valid namespace name.
+    #[error(transparent)]
+    InvalidNamespace(#[from] NamespaceNameError),
+
+    /// The org and bucket do not map to a valid namespace name.
+    #[error(transparent)]
+    InvalidOrgAndBucket(#[from] OrgBucketMappingError),
+
+    /// The request body is not a valid query request.
+    #[error("invalid query request: {0}")]
+    InvalidBody(serde_json::Error),
+
+    /// The query language is not supported.
+    #[error("unsupported query type {0:?}, expected \"influxql\" or \"sql\"")]
+    UnsupportedQueryType(String),
+
+    /// The CSV dialect delimiter is not a single character.
+    #[error("invalid dialect delimiter {0:?}")]
+    InvalidDelimiter(String),
+
+    /// The query is not valid InfluxQL.
+    #[error("error parsing query: {0}")]
+    ParseQuery(String),
+
+    /// The bind parameters of the query are not a valid JSON object.
+    #[error("invalid bind parameters: {0}")]
+    InvalidBindParams(serde_json::Error),
+
+    /// The query contains a statement that writes, but the request is not a
+    /// `POST` request.
+    #[error("{0} requires a POST request")]
+    RequiresPost(&'static str),
+
+    /// The query contains a `SELECT ... INTO` statement, but no router to
+    /// write to is configured.
+    #[error("SELECT ... INTO is not enabled on this querier")]
+    IntoNotEnabled,
+
+    /// The query could not be planned.
+    #[error("error planning query: {0}")]
+    Planning(DataFusionError),
+
+    /// The namespace does not exist.
+    #[error("database not found: {0}")]
+    NamespaceNotFound(String),
+
+    /// The client disconnected.
+    #[error("client disconnected")]
+    ClientHangup(hyper::Error),
+
+    /// The client sent a request body that exceeds the configured maximum.
+    #[error("max request size ({0} bytes) exceeded")]
+    RequestSizeExceeded(usize),
+
+    /// The request has no authentication, but authorization is configured.
+    #[error("authentication required")]
+    Unauthenticated,
+
+    /// The provided authorization is not sufficient to perform the request.
+    #[error("access denied")]
+    Forbidden,
+
+    /// The authorization service could not verify the request.
+    #[error("authorization failed: {0}")]
+    Authz(authz::Error),
+
+    /// The query output could not be encoded into the requested format.
+    #[error("error encoding response: {0}")]
+    Encode(Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl Error {
+    /// Convert the error into an appropriate [`StatusCode`] to be returned to
+    /// the end user.
+    pub fn as_status_code(&self) -> StatusCode {
+        match self {
+            Self::NoHandler => StatusCode::NOT_FOUND,
+            Self::InvalidParams(_) => StatusCode::BAD_REQUEST,
+            Self::MissingQuery => StatusCode::BAD_REQUEST,
+            Self::MissingDatabase => StatusCode::BAD_REQUEST,
+            Self::InvalidNamespace(_) => StatusCode::BAD_REQUEST,
+            Self::InvalidOrgAndBucket(_) => StatusCode::BAD_REQUEST,
+            Self::InvalidBody(_) => StatusCode::BAD_REQUEST,
+            Self::UnsupportedQueryType(_) => StatusCode::BAD_REQUEST,
+            Self::InvalidDelimiter(_) => StatusCode::BAD_REQUEST,
+            Self::ParseQuery(_) => StatusCode::BAD_REQUEST,
+            Self::InvalidBindParams(_) => StatusCode::BAD_REQUEST,
+            Self::RequiresPost(_) => StatusCode::METHOD_NOT_ALLOWED,
+            Self::IntoNotEnabled => StatusCode::NOT_IMPLEMENTED,
+            Self::Planning(_) => StatusCode::BAD_REQUEST,
+            Self::NamespaceNotFound(_) => StatusCode::NOT_FOUND,
+            Self::ClientHangup(_) => StatusCode::BAD_REQUEST,
+            Self::RequestSizeExceeded(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            Self::Unauthenticated => StatusCode::UNAUTHORIZED,
+            Self::Forbidden => StatusCode::FORBIDDEN,
+            Self::Authz(_) => StatusCode::SERVICE_UNAVAILABLE,
+            Self::Encode(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+impl From<authz::Error> for Error {
+    fn from(source: authz::Error) -> Self {
+        match source {
+            authz::Error::Forbidden | authz::Error::InvalidToken => Self::Forbidden,
+            authz::Error::NoToken => Self::Unauthenticated,
+            source => Self::Authz(source),
+        }
+    }
+}
+
+impl HttpApiErrorSource for Error {
+    fn to_http_api_error(&self) -> HttpApiError {
+        HttpApiError::new(self.as_status_code(), self.to_string())
+    }
+}
+
+/// The HTTP request handler of the querier, serving the query APIs of
+/// InfluxDB for the namespaces of a [`QueryNamespaceProvider`].
+#[derive(Debug)]
+pub struct HttpDelegate<S> {
+    server: Arc<S>,
+    authz: Option<Arc<dyn Authorizer>>,
+    max_request_bytes: usize,
+    into_writer: Option<Arc<dyn RollupWriter>>,
+    single_tenant: bool,
+}
+
+impl<S> HttpDelegate<S>
+where
+    S: QueryNamespaceProvider,
+{
+    /// Create a new handler querying the namespaces of `server`, authorizing
+    /// requests with `authz` if set, and rejecting request bodies larger than
+    /// `max_request_bytes`.
+    pub fn new(
+        server: Arc<S>,
+        authz: Option<Arc<dyn Authorizer>>,
+        max_request_bytes: usize,
+    ) -> Self {
+        Self {
+            server,
+            authz,
+            max_request_bytes,
+            into_writer: None,
+            single_tenant: false,
+        }
+    }
+
+    /// Resolve the namespace of V2 query requests the way a router deployed
+    /// in single tenant mode resolves the namespace of V2 writes.
+    pub fn with_single_tenant(mut self, single_tenant: bool) -> Self {
+        self.single_tenant = single_tenant;
+        self
+    }
+
+    /// Write the output of InfluxQL `SELECT ... INTO` statements with
+    /// `writer`, rather than rejecting them.
+    pub fn with_into_writer(mut self, writer: Arc<dyn RollupWriter>) -> Self {
+        self.into_writer = Some(writer);
+        self
+    }
+
+    /// Route `req` to the appropriate handler, if any, returning the handler
+    /// response.
+    pub async fn route(&self, req: Request<Body>) -> Result<Response<Body>, Error> {
+        match (req.method(), req.uri().path()) {
+            (&Method::GET | &Method::POST, "/query") => {
+                v1::query(
+                    &self.server,
+                    &self.authz,
+                    &self.into_writer,
+                    self.max_request_bytes,
+                    req,
+                )
+                .await
+            }
+            (&Method::POST, "/api/v2/query") => {
+                v2::query(
+                    self.server.as_ref(),
+                    &self.authz,
+                    self.single_tenant,
+                    self.max_request_bytes,
+                    req,
+                )
+                .await
+            }
+            _ => Err(Error::NoHandler),
+        }
+    }
+}
+
+/// Check that `token` grants permission to perform `action` on `namespace`.
+async fn authorize(
+    authz: &Option<Arc<dyn Authorizer>>,
+    token: Option<Vec<u8>>,
+    namespace: &NamespaceName<'_>,
+    action: Action,
+) -> Result<(), Error> {
+    let perms = [Permission::ResourceAction(
+        Resource::Database(namespace.to_string()),
+        action,
+    )];
+    authz.permissions(token, &perms).await?;
+    Ok(())
+}
+
+/// Read the body of `req`, failing without buffering the rest of it as soon
+/// as it exceeds `max_request_bytes`.
+async fn read_body(req: Request<Body>, max_request_bytes: usize) -> Result<Vec<u8>, Error> {
+    let mut payload = req.into_body();
+
+    let mut body = Vec::new();
+    while let Some(chunk) = payload.next().await {
+        let chunk = chunk.map_err(Error::ClientHangup)?;
+        // limit max size of in-memory payload
+        if (body.len() + chunk.len()) > max_request_bytes {
+            return Err(Error::RequestSizeExceeded(max_request_bytes));
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(body)
+}
@@ -0,0 +1,150 @@
+//! A minimal [MessagePack] encoder for query responses.
+//!
+//! [MessagePack]: https://github.com/msgpack/msgpack/blob/master/spec.md
+
+use serde_json::Value;
+
+/// Append the MessagePack encoding of `value` to `buf`.
+pub(crate) fn encode(value: &Value, buf: &mut Vec<u8>) {
+    match value {
+        Value::Null => buf.push(0xc0),
+        Value::Bool(false) => buf.push(0xc2),
+        Value::Bool(true) => buf.push(0xc3),
+        Value::Number(n) => {
+            if let Some(v) = n.as_u64() {
+                encode_u64(v, buf);
+            } else if let Some(v) = n.as_i64() {
+                encode_i64(v, buf);
+            } else {
+                buf.push(0xcb);
+                buf.extend_from_slice(&n.as_f64().unwrap_or(f64::NAN).to_be_bytes());
+            }
+        }
+        Value::String(s) => encode_str(s, buf),
+        Value::Array(values) => {
+            encode_collection_header(values.len(), 0x90, 0xdc, 0xdd, buf);
+            for v in values {
+                encode(v, buf);
+            }
+        }
+        Value::Object(map) => {
+            encode_collection_header(map.len(), 0x80, 0xde, 0xdf, buf);
+            for (k, v) in map {
+                encode_str(k, buf);
+                encode(v, buf);
+            }
+        }
+    }
+}
+
+fn encode_u64(v: u64, buf: &mut Vec<u8>) {
+    if v < 0x80 {
+        buf.push(v as u8);
+    } else if let Ok(v) = u8::try_from(v) {
+        buf.extend_from_slice(&[0xcc, v]);
+    } else if let Ok(v) = u16::try_from(v) {
+        buf.push(0xcd);
+        buf.extend_from_slice(&v.to_be_bytes());
+    } else if let Ok(v) = u32::try_from(v) {
+        buf.push(0xce);
+        buf.extend_from_slice(&v.to_be_bytes());
+    } else {
+        buf.push(0xcf);
+        buf.extend_from_slice(&v.to_be_bytes());
+    }
+}
+
+fn encode_i64(v: i64, buf: &mut Vec<u8>) {
+    if (-32..0).contains(&v) {
+        // negative fixint
+        buf.push(v as i8 as u8);
+    } else if let Ok(v) = i8::try_from(v) {
+        buf.extend_from_slice(&[0xd0, v as u8]);
+    } else if let Ok(v) = i16::try_from(v) {
+        buf.push(0xd1);
+        buf.extend_from_slice(&v.to_be_bytes());
+    } else if let Ok(v) = i32::try_from(v) {
+        buf.push(0xd2);
+        buf.extend_from_slice(&v.to_be_bytes());
+    } else {
+        buf.push(0xd3);
+        buf.extend_from_slice(&v.to_be_bytes());
+    }
+}
+
+fn encode_str(s: &str, buf: &mut Vec<u8>) {
+    let len = s.len();
+    if len < 32 {
+        buf.push(0xa0 | len as u8);
+    } else if let Ok(len) = u8::try_from(len) {
+        buf.extend_from_slice(&[0xd9, len]);
+    } else if let Ok(len) = u16::try_from(len) {
+        buf.push(0xda);
+        buf.extend_from_slice(&len.to_be_bytes());
+    } else {
+        buf.push(0xdb);
+        buf.extend_from_slice(&(len as u32).to_be_bytes());
+    }
+    buf.extend_from_slice(s.as_bytes());
+}
+
+fn encode_collection_header(len: usize, fix: u8, marker16: u8, marker32: u8, buf: &mut Vec<u8>) {
+    if len < 16 {
+        buf.push(fix | len as u8);
+    } else if let Ok(len) = u16::try_from(len) {
+        buf.push(marker16);
+        buf.extend_from_slice(&len.to_be_bytes());
+    } else {
+        buf.push(marker32);
+        buf.extend_from_slice(&(len as u32).to_be_bytes());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn encoded(value: Value) -> Vec<u8> {
+        let mut buf = vec![];
+        encode(&value, &mut buf);
+        buf
+    }
+
+    #[test]
+    fn test_encode_scalars() {
+        assert_eq!(encoded(json!(null)), [0xc0]);
+        assert_eq!(encoded(json!(true)), [0xc3]);
+        assert_eq!(encoded(json!(false)), [0xc2]);
+        assert_eq!(encoded(json!(7)), [0x07]);
+        assert_eq!(encoded(json!(200)), [0xcc, 0xc8]);
+        assert_eq!(encoded(json!(300)), [0xcd, 0x01, 0x2c]);
+        assert_eq!(encoded(json!(-1)), [0xff]);
+        assert_eq!(encoded(json!(-100)), [0xd0, 0x9c]);
+        assert_eq!(encoded(json!(-1000)), [0xd1, 0xfc, 0x18]);
+        assert_eq!(
+            encoded(json!(1.5)),
+            [0xcb, 0x3f, 0xf8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]
+        );
+        assert_eq!(encoded(json!("cpu")), [0xa3, b'c', b'p', b'u']);
+
+        let long = "x".repeat(40);
+        let mut want = vec![0xd9, 40];
+        want.extend_from_slice(long.as_bytes());
+        assert_eq!(encoded(json!(long)), want);
+    }
+
+    #[test]
+    fn test_encode_collections() {
+        assert_eq!(
+            encoded(json!({"a": [1, null]})),
+            [0x81, 0xa1, b'a', 0x92, 0x01, 0xc0]
+        );
+
+        let values = vec![json!(true); 20];
+        let mut want = vec![0xdc, 0x00, 20];
+        want.extend(std::iter::repeat(0xc3).take(20));
+        assert_eq!(encoded(Value::Array(values)), want);
+    }
+}
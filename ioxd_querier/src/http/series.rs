@@ -0,0 +1,462 @@
+//! Conversion of InfluxQL query output into InfluxDB 1.x series.
+
+use std::collections::BTreeMap;
+
+use arrow::{
+    array::{Array, ArrayRef, AsArray},
+    compute::cast,
+    datatypes::{
+        DataType, Float64Type, Int64Type, Schema, TimeUnit, TimestampNanosecondType, UInt64Type,
+    },
+    error::ArrowError,
+    record_batch::RecordBatch,
+    util::display::{ArrayFormatter, FormatOptions},
+};
+use chrono::{SecondsFormat, TimeZone, Utc};
+use generated_types::influxdata::iox::querier::v1::{
+    influx_ql_metadata::TagKeyColumn, InfluxQlMetadata,
+};
+use schema::{INFLUXQL_MEASUREMENT_COLUMN_NAME, INFLUXQL_METADATA_KEY};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+/// Errors converting query output into series.
+#[derive(Debug, Error)]
+pub(crate) enum SeriesError {
+    /// The InfluxQL metadata of the output schema could not be decoded.
+    #[error("invalid InfluxQL metadata: {0}")]
+    InvalidMetadata(#[from] serde_json::Error),
+
+    /// A column of the output could not be read.
+    #[error("error reading query output: {0}")]
+    Arrow(#[from] ArrowError),
+}
+
+/// The precision of the timestamps of a query response.
+///
+/// Timestamps are rendered as RFC3339 strings when no precision is requested.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub(crate) enum Epoch {
+    #[serde(rename = "h")]
+    Hour,
+    #[serde(rename = "m")]
+    Minute,
+    #[serde(rename = "s")]
+    Second,
+    #[serde(rename = "ms")]
+    Millisecond,
+    #[serde(rename = "u", alias = "µ")]
+    Microsecond,
+    #[serde(rename = "ns")]
+    Nanosecond,
+}
+
+impl Epoch {
+    /// The number of nanoseconds in one unit of this precision.
+    fn nanos(&self) -> i64 {
+        match self {
+            Self::Hour => 3_600_000_000_000,
+            Self::Minute => 60_000_000_000,
+            Self::Second => 1_000_000_000,
+            Self::Millisecond => 1_000_000,
+            Self::Microsecond => 1_000,
+            Self::Nanosecond => 1,
+        }
+    }
+}
+
+/// A series of rows sharing a measurement name and group-by tag values.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct Series {
+    pub(crate) name: String,
+    /// The `GROUP BY` tag values of the series, omitted when the query is not
+    /// grouped by any tags.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub(crate) tags: BTreeMap<String, String>,
+    pub(crate) columns: Vec<String>,
+    pub(crate) values: Vec<Vec<Value>>,
+    /// Set when further rows of this series follow in a later chunk.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub(crate) partial: bool,
+}
+
+/// The columns of InfluxQL query output that identify the series of a row.
+#[derive(Debug, Default)]
+pub(crate) struct SeriesKey {
+    /// The index of the measurement name column, if any.
+    pub(crate) measurement: Option<usize>,
+    /// The `GROUP BY` tag keys and the indexes of their columns.
+    pub(crate) tags: Vec<TagKeyColumn>,
+}
+
+impl SeriesKey {
+    /// Read the series key columns from the InfluxQL metadata of `schema`.
+    ///
+    /// Output without metadata, such as that of SQL queries, has no series key
+    /// columns beyond a measurement column, if present.
+    pub(crate) fn try_new(schema: &Schema) -> Result<Self, SeriesError> {
+        match schema.metadata().get(INFLUXQL_METADATA_KEY) {
+            Some(md) => {
+                let md = serde_json::from_str::<InfluxQlMetadata>(md)?;
+                Ok(Self {
+                    measurement: Some(md.measurement_column_index as usize),
+                    tags: md.tag_key_columns,
+                })
+            }
+            None => Ok(Self {
+                measurement: schema.index_of(INFLUXQL_MEASUREMENT_COLUMN_NAME).ok(),
+                tags: vec![],
+            }),
+        }
+    }
+}
+
+/// Splits the output of an InfluxQL query into [`Series`] as it is read.
+///
+/// The output is expected to be ordered by measurement and `GROUP BY` tag
+/// values, as produced by the InfluxQL planner, so only the rows of the last
+/// series read are held.
+#[derive(Debug)]
+pub(crate) struct SeriesBuilder {
+    key: SeriesKey,
+    /// The indexes of the output columns.
+    col_indexes: Vec<usize>,
+    columns: Vec<String>,
+    epoch: Option<Epoch>,
+    /// The maximum number of rows of a returned series.
+    max_rows: usize,
+    /// The series of the last row read, if any.
+    current: Option<Series>,
+}
+
+impl SeriesBuilder {
+    /// Create a builder for output with `schema`, returning series of at most
+    /// `max_rows` rows.
+    pub(crate) fn try_new(
+        schema: &Schema,
+        epoch: Option<Epoch>,
+        max_rows: usize,
+    ) -> Result<Self, SeriesError> {
+        let key = SeriesKey::try_new(schema)?;
+
+        // Output all columns except for the measurement name and any tag keys
+        // only referenced by the `GROUP BY` clause.
+        let col_indexes = (0..schema.fields().len())
+            .filter(|i| {
+                Some(*i) != key.measurement
+                    && !key
+                        .tags
+                        .iter()
+                        .any(|tk| tk.column_index as usize == *i && !tk.is_projected)
+            })
+            .collect::<Vec<_>>();
+        let columns = col_indexes
+            .iter()
+            .map(|i| schema.field(*i).name().clone())
+            .collect::<Vec<_>>();
+
+        Ok(Self {
+            key,
+            col_indexes,
+            columns,
+            epoch,
+            max_rows: max_rows.max(1),
+            current: None,
+        })
+    }
+
+    /// Add the rows of `batch`, returning the series they complete.
+    ///
+    /// A series of more than `max_rows` rows is returned in parts, all but the
+    /// last of which are marked [`partial`](Series::partial).
+    pub(crate) fn push(&mut self, batch: &RecordBatch) -> Result<Vec<Series>, SeriesError> {
+        let measurement = self
+            .key
+            .measurement
+            .map(|i| cast(batch.column(i), &DataType::Utf8))
+            .transpose()?;
+        let tag_values = self
+            .key
+            .tags
+            .iter()
+            .map(|tk| cast(batch.column(tk.column_index as usize), &DataType::Utf8))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut completed = vec![];
+        for row in 0..batch.num_rows() {
+            let name = measurement
+                .as_ref()
+                .map(|m| string_value(m, row))
+                .unwrap_or_default();
+            let tags = self
+                .key
+                .tags
+                .iter()
+                .zip(&tag_values)
+                .map(|(tk, values)| (tk.tag_key.clone(), string_value(values, row).to_string()))
+                .collect::<BTreeMap<_, _>>();
+            let row_values = self
+                .col_indexes
+                .iter()
+                .map(|i| value(batch.column(*i), row, self.epoch))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let same_series = matches!(&self.current, Some(s) if s.name == name && s.tags == tags);
+            if !same_series {
+                completed.extend(self.current.take());
+            }
+            let current = self.current.get_or_insert_with(|| Series {
+                name: name.to_string(),
+                tags,
+                columns: self.columns.clone(),
+                values: vec![],
+                partial: false,
+            });
+
+            if current.values.len() >= self.max_rows {
+                completed.push(Series {
+                    name: current.name.clone(),
+                    tags: current.tags.clone(),
+                    columns: current.columns.clone(),
+                    values: std::mem::take(&mut current.values),
+                    partial: true,
+                });
+            }
+            current.values.push(row_values);
+        }
+
+        Ok(completed)
+    }
+
+    /// Return the series of the last rows read, if any.
+    pub(crate) fn finish(self) -> Option<Series> {
+        self.current
+    }
+}
+
+/// Read row `row` of the string array `array`, returning an empty string for
+/// nulls.
+pub(crate) fn string_value(array: &ArrayRef, row: usize) -> &str {
+    let array = array.as_string::<i32>();
+    if array.is_null(row) {
+        ""
+    } else {
+        array.value(row)
+    }
+}
+
+/// Convert row `row` of `array` into a JSON value.
+pub(crate) fn value(
+    array: &ArrayRef,
+    row: usize,
+    epoch: Option<Epoch>,
+) -> Result<Value, ArrowError> {
+    if array.is_null(row) {
+        return Ok(Value::Null);
+    }
+
+    Ok(match array.data_type() {
+        DataType::Timestamp(TimeUnit::Nanosecond, _) => {
+            let ts = array.as_primitive::<TimestampNanosecondType>().value(row);
+            match epoch {
+                Some(epoch) => Value::from(ts / epoch.nanos()),
+                None => Value::from(
+                    Utc.timestamp_nanos(ts)
+                        .to_rfc3339_opts(SecondsFormat::AutoSi, true),
+                ),
+            }
+        }
+        DataType::Float64 => Value::from(array.as_primitive::<Float64Type>().value(row)),
+        DataType::Int64 => Value::from(array.as_primitive::<Int64Type>().value(row)),
+        DataType::UInt64 => Value::from(array.as_primitive::<UInt64Type>().value(row)),
+        DataType::Boolean => Value::from(array.as_boolean().value(row)),
+        DataType::Utf8 => Value::from(array.as_string::<i32>().value(row)),
+        _ => Value::from(
+            ArrayFormatter::try_new(array.as_ref(), &FormatOptions::default())?
+                .value(row)
+                .to_string(),
+        ),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, sync::Arc};
+
+    use arrow::{
+        array::{DictionaryArray, Float64Array, StringArray, TimestampNanosecondArray},
+        datatypes::{Field, Int32Type},
+    };
+    use serde_json::json;
+
+    use super::*;
+
+    fn series_from_batches(batches: &[RecordBatch], epoch: Option<Epoch>) -> Vec<Series> {
+        let Some(batch) = batches.first() else {
+            return vec![];
+        };
+        let mut builder = SeriesBuilder::try_new(&batch.schema(), epoch, usize::MAX).unwrap();
+        let mut series = vec![];
+        for batch in batches {
+            series.extend(builder.push(batch).unwrap());
+        }
+        series.extend(builder.finish());
+        series
+    }
+
+    fn batch(is_projected: bool) -> RecordBatch {
+        let metadata = InfluxQlMetadata {
+            measurement_column_index: 0,
+            tag_key_columns: vec![TagKeyColumn {
+                tag_key: "host".to_string(),
+                column_index: 2,
+                is_projected,
+            }],
+        };
+        let schema = Arc::new(Schema::new_with_metadata(
+            vec![
+                Field::new(INFLUXQL_MEASUREMENT_COLUMN_NAME, DataType::Utf8, false),
+                Field::new(
+                    "time",
+                    DataType::Timestamp(TimeUnit::Nanosecond, None),
+                    false,
+                ),
+                Field::new(
+                    "host",
+                    DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8)),
+                    true,
+                ),
+                Field::new("usage", DataType::Float64, true),
+            ],
+            HashMap::from([(
+                INFLUXQL_METADATA_KEY.to_owned(),
+                serde_json::to_string(&metadata).unwrap(),
+            )]),
+        ));
+
+        RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(StringArray::from(vec!["cpu", "cpu", "cpu", "mem"])),
+                Arc::new(TimestampNanosecondArray::from(vec![
+                    1_000_000_000,
+                    2_000_000_000,
+                    1_500_000_000,
+                    1_000_000_000,
+                ])),
+                Arc::new(
+                    vec![Some("a"), Some("a"), Some("b"), None]
+                        .into_iter()
+                        .collect::<DictionaryArray<Int32Type>>(),
+                ),
+                Arc::new(Float64Array::from(vec![
+                    Some(1.5),
+                    None,
+                    Some(3.0),
+                    Some(4.0),
+                ])),
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_series_grouped_by_tag() {
+        let series = series_from_batches(&[batch(false)], None);
+        let got = serde_json::to_value(series).unwrap();
+
+        assert_eq!(
+            got,
+            json!([
+                {
+                    "name": "cpu",
+                    "tags": {"host": "a"},
+                    "columns": ["time", "usage"],
+                    "values": [
+                        ["1970-01-01T00:00:01Z", 1.5],
+                        ["1970-01-01T00:00:02Z", null],
+                    ],
+                },
+                {
+                    "name": "cpu",
+                    "tags": {"host": "b"},
+                    "columns": ["time", "usage"],
+                    "values": [["1970-01-01T00:00:01.500Z", 3.0]],
+                },
+                {
+                    "name": "mem",
+                    "tags": {"host": ""},
+                    "columns": ["time", "usage"],
+                    "values": [["1970-01-01T00:00:01Z", 4.0]],
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_series_projected_tag_and_epoch() {
+        let series = series_from_batches(&[batch(true)], Some(Epoch::Millisecond));
+
+        assert_eq!(series.len(), 3);
+        assert_eq!(series[0].columns, ["time", "host", "usage"]);
+        assert_eq!(
+            series[0].values,
+            [
+                vec![json!(1000), json!("a"), json!(1.5)],
+                vec![json!(2000), json!("a"), Value::Null],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_series_without_metadata() {
+        let schema = Arc::new(Schema::new(vec![Field::new("name", DataType::Utf8, false)]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![Arc::new(StringArray::from(vec!["cpu", "mem"]))],
+        )
+        .unwrap();
+
+        let series = series_from_batches(&[batch], None);
+        assert_eq!(series.len(), 1);
+        assert_eq!(series[0].name, "");
+        assert!(series[0].tags.is_empty());
+        assert_eq!(series[0].values, [vec![json!("cpu")], vec![json!("mem")]]);
+    }
+
+    #[test]
+    fn test_no_output() {
+        assert!(series_from_batches(&[], None).is_empty());
+    }
+
+    #[test]
+    fn test_series_max_rows() {
+        let batch = batch(false);
+        let mut builder = SeriesBuilder::try_new(&batch.schema(), None, 1).unwrap();
+
+        // The first series of two rows is returned in two parts, and the
+        // last series is only returned once the output is finished.
+        let series = builder.push(&batch).unwrap();
+        assert_eq!(
+            series
+                .iter()
+                .map(|s| (s.name.as_str(), s.values.len(), s.partial))
+                .collect::<Vec<_>>(),
+            [("cpu", 1, true), ("cpu", 1, false), ("cpu", 1, false)]
+        );
+        assert_eq!(series[0].tags, series[1].tags);
+        assert_ne!(series[1].tags, series[2].tags);
+
+        let last = builder.finish().unwrap();
+        assert_eq!(last.name, "mem");
+        assert!(!last.partial);
+
+        // Rows of a series continue across batches.
+        let mut builder = SeriesBuilder::try_new(&batch.schema(), None, usize::MAX).unwrap();
+        assert_eq!(builder.push(&batch.slice(0, 1)).unwrap().len(), 0);
+        let series = builder.push(&batch.slice(1, 2)).unwrap();
+        assert_eq!(series.len(), 1);
+        assert_eq!(series[0].values.len(), 2);
+    }
+}
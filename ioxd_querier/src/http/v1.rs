@@ -0,0 +1,1422 @@
+//! Handling of HTTP requests that conform to the [V1 Query API].
+//!
+//! [V1 Query API]:
+//!     https://docs.influxdata.com/influxdb/v1.8/tools/api/#query-http-endpoint
+
+use std::{
+    collections::{HashSet, VecDeque},
+    sync::Arc,
+};
+
+use authz::{extract_token, http::AuthorizationHeaderExtension, Action, Authorizer};
+use data_types::NamespaceName;
+use datafusion::{error::DataFusionError, physical_plan::SendableRecordBatchStream};
+use futures::{stream, Stream, StreamExt};
+use hyper::{
+    header::{ACCEPT, CONTENT_TYPE},
+    Body, Method, Request, Response, StatusCode,
+};
+use influxdb_influxql_parser::{parse_statements, select::IntoMeasurement, statement::Statement};
+use iox_query::{QueryCompletedToken, QueryNamespace};
+use iox_query_influxql::{
+    database::{statement_namespaces, StatementNamespaces},
+    frontend::planner::NamespaceProvider,
+};
+use observability_deps::tracing::*;
+use rollup::RollupWriter;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use service_common::{
+    namespaces::AuthorizedNamespaces,
+    planner::{Planner, StatementParams},
+    QueryNamespaceProvider, QueryPermit,
+};
+use trace::{ctx::SpanContext, span::SpanExt};
+
+use super::{
+    authorize,
+    database::{namespace_name, DatabaseRef},
+    into, msgpack, read_body,
+    series::{Epoch, Series, SeriesBuilder},
+    Error,
+};
+
+/// The number of rows per chunk of a chunked response, unless set by the
+/// `chunk_size` parameter.
+const DEFAULT_CHUNK_SIZE: usize = 10_000;
+
+/// Parameters of a V1 query request.
+///
+/// These are read from the URL query string, and for `POST` requests from a
+/// `application/x-www-form-urlencoded` body.
+#[derive(Debug, Default, Deserialize)]
+struct QueryParams {
+    db: Option<String>,
+    rp: Option<String>,
+    q: Option<String>,
+    /// The values of the bind parameters of the query, as a JSON object.
+    params: Option<String>,
+    epoch: Option<Epoch>,
+    #[serde(default)]
+    chunked: bool,
+    chunk_size: Option<usize>,
+
+    // `u` is accepted but ignored, the `p` parameter is treated as a token.
+    #[serde(rename(deserialize = "p"))]
+    password: Option<String>,
+}
+
+impl QueryParams {
+    async fn try_from_request(req: Request<Body>, max_request_bytes: usize) -> Result<Self, Error> {
+        let mut pairs: Vec<(String, String)> = match req.uri().query() {
+            Some(query) => serde_urlencoded::from_str(query)?,
+            None => vec![],
+        };
+
+        let is_form = req
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.starts_with("application/x-www-form-urlencoded"))
+            .unwrap_or_default();
+
+        if req.method() == Method::POST && is_form {
+            let body = read_body(req, max_request_bytes).await?;
+            let form: Vec<(String, String)> = serde_urlencoded::from_bytes(&body)?;
+
+            // Parameters in the body take precedence over those in the URL.
+            pairs.retain(|(k, _)| !form.iter().any(|(form_k, _)| form_k == k));
+            pairs.extend(form);
+        }
+
+        // Drop repeated parameters, keeping the last occurrence.
+        let mut seen = Vec::with_capacity(pairs.len());
+        pairs.reverse();
+        pairs.retain(|(k, _)| {
+            let first = !seen.contains(k);
+            seen.push(k.clone());
+            first
+        });
+
+        let query = serde_urlencoded::to_string(&pairs).expect("encode decoded params");
+        Ok(serde_urlencoded::from_str(&query)?)
+    }
+
+    /// The database and retention policy used by statements that do not
+    /// specify their own.
+    fn default_database(&self) -> DatabaseRef {
+        DatabaseRef {
+            db: self.db.clone(),
+            rp: self.rp.clone(),
+        }
+    }
+}
+
+/// A statement of a query request, with the namespaces it operates on.
+#[derive(Debug)]
+struct RequestStatement {
+    statement: Statement,
+    /// The namespaces read by the statement.
+    namespaces: StatementNamespaces,
+    /// The namespace created by a `CREATE DATABASE` statement.
+    create: Option<NamespaceName<'static>>,
+    /// The namespace and measurement the output of a `SELECT ... INTO`
+    /// statement is written to.
+    into: Option<(NamespaceName<'static>, IntoMeasurement)>,
+}
+
+impl RequestStatement {
+    /// Resolve the namespaces `statement` operates on, where `namespace` is
+    /// the namespace of the `default` database of the request, if any.
+    ///
+    /// The `INTO` clause of a `SELECT ... INTO` statement is removed, as its
+    /// output is written by the request handler.
+    fn resolve(
+        mut statement: Statement,
+        default: &DatabaseRef,
+        namespace: Option<&NamespaceName<'static>>,
+    ) -> Result<Self, Error> {
+        let create = match &statement {
+            Statement::CreateDatabase(s) => Some(namespace_name(
+                Some(s.name.as_str()),
+                s.retention_name.as_ref().map(|rp| rp.as_str()),
+            )?),
+            _ => None,
+        };
+
+        let into = match &mut statement {
+            Statement::Select(s) => match s.into.take() {
+                Some(into) => {
+                    let target = DatabaseRef {
+                        db: into.database.map(|db| db.as_str().to_owned()),
+                        rp: into.retention_policy.map(|rp| rp.as_str().to_owned()),
+                    }
+                    .or(default)
+                    .namespace()?;
+                    Some((target, into.measurement))
+                }
+                None => None,
+            },
+            _ => None,
+        };
+
+        let namespaces = statement_namespaces(&statement, namespace.map(|ns| ns.as_str()));
+
+        Ok(Self {
+            statement,
+            namespaces,
+            create,
+            into,
+        })
+    }
+
+    /// The namespaces read by the statement, where `namespace` is the
+    /// namespace of the default database of the request.
+    fn read_namespaces(
+        &self,
+        namespace: Option<&NamespaceName<'static>>,
+    ) -> Result<Vec<NamespaceName<'static>>, Error> {
+        let mut read = self
+            .namespaces
+            .named
+            .iter()
+            .map(|ns| NamespaceName::new(ns.clone()).map_err(Error::from))
+            .collect::<Result<Vec<_>, _>>()?;
+        if self.namespaces.default {
+            read.push(namespace.cloned().ok_or(Error::MissingDatabase)?);
+        }
+        Ok(read)
+    }
+}
+
+/// The encoding of a query response, selected by the `Accept` header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Json,
+    Csv,
+    MsgPack,
+}
+
+impl Format {
+    fn from_request(req: &Request<Body>) -> Self {
+        req.headers()
+            .get(ACCEPT)
+            .and_then(|v| v.to_str().ok())
+            .into_iter()
+            .flat_map(|v| v.split(','))
+            .find_map(
+                |media_type| match media_type.split(';').next().unwrap_or_default().trim() {
+                    "application/csv" | "text/csv" => Some(Self::Csv),
+                    "application/x-msgpack" => Some(Self::MsgPack),
+                    "application/json" => Some(Self::Json),
+                    _ => None,
+                },
+            )
+            .unwrap_or(Self::Json)
+    }
+
+    fn content_type(&self) -> &'static str {
+        match self {
+            Self::Json => "application/json",
+            Self::Csv => "application/csv",
+            Self::MsgPack => "application/x-msgpack",
+        }
+    }
+}
+
+/// The response to a query request, or a chunk of it.
+#[derive(Debug, Serialize)]
+struct QueryResponse {
+    results: Vec<StatementResult>,
+}
+
+/// The output of a single statement of a query request.
+#[derive(Debug, Default, Serialize)]
+struct StatementResult {
+    statement_id: usize,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    series: Vec<Series>,
+    /// Set when further chunks of this statement follow.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    partial: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl StatementResult {
+    /// The result of the statement `statement_id` that failed with `error`.
+    fn failed(statement_id: usize, error: String) -> Self {
+        Self {
+            statement_id,
+            error: Some(error),
+            ..Default::default()
+        }
+    }
+}
+
+/// Serve a V1 query request against the namespaces of `server`.
+pub(super) async fn query<S>(
+    server: &Arc<S>,
+    authz: &Option<Arc<dyn Authorizer>>,
+    into_writer: &Option<Arc<dyn RollupWriter>>,
+    max_request_bytes: usize,
+    req: Request<Body>,
+) -> Result<Response<Body>, Error>
+where
+    S: QueryNamespaceProvider,
+{
+    let span_ctx: Option<SpanContext> = req.extensions().get().cloned();
+    let method = req.method().clone();
+    let format = Format::from_request(&req);
+    let header_token = extract_token(
+        req.extensions()
+            .get::<AuthorizationHeaderExtension>()
+            .and_then(|v| v.as_ref()),
+    );
+
+    let params = QueryParams::try_from_request(req, max_request_bytes).await?;
+    let query = params
+        .q
+        .as_deref()
+        .filter(|q| !q.trim().is_empty())
+        .ok_or(Error::MissingQuery)?;
+
+    let token = header_token.or_else(|| params.password.clone().map(String::into_bytes));
+
+    let statements = parse_statements(query).map_err(|e| Error::ParseQuery(e.to_string()))?;
+    let bind_params: StatementParams = match params.params.as_deref() {
+        Some(p) if !p.trim().is_empty() => {
+            serde_json::from_str(p).map_err(Error::InvalidBindParams)?
+        }
+        _ => StatementParams::default(),
+    };
+    let default = params.default_database();
+    let namespace = match default.db.as_deref() {
+        Some(db) if !db.is_empty() => Some(default.namespace()?),
+        _ => None,
+    };
+    let statements = statements
+        .into_iter()
+        .map(|statement| RequestStatement::resolve(statement, &default, namespace.as_ref()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Authorize all statements, and check the namespaces they read exist,
+    // before running any of them. Namespaces created by an earlier statement
+    // of the request are looked up when they are queried.
+    //
+    // Like InfluxDB, only read-only statements may be sent with GET, so that
+    // following a link cannot create a database or write data.
+    let mut checked = HashSet::new();
+    let mut created = HashSet::new();
+    for statement in &statements {
+        if let Some((target, _)) = &statement.into {
+            if into_writer.is_none() {
+                return Err(Error::IntoNotEnabled);
+            }
+            if method != Method::POST {
+                return Err(Error::RequiresPost("SELECT ... INTO"));
+            }
+            authorize(authz, token.clone(), target, Action::Write).await?;
+        }
+
+        if let Some(create) = &statement.create {
+            if method != Method::POST {
+                return Err(Error::RequiresPost("CREATE DATABASE"));
+            }
+            authorize(authz, token.clone(), create, Action::Create).await?;
+            created.insert(create.to_string());
+        }
+
+        for read in statement.read_namespaces(namespace.as_ref())? {
+            authorize(authz, token.clone(), &read, Action::Read).await?;
+            if !created.contains(read.as_str()) && checked.insert(read.to_string()) {
+                server
+                    .db(&read, span_ctx.child_span("get namespace"), false)
+                    .await
+                    .ok_or_else(|| Error::NamespaceNotFound(read.to_string()))?;
+            }
+        }
+    }
+
+    info!(db = ?params.db, %query, "V1 query request");
+
+    let request = QueryRequest {
+        server: Arc::clone(server),
+        namespace,
+        namespaces: Arc::new(AuthorizedNamespaces::new(
+            Arc::clone(server),
+            authz.clone(),
+            token,
+            span_ctx.clone(),
+        )),
+        into_writer: into_writer.clone(),
+        bind_params,
+        span_ctx,
+        epoch: params.epoch,
+    };
+
+    if params.chunked {
+        let chunk_size = params
+            .chunk_size
+            .filter(|n| *n > 0)
+            .unwrap_or(DEFAULT_CHUNK_SIZE);
+        let output = ChunkedOutput {
+            request,
+            statements: statements.into_iter().enumerate(),
+            chunk_size,
+            encoder: Encoder::new(format, true),
+            running: None,
+            chunks: VecDeque::new(),
+            done: false,
+        };
+
+        return Ok(Response::builder()
+            .status(StatusCode::OK)
+            .header(CONTENT_TYPE, format.content_type())
+            .body(Body::wrap_stream(output.into_stream()))
+            .expect("valid response"));
+    }
+
+    let mut results = Vec::with_capacity(statements.len());
+    for (statement_id, statement) in statements.iter().enumerate() {
+        let result = request.execute(statement_id, statement).await?;
+
+        // Like InfluxDB, stop at the first failing statement.
+        let failed = result.error.is_some();
+        results.push(result);
+        if failed {
+            break;
+        }
+    }
+
+    let body = Encoder::new(format, false).encode(&QueryResponse { results })?;
+
+    Ok(Response::builder()
+        .status(StatusCode::OK)
+        .header(CONTENT_TYPE, format.content_type())
+        .body(Body::from(body))
+        .expect("valid response"))
+}
+
+/// Where the output of a `SELECT ... INTO` statement is written.
+#[derive(Debug, Clone)]
+struct IntoTarget {
+    writer: Arc<dyn RollupWriter>,
+    namespace: String,
+    measurement: IntoMeasurement,
+}
+
+/// The state shared by the statements of a query request.
+struct QueryRequest<S> {
+    server: Arc<S>,
+    /// The namespace of the default database of the request, if any.
+    namespace: Option<NamespaceName<'static>>,
+    /// The namespaces the request may access, for statements reading or
+    /// creating other databases.
+    namespaces: Arc<dyn NamespaceProvider>,
+    /// The writer of the output of `SELECT ... INTO` statements, if enabled.
+    into_writer: Option<Arc<dyn RollupWriter>>,
+    bind_params: StatementParams,
+    span_ctx: Option<SpanContext>,
+    epoch: Option<Epoch>,
+}
+
+impl<S> QueryRequest<S>
+where
+    S: QueryNamespaceProvider,
+{
+    /// Plan and run `statement`, returning its output as series.
+    ///
+    /// Authorization failures for the databases referenced by the statement
+    /// fail the request.
+    async fn execute(
+        &self,
+        statement_id: usize,
+        statement: &RequestStatement,
+    ) -> Result<StatementResult, Error> {
+        let mut output = match self.start(statement_id, statement, usize::MAX).await? {
+            Started::Running(output) => output,
+            Started::Failed(result) => return Ok(result),
+        };
+
+        let mut series = vec![];
+        while let Some(next) = output.next_series().await {
+            match next {
+                Ok(next) => series.extend(next),
+                Err(error) => return Ok(StatementResult::failed(statement_id, error)),
+            }
+        }
+        series.extend(output.finish());
+
+        Ok(StatementResult {
+            statement_id,
+            series,
+            ..Default::default()
+        })
+    }
+
+    /// Plan `statement` and start running it, returning series of at most
+    /// `max_rows` rows from its output.
+    async fn start(
+        &self,
+        statement_id: usize,
+        statement: &RequestStatement,
+        max_rows: usize,
+    ) -> Result<Started, Error> {
+        // Statements reading the default database are planned and logged
+        // against its namespace, and count towards its query concurrency.
+        let db = match &self.namespace {
+            Some(namespace) if statement.namespaces.default => {
+                match self
+                    .server
+                    .db(namespace, self.span_ctx.child_span("get namespace"), false)
+                    .await
+                {
+                    Some(db) => Some((namespace, db)),
+                    None => {
+                        return Ok(Started::Failed(StatementResult::failed(
+                            statement_id,
+                            Error::NamespaceNotFound(namespace.to_string()).to_string(),
+                        )))
+                    }
+                }
+            }
+            _ => None,
+        };
+
+        let permit = match &db {
+            Some((namespace, _)) => Some(
+                self.server
+                    .acquire_semaphore(
+                        namespace,
+                        self.span_ctx.child_span("query rate limit semaphore"),
+                    )
+                    .await,
+            ),
+            None => None,
+        };
+
+        let text = statement.statement.to_string();
+        let ctx = match &db {
+            Some((_, db)) => db.new_query_context(self.span_ctx.clone()),
+            None => self.server.new_query_context(self.span_ctx.clone()),
+        };
+        let completed = db
+            .as_ref()
+            .map(|(_, db)| db.record_query(&ctx, "influxql", Box::new(text.clone())));
+
+        let batches = match Planner::new(&ctx)
+            .influxql_with_namespaces(
+                text,
+                self.bind_params.clone(),
+                self.namespace.as_ref().map(|ns| ns.to_string()),
+                Arc::clone(&self.namespaces),
+            )
+            .await
+        {
+            Ok(plan) => ctx.execute_stream(plan).await,
+            Err(DataFusionError::External(e)) if e.is::<authz::Error>() => {
+                let e = e.downcast::<authz::Error>().expect("checked above");
+                return Err((*e).into());
+            }
+            Err(e) => Err(e),
+        };
+
+        Ok(match batches {
+            Ok(batches) => Started::Running(StatementOutput {
+                statement_id,
+                batches,
+                into: statement
+                    .into
+                    .as_ref()
+                    .map(|(namespace, measurement)| IntoTarget {
+                        writer: Arc::clone(self.into_writer.as_ref().expect("checked by query")),
+                        namespace: namespace.to_string(),
+                        measurement: measurement.clone(),
+                    }),
+                written: 0,
+                series: None,
+                max_rows,
+                epoch: self.epoch,
+                completed,
+                _permit: permit,
+            }),
+            Err(e) => Started::Failed(StatementResult::failed(statement_id, e.to_string())),
+        })
+    }
+}
+
+/// A statement of a query request that was started.
+enum Started {
+    /// The statement is running.
+    Running(StatementOutput),
+    /// The statement failed before producing any output.
+    Failed(StatementResult),
+}
+
+/// The output of a running statement, read one batch at a time.
+///
+/// The query permit of the statement is held until its output has been read.
+struct StatementOutput {
+    statement_id: usize,
+    batches: SendableRecordBatchStream,
+    /// Where the output is written instead of being returned, for
+    /// `SELECT ... INTO` statements.
+    into: Option<IntoTarget>,
+    /// The number of points written to `into` so far.
+    written: usize,
+    /// The series of the output, created from the schema of the first batch.
+    series: Option<SeriesBuilder>,
+    max_rows: usize,
+    epoch: Option<Epoch>,
+    completed: Option<QueryCompletedToken>,
+    _permit: Option<QueryPermit>,
+}
+
+impl StatementOutput {
+    /// Read the next batch of output, returning the series it completes, or
+    /// `None` once all output has been read.
+    ///
+    /// The output of `SELECT ... INTO` statements is written as it is read,
+    /// and only counted.
+    async fn next_series(&mut self) -> Option<Result<Vec<Series>, String>> {
+        let batch = match self.batches.next().await? {
+            Ok(batch) => batch,
+            Err(e) => return Some(Err(e.to_string())),
+        };
+
+        if let Some(into) = &self.into {
+            let written = into::write_into(
+                into.writer.as_ref(),
+                &into.namespace,
+                &into.measurement,
+                &[batch],
+            )
+            .await;
+            return Some(match written {
+                Ok(written) => {
+                    self.written += written;
+                    Ok(vec![])
+                }
+                Err(e) => Err(e.to_string()),
+            });
+        }
+
+        if self.series.is_none() {
+            match SeriesBuilder::try_new(&batch.schema(), self.epoch, self.max_rows) {
+                Ok(builder) => self.series = Some(builder),
+                Err(e) => return Some(Err(e.to_string())),
+            }
+        }
+        let builder = self.series.as_mut().expect("created above");
+        Some(builder.push(&batch).map_err(|e| e.to_string()))
+    }
+
+    /// Complete the statement once all its output has been read, returning
+    /// its remaining series.
+    fn finish(mut self) -> Vec<Series> {
+        if let Some(completed) = &mut self.completed {
+            completed.set_success();
+        }
+
+        match &self.into {
+            Some(_) => vec![into::written_series(self.written, self.epoch)],
+            None => self
+                .series
+                .and_then(SeriesBuilder::finish)
+                .into_iter()
+                .collect(),
+        }
+    }
+}
+
+/// The state of a chunked query response.
+///
+/// The statements of the request are run one after another, and their output
+/// is encoded as it is read, so that at most a batch of output and a chunk of
+/// `chunk_size` rows of a single series are held at a time.
+struct ChunkedOutput<S> {
+    request: QueryRequest<S>,
+    statements: std::iter::Enumerate<std::vec::IntoIter<RequestStatement>>,
+    chunk_size: usize,
+    encoder: Encoder,
+    /// The statement whose output is being read, if any.
+    running: Option<StatementOutput>,
+    /// The chunks read but not yet sent.
+    chunks: VecDeque<StatementResult>,
+    /// Set once a statement failed, or the response could not be encoded.
+    done: bool,
+}
+
+impl<S> ChunkedOutput<S>
+where
+    S: QueryNamespaceProvider,
+{
+    /// Encode the output of the statements as a stream of chunks.
+    ///
+    /// Errors running a statement are reported as the final chunk, as the
+    /// status of the response has already been sent.
+    fn into_stream(self) -> impl Stream<Item = Result<Vec<u8>, Error>> {
+        stream::unfold(self, |mut state| async move {
+            let chunk = state.next_chunk().await?;
+            let encoded = state.encoder.encode(&QueryResponse {
+                results: vec![chunk],
+            });
+            if encoded.is_err() {
+                state.done = true;
+                state.chunks.clear();
+            }
+
+            Some((encoded, state))
+        })
+    }
+
+    /// Return the next chunk of the response, or `None` once all statements
+    /// have run.
+    async fn next_chunk(&mut self) -> Option<StatementResult> {
+        loop {
+            if let Some(chunk) = self.chunks.pop_front() {
+                return Some(chunk);
+            }
+            if self.done {
+                return None;
+            }
+
+            let Some(running) = &mut self.running else {
+                let (statement_id, statement) = self.statements.next()?;
+                match self
+                    .request
+                    .start(statement_id, &statement, self.chunk_size)
+                    .await
+                {
+                    Ok(Started::Running(output)) => self.running = Some(output),
+                    Ok(Started::Failed(result)) => {
+                        self.done = true;
+                        return Some(result);
+                    }
+                    Err(e) => {
+                        self.done = true;
+                        return Some(StatementResult::failed(statement_id, e.to_string()));
+                    }
+                }
+                continue;
+            };
+
+            // Like InfluxDB, mark all but the last chunk of a statement as
+            // partial.
+            let statement_id = running.statement_id;
+            let chunk = |series: Series| StatementResult {
+                statement_id,
+                series: vec![series],
+                partial: true,
+                error: None,
+            };
+            match running.next_series().await {
+                Some(Ok(series)) => self.chunks.extend(series.into_iter().map(chunk)),
+                Some(Err(error)) => {
+                    // Like InfluxDB, stop at the first failing statement.
+                    self.running = None;
+                    self.done = true;
+                    return Some(StatementResult::failed(statement_id, error));
+                }
+                None => {
+                    let output = self.running.take().expect("checked above");
+                    let mut last = output.finish().into_iter().map(chunk).collect::<Vec<_>>();
+                    match last.last_mut() {
+                        Some(chunk) => chunk.partial = false,
+                        None => last.push(StatementResult {
+                            statement_id,
+                            ..Default::default()
+                        }),
+                    }
+                    self.chunks.extend(last);
+                }
+            }
+        }
+    }
+}
+
+/// Encodes the responses of a query request in a [`Format`].
+///
+/// For chunked responses, each chunk is its own [`QueryResponse`]; JSON chunks
+/// are newline-delimited, and CSV rows of all chunks form a single table.
+#[derive(Debug)]
+struct Encoder {
+    format: Format,
+    chunked: bool,
+    /// The columns of the last CSV header row written.
+    csv_columns: Option<Vec<String>>,
+}
+
+impl Encoder {
+    fn new(format: Format, chunked: bool) -> Self {
+        Self {
+            format,
+            chunked,
+            csv_columns: None,
+        }
+    }
+
+    /// Encode `response`, a whole response or a chunk of it.
+    fn encode(&mut self, response: &QueryResponse) -> Result<Vec<u8>, Error> {
+        let mut buf = vec![];
+        match self.format {
+            Format::Json => {
+                serde_json::to_writer(&mut buf, response).map_err(|e| Error::Encode(e.into()))?;
+                if self.chunked {
+                    buf.push(b'\n');
+                }
+            }
+            Format::MsgPack => {
+                let value = serde_json::to_value(response).map_err(|e| Error::Encode(e.into()))?;
+                msgpack::encode(&value, &mut buf);
+            }
+            Format::Csv => {
+                let mut writer = csv::WriterBuilder::new()
+                    .flexible(true)
+                    .from_writer(&mut buf);
+                write_csv(&mut writer, response, &mut self.csv_columns)
+                    .map_err(|e| Error::Encode(e.into()))?;
+                writer.flush().map_err(|e| Error::Encode(e.into()))?;
+            }
+        }
+        Ok(buf)
+    }
+}
+
+/// Write the series of `response` as CSV, with a header row preceding the
+/// first series and any series whose columns differ from `columns`, the
+/// columns of the previous one.
+fn write_csv<W: std::io::Write>(
+    writer: &mut csv::Writer<W>,
+    response: &QueryResponse,
+    columns: &mut Option<Vec<String>>,
+) -> Result<(), csv::Error> {
+    for result in &response.results {
+        if let Some(error) = &result.error {
+            writer.write_record(["error"])?;
+            writer.write_record([error])?;
+            *columns = None;
+            continue;
+        }
+
+        for series in &result.series {
+            if columns.as_deref() != Some(series.columns.as_slice()) {
+                writer.write_record(
+                    ["name", "tags"]
+                        .into_iter()
+                        .chain(series.columns.iter().map(String::as_str)),
+                )?;
+                *columns = Some(series.columns.clone());
+            }
+
+            let tags = series
+                .tags
+                .iter()
+                .map(|(k, v)| format!("{k}={v}"))
+                .collect::<Vec<_>>()
+                .join(",");
+            for row in &series.values {
+                writer.write_record(
+                    [series.name.clone(), tags.clone()]
+                        .into_iter()
+                        .chain(row.iter().map(csv_value)),
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn csv_value(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        v => v.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use async_trait::async_trait;
+    use authz::Permission;
+    use hyper::header::HeaderValue;
+    use iox_query::test::TestChunk;
+    use serde_json::json;
+    use service_common::test_util::TestDatabaseStore;
+
+    use super::*;
+    use crate::http::HttpDelegate;
+
+    #[derive(Debug)]
+    struct MockAuthorizer;
+
+    #[async_trait]
+    impl Authorizer for MockAuthorizer {
+        async fn permissions(
+            &self,
+            token: Option<Vec<u8>>,
+            perms: &[Permission],
+        ) -> Result<Vec<Permission>, authz::Error> {
+            match token.as_deref() {
+                Some(b"GOOD") => Ok(perms.to_vec()),
+                Some(_) => Err(authz::Error::Forbidden),
+                None => Err(authz::Error::NoToken),
+            }
+        }
+    }
+
+    const MAX_REQUEST_BYTES: usize = 1024;
+
+    async fn delegate(authz: Option<Arc<dyn Authorizer>>) -> HttpDelegate<TestDatabaseStore> {
+        let store = Arc::new(TestDatabaseStore::default());
+        store.db_or_create("bananas").await.add_chunk(
+            "p1",
+            Arc::new(
+                TestChunk::new("cpu")
+                    .with_time_column()
+                    .with_tag_column("tag1")
+                    .with_i64_field_column("field_int")
+                    .with_three_rows_of_data(),
+            ),
+        );
+        HttpDelegate::new(store, authz, MAX_REQUEST_BYTES)
+    }
+
+    async fn body_json(response: Response<Body>) -> Value {
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_query_get() {
+        let delegate = delegate(None).await;
+        let req = Request::builder()
+            .uri("/query?db=bananas&epoch=ns&q=SELECT+field_int+FROM+cpu")
+            .body(Body::empty())
+            .unwrap();
+
+        let response = delegate.route(req).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get(CONTENT_TYPE).unwrap(),
+            "application/json"
+        );
+        assert_eq!(
+            body_json(response).await,
+            json!({"results": [{
+                "statement_id": 0,
+                "series": [{
+                    "name": "cpu",
+                    "columns": ["time", "field_int"],
+                    "values": [[8000, 1000], [10000, 10], [20000, 70]],
+                }],
+            }]})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_query_bind_params() {
+        let delegate = delegate(None).await;
+        let req = Request::builder()
+            .uri("/query?db=bananas&epoch=ns&q=SELECT+field_int+FROM+cpu+WHERE+tag1+%3D+%24tag&params=%7B%22tag%22%3A%22UT%22%7D")
+            .body(Body::empty())
+            .unwrap();
+
+        let got = body_json(delegate.route(req).await.unwrap()).await;
+        assert_eq!(
+            got["results"][0]["series"][0]["values"],
+            json!([[20000, 70]])
+        );
+
+        // A missing value fails the statement.
+        let req = Request::builder()
+            .uri("/query?db=bananas&q=SELECT+field_int+FROM+cpu+WHERE+tag1+%3D+%24tag")
+            .body(Body::empty())
+            .unwrap();
+
+        let got = body_json(delegate.route(req).await.unwrap()).await;
+        assert!(got["results"][0]["error"]
+            .as_str()
+            .unwrap()
+            .contains("missing value for bind parameter $tag"));
+    }
+
+    #[tokio::test]
+    async fn test_query_post_form_with_statement_error() {
+        let delegate = delegate(None).await;
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri("/query?db=bananas")
+            .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(Body::from(
+                "q=SELECT+field_int+FROM+cpu+GROUP+BY+tag1%3B+SELECT+nope(field_int)+FROM+cpu%3B+SELECT+field_int+FROM+cpu&epoch=s",
+            ))
+            .unwrap();
+
+        let got = body_json(delegate.route(req).await.unwrap()).await;
+        let results = got["results"].as_array().unwrap();
+
+        // Execution stops at the failing statement.
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0]["series"].as_array().unwrap().len(), 3);
+        assert_eq!(results[0]["series"][0]["tags"], json!({"tag1": "UT"}));
+        assert_eq!(results[0]["series"][0]["values"], json!([[0, 70]]));
+        assert_eq!(results[1]["statement_id"], 1);
+        assert!(results[1]["error"].is_string());
+    }
+
+    #[tokio::test]
+    async fn test_query_post_form_too_large() {
+        let delegate = delegate(None).await;
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri("/query?db=bananas")
+            .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(Body::from(format!(
+                "q=SELECT+field_int+FROM+cpu{}",
+                "+".repeat(MAX_REQUEST_BYTES)
+            )))
+            .unwrap();
+
+        let err = delegate.route(req).await.unwrap_err();
+        assert_eq!(err.as_status_code(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[tokio::test]
+    async fn test_query_chunked() {
+        let delegate = delegate(None).await;
+        let req = Request::builder()
+            .uri("/query?db=bananas&epoch=ns&chunked=true&chunk_size=2&q=SELECT+field_int+FROM+cpu")
+            .body(Body::empty())
+            .unwrap();
+
+        let response = delegate.route(req).await.unwrap();
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let chunks = std::str::from_utf8(&body)
+            .unwrap()
+            .lines()
+            .map(|l| serde_json::from_str::<Value>(l).unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            chunks,
+            [
+                json!({"results": [{
+                    "statement_id": 0,
+                    "series": [{
+                        "name": "cpu",
+                        "columns": ["time", "field_int"],
+                        "values": [[8000, 1000], [10000, 10]],
+                        "partial": true,
+                    }],
+                    "partial": true,
+                }]}),
+                json!({"results": [{
+                    "statement_id": 0,
+                    "series": [{
+                        "name": "cpu",
+                        "columns": ["time", "field_int"],
+                        "values": [[20000, 70]],
+                    }],
+                }]}),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_query_csv() {
+        let delegate = delegate(None).await;
+        let req = Request::builder()
+            .uri("/query?db=bananas&epoch=ns&q=SELECT+field_int+FROM+cpu+GROUP+BY+tag1")
+            .header(ACCEPT, "application/csv")
+            .body(Body::empty())
+            .unwrap();
+
+        let response = delegate.route(req).await.unwrap();
+        assert_eq!(
+            response.headers().get(CONTENT_TYPE).unwrap(),
+            "application/csv"
+        );
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        assert_eq!(
+            std::str::from_utf8(&body).unwrap(),
+            "name,tags,time,field_int\n\
+             cpu,tag1=UT,20000,70\n\
+             cpu,tag1=VT,10000,10\n\
+             cpu,tag1=WA,8000,1000\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_query_chunked_csv() {
+        let delegate = delegate(None).await;
+        let req = Request::builder()
+            .uri("/query?db=bananas&epoch=ns&chunked=true&chunk_size=1&q=SELECT+field_int+FROM+cpu+GROUP+BY+tag1;SELECT+field_int+FROM+cpu")
+            .header(ACCEPT, "application/csv")
+            .body(Body::empty())
+            .unwrap();
+
+        // The rows of all chunks form a single table
+        let response = delegate.route(req).await.unwrap();
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        assert_eq!(
+            std::str::from_utf8(&body).unwrap(),
+            "name,tags,time,field_int\n\
+             cpu,tag1=UT,20000,70\n\
+             cpu,tag1=VT,10000,10\n\
+             cpu,tag1=WA,8000,1000\n\
+             cpu,,8000,1000\n\
+             cpu,,10000,10\n\
+             cpu,,20000,70\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_query_msgpack() {
+        let delegate = delegate(None).await;
+        let req = Request::builder()
+            .uri("/query?db=bananas&q=SHOW+MEASUREMENTS")
+            .header(ACCEPT, "application/x-msgpack")
+            .body(Body::empty())
+            .unwrap();
+
+        let response = delegate.route(req).await.unwrap();
+        assert_eq!(
+            response.headers().get(CONTENT_TYPE).unwrap(),
+            "application/x-msgpack"
+        );
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        // A map with a single "results" key.
+        assert_eq!(&body[..9], b"\x81\xa7results");
+    }
+
+    #[tokio::test]
+    async fn test_query_errors() {
+        let delegate = delegate(None).await;
+
+        let cases = [
+            ("/query?q=SHOW+MEASUREMENTS", StatusCode::BAD_REQUEST),
+            ("/query?db=bananas", StatusCode::BAD_REQUEST),
+            ("/query?db=bananas&q=SELEC", StatusCode::BAD_REQUEST),
+            (
+                "/query?db=bananas&q=SHOW+MEASUREMENTS&params=nope",
+                StatusCode::BAD_REQUEST,
+            ),
+            (
+                "/query?db=bananas&q=SHOW+MEASUREMENTS&epoch=x",
+                StatusCode::BAD_REQUEST,
+            ),
+            (
+                "/query?db=platanos&q=SHOW+MEASUREMENTS",
+                StatusCode::NOT_FOUND,
+            ),
+            ("/nope", StatusCode::NOT_FOUND),
+        ];
+        for (uri, want) in cases {
+            let req = Request::builder().uri(uri).body(Body::empty()).unwrap();
+            let err = delegate.route(req).await.unwrap_err();
+            assert_eq!(err.as_status_code(), want, "{uri}: {err}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_query_authz() {
+        let delegate = delegate(Some(Arc::new(MockAuthorizer))).await;
+        let uri = "/query?db=bananas&q=SHOW+MEASUREMENTS";
+
+        let req = Request::builder().uri(uri).body(Body::empty()).unwrap();
+        assert_eq!(
+            delegate.route(req).await.unwrap_err().as_status_code(),
+            StatusCode::UNAUTHORIZED
+        );
+
+        let req = Request::builder()
+            .uri(uri)
+            .extension(AuthorizationHeaderExtension::new(Some(
+                HeaderValue::from_static("Token BAD"),
+            )))
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(
+            delegate.route(req).await.unwrap_err().as_status_code(),
+            StatusCode::FORBIDDEN
+        );
+
+        let req = Request::builder()
+            .uri(uri)
+            .extension(AuthorizationHeaderExtension::new(Some(
+                HeaderValue::from_static("Token GOOD"),
+            )))
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(delegate.route(req).await.unwrap().status(), StatusCode::OK);
+
+        // The `p` parameter is accepted as a token.
+        let req = Request::builder()
+            .uri(format!("{uri}&u=user&p=GOOD"))
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(delegate.route(req).await.unwrap().status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_show_and_create_databases() {
+        let delegate = delegate(None).await;
+        let query = |q: &str| {
+            let req = Request::builder()
+                .method(Method::POST)
+                .uri(format!("/query?q={q}"))
+                .body(Body::empty())
+                .unwrap();
+            delegate.route(req)
+        };
+
+        let got = body_json(query("SHOW+DATABASES").await.unwrap()).await;
+        assert_eq!(
+            got,
+            json!({"results": [{
+                "statement_id": 0,
+                "series": [{
+                    "name": "databases",
+                    "columns": ["name"],
+                    "values": [["bananas"]],
+                }],
+            }]})
+        );
+
+        // A database created with a retention policy is stored in the
+        // namespace of that retention policy, and can be queried in the same
+        // request.
+        let got = body_json(
+            query("CREATE+DATABASE+apples+WITH+DURATION+1d+NAME+ripe%3B+SHOW+MEASUREMENTS+ON+apples.ripe%3B+SHOW+DATABASES")
+                .await
+                .unwrap(),
+        )
+        .await;
+        let results = got["results"].as_array().unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0], json!({"statement_id": 0}));
+        assert!(results[1]["error"].is_null());
+        assert_eq!(
+            results[2]["series"][0]["values"],
+            json!([["apples"], ["bananas"]])
+        );
+        assert!(delegate
+            .server
+            .db("apples/ripe", None, false)
+            .await
+            .is_some());
+
+        // A database can't be created with a GET request.
+        let req = Request::builder()
+            .uri("/query?q=CREATE+DATABASE+pears")
+            .body(Body::empty())
+            .unwrap();
+        let err = delegate.route(req).await.unwrap_err();
+        assert_eq!(err.as_status_code(), StatusCode::METHOD_NOT_ALLOWED);
+        assert!(delegate.server.db("pears", None, false).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_query_qualified_database() {
+        let delegate = delegate(None).await;
+        let query = |q: &str| {
+            let req = Request::builder()
+                .uri(format!("/query?epoch=ns&q={q}"))
+                .body(Body::empty())
+                .unwrap();
+            delegate.route(req)
+        };
+
+        let got = body_json(query("SELECT+field_int+FROM+bananas..cpu").await.unwrap()).await;
+        assert_eq!(
+            got["results"][0]["series"][0]["values"],
+            json!([[8000, 1000], [10000, 10], [20000, 70]])
+        );
+
+        let got = body_json(query("SHOW+MEASUREMENTS+ON+bananas").await.unwrap()).await;
+        assert_eq!(got["results"][0]["series"][0]["values"], json!([["cpu"]]));
+
+        let got = body_json(query("SHOW+TAG+KEYS+ON+bananas+FROM+cpu").await.unwrap()).await;
+        assert_eq!(got["results"][0]["series"][0]["values"], json!([["tag1"]]));
+
+        // A statement may not read a namespace that does not exist.
+        let err = query("SELECT+field_int+FROM+bananas..cpu,platanos..cpu")
+            .await
+            .unwrap_err();
+        assert_eq!(err.as_status_code(), StatusCode::NOT_FOUND);
+
+        let err = query("SHOW+MEASUREMENTS+ON+platanos").await.unwrap_err();
+        assert_eq!(err.as_status_code(), StatusCode::NOT_FOUND);
+
+        // A statement may read more than one namespace.
+        delegate.server.db_or_create("platanos").await.add_chunk(
+            "p1",
+            Arc::new(
+                TestChunk::new("cpu")
+                    .with_time_column()
+                    .with_tag_column("tag1")
+                    .with_i64_field_column("field_int")
+                    .with_three_rows_of_data(),
+            ),
+        );
+        let got = body_json(
+            query("SELECT+field_int+FROM+bananas..cpu,platanos..cpu")
+                .await
+                .unwrap(),
+        )
+        .await;
+        let series = got["results"][0]["series"].as_array().unwrap();
+        assert_eq!(series.len(), 1);
+        assert_eq!(series[0]["name"], "cpu");
+        assert_eq!(series[0]["values"].as_array().unwrap().len(), 6);
+
+        let got = body_json(
+            query("SHOW+MEASUREMENTS+ON+bananas%3BSHOW+MEASUREMENTS+ON+platanos")
+                .await
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(got["results"][1]["series"][0]["values"], json!([["cpu"]]));
+    }
+
+    #[tokio::test]
+    async fn test_databases_authz() {
+        let delegate = delegate(Some(Arc::new(MockAuthorizer))).await;
+        let query = |q: &str, token: &'static str| {
+            let req = Request::builder()
+                .method(Method::POST)
+                .uri(format!("/query?q={q}"))
+                .extension(AuthorizationHeaderExtension::new(Some(
+                    HeaderValue::from_static(token),
+                )))
+                .body(Body::empty())
+                .unwrap();
+            delegate.route(req)
+        };
+
+        let got = body_json(query("SHOW+DATABASES", "Token GOOD").await.unwrap()).await;
+        assert_eq!(
+            got["results"][0]["series"][0]["values"],
+            json!([["bananas"]])
+        );
+
+        // Databases that cannot be read are not listed.
+        let got = body_json(query("SHOW+DATABASES", "Token BAD").await.unwrap()).await;
+        assert!(got["results"][0].get("series").is_none());
+
+        let err = query("CREATE+DATABASE+apples", "Token BAD")
+            .await
+            .unwrap_err();
+        assert_eq!(err.as_status_code(), StatusCode::FORBIDDEN);
+        assert!(delegate.server.db("apples", None, false).await.is_none());
+
+        let err = query("SHOW+MEASUREMENTS+ON+bananas", "Token BAD")
+            .await
+            .unwrap_err();
+        assert_eq!(err.as_status_code(), StatusCode::FORBIDDEN);
+    }
+
+    #[derive(Debug, Default)]
+    struct MockWriter(std::sync::Mutex<Vec<(String, String)>>);
+
+    #[async_trait]
+    impl RollupWriter for MockWriter {
+        async fn write_lp(&self, namespace: &str, lp: String) -> Result<(), rollup::WriteError> {
+            self.0.lock().unwrap().push((namespace.to_string(), lp));
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_select_into() {
+        let uri =
+            "/query?db=bananas&epoch=ns&q=SELECT+field_int+INTO+cpu_copy+FROM+cpu+GROUP+BY+tag1";
+
+        // SELECT ... INTO requires a writer.
+        let delegate = delegate(None).await;
+        let req = Request::builder().uri(uri).body(Body::empty()).unwrap();
+        let err = delegate.route(req).await.unwrap_err();
+        assert_eq!(err.as_status_code(), StatusCode::NOT_IMPLEMENTED);
+
+        let writer = Arc::new(MockWriter::default());
+        let delegate = delegate.with_into_writer(Arc::clone(&writer) as _);
+
+        // SELECT ... INTO can't be sent with a GET request.
+        let req = Request::builder().uri(uri).body(Body::empty()).unwrap();
+        let err = delegate.route(req).await.unwrap_err();
+        assert_eq!(err.as_status_code(), StatusCode::METHOD_NOT_ALLOWED);
+        assert!(writer.0.lock().unwrap().is_empty());
+
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri(uri)
+            .body(Body::empty())
+            .unwrap();
+        let got = body_json(delegate.route(req).await.unwrap()).await;
+        assert_eq!(
+            got,
+            json!({"results": [{
+                "statement_id": 0,
+                "series": [{
+                    "name": "result",
+                    "columns": ["time", "written"],
+                    "values": [[0, 3]],
+                }],
+            }]})
+        );
+
+        let writes = writer.0.lock().unwrap().clone();
+        assert_eq!(
+            writes,
+            [(
+                "bananas".to_string(),
+                "cpu_copy,tag1=UT field_int=70i 20000\n\
+                 cpu_copy,tag1=VT field_int=10i 10000\n\
+                 cpu_copy,tag1=WA field_int=1000i 8000\n"
+                    .to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_namespace_from_params() {
+        let params = |db: &str, rp: Option<&str>| QueryParams {
+            db: Some(db.to_string()),
+            rp: rp.map(ToString::to_string),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            params("bananas", None)
+                .default_database()
+                .namespace()
+                .unwrap()
+                .as_str(),
+            "bananas"
+        );
+        assert_eq!(
+            params("bananas", Some("autogen"))
+                .default_database()
+                .namespace()
+                .unwrap()
+                .as_str(),
+            "bananas"
+        );
+        assert_eq!(
+            params("bananas", Some("ripe"))
+                .default_database()
+                .namespace()
+                .unwrap()
+                .as_str(),
+            "bananas/ripe"
+        );
+        assert!(matches!(
+            params("", None).default_database().namespace(),
+            Err(Error::MissingDatabase)
+        ));
+    }
+}
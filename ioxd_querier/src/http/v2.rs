@@ -0,0 +1,356 @@
+//! Handling of HTTP requests that conform to the [V2 Query API], for SQL and
+//! InfluxQL queries only.
+//!
+//! [V2 Query API]:
+//!     https://docs.influxdata.com/influxdb/v2.6/api/#operation/PostQuery
+
+use std::sync::Arc;
+
+use authz::{extract_token, http::AuthorizationHeaderExtension, Action, Authorizer};
+use data_types::NamespaceName;
+use datafusion::physical_plan::SendableRecordBatchStream;
+use futures::{stream, Stream, StreamExt};
+use hyper::{header::CONTENT_TYPE, Body, Request, Response, StatusCode};
+use iox_query::{QueryCompletedToken, QueryNamespace};
+use observability_deps::tracing::*;
+use serde::Deserialize;
+use service_common::{
+    planner::{Planner, StatementParams},
+    QueryNamespaceProvider, QueryPermit,
+};
+use trace::{ctx::SpanContext, span::SpanExt};
+
+use super::{
+    annotated_csv::{AnnotatedCsvEncoder, Dialect, EncodeError},
+    authorize, read_body, Error,
+};
+
+/// Query parameters of a V2 query request.
+#[derive(Debug, Deserialize)]
+struct QueryParamsV2 {
+    #[serde(default)]
+    org: String,
+    #[serde(default)]
+    bucket: String,
+}
+
+/// The language of a V2 query request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum QueryType {
+    Flux,
+    InfluxQL,
+    Sql,
+}
+
+/// The JSON body of a V2 query request.
+#[derive(Debug, Deserialize)]
+struct QueryRequest {
+    query: String,
+    #[serde(rename = "type")]
+    query_type: Option<QueryType>,
+    /// The bucket to query, taking precedence over the `bucket` parameter.
+    bucket: Option<String>,
+    #[serde(default)]
+    dialect: Dialect,
+    /// The values of the bind parameters of an InfluxQL query.
+    #[serde(default)]
+    params: StatementParams,
+}
+
+/// Serve a V2 query request against the namespaces of `server`, streaming
+/// the output as annotated CSV.
+///
+/// The org and bucket are mapped to a namespace as by a router deployed in
+/// the same (`single_tenant` or multi-tenant) mode.
+pub(super) async fn query<S>(
+    server: &S,
+    authz: &Option<Arc<dyn Authorizer>>,
+    single_tenant: bool,
+    max_request_bytes: usize,
+    req: Request<Body>,
+) -> Result<Response<Body>, Error>
+where
+    S: QueryNamespaceProvider,
+{
+    let span_ctx: Option<SpanContext> = req.extensions().get().cloned();
+    let token = extract_token(
+        req.extensions()
+            .get::<AuthorizationHeaderExtension>()
+            .and_then(|v| v.as_ref()),
+    );
+
+    let params: QueryParamsV2 = serde_urlencoded::from_str(req.uri().query().unwrap_or_default())?;
+    let body = read_body(req, max_request_bytes).await?;
+    let request: QueryRequest = serde_json::from_slice(&body).map_err(Error::InvalidBody)?;
+
+    let bucket = request.bucket.as_deref().unwrap_or(&params.bucket);
+    let namespace = NamespaceName::from_v2_org_and_bucket(&params.org, bucket, single_tenant)?;
+
+    let query_type = match request.query_type {
+        Some(QueryType::InfluxQL) => "influxql",
+        Some(QueryType::Sql) => "sql",
+        Some(QueryType::Flux) => return Err(Error::UnsupportedQueryType("flux".to_string())),
+        None => return Err(Error::UnsupportedQueryType(String::new())),
+    };
+    let &[delimiter] = request.dialect.delimiter.as_bytes() else {
+        return Err(Error::InvalidDelimiter(request.dialect.delimiter));
+    };
+
+    authorize(authz, token, &namespace, Action::Read).await?;
+
+    let db = server
+        .db(&namespace, span_ctx.child_span("get namespace"), false)
+        .await
+        .ok_or_else(|| Error::NamespaceNotFound(namespace.to_string()))?;
+
+    let permit = server
+        .acquire_semaphore(
+            &namespace,
+            span_ctx.child_span("query rate limit semaphore"),
+        )
+        .await;
+
+    info!(%namespace, query=%request.query, %query_type, "V2 query request");
+
+    let ctx = db.new_query_context(span_ctx);
+    let completed = db.record_query(&ctx, query_type, Box::new(request.query.clone()));
+    let planner = Planner::new(&ctx);
+    let plan = match request.query_type {
+        Some(QueryType::InfluxQL) => planner.influxql(request.query, request.params).await,
+        _ => planner.sql(request.query).await,
+    }
+    .map_err(Error::Planning)?;
+    let batches = ctx.execute_stream(plan).await.map_err(Error::Planning)?;
+
+    let output = QueryOutput {
+        batches,
+        encoder: AnnotatedCsvEncoder::new(request.dialect, delimiter),
+        completed,
+        _permit: permit,
+        done: false,
+    };
+
+    Ok(Response::builder()
+        .status(StatusCode::OK)
+        .header(CONTENT_TYPE, "text/csv; charset=utf-8")
+        .body(Body::wrap_stream(output.into_stream()))
+        .expect("valid response"))
+}
+
+/// The state of a streaming query response.
+///
+/// The query permit is held until the output has been fully written.
+struct QueryOutput {
+    batches: SendableRecordBatchStream,
+    encoder: AnnotatedCsvEncoder,
+    completed: QueryCompletedToken,
+    _permit: QueryPermit,
+    done: bool,
+}
+
+impl QueryOutput {
+    /// Encode the query output as a stream of CSV chunks.
+    ///
+    /// Errors executing the query are reported as a final error table.
+    fn into_stream(self) -> impl Stream<Item = Result<Vec<u8>, EncodeError>> {
+        stream::unfold(self, |mut state| async move {
+            if state.done {
+                return None;
+            }
+
+            let chunk = match state.batches.next().await {
+                Some(Ok(batch)) => state.encoder.write_batch(&batch),
+                Some(Err(e)) => {
+                    state.done = true;
+                    state.encoder.write_error(&e.to_string())
+                }
+                None => {
+                    state.completed.set_success();
+                    return None;
+                }
+            };
+            state.done |= chunk.is_err();
+
+            Some((chunk, state))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::Method;
+    use iox_query::test::TestChunk;
+    use service_common::test_util::TestDatabaseStore;
+
+    use super::*;
+    use crate::http::HttpDelegate;
+
+    const MAX_REQUEST_BYTES: usize = 1024;
+
+    async fn delegate() -> HttpDelegate<TestDatabaseStore> {
+        let store = Arc::new(TestDatabaseStore::default());
+        store.db_or_create("bananas_great").await.add_chunk(
+            "p1",
+            Arc::new(
+                TestChunk::new("cpu")
+                    .with_time_column()
+                    .with_tag_column("tag1")
+                    .with_i64_field_column("field_int")
+                    .with_three_rows_of_data(),
+            ),
+        );
+        HttpDelegate::new(store, None, MAX_REQUEST_BYTES)
+    }
+
+    fn request(uri: &str, body: &str) -> Request<Body> {
+        Request::builder()
+            .method(Method::POST)
+            .uri(uri)
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    async fn body_string(response: Response<Body>) -> String {
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        String::from_utf8(body.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_influxql() {
+        let delegate = delegate().await;
+        let req = request(
+            "/api/v2/query?org=bananas&bucket=great",
+            r#"{"query": "SELECT field_int FROM cpu GROUP BY tag1", "type": "influxql"}"#,
+        );
+
+        let response = delegate.route(req).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            body_string(response).await,
+            "#group,false,false,false,false,true,true\r\n\
+             #datatype,string,long,dateTime:RFC3339,long,string,string\r\n\
+             #default,_result,,,,,\r\n\
+             ,result,table,time,field_int,_measurement,tag1\r\n\
+             ,,0,1970-01-01T00:00:00.000020Z,70,cpu,UT\r\n\
+             ,,1,1970-01-01T00:00:00.000010Z,10,cpu,VT\r\n\
+             ,,2,1970-01-01T00:00:00.000008Z,1000,cpu,WA\r\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sql_with_bucket_in_body() {
+        let delegate = delegate().await;
+        let req = request(
+            "/api/v2/query?org=bananas",
+            r#"{
+                "query": "SELECT tag1, field_int FROM cpu ORDER BY time",
+                "type": "sql",
+                "bucket": "great",
+                "dialect": {"annotations": ["datatype"]}
+            }"#,
+        );
+
+        let response = delegate.route(req).await.unwrap();
+        assert_eq!(
+            body_string(response).await,
+            "#datatype,string,long,string,long\r\n\
+             ,result,table,tag1,field_int\r\n\
+             ,_result,0,WA,1000\r\n\
+             ,_result,0,VT,10\r\n\
+             ,_result,0,UT,70\r\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_single_tenant_bucket() {
+        let store = Arc::new(TestDatabaseStore::default());
+        store.db_or_create("great").await.add_chunk(
+            "p1",
+            Arc::new(
+                TestChunk::new("cpu")
+                    .with_time_column()
+                    .with_i64_field_column("field_int")
+                    .with_one_row_of_data(),
+            ),
+        );
+        let delegate = HttpDelegate::new(store, None, MAX_REQUEST_BYTES).with_single_tenant(true);
+
+        // The org is discarded, and the bucket names the namespace unchanged.
+        let req = request(
+            "/api/v2/query?org=bananas&bucket=great",
+            r#"{"query": "SELECT field_int FROM cpu", "type": "sql", "dialect": {"annotations": []}}"#,
+        );
+        let response = delegate.route(req).await.unwrap();
+        assert_eq!(
+            body_string(response).await,
+            ",result,table,field_int\r\n,_result,0,1000\r\n"
+        );
+
+        // The multi-tenant mapping of the same org and bucket does not exist.
+        let req = request(
+            "/api/v2/query?org=bananas&bucket=bananas_great",
+            r#"{"query": "SELECT 1", "type": "sql"}"#,
+        );
+        let err = delegate.route(req).await.unwrap_err();
+        assert_eq!(err.as_status_code(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_errors() {
+        let delegate = delegate().await;
+
+        let cases = [
+            (
+                "/api/v2/query?org=bananas&bucket=great",
+                r#"{"query": "from(bucket: \"great\")", "type": "flux"}"#,
+                StatusCode::BAD_REQUEST,
+            ),
+            (
+                "/api/v2/query?org=bananas&bucket=great",
+                r#"{"query": "SELECT 1"}"#,
+                StatusCode::BAD_REQUEST,
+            ),
+            (
+                "/api/v2/query?org=bananas&bucket=great",
+                "not json",
+                StatusCode::BAD_REQUEST,
+            ),
+            (
+                "/api/v2/query?bucket=great",
+                r#"{"query": "SELECT 1", "type": "sql"}"#,
+                StatusCode::BAD_REQUEST,
+            ),
+            (
+                "/api/v2/query?org=bananas&bucket=great",
+                r#"{"query": "SELECT 1", "type": "sql", "dialect": {"delimiter": ";;"}}"#,
+                StatusCode::BAD_REQUEST,
+            ),
+            (
+                "/api/v2/query?org=bananas&bucket=great",
+                r#"{"query": "SELECT nope FROM cpu", "type": "sql"}"#,
+                StatusCode::BAD_REQUEST,
+            ),
+            (
+                "/api/v2/query?org=bananas&bucket=rotten",
+                r#"{"query": "SELECT 1", "type": "sql"}"#,
+                StatusCode::NOT_FOUND,
+            ),
+        ];
+        for (uri, body, want) in cases {
+            let err = delegate.route(request(uri, body)).await.unwrap_err();
+            assert_eq!(err.as_status_code(), want, "{body}: {err}");
+        }
+
+        let body = format!(
+            r#"{{"query": "SELECT 1{}", "type": "sql"}}"#,
+            " ".repeat(MAX_REQUEST_BYTES)
+        );
+        let err = delegate
+            .route(request("/api/v2/query?org=bananas&bucket=great", &body))
+            .await
+            .unwrap_err();
+        assert_eq!(err.as_status_code(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+}
@@ -32,7 +32,7 @@ use iox_query::exec::{Executor, ExecutorType};
 use iox_time::TimeProvider;
 use ioxd_common::{
     add_service,
-    http::error::{HttpApiError, HttpApiErrorCode, HttpApiErrorSource},
+    http::error::HttpApiErrorSource,
     rpc::RpcBuilderInput,
     serve_builder,
     server_type::{CommonServerState, RpcError, ServerType},
@@ -40,26 +40,32 @@ use ioxd_common::{
 };
 use metric::Registry;
 use object_store::{DynObjectStore, ObjectStore};
-use querier::{create_ingester_connections, QuerierCatalogCache, QuerierDatabase, QuerierServer};
-use std::{
-    fmt::{Debug, Display},
-    sync::Arc,
+use querier::{
+    create_ingester_connections, QuerierCatalogCache, QuerierDatabase, QuerierServer,
+    RouterNamespaceCreator,
 };
+use rollup::{NamespaceProviderExecutor, RollupScheduler, RouterWriter};
+use std::{fmt::Debug, sync::Arc};
 use thiserror::Error;
 use tokio::runtime::Handle;
 use tokio_util::sync::CancellationToken;
 use trace::TraceCollector;
 
+mod http;
 mod rpc;
 
+use crate::http::HttpDelegate;
+
 pub struct QuerierServerType {
     catalog: Arc<dyn Catalog>,
     database: Arc<QuerierDatabase>,
     server: QuerierServer,
+    http: HttpDelegate<QuerierDatabase>,
     metric_registry: Arc<Registry>,
     object_store: Arc<dyn ObjectStore>,
     trace_collector: Option<Arc<dyn TraceCollector>>,
     authz: Option<Arc<dyn Authorizer>>,
+    rollup_shutdown: CancellationToken,
 }
 
 impl std::fmt::Debug for QuerierServerType {
@@ -85,12 +91,12 @@ impl ServerType for QuerierServerType {
         self.trace_collector.as_ref().map(Arc::clone)
     }
 
-    /// Just return "not found".
+    /// Route HTTP requests to the InfluxDB compatible query APIs.
     async fn route_http_request(
         &self,
-        _req: Request<Body>,
+        req: Request<Body>,
     ) -> Result<Response<Body>, Box<dyn HttpApiErrorSource>> {
-        Err(Box::new(IoxHttpError::NotFound))
+        self.http.route(req).await.map_err(|e| Box::new(e) as _)
     }
 
     /// Configure the gRPC services.
@@ -138,38 +144,11 @@ impl ServerType for QuerierServerType {
 
     fn shutdown(&self, frontend: CancellationToken) {
         frontend.cancel();
+        self.rollup_shutdown.cancel();
         self.server.shutdown();
     }
 }
 
-/// Simple error struct, we're not really providing an HTTP interface for the compactor.
-#[derive(Debug)]
-pub enum IoxHttpError {
-    NotFound,
-}
-
-impl IoxHttpError {
-    fn status_code(&self) -> HttpApiErrorCode {
-        match self {
-            Self::NotFound => HttpApiErrorCode::NotFound,
-        }
-    }
-}
-
-impl Display for IoxHttpError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{self:?}")
-    }
-}
-
-impl std::error::Error for IoxHttpError {}
-
-impl HttpApiErrorSource for IoxHttpError {
-    fn to_http_api_error(&self) -> HttpApiError {
-        HttpApiError::new(self.status_code(), self.to_string())
-    }
-}
-
 /// Arguments required to create a [`ServerType`] for the querier.
 #[derive(Debug)]
 pub struct QuerierServerTypeArgs<'a> {
@@ -255,26 +234,57 @@ pub async fn create_querier_server_type(
         ))
     };
 
-    let database = Arc::new(
-        QuerierDatabase::new(
-            catalog_cache,
-            Arc::clone(&args.metric_registry),
-            args.exec,
-            ingester_connections,
-            args.querier_config.max_concurrent_queries,
-            Arc::new(args.querier_config.datafusion_config),
-        )
-        .await?,
-    );
+    let mut database = QuerierDatabase::new(
+        catalog_cache,
+        Arc::clone(&args.metric_registry),
+        args.exec,
+        ingester_connections,
+        args.querier_config.max_concurrent_queries,
+        Arc::new(args.querier_config.datafusion_config),
+    )
+    .await?;
+    if let Some(addr) = &args.querier_config.create_database_router_address {
+        database = database.with_namespace_creator(Arc::new(RouterNamespaceCreator::new(addr)));
+    }
+    let database = Arc::new(database);
+
+    let rollup_shutdown = CancellationToken::new();
+    if let Some(addr) = &args.querier_config.rollup_router_address {
+        let scheduler = RollupScheduler::new(
+            Arc::clone(&args.catalog),
+            Arc::new(NamespaceProviderExecutor::new(Arc::clone(&database))),
+            Arc::new(
+                RouterWriter::new(addr.clone())
+                    .with_single_tenant(args.querier_config.single_tenant_deployment),
+            ),
+            args.querier_config.rollup_interval,
+            &args.metric_registry,
+        );
+        tokio::spawn(scheduler.run(rollup_shutdown.clone()));
+    }
 
     let server = QuerierServer::new(Arc::clone(&database));
+    let mut http = HttpDelegate::new(
+        Arc::clone(&database),
+        authz.as_ref().map(Arc::clone),
+        args.common_state.run_config().max_http_request_size,
+    )
+    .with_single_tenant(args.querier_config.single_tenant_deployment);
+    if let Some(addr) = &args.querier_config.select_into_router_address {
+        http = http.with_into_writer(Arc::new(
+            RouterWriter::new(addr.clone())
+                .with_single_tenant(args.querier_config.single_tenant_deployment),
+        ));
+    }
     Ok(Arc::new(QuerierServerType {
         catalog: args.catalog,
         database,
         server,
+        http,
         metric_registry: args.metric_registry,
         object_store: args.object_store,
         trace_collector: args.common_state.trace_collector(),
         authz,
+        rollup_shutdown,
     }))
 }
@@ -38,6 +38,9 @@ fn namespace_to_proto(namespace: Namespace) -> proto::Namespace {
         retention_period_ns: namespace.retention_period_ns,
         max_tables: namespace.max_tables,
         max_columns_per_table: namespace.max_columns_per_table,
+        max_write_bytes_per_second: namespace.max_write_bytes_per_second,
+        max_write_lines_per_second: namespace.max_write_lines_per_second,
+        max_concurrent_queries: namespace.max_concurrent_queries,
     }
 }
 
@@ -94,6 +97,15 @@ impl proto::namespace_service_server::NamespaceService for NamespaceServiceImpl
             "use router instances to manage namespaces",
         ))
     }
+
+    async fn get_namespace_storage_usage(
+        &self,
+        _request: tonic::Request<proto::GetNamespaceStorageUsageRequest>,
+    ) -> Result<tonic::Response<proto::GetNamespaceStorageUsageResponse>, tonic::Status> {
+        Err(tonic::Status::unimplemented(
+            "use router instances to get the storage usage of namespaces",
+        ))
+    }
 }
 
 #[cfg(test)]
@@ -186,6 +198,9 @@ mod tests {
                         retention_period_ns: TEST_RETENTION_PERIOD_NS,
                         max_tables: TEST_MAX_TABLES,
                         max_columns_per_table: TEST_MAX_COLUMNS_PER_TABLE,
+                        max_write_bytes_per_second: None,
+                        max_write_lines_per_second: None,
+                        max_concurrent_queries: None,
                     },
                     proto::Namespace {
                         id: 2,
@@ -193,6 +208,9 @@ mod tests {
                         retention_period_ns: TEST_RETENTION_PERIOD_NS,
                         max_tables: TEST_MAX_TABLES,
                         max_columns_per_table: TEST_MAX_COLUMNS_PER_TABLE,
+                        max_write_bytes_per_second: None,
+                        max_write_lines_per_second: None,
+                        max_concurrent_queries: None,
                     },
                 ]
             }
@@ -36,7 +36,8 @@ use ioxd_common::{
         generated_types::influxdata::iox::{
             catalog::v1::catalog_service_server, gossip::Topic,
             namespace::v1::namespace_service_server, object_store::v1::object_store_service_server,
-            schema::v1::schema_service_server, table::v1::table_service_server,
+            rollup::v1::rollup_service_server, schema::v1::schema_service_server,
+            table::v1::table_service_server,
         },
         tonic::transport::Endpoint,
     },
@@ -186,6 +187,10 @@ where
             builder,
             table_service_server::TableServiceServer::new(self.server.grpc().table_service())
         );
+        add_service!(
+            builder,
+            rollup_service_server::RollupServiceServer::new(self.server.grpc().rollup_service())
+        );
         serve_builder!(builder);
 
         Ok(())
@@ -218,6 +223,7 @@ impl std::error::Error for IoxHttpErrorAdaptor {}
 impl HttpApiErrorSource for IoxHttpErrorAdaptor {
     fn to_http_api_error(&self) -> HttpApiError {
         HttpApiError::new(self.0.as_status_code(), self.to_string())
+            .with_retry_after_secs(self.0.retry_after_secs())
     }
 }
 
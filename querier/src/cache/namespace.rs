@@ -15,7 +15,7 @@ use cache_system::{
 };
 use data_types::{
     partition_template::TablePartitionTemplateOverride, Column, ColumnId, Namespace, NamespaceId,
-    Table, TableId,
+    RollupRule, Table, TableId, Timestamp,
 };
 use iox_catalog::interface::{Catalog, SoftDeletedRows};
 use iox_time::TimeProvider;
@@ -127,7 +127,24 @@ impl NamespaceCache {
                     .await
                     .expect("retry forever");
 
-                Some(Arc::new(CachedNamespace::new(namespace, tables, columns)))
+                let rollup_rules = Backoff::new(&backoff_config)
+                    .retry_all_errors("get namespace rollup rules", || async {
+                        catalog
+                            .repositories()
+                            .await
+                            .rollup_rules()
+                            .list_by_namespace_id(namespace.id)
+                            .await
+                    })
+                    .await
+                    .expect("retry forever");
+
+                Some(Arc::new(CachedNamespace::new(
+                    namespace,
+                    tables,
+                    columns,
+                    &rollup_rules,
+                )))
             }
         });
         let loader = Arc::new(MetricsLoader::new(
@@ -226,6 +243,11 @@ impl NamespaceCache {
             )
             .await
     }
+
+    /// Expire the cached namespace `name`, such as after the namespace was created.
+    pub fn expire(&self, name: &Arc<str>) {
+        self.remove_if_handle.remove_if(name, |_| true);
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -236,10 +258,12 @@ pub struct CachedTable {
     pub column_id_map_rev: HashMap<Arc<str>, ColumnId>,
     pub primary_key_column_ids: Box<[ColumnId]>,
     pub partition_template: TablePartitionTemplateOverride,
+    pub retention_period: Option<Duration>,
+    pub tiered_retention: Option<TieredRetention>,
 }
 
 impl CachedTable {
-    fn new(table: Table, mut columns: Vec<Column>) -> Self {
+    fn new(table: Table, mut columns: Vec<Column>, rollup_rules: &[RollupRule]) -> Self {
         // sort columns by name so that schema is normalized
         // Note: `sort_by_key` doesn't work if we don't wanna clone the strings every time
         columns.sort_by(|x, y| x.name.cmp(&y.name));
@@ -273,6 +297,8 @@ impl CachedTable {
             })
             .collect();
 
+        let tiered_retention = TieredRetention::from_rules(&table.name, rollup_rules);
+
         Self {
             id: table.id,
             schema,
@@ -280,6 +306,10 @@ impl CachedTable {
             column_id_map_rev,
             primary_key_column_ids,
             partition_template: table.partition_template,
+            retention_period: table
+                .retention_period_ns
+                .map(|retention| Duration::from_nanos(retention as u64)),
+            tiered_retention,
         }
     }
 
@@ -303,15 +333,65 @@ impl CachedTable {
     }
 }
 
+/// Retention of the source table of rollup rules with a raw retention period.
+///
+/// Raw data of such a table is only deleted once it is older than the longest raw retention
+/// period of the rules and has been rolled up by all of them, see
+/// [`ParquetFileRepo::flag_for_delete_by_retention`](iox_catalog::interface::ParquetFileRepo::flag_for_delete_by_retention).
+/// Data that has not been rolled up must remain readable, as the rollup rules read it through
+/// the querier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TieredRetention {
+    /// The longest raw retention period of the rules.
+    pub raw_retention_period: Duration,
+    /// The watermark every rule has reached.
+    pub covered_until: Option<Timestamp>,
+}
+
+impl TieredRetention {
+    fn from_rules(table_name: &str, rollup_rules: &[RollupRule]) -> Option<Self> {
+        rollup_rules
+            .iter()
+            .filter(|rule| rule.source_table == table_name)
+            .filter_map(|rule| {
+                rule.raw_retention_period_ns.map(|retention| Self {
+                    raw_retention_period: Duration::from_nanos(retention as u64),
+                    covered_until: rule.covered_until,
+                })
+            })
+            .reduce(|a, b| Self {
+                raw_retention_period: a.raw_retention_period.max(b.raw_retention_period),
+                covered_until: a.covered_until.min(b.covered_until),
+            })
+    }
+
+    /// The time before which data may have been deleted at `now_ns`, if any.
+    ///
+    /// A cached watermark may lag behind the catalog, which only makes more data readable.
+    pub fn retention_time_ns(&self, now_ns: i64) -> Option<i64> {
+        self.covered_until.map(|covered_until| {
+            covered_until
+                .get()
+                .min(now_ns - self.raw_retention_period.as_nanos() as i64)
+        })
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CachedNamespace {
     pub id: NamespaceId,
     pub retention_period: Option<Duration>,
+    pub max_concurrent_queries: Option<usize>,
     pub tables: HashMap<Arc<str>, Arc<CachedTable>>,
 }
 
 impl CachedNamespace {
-    pub fn new(namespace: Namespace, tables: Vec<Table>, columns: Vec<Column>) -> Self {
+    pub fn new(
+        namespace: Namespace,
+        tables: Vec<Table>,
+        columns: Vec<Column>,
+        rollup_rules: &[RollupRule],
+    ) -> Self {
         let mut tables_by_id = tables
             .into_iter()
             .map(|t| (t.id, (t, vec![])))
@@ -326,7 +406,7 @@ impl CachedNamespace {
             .into_iter()
             .map(|(_tid, (t, tcols))| {
                 let name = Arc::from(t.name.clone());
-                let table = Arc::new(CachedTable::new(t, tcols));
+                let table = Arc::new(CachedTable::new(t, tcols, rollup_rules));
                 (name, table)
             })
             .collect();
@@ -339,6 +419,7 @@ impl CachedNamespace {
         Self {
             id: namespace.id,
             retention_period,
+            max_concurrent_queries: namespace.max_concurrent_queries.map(|v| v as usize),
             tables,
         }
     }
@@ -360,7 +441,7 @@ mod tests {
         ram::test_util::test_ram_pool, test_util::assert_catalog_access_metric_count,
     };
     use arrow::datatypes::DataType;
-    use data_types::ColumnType;
+    use data_types::{ColumnType, RollupQueryLanguage, RollupRuleId, RollupRuleParams};
     use generated_types::influxdata::iox::partition_template::v1::{
         template_part::Part, PartitionTemplate, TemplatePart,
     };
@@ -418,6 +499,7 @@ mod tests {
         let expected_ns_1 = CachedNamespace {
             id: ns1.namespace.id,
             retention_period,
+            max_concurrent_queries: None,
             tables: HashMap::from([
                 (
                     Arc::from("table1"),
@@ -442,6 +524,8 @@ mod tests {
                         ]),
                         primary_key_column_ids: [col112.column.id, col113.column.id].into(),
                         partition_template: table11.table.partition_template.clone(),
+                        retention_period: None,
+                        tiered_retention: None,
                     }),
                 ),
                 (
@@ -464,6 +548,8 @@ mod tests {
                         ]),
                         primary_key_column_ids: [col122.column.id].into(),
                         partition_template: TablePartitionTemplateOverride::default(),
+                        retention_period: None,
+                        tiered_retention: None,
                     }),
                 ),
             ]),
@@ -482,6 +568,7 @@ mod tests {
         let expected_ns_2 = CachedNamespace {
             id: ns2.namespace.id,
             retention_period,
+            max_concurrent_queries: None,
             tables: HashMap::from([(
                 Arc::from("table1"),
                 Arc::new(CachedTable {
@@ -497,6 +584,8 @@ mod tests {
                     )]),
                     primary_key_column_ids: [col211.column.id].into(),
                     partition_template: TablePartitionTemplateOverride::default(),
+                    retention_period: None,
+                    tiered_retention: None,
                 }),
             )]),
         };
@@ -621,4 +710,60 @@ mod tests {
             .is_some());
         assert_catalog_access_metric_count(&catalog.metric_registry, "namespace_get_by_name", 6);
     }
+
+    #[test]
+    fn test_tiered_retention() {
+        const HOUR: i64 = 60 * 60 * 1_000_000_000;
+
+        let rule = |id: i64, source_table: &str, raw_retention_period_ns, covered_until| {
+            let mut rule = RollupRule::from_params(
+                RollupRuleParams {
+                    namespace_id: NamespaceId::new(1),
+                    name: format!("rule_{id}"),
+                    source_table: source_table.to_string(),
+                    target_table: format!("target_{id}"),
+                    language: RollupQueryLanguage::Sql,
+                    aggregates: "avg(usage) AS usage".to_string(),
+                    interval_ns: HOUR,
+                    raw_retention_period_ns,
+                },
+                RollupRuleId::new(id),
+            );
+            rule.covered_until = covered_until.map(Timestamp::new);
+            rule
+        };
+
+        // rules without a raw retention period, or of other tables, do not tier the retention
+        let rules = [
+            rule(1, "cpu", None, Some(HOUR)),
+            rule(2, "mem", Some(HOUR), Some(HOUR)),
+        ];
+        assert_eq!(TieredRetention::from_rules("cpu", &rules), None);
+
+        // the longest raw retention period and the lowest watermark apply
+        let rules = [
+            rule(1, "cpu", Some(2 * HOUR), Some(5 * HOUR)),
+            rule(2, "cpu", Some(HOUR), Some(3 * HOUR)),
+        ];
+        let tiered = TieredRetention::from_rules("cpu", &rules).unwrap();
+        assert_eq!(
+            tiered,
+            TieredRetention {
+                raw_retention_period: Duration::from_nanos(2 * HOUR as u64),
+                covered_until: Some(Timestamp::new(3 * HOUR)),
+            }
+        );
+
+        // data is only gone once it is both rolled up and older than the raw retention period
+        assert_eq!(tiered.retention_time_ns(10 * HOUR), Some(3 * HOUR));
+        assert_eq!(tiered.retention_time_ns(4 * HOUR), Some(2 * HOUR));
+
+        // nothing is deleted before a rule rolled up any data
+        let rules = [
+            rule(1, "cpu", Some(2 * HOUR), Some(5 * HOUR)),
+            rule(2, "cpu", Some(HOUR), None),
+        ];
+        let tiered = TieredRetention::from_rules("cpu", &rules).unwrap();
+        assert_eq!(tiered.retention_time_ns(10 * HOUR), None);
+    }
 }
@@ -432,6 +432,8 @@ mod tests {
             ]),
             primary_key_column_ids: [c1.column.id, c2.column.id].into(),
             partition_template: TablePartitionTemplateOverride::default(),
+            retention_period: None,
+            tiered_retention: None,
         });
 
         let cache = PartitionCache::new(
@@ -585,6 +587,8 @@ mod tests {
             ]),
             primary_key_column_ids: [c1.column.id, c2.column.id, c3.column.id, c4.column.id].into(),
             partition_template: t.table.partition_template.clone(),
+            retention_period: None,
+            tiered_retention: None,
         });
 
         let cache = PartitionCache::new(
@@ -813,6 +817,8 @@ mod tests {
             ]),
             primary_key_column_ids: [c1.column.id, c2.column.id].into(),
             partition_template: TablePartitionTemplateOverride::default(),
+            retention_period: None,
+            tiered_retention: None,
         });
 
         let cache = PartitionCache::new(
@@ -955,6 +961,8 @@ mod tests {
             column_id_map_rev: HashMap::default(),
             primary_key_column_ids: [].into(),
             partition_template: TablePartitionTemplateOverride::default(),
+            retention_period: None,
+            tiered_retention: None,
         });
 
         let cache = PartitionCache::new(
@@ -1108,6 +1116,8 @@ mod tests {
                 column_id_map_rev: HashMap::from([(Arc::from(c.column.name.clone()), c.column.id)]),
                 primary_key_column_ids: [c.column.id].into(),
                 partition_template: TablePartitionTemplateOverride::default(),
+                retention_period: None,
+                tiered_retention: None,
             });
             const N_PARTITIONS: usize = 20;
             let c_id = c.column.id.get();
@@ -213,6 +213,8 @@ mod tests {
             ]
             .into(),
             partition_template: TablePartitionTemplateOverride::default(),
+            retention_period: None,
+            tiered_retention: None,
         });
         let table_1b = Arc::new(CachedTable {
             id: table_id_1,
@@ -227,6 +229,8 @@ mod tests {
             ]
             .into(),
             partition_template: TablePartitionTemplateOverride::default(),
+            retention_period: None,
+            tiered_retention: None,
         });
         let table_2a = Arc::new(CachedTable {
             id: table_id_2,
@@ -242,6 +246,8 @@ mod tests {
             ]
             .into(),
             partition_template: TablePartitionTemplateOverride::default(),
+            retention_period: None,
+            tiered_retention: None,
         });
 
         // initial request
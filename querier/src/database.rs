@@ -4,22 +4,25 @@ use crate::{
     cache::CatalogCache,
     ingester::IngesterConnection,
     namespace::{QuerierNamespace, QuerierNamespaceArgs},
+    namespace_creator::{self, NamespaceCreator},
     parquet::ChunkAdapter,
     query_log::QueryLog,
     table::PruneMetrics,
 };
 use async_trait::async_trait;
 use backoff::{Backoff, BackoffConfig};
-use data_types::Namespace;
+use data_types::{Namespace, NamespaceId};
 use iox_catalog::interface::SoftDeletedRows;
-use iox_query::exec::Executor;
-use service_common::QueryNamespaceProvider;
+use iox_query::exec::{Executor, ExecutorType, IOxSessionContext};
+use parking_lot::Mutex;
+use service_common::{QueryNamespaceProvider, QueryPermit};
 use snafu::Snafu;
 use std::{collections::HashMap, sync::Arc};
-use trace::span::{Span, SpanRecorder};
-use tracker::{
-    AsyncSemaphoreMetrics, InstrumentedAsyncOwnedSemaphorePermit, InstrumentedAsyncSemaphore,
+use trace::{
+    ctx::SpanContext,
+    span::{Span, SpanRecorder},
 };
+use tracker::{AsyncSemaphoreMetrics, InstrumentedAsyncSemaphore};
 
 /// The number of entries to store in the circular query buffer log.
 ///
@@ -65,11 +68,21 @@ pub struct QuerierDatabase {
     /// If the same namespace is requested twice for different queries, it is counted twice.
     query_execution_semaphore: Arc<InstrumentedAsyncSemaphore>,
 
+    /// Semaphores enforcing the query concurrency quota of the namespaces that have one, along
+    /// with the quota they were created for.
+    namespace_semaphores: Mutex<HashMap<NamespaceId, (usize, Arc<InstrumentedAsyncSemaphore>)>>,
+
+    /// Metrics shared by all [`namespace_semaphores`](Self::namespace_semaphores).
+    namespace_semaphore_metrics: Arc<AsyncSemaphoreMetrics>,
+
     /// Chunk prune metrics.
     prune_metrics: Arc<PruneMetrics>,
 
     /// DataFusion config.
     datafusion_config: Arc<HashMap<String, String>>,
+
+    /// Creates the namespaces of `CREATE DATABASE` statements, if enabled.
+    namespace_creator: Option<Arc<dyn NamespaceCreator>>,
 }
 
 #[async_trait]
@@ -85,11 +98,70 @@ impl QueryNamespaceProvider for QuerierDatabase {
         self.namespace(name, span, include_debug_info_tables).await
     }
 
-    async fn acquire_semaphore(&self, span: Option<Span>) -> InstrumentedAsyncOwnedSemaphorePermit {
-        Arc::clone(&self.query_execution_semaphore)
-            .acquire_owned(span)
+    async fn acquire_semaphore(&self, name: &str, span: Option<Span>) -> QueryPermit {
+        let span_recorder = SpanRecorder::new(span);
+
+        // Wait for the namespace quota first, so that queries queued behind it do not hold on to
+        // global permits.
+        let namespace_permit = match self
+            .namespace_semaphore(name, span_recorder.child_span("cache GET namespace schema"))
             .await
-            .expect("Semaphore should not be closed by anyone")
+        {
+            Some(semaphore) => Some(
+                semaphore
+                    .acquire_owned(span_recorder.child_span("namespace semaphore"))
+                    .await
+                    .expect("Semaphore should not be closed by anyone"),
+            ),
+            None => None,
+        };
+
+        let permit = Arc::clone(&self.query_execution_semaphore)
+            .acquire_owned(span_recorder.child_span("global semaphore"))
+            .await
+            .expect("Semaphore should not be closed by anyone");
+
+        QueryPermit::new(permit, namespace_permit)
+    }
+
+    async fn namespace_names(&self) -> Vec<String> {
+        self.namespaces()
+            .await
+            .into_iter()
+            .map(|namespace| namespace.name)
+            .collect()
+    }
+
+    fn new_query_context(&self, span_ctx: Option<SpanContext>) -> IOxSessionContext {
+        let mut cfg = self
+            .exec
+            .new_execution_config(ExecutorType::Query)
+            .with_span_context(span_ctx)
+            .with_time_provider(self.catalog_cache.time_provider());
+
+        for (k, v) in self.datafusion_config.as_ref() {
+            cfg = cfg.with_config_option(k, v);
+        }
+
+        cfg.build()
+    }
+
+    async fn create_namespace(
+        &self,
+        name: &str,
+        retention_period_ns: Option<i64>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let creator = self
+            .namespace_creator
+            .as_ref()
+            .ok_or(namespace_creator::Error::NotEnabled)?;
+        creator.create_namespace(name, retention_period_ns).await?;
+
+        // Do not serve a cached absence of the namespace to the statements that follow.
+        self.catalog_cache
+            .namespace()
+            .expire(&Arc::from(name.to_owned()));
+        Ok(())
     }
 }
 
@@ -130,6 +202,10 @@ impl QuerierDatabase {
         ));
         let query_execution_semaphore =
             Arc::new(semaphore_metrics.new_semaphore(max_concurrent_queries));
+        let namespace_semaphore_metrics = Arc::new(AsyncSemaphoreMetrics::new(
+            &metric_registry,
+            &[("semaphore", "namespace_query_execution")],
+        ));
 
         let prune_metrics = Arc::new(PruneMetrics::new(&metric_registry));
 
@@ -141,11 +217,21 @@ impl QuerierDatabase {
             ingester_connection,
             query_log,
             query_execution_semaphore,
+            namespace_semaphores: Default::default(),
+            namespace_semaphore_metrics,
             prune_metrics,
             datafusion_config,
+            namespace_creator: None,
         })
     }
 
+    /// Create the namespaces of `CREATE DATABASE` statements with `creator`, rather than
+    /// rejecting them.
+    pub fn with_namespace_creator(mut self, creator: Arc<dyn NamespaceCreator>) -> Self {
+        self.namespace_creator = Some(creator);
+        self
+    }
+
     /// Get namespace if it exists.
     ///
     /// This will await the internal namespace semaphore. Existence of namespaces is checked AFTER
@@ -181,6 +267,39 @@ impl QuerierDatabase {
         })))
     }
 
+    /// Get the semaphore enforcing the query concurrency quota of the namespace `name`, if it
+    /// exists and has such a quota.
+    ///
+    /// The semaphore is replaced when the quota changes. Queries running under the old quota keep
+    /// their permit until they complete.
+    async fn namespace_semaphore(
+        &self,
+        name: &str,
+        span: Option<Span>,
+    ) -> Option<Arc<InstrumentedAsyncSemaphore>> {
+        let ns = self
+            .catalog_cache
+            .namespace()
+            .get(Arc::from(name.to_owned()), &[], span)
+            .await?;
+
+        let mut semaphores = self.namespace_semaphores.lock();
+        let Some(limit) = ns.max_concurrent_queries else {
+            semaphores.remove(&ns.id);
+            return None;
+        };
+        let limit = limit.min(Self::MAX_CONCURRENT_QUERIES_MAX);
+
+        match semaphores.get(&ns.id) {
+            Some((l, semaphore)) if *l == limit => Some(Arc::clone(semaphore)),
+            _ => {
+                let semaphore = Arc::new(self.namespace_semaphore_metrics.new_semaphore(limit));
+                semaphores.insert(ns.id, (limit, Arc::clone(&semaphore)));
+                Some(semaphore)
+            }
+        }
+    }
+
     /// Return all namespaces this querier knows about
     pub async fn namespaces(&self) -> Vec<Namespace> {
         let catalog = &self.catalog_cache.catalog();
@@ -213,6 +332,7 @@ mod tests {
     use super::*;
     use crate::create_ingester_connection_for_testing;
     use iox_tests::TestCatalog;
+    use std::time::Duration;
     use tokio::runtime::Handle;
 
     #[tokio::test]
@@ -252,6 +372,50 @@ mod tests {
         assert!(db.namespace("ns2", None, true).await.is_none());
     }
 
+    #[tokio::test]
+    async fn test_create_namespace() {
+        /// Creates namespaces in the catalog, like the router does.
+        #[derive(Debug)]
+        struct CatalogCreator(Arc<dyn iox_catalog::interface::Catalog>);
+
+        #[async_trait]
+        impl NamespaceCreator for CatalogCreator {
+            async fn create_namespace(
+                &self,
+                name: &str,
+                retention_period_ns: Option<i64>,
+            ) -> Result<(), namespace_creator::Error> {
+                let name = data_types::NamespaceName::new(name.to_owned()).unwrap();
+                self.0
+                    .repositories()
+                    .await
+                    .namespaces()
+                    .create(&name, None, retention_period_ns, None)
+                    .await
+                    .unwrap();
+                Ok(())
+            }
+        }
+
+        let catalog = TestCatalog::new();
+        let db = new_db(&catalog).await;
+
+        let err = db.create_namespace("ns1", None).await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "CREATE DATABASE is not enabled on this querier"
+        );
+
+        let db = new_db(&catalog)
+            .await
+            .with_namespace_creator(Arc::new(CatalogCreator(catalog.catalog())));
+
+        // The absence of the namespace is not served from the cache once it is created.
+        assert!(db.namespace("ns1", None, true).await.is_none());
+        db.create_namespace("ns1", None).await.unwrap();
+        assert!(db.namespace("ns1", None, true).await.is_some());
+    }
+
     #[tokio::test]
     async fn test_namespaces() {
         let catalog = TestCatalog::new();
@@ -267,6 +431,39 @@ mod tests {
         assert_eq!(namespaces[1].name, "ns2");
     }
 
+    #[tokio::test]
+    async fn test_namespace_query_concurrency_limit() {
+        let catalog = TestCatalog::new();
+        let db = new_db(&catalog).await;
+
+        catalog.create_namespace_1hr_retention("limited").await;
+        catalog.create_namespace_1hr_retention("unlimited").await;
+        catalog
+            .catalog()
+            .repositories()
+            .await
+            .namespaces()
+            .update_query_concurrency_limit("limited", Some(1))
+            .await
+            .unwrap();
+
+        // The second query against the limited namespace must wait for the first one.
+        let permit = db.acquire_semaphore("limited", None).await;
+        tokio::time::timeout(
+            Duration::from_millis(100),
+            db.acquire_semaphore("limited", None),
+        )
+        .await
+        .unwrap_err();
+
+        // Other namespaces are not affected.
+        let _p1 = db.acquire_semaphore("unlimited", None).await;
+        let _p2 = db.acquire_semaphore("unlimited", None).await;
+
+        drop(permit);
+        db.acquire_semaphore("limited", None).await;
+    }
+
     async fn new_db(catalog: &Arc<TestCatalog>) -> QuerierDatabase {
         let catalog_cache = Arc::new(CatalogCache::new_testing(
             catalog.catalog(),
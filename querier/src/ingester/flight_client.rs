@@ -512,6 +512,7 @@ mod tests {
             table_id: TableId::new(1337),
             columns: vec![String::from("col1"), String::from("col2")],
             predicate: Some(predicate),
+            aggregate: None,
         };
 
         let proto = serialize_ingester_query_request(request.clone()).expect("serialization");
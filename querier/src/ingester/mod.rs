@@ -419,6 +419,10 @@ async fn execute(
         table_id: cached_table.id,
         columns: columns.clone(),
         predicate: Some(Predicate::default().with_exprs(filters.iter().cloned())),
+        // Aggregated ingester data would have to be merged with partial
+        // aggregates over the persisted data by the plan, which it does not
+        // do, so only raw data is requested.
+        aggregate: None,
     };
 
     let query_res = {
@@ -1163,6 +1167,7 @@ mod tests {
                             partition_identifier: Some(PartitionIdentifier::CatalogId(1)),
                             ingester_uuid: ingester_uuid.to_string(),
                             completed_persistence_count: 5,
+                            aggregated: false,
                         },
                     ))],
                 }),
@@ -1392,6 +1397,7 @@ mod tests {
                             )),
                             ingester_uuid: ingester_uuid.to_string(),
                             completed_persistence_count: 5,
+                            aggregated: false,
                         },
                     ))],
                 }),
@@ -1598,6 +1604,7 @@ mod tests {
                 )),
                 ingester_uuid: ingester_uuid.into(),
                 completed_persistence_count,
+                aggregated: false,
             },
         ))
     }
@@ -1786,6 +1793,8 @@ mod tests {
             column_id_map_rev: Default::default(),
             primary_key_column_ids: Default::default(),
             partition_template: Default::default(),
+            retention_period: None,
+            tiered_retention: None,
         })
     }
 }
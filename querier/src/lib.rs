@@ -20,6 +20,7 @@ mod cache;
 mod database;
 mod ingester;
 mod namespace;
+mod namespace_creator;
 mod parquet;
 mod query_log;
 mod server;
@@ -42,4 +43,7 @@ pub use ingester::{
     Error as IngesterError, IngesterConnection, IngesterConnectionImpl, IngesterPartition,
 };
 pub use namespace::QuerierNamespace;
+pub use namespace_creator::{
+    Error as NamespaceCreatorError, NamespaceCreator, RouterNamespaceCreator,
+};
 pub use server::QuerierServer;
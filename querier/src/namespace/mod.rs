@@ -91,7 +91,8 @@ impl QuerierNamespace {
                 let table = Arc::new(QuerierTable::new(QuerierTableArgs {
                     namespace_id: ns.id,
                     namespace_name: Arc::clone(&name),
-                    namespace_retention_period: ns.retention_period,
+                    retention_period: cached_table.retention_period.or(ns.retention_period),
+                    tiered_retention: cached_table.tiered_retention,
                     table_id: cached_table.id,
                     table_name: Arc::clone(table_name),
                     schema: cached_table.schema.clone(),
@@ -15,6 +15,7 @@ use datafusion::{
     prelude::Expr,
 };
 use datafusion_util::config::DEFAULT_SCHEMA;
+use iox_catalog::interface::Catalog;
 use iox_query::{
     exec::{ExecutorType, IOxSessionContext},
     QueryChunk, QueryCompletedToken, QueryNamespace, QueryText,
@@ -54,10 +55,13 @@ impl QueryNamespace for QuerierNamespace {
         Ok(chunks)
     }
 
-    fn retention_time_ns(&self) -> Option<i64> {
-        self.retention_period.map(|d| {
-            self.catalog_cache.time_provider().now().timestamp_nanos() - d.as_nanos() as i64
-        })
+    fn retention_time_ns(&self, table_name: &str) -> Option<i64> {
+        match self.tables.get(table_name) {
+            Some(table) => table.retention_time_ns(),
+            None => self.retention_period.map(|d| {
+                self.catalog_cache.time_provider().now().timestamp_nanos() - d.as_nanos() as i64
+            }),
+        }
     }
 
     fn record_query(
@@ -79,7 +83,8 @@ impl QueryNamespace for QuerierNamespace {
             .exec
             .new_execution_config(ExecutorType::Query)
             .with_default_catalog(Arc::new(QuerierCatalogProvider::from_namespace(self)) as _)
-            .with_span_context(span_ctx);
+            .with_span_context(span_ctx)
+            .with_time_provider(self.catalog_cache.time_provider());
 
         for (k, v) in self.datafusion_config.as_ref() {
             cfg = cfg.with_config_option(k, v);
@@ -99,6 +104,9 @@ pub struct QuerierCatalogProvider {
     /// Query log.
     query_log: Arc<QueryLog>,
 
+    /// Catalog, for the system tables reading from it.
+    catalog: Arc<dyn Catalog>,
+
     /// Include debug info tables.
     include_debug_info_tables: bool,
 }
@@ -109,6 +117,7 @@ impl QuerierCatalogProvider {
             namespace_id: namespace.id,
             tables: Arc::clone(&namespace.tables),
             query_log: Arc::clone(&namespace.query_log),
+            catalog: namespace.catalog_cache.catalog(),
             include_debug_info_tables: namespace.include_debug_info_tables,
         }
     }
@@ -130,7 +139,12 @@ impl CatalogProvider for QuerierCatalogProvider {
             })),
             SYSTEM_SCHEMA => Some(Arc::new(SystemSchemaProvider::new(
                 Arc::clone(&self.query_log),
+                Arc::clone(&self.catalog),
                 self.namespace_id,
+                self.tables
+                    .values()
+                    .map(|t| (t.id(), Arc::clone(t.table_name())))
+                    .collect(),
                 self.include_debug_info_tables,
             ))),
             _ => None,
@@ -22,7 +22,17 @@ pub async fn querier_namespace(ns: &Arc<TestNamespace>) -> QuerierNamespace {
         .list_by_namespace_id(ns.namespace.id)
         .await
         .unwrap();
-    let cached_ns = Arc::new(CachedNamespace::new(ns.namespace.clone(), tables, columns));
+    let rollup_rules = repos
+        .rollup_rules()
+        .list_by_namespace_id(ns.namespace.id)
+        .await
+        .unwrap();
+    let cached_ns = Arc::new(CachedNamespace::new(
+        ns.namespace.clone(),
+        tables,
+        columns,
+        &rollup_rules,
+    ));
 
     let catalog_cache = Arc::new(QuerierCatalogCache::new_testing(
         ns.catalog.catalog(),
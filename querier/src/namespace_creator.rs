@@ -0,0 +1,161 @@
+//! Creation of the namespaces of InfluxQL `CREATE DATABASE` statements.
+
+use async_trait::async_trait;
+use data_types::{NamespaceName, NamespaceNameError};
+use influxdb_iox_client::{
+    connection::{self, Connection},
+    error::Error as ClientError,
+    namespace::Client,
+};
+use observability_deps::tracing::info;
+use snafu::{ResultExt, Snafu};
+
+#[allow(missing_docs)]
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("{source}"))]
+    InvalidName { source: NamespaceNameError },
+
+    #[snafu(display("CREATE DATABASE is not enabled on this querier"))]
+    NotEnabled,
+
+    #[snafu(display("failed to connect to router {addr}: {source}"))]
+    Connect {
+        addr: String,
+        source: connection::Error,
+    },
+
+    #[snafu(display("failed to create namespace {name}: {source}"))]
+    Create { name: String, source: ClientError },
+
+    #[snafu(display("failed to look up namespace {name}: {source}"))]
+    Lookup { name: String, source: ClientError },
+
+    #[snafu(display(
+        "retention policy conflicts with an existing policy: namespace {name} has a retention \
+         period of {}, not {}",
+        fmt_retention(*existing),
+        fmt_retention(*requested)
+    ))]
+    RetentionConflict {
+        name: String,
+        existing: Option<i64>,
+        requested: Option<i64>,
+    },
+}
+
+fn fmt_retention(retention_period_ns: Option<i64>) -> String {
+    match retention_period_ns {
+        Some(ns) => format!("{ns}ns"),
+        None => "infinite".to_owned(),
+    }
+}
+
+/// Creates the namespaces of InfluxQL `CREATE DATABASE` statements.
+#[async_trait]
+pub trait NamespaceCreator: std::fmt::Debug + Send + Sync + 'static {
+    /// Create the namespace `name` with the given retention period.
+    ///
+    /// Succeeds if the namespace already exists with the same retention period, and returns
+    /// [`Error::RetentionConflict`] if it exists with a different one.
+    async fn create_namespace(
+        &self,
+        name: &str,
+        retention_period_ns: Option<i64>,
+    ) -> Result<(), Error>;
+}
+
+/// A [`NamespaceCreator`] that creates namespaces through the namespace service of a router, so
+/// that they are created the same way as namespaces created by writes.
+#[derive(Debug)]
+pub struct RouterNamespaceCreator {
+    addr: String,
+}
+
+impl RouterNamespaceCreator {
+    /// Create namespaces through the router listening on the gRPC address `addr`.
+    pub fn new(addr: impl Into<String>) -> Self {
+        Self { addr: addr.into() }
+    }
+
+    async fn connect(&self) -> Result<Connection, Error> {
+        connection::Builder::new()
+            .build(self.addr.as_str())
+            .await
+            .context(ConnectSnafu { addr: &self.addr })
+    }
+}
+
+#[async_trait]
+impl NamespaceCreator for RouterNamespaceCreator {
+    async fn create_namespace(
+        &self,
+        name: &str,
+        retention_period_ns: Option<i64>,
+    ) -> Result<(), Error> {
+        let name = NamespaceName::new(name.to_owned()).context(InvalidNameSnafu)?;
+        // The router maps a zero retention period to an infinite one.
+        let requested = retention_period_ns.filter(|ns| *ns != 0);
+
+        let mut client = Client::new(self.connect().await?);
+        match client.create_namespace(&name, requested, None, None).await {
+            Ok(_) => {
+                info!(namespace=%name, ?requested, "created namespace");
+                Ok(())
+            }
+            Err(ClientError::AlreadyExists(_)) => {
+                let existing = client
+                    .get_namespaces()
+                    .await
+                    .context(LookupSnafu {
+                        name: name.as_str(),
+                    })?
+                    .into_iter()
+                    .find(|ns| ns.name == name.as_str())
+                    .and_then(|ns| ns.retention_period_ns);
+
+                check_retention(&name, existing, requested)
+            }
+            Err(source) => Err(Error::Create {
+                name: name.to_string(),
+                source,
+            }),
+        }
+    }
+}
+
+/// Check that the `existing` retention period of the namespace `name` matches the `requested`
+/// one.
+fn check_retention(name: &str, existing: Option<i64>, requested: Option<i64>) -> Result<(), Error> {
+    if existing == requested {
+        return Ok(());
+    }
+    RetentionConflictSnafu {
+        name,
+        existing,
+        requested,
+    }
+    .fail()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_retention() {
+        check_retention("ns", None, None).unwrap();
+        check_retention("ns", Some(3_600_000_000_000), Some(3_600_000_000_000)).unwrap();
+
+        let err = check_retention("ns", None, Some(3_600_000_000_000)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "retention policy conflicts with an existing policy: namespace ns has a retention \
+             period of infinite, not 3600000000000ns"
+        );
+        assert!(matches!(
+            check_retention("ns", Some(1), None),
+            Err(Error::RetentionConflict { .. })
+        ));
+    }
+}
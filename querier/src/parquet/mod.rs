@@ -226,7 +226,7 @@ pub mod tests {
                 .list_by_namespace_id(ns.namespace.id)
                 .await
                 .unwrap();
-            let cached_namespace = CachedNamespace::new(ns.namespace.clone(), tables, columns);
+            let cached_namespace = CachedNamespace::new(ns.namespace.clone(), tables, columns, &[]);
             let cached_table =
                 Arc::clone(cached_namespace.tables.get("table").expect("table exists"));
 
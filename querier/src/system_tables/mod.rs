@@ -1,7 +1,7 @@
 use crate::query_log::QueryLog;
 use arrow::{datatypes::SchemaRef, error::Result as ArrowResult, record_batch::RecordBatch};
 use async_trait::async_trait;
-use data_types::NamespaceId;
+use data_types::{NamespaceId, TableId};
 use datafusion::error::DataFusionError;
 use datafusion::physical_plan::{DisplayAs, DisplayFormatType};
 use datafusion::{
@@ -16,6 +16,7 @@ use datafusion::{
     },
     prelude::Expr,
 };
+use iox_catalog::interface::Catalog;
 use std::collections::HashMap;
 use std::{
     any::Any,
@@ -25,10 +26,12 @@ use std::{
 };
 
 mod queries;
+mod storage;
 
 pub const SYSTEM_SCHEMA: &str = "system";
 
 const QUERIES_TABLE: &str = "queries";
+const STORAGE_TABLE: &str = "storage";
 
 pub struct SystemSchemaProvider {
     tables: HashMap<&'static str, Arc<dyn TableProvider>>,
@@ -37,11 +40,22 @@ pub struct SystemSchemaProvider {
 impl SystemSchemaProvider {
     pub fn new(
         query_log: Arc<QueryLog>,
+        catalog: Arc<dyn Catalog>,
         namespace_id: NamespaceId,
+        table_names: HashMap<TableId, Arc<str>>,
         include_debug_info: bool,
     ) -> Self {
         let mut tables: HashMap<&'static str, Arc<dyn TableProvider>> = HashMap::new();
 
+        tables.insert(
+            STORAGE_TABLE,
+            Arc::new(storage::StorageTable::new(
+                catalog,
+                namespace_id,
+                table_names,
+            )),
+        );
+
         if include_debug_info {
             let queries = Arc::new(SystemTableProvider {
                 table: Arc::new(queries::QueriesTable::new(query_log, Some(namespace_id))),
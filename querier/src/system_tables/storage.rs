@@ -0,0 +1,173 @@
+use arrow::{
+    array::{ArrayRef, Int16Array, Int64Array, StringArray},
+    datatypes::{DataType, Field, Schema, SchemaRef},
+    record_batch::RecordBatch,
+};
+use async_trait::async_trait;
+use data_types::{NamespaceId, StorageUsage, TableId};
+use datafusion::{
+    datasource::TableProvider,
+    error::{DataFusionError, Result as DataFusionResult},
+    execution::context::SessionState,
+    logical_expr::TableType,
+    physical_plan::{memory::MemoryExec, ExecutionPlan},
+    prelude::Expr,
+};
+use iox_catalog::interface::Catalog;
+use std::{any::Any, collections::HashMap, sync::Arc};
+
+/// Implementation of the system.storage table, listing the object storage used by each table and
+/// compaction level of the namespace.
+///
+/// Unlike the other system tables, the usage is read from the catalog when the table is scanned.
+#[derive(Debug)]
+pub(super) struct StorageTable {
+    schema: SchemaRef,
+    catalog: Arc<dyn Catalog>,
+    namespace_id: NamespaceId,
+    table_names: HashMap<TableId, Arc<str>>,
+}
+
+impl StorageTable {
+    pub(super) fn new(
+        catalog: Arc<dyn Catalog>,
+        namespace_id: NamespaceId,
+        table_names: HashMap<TableId, Arc<str>>,
+    ) -> Self {
+        Self {
+            schema: storage_schema(),
+            catalog,
+            namespace_id,
+            table_names,
+        }
+    }
+}
+
+#[async_trait]
+impl TableProvider for StorageTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        Arc::clone(&self.schema)
+    }
+
+    fn table_type(&self) -> TableType {
+        TableType::Base
+    }
+
+    async fn scan(
+        &self,
+        _ctx: &SessionState,
+        projection: Option<&Vec<usize>>,
+        _filters: &[Expr],
+        _limit: Option<usize>,
+    ) -> DataFusionResult<Arc<dyn ExecutionPlan>> {
+        let usage = self
+            .catalog
+            .repositories()
+            .await
+            .parquet_files()
+            .storage_usage_by_namespace(self.namespace_id)
+            .await
+            .map_err(|e| DataFusionError::External(Box::new(e)))?;
+
+        let batch = from_storage_usage(self.schema(), &usage, &self.table_names)?;
+
+        Ok(Arc::new(MemoryExec::try_new(
+            &[vec![batch]],
+            self.schema(),
+            projection.cloned(),
+        )?))
+    }
+}
+
+fn storage_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("table_name", DataType::Utf8, true),
+        Field::new("compaction_level", DataType::Int16, false),
+        Field::new("file_count", DataType::Int64, false),
+        Field::new("file_size_bytes", DataType::Int64, false),
+        Field::new("row_count", DataType::Int64, false),
+    ]))
+}
+
+fn from_storage_usage(
+    schema: SchemaRef,
+    usage: &[StorageUsage],
+    table_names: &HashMap<TableId, Arc<str>>,
+) -> Result<RecordBatch, DataFusionError> {
+    let table_name = usage
+        .iter()
+        .map(|u| table_names.get(&u.table_id).map(|name| name.as_ref()))
+        .collect::<StringArray>();
+    let compaction_level = usage
+        .iter()
+        .map(|u| Some(u.compaction_level as i16))
+        .collect::<Int16Array>();
+    let file_count = usage
+        .iter()
+        .map(|u| Some(u.file_count))
+        .collect::<Int64Array>();
+    let file_size_bytes = usage
+        .iter()
+        .map(|u| Some(u.file_size_bytes))
+        .collect::<Int64Array>();
+    let row_count = usage
+        .iter()
+        .map(|u| Some(u.row_count))
+        .collect::<Int64Array>();
+
+    Ok(RecordBatch::try_new(
+        schema,
+        vec![
+            Arc::new(table_name) as ArrayRef,
+            Arc::new(compaction_level),
+            Arc::new(file_count),
+            Arc::new(file_size_bytes),
+            Arc::new(row_count),
+        ],
+    )?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_util::assert_batches_eq;
+    use data_types::CompactionLevel;
+
+    #[test]
+    fn test_from_storage_usage() {
+        let usage = |table_id, compaction_level, file_count| StorageUsage {
+            namespace_id: NamespaceId::new(1),
+            table_id: TableId::new(table_id),
+            compaction_level,
+            file_count,
+            file_size_bytes: file_count * 100,
+            row_count: file_count * 10,
+        };
+        let usage = [
+            usage(1, CompactionLevel::Initial, 3),
+            usage(1, CompactionLevel::Final, 1),
+            usage(2, CompactionLevel::FileNonOverlapped, 2),
+        ];
+        let table_names = HashMap::from([(TableId::new(1), Arc::from("cpu"))]);
+
+        let batch = from_storage_usage(storage_schema(), &usage, &table_names).unwrap();
+
+        // Tables created after the namespace snapshot was taken have no name.
+        assert_batches_eq!(
+            [
+                "+------------+------------------+------------+-----------------+-----------+",
+                "| table_name | compaction_level | file_count | file_size_bytes | row_count |",
+                "+------------+------------------+------------+-----------------+-----------+",
+                "| cpu        | 0                | 3          | 300             | 30        |",
+                "| cpu        | 2                | 1          | 100             | 10        |",
+                "|            | 1                | 2          | 200             | 20        |",
+                "+------------+------------------+------------+-----------------+-----------+",
+            ],
+            &[batch]
+        );
+    }
+}
@@ -1,7 +1,7 @@
 use self::query_access::QuerierTableChunkPruner;
 use crate::{
     cache::{
-        namespace::CachedTable,
+        namespace::{CachedTable, TieredRetention},
         partition::{CachedPartition, PartitionRequest},
     },
     ingester::{self, IngesterPartition},
@@ -53,7 +53,8 @@ impl From<Error> for DataFusionError {
 pub struct QuerierTableArgs {
     pub namespace_id: NamespaceId,
     pub namespace_name: Arc<str>,
-    pub namespace_retention_period: Option<Duration>,
+    pub retention_period: Option<Duration>,
+    pub tiered_retention: Option<TieredRetention>,
     pub table_id: TableId,
     pub table_name: Arc<str>,
     pub schema: Schema,
@@ -71,8 +72,13 @@ pub struct QuerierTable {
     /// Namespace ID for this table.
     namespace_id: NamespaceId,
 
-    /// Namespace retenion
-    namespace_retention_period: Option<Duration>,
+    /// Retention period of the table, which is the namespace retention period unless the table
+    /// overrides it.
+    retention_period: Option<Duration>,
+
+    /// Retention of the table if it is the source table of rollup rules with a raw retention
+    /// period, which replaces [`Self::retention_period`].
+    tiered_retention: Option<TieredRetention>,
 
     /// Table name.
     table_name: Arc<str>,
@@ -99,7 +105,8 @@ impl QuerierTable {
         let QuerierTableArgs {
             namespace_id,
             namespace_name,
-            namespace_retention_period,
+            retention_period,
+            tiered_retention,
             table_id,
             table_name,
             schema,
@@ -111,7 +118,8 @@ impl QuerierTable {
         Self {
             namespace_name,
             namespace_id,
-            namespace_retention_period,
+            retention_period,
+            tiered_retention,
             table_name,
             table_id,
             schema,
@@ -131,6 +139,24 @@ impl QuerierTable {
         self.table_id
     }
 
+    /// The time before which data of this table may have been deleted, and is not returned by
+    /// queries.
+    pub fn retention_time_ns(&self) -> Option<i64> {
+        let now = self
+            .chunk_adapter
+            .catalog_cache()
+            .time_provider()
+            .now()
+            .timestamp_nanos();
+
+        match &self.tiered_retention {
+            Some(tiered_retention) => tiered_retention.retention_time_ns(now),
+            None => self
+                .retention_period
+                .map(|retention| now - retention.as_nanos() as i64),
+        }
+    }
+
     /// Schema.
     pub fn schema(&self) -> &Schema {
         &self.schema
@@ -53,22 +53,12 @@ impl TableProvider for QuerierTable {
         let mut builder =
             ProviderBuilder::new(Arc::clone(self.table_name()), self.schema().clone());
 
-        let filters = match self.namespace_retention_period {
-            Some(d) => {
-                let ts = self
-                    .chunk_adapter
-                    .catalog_cache()
-                    .time_provider()
-                    .now()
-                    .timestamp_nanos()
-                    - d.as_nanos() as i64;
-
-                filters
-                    .iter()
-                    .cloned()
-                    .chain(std::iter::once(retention_expr(ts)))
-                    .collect::<Vec<_>>()
-            }
+        let filters = match self.retention_time_ns() {
+            Some(ts) => filters
+                .iter()
+                .cloned()
+                .chain(std::iter::once(retention_expr(ts)))
+                .collect::<Vec<_>>(),
             None => filters.to_vec(),
         };
 
@@ -38,15 +38,16 @@ pub async fn querier_table(catalog: &Arc<TestCatalog>, table: &Arc<TestTable>) -
 
     let namespace_name = Arc::from(table.namespace.namespace.name.as_str());
 
-    let namespace_retention_period = table
-        .namespace
-        .namespace
+    let retention_period = table
+        .table
         .retention_period_ns
+        .or(table.namespace.namespace.retention_period_ns)
         .map(|retention| Duration::from_nanos(retention as u64));
     QuerierTable::new(QuerierTableArgs {
         namespace_id: table.namespace.namespace.id,
         namespace_name,
-        namespace_retention_period,
+        retention_period,
+        tiered_retention: None,
         table_id: table.table.id,
         table_name: table.table.name.clone().into(),
         schema,
@@ -0,0 +1,101 @@
+//! Execution of rollup queries.
+
+use std::{fmt::Debug, sync::Arc};
+
+use arrow::record_batch::RecordBatch;
+use async_trait::async_trait;
+use data_types::RollupQueryLanguage;
+use iox_query::QueryNamespace;
+use service_common::{planner::Planner, QueryNamespaceProvider};
+use snafu::prelude::*;
+
+/// Errors executing a rollup query.
+#[derive(Debug, Snafu)]
+#[allow(missing_docs)]
+pub enum ExecutionError {
+    #[snafu(display("Namespace not found: {namespace}"))]
+    NamespaceNotFound { namespace: String },
+
+    #[snafu(display("Error executing rollup query: {source}"))]
+    Query {
+        source: datafusion::error::DataFusionError,
+    },
+}
+
+/// Runs the queries of rollup rules.
+#[async_trait]
+pub trait RollupExecutor: Debug + Send + Sync {
+    /// Run `query`, written in `language`, against `namespace` and return all of its output.
+    async fn query(
+        &self,
+        namespace: &str,
+        language: RollupQueryLanguage,
+        query: &str,
+    ) -> Result<Vec<RecordBatch>, ExecutionError>;
+
+    /// The time before which data of `table` in `namespace` is not returned by queries, as it
+    /// may have been deleted by its retention period.
+    async fn retention_time_ns(
+        &self,
+        namespace: &str,
+        table: &str,
+    ) -> Result<Option<i64>, ExecutionError>;
+}
+
+/// A [`RollupExecutor`] running queries against the namespaces of a [`QueryNamespaceProvider`],
+/// such as the querier's database.
+#[derive(Debug)]
+pub struct NamespaceProviderExecutor<P> {
+    provider: Arc<P>,
+}
+
+impl<P> NamespaceProviderExecutor<P> {
+    /// Create a new executor for the namespaces of `provider`.
+    pub fn new(provider: Arc<P>) -> Self {
+        Self { provider }
+    }
+}
+
+#[async_trait]
+impl<P> RollupExecutor for NamespaceProviderExecutor<P>
+where
+    P: QueryNamespaceProvider,
+{
+    async fn query(
+        &self,
+        namespace: &str,
+        language: RollupQueryLanguage,
+        query: &str,
+    ) -> Result<Vec<RecordBatch>, ExecutionError> {
+        let db = self
+            .provider
+            .db(namespace, None, false)
+            .await
+            .context(NamespaceNotFoundSnafu { namespace })?;
+
+        let _permit = self.provider.acquire_semaphore(namespace, None).await;
+        let ctx = db.new_query_context(None);
+        let planner = Planner::new(&ctx);
+        let plan = match language {
+            RollupQueryLanguage::Sql => planner.sql(query).await,
+            RollupQueryLanguage::InfluxQl => planner.influxql(query, Default::default()).await,
+        }
+        .context(QuerySnafu)?;
+
+        ctx.collect(plan).await.context(QuerySnafu)
+    }
+
+    async fn retention_time_ns(
+        &self,
+        namespace: &str,
+        table: &str,
+    ) -> Result<Option<i64>, ExecutionError> {
+        let db = self
+            .provider
+            .db(namespace, None, false)
+            .await
+            .context(NamespaceNotFoundSnafu { namespace })?;
+
+        Ok(db.retention_time_ns(table))
+    }
+}
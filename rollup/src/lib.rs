@@ -0,0 +1,1009 @@
+//! Continuous rollup of raw data into downsampled tables.
+//!
+//! The [`RollupScheduler`] periodically evaluates every [`RollupRule`] in the catalog over the
+//! time range that has been persisted since the rule last ran. The generated aggregation query
+//! is executed by a [`RollupExecutor`] and the result is written into the target table of the
+//! rule by a [`RollupWriter`], after which the rule's `covered_until` watermark is advanced.
+//! A failed run leaves the watermark where it is, so the same windows are retried in the next
+//! iteration, and records the error as the `last_error` of the rule.
+//!
+//! Only complete windows are rolled up: a window is ready once data newer than its end has been
+//! persisted for the source table.
+//!
+//! Each run only lists the files of the source table created since the previous one, tracked by
+//! the `files_checked_until` watermark of the rule. Windows before `covered_until` that such a
+//! file persisted data into, because it was written late or backfilled, are rolled up again,
+//! replacing the rows written before. Late data is only rolled up while the querier returns all
+//! data of its window: windows before the retention time of the source table, and for rules with
+//! a raw retention period the windows whose raw data was already deleted, keep their rollup.
+//!
+//! The raw data of a rule with a raw retention period is deleted once it has been rolled up, so
+//! the watermark of such a rule never advances over persisted data the querier did not return
+//! because of the retention period of the source table.
+
+#![deny(
+    rustdoc::broken_intra_doc_links,
+    rust_2018_idioms,
+    missing_debug_implementations,
+    unreachable_pub
+)]
+#![warn(
+    missing_docs,
+    clippy::todo,
+    clippy::dbg_macro,
+    clippy::clone_on_ref_ptr,
+    // See https://github.com/influxdata/influxdb_iox/pull/1671
+    clippy::future_not_send,
+    unused_crate_dependencies
+)]
+#![allow(clippy::missing_docs_in_private_items)]
+
+// Workaround for "unused crate" lint false positives.
+use workspace_hack as _;
+
+use std::{collections::HashSet, ops::Range, sync::Arc, time::Duration};
+
+use arrow::record_batch::RecordBatch;
+use data_types::{ColumnType, CompactionLevel, ParquetFile, RollupRule, Timestamp};
+use iox_catalog::interface::{Catalog, SoftDeletedRows};
+use metric::U64Counter;
+use observability_deps::tracing::*;
+use snafu::prelude::*;
+use tokio::{select, time::sleep};
+use tokio_util::sync::CancellationToken;
+
+mod executor;
+mod query;
+mod writer;
+
+pub use executor::{ExecutionError, NamespaceProviderExecutor, RollupExecutor};
+pub use writer::{batch_to_lp, RollupWriter, RouterWriter, WriteError};
+
+use query::{align_down, rollup_query};
+
+/// The maximum number of windows rolled up by a single query.
+///
+/// Bounds the amount of data read and written at once when a rule has a lot of catching up to
+/// do, for example right after it was created.
+const MAX_WINDOWS_PER_QUERY: i64 = 1_000;
+
+/// How long after its creation time a parquet file may become visible in the catalog.
+///
+/// The creation time of a file is taken before it is committed, so every run lists the files
+/// created since this long before the previous one. Files seen by the previous run may be listed
+/// again, rolling up the windows they contain data of once more.
+const FILE_COMMIT_LAG: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Snafu)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[snafu(display("Failed to access the catalog: {source}"))]
+    Catalog {
+        source: iox_catalog::interface::Error,
+    },
+
+    #[snafu(display("Namespace {namespace_id} of rollup rule '{name}' not found"))]
+    NamespaceNotFound {
+        namespace_id: data_types::NamespaceId,
+        name: String,
+    },
+
+    #[snafu(display("Rollup rule '{name}' has an invalid interval of {interval_ns}ns"))]
+    InvalidInterval { name: String, interval_ns: i64 },
+
+    #[snafu(display("Failed to execute rollup query '{query}': {source}"))]
+    Execute {
+        query: String,
+        source: ExecutionError,
+    },
+
+    #[snafu(display(
+        "Failed to get the retention time of the source table of rollup rule '{name}': {source}"
+    ))]
+    RetentionTime {
+        name: String,
+        source: ExecutionError,
+    },
+
+    #[snafu(display("Failed to write rolled up data of rule '{name}': {source}"))]
+    Write { name: String, source: WriteError },
+
+    #[snafu(display(
+        "Persisted data of the source table of rollup rule '{name}' between {from} and {until} \
+         is not readable because of its retention period"
+    ))]
+    SourceNotReadable { name: String, from: i64, until: i64 },
+}
+
+/// A specialized `Error` for rollup errors
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Periodically runs all rollup rules found in the catalog.
+#[derive(Debug)]
+pub struct RollupScheduler {
+    catalog: Arc<dyn Catalog>,
+    executor: Arc<dyn RollupExecutor>,
+    writer: Arc<dyn RollupWriter>,
+    interval: Duration,
+
+    /// Number of successful runs of a rule.
+    runs_ok: U64Counter,
+    /// Number of failed runs of a rule.
+    runs_error: U64Counter,
+}
+
+impl RollupScheduler {
+    /// Create a new scheduler, checking the rules for new data every `interval`.
+    pub fn new(
+        catalog: Arc<dyn Catalog>,
+        executor: Arc<dyn RollupExecutor>,
+        writer: Arc<dyn RollupWriter>,
+        interval: Duration,
+        metrics: &metric::Registry,
+    ) -> Self {
+        let runs = metrics.register_metric::<U64Counter>(
+            "rollup_rule_runs",
+            "number of runs of a rollup rule, by result",
+        );
+
+        Self {
+            catalog,
+            executor,
+            writer,
+            interval,
+            runs_ok: runs.recorder(&[("result", "success")]),
+            runs_error: runs.recorder(&[("result", "error")]),
+        }
+    }
+
+    /// Run rollups until `shutdown` is cancelled.
+    ///
+    /// Failures of individual rules are logged and retried in the next iteration.
+    pub async fn run(self, shutdown: CancellationToken) {
+        info!(interval = ?self.interval, "starting rollup scheduler");
+
+        loop {
+            if let Err(e) = self.run_once().await {
+                warn!(%e, "failed to run rollup rules");
+            }
+
+            select! {
+                _ = shutdown.cancelled() => {
+                    break
+                },
+                _ = sleep(self.interval) => (),
+            }
+        }
+
+        info!("rollup scheduler stopped");
+    }
+
+    /// Run every rollup rule in the catalog once.
+    pub async fn run_once(&self) -> Result<()> {
+        let rules = self
+            .catalog
+            .repositories()
+            .await
+            .rollup_rules()
+            .list()
+            .await
+            .context(CatalogSnafu)?;
+
+        for rule in rules {
+            let last_error = match self.run_rule(&rule).await {
+                Ok(Some(covered_until)) => {
+                    info!(rule = %rule.name, %covered_until, "rolled up data");
+                    self.runs_ok.inc(1);
+                    None
+                }
+                Ok(None) => {
+                    debug!(rule = %rule.name, "no new data to roll up");
+                    self.runs_ok.inc(1);
+                    None
+                }
+                Err(e) => {
+                    warn!(rule = %rule.name, %e, "failed to run rollup rule");
+                    self.runs_error.inc(1);
+                    Some(e.to_string())
+                }
+            };
+
+            if rule.last_error != last_error {
+                if let Err(e) = self
+                    .catalog
+                    .repositories()
+                    .await
+                    .rollup_rules()
+                    .update_last_error(rule.id, last_error)
+                    .await
+                {
+                    warn!(rule = %rule.name, %e, "failed to record the status of rollup rule");
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Roll up all complete windows of `rule` that have not been rolled up yet, and roll up
+    /// again the windows that files persisted since the previous run added data to.
+    ///
+    /// Returns the new `covered_until` watermark of the rule, if it advanced.
+    async fn run_rule(&self, rule: &RollupRule) -> Result<Option<i64>> {
+        ensure!(
+            rule.interval_ns > 0,
+            InvalidIntervalSnafu {
+                name: &rule.name,
+                interval_ns: rule.interval_ns,
+            }
+        );
+
+        let mut repos = self.catalog.repositories().await;
+
+        let namespace = repos
+            .namespaces()
+            .get_by_id(rule.namespace_id, SoftDeletedRows::ExcludeDeleted)
+            .await
+            .context(CatalogSnafu)?
+            .context(NamespaceNotFoundSnafu {
+                namespace_id: rule.namespace_id,
+                name: &rule.name,
+            })?;
+
+        let Some(source) = repos
+            .tables()
+            .get_by_namespace_and_name(rule.namespace_id, &rule.source_table)
+            .await
+            .context(CatalogSnafu)?
+        else {
+            // nothing has been written to the source table yet
+            return Ok(None);
+        };
+
+        // Taken before listing, so files committed while listing are listed in the next run.
+        let checked_until = Timestamp::from(self.catalog.time_provider().now());
+        let late_from = rule.covered_until.zip(rule.files_checked_until);
+        let files = match late_from {
+            Some((_, files_checked_until)) => {
+                repos
+                    .parquet_files()
+                    .list_by_table_created_after(
+                        source.id,
+                        files_checked_until - FILE_COMMIT_LAG.as_nanos() as i64,
+                    )
+                    .await
+            }
+            None => {
+                repos
+                    .parquet_files()
+                    .list_by_table_not_to_delete(source.id)
+                    .await
+            }
+        }
+        .context(CatalogSnafu)?;
+
+        let late = match late_from {
+            Some((covered_until, _)) => late_ranges(&files, rule.interval_ns, covered_until.get()),
+            None => vec![],
+        };
+
+        let forward = files
+            .iter()
+            .map(|f| f.max_time.get())
+            .max()
+            .and_then(|max_time| {
+                // the window containing the newest persisted data may still receive writes
+                let ready_until = align_down(max_time, rule.interval_ns);
+                let start = match rule.covered_until {
+                    Some(covered_until) => covered_until.get(),
+                    None => align_down(
+                        files
+                            .iter()
+                            .map(|f| f.min_time.get())
+                            .min()
+                            .expect("at least one file"),
+                        rule.interval_ns,
+                    ),
+                };
+                (start < ready_until).then_some(start..ready_until)
+            });
+
+        let tags = if late.is_empty() && forward.is_none() {
+            vec![]
+        } else {
+            repos
+                .columns()
+                .list_by_table_id(source.id)
+                .await
+                .context(CatalogSnafu)?
+                .into_iter()
+                .filter(|c| c.column_type == ColumnType::Tag)
+                .map(|c| c.name)
+                .collect::<Vec<_>>()
+        };
+        let tag_set = tags.iter().cloned().collect::<HashSet<_>>();
+        drop(repos);
+
+        for range in late {
+            self.roll_up_late(rule, namespace.name.as_str(), &tags, &tag_set, range)
+                .await?;
+        }
+
+        let mut covered_until = None;
+        if let Some(range) = forward {
+            let step = rule.interval_ns.saturating_mul(MAX_WINDOWS_PER_QUERY);
+            let mut start = range.start;
+            while start < range.end {
+                let end = start.saturating_add(step).min(range.end);
+                let batches = self
+                    .execute(rule, namespace.name.as_str(), &tags, start..end)
+                    .await?;
+
+                // Raw data is deleted once covered, so it must not be covered unread. Until its
+                // namespace cache knows about the raw retention period of a new rule, or the
+                // latest watermark, the querier prunes data by the retention period of the
+                // source table. The retention time only moves forward, so it is checked after
+                // the query.
+                if rule.raw_retention_period_ns.is_some() {
+                    let retention_time = self
+                        .executor
+                        .retention_time_ns(namespace.name.as_str(), &rule.source_table)
+                        .await
+                        .context(RetentionTimeSnafu { name: &rule.name })?;
+                    if let Some(until) = retention_time.filter(|t| *t > start) {
+                        // only the files persisted since the previous run have been listed
+                        let files = self
+                            .catalog
+                            .repositories()
+                            .await
+                            .parquet_files()
+                            .list_by_table_not_to_delete(source.id)
+                            .await
+                            .context(CatalogSnafu)?;
+                        ensure!(
+                            !files
+                                .iter()
+                                .any(|f| f.min_time.get() < until && f.max_time.get() >= start),
+                            SourceNotReadableSnafu {
+                                name: &rule.name,
+                                from: start,
+                                until,
+                            }
+                        );
+                    }
+                }
+
+                self.write(rule, namespace.name.as_str(), &tag_set, &batches)
+                    .await?;
+
+                // Record progress after every successful write, so a failure later on does not
+                // roll up the same windows again.
+                self.catalog
+                    .repositories()
+                    .await
+                    .rollup_rules()
+                    .update_covered_until(rule.id, Timestamp::new(end))
+                    .await
+                    .context(CatalogSnafu)?;
+                start = end;
+                covered_until = Some(end);
+            }
+        }
+
+        // Files are only listed since the previous run once data has been rolled up, as the
+        // first window to roll up is found from all files.
+        if rule.covered_until.is_some() || covered_until.is_some() {
+            self.catalog
+                .repositories()
+                .await
+                .rollup_rules()
+                .update_files_checked_until(rule.id, checked_until)
+                .await
+                .context(CatalogSnafu)?;
+        }
+
+        Ok(covered_until)
+    }
+
+    /// Roll up the windows in `range` again, as files persisted after they were rolled up added
+    /// data to them.
+    ///
+    /// Windows before the retention time of the source table are skipped, as the querier no
+    /// longer returns all of their data and rolling them up again would replace their rolled up
+    /// data with an aggregate over part of it.
+    async fn roll_up_late(
+        &self,
+        rule: &RollupRule,
+        namespace: &str,
+        tags: &[String],
+        tag_set: &HashSet<String>,
+        range: Range<i64>,
+    ) -> Result<()> {
+        let step = rule.interval_ns.saturating_mul(MAX_WINDOWS_PER_QUERY);
+        let mut start = range.start;
+        while start < range.end {
+            let readable_from = self.readable_from(rule, namespace).await?;
+            if readable_from > start {
+                warn!(
+                    rule = %rule.name,
+                    from = start,
+                    until = readable_from.min(range.end),
+                    "not rolling up late data before the retention time of the source table"
+                );
+                start = readable_from;
+                continue;
+            }
+
+            let end = start.saturating_add(step).min(range.end);
+            let batches = self.execute(rule, namespace, tags, start..end).await?;
+            // the retention time may have moved past the start while querying
+            if self.readable_from(rule, namespace).await? > start {
+                continue;
+            }
+            self.write(rule, namespace, tag_set, &batches).await?;
+
+            info!(rule = %rule.name, from = start, until = end, "rolled up late data");
+            start = end;
+        }
+
+        Ok(())
+    }
+
+    /// The start of the first window of `rule` the querier returns all data of.
+    async fn readable_from(&self, rule: &RollupRule, namespace: &str) -> Result<i64> {
+        let retention_time = self
+            .executor
+            .retention_time_ns(namespace, &rule.source_table)
+            .await
+            .context(RetentionTimeSnafu { name: &rule.name })?;
+
+        Ok(retention_time.map_or(i64::MIN, |t| {
+            align_down(t.saturating_add(rule.interval_ns - 1), rule.interval_ns)
+        }))
+    }
+
+    /// Query the rolled up data of the windows of `rule` in `range`.
+    async fn execute(
+        &self,
+        rule: &RollupRule,
+        namespace: &str,
+        tags: &[String],
+        range: Range<i64>,
+    ) -> Result<Vec<RecordBatch>> {
+        let query = rollup_query(rule, tags, range);
+        self.executor
+            .query(namespace, rule.language, &query)
+            .await
+            .context(ExecuteSnafu { query: &query })
+    }
+
+    /// Write the rolled up data of `rule` into its target table.
+    async fn write(
+        &self,
+        rule: &RollupRule,
+        namespace: &str,
+        tag_set: &HashSet<String>,
+        batches: &[RecordBatch],
+    ) -> Result<()> {
+        let lp = batches
+            .iter()
+            .map(|batch| writer::batch_to_lp(&rule.target_table, tag_set, batch))
+            .collect::<Result<String, _>>()
+            .context(WriteSnafu { name: &rule.name })?;
+        if !lp.is_empty() {
+            self.writer
+                .write_lp(namespace, lp)
+                .await
+                .context(WriteSnafu { name: &rule.name })?;
+        }
+        Ok(())
+    }
+}
+
+/// The windows before `covered_until` that `files` contain data of, as merged ranges.
+///
+/// Only files persisted by the ingester contain new data: compacted files contain data that was
+/// persisted before.
+fn late_ranges(files: &[ParquetFile], interval_ns: i64, covered_until: i64) -> Vec<Range<i64>> {
+    let mut ranges = files
+        .iter()
+        .filter(|f| {
+            f.compaction_level == CompactionLevel::Initial && f.min_time.get() < covered_until
+        })
+        .map(|f| {
+            let end = align_down(f.max_time.get(), interval_ns).saturating_add(interval_ns);
+            align_down(f.min_time.get(), interval_ns)..end.min(covered_until)
+        })
+        .collect::<Vec<_>>();
+    ranges.sort_unstable_by_key(|r| r.start);
+
+    let mut merged: Vec<Range<i64>> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::{
+        array::{ArrayRef, Float64Array, StringArray, TimestampNanosecondArray},
+        record_batch::RecordBatch,
+    };
+    use async_trait::async_trait;
+    use data_types::{RollupQueryLanguage, RollupRuleParams};
+    use iox_catalog::{
+        mem::MemCatalog,
+        test_helpers::{arbitrary_namespace, arbitrary_parquet_file_params, arbitrary_table},
+    };
+    use parking_lot::Mutex;
+
+    use super::*;
+
+    const HOUR: i64 = 60 * 60 * 1_000_000_000;
+
+    #[derive(Debug, Default)]
+    struct MockExecutor {
+        queries: Mutex<Vec<(String, RollupQueryLanguage, String)>>,
+        retention_time_ns: Mutex<Option<i64>>,
+    }
+
+    #[async_trait]
+    impl RollupExecutor for MockExecutor {
+        async fn query(
+            &self,
+            namespace: &str,
+            language: RollupQueryLanguage,
+            query: &str,
+        ) -> Result<Vec<RecordBatch>, ExecutionError> {
+            self.queries
+                .lock()
+                .push((namespace.to_string(), language, query.to_string()));
+
+            let batch = RecordBatch::try_from_iter(vec![
+                (
+                    "time",
+                    Arc::new(TimestampNanosecondArray::from(vec![0])) as ArrayRef,
+                ),
+                ("host", Arc::new(StringArray::from(vec!["a"])) as ArrayRef),
+                ("usage", Arc::new(Float64Array::from(vec![1.5])) as ArrayRef),
+            ])
+            .unwrap();
+            Ok(vec![batch])
+        }
+
+        async fn retention_time_ns(
+            &self,
+            _namespace: &str,
+            _table: &str,
+        ) -> Result<Option<i64>, ExecutionError> {
+            Ok(*self.retention_time_ns.lock())
+        }
+    }
+
+    #[derive(Debug, Default)]
+    struct MockWriter {
+        writes: Mutex<Vec<(String, String)>>,
+        fail: Mutex<bool>,
+    }
+
+    #[async_trait]
+    impl RollupWriter for MockWriter {
+        async fn write_lp(&self, namespace: &str, lp: String) -> Result<(), WriteError> {
+            if *self.fail.lock() {
+                return Err(WriteError::NoFields);
+            }
+            self.writes.lock().push((namespace.to_string(), lp));
+            Ok(())
+        }
+    }
+
+    async fn rule(catalog: &Arc<dyn Catalog>) -> RollupRule {
+        let rules = catalog
+            .repositories()
+            .await
+            .rollup_rules()
+            .list()
+            .await
+            .unwrap();
+        assert_eq!(rules.len(), 1);
+        rules[0].clone()
+    }
+
+    async fn covered_until(catalog: &Arc<dyn Catalog>) -> Option<Timestamp> {
+        rule(catalog).await.covered_until
+    }
+
+    fn runs(metrics: &metric::Registry, result: &'static str) -> u64 {
+        metrics
+            .get_instrument::<metric::Metric<U64Counter>>("rollup_rule_runs")
+            .expect("failed to read metric")
+            .get_observer(&metric::Attributes::from(&[("result", result)]))
+            .expect("failed to get observer")
+            .fetch()
+    }
+
+    #[tokio::test]
+    async fn test_run_rule_over_persisted_windows() {
+        let metrics = Arc::new(metric::Registry::default());
+        let catalog: Arc<dyn Catalog> = Arc::new(MemCatalog::new(Arc::clone(&metrics)));
+
+        let mut repos = catalog.repositories().await;
+        let namespace = arbitrary_namespace(&mut *repos, "ns").await;
+        let table = arbitrary_table(&mut *repos, "cpu", &namespace).await;
+        repos
+            .columns()
+            .create_or_get("host", table.id, ColumnType::Tag)
+            .await
+            .unwrap();
+        let partition = repos
+            .partitions()
+            .create_or_get("1970-01-01".into(), table.id)
+            .await
+            .unwrap();
+        repos
+            .rollup_rules()
+            .create(RollupRuleParams {
+                namespace_id: namespace.id,
+                name: "cpu_1h".to_string(),
+                source_table: "cpu".to_string(),
+                target_table: "cpu_1h".to_string(),
+                language: RollupQueryLanguage::Sql,
+                aggregates: "avg(usage) AS usage".to_string(),
+                interval_ns: HOUR,
+                raw_retention_period_ns: None,
+            })
+            .await
+            .unwrap();
+
+        // persisted data covering the first hour and part of the second one
+        let mut params = arbitrary_parquet_file_params(&namespace, &table, &partition);
+        params.min_time = Timestamp::new(10);
+        params.max_time = Timestamp::new(HOUR + 10);
+        repos.parquet_files().create(params.clone()).await.unwrap();
+        drop(repos);
+
+        let executor = Arc::new(MockExecutor::default());
+        let writer = Arc::new(MockWriter::default());
+        let scheduler = RollupScheduler::new(
+            Arc::clone(&catalog),
+            Arc::clone(&executor) as _,
+            Arc::clone(&writer) as _,
+            Duration::from_secs(1),
+            &metrics,
+        );
+
+        // only the first, complete hour is rolled up
+        scheduler.run_once().await.unwrap();
+        {
+            let queries = executor.queries.lock();
+            assert_eq!(queries.len(), 1);
+            assert_eq!(queries[0].0, "ns");
+            assert!(queries[0].2.contains(
+                "time >= TIMESTAMP '1970-01-01T00:00:00Z' \
+                 AND time < TIMESTAMP '1970-01-01T01:00:00Z'"
+            ));
+            assert_eq!(
+                *writer.writes.lock(),
+                vec![("ns".to_string(), "cpu_1h,host=a usage=1.5 0\n".to_string())]
+            );
+        }
+        let first = rule(&catalog).await;
+        assert_eq!(first.covered_until, Some(Timestamp::new(HOUR)));
+        assert!(first.files_checked_until.is_some());
+
+        // nothing new was persisted, so the next run does nothing
+        scheduler.run_once().await.unwrap();
+        assert_eq!(executor.queries.lock().len(), 1);
+
+        // once newer data is persisted, the following windows are rolled up incrementally
+        params.object_store_id = uuid::Uuid::new_v4();
+        params.created_at = Timestamp::from(catalog.time_provider().now());
+        params.min_time = Timestamp::new(HOUR + 20);
+        params.max_time = Timestamp::new(3 * HOUR + 1);
+        catalog
+            .repositories()
+            .await
+            .parquet_files()
+            .create(params)
+            .await
+            .unwrap();
+        scheduler.run_once().await.unwrap();
+        {
+            let queries = executor.queries.lock();
+            assert_eq!(queries.len(), 2);
+            assert!(queries[1].2.contains(
+                "time >= TIMESTAMP '1970-01-01T01:00:00Z' \
+                 AND time < TIMESTAMP '1970-01-01T03:00:00Z'"
+            ));
+        }
+        assert_eq!(
+            covered_until(&catalog).await,
+            Some(Timestamp::new(3 * HOUR))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_rule_late_data() {
+        let metrics = Arc::new(metric::Registry::default());
+        let catalog: Arc<dyn Catalog> = Arc::new(MemCatalog::new(Arc::clone(&metrics)));
+
+        let mut repos = catalog.repositories().await;
+        let namespace = arbitrary_namespace(&mut *repos, "ns").await;
+        let table = arbitrary_table(&mut *repos, "cpu", &namespace).await;
+        let partition = repos
+            .partitions()
+            .create_or_get("1970-01-01".into(), table.id)
+            .await
+            .unwrap();
+        repos
+            .rollup_rules()
+            .create(RollupRuleParams {
+                namespace_id: namespace.id,
+                name: "cpu_1h".to_string(),
+                source_table: "cpu".to_string(),
+                target_table: "cpu_1h".to_string(),
+                language: RollupQueryLanguage::Sql,
+                aggregates: "avg(usage) AS usage".to_string(),
+                interval_ns: HOUR,
+                raw_retention_period_ns: None,
+            })
+            .await
+            .unwrap();
+        let mut params = arbitrary_parquet_file_params(&namespace, &table, &partition);
+        params.min_time = Timestamp::new(10);
+        params.max_time = Timestamp::new(3 * HOUR + 10);
+        repos.parquet_files().create(params.clone()).await.unwrap();
+        drop(repos);
+
+        let executor = Arc::new(MockExecutor::default());
+        let writer = Arc::new(MockWriter::default());
+        let scheduler = RollupScheduler::new(
+            Arc::clone(&catalog),
+            Arc::clone(&executor) as _,
+            Arc::clone(&writer) as _,
+            Duration::from_secs(1),
+            &metrics,
+        );
+        scheduler.run_once().await.unwrap();
+        assert_eq!(executor.queries.lock().len(), 1);
+        assert_eq!(
+            covered_until(&catalog).await,
+            Some(Timestamp::new(3 * HOUR))
+        );
+
+        // compacting covered data does not add any
+        params.object_store_id = uuid::Uuid::new_v4();
+        params.compaction_level = CompactionLevel::FileNonOverlapped;
+        params.created_at = Timestamp::from(catalog.time_provider().now());
+        catalog
+            .repositories()
+            .await
+            .parquet_files()
+            .create(params.clone())
+            .await
+            .unwrap();
+        scheduler.run_once().await.unwrap();
+        assert_eq!(executor.queries.lock().len(), 1);
+
+        // data persisted late into a covered window rolls up that window again
+        params.object_store_id = uuid::Uuid::new_v4();
+        params.compaction_level = CompactionLevel::Initial;
+        params.min_time = Timestamp::new(HOUR + 5);
+        params.max_time = Timestamp::new(HOUR + 6);
+        params.created_at = Timestamp::from(catalog.time_provider().now());
+        catalog
+            .repositories()
+            .await
+            .parquet_files()
+            .create(params.clone())
+            .await
+            .unwrap();
+        scheduler.run_once().await.unwrap();
+        {
+            let queries = executor.queries.lock();
+            assert_eq!(queries.len(), 2);
+            assert!(queries[1].2.contains(
+                "time >= TIMESTAMP '1970-01-01T01:00:00Z' \
+                 AND time < TIMESTAMP '1970-01-01T02:00:00Z'"
+            ));
+            assert_eq!(writer.writes.lock().len(), 2);
+        }
+        assert_eq!(
+            covered_until(&catalog).await,
+            Some(Timestamp::new(3 * HOUR))
+        );
+
+        // windows partially before the retention time are not rolled up again
+        *executor.retention_time_ns.lock() = Some(30);
+        params.object_store_id = uuid::Uuid::new_v4();
+        params.min_time = Timestamp::new(20);
+        params.max_time = Timestamp::new(HOUR + 30);
+        params.created_at = Timestamp::from(catalog.time_provider().now());
+        catalog
+            .repositories()
+            .await
+            .parquet_files()
+            .create(params)
+            .await
+            .unwrap();
+        scheduler.run_once().await.unwrap();
+        {
+            let queries = executor.queries.lock();
+            assert_eq!(queries.len(), 3);
+            assert!(queries[2].2.contains(
+                "time >= TIMESTAMP '1970-01-01T01:00:00Z' \
+                 AND time < TIMESTAMP '1970-01-01T02:00:00Z'"
+            ));
+        }
+        let last = rule(&catalog).await;
+        assert_eq!(last.covered_until, Some(Timestamp::new(3 * HOUR)));
+        assert_eq!(last.last_error, None);
+    }
+
+    #[tokio::test]
+    async fn test_run_rule_without_source_table() {
+        let metrics = Arc::new(metric::Registry::default());
+        let catalog: Arc<dyn Catalog> = Arc::new(MemCatalog::new(Arc::clone(&metrics)));
+
+        let mut repos = catalog.repositories().await;
+        let namespace = arbitrary_namespace(&mut *repos, "ns").await;
+        repos
+            .rollup_rules()
+            .create(RollupRuleParams {
+                namespace_id: namespace.id,
+                name: "cpu_1h".to_string(),
+                source_table: "cpu".to_string(),
+                target_table: "cpu_1h".to_string(),
+                language: RollupQueryLanguage::InfluxQl,
+                aggregates: "mean(usage) AS usage".to_string(),
+                interval_ns: HOUR,
+                raw_retention_period_ns: None,
+            })
+            .await
+            .unwrap();
+        drop(repos);
+
+        let executor = Arc::new(MockExecutor::default());
+        let scheduler = RollupScheduler::new(
+            Arc::clone(&catalog),
+            Arc::clone(&executor) as _,
+            Arc::new(MockWriter::default()),
+            Duration::from_secs(1),
+            &metrics,
+        );
+
+        scheduler.run_once().await.unwrap();
+        assert!(executor.queries.lock().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_run_rule_write_failure() {
+        let metrics = Arc::new(metric::Registry::default());
+        let catalog: Arc<dyn Catalog> = Arc::new(MemCatalog::new(Arc::clone(&metrics)));
+
+        let mut repos = catalog.repositories().await;
+        let namespace = arbitrary_namespace(&mut *repos, "ns").await;
+        let table = arbitrary_table(&mut *repos, "cpu", &namespace).await;
+        let partition = repos
+            .partitions()
+            .create_or_get("1970-01-01".into(), table.id)
+            .await
+            .unwrap();
+        repos
+            .rollup_rules()
+            .create(RollupRuleParams {
+                namespace_id: namespace.id,
+                name: "cpu_1h".to_string(),
+                source_table: "cpu".to_string(),
+                target_table: "cpu_1h".to_string(),
+                language: RollupQueryLanguage::Sql,
+                aggregates: "avg(usage) AS usage".to_string(),
+                interval_ns: HOUR,
+                raw_retention_period_ns: None,
+            })
+            .await
+            .unwrap();
+        let mut params = arbitrary_parquet_file_params(&namespace, &table, &partition);
+        params.min_time = Timestamp::new(10);
+        params.max_time = Timestamp::new(HOUR + 10);
+        repos.parquet_files().create(params).await.unwrap();
+        drop(repos);
+
+        let writer = Arc::new(MockWriter::default());
+        *writer.fail.lock() = true;
+        let scheduler = RollupScheduler::new(
+            Arc::clone(&catalog),
+            Arc::new(MockExecutor::default()),
+            Arc::clone(&writer) as _,
+            Duration::from_secs(1),
+            &metrics,
+        );
+
+        // a failed write keeps the watermark and records the error
+        scheduler.run_once().await.unwrap();
+        let failed = rule(&catalog).await;
+        assert_eq!(failed.covered_until, None);
+        let last_error = failed.last_error.expect("error is recorded");
+        assert!(last_error.contains("Failed to write"), "{last_error}");
+        assert_eq!(runs(&metrics, "error"), 1);
+        assert_eq!(runs(&metrics, "success"), 0);
+
+        // the same windows are rolled up once writes succeed again
+        *writer.fail.lock() = false;
+        scheduler.run_once().await.unwrap();
+        let recovered = rule(&catalog).await;
+        assert_eq!(recovered.covered_until, Some(Timestamp::new(HOUR)));
+        assert_eq!(recovered.last_error, None);
+        assert_eq!(writer.writes.lock().len(), 1);
+        assert_eq!(runs(&metrics, "error"), 1);
+        assert_eq!(runs(&metrics, "success"), 1);
+    }
+
+    #[tokio::test]
+    async fn test_run_rule_source_not_readable() {
+        let metrics = Arc::new(metric::Registry::default());
+        let catalog: Arc<dyn Catalog> = Arc::new(MemCatalog::new(Arc::clone(&metrics)));
+
+        let mut repos = catalog.repositories().await;
+        let namespace = arbitrary_namespace(&mut *repos, "ns").await;
+        let table = arbitrary_table(&mut *repos, "cpu", &namespace).await;
+        let partition = repos
+            .partitions()
+            .create_or_get("1970-01-01".into(), table.id)
+            .await
+            .unwrap();
+        repos
+            .rollup_rules()
+            .create(RollupRuleParams {
+                namespace_id: namespace.id,
+                name: "cpu_1h".to_string(),
+                source_table: "cpu".to_string(),
+                target_table: "cpu_1h".to_string(),
+                language: RollupQueryLanguage::Sql,
+                aggregates: "avg(usage) AS usage".to_string(),
+                interval_ns: HOUR,
+                raw_retention_period_ns: Some(2 * HOUR),
+            })
+            .await
+            .unwrap();
+        let mut params = arbitrary_parquet_file_params(&namespace, &table, &partition);
+        params.min_time = Timestamp::new(10);
+        params.max_time = Timestamp::new(HOUR + 10);
+        repos.parquet_files().create(params).await.unwrap();
+        drop(repos);
+
+        // the querier prunes part of the persisted data of the first window
+        let executor = Arc::new(MockExecutor::default());
+        *executor.retention_time_ns.lock() = Some(20);
+        let writer = Arc::new(MockWriter::default());
+        let scheduler = RollupScheduler::new(
+            Arc::clone(&catalog),
+            Arc::clone(&executor) as _,
+            Arc::clone(&writer) as _,
+            Duration::from_secs(1),
+            &metrics,
+        );
+
+        // the window is neither written nor covered, so its raw data is kept
+        scheduler.run_once().await.unwrap();
+        let failed = rule(&catalog).await;
+        assert_eq!(failed.covered_until, None);
+        let last_error = failed.last_error.expect("error is recorded");
+        assert!(last_error.contains("not readable"), "{last_error}");
+        assert!(writer.writes.lock().is_empty());
+
+        // a retention time before the persisted data does not prune anything
+        *executor.retention_time_ns.lock() = Some(5);
+        scheduler.run_once().await.unwrap();
+        let recovered = rule(&catalog).await;
+        assert_eq!(recovered.covered_until, Some(Timestamp::new(HOUR)));
+        assert_eq!(recovered.last_error, None);
+        assert_eq!(writer.writes.lock().len(), 1);
+    }
+}
@@ -0,0 +1,154 @@
+//! Generation of the queries evaluating a [`RollupRule`] over a time range.
+
+use chrono::{SecondsFormat, TimeZone, Utc};
+use data_types::{RollupQueryLanguage, RollupRule};
+use std::ops::Range;
+
+/// Round `ts` down to the closest multiple of `interval_ns`.
+///
+/// Windows of a rule are aligned to the unix epoch, like `date_bin` and the InfluxQL
+/// `GROUP BY time()` clause without an offset.
+pub(crate) fn align_down(ts: i64, interval_ns: i64) -> i64 {
+    ts - ts.rem_euclid(interval_ns)
+}
+
+/// Build the query rolling up the rows of `rule.source_table` with a timestamp in `range`.
+///
+/// `range` must be aligned to the interval of the rule, so that every window of the query is
+/// complete. The output is grouped by `time` and all `tags` of the source table.
+pub(crate) fn rollup_query(rule: &RollupRule, tags: &[String], range: Range<i64>) -> String {
+    let start = format_timestamp(range.start);
+    let end = format_timestamp(range.end);
+
+    match rule.language {
+        RollupQueryLanguage::Sql => {
+            let window = format!(
+                "date_bin(INTERVAL '{} nanoseconds', time, TIMESTAMP '1970-01-01T00:00:00Z')",
+                rule.interval_ns
+            );
+            let tags = tags.iter().map(|t| quote_sql(t)).collect::<Vec<_>>();
+
+            let mut select = vec![format!("{window} AS time")];
+            select.extend(tags.iter().cloned());
+            select.push(rule.aggregates.clone());
+
+            let mut group_by = vec![window];
+            group_by.extend(tags);
+
+            format!(
+                "SELECT {} FROM {} WHERE time >= TIMESTAMP '{start}' AND time < TIMESTAMP '{end}' \
+                 GROUP BY {}",
+                select.join(", "),
+                quote_sql(&rule.source_table),
+                group_by.join(", "),
+            )
+        }
+        RollupQueryLanguage::InfluxQl => format!(
+            "SELECT {} FROM {} WHERE time >= '{start}' AND time < '{end}' \
+             GROUP BY time({}ns), * FILL(none)",
+            rule.aggregates,
+            quote_influxql(&rule.source_table),
+            rule.interval_ns,
+        ),
+    }
+}
+
+fn format_timestamp(ts: i64) -> String {
+    Utc.timestamp_nanos(ts)
+        .to_rfc3339_opts(SecondsFormat::AutoSi, true)
+}
+
+fn quote_sql(ident: &str) -> String {
+    format!("\"{}\"", ident.replace('"', "\"\""))
+}
+
+fn quote_influxql(ident: &str) -> String {
+    format!("\"{}\"", ident.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use data_types::{NamespaceId, RollupRuleId};
+
+    use super::*;
+
+    const HOUR: i64 = 60 * 60 * 1_000_000_000;
+
+    fn rule(language: RollupQueryLanguage, aggregates: &str) -> RollupRule {
+        RollupRule {
+            id: RollupRuleId::new(1),
+            namespace_id: NamespaceId::new(1),
+            name: "cpu_1h".to_string(),
+            source_table: "cpu".to_string(),
+            target_table: "cpu_1h".to_string(),
+            language,
+            aggregates: aggregates.to_string(),
+            interval_ns: HOUR,
+            covered_until: None,
+            raw_retention_period_ns: None,
+            last_error: None,
+            files_checked_until: None,
+        }
+    }
+
+    #[test]
+    fn test_align_down() {
+        assert_eq!(align_down(0, HOUR), 0);
+        assert_eq!(align_down(HOUR - 1, HOUR), 0);
+        assert_eq!(align_down(HOUR, HOUR), HOUR);
+        assert_eq!(align_down(HOUR + 1, HOUR), HOUR);
+        assert_eq!(align_down(-1, HOUR), -HOUR);
+    }
+
+    #[test]
+    fn test_sql_query() {
+        let query = rollup_query(
+            &rule(RollupQueryLanguage::Sql, "avg(usage) AS usage"),
+            &["host".to_string(), "re\"gion".to_string()],
+            0..2 * HOUR,
+        );
+        assert_eq!(
+            query,
+            "SELECT date_bin(INTERVAL '3600000000000 nanoseconds', time, \
+             TIMESTAMP '1970-01-01T00:00:00Z') AS time, \"host\", \"re\"\"gion\", \
+             avg(usage) AS usage FROM \"cpu\" \
+             WHERE time >= TIMESTAMP '1970-01-01T00:00:00Z' \
+             AND time < TIMESTAMP '1970-01-01T02:00:00Z' \
+             GROUP BY date_bin(INTERVAL '3600000000000 nanoseconds', time, \
+             TIMESTAMP '1970-01-01T00:00:00Z'), \"host\", \"re\"\"gion\""
+        );
+    }
+
+    #[test]
+    fn test_sql_query_no_tags() {
+        let query = rollup_query(
+            &rule(RollupQueryLanguage::Sql, "max(usage) AS usage_max"),
+            &[],
+            HOUR..2 * HOUR,
+        );
+        assert_eq!(
+            query,
+            "SELECT date_bin(INTERVAL '3600000000000 nanoseconds', time, \
+             TIMESTAMP '1970-01-01T00:00:00Z') AS time, max(usage) AS usage_max FROM \"cpu\" \
+             WHERE time >= TIMESTAMP '1970-01-01T01:00:00Z' \
+             AND time < TIMESTAMP '1970-01-01T02:00:00Z' \
+             GROUP BY date_bin(INTERVAL '3600000000000 nanoseconds', time, \
+             TIMESTAMP '1970-01-01T00:00:00Z')"
+        );
+    }
+
+    #[test]
+    fn test_influxql_query() {
+        let query = rollup_query(
+            &rule(RollupQueryLanguage::InfluxQl, "mean(usage) AS usage"),
+            &["host".to_string()],
+            0..HOUR,
+        );
+        assert_eq!(
+            query,
+            "SELECT mean(usage) AS usage FROM \"cpu\" \
+             WHERE time >= '1970-01-01T00:00:00Z' AND time < '1970-01-01T01:00:00Z' \
+             GROUP BY time(3600000000000ns), * FILL(none)"
+        );
+    }
+}
@@ -0,0 +1,328 @@
+//! Writing the output of rollup queries back into the target table.
+
+use std::{collections::HashSet, fmt::Debug, fmt::Formatter};
+
+use arrow::{
+    array::{ArrayRef, BooleanArray, StringArray},
+    compute::{
+        cast, filter_record_batch, is_not_null, kernels::comparison::eq_utf8_scalar, nullif, or,
+    },
+    datatypes::{DataType, TimeUnit},
+    record_batch::RecordBatch,
+};
+use async_trait::async_trait;
+use data_types::NamespaceName;
+use influxdb_iox_client::{
+    connection::{Builder, Connection},
+    write::Client,
+};
+use parquet_to_line_protocol::convert_to_lines;
+use schema::{InfluxFieldType, SchemaBuilder};
+use snafu::prelude::*;
+use tokio::sync::Mutex;
+
+/// Name of the column holding the timestamp of each window.
+const TIME_COLUMN: &str = "time";
+
+/// Name of the column InfluxQL queries add to their output.
+const MEASUREMENT_COLUMN: &str = "iox::measurement";
+
+/// Errors converting or writing rolled up data.
+#[derive(Debug, Snafu)]
+#[allow(missing_docs)]
+pub enum WriteError {
+    #[snafu(display("Rollup output has no time column"))]
+    NoTimeColumn,
+
+    #[snafu(display("Column '{name}' of the rollup output has unsupported type {data_type}"))]
+    UnsupportedType { name: String, data_type: DataType },
+
+    #[snafu(display("Rollup output has no fields"))]
+    NoFields,
+
+    #[snafu(display("Failed to convert the rollup output: {source}"))]
+    Arrow { source: arrow::error::ArrowError },
+
+    #[snafu(display("Rollup output has an invalid schema: {source}"))]
+    Schema { source: schema::builder::Error },
+
+    #[snafu(display("Failed to convert the rollup output to line protocol: {message}"))]
+    LineProtocol { message: String },
+
+    #[snafu(display("Namespace '{namespace}' cannot be written through the router"))]
+    UnmappedNamespace { namespace: String },
+
+    #[snafu(display("Failed to connect to the router at {address}: {source}"))]
+    Connect {
+        address: String,
+        source: influxdb_iox_client::connection::Error,
+    },
+
+    #[snafu(display("Failed to write to the router: {source}"))]
+    Client {
+        source: influxdb_iox_client::error::Error,
+    },
+}
+
+/// Writes rolled up data into a table.
+#[async_trait]
+pub trait RollupWriter: Debug + Send + Sync {
+    /// Write the line protocol `lp` into `namespace`.
+    async fn write_lp(&self, namespace: &str, lp: String) -> Result<(), WriteError>;
+}
+
+/// A [`RollupWriter`] writing through the HTTP write API of a router, so rolled up data takes
+/// the same path as any other write.
+///
+/// The router maps the org and bucket of a write to a namespace depending on whether it runs in
+/// a single or multi-tenant deployment, so the writer must be configured for the same mode to
+/// write into the right namespace.
+///
+/// The connection is established on first use, so the router does not need to be up when the
+/// writer is created.
+pub struct RouterWriter {
+    address: String,
+    single_tenant: bool,
+    connection: Mutex<Option<Connection>>,
+}
+
+impl RouterWriter {
+    /// Create a new writer for the multi-tenant router listening at `address`.
+    pub fn new(address: impl Into<String>) -> Self {
+        Self {
+            address: address.into(),
+            single_tenant: false,
+            connection: Default::default(),
+        }
+    }
+
+    /// Set whether the router runs in a single tenant deployment.
+    pub fn with_single_tenant(mut self, single_tenant: bool) -> Self {
+        self.single_tenant = single_tenant;
+        self
+    }
+
+    async fn connection(&self) -> Result<Connection, WriteError> {
+        let mut connection = self.connection.lock().await;
+        if let Some(connection) = connection.as_ref() {
+            return Ok(connection.clone());
+        }
+
+        let new = Builder::default()
+            .build(self.address.as_str())
+            .await
+            .context(ConnectSnafu {
+                address: &self.address,
+            })?;
+        *connection = Some(new.clone());
+        Ok(new)
+    }
+}
+
+impl Debug for RouterWriter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RouterWriter")
+            .field("address", &self.address)
+            .field("single_tenant", &self.single_tenant)
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl RollupWriter for RouterWriter {
+    async fn write_lp(&self, namespace: &str, lp: String) -> Result<(), WriteError> {
+        let name = NamespaceName::new(namespace).ok();
+        let (org, bucket) = name
+            .as_ref()
+            .and_then(|name| name.to_v2_org_and_bucket(self.single_tenant))
+            .context(UnmappedNamespaceSnafu { namespace })?;
+
+        Client::new(self.connection().await?)
+            .write_lp_to_org_bucket(org, bucket, lp)
+            .await
+            .context(ClientSnafu)?;
+        Ok(())
+    }
+}
+
+/// Convert the output `batch` of a rollup query, or any other query written back into a
+/// table, into line protocol for `measurement`.
+///
+/// Columns named in `tags` are written as tags, `time` as the timestamp and every other column
+/// as a field. Rows without any non-null field are skipped, as are empty tag values.
+pub fn batch_to_lp(
+    measurement: &str,
+    tags: &HashSet<String>,
+    batch: &RecordBatch,
+) -> Result<String, WriteError> {
+    let schema = batch.schema();
+
+    // Cast the output to the column types of an IOx schema, so it can be converted like any
+    // other IOx data.
+    let mut builder = SchemaBuilder::new();
+    let mut columns = vec![];
+    let mut field_indices = vec![];
+    for (field, column) in schema.fields().iter().zip(batch.columns()) {
+        let name = field.name().as_str();
+        if name == TIME_COLUMN {
+            builder.timestamp();
+            columns.push(
+                cast(column, &DataType::Timestamp(TimeUnit::Nanosecond, None))
+                    .context(ArrowSnafu)?,
+            );
+        } else if name == MEASUREMENT_COLUMN {
+            continue;
+        } else if tags.contains(name) {
+            builder.tag(name);
+            columns.push(tag_column(column)?);
+        } else {
+            let field_type = field_type(name, column.data_type())?;
+            builder.influx_field(name, field_type);
+            field_indices.push(columns.len());
+            columns.push(cast(column, &field_type.into()).context(ArrowSnafu)?);
+        }
+    }
+    ensure!(
+        schema.column_with_name(TIME_COLUMN).is_some(),
+        NoTimeColumnSnafu
+    );
+    ensure!(!field_indices.is_empty(), NoFieldsSnafu);
+
+    let schema = builder.build().context(SchemaSnafu)?;
+    let batch = RecordBatch::try_new(schema.as_arrow(), columns).context(ArrowSnafu)?;
+
+    // Line protocol needs at least one field per line.
+    let mut has_fields: Option<BooleanArray> = None;
+    for index in field_indices {
+        let valid = is_not_null(batch.column(index)).context(ArrowSnafu)?;
+        has_fields = Some(match has_fields {
+            Some(has_fields) => or(&has_fields, &valid).context(ArrowSnafu)?,
+            None => valid,
+        });
+    }
+    let batch = filter_record_batch(&batch, &has_fields.expect("at least one field"))
+        .context(ArrowSnafu)?;
+
+    let lp = convert_to_lines(measurement, &schema, &batch)
+        .map_err(|message| LineProtocolSnafu { message }.build())?;
+    Ok(String::from_utf8(lp).expect("line protocol is valid utf8"))
+}
+
+/// Cast a tag `column` to the dictionary type of IOx tags, replacing empty values, which line
+/// protocol cannot represent, with nulls.
+fn tag_column(column: &ArrayRef) -> Result<ArrayRef, WriteError> {
+    let values = cast(column, &DataType::Utf8).context(ArrowSnafu)?;
+    let empty = eq_utf8_scalar(
+        values
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .expect("cast to utf8"),
+        "",
+    )
+    .context(ArrowSnafu)?;
+    let values = nullif(&values, &empty).context(ArrowSnafu)?;
+
+    cast(
+        &values,
+        &DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8)),
+    )
+    .context(ArrowSnafu)
+}
+
+/// The IOx field type a field column of type `data_type` is written as.
+fn field_type(name: &str, data_type: &DataType) -> Result<InfluxFieldType, WriteError> {
+    Ok(match data_type {
+        DataType::Float16 | DataType::Float32 | DataType::Float64 => InfluxFieldType::Float,
+        DataType::Int8 | DataType::Int16 | DataType::Int32 | DataType::Int64 => {
+            InfluxFieldType::Integer
+        }
+        DataType::UInt8 | DataType::UInt16 | DataType::UInt32 | DataType::UInt64 => {
+            InfluxFieldType::UInteger
+        }
+        DataType::Boolean => InfluxFieldType::Boolean,
+        DataType::Utf8 | DataType::LargeUtf8 | DataType::Dictionary(_, _) => {
+            InfluxFieldType::String
+        }
+        data_type => {
+            return UnsupportedTypeSnafu {
+                name,
+                data_type: data_type.clone(),
+            }
+            .fail()
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::{
+        array::{DictionaryArray, Float64Array, Int64Array, TimestampNanosecondArray},
+        datatypes::Int32Type,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_batch_to_lp() {
+        let host: DictionaryArray<Int32Type> =
+            vec![Some("a"), Some(""), Some("b")].into_iter().collect();
+        let batch = RecordBatch::try_from_iter(vec![
+            (
+                "iox::measurement",
+                Arc::new(StringArray::from(vec!["cpu", "cpu", "cpu"])) as ArrayRef,
+            ),
+            (
+                "time",
+                Arc::new(TimestampNanosecondArray::from(vec![0, 10, 20])) as ArrayRef,
+            ),
+            ("host", Arc::new(host) as ArrayRef),
+            (
+                "usage",
+                Arc::new(Float64Array::from(vec![Some(1.5), Some(2.0), None])) as ArrayRef,
+            ),
+            (
+                "count",
+                Arc::new(Int64Array::from(vec![Some(3), None, None])) as ArrayRef,
+            ),
+        ])
+        .unwrap();
+
+        let tags = HashSet::from(["host".to_string()]);
+        let lp = batch_to_lp("cpu_1h", &tags, &batch).unwrap();
+
+        // empty tag values are skipped, and so is the last row, which has no fields
+        assert_eq!(
+            lp,
+            "cpu_1h,host=a usage=1.5,count=3i 0\n\
+             cpu_1h usage=2 10\n"
+        );
+    }
+
+    #[test]
+    fn test_batch_to_lp_no_time() {
+        let batch = RecordBatch::try_from_iter(vec![(
+            "usage",
+            Arc::new(Float64Array::from(vec![1.0])) as ArrayRef,
+        )])
+        .unwrap();
+
+        let err = batch_to_lp("cpu_1h", &HashSet::new(), &batch).unwrap_err();
+        assert!(matches!(err, WriteError::NoTimeColumn), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_router_writer_unmapped_namespace() {
+        // a multi-tenant router only maps org and bucket to namespaces containing an '_'
+        let writer = RouterWriter::new("http://127.0.0.1:1");
+        let err = writer
+            .write_lp("bucket", "m f=1 0".into())
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, WriteError::UnmappedNamespace { ref namespace } if namespace == "bucket"),
+            "{err}"
+        );
+    }
+}
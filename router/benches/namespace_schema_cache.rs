@@ -126,6 +126,7 @@ fn generate_namespace_schema(tables: usize, columns_per_table: usize) -> Namespa
             .map(|i| {
                 let schema = TableSchema {
                     id: TableId::new(i as _),
+                    retention_period_ns: None,
                     columns: (0..columns_per_table)
                         .map(|j| {
                             (
@@ -149,6 +150,9 @@ fn generate_namespace_schema(tables: usize, columns_per_table: usize) -> Namespa
             .collect::<BTreeMap<_, _>>(),
         max_columns_per_table: usize::MAX,
         max_tables: usize::MAX,
+        max_write_bytes_per_second: None,
+        max_write_lines_per_second: None,
+        max_concurrent_queries: None,
         retention_period_ns: None,
         partition_template,
     }
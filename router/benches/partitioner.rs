@@ -168,6 +168,9 @@ fn bench(
         tables: Default::default(),
         max_columns_per_table: 1000,
         max_tables: 1000,
+        max_write_bytes_per_second: None,
+        max_write_lines_per_second: None,
+        max_concurrent_queries: None,
         retention_period_ns: None,
         partition_template: partition_template.clone(),
     });
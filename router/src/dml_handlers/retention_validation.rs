@@ -31,11 +31,12 @@ pub enum RetentionError {
 }
 
 /// A [`DmlHandler`] implementation that validates that the write is within the
-/// retention period of the namespace.
+/// retention period of the namespace, or of the table if it overrides the
+/// namespace retention period.
 ///
 /// Each row of data being wrote is inspected, and if any "time" column
-/// timestamp lays outside of the configured namespace retention period, the
-/// entire write is rejected.
+/// timestamp lays outside of the configured retention period, the entire write
+/// is rejected.
 #[derive(Debug, Default)]
 pub struct RetentionValidator<P = SystemProvider> {
     time_provider: P,
@@ -66,22 +67,27 @@ where
         batch: Self::WriteInput,
         _span_ctx: Option<SpanContext>,
     ) -> Result<Self::WriteOutput, Self::WriteError> {
-        // retention is not infinte, validate all lines of a write are within the retention period
-        if let Some(retention_period_ns) = namespace_schema.retention_period_ns {
-            let min_retention = self.time_provider.now().timestamp_nanos() - retention_period_ns;
-            // batch is a HashMap<tring, MutableBatch>
-            for (table_name, batch) in &batch {
-                if let Some(min) = batch.timestamp_summary().and_then(|v| v.stats.min) {
-                    if min < min_retention {
-                        return Err(RetentionError::OutsideRetention {
-                            table_name: table_name.clone(),
-                            min_acceptable_ts: iox_time::Time::from_timestamp_nanos(min_retention),
-                            observed_ts: iox_time::Time::from_timestamp_nanos(min),
-                        });
-                    }
+        let now = self.time_provider.now().timestamp_nanos();
+
+        // validate all lines of a write are within the retention period of their table, which
+        // is the namespace retention period unless the table overrides it.
+        for (table_name, batch) in &batch {
+            let retention_period_ns = match namespace_schema.table_retention_period_ns(table_name) {
+                Some(v) => v,
+                None => continue, // infinite retention
+            };
+
+            let min_retention = now - retention_period_ns;
+            if let Some(min) = batch.timestamp_summary().and_then(|v| v.stats.min) {
+                if min < min_retention {
+                    return Err(RetentionError::OutsideRetention {
+                        table_name: table_name.clone(),
+                        min_acceptable_ts: iox_time::Time::from_timestamp_nanos(min_retention),
+                        observed_ts: iox_time::Time::from_timestamp_nanos(min),
+                    });
                 }
             }
-        };
+        }
 
         Ok(batch)
     }
@@ -238,6 +244,57 @@ mod tests {
         });
     }
 
+    #[tokio::test]
+    async fn test_table_retention_period_overrides_namespace() {
+        let namespace = test_setup().await;
+
+        // Create the tables so that they exist in the namespace schema.
+        let _want_id = namespace.create_table("bananas").await.table.id;
+        let _want_id = namespace.create_table("apple").await.table.id;
+
+        let mock_now = iox_time::Time::from_rfc3339("2023-05-23T09:59:06+00:00").unwrap();
+        let mock_time = MockProvider::new(mock_now);
+
+        // Create the validator whose namespace retention period is 1 hour
+        let handler = RetentionValidator {
+            time_provider: mock_time.clone(),
+        };
+
+        // Override the retention period of the "bananas" table to 30 minutes
+        let mut schema = namespace.schema().await;
+        schema
+            .tables
+            .get_mut("bananas")
+            .unwrap()
+            .retention_period_ns = Some(30 * 60 * 1_000_000_000);
+        let schema = Arc::new(schema);
+
+        // Make time inside the namespace retention period, but outside the
+        // table retention period
+        let forty_five_mins_ago =
+            (mock_now.timestamp_nanos() - 45 * 60 * 1_000_000_000).to_string();
+
+        // The "apple" table uses the namespace retention period
+        let line = "apple,tag1=A,tag2=B val=42i ".to_string() + &forty_five_mins_ago;
+        let result = handler
+            .write(&NAMESPACE, Arc::clone(&schema), lp_to_writes(&line), None)
+            .await;
+        assert_matches!(result, Ok(_));
+
+        // The "bananas" table uses its own, shorter retention period
+        let line = "bananas,tag1=A,tag2=B val=42i ".to_string() + &forty_five_mins_ago;
+        let result = handler
+            .write(&NAMESPACE, schema, lp_to_writes(&line), None)
+            .await;
+        assert_matches!(result, Err(e) => {
+            assert_eq!(
+                e.to_string(),
+                "data in table bananas is outside of the retention period: minimum \
+                 acceptable timestamp is 2023-05-23T09:29:06+00:00, but observed \
+                 timestamp 2023-05-23T09:14:06+00:00 is older.")
+        });
+    }
+
     // Parse `lp` into a table-keyed MutableBatch map.
     fn lp_to_writes(lp: &str) -> HashMap<String, MutableBatch> {
         let (writes, _) = mutable_batch_lp::lines_to_batches_stats(lp, 42)
@@ -133,6 +133,7 @@ mod tests {
             "platanos".to_string(),
             TableSchema {
                 id: TableId::new(4242),
+                retention_period_ns: None,
                 partition_template: test_table_partition_override(vec![
                     data_types::partition_template::TemplatePart::TagValue("bananatastic"),
                 ]),
@@ -152,6 +153,9 @@ mod tests {
             tables,
             max_columns_per_table: 1,
             max_tables: 2,
+            max_write_bytes_per_second: None,
+            max_write_lines_per_second: None,
+            max_concurrent_queries: None,
             retention_period_ns: Some(1234),
             partition_template: NamespacePartitionTemplateOverride::try_from(
                 (**PARTITION_BY_DAY_PROTO).clone(),
@@ -189,6 +193,7 @@ mod tests {
             "platanos".to_string(),
             TableSchema {
                 id: TableId::new(4242),
+                retention_period_ns: None,
                 partition_template: TablePartitionTemplateOverride::try_new(
                     None,
                     &NamespacePartitionTemplateOverride::default(),
@@ -210,6 +215,9 @@ mod tests {
             tables,
             max_columns_per_table: 1,
             max_tables: 2,
+            max_write_bytes_per_second: None,
+            max_write_lines_per_second: None,
+            max_concurrent_queries: None,
             retention_period_ns: Some(1234),
             partition_template: NamespacePartitionTemplateOverride::default(),
         };
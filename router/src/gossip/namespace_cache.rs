@@ -182,6 +182,9 @@ where
                         tables: Default::default(),
                         max_columns_per_table: note.max_columns_per_table as _,
                         max_tables: note.max_tables as _,
+                        max_write_bytes_per_second: note.max_write_bytes_per_second,
+                        max_write_lines_per_second: note.max_write_lines_per_second,
+                        max_concurrent_queries: note.max_concurrent_queries.map(|v| v as _),
                         retention_period_ns: note.retention_period_ns,
                         partition_template,
                     },
@@ -301,6 +304,7 @@ where
 
                 Some(TableSchema {
                     id: table_id,
+                    retention_period_ns: None,
                     partition_template,
                     columns: ColumnsByName::from(columns),
                 })
@@ -407,6 +411,9 @@ mod tests {
         tables: BTreeMap::new(),
         max_columns_per_table: 1,
         max_tables: 2,
+        max_write_bytes_per_second: None,
+        max_write_lines_per_second: None,
+        max_concurrent_queries: None,
         retention_period_ns: None,
         partition_template: DEFAULT_NAMESPACE_PARTITION_TEMPLATE,
     };
@@ -861,6 +868,9 @@ mod tests {
             partition_template: Some((**PARTITION_BY_DAY_PROTO).clone()),
             max_columns_per_table: DEFAULT_NAMESPACE.max_columns_per_table as _,
             max_tables: DEFAULT_NAMESPACE.max_tables as _,
+            max_write_bytes_per_second: None,
+            max_write_lines_per_second: None,
+            max_concurrent_queries: None,
             retention_period_ns: DEFAULT_NAMESPACE.retention_period_ns,
         }),
         want = Err(CacheMissErr { .. })
@@ -876,6 +886,9 @@ mod tests {
             partition_template: None,
             max_columns_per_table: DEFAULT_NAMESPACE.max_columns_per_table as _,
             max_tables: DEFAULT_NAMESPACE.max_tables as _,
+            max_write_bytes_per_second: None,
+            max_write_lines_per_second: None,
+            max_concurrent_queries: None,
             retention_period_ns: DEFAULT_NAMESPACE.retention_period_ns,
         }),
         want = Ok(v) => {
@@ -894,6 +907,9 @@ mod tests {
             partition_template: Some((**PARTITION_BY_DAY_PROTO).clone()),
             max_columns_per_table: DEFAULT_NAMESPACE.max_columns_per_table as _,
             max_tables: DEFAULT_NAMESPACE.max_tables as _,
+            max_write_bytes_per_second: None,
+            max_write_lines_per_second: None,
+            max_concurrent_queries: None,
             retention_period_ns: DEFAULT_NAMESPACE.retention_period_ns,
         }),
         want = Ok(v) => {
@@ -920,6 +936,9 @@ mod tests {
             // They will be ignored, and the local values used instead.
             max_columns_per_table: 123456,
             max_tables: 123456,
+            max_write_bytes_per_second: None,
+            max_write_lines_per_second: None,
+            max_concurrent_queries: None,
             retention_period_ns: Some(123456),
         }),
         want = Ok(v) => {
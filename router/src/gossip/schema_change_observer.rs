@@ -123,6 +123,9 @@ where
             max_columns_per_table: schema.max_columns_per_table as u64,
             max_tables: schema.max_tables as u64,
             retention_period_ns: schema.retention_period_ns,
+            max_write_bytes_per_second: schema.max_write_bytes_per_second,
+            max_write_lines_per_second: schema.max_write_lines_per_second,
+            max_concurrent_queries: schema.max_concurrent_queries.map(|v| v as u64),
         };
 
         self.tx.broadcast(Event::NamespaceCreated(msg));
@@ -225,6 +228,9 @@ mod tests {
         tables: BTreeMap::new(),
         max_columns_per_table: 1,
         max_tables: 2,
+        max_write_bytes_per_second: None,
+        max_write_lines_per_second: None,
+        max_concurrent_queries: None,
         retention_period_ns: None,
         partition_template: DEFAULT_NAMESPACE_PARTITION_TEMPLATE,
     };
@@ -280,7 +286,8 @@ mod tests {
             partition_template,
             max_columns_per_table,
             max_tables,
-            retention_period_ns
+            retention_period_ns,
+            ..
         })] => {
             assert_eq!(namespace_name, NAMESPACE_NAME);
             assert_eq!(*namespace_id, DEFAULT_NAMESPACE.id.get());
@@ -299,6 +306,7 @@ mod tests {
             new_tables: new_map(&[
                 (TABLE_NAME, TableSchema {
                     id: TableId::new(TABLE_ID),
+                    retention_period_ns: None,
                     columns: ColumnsByName::new([
                         data_types::Column {
                             name: "platanos".to_string(),
@@ -324,7 +332,8 @@ mod tests {
             partition_template,
             max_columns_per_table,
             max_tables,
-            retention_period_ns
+            retention_period_ns,
+            ..
         }),
         Event::TableCreated(TableCreated { table, partition_template: table_template })] => {
             // Validate the namespace create message
@@ -363,6 +372,7 @@ mod tests {
             new_tables: new_map(&[
                 (TABLE_NAME, TableSchema {
                     id: TableId::new(TABLE_ID),
+                    retention_period_ns: None,
                     columns: ColumnsByName::new([
                         data_types::Column {
                             name: "platanos".to_string(),
@@ -409,6 +419,7 @@ mod tests {
             new_tables: new_map(&[
                 (TABLE_NAME, TableSchema {
                     id: TableId::new(TABLE_ID),
+                    retention_period_ns: None,
                     columns: ColumnsByName::new([
                         data_types::Column {
                             name: "platanos".to_string(),
@@ -440,6 +451,7 @@ mod tests {
             let mut ns = DEFAULT_NAMESPACE.clone();
             ns.tables.insert("more-bananas".to_string(), TableSchema {
                 id: TableId::new(4321),
+                retention_period_ns: None,
                 partition_template:  test_table_partition_override(vec![]),
                 columns: ColumnsByName::new([
                     data_types::Column {
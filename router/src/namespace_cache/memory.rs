@@ -198,6 +198,9 @@ mod tests {
             tables: Default::default(),
             max_columns_per_table: 50,
             max_tables: 24,
+            max_write_bytes_per_second: None,
+            max_write_lines_per_second: None,
+            max_concurrent_queries: None,
             retention_period_ns: Some(876),
             partition_template: Default::default(),
         };
@@ -215,6 +218,9 @@ mod tests {
             tables: Default::default(),
             max_columns_per_table: 10,
             max_tables: 42,
+            max_write_bytes_per_second: None,
+            max_write_lines_per_second: None,
+            max_concurrent_queries: None,
             retention_period_ns: Some(876),
             partition_template: Default::default(),
         };
@@ -234,6 +240,7 @@ mod tests {
     fn empty_table_schema(id: TableId) -> TableSchema {
         TableSchema {
             id,
+            retention_period_ns: None,
             partition_template: Default::default(),
             columns: ColumnsByName::new([]),
         }
@@ -273,6 +280,9 @@ mod tests {
             tables: BTreeMap::from([(String::from(table_name), first_write_table_schema.clone())]),
             max_columns_per_table: 50,
             max_tables: 24,
+            max_write_bytes_per_second: None,
+            max_write_lines_per_second: None,
+            max_concurrent_queries: None,
             retention_period_ns: None,
             partition_template: Default::default(),
         };
@@ -375,6 +385,9 @@ mod tests {
             ]),
             max_columns_per_table: 50,
             max_tables: 24,
+            max_write_bytes_per_second: None,
+            max_write_lines_per_second: None,
+            max_concurrent_queries: None,
             retention_period_ns: None,
             partition_template: Default::default(),
         };
@@ -467,6 +480,7 @@ mod tests {
             let columns = ColumnsByName::from(columns);
             TableSchema {
                 id: TableId::new(id),
+                retention_period_ns: None,
                 partition_template: Default::default(),
                 columns,
             }
@@ -490,6 +504,9 @@ mod tests {
                 tables,
                 max_columns_per_table,
                 max_tables,
+                max_write_bytes_per_second: None,
+                max_write_lines_per_second: None,
+                max_concurrent_queries: None,
                 retention_period_ns,
                 partition_template: Default::default(),
             }
@@ -153,6 +153,7 @@ mod tests {
                     i.to_string(),
                     TableSchema {
                         id: TableId::new(i as _),
+                        retention_period_ns: None,
                         partition_template: Default::default(),
                         columns: ColumnsByName::new(columns),
                     },
@@ -165,6 +166,9 @@ mod tests {
             tables,
             max_columns_per_table: 100,
             max_tables: 42,
+            max_write_bytes_per_second: None,
+            max_write_lines_per_second: None,
+            max_concurrent_queries: None,
             retention_period_ns: None,
             partition_template: Default::default(),
         }
@@ -123,6 +123,9 @@ mod tests {
             tables: Default::default(),
             max_columns_per_table: iox_catalog::DEFAULT_MAX_COLUMNS_PER_TABLE as usize,
             max_tables: iox_catalog::DEFAULT_MAX_TABLES as usize,
+            max_write_bytes_per_second: None,
+            max_write_lines_per_second: None,
+            max_concurrent_queries: None,
             retention_period_ns: iox_catalog::DEFAULT_RETENTION_PERIOD,
             partition_template: Default::default(),
         };
@@ -159,6 +162,9 @@ mod tests {
             tables: Default::default(),
             max_columns_per_table: iox_catalog::DEFAULT_MAX_COLUMNS_PER_TABLE as usize,
             max_tables: iox_catalog::DEFAULT_MAX_TABLES as usize,
+            max_write_bytes_per_second: None,
+            max_write_lines_per_second: None,
+            max_concurrent_queries: None,
             retention_period_ns: iox_catalog::DEFAULT_RETENTION_PERIOD,
             partition_template: Default::default(),
         };
@@ -99,6 +99,9 @@ mod tests {
                 tables: Default::default(),
                 max_columns_per_table: 4,
                 max_tables: 42,
+                max_write_bytes_per_second: None,
+                max_write_lines_per_second: None,
+                max_concurrent_queries: None,
                 retention_period_ns: None,
                 partition_template: Default::default(),
             },
@@ -44,6 +44,9 @@ fn new_empty_namespace_schema(id: NamespaceId) -> NamespaceSchema {
         tables: BTreeMap::new(),
         max_columns_per_table: 500,
         max_tables: 200,
+        max_write_bytes_per_second: None,
+        max_write_lines_per_second: None,
+        max_concurrent_queries: None,
         retention_period_ns: None,
         partition_template: Default::default(),
     }
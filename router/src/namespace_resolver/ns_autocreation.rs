@@ -170,6 +170,9 @@ mod tests {
                 tables: Default::default(),
                 max_columns_per_table: 4,
                 max_tables: 42,
+                max_write_bytes_per_second: None,
+                max_write_lines_per_second: None,
+                max_concurrent_queries: None,
                 retention_period_ns: None,
                 partition_template: Default::default(),
             },
@@ -245,6 +248,9 @@ mod tests {
                 name: ns.to_string(),
                 max_tables: iox_catalog::DEFAULT_MAX_TABLES,
                 max_columns_per_table: iox_catalog::DEFAULT_MAX_COLUMNS_PER_TABLE,
+                max_write_bytes_per_second: None,
+                max_write_lines_per_second: None,
+                max_concurrent_queries: None,
                 retention_period_ns: TEST_RETENTION_PERIOD_NS,
                 deleted_at: None,
                 partition_template: Default::default(),
@@ -1,13 +1,14 @@
 //! gRPC service implementations for `router`.
 
 use generated_types::influxdata::iox::{
-    catalog::v1::*, namespace::v1::*, object_store::v1::*, table::v1::*,
+    catalog::v1::*, namespace::v1::*, object_store::v1::*, rollup::v1::*, table::v1::*,
 };
 use iox_catalog::interface::Catalog;
 use object_store::DynObjectStore;
 use service_grpc_catalog::CatalogService;
 use service_grpc_namespace::NamespaceService;
 use service_grpc_object_store::ObjectStoreService;
+use service_grpc_rollup::RollupService;
 use service_grpc_schema::SchemaService;
 use service_grpc_table::TableService;
 use std::sync::Arc;
@@ -62,4 +63,11 @@ impl RpcWriteGrpcDelegate {
     pub fn table_service(&self) -> impl table_service_server::TableService {
         TableService::new(Arc::clone(&self.catalog))
     }
+
+    /// Acquire a [`RollupService`] gRPC service implementation.
+    ///
+    /// [`RollupService`]: generated_types::influxdata::iox::rollup::v1::rollup_service_server::RollupService
+    pub fn rollup_service(&self) -> impl rollup_service_server::RollupService {
+        RollupService::new(Arc::clone(&self.catalog))
+    }
 }
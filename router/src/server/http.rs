@@ -1,5 +1,6 @@
 //! HTTP service implementations for `router`.
 
+pub mod quota;
 pub mod write;
 
 use std::{str::Utf8Error, time::Instant};
@@ -17,6 +18,7 @@ use thiserror::Error;
 use tokio::sync::{Semaphore, TryAcquireError};
 use trace::ctx::SpanContext;
 
+use self::quota::{QuotaError, WriteQuotas};
 use self::write::{
     multi_tenant::MultiTenantExtractError, single_tenant::SingleTenantExtractError, WriteParams,
     WriteRequestUnifier,
@@ -87,6 +89,10 @@ pub enum Error {
     #[error(transparent)]
     NamespaceResolver(#[from] crate::namespace_resolver::Error),
 
+    /// The write exceeds a rate quota of its namespace.
+    #[error(transparent)]
+    Quota(#[from] QuotaError),
+
     /// The router is currently servicing the maximum permitted number of
     /// simultaneous requests.
     #[error("this service is overloaded, please try again later")]
@@ -124,6 +130,7 @@ impl Error {
                 crate::namespace_resolver::ns_autocreation::NamespaceCreationError::Reject(_),
             )) => StatusCode::BAD_REQUEST,
             Error::NamespaceResolver(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            Error::Quota(_) => StatusCode::TOO_MANY_REQUESTS,
             Error::RequestLimit => StatusCode::SERVICE_UNAVAILABLE,
             Error::Unauthenticated => StatusCode::UNAUTHORIZED,
             Error::Forbidden => StatusCode::FORBIDDEN,
@@ -131,6 +138,15 @@ impl Error {
             Error::MultiTenantError(e) => StatusCode::from(e),
         }
     }
+
+    /// The number of seconds after which a rejected request may be retried,
+    /// if known, for use in the `Retry-After` response header.
+    pub fn retry_after_secs(&self) -> Option<u64> {
+        match self {
+            Error::Quota(e) => Some(e.retry_after_secs()),
+            _ => None,
+        }
+    }
 }
 
 impl From<&DmlError> for StatusCode {
@@ -198,12 +214,16 @@ pub struct HttpDelegate<D, N, T = SystemProvider> {
     // overall system availability, instead of OOMing or otherwise failing.
     request_sem: Semaphore,
 
+    // The write rate of each namespace, checked against its write quotas.
+    write_quotas: WriteQuotas,
+
     write_metric_lines: U64Counter,
     http_line_protocol_parse_duration: DurationHistogram,
     write_metric_fields: U64Counter,
     write_metric_tables: U64Counter,
     write_metric_body_size: U64Counter,
     request_limit_rejected: U64Counter,
+    quota_rejected: U64Counter,
 }
 
 impl<D, N> HttpDelegate<D, N, SystemProvider> {
@@ -250,6 +270,12 @@ impl<D, N> HttpDelegate<D, N, SystemProvider> {
                 "number of HTTP requests rejected due to exceeding parallel request limit",
             )
             .recorder(&[]);
+        let quota_rejected = metrics
+            .register_metric::<U64Counter>(
+                "http_write_quota_rejected",
+                "number of HTTP write requests rejected due to exceeding a namespace write quota",
+            )
+            .recorder(&[]);
         let http_line_protocol_parse_duration = metrics
             .register_metric::<DurationHistogram>(
                 "http_line_protocol_parse_duration",
@@ -264,12 +290,14 @@ impl<D, N> HttpDelegate<D, N, SystemProvider> {
             write_request_mode_handler,
             dml_handler,
             request_sem: Semaphore::new(max_requests),
+            write_quotas: Default::default(),
             write_metric_lines,
             http_line_protocol_parse_duration,
             write_metric_fields,
             write_metric_tables,
             write_metric_body_size,
             request_limit_rejected,
+            quota_rejected,
         }
     }
 }
@@ -373,6 +401,19 @@ where
             .get_namespace_schema(&write_info.namespace)
             .await?;
 
+        // Reject the write if the namespace is writing faster than its quotas allow.
+        if let Err(e) = self.write_quotas.check(
+            &write_info.namespace,
+            &namespace_schema,
+            body.len() as u64,
+            stats.num_lines as u64,
+            self.time_provider.now(),
+        ) {
+            warn!(error=%e, namespace=%write_info.namespace, "write quota exceeded");
+            self.quota_rejected.inc(1);
+            return Err(e.into());
+        }
+
         self.dml_handler
             .write(&write_info.namespace, namespace_schema, batches, span_ctx)
             .await
@@ -450,7 +491,8 @@ mod tests {
 
     use assert_matches::assert_matches;
     use data_types::{
-        NamespaceId, NamespaceName, NamespaceNameError, OrgBucketMappingError, TableId,
+        NamespaceId, NamespaceName, NamespaceNameError, NamespaceSchema, OrgBucketMappingError,
+        TableId,
     };
     use flate2::{write::GzEncoder, Compression};
     use hyper::header::HeaderValue;
@@ -1176,6 +1218,70 @@ mod tests {
         assert_metric_hit(&metrics, "http_request_limit_rejected", Some(1));
     }
 
+    // This test ensures writes are rejected with a 429 once a namespace exceeds
+    // its write quota.
+    #[tokio::test]
+    async fn test_write_quota_enforced() {
+        let schema = NamespaceSchema {
+            id: NAMESPACE_ID,
+            tables: Default::default(),
+            max_columns_per_table: 500,
+            max_tables: 200,
+            max_write_bytes_per_second: None,
+            max_write_lines_per_second: Some(2),
+            max_concurrent_queries: None,
+            retention_period_ns: None,
+            partition_template: Default::default(),
+        };
+        let mock_namespace_resolver = MockNamespaceResolver::new(
+            [(
+                NamespaceName::new(NAMESPACE_NAME).unwrap(),
+                Arc::new(schema),
+            )]
+            .into(),
+        );
+
+        let dml_handler = Arc::new(MockDmlHandler::default().with_write_return([Ok(())]));
+        let metrics = Arc::new(metric::Registry::default());
+        let delegate = HttpDelegate::new(
+            MAX_BYTES,
+            100,
+            mock_namespace_resolver,
+            Arc::clone(&dml_handler),
+            &metrics,
+            Box::<MultiTenantRequestUnifier>::default(),
+        );
+
+        let request = || {
+            Request::builder()
+                .uri("https://bananas.example/api/v2/write?org=bananas&bucket=test")
+                .method("POST")
+                .body(Body::from(
+                    "platanos val=1i 1\nplatanos val=2i 2\nplatanos val=3i 3",
+                ))
+                .unwrap()
+        };
+
+        // The first write exhausts the quota...
+        delegate
+            .route(request())
+            .await
+            .expect("first write should succeed");
+        assert_metric_hit(&metrics, "http_write_quota_rejected", Some(0));
+
+        // ...causing the following one to be rejected before reaching the DML
+        // handlers.
+        let err = delegate
+            .route(request())
+            .await
+            .expect_err("second write should be rate limited");
+        assert_matches!(err, Error::Quota(QuotaError::WriteLines { limit: 2, .. }));
+        assert_eq!(err.as_status_code(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(err.retry_after_secs(), Some(1));
+        assert_metric_hit(&metrics, "http_write_quota_rejected", Some(1));
+        assert_eq!(dml_handler.calls().len(), 1);
+    }
+
     /// Assert the router rejects writes to the V1 endpoint when in
     /// "multi-tenant" mode.
     #[tokio::test]
@@ -1431,6 +1537,15 @@ mod tests {
             "rejecting write due to non-existing namespace: bananas",
         ),
 
+        (
+            Quota(QuotaError::WriteLines {
+                namespace: "bananas".to_string(),
+                limit: 42,
+                retry_after: Duration::from_secs(2),
+            }),
+            "namespace bananas exceeded its write quota of 42 lines per second, retry after 2s",
+        ),
+
         (
             RequestLimit,
             "this service is overloaded, please try again later",
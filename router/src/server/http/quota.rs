@@ -0,0 +1,271 @@
+//! Enforcement of the per-namespace write rate quotas.
+
+use std::time::Duration;
+
+use data_types::{NamespaceId, NamespaceName, NamespaceSchema};
+use hashbrown::HashMap;
+use iox_time::Time;
+use parking_lot::Mutex;
+use thiserror::Error;
+
+/// Errors returned when a write exceeds the rate quota of its namespace.
+#[derive(Debug, Error)]
+pub enum QuotaError {
+    /// The namespace exceeded its write bytes per second quota.
+    #[error(
+        "namespace {namespace} exceeded its write quota of {limit} bytes per second, \
+        retry after {}s", self.retry_after_secs()
+    )]
+    WriteBytes {
+        /// The name of the rate limited namespace.
+        namespace: String,
+        /// The configured limit.
+        limit: u64,
+        /// The time after which the write may succeed.
+        retry_after: Duration,
+    },
+
+    /// The namespace exceeded its write lines per second quota.
+    #[error(
+        "namespace {namespace} exceeded its write quota of {limit} lines per second, \
+        retry after {}s", self.retry_after_secs()
+    )]
+    WriteLines {
+        /// The name of the rate limited namespace.
+        namespace: String,
+        /// The configured limit.
+        limit: u64,
+        /// The time after which the write may succeed.
+        retry_after: Duration,
+    },
+}
+
+impl QuotaError {
+    /// The number of whole seconds after which the write may succeed, rounded
+    /// up and at least one, as sent in the `Retry-After` response header.
+    pub fn retry_after_secs(&self) -> u64 {
+        let retry_after = match self {
+            Self::WriteBytes { retry_after, .. } | Self::WriteLines { retry_after, .. } => {
+                retry_after
+            }
+        };
+
+        let secs = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+        secs.max(1)
+    }
+}
+
+/// Tracks the write rate of each namespace against the `max_write_bytes_per_second` and
+/// `max_write_lines_per_second` quotas of its [`NamespaceSchema`].
+///
+/// Each quota is a token bucket holding at most one second worth of writes. A write is admitted
+/// as long as the bucket is not in debt, and its full cost is then taken from the bucket, so a
+/// single write larger than the quota is accepted but delays the next writes accordingly.
+///
+/// The rate is tracked by each router independently: a namespace written to through N routers
+/// may be written to at up to N times its quota.
+#[derive(Debug, Default)]
+pub struct WriteQuotas {
+    buckets: Mutex<HashMap<NamespaceId, NamespaceBuckets>>,
+}
+
+#[derive(Debug, Default)]
+struct NamespaceBuckets {
+    bytes: Option<TokenBucket>,
+    lines: Option<TokenBucket>,
+}
+
+impl WriteQuotas {
+    /// Account for a write of `bytes` of line protocol containing `lines` lines into `namespace`
+    /// at `now`, returning an error without accounting for the write if it exceeds one of the
+    /// quotas of `schema`.
+    pub fn check(
+        &self,
+        namespace: &NamespaceName<'_>,
+        schema: &NamespaceSchema,
+        bytes: u64,
+        lines: u64,
+        now: Time,
+    ) -> Result<(), QuotaError> {
+        if schema.max_write_bytes_per_second.is_none()
+            && schema.max_write_lines_per_second.is_none()
+        {
+            return Ok(());
+        }
+
+        let mut buckets = self.buckets.lock();
+        let buckets = buckets.entry(schema.id).or_default();
+
+        let bytes_bucket =
+            TokenBucket::sync(&mut buckets.bytes, schema.max_write_bytes_per_second, now);
+        if let Some(retry_after) = bytes_bucket.as_ref().and_then(|b| b.retry_after()) {
+            return Err(QuotaError::WriteBytes {
+                namespace: namespace.to_string(),
+                limit: bytes_bucket.as_ref().unwrap().limit,
+                retry_after,
+            });
+        }
+
+        let lines_bucket =
+            TokenBucket::sync(&mut buckets.lines, schema.max_write_lines_per_second, now);
+        if let Some(retry_after) = lines_bucket.as_ref().and_then(|b| b.retry_after()) {
+            return Err(QuotaError::WriteLines {
+                namespace: namespace.to_string(),
+                limit: lines_bucket.as_ref().unwrap().limit,
+                retry_after,
+            });
+        }
+
+        if let Some(b) = bytes_bucket {
+            b.take(bytes);
+        }
+        if let Some(b) = lines_bucket {
+            b.take(lines);
+        }
+
+        Ok(())
+    }
+}
+
+/// A token bucket refilled at `limit` tokens per second, holding at most `limit` tokens.
+#[derive(Debug)]
+struct TokenBucket {
+    limit: u64,
+    /// May be negative when the bucket is in debt.
+    tokens: f64,
+    last_refill: Time,
+}
+
+impl TokenBucket {
+    /// Bring `bucket` up to date with the current `limit` at `now`, creating a full bucket for
+    /// newly set limits and removing it when the limit is lifted.
+    fn sync(bucket: &mut Option<Self>, limit: Option<u64>, now: Time) -> Option<&mut Self> {
+        let Some(limit) = limit else {
+            *bucket = None;
+            return None;
+        };
+
+        if let Some(b) = bucket {
+            let elapsed = now
+                .checked_duration_since(b.last_refill)
+                .unwrap_or_default();
+            b.limit = limit;
+            b.tokens = (b.tokens + elapsed.as_secs_f64() * limit as f64).min(limit as f64);
+            b.last_refill = now;
+        } else {
+            *bucket = Some(Self {
+                limit,
+                tokens: limit as f64,
+                last_refill: now,
+            });
+        }
+        bucket.as_mut()
+    }
+
+    /// The time until the bucket is out of debt, if it is in debt.
+    fn retry_after(&self) -> Option<Duration> {
+        (self.tokens <= 0.0).then(|| {
+            Duration::from_secs_f64((0.0 - self.tokens) / self.limit as f64)
+                .max(Duration::from_millis(1))
+        })
+    }
+
+    fn take(&mut self, n: u64) {
+        self.tokens -= n as f64;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use super::*;
+
+    fn schema(bytes: Option<u64>, lines: Option<u64>) -> NamespaceSchema {
+        NamespaceSchema {
+            id: NamespaceId::new(42),
+            tables: Default::default(),
+            max_columns_per_table: 200,
+            max_tables: 200,
+            max_write_bytes_per_second: bytes,
+            max_write_lines_per_second: lines,
+            max_concurrent_queries: None,
+            retention_period_ns: None,
+            partition_template: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_unlimited() {
+        let quotas = WriteQuotas::default();
+        let ns = NamespaceName::new("bananas").unwrap();
+        let now = Time::from_timestamp_nanos(0);
+
+        for _ in 0..10 {
+            quotas
+                .check(&ns, &schema(None, None), u64::MAX, u64::MAX, now)
+                .unwrap();
+        }
+        assert!(quotas.buckets.lock().is_empty());
+    }
+
+    #[test]
+    fn test_bytes_quota() {
+        let quotas = WriteQuotas::default();
+        let ns = NamespaceName::new("bananas").unwrap();
+        let schema = schema(Some(100), None);
+        let now = Time::from_timestamp_nanos(0);
+
+        // The first second worth of writes is accepted, the write exceeding it too.
+        quotas.check(&ns, &schema, 60, 1, now).unwrap();
+        quotas.check(&ns, &schema, 60, 1, now).unwrap();
+
+        // But the bucket is now in debt.
+        let err = quotas.check(&ns, &schema, 1, 1, now).unwrap_err();
+        assert_matches!(err, QuotaError::WriteBytes { limit: 100, retry_after, .. } => {
+            assert_eq!(retry_after, Duration::from_millis(200));
+        });
+
+        // Sub-second waits are rounded up to a whole second.
+        assert_eq!(err.retry_after_secs(), 1);
+        assert!(err.to_string().ends_with("retry after 1s"));
+
+        // Until enough time passed to repay it.
+        let now = now.checked_add(Duration::from_millis(100)).unwrap();
+        quotas.check(&ns, &schema, 1, 1, now).unwrap_err();
+        let now = now.checked_add(Duration::from_millis(150)).unwrap();
+        quotas.check(&ns, &schema, 1, 1, now).unwrap();
+    }
+
+    #[test]
+    fn test_lines_quota() {
+        let quotas = WriteQuotas::default();
+        let ns = NamespaceName::new("bananas").unwrap();
+        let schema = schema(Some(1_000), Some(10));
+        let now = Time::from_timestamp_nanos(0);
+
+        quotas.check(&ns, &schema, 10, 10, now).unwrap();
+        let err = quotas.check(&ns, &schema, 10, 1, now).unwrap_err();
+        assert_matches!(err, QuotaError::WriteLines { limit: 10, .. });
+
+        // The rejected write did not consume the bytes quota.
+        let buckets = quotas.buckets.lock();
+        let bytes = buckets[&schema.id].bytes.as_ref().unwrap();
+        assert_eq!(bytes.tokens, 990.0);
+    }
+
+    #[test]
+    fn test_quota_lifted() {
+        let quotas = WriteQuotas::default();
+        let ns = NamespaceName::new("bananas").unwrap();
+        let now = Time::from_timestamp_nanos(0);
+
+        quotas
+            .check(&ns, &schema(Some(10), None), 100, 1, now)
+            .unwrap();
+        quotas
+            .check(&ns, &schema(Some(10), None), 1, 1, now)
+            .unwrap_err();
+        quotas.check(&ns, &schema(None, None), 1, 1, now).unwrap();
+    }
+}
@@ -89,6 +89,41 @@ async fn test_write_ok() {
     );
 }
 
+#[tokio::test]
+async fn test_write_v2_org_and_bucket_of_namespace() {
+    let ctx = TestContextBuilder::default()
+        .with_autocreate_namespace(None)
+        .build()
+        .await;
+
+    // Write to a namespace with more than one '_' using the org and bucket
+    // writers addressing a namespace by name map it to.
+    let namespace = data_types::NamespaceName::new("my_org_bucket").unwrap();
+    let (org, bucket) = namespace
+        .to_v2_org_and_bucket(false)
+        .expect("namespace has an org and bucket");
+
+    let now = SystemProvider::default()
+        .now()
+        .timestamp_nanos()
+        .to_string();
+    let response = ctx
+        .write_lp(org, bucket, "platanos val=42i ".to_string() + &now)
+        .await
+        .expect("write failed");
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    ctx.catalog()
+        .repositories()
+        .await
+        .namespaces()
+        .get_by_name(namespace.as_str(), SoftDeletedRows::ExcludeDeleted)
+        .await
+        .expect("query should succeed")
+        .expect("namespace not found");
+    assert_eq!(ctx.write_calls().len(), 1);
+}
+
 #[tokio::test]
 async fn test_write_outside_retention_period() {
     let ctx = TestContextBuilder::default()
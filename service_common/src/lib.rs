@@ -18,14 +18,15 @@
 use workspace_hack as _;
 
 mod error;
+pub mod namespaces;
 pub mod planner;
 pub mod test_util;
 
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use iox_query::QueryNamespace;
-use trace::span::Span;
+use iox_query::{exec::IOxSessionContext, QueryNamespace};
+use trace::{ctx::SpanContext, span::Span};
 use tracker::InstrumentedAsyncOwnedSemaphorePermit;
 
 /// Trait that allows the query engine (which includes flight and storage/InfluxRPC) to access a
@@ -47,8 +48,49 @@ pub trait QueryNamespaceProvider: std::fmt::Debug + Send + Sync + 'static {
         include_debug_info_tables: bool,
     ) -> Option<Arc<Self::Db>>;
 
-    /// Acquire concurrency-limiting sempahore
-    async fn acquire_semaphore(&self, span: Option<Span>) -> InstrumentedAsyncOwnedSemaphorePermit;
+    /// Acquire concurrency-limiting sempahore for a query against the namespace `name`.
+    ///
+    /// On top of the global limit, the query counts against the query concurrency quota of the
+    /// namespace, if it has one.
+    async fn acquire_semaphore(&self, name: &str, span: Option<Span>) -> QueryPermit;
+
+    /// List the names of all namespaces that can be queried.
+    async fn namespace_names(&self) -> Vec<String>;
+
+    /// Create a query context that is not bound to a namespace, for statements such as
+    /// `SHOW DATABASES`.
+    fn new_query_context(&self, span_ctx: Option<SpanContext>) -> IOxSessionContext;
+
+    /// Create the namespace `name` with the given retention period, succeeding if it
+    /// already exists with the same retention period.
+    async fn create_namespace(
+        &self,
+        name: &str,
+        retention_period_ns: Option<i64>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+}
+
+/// Permit to run a query, acquired through [`QueryNamespaceProvider::acquire_semaphore`].
+///
+/// The permit is released when dropped.
+#[derive(Debug)]
+pub struct QueryPermit {
+    _namespace: Option<InstrumentedAsyncOwnedSemaphorePermit>,
+    _global: InstrumentedAsyncOwnedSemaphorePermit,
+}
+
+impl QueryPermit {
+    /// Create a new permit from the `global` permit and, if the namespace has a query concurrency
+    /// quota, its `namespace` permit.
+    pub fn new(
+        global: InstrumentedAsyncOwnedSemaphorePermit,
+        namespace: Option<InstrumentedAsyncOwnedSemaphorePermit>,
+    ) -> Self {
+        Self {
+            _namespace: namespace,
+            _global: global,
+        }
+    }
 }
 
 pub use error::datafusion_error_to_tonic_code;
@@ -0,0 +1,131 @@
+//! Access to the namespaces referenced by InfluxQL statements.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use authz::{Action, Authorizer, Permission, Resource};
+use datafusion::{
+    catalog::schema::SchemaProvider,
+    error::{DataFusionError, Result},
+};
+use iox_query::QueryNamespace;
+use iox_query_influxql::frontend::planner::NamespaceProvider;
+use trace::{ctx::SpanContext, span::SpanExt};
+
+use crate::QueryNamespaceProvider;
+
+/// A [`NamespaceProvider`] for the namespaces of a [`QueryNamespaceProvider`]
+/// that the request token is authorized to access.
+///
+/// Authorization failures are returned as [`DataFusionError::External`]
+/// errors, see [`authz_error`].
+#[derive(Debug)]
+pub struct AuthorizedNamespaces<S> {
+    server: Arc<S>,
+    authz: Option<Arc<dyn Authorizer>>,
+    token: Option<Vec<u8>>,
+    span_ctx: Option<SpanContext>,
+}
+
+impl<S> AuthorizedNamespaces<S>
+where
+    S: QueryNamespaceProvider,
+{
+    /// Create a provider for the namespaces of `server` that `token` grants
+    /// access to, as checked by `authz` if set.
+    pub fn new(
+        server: Arc<S>,
+        authz: Option<Arc<dyn Authorizer>>,
+        token: Option<Vec<u8>>,
+        span_ctx: Option<SpanContext>,
+    ) -> Self {
+        Self {
+            server,
+            authz,
+            token,
+            span_ctx,
+        }
+    }
+
+    async fn authorize(&self, name: &str, action: Action) -> Result<()> {
+        let perms = [Permission::ResourceAction(
+            Resource::Database(name.to_owned()),
+            action,
+        )];
+        self.authz
+            .permissions(self.token.clone(), &perms)
+            .await
+            .map_err(|e| DataFusionError::External(Box::new(e)))?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<S> NamespaceProvider for AuthorizedNamespaces<S>
+where
+    S: QueryNamespaceProvider,
+{
+    async fn namespace_names(&self) -> Result<Vec<String>> {
+        let perms = self
+            .server
+            .namespace_names()
+            .await
+            .into_iter()
+            .map(|ns| Permission::ResourceAction(Resource::Database(ns), Action::Read))
+            .collect::<Vec<_>>();
+        if perms.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let granted = match self.authz.permissions(self.token.clone(), &perms).await {
+            Ok(granted) => granted,
+            Err(authz::Error::Forbidden) => vec![],
+            Err(e) => return Err(DataFusionError::External(Box::new(e))),
+        };
+
+        Ok(granted
+            .into_iter()
+            .filter_map(|perm| match perm {
+                Permission::ResourceAction(Resource::Database(ns), Action::Read) => Some(ns),
+                _ => None,
+            })
+            .collect())
+    }
+
+    async fn namespace_schema(&self, name: &str) -> Result<Option<Arc<dyn SchemaProvider>>> {
+        self.authorize(name, Action::Read).await?;
+
+        let Some(db) = self
+            .server
+            .db(name, self.span_ctx.child_span("get namespace"), false)
+            .await
+        else {
+            return Ok(None);
+        };
+
+        let ctx = db.new_query_context(self.span_ctx.clone());
+        let cfg = ctx.inner().copied_config();
+        let catalog = &cfg.options().catalog;
+        Ok(ctx
+            .inner()
+            .catalog(&catalog.default_catalog)
+            .and_then(|c| c.schema(&catalog.default_schema)))
+    }
+
+    async fn create_namespace(&self, name: &str, retention_period_ns: Option<i64>) -> Result<()> {
+        self.authorize(name, Action::Create).await?;
+
+        self.server
+            .create_namespace(name, retention_period_ns)
+            .await
+            .map_err(DataFusionError::External)
+    }
+}
+
+/// The authorization error `e` was caused by, if any.
+pub fn authz_error(e: &DataFusionError) -> Option<&authz::Error> {
+    match e {
+        DataFusionError::External(e) => e.downcast_ref(),
+        _ => None,
+    }
+}
@@ -3,9 +3,10 @@ use std::sync::Arc;
 
 use bytes::Bytes;
 use datafusion::{
-    arrow::datatypes::SchemaRef, error::DataFusionError, physical_plan::ExecutionPlan,
+    arrow::datatypes::SchemaRef, error::DataFusionError, logical_expr::LogicalPlan,
+    physical_plan::ExecutionPlan,
 };
-use flightsql::{FlightSQLCommand, FlightSQLPlanner};
+use flightsql::{FlightSQLCommand, FlightSQLPlanner, PreparedStatementCache};
 use iox_query::{
     exec::IOxSessionContext,
     frontend::sql::SqlQueryPlanner,
@@ -15,7 +16,8 @@ use iox_query::{
 use iox_query_influxrpc::InfluxRpcPlanner;
 
 pub use datafusion::error::{DataFusionError as Error, Result};
-use iox_query_influxql::frontend::planner::InfluxQLQueryPlanner;
+pub use iox_query_influxql::frontend::params::{ParamValue, StatementParams};
+use iox_query_influxql::frontend::planner::{InfluxQLQueryPlanner, NamespaceProvider};
 use predicate::rpc_predicate::InfluxRpcPredicate;
 
 /// Query planner that plans queries on a separate threadpool.
@@ -51,16 +53,45 @@ impl Planner {
 
     /// Plan an InfluxQL query against the data in `database`, and return a
     /// DataFusion physical execution plan.
+    ///
+    /// The bind parameters of `query` are replaced with the values of
+    /// `params`.
     pub async fn influxql(
         &self,
         query: impl Into<String> + Send,
+        params: StatementParams,
     ) -> Result<Arc<dyn ExecutionPlan>> {
         let planner = InfluxQLQueryPlanner::new();
         let query = query.into();
         let ctx = self.ctx.child_ctx("planner influxql");
 
         self.ctx
-            .run(async move { planner.query(&query, &ctx).await })
+            .run(async move { planner.query(&query, params, &ctx).await })
+            .await
+    }
+
+    /// Plan an InfluxQL query against the data in the namespace `namespace`,
+    /// and return a DataFusion physical execution plan.
+    ///
+    /// The other databases referenced by the query are resolved, and those of
+    /// `CREATE DATABASE` statements created, with `namespaces`. Without a
+    /// `namespace`, the query may only reference databases explicitly.
+    pub async fn influxql_with_namespaces(
+        &self,
+        query: impl Into<String> + Send,
+        params: StatementParams,
+        namespace: Option<String>,
+        namespaces: Arc<dyn NamespaceProvider>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let mut planner = InfluxQLQueryPlanner::new().with_namespace_provider(namespaces);
+        if let Some(namespace) = namespace {
+            planner = planner.with_namespace(namespace);
+        }
+        let query = query.into();
+        let ctx = self.ctx.child_ctx("planner influxql");
+
+        self.ctx
+            .run(async move { planner.query(&query, params, &ctx).await })
             .await
     }
 
@@ -94,6 +125,7 @@ impl Planner {
         namespace_name: impl Into<String> + Send,
         namespace: Arc<N>,
         cmd: FlightSQLCommand,
+        statements: Arc<PreparedStatementCache>,
     ) -> Result<Bytes>
     where
         N: QueryNamespace + 'static,
@@ -103,7 +135,7 @@ impl Planner {
 
         self.ctx
             .run(async move {
-                FlightSQLPlanner::do_action(namespace_name, namespace, cmd, &ctx)
+                FlightSQLPlanner::do_action(namespace_name, namespace, cmd, &statements, &ctx)
                     .await
                     .map_err(DataFusionError::from)
             })
@@ -242,6 +274,28 @@ impl Planner {
             .await
     }
 
+    /// Creates a plan as described on [`InfluxRpcPlanner::series_cardinality`],
+    /// on a separate threadpool
+    pub async fn series_cardinality<N>(
+        &self,
+        namespace: Arc<N>,
+        predicate: InfluxRpcPredicate,
+    ) -> Result<Option<LogicalPlan>>
+    where
+        N: QueryNamespace + 'static,
+    {
+        let planner = InfluxRpcPlanner::new(self.ctx.child_ctx("planner series_cardinality")).await;
+
+        self.ctx
+            .run(async move {
+                planner
+                    .series_cardinality(namespace, predicate)
+                    .await
+                    .map_err(|e| e.to_df_error("series_cardinality"))
+            })
+            .await
+    }
+
     /// Creates a plan as described on [`InfluxRpcPlanner::read_group`], on a
     /// separate threadpool
     pub async fn read_group<N>(
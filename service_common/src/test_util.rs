@@ -1,14 +1,15 @@
 use std::{collections::BTreeMap, sync::Arc};
 
 use async_trait::async_trait;
-use iox_query::{exec::Executor, test::TestDatabase};
-use parking_lot::Mutex;
-use trace::span::Span;
-use tracker::{
-    AsyncSemaphoreMetrics, InstrumentedAsyncOwnedSemaphorePermit, InstrumentedAsyncSemaphore,
+use iox_query::{
+    exec::{Executor, ExecutorType, IOxSessionContext},
+    test::TestDatabase,
 };
+use parking_lot::Mutex;
+use trace::{ctx::SpanContext, span::Span};
+use tracker::{AsyncSemaphoreMetrics, InstrumentedAsyncSemaphore};
 
-use crate::QueryNamespaceProvider;
+use crate::{QueryNamespaceProvider, QueryPermit};
 
 #[derive(Debug)]
 pub struct TestDatabaseStore {
@@ -72,10 +73,31 @@ impl QueryNamespaceProvider for TestDatabaseStore {
         databases.get(name).cloned()
     }
 
-    async fn acquire_semaphore(&self, span: Option<Span>) -> InstrumentedAsyncOwnedSemaphorePermit {
-        Arc::clone(&self.query_semaphore)
+    async fn acquire_semaphore(&self, _name: &str, span: Option<Span>) -> QueryPermit {
+        let permit = Arc::clone(&self.query_semaphore)
             .acquire_owned(span)
             .await
-            .unwrap()
+            .unwrap();
+        QueryPermit::new(permit, None)
+    }
+
+    async fn namespace_names(&self) -> Vec<String> {
+        self.databases.lock().keys().cloned().collect()
+    }
+
+    fn new_query_context(&self, span_ctx: Option<SpanContext>) -> IOxSessionContext {
+        self.executor
+            .new_execution_config(ExecutorType::Query)
+            .with_span_context(span_ctx)
+            .build()
+    }
+
+    async fn create_namespace(
+        &self,
+        name: &str,
+        _retention_period_ns: Option<i64>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.db_or_create(name).await;
+        Ok(())
     }
 }
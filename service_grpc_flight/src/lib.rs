@@ -23,9 +23,11 @@ use workspace_hack as _;
 mod keep_alive;
 mod request;
 
-use arrow::error::ArrowError;
+use arrow::{compute::concat_batches, error::ArrowError};
 use arrow_flight::{
+    decode::FlightRecordBatchStream,
     encode::FlightDataEncoderBuilder,
+    error::FlightError,
     flight_descriptor::DescriptorType,
     flight_service_server::{FlightService as Flight, FlightServiceServer as FlightServer},
     Action, ActionType, Criteria, Empty, FlightData, FlightDescriptor, FlightEndpoint, FlightInfo,
@@ -34,14 +36,19 @@ use arrow_flight::{
 use authz::{extract_token, Authorizer};
 use data_types::NamespaceNameError;
 use datafusion::{error::DataFusionError, physical_plan::ExecutionPlan};
-use flightsql::FlightSQLCommand;
+use flightsql::{FlightSQLCommand, PreparedStatementCache};
 use futures::{ready, Stream, StreamExt, TryStreamExt};
 use generated_types::influxdata::iox::querier::v1 as proto;
 use iox_query::{exec::IOxSessionContext, QueryCompletedToken, QueryNamespace};
 use observability_deps::tracing::{debug, info, warn};
 use prost::Message;
 use request::{IoxGetRequest, RunQuery};
-use service_common::{datafusion_error_to_tonic_code, planner::Planner, QueryNamespaceProvider};
+use service_common::{
+    datafusion_error_to_tonic_code,
+    namespaces::{authz_error, AuthorizedNamespaces},
+    planner::Planner,
+    QueryNamespaceProvider, QueryPermit,
+};
 use snafu::{OptionExt, ResultExt, Snafu};
 use std::{
     fmt::Debug,
@@ -56,7 +63,6 @@ use tonic::{
 };
 use trace::{ctx::SpanContext, span::SpanExt};
 use trace_http::ctx::{RequestLogContext, RequestLogContextExt};
-use tracker::InstrumentedAsyncOwnedSemaphorePermit;
 
 /// The supported names of the grpc header that contain the target database
 /// for FlightSQL requests.
@@ -136,6 +142,12 @@ pub enum Error {
     #[snafu(display("Invalid protobuf: {}", source))]
     Deserialization { source: prost::DecodeError },
 
+    #[snafu(display("DoPut request has no FlightDescriptor"))]
+    NoFlightDescriptor,
+
+    #[snafu(display("Invalid parameters in DoPut request: {}", source))]
+    DecodeParameters { source: FlightError },
+
     #[snafu(display("Unsupported message type: {}", description))]
     UnsupportedMessageType { description: String },
 
@@ -166,6 +178,8 @@ impl From<Error> for tonic::Status {
             | Error::Unauthenticated { .. }
             | Error::PermissionDenied { .. }
             | Error::InvalidDatabaseName { .. }
+            | Error::NoFlightDescriptor
+            | Error::DecodeParameters { .. }
             | Error::Query { .. } => info!(e=%err, %namespace, %query, msg),
             Error::Optimize { .. }
             | Error::EncodeSchema { .. }
@@ -199,13 +213,18 @@ impl Error {
             | Self::TooManyFlightSQLDatabases { .. }
             | Self::NoFlightSQLDatabase
             | Self::InvalidDatabaseHeader { .. }
-            | Self::InvalidDatabaseName { .. } => tonic::Code::InvalidArgument,
+            | Self::InvalidDatabaseName { .. }
+            | Self::NoFlightDescriptor
+            | Self::DecodeParameters { .. } => tonic::Code::InvalidArgument,
             Self::Planning { source, .. } | Self::Query { source, .. } => {
                 datafusion_error_to_tonic_code(&source)
             }
             Self::UnsupportedMessageType { .. } => tonic::Code::Unimplemented,
             Self::FlightSQL { source } => match source {
                 flightsql::Error::InvalidHandle { .. }
+                | flightsql::Error::InvalidParameters { .. }
+                | flightsql::Error::InvalidParameterCount { .. }
+                | flightsql::Error::UnsupportedParameter { .. }
                 | flightsql::Error::Decode { .. }
                 | flightsql::Error::Protocol { .. }
                 | flightsql::Error::UnsupportedMessageType { .. } => tonic::Code::InvalidArgument,
@@ -242,6 +261,8 @@ impl Error {
             | Error::EncodeSchema { .. }
             | Error::FlightSQL { .. }
             | Error::Deserialization { .. }
+            | Error::NoFlightDescriptor
+            | Error::DecodeParameters { .. }
             | Error::UnsupportedMessageType { .. }
             | Error::Unauthenticated
             | Error::PermissionDenied
@@ -266,6 +287,8 @@ impl Error {
             | Error::EncodeSchema { .. }
             | Error::FlightSQL { .. }
             | Error::Deserialization { .. }
+            | Error::NoFlightDescriptor
+            | Error::DecodeParameters { .. }
             | Error::UnsupportedMessageType { .. }
             | Error::Unauthenticated
             | Error::PermissionDenied
@@ -411,7 +434,7 @@ type TonicStream<T> = Pin<Box<dyn Stream<Item = Result<T, tonic::Status>> + Send
 ///       ┃                                                  ┃
 /// ```
 ///
-/// ## FlightSQL Prepared Statement (no bind parameters like $1, etc)
+/// ## FlightSQL Prepared Statement
 ///
 /// To run a prepared query, via FlightSQL, the client undertakes a
 /// few more steps:
@@ -422,7 +445,8 @@ type TonicStream<T> = Pin<Box<dyn Stream<Item = Result<T, tonic::Status>> + Send
 /// 2. Call `DoAction` method with the the request
 ///
 /// 3. Receive a `ActionCreatePreparedStatementResponse`, which contains
-/// a prepared statement "handle".
+/// a prepared statement "handle" and the schema of the parameters
+/// (`$1`, `$2`, etc) of the query.
 ///
 /// 4. Encode the handle in a `CommandPreparedStatementQuery`
 /// FlightSQL structure in a [`FlightDescriptor`] and call the
@@ -430,6 +454,14 @@ type TonicStream<T> = Pin<Box<dyn Stream<Item = Result<T, tonic::Status>> + Send
 ///
 /// 5. Steps 5,6,7 proceed the same as for a FlightSQL ad-hoc query
 ///
+/// If the query has parameters, the client binds their values before step
+/// 4 by calling `DoPut` with the same [`FlightDescriptor`] and a single row
+/// of values. The `app_metadata` of the returned `PutResult` is a new
+/// handle that carries the values, which the client uses in place of the
+/// original handle from step 4 on, so any querier can run the statement.
+/// The values are also kept by the querier that received them for clients
+/// that keep using the original handle.
+///
 /// ```text
 ///                                                      .───────.
 /// ╔═══════════╗                                       (         )
@@ -470,6 +502,8 @@ where
 {
     server: Arc<S>,
     authz: Option<Arc<dyn Authorizer>>,
+    /// State of the FlightSQL prepared statements created on this querier
+    prepared_statements: Arc<PreparedStatementCache>,
 }
 
 pub fn make_server<S>(
@@ -479,7 +513,11 @@ pub fn make_server<S>(
 where
     S: QueryNamespaceProvider,
 {
-    FlightServer::new(FlightService { server, authz })
+    FlightServer::new(FlightService {
+        server,
+        authz,
+        prepared_statements: Default::default(),
+    })
 }
 
 impl<S> FlightService<S>
@@ -487,14 +525,16 @@ where
     S: QueryNamespaceProvider,
 {
     /// Implementation of the `DoGet` method
+    #[allow(clippy::too_many_arguments)]
     async fn run_do_get(
         &self,
         span_ctx: Option<SpanContext>,
         trace: String,
-        permit: InstrumentedAsyncOwnedSemaphorePermit,
+        permit: QueryPermit,
         query: RunQuery,
         namespace_name: String,
         is_debug: bool,
+        authz_token: Option<Vec<u8>>,
     ) -> Result<Response<TonicStream<FlightData>>, tonic::Status> {
         let db = self
             .server
@@ -508,7 +548,7 @@ where
                 namespace_name: &namespace_name,
             })?;
 
-        let ctx = db.new_query_context(span_ctx);
+        let ctx = db.new_query_context(span_ctx.clone());
         let (query_completed_token, physical_plan) = match &query {
             RunQuery::Sql(sql_query) => {
                 let token = db.record_query(&ctx, "sql", Box::new(sql_query.clone()));
@@ -521,14 +561,33 @@ where
                     })?;
                 (token, plan)
             }
-            RunQuery::InfluxQL(sql_query) => {
+            RunQuery::InfluxQL(sql_query, params) => {
                 let token = db.record_query(&ctx, "influxql", Box::new(sql_query.clone()));
+                // Other databases referenced by the query are authorized as they are resolved.
+                let namespaces = Arc::new(AuthorizedNamespaces::new(
+                    Arc::clone(&self.server),
+                    self.authz.clone(),
+                    authz_token,
+                    span_ctx,
+                ));
                 let plan = Planner::new(&ctx)
-                    .influxql(sql_query)
+                    .influxql_with_namespaces(
+                        sql_query,
+                        params.clone(),
+                        Some(namespace_name.clone()),
+                        namespaces,
+                    )
                     .await
-                    .context(PlanningSnafu {
-                        namespace_name: &namespace_name,
-                        query: query.to_string(),
+                    .map_err(|source| match authz_error(&source) {
+                        Some(authz::Error::Forbidden | authz::Error::InvalidToken) => {
+                            Error::PermissionDenied
+                        }
+                        Some(authz::Error::NoToken) => Error::Unauthenticated,
+                        _ => Error::Planning {
+                            namespace_name: namespace_name.clone(),
+                            query: query.to_string(),
+                            source,
+                        },
                     })?;
                 (token, plan)
             }
@@ -615,19 +674,22 @@ where
 
         let perms = match query {
             RunQuery::FlightSQL(cmd) => flightsql_permissions(namespace_name, cmd),
-            RunQuery::Sql(_) | RunQuery::InfluxQL(_) => vec![authz::Permission::ResourceAction(
+            RunQuery::Sql(_) | RunQuery::InfluxQL(..) => vec![authz::Permission::ResourceAction(
                 authz::Resource::Database(namespace_name.to_string()),
                 authz::Action::Read,
             )],
         };
         self.authz
-            .permissions(authz_token, &perms)
+            .permissions(authz_token.clone(), &perms)
             .await
             .map_err(Error::from)?;
 
         let permit = self
             .server
-            .acquire_semaphore(span_ctx.child_span("query rate limit semaphore"))
+            .acquire_semaphore(
+                namespace_name,
+                span_ctx.child_span("query rate limit semaphore"),
+            )
             .await;
 
         // Log after we acquire the permit and are about to start execution
@@ -648,6 +710,7 @@ where
                 query.clone(),
                 namespace_name.to_string(),
                 is_debug,
+                authz_token,
             )
             .await;
 
@@ -731,6 +794,18 @@ where
         let cmd = cmd_from_descriptor(flight_descriptor.clone())?;
         info!(%namespace_name, %cmd, %trace, "GetFlightInfo request");
 
+        // Parameter values bound to a prepared statement via `DoPut` are
+        // passed on to `DoGet` in the ticket, so any querier can run it
+        let cmd = match cmd {
+            FlightSQLCommand::CommandPreparedStatementQuery(handle) => {
+                FlightSQLCommand::CommandPreparedStatementQuery(
+                    self.prepared_statements
+                        .with_bound_params(&namespace_name, handle),
+                )
+            }
+            cmd => cmd,
+        };
+
         let perms = flightsql_permissions(&namespace_name, &cmd);
         self.authz
             .permissions(authz_token, &perms)
@@ -782,13 +857,73 @@ where
         Ok(tonic::Response::new(flight_info))
     }
 
+    /// Handles `DoPut` RPC requests, which bind the parameters of a
+    /// FlightSQL prepared statement to the values in the stream.
+    ///
+    /// see [`FlightService`] for more details.
     async fn do_put(
         &self,
-        _request: Request<Streaming<FlightData>>,
+        request: Request<Streaming<FlightData>>,
     ) -> Result<Response<Self::DoPutStream>, tonic::Status> {
-        info!("Handling flightsql do_put body");
+        let external_span_ctx: Option<RequestLogContext> = request.extensions().get().cloned();
+        let trace = external_span_ctx.format_jaeger();
+
+        let namespace_name = get_flightsql_namespace(request.metadata())?;
+        let authz_token = get_flight_authz(request.metadata());
+        let mut flight_data = request.into_inner();
+
+        // The first message carries the FlightSQL command in its descriptor
+        let first = flight_data
+            .message()
+            .await?
+            .context(NoFlightDescriptorSnafu)?;
+        let flight_descriptor = first
+            .flight_descriptor
+            .clone()
+            .context(NoFlightDescriptorSnafu)?;
+        let cmd = cmd_from_descriptor(flight_descriptor)?;
+        info!(%namespace_name, %cmd, %trace, "DoPut request");
+
+        let handle = match cmd {
+            FlightSQLCommand::CommandPreparedStatementQuery(handle) => handle,
+            cmd => return Err(Error::unsupported_message_type(format!("DoPut with {cmd}")).into()),
+        };
 
-        Err(tonic::Status::unimplemented("Not yet implemented: do_put"))
+        let perms = flightsql_permissions(
+            &namespace_name,
+            &FlightSQLCommand::CommandPreparedStatementQuery(handle.clone()),
+        );
+        self.authz
+            .permissions(authz_token, &perms)
+            .await
+            .map_err(Error::from)?;
+
+        let flight_data =
+            futures::stream::iter([Ok(first)]).chain(flight_data.map_err(FlightError::Tonic));
+        let batches: Vec<_> = FlightRecordBatchStream::new_from_flight_data(flight_data)
+            .try_collect()
+            .await
+            .context(DecodeParametersSnafu)?;
+        let params = match batches.first() {
+            Some(batch) => concat_batches(&batch.schema(), &batches)
+                .map_err(FlightError::Arrow)
+                .context(DecodeParametersSnafu)?,
+            None => {
+                return Err(Error::from(flightsql::Error::InvalidParameters { num_rows: 0 }).into())
+            }
+        };
+
+        let bound = self
+            .prepared_statements
+            .bind(&namespace_name, handle, params)
+            .context(FlightSQLSnafu)?;
+        debug!(%namespace_name, %bound, %trace, "Completed DoPut request");
+
+        // The bound handle is returned so the client can run the statement
+        // on any querier
+        let app_metadata = bound.try_encode().context(FlightSQLSnafu)?;
+        let stream = futures::stream::iter([Ok(PutResult { app_metadata })]);
+        Ok(Response::new(stream.boxed()))
     }
 
     async fn do_action(
@@ -832,7 +967,12 @@ where
 
         let ctx = db.new_query_context(span_ctx);
         let body = Planner::new(&ctx)
-            .flight_sql_do_action(&namespace_name, db, cmd.clone())
+            .flight_sql_do_action(
+                &namespace_name,
+                db,
+                cmd.clone(),
+                Arc::clone(&self.prepared_statements),
+            )
             .await
             .context(PlanningSnafu {
                 namespace_name: &namespace_name,
@@ -958,7 +1098,7 @@ fn has_debug_header(metadata: &MetadataMap) -> bool {
 struct GetStream {
     inner: KeepAliveStream,
     #[allow(dead_code)]
-    permit: InstrumentedAsyncOwnedSemaphorePermit,
+    permit: QueryPermit,
     query_completed_token: QueryCompletedToken,
     done: bool,
 }
@@ -970,7 +1110,7 @@ impl GetStream {
         namespace_name: String,
         query: &RunQuery,
         query_completed_token: QueryCompletedToken,
-        permit: InstrumentedAsyncOwnedSemaphorePermit,
+        permit: QueryPermit,
     ) -> Result<Self, tonic::Status> {
         let app_metadata = proto::AppMetadata {};
 
@@ -1076,6 +1216,7 @@ mod tests {
         let service = FlightService {
             server: Arc::clone(&test_storage),
             authz: Option::<Arc<dyn Authorizer>>::None,
+            prepared_statements: Default::default(),
         };
         let ticket = Ticket {
             ticket: br#"{"namespace_name": "my_db", "sql_query": "SELECT 1;"}"#
@@ -1239,6 +1380,7 @@ mod tests {
         let svc = FlightService {
             server: Arc::clone(&test_storage),
             authz: Some(Arc::new(MockAuthorizer {})),
+            prepared_statements: Default::default(),
         };
 
         async fn assert_code(
@@ -1277,7 +1419,20 @@ mod tests {
 
         fn influxql_request(authorization: &'static str) -> tonic::Request<arrow_flight::Ticket> {
             request(
-                RunQuery::InfluxQL("SHOW DATABASES".to_string()),
+                RunQuery::InfluxQL("SHOW DATABASES".to_string(), Default::default()),
+                authorization,
+            )
+        }
+
+        fn influxql_on_request(
+            database: &str,
+            authorization: &'static str,
+        ) -> tonic::Request<arrow_flight::Ticket> {
+            request(
+                RunQuery::InfluxQL(
+                    format!("SHOW MEASUREMENTS ON {database}"),
+                    Default::default(),
+                ),
                 authorization,
             )
         }
@@ -1309,19 +1464,29 @@ mod tests {
 
         assert_code(&svc, tonic::Code::Unauthenticated, influxql_request("")).await;
 
+        assert_code(&svc, tonic::Code::Ok, influxql_request("Bearer GOOD")).await;
+        assert_code(
+            &svc,
+            tonic::Code::PermissionDenied,
+            influxql_request("Bearer BAD"),
+        )
+        .await;
+        assert_code(&svc, tonic::Code::Internal, influxql_request("Bearer UGLY")).await;
+
+        // Databases other than that of the ticket are resolved by the planner.
+        test_storage.db_or_create("platanos").await;
         assert_code(
             &svc,
-            tonic::Code::InvalidArgument, // SHOW DATABASE has not been implemented yet.
-            influxql_request("Bearer GOOD"),
+            tonic::Code::Ok,
+            influxql_on_request("platanos", "Bearer GOOD"),
         )
         .await;
         assert_code(
             &svc,
-            tonic::Code::PermissionDenied,
-            influxql_request("Bearer BAD"),
+            tonic::Code::InvalidArgument,
+            influxql_on_request("apples", "Bearer GOOD"),
         )
         .await;
-        assert_code(&svc, tonic::Code::Internal, influxql_request("Bearer UGLY")).await;
 
         assert_code(&svc, tonic::Code::Unauthenticated, flightsql_request("")).await;
         assert_code(&svc, tonic::Code::Ok, flightsql_request("Bearer GOOD")).await;
@@ -1347,6 +1512,7 @@ mod tests {
         let svc = FlightService {
             server: Arc::clone(&test_storage),
             authz: Some(Arc::new(MockAuthorizer {})),
+            prepared_statements: Default::default(),
         };
 
         async fn assert_code(
@@ -5,10 +5,12 @@ use bytes::Bytes;
 use flightsql::FlightSQLCommand;
 use generated_types::google::protobuf::Any;
 use generated_types::influxdata::iox::querier::v1 as proto;
+use generated_types::influxdata::iox::querier::v1::query_param::Value as ParamProto;
 use generated_types::influxdata::iox::querier::v1::read_info::QueryType;
 use observability_deps::tracing::trace;
 use prost::Message;
 use serde::Deserialize;
+use service_common::planner::{ParamValue, StatementParams};
 use snafu::{ResultExt, Snafu};
 use std::fmt::{Debug, Display, Formatter};
 
@@ -90,6 +92,17 @@ enum AnyError {
 ///   "query_type": "influxql"
 /// }
 /// ```
+///
+/// This runs an InfluxQL query with the bind parameter `$host` set to `"server01"`
+///
+/// ```json
+/// {
+///   "database": "my_db",
+///   "sql_query": "SELECT usage FROM cpu WHERE host = $host;"
+///   "query_type": "influxql",
+///   "params": {"host": "server01"}
+/// }
+/// ```
 #[derive(Debug, PartialEq, Clone)]
 pub struct IoxGetRequest {
     database: String,
@@ -101,8 +114,8 @@ pub struct IoxGetRequest {
 pub enum RunQuery {
     /// Unparameterized SQL query
     Sql(String),
-    /// InfluxQL
-    InfluxQL(String),
+    /// InfluxQL, with the values of its bind parameters
+    InfluxQL(String, StatementParams),
     /// Execute a FlightSQL command. The payload is an encoded
     /// FlightSQL Command*. message that was received at the
     /// get_flight_info endpoint
@@ -113,7 +126,7 @@ impl RunQuery {
     pub fn variant(&self) -> &'static str {
         match self {
             Self::Sql(_) => "sql",
-            Self::InfluxQL(_) => "influxql",
+            Self::InfluxQL(..) => "influxql",
             Self::FlightSQL(_) => "flightsql",
         }
     }
@@ -123,7 +136,7 @@ impl Display for RunQuery {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Sql(s) => Display::fmt(s, f),
-            Self::InfluxQL(s) => Display::fmt(s, f),
+            Self::InfluxQL(s, _) => Display::fmt(s, f),
             Self::FlightSQL(s) => Display::fmt(s, f),
         }
     }
@@ -183,14 +196,19 @@ impl IoxGetRequest {
                 query_type: QueryType::Sql.into(),
                 flightsql_command: vec![],
                 is_debug,
+                params: Default::default(),
             },
-            RunQuery::InfluxQL(influxql) => proto::ReadInfo {
+            RunQuery::InfluxQL(influxql, params) => proto::ReadInfo {
                 database,
                 // field name is misleading
                 sql_query: influxql,
                 query_type: QueryType::InfluxQl.into(),
                 flightsql_command: vec![],
                 is_debug,
+                params: params
+                    .into_iter()
+                    .map(|(name, value)| (name, encode_param(value)))
+                    .collect(),
             },
             RunQuery::FlightSQL(flightsql_command) => proto::ReadInfo {
                 database,
@@ -201,6 +219,7 @@ impl IoxGetRequest {
                     .context(FlightSQLSnafu)?
                     .into(),
                 is_debug,
+                params: Default::default(),
             },
         };
 
@@ -229,6 +248,8 @@ impl IoxGetRequest {
             query_type: Option<String>,
             #[serde(default = "Default::default")]
             is_debug: bool,
+            #[serde(default = "Default::default")]
+            params: StatementParams,
         }
 
         let ReadInfoJson {
@@ -236,12 +257,17 @@ impl IoxGetRequest {
             sql_query,
             query_type,
             is_debug,
+            params,
         } = serde_json::from_str(&json_str).map_err(|e| format!("JSON parse error: {e}"))?;
 
+        if !params.is_empty() && query_type.as_deref() != Some("influxql") {
+            return Err("bind parameters are only supported for InfluxQL queries".to_string());
+        }
+
         let query = if let Some(query_type) = query_type {
             match query_type.as_str() {
                 "sql" => RunQuery::Sql(sql_query),
-                "influxql" => RunQuery::InfluxQL(sql_query),
+                "influxql" => RunQuery::InfluxQL(sql_query, params),
                 _ => {
                     return Err(format!(
                         "unknown query type. Expected 'sql' or 'influxql', got {query_type}'"
@@ -284,8 +310,16 @@ impl IoxGetRequest {
             query_type: _,
             flightsql_command,
             is_debug,
+            params,
         } = read_info;
 
+        if !params.is_empty() && query_type != QueryType::InfluxQl {
+            return InvalidContentSnafu {
+                msg: "bind parameters are only supported for QueryType::InfluxQl",
+            }
+            .fail();
+        }
+
         Ok(Self {
             database,
             query: match query_type {
@@ -305,7 +339,11 @@ impl IoxGetRequest {
                         }
                         .fail();
                     }
-                    RunQuery::InfluxQL(sql_query)
+                    let params = params
+                        .into_iter()
+                        .map(|(name, value)| Ok((name, decode_param(value)?)))
+                        .collect::<Result<_>>()?;
+                    RunQuery::InfluxQL(sql_query, params)
                 }
                 QueryType::FlightSqlMessage => {
                     if !sql_query.is_empty() {
@@ -336,6 +374,33 @@ impl IoxGetRequest {
     }
 }
 
+fn encode_param(value: ParamValue) -> proto::QueryParam {
+    let value = match value {
+        ParamValue::Boolean(v) => ParamProto::Boolean(v),
+        ParamValue::Integer(v) => ParamProto::Integer(v),
+        ParamValue::Unsigned(v) => ParamProto::UnsignedInteger(v),
+        ParamValue::Float(v) => ParamProto::Double(v),
+        ParamValue::String(v) => ParamProto::String(v),
+    };
+    proto::QueryParam { value: Some(value) }
+}
+
+fn decode_param(param: proto::QueryParam) -> Result<ParamValue> {
+    Ok(match param.value {
+        Some(ParamProto::Boolean(v)) => ParamValue::Boolean(v),
+        Some(ParamProto::Integer(v)) => ParamValue::Integer(v),
+        Some(ParamProto::UnsignedInteger(v)) => ParamValue::Unsigned(v),
+        Some(ParamProto::Double(v)) => ParamValue::Float(v),
+        Some(ParamProto::String(v)) => ParamValue::String(v),
+        None => {
+            return InvalidContentSnafu {
+                msg: "bind parameter has no value",
+            }
+            .fail()
+        }
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use arrow_flight::sql::CommandStatementQuery;
@@ -383,7 +448,7 @@ mod tests {
                     json,
                     expected: IoxGetRequest {
                         database: String::from(expected_database),
-                        query: RunQuery::InfluxQL(String::from(query)),
+                        query: RunQuery::InfluxQL(String::from(query), Default::default()),
                         is_debug: false,
                     },
                 }
@@ -564,6 +629,7 @@ mod tests {
             query_type: QueryType::Unspecified.into(),
             flightsql_command: vec![],
             is_debug: false,
+            params: Default::default(),
         });
 
         // Reverts to default (unspecified) for invalid query_type enumeration, and thus SQL
@@ -580,6 +646,7 @@ mod tests {
             query_type: QueryType::Sql.into(),
             flightsql_command: vec![],
             is_debug: false,
+            params: Default::default(),
         });
 
         let ri = IoxGetRequest::try_decode(ticket).unwrap();
@@ -595,11 +662,12 @@ mod tests {
             query_type: QueryType::InfluxQl.into(),
             flightsql_command: vec![],
             is_debug: false,
+            params: Default::default(),
         });
 
         let ri = IoxGetRequest::try_decode(ticket).unwrap();
         assert_eq!(ri.database, "<foo>_<bar>");
-        assert_matches!(ri.query, RunQuery::InfluxQL(query) => assert_eq!(query, "SELECT 1"));
+        assert_matches!(ri.query, RunQuery::InfluxQL(query, _) => assert_eq!(query, "SELECT 1"));
     }
 
     #[test]
@@ -610,6 +678,7 @@ mod tests {
             query_type: 42, // not a known query type
             flightsql_command: vec![],
             is_debug: false,
+            params: Default::default(),
         });
 
         // Reverts to default (unspecified) for invalid query_type enumeration, and thus SQL
@@ -627,6 +696,7 @@ mod tests {
             // can't have both sql_query and flightsql
             flightsql_command: vec![1, 2, 3],
             is_debug: false,
+            params: Default::default(),
         });
 
         let e = IoxGetRequest::try_decode(ticket).unwrap_err();
@@ -642,6 +712,7 @@ mod tests {
             // can't have both sql_query and flightsql
             flightsql_command: vec![1, 2, 3],
             is_debug: false,
+            params: Default::default(),
         });
 
         let e = IoxGetRequest::try_decode(ticket).unwrap_err();
@@ -657,6 +728,7 @@ mod tests {
             // can't have both sql_query and flightsql
             flightsql_command: vec![1, 2, 3],
             is_debug: false,
+            params: Default::default(),
         });
 
         let e = IoxGetRequest::try_decode(ticket).unwrap_err();
@@ -682,6 +754,7 @@ mod tests {
             query_type: QueryType::Unspecified.into(),
             flightsql_command: vec![],
             is_debug: false,
+            params: Default::default(),
         });
 
         // Reverts to default (unspecified) for invalid query_type enumeration, and thus SQL
@@ -698,6 +771,7 @@ mod tests {
             query_type: QueryType::Sql.into(),
             flightsql_command: vec![],
             is_debug: false,
+            params: Default::default(),
         });
 
         let ri = IoxGetRequest::try_decode(ticket).unwrap();
@@ -713,11 +787,12 @@ mod tests {
             query_type: QueryType::InfluxQl.into(),
             flightsql_command: vec![],
             is_debug: false,
+            params: Default::default(),
         });
 
         let ri = IoxGetRequest::try_decode(ticket).unwrap();
         assert_eq!(ri.database, "<foo>_<bar>");
-        assert_matches!(ri.query, RunQuery::InfluxQL(query) => assert_eq!(query, "SELECT 1"));
+        assert_matches!(ri.query, RunQuery::InfluxQL(query, _) => assert_eq!(query, "SELECT 1"));
     }
 
     #[test]
@@ -728,6 +803,7 @@ mod tests {
             query_type: 42, // not a known query type
             flightsql_command: vec![],
             is_debug: false,
+            params: Default::default(),
         });
 
         // Reverts to default (unspecified) for invalid query_type enumeration, and thus SQL
@@ -745,6 +821,7 @@ mod tests {
             // can't have both sql_query and flightsql
             flightsql_command: vec![1, 2, 3],
             is_debug: false,
+            params: Default::default(),
         });
 
         let e = IoxGetRequest::try_decode(ticket).unwrap_err();
@@ -760,6 +837,7 @@ mod tests {
             // can't have both sql_query and flightsql
             flightsql_command: vec![1, 2, 3],
             is_debug: false,
+            params: Default::default(),
         });
 
         let e = IoxGetRequest::try_decode(ticket).unwrap_err();
@@ -775,6 +853,7 @@ mod tests {
             // can't have both sql_query and flightsql
             flightsql_command: vec![1, 2, 3],
             is_debug: false,
+            params: Default::default(),
         });
 
         let e = IoxGetRequest::try_decode(ticket).unwrap_err();
@@ -825,7 +904,7 @@ mod tests {
     fn round_trip_influxql() {
         let request = IoxGetRequest {
             database: "foo_blarg".into(),
-            query: RunQuery::InfluxQL("select * from bar".into()),
+            query: RunQuery::InfluxQL("select * from bar".into(), Default::default()),
             is_debug: false,
         };
 
@@ -836,6 +915,46 @@ mod tests {
         assert_eq!(request, roundtripped)
     }
 
+    #[test]
+    fn round_trip_influxql_params() {
+        let params = StatementParams::from([
+            ("b".to_string(), ParamValue::Boolean(true)),
+            ("i".to_string(), ParamValue::Integer(-1)),
+            ("u".to_string(), ParamValue::Unsigned(u64::MAX)),
+            ("f".to_string(), ParamValue::Float(1.5)),
+            ("s".to_string(), ParamValue::String("cpu0".into())),
+        ]);
+        let request = IoxGetRequest {
+            database: "foo_blarg".into(),
+            query: RunQuery::InfluxQL("select * from bar where a = $s".into(), params),
+            is_debug: false,
+        };
+
+        let ticket = request.clone().try_encode().expect("encoding failed");
+
+        let roundtripped = IoxGetRequest::try_decode(ticket).expect("decode failed");
+
+        assert_eq!(request, roundtripped)
+    }
+
+    #[test]
+    fn json_ticket_decoding_params() {
+        let ticket = make_json_ticket(
+            r#"{"database": "my_db", "sql_query": "SELECT a FROM cpu WHERE b = $b", "query_type": "influxql", "params": {"b": 1}}"#,
+        );
+        let ri = IoxGetRequest::try_decode(ticket).unwrap();
+        assert_matches!(ri.query, RunQuery::InfluxQL(_, params) => {
+            assert_eq!(params, StatementParams::from([("b".to_string(), ParamValue::Integer(1))]));
+        });
+
+        // bind parameters are only supported for InfluxQL
+        let ticket = make_json_ticket(
+            r#"{"database": "my_db", "sql_query": "SELECT 1", "params": {"b": 1}}"#,
+        );
+        let e = IoxGetRequest::try_decode(ticket).unwrap_err();
+        assert_matches!(e, Error::Invalid);
+    }
+
     #[test]
     fn round_trip_flightsql() {
         let cmd = FlightSQLCommand::CommandStatementQuery(CommandStatementQuery {
@@ -1,6 +1,6 @@
 use futures::Stream;
 use pin_project::pin_project;
-use tracker::InstrumentedAsyncOwnedSemaphorePermit;
+use service_common::QueryPermit;
 
 /// Helper to keep a semaphore permit attached to a stream.
 #[derive(Debug)]
@@ -9,11 +9,11 @@ pub struct StreamWithPermit<S> {
     #[pin]
     stream: S,
     #[allow(dead_code)]
-    permit: InstrumentedAsyncOwnedSemaphorePermit,
+    permit: QueryPermit,
 }
 
 impl<S> StreamWithPermit<S> {
-    pub fn new(stream: S, permit: InstrumentedAsyncOwnedSemaphorePermit) -> Self {
+    pub fn new(stream: S, permit: QueryPermit) -> Self {
         Self { stream, permit }
     }
 }
@@ -13,6 +13,10 @@ use crate::{
     response_chunking::ChunkReadResponses,
     StorageService,
 };
+use arrow::{
+    array::{as_primitive_array, Array},
+    datatypes::Int64Type,
+};
 use data_types::NamespaceName;
 use datafusion::error::DataFusionError;
 use futures::{stream::BoxStream, Stream, StreamExt, TryStreamExt};
@@ -39,18 +43,18 @@ use iox_query::{
 };
 use observability_deps::tracing::{error, info, trace};
 use prost::{bytes::BytesMut, Message};
-use service_common::{datafusion_error_to_tonic_code, planner::Planner, QueryNamespaceProvider};
+use service_common::{
+    datafusion_error_to_tonic_code, planner::Planner, QueryNamespaceProvider, QueryPermit,
+};
 use snafu::{OptionExt, ResultExt, Snafu};
 use std::{
     collections::{BTreeSet, HashMap},
     fmt::{Display, Formatter, Result as FmtResult},
     sync::Arc,
 };
-use tokio_stream::wrappers::ReceiverStream;
 use tonic::{metadata::MetadataMap, Response, Status};
 use trace::{ctx::SpanContext, span::SpanExt};
 use trace_http::ctx::{RequestLogContext, RequestLogContextExt};
-use tracker::InstrumentedAsyncOwnedSemaphorePermit;
 
 /// The size to which we limit our [`ReadResponse`] payloads.
 ///
@@ -374,11 +378,14 @@ where
         let span_ctx: Option<SpanContext> = req.extensions().get().cloned();
 
         let req = req.into_inner();
+        let db_name = get_namespace_name(&req)?;
         let permit = self
             .db_store
-            .acquire_semaphore(span_ctx.child_span("query rate limit semaphore"))
+            .acquire_semaphore(
+                db_name.as_str(),
+                span_ctx.child_span("query rate limit semaphore"),
+            )
             .await;
-        let db_name = get_namespace_name(&req)?;
         info!(
             %db_name,
             ?req.range,
@@ -417,13 +424,15 @@ where
         let external_span_ctx: Option<RequestLogContext> = req.extensions().get().cloned();
         let span_ctx: Option<SpanContext> = req.extensions().get().cloned();
         let req = req.into_inner();
+        let db_name = get_namespace_name(&req)?;
         let permit = self
             .db_store
-            .acquire_semaphore(span_ctx.child_span("query rate limit semaphore"))
+            .acquire_semaphore(
+                db_name.as_str(),
+                span_ctx.child_span("query rate limit semaphore"),
+            )
             .await;
 
-        let db_name = get_namespace_name(&req)?;
-
         info!(
             %db_name,
             ?req.range,
@@ -493,12 +502,15 @@ where
         let external_span_ctx: Option<RequestLogContext> = req.extensions().get().cloned();
         let span_ctx: Option<SpanContext> = req.extensions().get().cloned();
         let req = req.into_inner();
+        let db_name = get_namespace_name(&req)?;
         let permit = self
             .db_store
-            .acquire_semaphore(span_ctx.child_span("query rate limit semaphore"))
+            .acquire_semaphore(
+                db_name.as_str(),
+                span_ctx.child_span("query rate limit semaphore"),
+            )
             .await;
 
-        let db_name = get_namespace_name(&req)?;
         info!(
             %db_name,
             ?req.range,
@@ -575,12 +587,15 @@ where
         let span_ctx: Option<SpanContext> = req.extensions().get().cloned();
 
         let req = req.into_inner();
+        let db_name = get_namespace_name(&req)?;
         let permit = self
             .db_store
-            .acquire_semaphore(span_ctx.child_span("query rate limit semaphore"))
+            .acquire_semaphore(
+                db_name.as_str(),
+                span_ctx.child_span("query rate limit semaphore"),
+            )
             .await;
 
-        let db_name = get_namespace_name(&req)?;
         info!(
             %db_name,
             ?req.range,
@@ -640,12 +655,15 @@ where
         let span_ctx: Option<SpanContext> = req.extensions().get().cloned();
 
         let req = req.into_inner();
+        let db_name = get_namespace_name(&req)?;
         let permit = self
             .db_store
-            .acquire_semaphore(span_ctx.child_span("query rate limit semaphore"))
+            .acquire_semaphore(
+                db_name.as_str(),
+                span_ctx.child_span("query rate limit semaphore"),
+            )
             .await;
 
-        let db_name = get_namespace_name(&req)?;
         let tag_key = DecodedTagKey::try_from(req.tag_key.clone())
             .context(ConvertingTagKeyInTagValuesSnafu)?;
         info!(
@@ -742,12 +760,15 @@ where
         let span_ctx: Option<SpanContext> = req.extensions().get().cloned();
 
         let req = req.into_inner();
+        let db_name = get_namespace_name(&req)?;
         let permit = self
             .db_store
-            .acquire_semaphore(span_ctx.child_span("query rate limit semaphore"))
+            .acquire_semaphore(
+                db_name.as_str(),
+                span_ctx.child_span("query rate limit semaphore"),
+            )
             .await;
 
-        let db_name = get_namespace_name(&req)?;
         info!(
             %db_name,
             ?req.measurement_patterns,
@@ -785,13 +806,65 @@ where
         )
     }
 
-    type ReadSeriesCardinalityStream = ReceiverStream<Result<Int64ValuesResponse, Status>>;
+    type ReadSeriesCardinalityStream = StreamWithPermit<
+        QueryCompletedTokenStream<
+            BoxStream<'static, Result<Int64ValuesResponse, Status>>,
+            Int64ValuesResponse,
+            Status,
+        >,
+    >;
 
     async fn read_series_cardinality(
         &self,
-        _req: tonic::Request<ReadSeriesCardinalityRequest>,
+        req: tonic::Request<ReadSeriesCardinalityRequest>,
     ) -> Result<Response<Self::ReadSeriesCardinalityStream>, Status> {
-        unimplemented!("read_series_cardinality not yet implemented. https://github.com/influxdata/influxdb_iox/issues/447");
+        let external_span_ctx: Option<RequestLogContext> = req.extensions().get().cloned();
+        let span_ctx: Option<SpanContext> = req.extensions().get().cloned();
+
+        let req = req.into_inner();
+        let db_name = get_namespace_name(&req)?;
+        let permit = self
+            .db_store
+            .acquire_semaphore(
+                db_name.as_str(),
+                span_ctx.child_span("query rate limit semaphore"),
+            )
+            .await;
+
+        info!(
+            %db_name,
+            ?req.range,
+            predicate=%req.predicate.loggable(),
+            trace=%external_span_ctx.format_jaeger(),
+            "read_series_cardinality",
+        );
+
+        let db = self
+            .db_store
+            .db(&db_name, span_ctx.child_span("get namespace"), false)
+            .await
+            .context(NamespaceNotFoundSnafu { db_name: &db_name })?;
+
+        let ctx = db.new_query_context(span_ctx);
+        let query_completed_token =
+            db.record_query(&ctx, "read_series_cardinality", defer_json(&req));
+
+        let ReadSeriesCardinalityRequest {
+            read_series_cardinality_source: _read_series_cardinality_source,
+            range,
+            predicate,
+        } = req;
+
+        let response =
+            read_series_cardinality_impl(Arc::clone(&db), db_name, range, predicate, &ctx)
+                .await
+                .map_err(|e| e.into_status());
+
+        make_response(
+            futures::stream::once(async move { response }).boxed(),
+            query_completed_token,
+            permit,
+        )
     }
 
     async fn capabilities(
@@ -852,12 +925,15 @@ where
         let span_ctx: Option<SpanContext> = req.extensions().get().cloned();
 
         let req = req.into_inner();
+        let db_name = get_namespace_name(&req)?;
         let permit = self
             .db_store
-            .acquire_semaphore(span_ctx.child_span("query rate limit semaphore"))
+            .acquire_semaphore(
+                db_name.as_str(),
+                span_ctx.child_span("query rate limit semaphore"),
+            )
             .await;
 
-        let db_name = get_namespace_name(&req)?;
         info!(
             %db_name,
             ?req.range,
@@ -908,12 +984,15 @@ where
         let span_ctx: Option<SpanContext> = req.extensions().get().cloned();
 
         let req = req.into_inner();
+        let db_name = get_namespace_name(&req)?;
         let permit = self
             .db_store
-            .acquire_semaphore(span_ctx.child_span("query rate limit semaphore"))
+            .acquire_semaphore(
+                db_name.as_str(),
+                span_ctx.child_span("query rate limit semaphore"),
+            )
             .await;
 
-        let db_name = get_namespace_name(&req)?;
         info!(
             %db_name,
             ?req.range,
@@ -975,12 +1054,15 @@ where
         let span_ctx: Option<SpanContext> = req.extensions().get().cloned();
 
         let req = req.into_inner();
+        let db_name = get_namespace_name(&req)?;
         let permit = self
             .db_store
-            .acquire_semaphore(span_ctx.child_span("query rate limit semaphore"))
+            .acquire_semaphore(
+                db_name.as_str(),
+                span_ctx.child_span("query rate limit semaphore"),
+            )
             .await;
 
-        let db_name = get_namespace_name(&req)?;
         info!(
             %db_name,
             ?req.range,
@@ -1046,12 +1128,15 @@ where
         let span_ctx: Option<SpanContext> = req.extensions().get().cloned();
 
         let req = req.into_inner();
+        let db_name = get_namespace_name(&req)?;
         let permit = self
             .db_store
-            .acquire_semaphore(span_ctx.child_span("query rate limit semaphore"))
+            .acquire_semaphore(
+                db_name.as_str(),
+                span_ctx.child_span("query rate limit semaphore"),
+            )
             .await;
 
-        let db_name = get_namespace_name(&req)?;
         info!(
             %db_name,
             ?req.range,
@@ -1362,35 +1447,35 @@ where
     // if big queries are causing a significant latency in TTFB.
 
     // Build the plans
-    let series_plan = Planner::new(ctx)
-        .read_filter(db, predicate)
+    let Some(plan) = Planner::new(ctx)
+        .series_cardinality(db, predicate)
         .await
-        .context(PlanningFilteringSeriesSnafu { db_name })?;
+        .context(PlanningFilteringSeriesSnafu { db_name })?
+    else {
+        return Ok(Int64ValuesResponse { values: vec![0] });
+    };
 
-    // Execute the plans.
-    let db_name = db_name.to_owned();
-    let series_or_groups = ctx
-        .to_series_and_groups(
-            series_plan,
-            Arc::clone(&ctx.inner().runtime_env().memory_pool),
-            MAX_POINTS_PER_FRAME,
-        )
+    let physical_plan = ctx
+        .create_physical_plan(&plan)
         .await
-        .context(FilteringSeriesSnafu {
-            db_name: db_name.clone(),
-        })
-        .log_if_error("Running series set plan")?
-        .map_err(move |e| Error::FilteringSeries {
-            db_name: db_name.clone(),
-            source: e,
-        });
+        .context(PlanningFilteringSeriesSnafu { db_name })?;
+    let batches = ctx
+        .collect(physical_plan)
+        .await
+        .context(FilteringSeriesSnafu { db_name })
+        .log_if_error("Counting series")?;
 
-    let emit_tag_keys_binary_format = req.tag_key_meta_names == TagKeyMetaNames::Binary as i32;
+    // The plan sums the series of every table into a single row.
+    let count = batches
+        .iter()
+        .map(|batch| as_primitive_array::<Int64Type>(batch.column(0)))
+        .filter(|counts| !counts.is_empty() && counts.is_valid(0))
+        .map(|counts| counts.value(0))
+        .sum::<i64>();
 
-    Ok(series_or_groups_to_frames(
-        series_or_groups,
-        emit_tag_keys_binary_format,
-    ))
+    Ok(Int64ValuesResponse {
+        values: vec![count],
+    })
 }
 
 /// Launch async tasks that send the result of executing read_group to `tx`
@@ -1705,7 +1790,7 @@ impl<T, E: std::error::Error> ErrorLogger for Result<T, E> {
 pub fn make_response<S, T, E>(
     stream: S,
     token: QueryCompletedToken,
-    permit: InstrumentedAsyncOwnedSemaphorePermit,
+    permit: QueryPermit,
 ) -> Result<Response<StreamWithPermit<QueryCompletedTokenStream<S, T, E>>>, Status>
 where
     S: Stream<Item = Result<T, E>> + Unpin + Send,
@@ -2652,6 +2737,75 @@ mod tests {
         grpc_request_metric_has_count(&fixture, "ReadFilter", "ok", 1);
     }
 
+    #[tokio::test]
+    async fn test_read_series_cardinality() {
+        test_helpers::maybe_start_logging();
+        // Start a test gRPC server on a randomally allocated port
+        let mut fixture = Fixture::new().await.expect("Connecting to test server");
+
+        let db_info = org_and_bucket();
+
+        let chunk = TestChunk::new("TheMeasurement")
+            .with_time_column()
+            .with_tag_column("tag1")
+            .with_i64_field_column("field")
+            .with_four_rows_of_data();
+
+        fixture
+            .test_storage
+            .db_or_create(db_info.db_name())
+            .await
+            .add_chunk("my_partition_key", Arc::new(chunk));
+
+        let source = Some(StorageClient::read_source(&db_info, 1));
+
+        let request = ReadSeriesCardinalityRequest {
+            read_series_cardinality_source: source.clone(),
+            range: Some(make_timestamp_range(0, i64::MAX)),
+            predicate: None,
+        };
+
+        let values = fixture
+            .storage_client
+            .read_series_cardinality(request)
+            .await
+            .unwrap();
+
+        // one series for each of the distinct "tag1" values
+        assert_eq!(values, vec![3]);
+
+        let request = ReadSeriesCardinalityRequest {
+            read_series_cardinality_source: source.clone(),
+            range: Some(make_timestamp_range(0, i64::MAX)),
+            predicate: Some(make_tag_predicate("tag1", "VT", node::Comparison::Equal)),
+        };
+
+        let values = fixture
+            .storage_client
+            .read_series_cardinality(request)
+            .await
+            .unwrap();
+
+        // the two rows with "tag1" of "VT" are a single series
+        assert_eq!(values, vec![1]);
+
+        // no data in the range
+        let request = ReadSeriesCardinalityRequest {
+            read_series_cardinality_source: source.clone(),
+            range: Some(make_timestamp_range(i64::MAX - 1, i64::MAX)),
+            predicate: None,
+        };
+
+        let values = fixture
+            .storage_client
+            .read_series_cardinality(request)
+            .await
+            .unwrap();
+        assert_eq!(values, vec![0]);
+
+        grpc_request_metric_has_count(&fixture, "ReadSeriesCardinality", "ok", 3);
+    }
+
     #[tokio::test]
     async fn test_read_filter_empty_string() {
         test_helpers::maybe_start_logging();
@@ -16,7 +16,7 @@
 // Workaround for "unused crate" lint false positives.
 use workspace_hack as _;
 
-use std::sync::Arc;
+use std::{cmp::Ordering, collections::HashMap, sync::Arc};
 
 use data_types::{
     partition_template::NamespacePartitionTemplateOverride, Namespace as CatalogNamespace,
@@ -84,6 +84,21 @@ impl namespace_service_server::NamespaceService for NamespaceService {
 
         let retention_period_ns = map_retention_period(retention_period_ns)?;
 
+        if let Some(limits) = &service_protection_limits {
+            map_quota(
+                limits.max_write_bytes_per_second.unwrap_or_default(),
+                "max write bytes per second",
+            )?;
+            map_quota(
+                limits.max_write_lines_per_second.unwrap_or_default(),
+                "max write lines per second",
+            )?;
+            map_quota(
+                limits.max_concurrent_queries.unwrap_or_default(),
+                "max concurrent queries",
+            )?;
+        }
+
         debug!(%namespace_name, ?retention_period_ns, "Creating namespace");
 
         let namespace = repos
@@ -237,6 +252,54 @@ impl namespace_service_server::NamespaceService for NamespaceService {
                         status_from_catalog_namespace_error(e)
                     })
             }
+            Some(LimitUpdate::MaxWriteBytesPerSecond(n)) => {
+                let n = map_quota(n, "max write bytes per second")?;
+                repos
+                    .namespaces()
+                    .update_write_bytes_limit(&namespace_name, n)
+                    .await
+                    .map_err(|e| {
+                        warn!(
+                            error = %e,
+                            %namespace_name,
+                            write_bytes_limit = ?n,
+                            "failed to update write bytes limit for namespace",
+                        );
+                        status_from_catalog_namespace_error(e)
+                    })
+            }
+            Some(LimitUpdate::MaxWriteLinesPerSecond(n)) => {
+                let n = map_quota(n, "max write lines per second")?;
+                repos
+                    .namespaces()
+                    .update_write_lines_limit(&namespace_name, n)
+                    .await
+                    .map_err(|e| {
+                        warn!(
+                            error = %e,
+                            %namespace_name,
+                            write_lines_limit = ?n,
+                            "failed to update write lines limit for namespace",
+                        );
+                        status_from_catalog_namespace_error(e)
+                    })
+            }
+            Some(LimitUpdate::MaxConcurrentQueries(n)) => {
+                let n = map_quota(n, "max concurrent queries")?;
+                repos
+                    .namespaces()
+                    .update_query_concurrency_limit(&namespace_name, n)
+                    .await
+                    .map_err(|e| {
+                        warn!(
+                            error = %e,
+                            %namespace_name,
+                            query_concurrency_limit = ?n,
+                            "failed to update query concurrency limit for namespace",
+                        );
+                        status_from_catalog_namespace_error(e)
+                    })
+            }
             None => Err(Status::invalid_argument(
                 "unsupported service protection limit change requested",
             )),
@@ -247,6 +310,9 @@ impl namespace_service_server::NamespaceService for NamespaceService {
             namespace_id = %namespace.id,
             max_tables = %namespace.max_tables,
             max_columns_per_table = %namespace.max_columns_per_table,
+            max_write_bytes_per_second = ?namespace.max_write_bytes_per_second,
+            max_write_lines_per_second = ?namespace.max_write_lines_per_second,
+            max_concurrent_queries = ?namespace.max_concurrent_queries,
             "updated namespace service protection limits",
         );
 
@@ -256,6 +322,52 @@ impl namespace_service_server::NamespaceService for NamespaceService {
             },
         ))
     }
+
+    async fn get_namespace_storage_usage(
+        &self,
+        request: Request<GetNamespaceStorageUsageRequest>,
+    ) -> Result<Response<GetNamespaceStorageUsageResponse>, Status> {
+        let mut repos = self.catalog.repositories().await;
+
+        let namespace_name = request.into_inner().name;
+
+        let namespace = repos
+            .namespaces()
+            .get_by_name(&namespace_name, SoftDeletedRows::ExcludeDeleted)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?
+            .ok_or_else(|| {
+                Status::not_found(format!(
+                    "Could not find a namespace with name {namespace_name}"
+                ))
+            })?;
+
+        let table_names = repos
+            .tables()
+            .list_by_namespace_id(namespace.id)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?
+            .into_iter()
+            .map(|t| (t.id, t.name))
+            .collect::<HashMap<_, _>>();
+
+        let usage = repos
+            .parquet_files()
+            .storage_usage_by_namespace(namespace.id)
+            .await
+            .map_err(|e| {
+                warn!(error=%e, %namespace_name, "failed to get namespace storage usage");
+                Status::internal(e.to_string())
+            })?
+            .into_iter()
+            .map(|u| {
+                let table_name = table_names.get(&u.table_id).cloned().unwrap_or_default();
+                u.to_proto(table_name)
+            })
+            .collect();
+
+        Ok(Response::new(GetNamespaceStorageUsageResponse { usage }))
+    }
 }
 
 fn namespace_to_proto(namespace: CatalogNamespace) -> Namespace {
@@ -265,6 +377,9 @@ fn namespace_to_proto(namespace: CatalogNamespace) -> Namespace {
         retention_period_ns: namespace.retention_period_ns,
         max_tables: namespace.max_tables,
         max_columns_per_table: namespace.max_columns_per_table,
+        max_write_bytes_per_second: namespace.max_write_bytes_per_second,
+        max_write_lines_per_second: namespace.max_write_lines_per_second,
+        max_concurrent_queries: namespace.max_concurrent_queries,
     }
 }
 
@@ -276,10 +391,31 @@ fn namespace_to_create_response_proto(namespace: CatalogNamespace) -> CreateName
             retention_period_ns: namespace.retention_period_ns,
             max_tables: namespace.max_tables,
             max_columns_per_table: namespace.max_columns_per_table,
+            max_write_bytes_per_second: namespace.max_write_bytes_per_second,
+            max_write_lines_per_second: namespace.max_write_lines_per_second,
+            max_concurrent_queries: namespace.max_concurrent_queries,
         }),
     }
 }
 
+/// Map a user-submitted quota value to the correct internal encoding.
+///
+/// 0 is always mapped to [`None`], removing the quota.
+///
+/// Negative quotas are rejected with an error.
+fn map_quota<T>(v: T, quota: &str) -> Result<Option<T>, Status>
+where
+    T: Ord + Default,
+{
+    match v.cmp(&T::default()) {
+        Ordering::Greater => Ok(Some(v)),
+        Ordering::Equal => Ok(None),
+        Ordering::Less => Err(Status::invalid_argument(format!(
+            "{quota} limit for namespace must not be negative"
+        ))),
+    }
+}
+
 /// Map a user-submitted retention period value to the correct internal
 /// encoding.
 ///
@@ -312,10 +448,13 @@ mod tests {
 
     use assert_matches::assert_matches;
     use generated_types::influxdata::iox::{
-        namespace::v1::namespace_service_server::NamespaceService as _,
+        catalog::v1::StorageUsage, namespace::v1::namespace_service_server::NamespaceService as _,
         partition_template::v1::PartitionTemplate,
     };
-    use iox_catalog::mem::MemCatalog;
+    use iox_catalog::{
+        mem::MemCatalog,
+        test_helpers::{arbitrary_namespace, arbitrary_parquet_file_params, arbitrary_table},
+    };
     use tonic::Code;
 
     use super::*;
@@ -630,6 +769,127 @@ mod tests {
                 "invalid namespace update request for max columns per table limit should fail",
             );
         assert_eq!(status.code(), Code::InvalidArgument);
+
+        // ...and any attempt to set a negative quota.
+        let status = handler
+            .update_namespace_service_protection_limit(Request::new(
+                UpdateNamespaceServiceProtectionLimitRequest {
+                    name: NS_NAME.to_string(),
+                    limit_update: Some(LimitUpdate::MaxConcurrentQueries(-1)),
+                },
+            ))
+            .await
+            .expect_err("invalid namespace update request for query concurrency should fail");
+        assert_eq!(status.code(), Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn test_update_quotas() {
+        let catalog: Arc<dyn Catalog> =
+            Arc::new(MemCatalog::new(Arc::new(metric::Registry::default())));
+
+        let handler = NamespaceService::new(catalog);
+        let req = CreateNamespaceRequest {
+            name: NS_NAME.to_string(),
+            retention_period_ns: Some(RETENTION),
+            partition_template: None,
+            service_protection_limits: Some(ServiceProtectionLimits {
+                max_tables: None,
+                max_columns_per_table: None,
+                max_write_bytes_per_second: Some(4096),
+                max_write_lines_per_second: None,
+                max_concurrent_queries: Some(0),
+            }),
+        };
+        let created_ns = handler
+            .create_namespace(Request::new(req))
+            .await
+            .expect("failed to create namespace")
+            .into_inner()
+            .namespace
+            .expect("no namespace in response");
+        assert_eq!(created_ns.max_write_bytes_per_second, Some(4096));
+        assert_eq!(created_ns.max_write_lines_per_second, None);
+        // 0 means unlimited
+        assert_eq!(created_ns.max_concurrent_queries, None);
+
+        let update = |limit_update| {
+            handler.update_namespace_service_protection_limit(Request::new(
+                UpdateNamespaceServiceProtectionLimitRequest {
+                    name: NS_NAME.to_string(),
+                    limit_update: Some(limit_update),
+                },
+            ))
+        };
+
+        update(LimitUpdate::MaxWriteLinesPerSecond(100))
+            .await
+            .expect("failed to update write lines limit");
+        update(LimitUpdate::MaxConcurrentQueries(2))
+            .await
+            .expect("failed to update query concurrency limit");
+        let ns = update(LimitUpdate::MaxWriteBytesPerSecond(0))
+            .await
+            .expect("failed to update write bytes limit")
+            .into_inner()
+            .namespace
+            .expect("no namespace in response");
+
+        assert_eq!(ns.max_write_bytes_per_second, None);
+        assert_eq!(ns.max_write_lines_per_second, Some(100));
+        assert_eq!(ns.max_concurrent_queries, Some(2));
+    }
+
+    #[tokio::test]
+    async fn test_storage_usage() {
+        let catalog: Arc<dyn Catalog> =
+            Arc::new(MemCatalog::new(Arc::new(metric::Registry::default())));
+
+        let mut repos = catalog.repositories().await;
+        let namespace = arbitrary_namespace(&mut *repos, NS_NAME).await;
+        let table = arbitrary_table(&mut *repos, "platanos", &namespace).await;
+        let partition = repos
+            .partitions()
+            .create_or_get("bananas".into(), table.id)
+            .await
+            .unwrap();
+        let file = repos
+            .parquet_files()
+            .create(arbitrary_parquet_file_params(
+                &namespace, &table, &partition,
+            ))
+            .await
+            .unwrap();
+        drop(repos);
+
+        let handler = NamespaceService::new(catalog);
+        let usage = handler
+            .get_namespace_storage_usage(Request::new(GetNamespaceStorageUsageRequest {
+                name: NS_NAME.to_string(),
+            }))
+            .await
+            .unwrap()
+            .into_inner()
+            .usage;
+        assert_eq!(
+            usage,
+            vec![StorageUsage {
+                table_id: table.id.get(),
+                table_name: table.name,
+                compaction_level: 0,
+                file_count: 1,
+                file_size_bytes: file.file_size_bytes,
+                row_count: file.row_count,
+            }]
+        );
+
+        let status = handler
+            .get_namespace_storage_usage(Request::new(GetNamespaceStorageUsageRequest {
+                name: "does_not_exist".to_string(),
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), Code::NotFound);
     }
 
     #[tokio::test]
@@ -648,6 +908,9 @@ mod tests {
             service_protection_limits: Some(ServiceProtectionLimits {
                 max_tables: Some(max_tables),
                 max_columns_per_table: Some(max_columns_per_table),
+                max_write_bytes_per_second: None,
+                max_write_lines_per_second: None,
+                max_concurrent_queries: None,
             }),
         };
         let created_ns = handler
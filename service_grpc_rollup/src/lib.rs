@@ -0,0 +1,363 @@
+//! Implementation of the rollup gRPC service
+
+#![deny(
+    rustdoc::broken_intra_doc_links,
+    rustdoc::bare_urls,
+    rust_2018_idioms,
+    missing_debug_implementations,
+    unreachable_pub
+)]
+#![warn(
+    missing_docs,
+    clippy::todo,
+    clippy::dbg_macro,
+    clippy::clone_on_ref_ptr,
+    clippy::future_not_send,
+    clippy::todo,
+    clippy::dbg_macro,
+    unused_crate_dependencies
+)]
+#![allow(clippy::missing_docs_in_private_items)]
+
+// Workaround for "unused crate" lint false positives.
+use workspace_hack as _;
+
+use std::sync::Arc;
+
+use data_types::{Namespace, NamespaceName, RollupQueryLanguage, RollupRuleParams};
+use generated_types::influxdata::iox::rollup::v1::*;
+use iox_catalog::interface::{Catalog, RepoCollection, SoftDeletedRows};
+use observability_deps::tracing::{debug, info, warn};
+use tonic::{Request, Response, Status};
+
+/// Implementation of the rollup gRPC service
+#[derive(Debug)]
+pub struct RollupService {
+    /// Catalog.
+    catalog: Arc<dyn Catalog>,
+}
+
+impl RollupService {
+    /// Create a new `RollupService` instance
+    pub fn new(catalog: Arc<dyn Catalog>) -> Self {
+        Self { catalog }
+    }
+}
+
+#[tonic::async_trait]
+impl rollup_service_server::RollupService for RollupService {
+    // create a rollup rule
+    async fn create_rollup_rule(
+        &self,
+        request: Request<CreateRollupRuleRequest>,
+    ) -> Result<Response<CreateRollupRuleResponse>, Status> {
+        let mut repos = self.catalog.repositories().await;
+
+        let CreateRollupRuleRequest {
+            namespace,
+            name,
+            source_table,
+            target_table,
+            language,
+            aggregates,
+            interval_ns,
+            raw_retention_period_ns,
+        } = request.into_inner();
+
+        let language = RollupQueryLanguage::try_from(language)
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+        if name.is_empty() {
+            return Err(Status::invalid_argument("rollup rule name must be set"));
+        }
+        if source_table.is_empty() || target_table.is_empty() {
+            return Err(Status::invalid_argument(
+                "source and target table must be set",
+            ));
+        }
+        if source_table == target_table {
+            return Err(Status::invalid_argument(
+                "source and target table must be different",
+            ));
+        }
+        if aggregates.trim().is_empty() {
+            return Err(Status::invalid_argument("aggregates must be set"));
+        }
+        if interval_ns <= 0 {
+            return Err(Status::invalid_argument("interval must be positive"));
+        }
+        let raw_retention_period_ns = map_retention_period(raw_retention_period_ns)?;
+
+        let namespace = get_namespace(&mut *repos, namespace).await?;
+
+        debug!(
+            %name,
+            namespace = %namespace.name,
+            %source_table,
+            %target_table,
+            "Creating rollup rule"
+        );
+
+        let rule = repos
+            .rollup_rules()
+            .create(RollupRuleParams {
+                namespace_id: namespace.id,
+                name: name.clone(),
+                source_table,
+                target_table,
+                language,
+                aggregates,
+                interval_ns,
+                raw_retention_period_ns,
+            })
+            .await
+            .map_err(|e| {
+                warn!(error=%e, %name, "failed to create rollup rule");
+                match e {
+                    iox_catalog::interface::Error::RollupRuleNameExists { name, .. } => {
+                        Status::already_exists(format!(
+                            "A rollup rule with the name `{name}` already exists \
+                                in the namespace `{}`",
+                            namespace.name
+                        ))
+                    }
+                    other => Status::internal(other.to_string()),
+                }
+            })?;
+
+        info!(%name, rule_id = %rule.id, namespace = %namespace.name, "created rollup rule");
+
+        Ok(Response::new(CreateRollupRuleResponse {
+            rule: Some(rule.into()),
+        }))
+    }
+
+    // list the rollup rules of a namespace
+    async fn get_rollup_rules(
+        &self,
+        request: Request<GetRollupRulesRequest>,
+    ) -> Result<Response<GetRollupRulesResponse>, Status> {
+        let mut repos = self.catalog.repositories().await;
+
+        let GetRollupRulesRequest { namespace } = request.into_inner();
+        let namespace = get_namespace(&mut *repos, namespace).await?;
+
+        let rules = repos
+            .rollup_rules()
+            .list_by_namespace_id(namespace.id)
+            .await
+            .map_err(|e| {
+                warn!(error=%e, namespace = %namespace.name, "failed to list rollup rules");
+                Status::internal(e.to_string())
+            })?;
+
+        Ok(Response::new(GetRollupRulesResponse {
+            rules: rules.into_iter().map(Into::into).collect(),
+        }))
+    }
+
+    // delete a rollup rule
+    async fn delete_rollup_rule(
+        &self,
+        request: Request<DeleteRollupRuleRequest>,
+    ) -> Result<Response<DeleteRollupRuleResponse>, Status> {
+        let mut repos = self.catalog.repositories().await;
+
+        let DeleteRollupRuleRequest { namespace, name } = request.into_inner();
+        let namespace = get_namespace(&mut *repos, namespace).await?;
+
+        let rule = repos
+            .rollup_rules()
+            .get_by_namespace_and_name(namespace.id, &name)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?
+            .ok_or_else(|| {
+                Status::not_found(format!(
+                    "Could not find a rollup rule with name {name} in namespace {}",
+                    namespace.name
+                ))
+            })?;
+
+        repos.rollup_rules().delete(rule.id).await.map_err(|e| {
+            warn!(error=%e, %name, "failed to delete rollup rule");
+            match e {
+                iox_catalog::interface::Error::RollupRuleNotFound { .. } => {
+                    Status::not_found(e.to_string())
+                }
+                other => Status::internal(other.to_string()),
+            }
+        })?;
+
+        info!(%name, rule_id = %rule.id, namespace = %namespace.name, "deleted rollup rule");
+
+        Ok(Response::new(DeleteRollupRuleResponse {}))
+    }
+}
+
+/// Map a user-submitted raw data retention period value to the correct internal
+/// encoding.
+///
+/// 0 is always mapped to [`None`], indicating the retention period of the
+/// source table applies.
+///
+/// Negative retention periods are rejected with an error.
+fn map_retention_period(v: Option<i64>) -> Result<Option<i64>, Status> {
+    match v {
+        Some(0) => Ok(None),
+        Some(v @ 1..) => Ok(Some(v)),
+        Some(_v @ ..=0) => Err(Status::invalid_argument(
+            "invalid negative retention period",
+        )),
+        None => Ok(None),
+    }
+}
+
+async fn get_namespace(
+    repos: &mut dyn RepoCollection,
+    namespace: String,
+) -> Result<Namespace, Status> {
+    let namespace_name =
+        NamespaceName::try_from(namespace).map_err(|e| Status::invalid_argument(e.to_string()))?;
+
+    repos
+        .namespaces()
+        .get_by_name(&namespace_name, SoftDeletedRows::ExcludeDeleted)
+        .await
+        .map_err(|e| Status::internal(e.to_string()))?
+        .ok_or_else(|| {
+            Status::not_found(format!(
+                "Could not find a namespace with name {namespace_name}"
+            ))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use generated_types::influxdata::iox::rollup::v1::rollup_service_server::RollupService as _;
+    use iox_catalog::{mem::MemCatalog, test_helpers::arbitrary_namespace};
+    use tonic::Code;
+
+    use super::*;
+
+    fn create_request(namespace: &str) -> CreateRollupRuleRequest {
+        CreateRollupRuleRequest {
+            namespace: namespace.to_string(),
+            name: "cpu_1m".to_string(),
+            source_table: "cpu".to_string(),
+            target_table: "cpu_1m".to_string(),
+            language: QueryLanguage::Influxql.into(),
+            aggregates: "mean(usage) AS usage".to_string(),
+            interval_ns: 60_000_000_000,
+            raw_retention_period_ns: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_basic_happy_path() {
+        let catalog: Arc<dyn Catalog> =
+            Arc::new(MemCatalog::new(Arc::new(metric::Registry::default())));
+        let handler = RollupService::new(Arc::clone(&catalog));
+
+        let namespace = arbitrary_namespace(&mut *catalog.repositories().await, "grapes").await;
+
+        let created = handler
+            .create_rollup_rule(Request::new(create_request(&namespace.name)))
+            .await
+            .unwrap()
+            .into_inner()
+            .rule
+            .unwrap();
+        assert!(created.id > 0);
+        assert_eq!(created.namespace_id, namespace.id.get());
+        assert_eq!(created.name, "cpu_1m");
+        assert_eq!(created.language, QueryLanguage::Influxql as i32);
+        assert_eq!(created.covered_until_ns, None);
+
+        // creating it again fails
+        let error = handler
+            .create_rollup_rule(Request::new(create_request(&namespace.name)))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code(), Code::AlreadyExists);
+
+        let rules = handler
+            .get_rollup_rules(Request::new(GetRollupRulesRequest {
+                namespace: namespace.name.clone(),
+            }))
+            .await
+            .unwrap()
+            .into_inner()
+            .rules;
+        assert_eq!(rules, vec![created]);
+
+        handler
+            .delete_rollup_rule(Request::new(DeleteRollupRuleRequest {
+                namespace: namespace.name.clone(),
+                name: "cpu_1m".to_string(),
+            }))
+            .await
+            .unwrap();
+        let rules = handler
+            .get_rollup_rules(Request::new(GetRollupRulesRequest {
+                namespace: namespace.name.clone(),
+            }))
+            .await
+            .unwrap()
+            .into_inner()
+            .rules;
+        assert!(rules.is_empty());
+
+        // deleting it again fails
+        let error = handler
+            .delete_rollup_rule(Request::new(DeleteRollupRuleRequest {
+                namespace: namespace.name.clone(),
+                name: "cpu_1m".to_string(),
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code(), Code::NotFound);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_rules() {
+        let catalog: Arc<dyn Catalog> =
+            Arc::new(MemCatalog::new(Arc::new(metric::Registry::default())));
+        let handler = RollupService::new(Arc::clone(&catalog));
+
+        let namespace = arbitrary_namespace(&mut *catalog.repositories().await, "grapes").await;
+
+        for request in [
+            CreateRollupRuleRequest {
+                language: QueryLanguage::Unspecified.into(),
+                ..create_request(&namespace.name)
+            },
+            CreateRollupRuleRequest {
+                interval_ns: 0,
+                ..create_request(&namespace.name)
+            },
+            CreateRollupRuleRequest {
+                target_table: "cpu".to_string(),
+                ..create_request(&namespace.name)
+            },
+            CreateRollupRuleRequest {
+                aggregates: " ".to_string(),
+                ..create_request(&namespace.name)
+            },
+            CreateRollupRuleRequest {
+                raw_retention_period_ns: Some(-1),
+                ..create_request(&namespace.name)
+            },
+        ] {
+            let error = handler
+                .create_rollup_rule(Request::new(request))
+                .await
+                .unwrap_err();
+            assert_eq!(error.code(), Code::InvalidArgument);
+        }
+
+        let error = handler
+            .create_rollup_rule(Request::new(create_request("not_a_namespace")))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code(), Code::NotFound);
+    }
+}
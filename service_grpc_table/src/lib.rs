@@ -110,17 +110,159 @@ impl table_service_server::TableService for TableService {
             "created table"
         );
 
-        Ok(Response::new(table_to_create_response_proto(table)))
+        Ok(Response::new(CreateTableResponse {
+            table: Some(table_to_proto(table)),
+        }))
+    }
+
+    // update the retention period override of a table
+    async fn update_table_retention(
+        &self,
+        request: Request<UpdateTableRetentionRequest>,
+    ) -> Result<Response<UpdateTableRetentionResponse>, Status> {
+        let mut repos = self.catalog.repositories().await;
+
+        let UpdateTableRetentionRequest {
+            name,
+            namespace,
+            retention_period_ns,
+        } = request.into_inner();
+
+        let retention_period_ns = map_retention_period(retention_period_ns)?;
+
+        let namespace_name = NamespaceName::try_from(namespace)
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+
+        debug!(
+            %name,
+            %namespace_name,
+            ?retention_period_ns,
+            "Updating table retention",
+        );
+
+        let namespace = repos
+            .namespaces()
+            .get_by_name(&namespace_name, SoftDeletedRows::ExcludeDeleted)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?
+            .ok_or_else(|| {
+                Status::not_found(format!(
+                    "Could not find a namespace with name {namespace_name}"
+                ))
+            })?;
+
+        let table = repos
+            .tables()
+            .get_by_namespace_and_name(namespace.id, &name)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?
+            .ok_or_else(|| {
+                Status::not_found(format!(
+                    "Could not find a table with name {name} in namespace {namespace_name}"
+                ))
+            })?;
+
+        let table = repos
+            .tables()
+            .update_retention_period(table.id, retention_period_ns)
+            .await
+            .map_err(|e| {
+                warn!(error=%e, %name, "failed to update table retention");
+                match e {
+                    iox_catalog::interface::Error::TableNotFound { .. } => {
+                        Status::not_found(e.to_string())
+                    }
+                    other => Status::internal(other.to_string()),
+                }
+            })?;
+
+        info!(
+            %name,
+            table_id = %table.id,
+            retention_period_ns,
+            "updated table retention"
+        );
+
+        Ok(Response::new(UpdateTableRetentionResponse {
+            table: Some(table_to_proto(table)),
+        }))
+    }
+
+    // get the object storage used by a table
+    async fn get_table_storage_usage(
+        &self,
+        request: Request<GetTableStorageUsageRequest>,
+    ) -> Result<Response<GetTableStorageUsageResponse>, Status> {
+        let mut repos = self.catalog.repositories().await;
+
+        let GetTableStorageUsageRequest { name, namespace } = request.into_inner();
+
+        let namespace_name = NamespaceName::try_from(namespace)
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+
+        let namespace = repos
+            .namespaces()
+            .get_by_name(&namespace_name, SoftDeletedRows::ExcludeDeleted)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?
+            .ok_or_else(|| {
+                Status::not_found(format!(
+                    "Could not find a namespace with name {namespace_name}"
+                ))
+            })?;
+
+        let table = repos
+            .tables()
+            .get_by_namespace_and_name(namespace.id, &name)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?
+            .ok_or_else(|| {
+                Status::not_found(format!(
+                    "Could not find a table with name {name} in namespace {namespace_name}"
+                ))
+            })?;
+
+        let usage = repos
+            .parquet_files()
+            .storage_usage_by_namespace(namespace.id)
+            .await
+            .map_err(|e| {
+                warn!(error=%e, %name, "failed to get table storage usage");
+                Status::internal(e.to_string())
+            })?
+            .into_iter()
+            .filter(|u| u.table_id == table.id)
+            .map(|u| u.to_proto(&table.name))
+            .collect();
+
+        Ok(Response::new(GetTableStorageUsageResponse { usage }))
     }
 }
 
-fn table_to_create_response_proto(table: CatalogTable) -> CreateTableResponse {
-    CreateTableResponse {
-        table: Some(Table {
-            id: table.id.get(),
-            name: table.name.clone(),
-            namespace_id: table.namespace_id.get(),
-        }),
+fn table_to_proto(table: CatalogTable) -> Table {
+    Table {
+        id: table.id.get(),
+        name: table.name,
+        namespace_id: table.namespace_id.get(),
+        retention_period_ns: table.retention_period_ns,
+    }
+}
+
+/// Map a user-submitted retention period value to the correct internal
+/// encoding.
+///
+/// 0 is always mapped to [`None`], indicating the namespace retention period
+/// applies.
+///
+/// Negative retention periods are rejected with an error.
+fn map_retention_period(v: Option<i64>) -> Result<Option<i64>, Status> {
+    match v {
+        Some(0) => Ok(None),
+        Some(v @ 1..) => Ok(Some(v)),
+        Some(_v @ ..=0) => Err(Status::invalid_argument(
+            "invalid negative retention period",
+        )),
+        None => Ok(None),
     }
 }
 
@@ -128,10 +270,14 @@ fn table_to_create_response_proto(table: CatalogTable) -> CreateTableResponse {
 mod tests {
     use data_types::{partition_template::NamespacePartitionTemplateOverride, TableId};
     use generated_types::influxdata::iox::{
+        catalog::v1::StorageUsage,
         partition_template::v1::{template_part, PartitionTemplate, TemplatePart},
         table::v1::table_service_server::TableService as _,
     };
-    use iox_catalog::{mem::MemCatalog, test_helpers::arbitrary_namespace};
+    use iox_catalog::{
+        mem::MemCatalog,
+        test_helpers::{arbitrary_namespace, arbitrary_parquet_file_params, arbitrary_table},
+    };
     use tonic::Code;
 
     use super::*;
@@ -397,4 +543,134 @@ mod tests {
         let all_tables = catalog.repositories().await.tables().list().await.unwrap();
         assert!(all_tables.is_empty());
     }
+
+    #[tokio::test]
+    async fn update_table_retention() {
+        let catalog: Arc<dyn Catalog> =
+            Arc::new(MemCatalog::new(Arc::new(metric::Registry::default())));
+        let handler = TableService::new(Arc::clone(&catalog));
+
+        let namespace = arbitrary_namespace(&mut *catalog.repositories().await, "grapes").await;
+        let table_name = "varietals";
+
+        let created_table = handler
+            .create_table(Request::new(CreateTableRequest {
+                name: table_name.into(),
+                namespace: namespace.name.clone(),
+                partition_template: None,
+            }))
+            .await
+            .unwrap()
+            .into_inner()
+            .table
+            .unwrap();
+        assert_eq!(created_table.retention_period_ns, None);
+
+        // Set a retention period override
+        let updated_table = handler
+            .update_table_retention(Request::new(UpdateTableRetentionRequest {
+                name: table_name.into(),
+                namespace: namespace.name.clone(),
+                retention_period_ns: Some(42),
+            }))
+            .await
+            .unwrap()
+            .into_inner()
+            .table
+            .unwrap();
+        assert_eq!(updated_table.id, created_table.id);
+        assert_eq!(updated_table.retention_period_ns, Some(42));
+
+        // A zero removes the override
+        let updated_table = handler
+            .update_table_retention(Request::new(UpdateTableRetentionRequest {
+                name: table_name.into(),
+                namespace: namespace.name.clone(),
+                retention_period_ns: Some(0),
+            }))
+            .await
+            .unwrap()
+            .into_inner()
+            .table
+            .unwrap();
+        assert_eq!(updated_table.retention_period_ns, None);
+
+        // Negative retention periods are rejected
+        let error = handler
+            .update_table_retention(Request::new(UpdateTableRetentionRequest {
+                name: table_name.into(),
+                namespace: namespace.name.clone(),
+                retention_period_ns: Some(-1),
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code(), Code::InvalidArgument);
+
+        // Unknown tables are not found
+        let error = handler
+            .update_table_retention(Request::new(UpdateTableRetentionRequest {
+                name: "does_not_exist".into(),
+                namespace: namespace.name.clone(),
+                retention_period_ns: Some(42),
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code(), Code::NotFound);
+    }
+
+    #[tokio::test]
+    async fn table_storage_usage() {
+        let catalog: Arc<dyn Catalog> =
+            Arc::new(MemCatalog::new(Arc::new(metric::Registry::default())));
+        let handler = TableService::new(Arc::clone(&catalog));
+
+        let mut repos = catalog.repositories().await;
+        let namespace = arbitrary_namespace(&mut *repos, "grapes").await;
+        let table = arbitrary_table(&mut *repos, "varietals", &namespace).await;
+        let other_table = arbitrary_table(&mut *repos, "regions", &namespace).await;
+        for table in [&table, &table, &other_table] {
+            let partition = repos
+                .partitions()
+                .create_or_get("bananas".into(), table.id)
+                .await
+                .unwrap();
+            repos
+                .parquet_files()
+                .create(arbitrary_parquet_file_params(&namespace, table, &partition))
+                .await
+                .unwrap();
+        }
+        drop(repos);
+
+        let usage = handler
+            .get_table_storage_usage(Request::new(GetTableStorageUsageRequest {
+                name: table.name.clone(),
+                namespace: namespace.name.clone(),
+            }))
+            .await
+            .unwrap()
+            .into_inner()
+            .usage;
+        assert_eq!(
+            usage,
+            vec![StorageUsage {
+                table_id: table.id.get(),
+                table_name: table.name.clone(),
+                compaction_level: 0,
+                file_count: 2,
+                file_size_bytes: 2 * 1337,
+                row_count: 0,
+            }]
+        );
+
+        // Unknown tables are not found
+        let error = handler
+            .get_table_storage_usage(Request::new(GetTableStorageUsageRequest {
+                name: "does_not_exist".into(),
+                namespace: namespace.name.clone(),
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code(), Code::NotFound);
+    }
 }
@@ -1,4 +1,7 @@
-use crate::{FileTypeIdentifier, SegmentEntry, SegmentIdBytes, SequencedWalOp};
+use crate::{
+    FileTypeIdentifier, SegmentCodec, SegmentEntry, SegmentIdBytes, SequencedWalOp,
+    CODEC_FILE_TYPE_IDENTIFIER,
+};
 use byteorder::{BigEndian, ReadBytesExt};
 use crc32fast::Hasher;
 use generated_types::influxdata::iox::wal::v1::WalOpBatch as ProtoWalOpBatch;
@@ -11,8 +14,21 @@ use std::{
     path::{Path, PathBuf},
 };
 
+/// The number of bytes preceding the data of each entry: a `u32` checksum
+/// and a `u32` length.
+const ENTRY_HEADER_LEN: u64 = 8;
+
 #[derive(Debug)]
-pub struct ClosedSegmentFileReader<R>(R);
+pub struct ClosedSegmentFileReader<R> {
+    inner: R,
+
+    /// The number of bytes of the file that have been read up to the end of
+    /// the last entry that was read in full.
+    bytes_read: u64,
+
+    /// The codec the entries are compressed with, as recorded in the header.
+    codec: SegmentCodec,
+}
 
 impl ClosedSegmentFileReader<BufReader<File>> {
     pub fn from_path(path: impl AsRef<Path>) -> Result<Self> {
@@ -28,43 +44,78 @@ where
     R: Read,
 {
     pub fn new(f: R) -> Self {
-        Self(f)
+        Self {
+            inner: f,
+            bytes_read: 0,
+            codec: SegmentCodec::Snappy,
+        }
+    }
+
+    /// Returns the offset of the end of the last entry that was read in full,
+    /// which is the file length once all entries have been read.
+    ///
+    /// An entry that fails its checksum (or can not be decoded) has still been
+    /// read in full, and reading resumes at the next entry. An entry with an
+    /// unreadable length or data does not advance this offset, as the position
+    /// of the entries following it can not be known.
+    pub fn bytes_read(&self) -> u64 {
+        self.bytes_read
+    }
+
+    /// Returns the codec the entries are compressed with, which is only known
+    /// once the header has been read.
+    pub fn codec(&self) -> SegmentCodec {
+        self.codec
     }
 
     fn read_array<const N: usize>(&mut self) -> Result<[u8; N]> {
         let mut data = [0u8; N];
-        self.0
+        self.inner
             .read_exact(&mut data)
             .context(UnableToReadArraySnafu { length: N })?;
+        self.bytes_read += N as u64;
         Ok(data)
     }
 
     pub fn read_header(&mut self) -> Result<(FileTypeIdentifier, SegmentIdBytes)> {
-        Ok((self.read_array()?, self.read_array()?))
+        let file_type: FileTypeIdentifier = self.read_array()?;
+        let id: SegmentIdBytes = self.read_array()?;
+
+        // Only the newer header records the codec, the entries of segments
+        // with the original header are always snappy compressed.
+        if &file_type == CODEC_FILE_TYPE_IDENTIFIER {
+            let [codec] = self.read_array::<1>()?;
+            self.codec = SegmentCodec::from_id(codec).context(UnknownCodecSnafu { id: codec })?;
+        }
+
+        Ok((file_type, id))
     }
 
     fn one_entry(&mut self) -> Result<Option<SegmentEntry>> {
-        let expected_checksum = match self.0.read_u32::<BigEndian>() {
+        let expected_checksum = match self.inner.read_u32::<BigEndian>() {
             Err(ref e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
             other => other.context(UnableToReadChecksumSnafu)?,
         };
 
         let expected_len = self
-            .0
+            .inner
             .read_u32::<BigEndian>()
             .context(UnableToReadLengthSnafu)?
             .into();
 
-        let compressed_read = self.0.by_ref().take(expected_len);
-        let hashing_read = CrcReader::new(compressed_read);
-        let mut decompressing_read = FrameDecoder::new(hashing_read);
+        let compressed_read = self.inner.by_ref().take(expected_len);
+        let mut hashing_read = CrcReader::new(compressed_read);
 
         let mut data = Vec::with_capacity(100);
-        decompressing_read
-            .read_to_end(&mut data)
-            .context(UnableToReadDataSnafu)?;
+        match self.codec {
+            SegmentCodec::Snappy => FrameDecoder::new(&mut hashing_read).read_to_end(&mut data),
+            SegmentCodec::Zstd => zstd::stream::read::Decoder::new(&mut hashing_read)
+                .and_then(|mut decoder| decoder.read_to_end(&mut data)),
+            SegmentCodec::Uncompressed => hashing_read.read_to_end(&mut data),
+        }
+        .context(UnableToReadDataSnafu)?;
 
-        let (actual_compressed_len, actual_checksum) = decompressing_read.get_mut().checksum();
+        let (actual_compressed_len, actual_checksum) = hashing_read.checksum();
 
         ensure!(
             expected_len == actual_compressed_len,
@@ -74,6 +125,8 @@ where
             }
         );
 
+        self.bytes_read += ENTRY_HEADER_LEN + expected_len;
+
         ensure!(
             expected_checksum == actual_checksum,
             ChecksumMismatchSnafu {
@@ -118,13 +171,8 @@ impl<R> CrcReader<R> {
         }
     }
 
-    fn checksum(&mut self) -> (u64, u32) {
-        // FIXME: If rust-snappy added an `into_inner`, we should
-        // take `self` by value
-        (
-            std::mem::take(&mut self.bytes_seen),
-            std::mem::take(&mut self.hasher).finalize(),
-        )
+    fn checksum(self) -> (u64, u32) {
+        (self.bytes_seen, self.hasher.finalize())
     }
 }
 
@@ -142,6 +190,21 @@ where
     }
 }
 
+impl Error {
+    /// Returns true if this error is the result of reading an entry that
+    /// extends past the end of the file, as left by a write that was
+    /// interrupted part way through.
+    pub fn is_truncated_entry(&self) -> bool {
+        match self {
+            Self::UnableToReadLength { source } | Self::UnableToReadData { source } => {
+                source.kind() == io::ErrorKind::UnexpectedEof
+            }
+            Self::LengthMismatch { expected, actual } => actual < expected,
+            _ => false,
+        }
+    }
+}
+
 #[derive(Debug, Snafu)]
 pub enum Error {
     UnableToOpenFile {
@@ -154,6 +217,10 @@ pub enum Error {
         length: usize,
     },
 
+    UnknownCodec {
+        id: u8,
+    },
+
     UnableToReadChecksum {
         source: io::Error,
     },
@@ -194,11 +261,15 @@ pub type Result<T, E = Error> = std::result::Result<T, E>;
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{SegmentId, FILE_TYPE_IDENTIFIER};
+    use crate::{SegmentId, CODEC_FILE_TYPE_IDENTIFIER, FILE_TYPE_IDENTIFIER};
     use byteorder::WriteBytesExt;
     use std::io::Write;
     use test_helpers::assert_error;
 
+    /// The length of the file type identifier and segment id at the start of
+    /// a segment file.
+    const HEADER_LEN: u64 = 16;
+
     #[test]
     fn successful_read_no_entries() {
         let segment_file = FakeSegmentFile::new();
@@ -240,6 +311,7 @@ mod tests {
 
         let entry = reader.one_entry().unwrap();
         assert!(entry.is_none());
+        assert_eq!(reader.bytes_read(), data.len() as u64);
     }
 
     #[test]
@@ -262,6 +334,8 @@ mod tests {
 
         let read_fail = reader.one_entry();
         assert_error!(read_fail, Error::UnableToReadData { .. });
+        // The damaged entry is not counted as read
+        assert_eq!(reader.bytes_read(), HEADER_LEN);
         // Trying to continue reading will fail as well, see:
         // <https://github.com/influxdata/influxdb_iox/issues/6222>
         assert_error!(reader.one_entry(), Error::UnableToReadData { .. });
@@ -287,6 +361,8 @@ mod tests {
 
         let read_fail = reader.one_entry();
         assert_error!(read_fail, Error::UnableToReadData { .. });
+        // The damaged entry is not counted as read
+        assert_eq!(reader.bytes_read(), HEADER_LEN);
         // Trying to continue reading will fail as well, see:
         // <https://github.com/influxdata/influxdb_iox/issues/6222>
         assert_error!(reader.one_entry(), Error::UnableToReadData { .. });
@@ -298,6 +374,7 @@ mod tests {
         let bad_entry_input = FakeSegmentEntry::new(b"hello");
         let good_checksum = bad_entry_input.checksum();
         let bad_entry_input = bad_entry_input.with_checksum(good_checksum + 1);
+        let bad_entry_input_len = bad_entry_input.compressed_len();
         segment_file.add_entry(bad_entry_input);
 
         let good_entry_input = FakeSegmentEntry::new(b"goodbye");
@@ -312,6 +389,11 @@ mod tests {
 
         let read_fail = reader.one_entry();
         assert_error!(read_fail, Error::ChecksumMismatch { .. });
+        // The entry was read in full, allowing reading to resume after it
+        assert_eq!(
+            reader.bytes_read(),
+            HEADER_LEN + ENTRY_HEADER_LEN + u64::from(bad_entry_input_len)
+        );
 
         // A bad checksum won't corrupt further entries
         let entry_output_2 = reader.one_entry().unwrap().unwrap();
@@ -322,6 +404,45 @@ mod tests {
         assert!(entry.is_none());
     }
 
+    #[test]
+    fn unsuccessful_read_truncated_entry() {
+        let mut segment_file = FakeSegmentFile::new();
+        segment_file.add_entry(FakeSegmentEntry::new(b"hello"));
+
+        // Each prefix of the entry is reported as a truncated entry, bar
+        // those too short to hold a checksum, which read as no entry at all.
+        let data = segment_file.data();
+        for len in (HEADER_LEN as usize + 4)..data.len() {
+            let mut reader = ClosedSegmentFileReader::new(&data[..len]);
+            reader.read_header().unwrap();
+
+            let err = reader.one_entry().expect_err("truncated entry should fail");
+            assert!(err.is_truncated_entry(), "{len}: {err}");
+            assert_eq!(reader.bytes_read(), HEADER_LEN);
+        }
+
+        // Whereas an entry read in full is not truncated, even if damaged.
+        let mut segment_file = FakeSegmentFile::new();
+        let entry = FakeSegmentEntry::new(b"hello");
+        let checksum = entry.checksum();
+        segment_file.add_entry(entry.with_checksum(checksum + 1));
+        let data = segment_file.data();
+        let mut reader = ClosedSegmentFileReader::new(data.as_slice());
+        reader.read_header().unwrap();
+        assert!(!reader.one_entry().unwrap_err().is_truncated_entry());
+    }
+
+    #[test]
+    fn unsuccessful_read_unknown_codec() {
+        let mut data = Vec::new();
+        data.write_all(CODEC_FILE_TYPE_IDENTIFIER).unwrap();
+        data.write_all(&SegmentId::new(0).as_bytes()).unwrap();
+        data.write_u8(42).unwrap();
+
+        let mut reader = ClosedSegmentFileReader::new(data.as_slice());
+        assert_error!(reader.read_header(), Error::UnknownCodec { id: 42 });
+    }
+
     #[derive(Debug)]
     struct FakeSegmentFile {
         id: SegmentId,
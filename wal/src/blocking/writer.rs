@@ -1,4 +1,7 @@
-use crate::{ClosedSegment, SegmentId, WriteSummary, FILE_TYPE_IDENTIFIER};
+use crate::{
+    ClosedSegment, FsyncPolicy, SegmentCodec, SegmentId, WalConfig, WriteSummary,
+    CODEC_FILE_TYPE_IDENTIFIER, FILE_TYPE_IDENTIFIER,
+};
 use byteorder::{BigEndian, WriteBytesExt};
 use crc32fast::Hasher;
 use snafu::prelude::*;
@@ -32,6 +35,11 @@ pub struct OpenSegmentFileWriter {
     f: File,
     bytes_written: usize,
 
+    fsync: FsyncPolicy,
+    codec: SegmentCodec,
+    /// True if data has been written to `f` since it was last fsync-ed.
+    unsynced: bool,
+
     buffer: Vec<u8>,
 }
 
@@ -39,6 +47,7 @@ impl OpenSegmentFileWriter {
     pub fn new_in_directory(
         dir: impl Into<PathBuf>,
         next_id_source: Arc<AtomicU64>,
+        config: WalConfig,
     ) -> Result<Self> {
         let id = SegmentId::new(next_id_source.fetch_add(1, Ordering::Relaxed));
         let path = crate::build_segment_path(dir, id);
@@ -49,25 +58,41 @@ impl OpenSegmentFileWriter {
             .open(&path)
             .context(SegmentCreateSnafu)?;
 
-        f.write_all(FILE_TYPE_IDENTIFIER)
-            .context(SegmentWriteFileTypeSnafu)?;
-        let file_type_bytes_written = FILE_TYPE_IDENTIFIER.len();
+        // Snappy segments are written with the original header, which has no
+        // codec byte, so that older readers can still read them.
+        let file_type = match config.codec {
+            SegmentCodec::Snappy => FILE_TYPE_IDENTIFIER,
+            SegmentCodec::Zstd | SegmentCodec::Uncompressed => CODEC_FILE_TYPE_IDENTIFIER,
+        };
+        f.write_all(file_type).context(SegmentWriteFileTypeSnafu)?;
+        let file_type_bytes_written = file_type.len();
 
         let id_bytes = id.as_bytes();
         f.write_all(&id_bytes).context(SegmentWriteIdSnafu)?;
         let id_bytes_written = id_bytes.len();
 
-        f.sync_all().expect("fsync failure");
+        let mut codec_bytes_written = 0;
+        if file_type == CODEC_FILE_TYPE_IDENTIFIER {
+            f.write_u8(config.codec.id())
+                .context(SegmentWriteCodecSnafu)?;
+            codec_bytes_written = 1;
+        }
 
-        let bytes_written = file_type_bytes_written + id_bytes_written;
+        let bytes_written = file_type_bytes_written + id_bytes_written + codec_bytes_written;
 
-        Ok(Self {
+        let mut writer = Self {
             id,
             path,
             f,
             bytes_written,
+            fsync: config.fsync,
+            codec: config.codec,
+            unsynced: true,
             buffer: Vec::with_capacity(8 * 1204), // 8kiB initial size
-        })
+        };
+        writer.sync()?;
+
+        Ok(writer)
     }
 
     pub fn id(&self) -> SegmentId {
@@ -96,12 +121,28 @@ impl OpenSegmentFileWriter {
 
         // Compress the payload into the reused buffer, recording the crc hash
         // as it is wrote.
-        let mut encoder = snap::write::FrameEncoder::new(HasherWrapper::new(&mut self.buffer));
-        encoder.write_all(data).context(UnableToCompressDataSnafu)?;
-        let (checksum, buf) = encoder
-            .into_inner()
-            .expect("cannot fail to flush to a Vec")
-            .finalize();
+        let mut hasher = HasherWrapper::new(&mut self.buffer);
+        let hasher = match self.codec {
+            SegmentCodec::Snappy => {
+                let mut encoder = snap::write::FrameEncoder::new(hasher);
+                encoder.write_all(data).context(UnableToCompressDataSnafu)?;
+                encoder.into_inner().expect("cannot fail to flush to a Vec")
+            }
+            SegmentCodec::Zstd => {
+                let mut encoder =
+                    zstd::stream::write::Encoder::new(hasher, zstd::DEFAULT_COMPRESSION_LEVEL)
+                        .context(UnableToCompressDataSnafu)?;
+                encoder.write_all(data).context(UnableToCompressDataSnafu)?;
+                encoder.finish().context(UnableToCompressDataSnafu)?
+            }
+            SegmentCodec::Uncompressed => {
+                hasher
+                    .write_all(data)
+                    .expect("cannot fail to write to buffer");
+                hasher
+            }
+        };
+        let (checksum, buf) = hasher.finalize();
 
         // Adjust the compressed length to take into account the u64 padding
         // above.
@@ -123,11 +164,14 @@ impl OpenSegmentFileWriter {
         let buf = buf.into_inner();
         let bytes_written = buf.len();
         self.f.write_all(buf).context(SegmentWriteDataSnafu)?;
-
-        // fsync the fd
-        self.f.sync_all().expect("fsync failure");
-
         self.bytes_written += bytes_written;
+        self.unsynced = true;
+
+        // Under any other policy, the fd is fsync-ed by a separate call to
+        // sync().
+        if self.fsync == FsyncPolicy::PerBatch {
+            self.sync()?;
+        }
 
         Ok(WriteSummary {
             total_bytes: self.bytes_written,
@@ -136,6 +180,21 @@ impl OpenSegmentFileWriter {
         })
     }
 
+    /// fsync any data written to the segment file since it was last synced,
+    /// unless the [`FsyncPolicy`] is [`FsyncPolicy::Off`].
+    ///
+    /// If this fails, it is unknown which of the unsynced writes are durable.
+    pub fn sync(&mut self) -> Result<()> {
+        if !self.unsynced || self.fsync == FsyncPolicy::Off {
+            return Ok(());
+        }
+
+        self.f.sync_all().context(SegmentSyncSnafu)?;
+        self.unsynced = false;
+
+        Ok(())
+    }
+
     pub fn close(self) -> Result<ClosedSegment> {
         let Self {
             id,
@@ -167,6 +226,10 @@ pub enum Error {
         source: io::Error,
     },
 
+    SegmentWriteCodec {
+        source: io::Error,
+    },
+
     SegmentWriteChecksum {
         source: io::Error,
     },
@@ -179,6 +242,10 @@ pub enum Error {
         source: io::Error,
     },
 
+    SegmentSync {
+        source: io::Error,
+    },
+
     ChunkSizeTooLarge {
         source: num::TryFromIntError,
         actual: usize,
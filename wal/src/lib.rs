@@ -34,7 +34,7 @@ use generated_types::{
 use hashbrown::HashMap;
 use mutable_batch::MutableBatch;
 use mutable_batch_pb::decode::decode_database_batch;
-use observability_deps::tracing::info;
+use observability_deps::tracing::{error, info};
 use parking_lot::Mutex;
 use snafu::prelude::*;
 use tokio::{sync::watch, task::JoinHandle};
@@ -127,6 +127,16 @@ pub enum Error {
         path: PathBuf,
     },
 
+    QuarantineClosedSegment {
+        source: std::io::Error,
+        path: PathBuf,
+    },
+
+    UnableToReadSegment {
+        source: std::io::Error,
+        path: PathBuf,
+    },
+
     OpenSegmentDirectory {
         source: std::io::Error,
         path: PathBuf,
@@ -139,6 +149,18 @@ pub enum Error {
     UnableToCreateSegmentFile {
         source: blocking::WriterError,
     },
+
+    #[snafu(display("a previous fsync of the wal failed, rejecting all writes"))]
+    SyncFailed,
+}
+
+impl Error {
+    /// Returns true if this error is the result of reading a segment entry
+    /// that was only partially written, see
+    /// [`blocking::ReaderError::is_truncated_entry()`].
+    pub fn is_truncated_entry(&self) -> bool {
+        matches!(self, Self::UnableToReadNextOps { source } if source.is_truncated_entry())
+    }
 }
 
 /// Errors that occur when decoding internal types from a WAL file.
@@ -189,6 +211,24 @@ impl std::fmt::Display for SegmentId {
     }
 }
 
+/// Returns the name of segment `id` when archived at `archived_at`, a
+/// timestamp in nanoseconds since the epoch.
+///
+/// Archived segment names sort in the order the segments were archived in.
+pub fn archived_segment_name(archived_at: i64, id: SegmentId) -> String {
+    format!("{archived_at:020}-{id}.{SEGMENT_FILE_EXTENSION}")
+}
+
+/// Parses a name returned by [`archived_segment_name()`] into the timestamp
+/// the segment was archived at, and its [`SegmentId`].
+pub fn parse_archived_segment_name(name: &str) -> Option<(i64, SegmentId)> {
+    let stem = name
+        .strip_suffix(SEGMENT_FILE_EXTENSION)?
+        .strip_suffix('.')?;
+    let (archived_at, id) = stem.split_once('-')?;
+    Some((archived_at.parse().ok()?, SegmentId::new(id.parse().ok()?)))
+}
+
 pub(crate) fn build_segment_path(dir: impl Into<PathBuf>, id: SegmentId) -> PathBuf {
     let mut path = dir.into();
     path.push(id.to_string());
@@ -199,9 +239,103 @@ pub(crate) fn build_segment_path(dir: impl Into<PathBuf>, id: SegmentId) -> Path
 /// The first bytes written into a segment file to identify it and its version.
 // TODO: What's the expected way of upgrading -- what happens when we need version 31?
 type FileTypeIdentifier = [u8; 8];
+/// Identifies a segment file with a header of the identifier and segment ID,
+/// followed by [`SegmentCodec::Snappy`] compressed entries.
 const FILE_TYPE_IDENTIFIER: &FileTypeIdentifier = b"INFLUXV3";
+/// Identifies a segment file with a header of the identifier, segment ID and a
+/// single byte recording the [`SegmentCodec`] of its entries.
+///
+/// Segments compressed with [`SegmentCodec::Snappy`] are always written with
+/// the [`FILE_TYPE_IDENTIFIER`] header, so that readers predating this header
+/// can read them.
+const CODEC_FILE_TYPE_IDENTIFIER: &FileTypeIdentifier = b"INFLUXV4";
 /// File extension for segment files.
 const SEGMENT_FILE_EXTENSION: &str = "dat";
+/// Name of the directory within the WAL root that damaged segment files are
+/// moved to.
+pub const QUARANTINE_DIRECTORY: &str = "quarantine";
+
+/// The durability policy of a [`Wal`], controlling when writes to segment
+/// files are fsync-ed to disk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FsyncPolicy {
+    /// fsync each batch of writes before reporting them as written.
+    #[default]
+    PerBatch,
+
+    /// fsync writes at most once per interval, and when the segment they were
+    /// written to is rotated.
+    ///
+    /// Writes are reported as written before they are fsync-ed, so those made
+    /// within the last interval may be lost should the host crash or lose
+    /// power.
+    Interval(Duration),
+
+    /// Never fsync writes, leaving it to the OS to flush them to disk.
+    ///
+    /// Any write may be lost should the host crash or lose power - intended
+    /// for benchmarks only.
+    Off,
+}
+
+/// The compression codec applied to the entries of a segment file.
+///
+/// The codec is recorded in the header of each segment file, so segments
+/// written with any codec can be read regardless of the codec configured for
+/// new segments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SegmentCodec {
+    /// Snappy framed compression, the codec of all segments written before
+    /// the codec was configurable.
+    #[default]
+    Snappy,
+
+    /// Zstandard compression, trading CPU time for smaller segments.
+    Zstd,
+
+    /// No compression.
+    Uncompressed,
+}
+
+impl SegmentCodec {
+    /// The byte recording this codec in a segment file header.
+    pub(crate) fn id(self) -> u8 {
+        match self {
+            Self::Snappy => 0,
+            Self::Zstd => 1,
+            Self::Uncompressed => 2,
+        }
+    }
+
+    /// Returns the codec recorded by the header byte `id`, if any.
+    pub(crate) fn from_id(id: u8) -> Option<Self> {
+        match id {
+            0 => Some(Self::Snappy),
+            1 => Some(Self::Zstd),
+            2 => Some(Self::Uncompressed),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for SegmentCodec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Snappy => write!(f, "snappy"),
+            Self::Zstd => write!(f, "zstd"),
+            Self::Uncompressed => write!(f, "none"),
+        }
+    }
+}
+
+/// Configuration of how a [`Wal`] writes segment files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct WalConfig {
+    /// When writes are fsync-ed to disk.
+    pub fsync: FsyncPolicy,
+    /// The codec used to compress the entries of new segment files.
+    pub codec: SegmentCodec,
+}
 
 /// The main type representing one WAL for one ingester instance.
 ///
@@ -215,6 +349,7 @@ const SEGMENT_FILE_EXTENSION: &str = "dat";
 /// is not supported.
 pub struct Wal {
     root: PathBuf,
+    config: WalConfig,
     segments: Arc<Mutex<Segments>>,
     next_id_source: Arc<AtomicU64>,
     buffer: Mutex<WalBuffer>,
@@ -234,8 +369,16 @@ impl Wal {
     /// Similarly, editing or deleting files within a `Wal`'s root directory via some other
     /// mechanism is not supported.
     pub async fn new(root: impl Into<PathBuf>) -> Result<Arc<Self>> {
+        Self::with_config(root, WalConfig::default()).await
+    }
+
+    /// Creates a `Wal` instance that manages files in the specified root
+    /// directory, writing new segment files as specified by `config`.
+    ///
+    /// The same constraints as [`Wal::new()`] apply.
+    pub async fn with_config(root: impl Into<PathBuf>, config: WalConfig) -> Result<Arc<Self>> {
         let root = root.into();
-        info!(wal_dir=?root, "Initalizing Write Ahead Log (WAL)");
+        info!(wal_dir=?root, ?config, "Initalizing Write Ahead Log (WAL)");
         tokio::fs::create_dir_all(&root)
             .await
             .context(UnableToCreateWalDirSnafu { path: &root })?;
@@ -245,7 +388,7 @@ impl Wal {
         File::open(&root)
             .expect("should be able to open just-created directory")
             .sync_all()
-            .expect("fsync failure");
+            .context(UnableToSyncSnafu)?;
 
         let mut dir = tokio::fs::read_dir(&root)
             .await
@@ -290,17 +433,19 @@ impl Wal {
             .unwrap_or(0);
         let next_id_source = Arc::new(AtomicU64::new(next_id));
         let open_segment =
-            OpenSegmentFileWriter::new_in_directory(&root, Arc::clone(&next_id_source))
+            OpenSegmentFileWriter::new_in_directory(&root, Arc::clone(&next_id_source), config)
                 .context(UnableToCreateSegmentFileSnafu)?;
 
         let buffer = WalBuffer::new(None);
 
         let wal = Self {
             root,
+            config,
             segments: Arc::new(Mutex::new(Segments {
                 closed_segments,
                 open_segment,
                 open_segment_ids: SequenceNumberSet::default(),
+                sync_failed: watch::channel(false).0,
             })),
             next_id_source,
             buffer: Mutex::new(buffer),
@@ -330,6 +475,12 @@ impl Wal {
         ClosedSegmentFileReader::from_path(path)
     }
 
+    /// Read the raw contents of a closed segment file
+    pub fn read_segment(&self, id: SegmentId) -> Result<Vec<u8>> {
+        let path = build_segment_path(&self.root, id);
+        std::fs::read(&path).context(UnableToReadSegmentSnafu { path })
+    }
+
     /// Writes one [`SequencedWalOp`] to the buffer and returns a watch channel
     /// for when the buffer is flushed and fsync'd to disk.
     pub fn write_op(&self, op: SequencedWalOp) -> watch::Receiver<Option<WriteResult>> {
@@ -338,16 +489,41 @@ impl Wal {
         b.flush_notification.clone()
     }
 
+    /// Returns a watch that changes to `true` once an fsync of a segment file
+    /// fails.
+    ///
+    /// After an fsync failure it is unknown which writes made it to disk, so
+    /// the WAL rejects all further writes and can not be rotated.
+    pub fn sync_failed(&self) -> watch::Receiver<bool> {
+        self.segments.lock().sync_failed.subscribe()
+    }
+
     /// Closes the currently open segment and opens a new one, returning the
     /// closed segment details, including the [`SequenceNumberSet`] containing
     /// the sequence numbers of the writes within the closed segment.
+    ///
+    /// Any writes to the closed segment not yet fsync-ed under the configured
+    /// [`FsyncPolicy`] are fsync-ed before it is closed.
     pub fn rotate(&self) -> Result<(ClosedSegment, SequenceNumberSet)> {
-        let new_open_segment =
-            OpenSegmentFileWriter::new_in_directory(&self.root, Arc::clone(&self.next_id_source))
-                .context(UnableToCreateSegmentFileSnafu)?;
-
         let mut segments = self.segments.lock();
 
+        // The new segment file is only created once the open segment is known
+        // to be durable, so a failed rotation leaves no empty segment behind
+        // to be picked up by replay.
+        ensure!(!*segments.sync_failed.borrow(), SyncFailedSnafu);
+        if let Err(e) = segments.open_segment.sync() {
+            error!(error=%e, "failed to fsync WAL segment before rotation");
+            segments.sync_failed.send_replace(true);
+            return Err(e).context(UnableToWriteSnafu);
+        }
+
+        let new_open_segment = OpenSegmentFileWriter::new_in_directory(
+            &self.root,
+            Arc::clone(&self.next_id_source),
+            self.config,
+        )
+        .context(UnableToCreateSegmentFileSnafu)?;
+
         let closed = std::mem::replace(&mut segments.open_segment, new_open_segment);
         let seqnum_set = std::mem::take(&mut segments.open_segment_ids);
         let closed = closed.close().expect("should convert to closed segment");
@@ -373,13 +549,31 @@ impl Wal {
 
         let mut interval = tokio::time::interval(WAL_FLUSH_INTERVAL);
 
+        // Under the interval fsync policy, writes are fsync-ed by requesting
+        // the I/O thread sync the open segment once per interval.
+        let mut sync_interval = match self.config.fsync {
+            FsyncPolicy::Interval(period) => Some(tokio::time::interval(period)),
+            FsyncPolicy::PerBatch | FsyncPolicy::Off => None,
+        };
+
         // Pre-allocate the WAL buffer outside of the exclusive lock, and track
         // the buffer utilisation to optimise pre-allocation.
         let mut size_hint = None;
         let mut new_buf = WalBuffer::new(size_hint);
 
         loop {
-            interval.tick().await;
+            match &mut sync_interval {
+                Some(sync_interval) => tokio::select! {
+                    _ = interval.tick() => {}
+                    _ = sync_interval.tick() => {
+                        io_thread.enqueue_sync().await;
+                        continue;
+                    }
+                },
+                None => {
+                    interval.tick().await;
+                }
+            }
 
             // Rust's move properties ensure we never accidentally reuse a
             // buffer, but make it clear the buffer is always fresh before use.
@@ -421,6 +615,72 @@ impl Wal {
             .context(SegmentNotFoundSnafu { id })?;
         std::fs::remove_file(&closed.path).context(DeleteClosedSegmentSnafu { path: closed.path })
     }
+
+    /// Moves the specified segment into the [`QUARANTINE_DIRECTORY`] of the
+    /// WAL, returning its new path.
+    ///
+    /// A quarantined segment is no longer part of the WAL and is not replayed,
+    /// but its contents are kept on disk for inspection.
+    pub async fn quarantine(&self, id: SegmentId) -> Result<PathBuf> {
+        let closed = self
+            .segments
+            .lock()
+            .closed_segments
+            .remove(&id)
+            .context(SegmentNotFoundSnafu { id })?;
+
+        let dir = self.root.join(QUARANTINE_DIRECTORY);
+        std::fs::create_dir_all(&dir).context(UnableToCreateWalDirSnafu { path: &dir })?;
+
+        let path = build_segment_path(dir, id);
+        std::fs::rename(&closed.path, &path)
+            .context(QuarantineClosedSegmentSnafu { path: closed.path })?;
+
+        Ok(path)
+    }
+
+    /// Removes the oldest segments from the [`QUARANTINE_DIRECTORY`] until the
+    /// segments remaining in it total at most `max_bytes`, returning the paths
+    /// of the removed segments.
+    ///
+    /// Files in the directory that are not named as segments are left alone,
+    /// and not counted.
+    pub fn trim_quarantine(&self, max_bytes: u64) -> Result<Vec<PathBuf>> {
+        let dir = self.root.join(QUARANTINE_DIRECTORY);
+        let entries = match std::fs::read_dir(&dir) {
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+            v => v.context(UnableToReadDirectoryContentsSnafu { path: &dir })?,
+        };
+
+        let mut segments = Vec::new();
+        for entry in entries {
+            let entry = entry.context(UnableToReadDirectoryContentsSnafu { path: &dir })?;
+            let metadata = entry.metadata().context(UnableToReadFileMetadataSnafu)?;
+            let path = entry.path();
+            let id = path
+                .file_stem()
+                .and_then(|v| v.to_str())
+                .and_then(|v| v.parse().ok())
+                .map(SegmentId::new);
+            if let (true, Some(id)) = (metadata.is_file(), id) {
+                segments.push((id, path, metadata.len()));
+            }
+        }
+        segments.sort_unstable_by_key(|(id, _, _)| *id);
+
+        let mut total = segments.iter().map(|(_, _, size)| size).sum::<u64>();
+        let mut removed = Vec::new();
+        for (_, path, size) in segments {
+            if total <= max_bytes {
+                break;
+            }
+            std::fs::remove_file(&path).context(DeleteClosedSegmentSnafu { path: &path })?;
+            total -= size;
+            removed.push(path);
+        }
+
+        Ok(removed)
+    }
 }
 
 impl Drop for Wal {
@@ -446,6 +706,8 @@ struct Segments {
     closed_segments: BTreeMap<SegmentId, ClosedSegment>,
     open_segment: OpenSegmentFileWriter,
     open_segment_ids: SequenceNumberSet,
+    /// Set to `true` once an fsync of the open segment fails.
+    sync_failed: watch::Sender<bool>,
 }
 
 #[derive(Debug)]
@@ -565,6 +827,20 @@ impl ClosedSegmentFileReader {
         self.id
     }
 
+    /// Return the number of bytes of the segment file read up to the end of
+    /// the last entry read in full.
+    ///
+    /// Once the reader yields no more entries, any bytes past this offset are
+    /// a truncated or otherwise unreadable tail of the file.
+    pub fn bytes_read(&self) -> u64 {
+        self.file.bytes_read()
+    }
+
+    /// Return the codec the entries of the segment file are compressed with.
+    pub fn codec(&self) -> SegmentCodec {
+        self.file.codec()
+    }
+
     /// Open the segment file and read its header, ensuring it is a segment file and reading its id.
     pub fn from_path(path: impl AsRef<Path>) -> Result<Self> {
         let path = path.as_ref();
@@ -574,7 +850,7 @@ impl ClosedSegmentFileReader {
         let (file_type, id) = file.read_header().context(UnableToReadFileHeaderSnafu)?;
 
         ensure!(
-            &file_type == FILE_TYPE_IDENTIFIER,
+            &file_type == FILE_TYPE_IDENTIFIER || &file_type == CODEC_FILE_TYPE_IDENTIFIER,
             SegmentFileIdentifierMismatchSnafu,
         );
 
@@ -767,6 +1043,119 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn wal_write_and_read_codecs() {
+        let dir = test_helpers::tmp_dir().unwrap();
+
+        let mut segments = Vec::new();
+        for codec in [
+            SegmentCodec::Snappy,
+            SegmentCodec::Zstd,
+            SegmentCodec::Uncompressed,
+        ] {
+            let config = WalConfig {
+                codec,
+                ..Default::default()
+            };
+            let wal = Wal::with_config(dir.path(), config).await.unwrap();
+
+            let op = SequencedWalOp {
+                table_write_sequence_numbers: [(TableId::new(0), 0)].into_iter().collect(),
+                op: WalOp::Write(test_data("m1,t=foo v=1i 1")),
+            };
+            wal.write_op(op.clone()).changed().await.unwrap();
+
+            let (closed, _) = wal.rotate().unwrap();
+            segments.push((closed.id(), codec, op));
+        }
+
+        // Segments written with every codec can be read by a WAL configured
+        // with the default codec.
+        let wal = Wal::new(dir.path()).await.unwrap();
+        for (id, codec, op) in segments {
+            let mut reader = wal.reader_for_segment(id).unwrap();
+            assert_eq!(reader.codec(), codec);
+            assert_eq!(reader.next().unwrap().unwrap(), [op]);
+            assert!(reader.next().is_none());
+        }
+    }
+
+    #[tokio::test]
+    async fn snappy_segments_have_original_header() {
+        let dir = test_helpers::tmp_dir().unwrap();
+
+        let wal = Wal::new(dir.path()).await.unwrap();
+        let (closed, _) = wal.rotate().unwrap();
+        let data = wal.read_segment(closed.id()).unwrap();
+        assert_eq!(&data[..8], FILE_TYPE_IDENTIFIER);
+        assert_eq!(data.len(), 16);
+
+        let config = WalConfig {
+            codec: SegmentCodec::Zstd,
+            ..Default::default()
+        };
+        let wal = Wal::with_config(dir.path(), config).await.unwrap();
+        let (closed, _) = wal.rotate().unwrap();
+        let data = wal.read_segment(closed.id()).unwrap();
+        assert_eq!(&data[..8], CODEC_FILE_TYPE_IDENTIFIER);
+        assert_eq!(data[16], SegmentCodec::Zstd.id());
+        assert_eq!(data.len(), 17);
+    }
+
+    #[tokio::test]
+    async fn wal_fsync_policies() {
+        for fsync in [
+            FsyncPolicy::PerBatch,
+            FsyncPolicy::Interval(Duration::from_millis(1)),
+            FsyncPolicy::Off,
+        ] {
+            let dir = test_helpers::tmp_dir().unwrap();
+            let config = WalConfig {
+                fsync,
+                ..Default::default()
+            };
+            let wal = Wal::with_config(dir.path(), config).await.unwrap();
+
+            let op = SequencedWalOp {
+                table_write_sequence_numbers: [(TableId::new(0), 0)].into_iter().collect(),
+                op: WalOp::Write(test_data("m1,t=foo v=1i 1")),
+            };
+            let mut result = wal.write_op(op.clone());
+            result.changed().await.unwrap();
+            assert_matches!(*result.borrow(), Some(WriteResult::Ok(_)));
+
+            // Let the interval policy sync the segment at least once.
+            tokio::time::sleep(Duration::from_millis(5)).await;
+
+            let (closed, ids) = wal.rotate().unwrap();
+            assert_eq!(ids.len(), 1);
+            assert!(!*wal.sync_failed().borrow());
+
+            let ops = wal
+                .reader_for_segment(closed.id())
+                .unwrap()
+                .flat_map(|batch| batch.unwrap())
+                .collect::<Vec<_>>();
+            assert_eq!(ops, [op]);
+        }
+    }
+
+    #[tokio::test]
+    async fn rotate_after_sync_failure_creates_no_segment() {
+        let dir = test_helpers::tmp_dir().unwrap();
+        let wal = Wal::new(dir.path()).await.unwrap();
+
+        let n_files = || std::fs::read_dir(dir.path()).unwrap().count();
+        let before = n_files();
+
+        wal.segments.lock().sync_failed.send_replace(true);
+
+        assert_matches!(wal.rotate(), Err(Error::SyncFailed));
+        assert_matches!(wal.rotate(), Err(Error::SyncFailed));
+        assert_eq!(n_files(), before);
+        assert!(wal.closed_segments().is_empty());
+    }
+
     // open wal with files that aren't segments (should log and skip)
 
     // read segment works even if last entry is truncated
@@ -807,6 +1196,87 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn quarantine_segment() {
+        let dir = test_helpers::tmp_dir().unwrap();
+
+        let wal = Wal::new(dir.path()).await.unwrap();
+        let (closed, _) = wal.rotate().unwrap();
+
+        // Quarantining a segment removes it from the closed segments, keeping
+        // the file in the quarantine directory.
+        let path = wal.quarantine(closed.id()).await.unwrap();
+        assert!(wal.closed_segments().is_empty());
+        assert_eq!(path.parent(), Some(&*dir.path().join(QUARANTINE_DIRECTORY)));
+        let reader = ClosedSegmentFileReader::from_path(&path).unwrap();
+        assert_eq!(reader.id(), closed.id());
+
+        // It can only be quarantined once
+        assert_matches!(
+            wal.quarantine(closed.id()).await,
+            Err(Error::SegmentNotFound { .. })
+        );
+        drop(wal);
+
+        // And is not picked up as a segment when the WAL is reopened.
+        let wal = Wal::new(dir.path()).await.unwrap();
+        let closed_segment_ids = wal
+            .closed_segments()
+            .iter()
+            .map(|c| c.id())
+            .collect::<Vec<_>>();
+        assert!(!closed_segment_ids.contains(&closed.id()));
+    }
+
+    #[tokio::test]
+    async fn trim_quarantine() {
+        let dir = test_helpers::tmp_dir().unwrap();
+
+        let wal = Wal::new(dir.path()).await.unwrap();
+
+        // Trimming a quarantine directory that does not exist is a no-op.
+        assert!(wal.trim_quarantine(0).unwrap().is_empty());
+
+        let (first, _) = wal.rotate().unwrap();
+        let (second, _) = wal.rotate().unwrap();
+        let first = wal.quarantine(first.id()).await.unwrap();
+        let second = wal.quarantine(second.id()).await.unwrap();
+        let second_size = std::fs::metadata(&second).unwrap().len();
+
+        // Files other than segments are left alone.
+        let other = dir.path().join(QUARANTINE_DIRECTORY).join("README");
+        std::fs::write(&other, "bananas").unwrap();
+
+        // Nothing is removed while the segments fit within the limit.
+        assert!(wal.trim_quarantine(u64::MAX).unwrap().is_empty());
+
+        // Otherwise the oldest segments are removed first.
+        assert_eq!(wal.trim_quarantine(second_size).unwrap(), [first.clone()]);
+        assert!(!first.exists());
+        assert!(second.exists());
+        assert!(other.exists());
+    }
+
+    #[test]
+    fn archived_segment_names() {
+        let name = archived_segment_name(1_694_000_000_000_000_000, SegmentId::new(42));
+        assert_eq!(name, "01694000000000000000-42.dat");
+        assert_eq!(
+            parse_archived_segment_name(&name),
+            Some((1_694_000_000_000_000_000, SegmentId::new(42)))
+        );
+
+        // Names sort by archival time, regardless of segment id
+        assert!(
+            archived_segment_name(9, SegmentId::new(1))
+                < archived_segment_name(10, SegmentId::new(0))
+        );
+
+        assert_eq!(parse_archived_segment_name("42.dat"), None);
+        assert_eq!(parse_archived_segment_name("1-42.parquet"), None);
+        assert_eq!(parse_archived_segment_name("1-bananas.dat"), None);
+    }
+
     #[tokio::test]
     async fn decode_write_op_entries() {
         let dir = test_helpers::tmp_dir().unwrap();
@@ -7,16 +7,25 @@ use parking_lot::Mutex;
 use prost::Message;
 use tokio::sync::mpsc;
 
-use crate::{Segments, WalBuffer, WriteResult};
+use crate::{blocking::WriterError, Segments, WalBuffer, WriteResult};
 
 /// The number of [`WalBuffer`] that may be enqueued for persistence.
 const FLUSH_QUEUE_DEPTH: usize = 1;
 
+/// A request for the I/O thread to perform.
+#[derive(Debug)]
+enum IoRequest {
+    /// Write the batch to the open segment file.
+    Write(WalBuffer),
+    /// fsync any writes to the open segment file not yet synced.
+    Sync,
+}
+
 /// An inner/non-pub struct that contains the [`WriterIoThreadHandle`] state -
 /// this lets the [`Drop`] impl of [`WriterIoThreadHandle`] destroy the channel
 /// tx and consume the [`JoinHandle`].
 struct HandleInner {
-    batch_tx: mpsc::Sender<WalBuffer>,
+    batch_tx: mpsc::Sender<IoRequest>,
     join_handle: JoinHandle<()>,
 }
 
@@ -63,11 +72,25 @@ impl WriterIoThreadHandle {
     ///
     /// Panics if the I/O thread is not running.
     pub(crate) async fn enqueue_batch(&self, batch: WalBuffer) {
+        self.enqueue(IoRequest::Write(batch)).await
+    }
+
+    /// Enqueue a request to fsync any writes to the current open segment file
+    /// that are not yet durable.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the I/O thread is not running.
+    pub(crate) async fn enqueue_sync(&self) {
+        self.enqueue(IoRequest::Sync).await
+    }
+
+    async fn enqueue(&self, request: IoRequest) {
         self.inner
             .as_ref()
             .unwrap()
             .batch_tx
-            .send(batch)
+            .send(request)
             .await
             .expect("wal writer IO thread is dead")
     }
@@ -88,13 +111,13 @@ impl Drop for WriterIoThreadHandle {
 /// The state of the I/O actor thread.
 struct WriterIoThread {
     /// A channel to receive batches to flush.
-    batch_rx: mpsc::Receiver<WalBuffer>,
+    batch_rx: mpsc::Receiver<IoRequest>,
     /// The set of segments, used to obtain the current open segment handle.
     segments: Arc<Mutex<Segments>>,
 }
 
 impl WriterIoThread {
-    fn new(batch_rx: mpsc::Receiver<WalBuffer>, segments: Arc<Mutex<Segments>>) -> Self {
+    fn new(batch_rx: mpsc::Receiver<IoRequest>, segments: Arc<Mutex<Segments>>) -> Self {
         Self { batch_rx, segments }
     }
 
@@ -107,7 +130,11 @@ impl WriterIoThread {
             proto_data.clear();
 
             let batch = match self.batch_rx.blocking_recv() {
-                Some(batch) => batch,
+                Some(IoRequest::Write(batch)) => batch,
+                Some(IoRequest::Sync) => {
+                    self.sync();
+                    continue;
+                }
                 None => {
                     // The batch channel has closed - all handles have been
                     // dropped.
@@ -140,8 +167,18 @@ impl WriterIoThread {
             // Obtain the segments lock - this prevents concurrent rotation, but
             // has no impact on concurrent writers.
             {
-                // Write the serialised data to the current open segment file.
                 let mut segments = self.segments.lock();
+
+                // Once an fsync has failed, the state of the segment file on
+                // disk is unknown and no further writes are accepted.
+                if *segments.sync_failed.borrow() {
+                    let _ = batch
+                        .notify_flush
+                        .send(Some(WriteResult::Err(crate::Error::SyncFailed.to_string())));
+                    continue;
+                }
+
+                // Write the serialised data to the current open segment file.
                 match segments.open_segment.write(&proto_data) {
                     Ok(summary) => {
                         // Broadcast the result to all writers to this batch.
@@ -158,6 +195,9 @@ impl WriterIoThread {
                     }
                     Err(e) => {
                         error!(error=%e, "failed to write WAL batch");
+                        if matches!(e, WriterError::SegmentSync { .. }) {
+                            segments.sync_failed.send_replace(true);
+                        }
                         let _ = batch
                             .notify_flush
                             .send(Some(WriteResult::Err(e.to_string())));
@@ -166,4 +206,18 @@ impl WriterIoThread {
             };
         }
     }
+
+    /// fsync the writes to the current open segment file that are not yet
+    /// durable.
+    fn sync(&self) {
+        let mut segments = self.segments.lock();
+        if *segments.sync_failed.borrow() {
+            return;
+        }
+
+        if let Err(e) = segments.open_segment.sync() {
+            error!(error=%e, "failed to fsync WAL segment");
+            segments.sync_failed.send_replace(true);
+        }
+    }
 }
@@ -0,0 +1,289 @@
+//! Selection of the writes within segment files and their export to parquet.
+
+use std::{
+    collections::BTreeMap,
+    fs::{create_dir_all, OpenOptions},
+    ops::{Range, RangeInclusive},
+    path::PathBuf,
+};
+
+use data_types::{NamespaceId, TableId};
+use mutable_batch::{column::ColumnData, MutableBatch};
+use parquet::arrow::ArrowWriter;
+use schema::{Projection, TIME_COLUMN_NAME};
+use wal::WriteOpEntry;
+
+use crate::{TableBatchWriter, WriteError};
+
+/// Selects the writes of a [`WriteOpEntry`] to export, by namespace, table
+/// and time range.
+///
+/// An unset criteria matches all writes.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExportFilter {
+    /// Only export writes to this namespace.
+    pub namespace: Option<NamespaceId>,
+    /// Only export writes to this table.
+    pub table: Option<TableId>,
+    /// Only export rows with a timestamp (in nanoseconds) within this range.
+    pub time_range: Option<RangeInclusive<i64>>,
+}
+
+impl ExportFilter {
+    /// Returns the table batches of `entry` matched by this filter, with any
+    /// rows outside of the time range removed.
+    pub fn filter_entry(
+        &self,
+        entry: WriteOpEntry,
+    ) -> Result<Vec<(TableId, MutableBatch)>, mutable_batch::Error> {
+        if self.namespace.is_some_and(|id| id != entry.namespace) {
+            return Ok(vec![]);
+        }
+
+        let mut batches = vec![];
+        for (table_id, batch) in entry.table_batches {
+            if self.table.is_some_and(|id| id != table_id) {
+                continue;
+            }
+
+            let batch = match &self.time_range {
+                Some(range) => match filter_time_range(batch, range)? {
+                    Some(batch) => batch,
+                    None => continue,
+                },
+                None => batch,
+            };
+
+            batches.push((table_id, batch));
+        }
+
+        // Output the tables in a deterministic order.
+        batches.sort_unstable_by_key(|(table_id, _)| *table_id);
+
+        Ok(batches)
+    }
+}
+
+/// Returns the rows of `batch` with a timestamp within `range`, or [`None`] if
+/// there are none.
+fn filter_time_range(
+    batch: MutableBatch,
+    range: &RangeInclusive<i64>,
+) -> Result<Option<MutableBatch>, mutable_batch::Error> {
+    let ColumnData::I64(times, _) = batch.column(TIME_COLUMN_NAME)?.data() else {
+        unreachable!("time column must contain i64 timestamps");
+    };
+
+    // Collapse the matching rows into contiguous ranges.
+    let mut rows: Vec<Range<usize>> = vec![];
+    for (idx, _) in times.iter().enumerate().filter(|(_, t)| range.contains(t)) {
+        match rows.last_mut() {
+            Some(last) if last.end == idx => last.end = idx + 1,
+            _ => rows.push(idx..idx + 1),
+        }
+    }
+
+    match rows.as_slice() {
+        [] => Ok(None),
+        [all] if all.len() == batch.rows() => Ok(Some(batch)),
+        _ => {
+            let mut filtered = MutableBatch::new();
+            filtered.extend_from_ranges(&batch, &rows)?;
+            Ok(Some(filtered))
+        }
+    }
+}
+
+/// The [`ParquetWriter`] accumulates table-keyed mutable batches, writing the
+/// rows of each table to its own parquet file in a directory once finished.
+#[derive(Debug)]
+pub struct ParquetWriter {
+    output_dir: PathBuf,
+    replace_existing: bool,
+    tables: BTreeMap<TableId, MutableBatch>,
+}
+
+impl ParquetWriter {
+    /// Constructs a new [`ParquetWriter`] which writes the batches of each
+    /// table to "`table_id`.parquet" in `output_dir` when finished.
+    ///
+    /// If `replace_existing` is set then any pre-existing file is replaced.
+    pub fn new(output_dir: PathBuf, replace_existing: bool) -> Self {
+        Self {
+            output_dir,
+            replace_existing,
+            tables: Default::default(),
+        }
+    }
+
+    /// Write the accumulated rows of each table to their parquet files,
+    /// creating the output directory if it does not exist, and returning the
+    /// paths of the files written.
+    pub fn finish(self) -> Result<Vec<PathBuf>, WriteError> {
+        create_dir_all(&self.output_dir)?;
+
+        let mut open_options = OpenOptions::new().write(true).to_owned();
+        if self.replace_existing {
+            open_options.create(true).truncate(true);
+        } else {
+            open_options.create_new(true);
+        }
+
+        let mut paths = Vec::with_capacity(self.tables.len());
+        for (table_id, batch) in self.tables {
+            let record_batch = batch.to_arrow(Projection::All)?;
+
+            let path = self.output_dir.join(format!("{table_id}.parquet"));
+            let file = open_options.open(&path)?;
+
+            let mut writer = ArrowWriter::try_new(file, record_batch.schema(), None)?;
+            writer.write(&record_batch)?;
+            writer.close()?;
+
+            paths.push(path);
+        }
+
+        Ok(paths)
+    }
+}
+
+impl TableBatchWriter for ParquetWriter {
+    type WriteError = WriteError;
+
+    /// Appends the rows of the provided table batches to those previously
+    /// written for each table.
+    fn write_table_batches<B>(&mut self, table_batches: B) -> Result<(), Self::WriteError>
+    where
+        B: Iterator<Item = (TableId, MutableBatch)>,
+    {
+        for (table_id, batch) in table_batches {
+            match self.tables.get_mut(&table_id) {
+                Some(existing) => existing.extend_from(&batch)?,
+                None => {
+                    self.tables.insert(table_id, batch);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+
+    use arrow_util::assert_batches_sorted_eq;
+    use assert_matches::assert_matches;
+    use mutable_batch_lp::lines_to_batches;
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    use super::*;
+
+    fn entry(namespace_id: i64, lp: &str) -> WriteOpEntry {
+        WriteOpEntry {
+            namespace: NamespaceId::new(namespace_id),
+            table_batches: lines_to_batches(lp, 0)
+                .unwrap()
+                .into_iter()
+                .map(|(table_name, batch)| {
+                    let table_id = table_name.trim_start_matches('m').parse().unwrap();
+                    (TableId::new(table_id), batch)
+                })
+                .collect(),
+        }
+    }
+
+    fn row_counts(batches: &[(TableId, MutableBatch)]) -> Vec<(i64, usize)> {
+        batches
+            .iter()
+            .map(|(table_id, batch)| (table_id.get(), batch.rows()))
+            .collect()
+    }
+
+    #[test]
+    fn filter_entries() {
+        let lp = "m1,t=a v=1i 1\nm1,t=b v=2i 2\nm1,t=c v=3i 3\nm1,t=d v=4i 4\nm2,t=a v=1i 5";
+
+        let filter = ExportFilter::default();
+        let batches = filter.filter_entry(entry(1, lp)).unwrap();
+        assert_eq!(row_counts(&batches), [(1, 4), (2, 1)]);
+
+        let filter = ExportFilter {
+            namespace: Some(NamespaceId::new(2)),
+            ..Default::default()
+        };
+        assert!(filter.filter_entry(entry(1, lp)).unwrap().is_empty());
+
+        let filter = ExportFilter {
+            namespace: Some(NamespaceId::new(1)),
+            table: Some(TableId::new(2)),
+            ..Default::default()
+        };
+        let batches = filter.filter_entry(entry(1, lp)).unwrap();
+        assert_eq!(row_counts(&batches), [(2, 1)]);
+
+        let filter = ExportFilter {
+            time_range: Some(2..=3),
+            ..Default::default()
+        };
+        let batches = filter.filter_entry(entry(1, lp)).unwrap();
+        assert_eq!(row_counts(&batches), [(1, 2)]);
+        let expected = [
+            "+---+--------------------------------+---+",
+            "| t | time                           | v |",
+            "+---+--------------------------------+---+",
+            "| b | 1970-01-01T00:00:00.000000002Z | 2 |",
+            "| c | 1970-01-01T00:00:00.000000003Z | 3 |",
+            "+---+--------------------------------+---+",
+        ];
+        assert_batches_sorted_eq!(expected, &[batches[0].1.to_arrow(Projection::All).unwrap()]);
+    }
+
+    #[test]
+    fn write_parquet() {
+        let test_dir = test_helpers::tmp_dir().expect("failed to create test dir");
+        let output_dir = test_dir.path().join("out");
+
+        let mut writer = ParquetWriter::new(output_dir.clone(), false);
+        for lp in ["m1,t=a v=1i 1\nm2,t=a v=1i 1", "m1,t=b v=2i 2"] {
+            writer
+                .write_table_batches(entry(1, lp).table_batches.into_iter())
+                .expect("should not fail to write table batches");
+        }
+
+        let paths = writer.finish().expect("failed to write parquet files");
+        assert_eq!(
+            paths,
+            [output_dir.join("1.parquet"), output_dir.join("2.parquet")]
+        );
+
+        let batches = ParquetRecordBatchReaderBuilder::try_new(File::open(&paths[0]).unwrap())
+            .unwrap()
+            .build()
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let expected = [
+            "+---+--------------------------------+---+",
+            "| t | time                           | v |",
+            "+---+--------------------------------+---+",
+            "| a | 1970-01-01T00:00:00.000000001Z | 1 |",
+            "| b | 1970-01-01T00:00:00.000000002Z | 2 |",
+            "+---+--------------------------------+---+",
+        ];
+        assert_batches_sorted_eq!(expected, &batches);
+
+        // Existing files are not replaced unless requested.
+        let mut writer = ParquetWriter::new(output_dir.clone(), false);
+        writer
+            .write_table_batches(entry(1, "m1,t=a v=1i 1").table_batches.into_iter())
+            .unwrap();
+        assert_matches!(writer.finish(), Err(WriteError::IoError(_)));
+
+        let mut writer = ParquetWriter::new(output_dir, true);
+        writer
+            .write_table_batches(entry(1, "m1,t=a v=1i 1").table_batches.into_iter())
+            .unwrap();
+        assert_eq!(writer.finish().unwrap().len(), 1);
+    }
+}
@@ -31,6 +31,14 @@ use mutable_batch::MutableBatch;
 use parquet_to_line_protocol::convert_to_lines;
 use thiserror::Error;
 
+mod export;
+mod stats;
+mod verify;
+
+pub use export::*;
+pub use stats::*;
+pub use verify::*;
+
 /// Errors emitted by a [`TableBatchWriter`] during operation.
 #[derive(Debug, Error)]
 pub enum WriteError {
@@ -47,6 +55,10 @@ pub enum WriteError {
     /// A write failure caused by an IO error
     #[error("failed to write table batch: {0}")]
     IoError(#[from] std::io::Error),
+
+    /// The table batches could not be encoded as parquet
+    #[error("failed to write parquet: {0}")]
+    ParquetError(#[from] parquet::errors::ParquetError),
 }
 
 /// The [`TableBatchWriter`] trait provides functionality to write table-ID
@@ -99,6 +111,12 @@ where
             }
         }
     }
+
+    /// Consumes the [`NamespaceDemultiplexer`], returning the instances of `T`
+    /// initialised so far, keyed by [`NamespaceId`].
+    pub fn into_inner(self) -> HashMap<NamespaceId, T> {
+        self.demux_map
+    }
 }
 
 /// The [`LineProtoWriter`] enables rewriting table-keyed mutable batches as
@@ -332,7 +350,7 @@ m2,t=bar v="arán" 1"#,
         (table_id_index, table_name_index)
     }
 
-    fn encode_line(
+    pub(crate) fn encode_line(
         ns: NamespaceId,
         table_id_index: &HashMap<String, TableId>,
         lp: &str,
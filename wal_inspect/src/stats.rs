@@ -0,0 +1,155 @@
+//! Summary statistics of the writes within segment files.
+
+use std::{collections::BTreeMap, ops::RangeInclusive};
+
+use data_types::{NamespaceId, TableId};
+use generated_types::influxdata::iox::wal::v1::sequenced_wal_op::Op;
+use prost::Message;
+use wal::SequencedWalOp;
+
+/// The writes to a single table within a segment file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TableStats {
+    /// The number of write ops containing data for the table.
+    pub ops: u64,
+    /// The number of rows written to the table.
+    pub rows: u64,
+    /// The encoded size of the data written to the table, before compression.
+    pub bytes: u64,
+    /// The lowest and highest sequence numbers of the writes to the table.
+    pub sequence_numbers: Option<RangeInclusive<u64>>,
+}
+
+/// The ops within a segment file, with the writes broken down by namespace
+/// and table.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SegmentStats {
+    /// The number of entries (batches of ops) in the segment.
+    pub entries: u64,
+    /// The number of delete ops in the segment.
+    pub deletes: u64,
+    /// The number of persist ops in the segment.
+    pub persists: u64,
+    /// The statistics of the writes to each table, keyed by namespace and
+    /// table.
+    pub tables: BTreeMap<(NamespaceId, TableId), TableStats>,
+}
+
+impl SegmentStats {
+    /// Add the ops of a single segment entry to the statistics.
+    pub fn observe_entry(&mut self, ops: &[SequencedWalOp]) {
+        self.entries += 1;
+
+        for op in ops {
+            let batch = match &op.op {
+                Op::Write(batch) => batch,
+                Op::Delete(_) => {
+                    self.deletes += 1;
+                    continue;
+                }
+                Op::Persist(_) => {
+                    self.persists += 1;
+                    continue;
+                }
+            };
+
+            let namespace_id = NamespaceId::new(batch.database_id);
+            for table_batch in &batch.table_batches {
+                let table_id = TableId::new(table_batch.table_id);
+                let stats = self.tables.entry((namespace_id, table_id)).or_default();
+
+                stats.ops += 1;
+                stats.rows += u64::from(table_batch.row_count);
+                stats.bytes += table_batch.encoded_len() as u64;
+
+                if let Some(&sequence_number) = op.table_write_sequence_numbers.get(&table_id) {
+                    stats.sequence_numbers = Some(match stats.sequence_numbers.take() {
+                        None => sequence_number..=sequence_number,
+                        Some(range) => {
+                            (*range.start()).min(sequence_number)
+                                ..=(*range.end()).max(sequence_number)
+                        }
+                    });
+                }
+            }
+        }
+    }
+
+    /// The total number of ops observed.
+    pub fn ops(&self) -> u64 {
+        self.tables.values().map(|t| t.ops).sum::<u64>() + self.deletes + self.persists
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use generated_types::influxdata::iox::{delete::v1::DeletePayload, wal::v1::PersistOp};
+
+    use super::*;
+    use crate::tests::encode_line;
+
+    #[test]
+    fn observe_entries() {
+        let table_id_index = [
+            ("m1".to_string(), TableId::new(1)),
+            ("m2".to_string(), TableId::new(2)),
+        ]
+        .into_iter()
+        .collect();
+
+        let write = |namespace_id, lp, sequence_numbers: &[(i64, u64)]| SequencedWalOp {
+            table_write_sequence_numbers: sequence_numbers
+                .iter()
+                .map(|&(table_id, sequence_number)| (TableId::new(table_id), sequence_number))
+                .collect(),
+            op: Op::Write(encode_line(
+                NamespaceId::new(namespace_id),
+                &table_id_index,
+                lp,
+            )),
+        };
+
+        let mut stats = SegmentStats::default();
+        stats.observe_entry(&[
+            write(1, "m1,t=foo v=1i 1\nm1,t=bar v=2i 1", &[(1, 7)]),
+            write(1, "m1,t=foo v=3i 2\nm2,t=foo v=1i 2", &[(1, 3), (2, 4)]),
+        ]);
+        stats.observe_entry(&[
+            write(2, "m1,t=foo v=1i 1", &[(1, 5)]),
+            SequencedWalOp {
+                table_write_sequence_numbers: Default::default(),
+                op: Op::Delete(DeletePayload::default()),
+            },
+            SequencedWalOp {
+                table_write_sequence_numbers: Default::default(),
+                op: Op::Persist(PersistOp::default()),
+            },
+        ]);
+
+        assert_eq!(stats.entries, 2);
+        assert_eq!(stats.deletes, 1);
+        assert_eq!(stats.persists, 1);
+        assert_eq!(stats.ops(), 6);
+
+        let keys = stats.tables.keys().copied().collect::<Vec<_>>();
+        assert_eq!(
+            keys,
+            [
+                (NamespaceId::new(1), TableId::new(1)),
+                (NamespaceId::new(1), TableId::new(2)),
+                (NamespaceId::new(2), TableId::new(1)),
+            ]
+        );
+
+        let m1 = &stats.tables[&(NamespaceId::new(1), TableId::new(1))];
+        assert_eq!(m1.ops, 2);
+        assert_eq!(m1.rows, 3);
+        assert!(m1.bytes > 0);
+        assert_eq!(m1.sequence_numbers, Some(3..=7));
+
+        let m2 = &stats.tables[&(NamespaceId::new(1), TableId::new(2))];
+        assert_eq!(m2.ops, 1);
+        assert_eq!(m2.rows, 1);
+        assert_eq!(m2.sequence_numbers, Some(4..=4));
+    }
+}
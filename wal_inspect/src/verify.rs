@@ -0,0 +1,173 @@
+//! Verification of the checksums and decodability of segment file entries.
+
+use generated_types::influxdata::iox::wal::v1::sequenced_wal_op::Op;
+use mutable_batch_pb::decode::decode_database_batch;
+use wal::{ClosedSegmentFileReader, SegmentCodec, SegmentId, SequencedWalOp};
+
+/// A damaged region of a segment file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DamagedEntry {
+    /// The offset of the start of the damaged entry within the segment file.
+    pub offset: u64,
+    /// A description of the damage.
+    pub error: String,
+}
+
+/// The outcome of verifying every entry of a segment file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SegmentVerification {
+    /// The ID of the segment, as recorded in its header.
+    pub id: SegmentId,
+    /// The codec the entries of the segment are compressed with.
+    pub codec: SegmentCodec,
+    /// The number of entries that were read and decoded successfully.
+    pub valid_entries: usize,
+    /// Entries that were read in full but fail their checksum or can not be
+    /// decoded. Reading resumes after each of them.
+    pub damaged_entries: Vec<DamagedEntry>,
+    /// The start of the unreadable tail of the segment file, if any, such as
+    /// a truncated entry or one with a damaged length. No entries after it
+    /// can be located.
+    pub unreadable_tail: Option<DamagedEntry>,
+}
+
+impl SegmentVerification {
+    /// Returns true if no damage was found in the segment file.
+    pub fn is_ok(&self) -> bool {
+        self.damaged_entries.is_empty() && self.unreadable_tail.is_none()
+    }
+}
+
+/// Read every entry of the segment file of `file_len` bytes opened by
+/// `reader`, checking each entry's checksum and that the writes it contains
+/// decode, and reporting the offsets of any damaged entries.
+pub fn verify_segment(mut reader: ClosedSegmentFileReader, file_len: u64) -> SegmentVerification {
+    let mut verification = SegmentVerification {
+        id: reader.id(),
+        codec: reader.codec(),
+        valid_entries: 0,
+        damaged_entries: vec![],
+        unreadable_tail: None,
+    };
+
+    loop {
+        let offset = reader.bytes_read();
+        match reader.next() {
+            None => break,
+            Some(Ok(ops)) => match decode_ops(&ops) {
+                Ok(()) => verification.valid_entries += 1,
+                Err(error) => verification
+                    .damaged_entries
+                    .push(DamagedEntry { offset, error }),
+            },
+            // The reader advanced past the damaged entry, and can continue
+            // reading from the next one.
+            Some(Err(e)) if reader.bytes_read() > offset => {
+                verification.damaged_entries.push(DamagedEntry {
+                    offset,
+                    error: e.to_string(),
+                });
+            }
+            // Otherwise the position of the next entry is unknown.
+            Some(Err(e)) => {
+                verification.unreadable_tail = Some(DamagedEntry {
+                    offset,
+                    error: e.to_string(),
+                });
+                return verification;
+            }
+        }
+    }
+
+    // The reader stops without error at a partial entry header, leaving some
+    // trailing bytes unread.
+    let offset = reader.bytes_read();
+    if offset < file_len {
+        verification.unreadable_tail = Some(DamagedEntry {
+            offset,
+            error: format!("{} trailing bytes", file_len - offset),
+        });
+    }
+
+    verification
+}
+
+/// Decode the writes within `ops`, describing the first failure.
+fn decode_ops(ops: &[SequencedWalOp]) -> Result<(), String> {
+    for op in ops {
+        if let Op::Write(batch) = &op.op {
+            decode_database_batch(batch).map_err(|e| {
+                format!(
+                    "failed to decode write to namespace {}: {e}",
+                    batch.database_id
+                )
+            })?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs::OpenOptions, io::Write};
+
+    use assert_matches::assert_matches;
+    use data_types::{NamespaceId, TableId};
+
+    use super::*;
+    use crate::tests::encode_line;
+
+    #[tokio::test]
+    async fn verify_damaged_segment() {
+        let test_dir = test_helpers::tmp_dir().expect("failed to create test dir");
+        let wal = wal::Wal::new(test_dir.path()).await.unwrap();
+
+        let table_id_index = [("m1".to_string(), TableId::new(1))].into_iter().collect();
+        for (i, line) in ["m1,t=foo v=1i 1", "m1,t=foo v=2i 2"]
+            .into_iter()
+            .enumerate()
+        {
+            wal.write_op(SequencedWalOp {
+                table_write_sequence_numbers: [(TableId::new(1), i as u64)].into_iter().collect(),
+                op: Op::Write(encode_line(NamespaceId::new(1), &table_id_index, line)),
+            })
+            .changed()
+            .await
+            .expect("WAL should have changed");
+        }
+        let (closed, _) = wal.rotate().expect("failed to rotate WAL");
+
+        let path = test_dir.path().join(format!("{}.dat", closed.id()));
+        let reader = ClosedSegmentFileReader::from_path(&path).unwrap();
+        let verification = verify_segment(reader, closed.size());
+        assert!(verification.is_ok());
+        assert_eq!(verification.id, closed.id());
+        assert_eq!(verification.codec, SegmentCodec::Snappy);
+        assert_eq!(verification.valid_entries, 2);
+
+        // Damage the checksum of the first entry, which follows the 16 byte
+        // file header, and append a partial entry header.
+        let mut data = std::fs::read(&path).unwrap();
+        data[16] ^= 0xFF;
+        std::fs::write(&path, &data).unwrap();
+        OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(&[42, 42])
+            .unwrap();
+
+        let reader = ClosedSegmentFileReader::from_path(&path).unwrap();
+        let verification = verify_segment(reader, closed.size() + 2);
+        assert!(!verification.is_ok());
+        assert_eq!(verification.valid_entries, 1);
+        assert_matches!(
+            verification.damaged_entries.as_slice(),
+            [DamagedEntry { offset: 16, .. }]
+        );
+        assert_matches!(
+            verification.unreadable_tail,
+            Some(DamagedEntry { offset, .. }) if offset == closed.size()
+        );
+    }
+}